@@ -1,6 +1,7 @@
 use auto_update::{AutoUpdateStatus, AutoUpdater, DismissErrorMessage};
 use editor::Editor;
 use extension::ExtensionStore;
+use feedback::GiveFeedback;
 use futures::StreamExt;
 use gpui::{
     actions, anchored, deferred, percentage, Animation, AnimationExt as _, AppContext, CursorStyle,
@@ -13,9 +14,11 @@ use language::{
 };
 use project::{LanguageServerProgress, Project};
 use smallvec::SmallVec;
-use std::{cmp::Reverse, fmt::Write, sync::Arc, time::Duration};
+use std::{cmp::Reverse, collections::BTreeMap, fmt::Write, sync::Arc, time::Duration};
 use ui::{prelude::*, ContextMenu};
-use workspace::{item::ItemHandle, StatusItemView, Workspace};
+use workspace::{
+    item::ItemHandle, notifications::NotificationId, StatusItemView, Toast, Workspace,
+};
 
 actions!(activity_indicator, [ShowErrorMessage]);
 
@@ -109,6 +112,22 @@ impl ActivityIndicator {
                             None,
                             cx,
                         );
+
+                        struct LanguageServerError;
+
+                        let feedback = language_server_feedback(&lsp_name, &error);
+                        workspace.show_toast(
+                            Toast::new(
+                                NotificationId::identified::<LanguageServerError>(
+                                    SharedString::from(lsp_name.to_string()),
+                                ),
+                                format!("Failed to download {lsp_name}."),
+                            )
+                            .on_click("Give Feedback", move |cx| {
+                                cx.dispatch_action(Box::new(feedback.clone()))
+                            }),
+                            cx,
+                        );
                     })?;
 
                     anyhow::Ok(())
@@ -436,6 +455,19 @@ impl ActivityIndicator {
     }
 }
 
+/// Gives feedback about a language server that couldn't be downloaded, with
+/// the error attached to it.
+fn language_server_feedback(lsp_name: &str, error: &str) -> GiveFeedback {
+    GiveFeedback {
+        prefill: format!("Zed failed to download the {lsp_name} language server."),
+        context: BTreeMap::from_iter([
+            ("source".to_string(), "language_server".to_string()),
+            ("language_server".to_string(), lsp_name.to_string()),
+            ("error".to_string(), error.to_string()),
+        ]),
+    }
+}
+
 impl EventEmitter<Event> for ActivityIndicator {}
 
 impl Render for ActivityIndicator {
@@ -472,4 +504,8 @@ impl Render for ActivityIndicator {
 
 impl StatusItemView for ActivityIndicator {
     fn set_active_pane_item(&mut self, _: Option<&dyn ItemHandle>, _: &mut ViewContext<Self>) {}
+
+    fn status_item_id() -> Option<&'static str> {
+        Some("activity")
+    }
 }
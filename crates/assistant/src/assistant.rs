@@ -42,6 +42,8 @@ actions!(
         Split,
         CycleMessageRole,
         QuoteSelection,
+        InsertIntoEditor,
+        ReplaceEditorSelection,
         ToggleFocus,
         ResetKey,
         InlineAssist,
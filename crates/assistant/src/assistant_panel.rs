@@ -7,9 +7,10 @@ use crate::{
         SlashCommandRegistry,
     },
     ApplyEdit, Assist, CompletionProvider, ConfirmCommand, ContextStore, CycleMessageRole,
-    InlineAssist, InlineAssistant, LanguageModelRequest, LanguageModelRequestMessage, MessageId,
-    MessageMetadata, MessageStatus, ModelSelector, QuoteSelection, ResetKey, Role, SavedContext,
-    SavedContextMetadata, SavedMessage, Split, ToggleFocus, ToggleHistory, ToggleModelSelector,
+    InlineAssist, InlineAssistant, InsertIntoEditor, LanguageModelRequest,
+    LanguageModelRequestMessage, MessageId, MessageMetadata, MessageStatus, ModelSelector,
+    QuoteSelection, ReplaceEditorSelection, ResetKey, Role, SavedContext, SavedContextMetadata,
+    SavedMessage, Split, ToggleFocus, ToggleHistory, ToggleModelSelector,
 };
 use anyhow::{anyhow, Result};
 use assistant_slash_command::{SlashCommand, SlashCommandOutput, SlashCommandOutputSection};
@@ -2904,6 +2905,56 @@ impl ContextEditor {
         }
     }
 
+    fn insert_into_editor(&mut self, _: &InsertIntoEditor, cx: &mut ViewContext<Self>) {
+        self.write_into_active_editor(false, cx);
+    }
+
+    fn replace_editor_selection(&mut self, _: &ReplaceEditorSelection, cx: &mut ViewContext<Self>) {
+        self.write_into_active_editor(true, cx);
+    }
+
+    /// Writes the selected text, or else the code block under the cursor, into the
+    /// workspace's active editor, either at its cursors or in place of its selections.
+    fn write_into_active_editor(&mut self, replace_selection: bool, cx: &mut ViewContext<Self>) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let Some(editor) = workspace
+            .read(cx)
+            .active_item(cx)
+            .and_then(|item| item.act_as::<Editor>(cx))
+        else {
+            return;
+        };
+
+        let text = {
+            let context_editor = self.editor.read(cx);
+            let selection = context_editor.selections.newest::<usize>(cx);
+            let buffer = context_editor.buffer().read(cx).snapshot(cx);
+            if selection.is_empty() {
+                let text = buffer.text();
+                let Some(range) = code_block_at(&text, selection.head()) else {
+                    return;
+                };
+                text[range].to_string()
+            } else {
+                buffer.text_for_range(selection.range()).collect::<String>()
+            }
+        };
+
+        editor.update(cx, |editor, cx| {
+            if !replace_selection {
+                editor.change_selections(None, cx, |selections| {
+                    selections.move_with(|_, selection| {
+                        selection.collapse_to(selection.head(), selection.goal)
+                    });
+                });
+            }
+            editor.insert(&text, cx);
+        });
+        cx.focus_view(&editor);
+    }
+
     fn copy(&mut self, _: &editor::actions::Copy, cx: &mut ViewContext<Self>) {
         let editor = self.editor.read(cx);
         let context = self.context.read(cx);
@@ -3134,6 +3185,31 @@ impl ContextEditor {
 
 impl EventEmitter<ContextEditorEvent> for ContextEditor {}
 
+/// Returns the range of the contents of the fenced code block that contains `offset`.
+fn code_block_at(text: &str, offset: usize) -> Option<Range<usize>> {
+    let mut open_fence = None;
+    let mut line_start = 0;
+    for line in text.split_inclusive('\n') {
+        let line_end = line_start + line.len();
+        if line.trim_start().starts_with("```") {
+            if let Some((fence_start, content_start)) = open_fence.take() {
+                if (fence_start..line_end).contains(&offset)
+                    || (offset == text.len() && line_end == text.len())
+                {
+                    let content_end = line_start.saturating_sub(1).max(content_start);
+                    return Some(content_start..content_end);
+                } else if offset < fence_start {
+                    return None;
+                }
+            } else {
+                open_fence = Some((line_start, line_end));
+            }
+        }
+        line_start = line_end;
+    }
+    None
+}
+
 impl Render for ContextEditor {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
         div()
@@ -3146,6 +3222,8 @@ impl Render for ContextEditor {
             .on_action(cx.listener(ContextEditor::assist))
             .on_action(cx.listener(ContextEditor::split))
             .on_action(cx.listener(ContextEditor::apply_edit))
+            .on_action(cx.listener(ContextEditor::insert_into_editor))
+            .on_action(cx.listener(ContextEditor::replace_editor_selection))
             .size_full()
             .v_flex()
             .child(
@@ -3864,6 +3942,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_code_block_at() {
+        let text = "Sure:\n```rust\nfn main() {}\n```\nOr:\n```\n```\n";
+        let block = text.find("fn main").unwrap();
+        assert_eq!(code_block_at(text, 0), None);
+        assert_eq!(code_block_at(text, block), Some(block..block + 12));
+        assert_eq!(
+            code_block_at(text, text.find("```rust").unwrap()),
+            Some(block..block + 12)
+        );
+        assert_eq!(code_block_at(text, text.find("Or:").unwrap()), None);
+        let empty_block = text.rfind("```").unwrap();
+        assert_eq!(
+            code_block_at(text, empty_block),
+            Some(empty_block..empty_block)
+        );
+
+        let unterminated = "```\nlet x = 1;";
+        assert_eq!(code_block_at(unterminated, 6), None);
+        let at_end = "```\nlet x = 1;\n```";
+        assert_eq!(code_block_at(at_end, at_end.len()), Some(4..14));
+    }
+
     fn messages(context: &Model<Context>, cx: &AppContext) -> Vec<(MessageId, Role, Range<usize>)> {
         context
             .read(cx)
@@ -20,6 +20,7 @@ pub struct OpenAiCompletionProvider {
     api_key: Option<String>,
     api_url: String,
     model: OpenAiModel,
+    available_models: Vec<OpenAiModel>,
     http_client: Arc<dyn HttpClient>,
     low_speed_timeout: Option<Duration>,
     settings_version: usize,
@@ -28,6 +29,7 @@ pub struct OpenAiCompletionProvider {
 impl OpenAiCompletionProvider {
     pub fn new(
         model: OpenAiModel,
+        available_models: Vec<OpenAiModel>,
         api_url: String,
         http_client: Arc<dyn HttpClient>,
         low_speed_timeout: Option<Duration>,
@@ -37,6 +39,7 @@ impl OpenAiCompletionProvider {
             api_key: None,
             api_url,
             model,
+            available_models,
             http_client,
             low_speed_timeout,
             settings_version,
@@ -46,18 +49,22 @@ impl OpenAiCompletionProvider {
     pub fn update(
         &mut self,
         model: OpenAiModel,
+        available_models: Vec<OpenAiModel>,
         api_url: String,
         low_speed_timeout: Option<Duration>,
         settings_version: usize,
     ) {
         self.model = model;
+        self.available_models = available_models;
         self.api_url = api_url;
         self.low_speed_timeout = low_speed_timeout;
         self.settings_version = settings_version;
     }
 
-    pub fn available_models(&self) -> impl Iterator<Item = OpenAiModel> {
+    pub fn available_models(&self) -> impl Iterator<Item = OpenAiModel> + '_ {
         OpenAiModel::iter()
+            .filter(|model| !matches!(model, OpenAiModel::Custom { .. }))
+            .chain(self.available_models.iter().cloned())
     }
 
     pub fn settings_version(&self) -> usize {
@@ -161,7 +168,7 @@ impl OpenAiCompletionProvider {
         };
 
         Request {
-            model,
+            model: model.id().into(),
             messages: request
                 .messages
                 .into_iter()
@@ -217,6 +224,10 @@ pub fn count_open_ai_tokens(
                     // same tokenizer as GPT-4.
                     tiktoken_rs::num_tokens_from_messages("gpt-4", &messages)
                 }
+                // Models served by other APIs don't have a known tokenizer either.
+                LanguageModel::OpenAi(OpenAiModel::Custom { .. }) => {
+                    tiktoken_rs::num_tokens_from_messages("gpt-4", &messages)
+                }
                 _ => tiktoken_rs::num_tokens_from_messages(request.model.id(), &messages),
             }
         })
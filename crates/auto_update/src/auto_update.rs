@@ -17,18 +17,19 @@ use serde::Deserialize;
 use serde_derive::Serialize;
 use smol::{fs, io::AsyncReadExt};
 
-use settings::{Settings, SettingsSources, SettingsStore};
+use settings::{update_settings_file, Settings, SettingsSources, SettingsStore};
 use smol::{fs::File, process::Command};
 
 use http::{HttpClient, HttpClientWithUrl};
 use release_channel::{AppCommitSha, AppVersion, ReleaseChannel};
 use std::{
+    collections::BTreeMap,
     env::{
         self,
         consts::{ARCH, OS},
     },
     ffi::OsString,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::Arc,
     time::Duration,
 };
@@ -38,6 +39,13 @@ use workspace::notifications::NotificationId;
 use workspace::Workspace;
 
 const SHOULD_SHOW_UPDATE_NOTIFICATION_KEY: &str = "auto-updater-should-show-updated-notification";
+const RELEASE_NOTES_KEY_PREFIX: &str = "auto-updater-release-notes";
+const RELEASE_NOTES_VERSIONS_KEY_PREFIX: &str = "auto-updater-release-notes-versions";
+/// How many versions' release notes are kept in the cache, for each channel.
+const MAX_CACHED_RELEASE_NOTES: usize = 20;
+const ROLLBACK_KEY: &str = "auto-updater-rollback";
+const SKIPPED_VERSION_KEY: &str = "auto-updater-skipped-version";
+const INSTALLED_CHANNEL_VERSION_KEY_PREFIX: &str = "auto-updater-installed-channel-version";
 const POLL_INTERVAL: Duration = Duration::from_secs(60 * 60);
 
 actions!(
@@ -45,6 +53,8 @@ actions!(
     [
         Check,
         DismissErrorMessage,
+        Rollback,
+        SwitchChannel,
         ViewReleaseNotes,
         ViewReleaseNotesLocally
     ]
@@ -86,6 +96,20 @@ struct JsonRelease {
     url: String,
 }
 
+/// The installation that was replaced by the most recent update, kept around
+/// so that it can be restored if the update turns out to be broken.
+#[derive(Serialize, Deserialize)]
+struct RollbackInfo {
+    /// The version that was installed before the update.
+    version: String,
+    /// The version that the update installed.
+    updated_version: String,
+    /// Where the backed-up installation should be restored to.
+    app_path: PathBuf,
+    /// Where the backed-up installation is stored.
+    backup_path: PathBuf,
+}
+
 struct AutoUpdateSetting(bool);
 
 /// Whether or not to automatically check for updates.
@@ -110,12 +134,89 @@ impl Settings for AutoUpdateSetting {
     }
 }
 
+/// The release channel that updates are downloaded from.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, JsonSchema, Deserialize, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateChannel {
+    Stable,
+    Preview,
+    Nightly,
+}
+
+impl UpdateChannel {
+    pub const ALL: [Self; 3] = [Self::Stable, Self::Preview, Self::Nightly];
+
+    pub fn release_channel(&self) -> ReleaseChannel {
+        match self {
+            Self::Stable => ReleaseChannel::Stable,
+            Self::Preview => ReleaseChannel::Preview,
+            Self::Nightly => ReleaseChannel::Nightly,
+        }
+    }
+
+    fn for_release_channel(release_channel: ReleaseChannel) -> Option<Self> {
+        match release_channel {
+            ReleaseChannel::Stable => Some(Self::Stable),
+            ReleaseChannel::Preview => Some(Self::Preview),
+            ReleaseChannel::Nightly => Some(Self::Nightly),
+            ReleaseChannel::Dev => None,
+        }
+    }
+}
+
+/// The release channels to install updates from, by the channel of the
+/// running build. The settings file is shared by the builds of all channels,
+/// so each of them only follows what was chosen for it.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct AutoUpdateChannelSetting(BTreeMap<UpdateChannel, UpdateChannel>);
+
+impl AutoUpdateChannelSetting {
+    /// Returns the channel that a build of the given channel installs updates
+    /// from, which is its own channel unless another one was chosen for it.
+    fn update_channel(&self, running_channel: ReleaseChannel) -> ReleaseChannel {
+        UpdateChannel::for_release_channel(running_channel)
+            .and_then(|running_channel| self.0.get(&running_channel))
+            .map_or(running_channel, |channel| channel.release_channel())
+    }
+}
+
+impl Settings for AutoUpdateChannelSetting {
+    const KEY: Option<&'static str> = Some("auto_update_channel");
+
+    /// The release channel to install updates from, for builds of each
+    /// channel, like `{ "stable": "preview" }`. Builds of the channels that
+    /// aren't given install updates from their own channel.
+    ///
+    /// Default: {}
+    type FileContent = BTreeMap<UpdateChannel, UpdateChannel>;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut AppContext) -> Result<Self> {
+        let mut channels = sources.default.clone();
+        for customization in [sources.user, sources.release_channel]
+            .into_iter()
+            .flatten()
+        {
+            channels.extend(customization);
+        }
+
+        Ok(Self(channels))
+    }
+}
+
+/// Returns the release channel that updates should be downloaded from.
+fn update_release_channel(cx: &AppContext) -> Option<ReleaseChannel> {
+    let running_channel = ReleaseChannel::try_global(cx)?;
+    Some(AutoUpdateChannelSetting::get_global(cx).update_channel(running_channel))
+}
+
 #[derive(Default)]
 struct GlobalAutoUpdate(Option<Model<AutoUpdater>>);
 
 impl Global for GlobalAutoUpdate {}
 
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize)]
 struct ReleaseNotesBody {
     title: String,
     release_notes: String,
@@ -123,16 +224,23 @@ struct ReleaseNotesBody {
 
 pub fn init(http_client: Arc<HttpClientWithUrl>, cx: &mut AppContext) {
     AutoUpdateSetting::register(cx);
+    AutoUpdateChannelSetting::register(cx);
 
     cx.observe_new_views(|workspace: &mut Workspace, _cx| {
         workspace.register_action(|_, action: &Check, cx| check(action, cx));
 
+        workspace.register_action(|workspace, action: &SwitchChannel, cx| {
+            switch_channel(workspace, action, cx);
+        });
+
+        workspace.register_action(|_, action: &Rollback, cx| rollback(action, cx));
+
         workspace.register_action(|_, action, cx| {
             view_release_notes(action, cx);
         });
 
         workspace.register_action(|workspace, _: &ViewReleaseNotesLocally, cx| {
-            view_release_notes_locally(workspace, cx);
+            view_release_notes_locally(workspace, None, cx);
         });
     })
     .detach();
@@ -198,6 +306,112 @@ pub fn check(_: &Check, cx: &mut WindowContext) {
     }
 }
 
+fn switch_channel(workspace: &mut Workspace, _: &SwitchChannel, cx: &mut ViewContext<Workspace>) {
+    if AutoUpdater::get(cx).is_none() {
+        drop(cx.prompt(
+            gpui::PromptLevel::Info,
+            "Could not switch channels",
+            Some("Auto-updates disabled for non-bundled app."),
+            &["Ok"],
+        ));
+        return;
+    }
+
+    let fs = workspace.app_state().fs.clone();
+    let running_channel =
+        ReleaseChannel::try_global(cx).and_then(UpdateChannel::for_release_channel);
+    let current_channel = update_release_channel(cx);
+    let mut answers = UpdateChannel::ALL
+        .iter()
+        .map(|channel| channel.release_channel().display_name())
+        .collect::<Vec<_>>();
+    answers.push("Cancel");
+    let detail = current_channel.map(|channel| {
+        format!(
+            "Updates are currently installed from {}.",
+            channel.display_name()
+        )
+    });
+    let answer = cx.prompt(
+        gpui::PromptLevel::Info,
+        "Which release channel would you like to install updates from?",
+        detail.as_deref(),
+        &answers,
+    );
+
+    cx.spawn(|_, mut cx| async move {
+        let Some(&channel) = UpdateChannel::ALL.get(answer.await?) else {
+            return Ok(());
+        };
+        cx.update(|cx| {
+            if let Some(running_channel) = running_channel {
+                update_settings_file::<AutoUpdateChannelSetting>(fs, cx, move |setting| {
+                    if channel == running_channel {
+                        setting.remove(&running_channel);
+                    } else {
+                        setting.insert(running_channel, channel);
+                    }
+                });
+            }
+            if current_channel != Some(channel.release_channel()) {
+                if let Some(updater) = AutoUpdater::get(cx) {
+                    updater.update(cx, |updater, cx| updater.poll_channel(channel, cx));
+                }
+            }
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+pub fn rollback(_: &Rollback, cx: &mut WindowContext) {
+    let Some(updater) = AutoUpdater::get(cx) else {
+        drop(cx.prompt(
+            gpui::PromptLevel::Info,
+            "Could not roll back",
+            Some("Auto-updates disabled for non-bundled app."),
+            &["Ok"],
+        ));
+        return;
+    };
+
+    let rollback_info = cx
+        .background_executor()
+        .spawn(async move { AutoUpdater::rollback_info() });
+
+    cx.spawn(|mut cx| async move {
+        let Some(rollback_info) = rollback_info.await? else {
+            cx.update(|cx| {
+                drop(cx.prompt(
+                    gpui::PromptLevel::Info,
+                    "Could not roll back",
+                    Some("No previous version of Zed is available."),
+                    &["Ok"],
+                ))
+            })?;
+            return anyhow::Ok(());
+        };
+
+        let answer = cx.update(|cx| {
+            cx.prompt(
+                gpui::PromptLevel::Warning,
+                &format!("Roll back to version {}?", rollback_info.version),
+                Some(&format!(
+                    "Version {} will not be installed again automatically. Zed will need to restart to finish rolling back.",
+                    rollback_info.updated_version
+                )),
+                &["Roll Back", "Cancel"],
+            )
+        })?;
+        if answer.await? != 0 {
+            return Ok(());
+        }
+
+        updater.update(&mut cx, |updater, cx| updater.rollback(rollback_info, cx))?;
+        Ok(())
+    })
+    .detach_and_log_err(cx);
+}
+
 pub fn view_release_notes(_: &ViewReleaseNotes, cx: &mut AppContext) -> Option<()> {
     let auto_updater = AutoUpdater::get(cx)?;
     let release_channel = ReleaseChannel::try_global(cx)?;
@@ -218,16 +432,19 @@ pub fn view_release_notes(_: &ViewReleaseNotes, cx: &mut AppContext) -> Option<(
     None
 }
 
-fn view_release_notes_locally(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
+/// Opens the release notes for the running version in a markdown preview.
+///
+/// The notes are cached so that they remain available offline. When
+/// `previous_version` is given, the notes mention the version that was
+/// updated from, and include the notes of the versions downloaded since.
+pub(crate) fn view_release_notes_locally(
+    workspace: &mut Workspace,
+    previous_version: Option<String>,
+    cx: &mut ViewContext<Workspace>,
+) {
     let release_channel = ReleaseChannel::global(cx);
     let version = AppVersion::global(cx).to_string();
-
     let client = client::Client::global(cx).http_client();
-    let url = client.build_url(&format!(
-        "/api/release_notes/{}/{}",
-        release_channel.dev_name(),
-        version
-    ));
 
     let markdown = workspace
         .app_state()
@@ -238,18 +455,16 @@ fn view_release_notes_locally(workspace: &mut Workspace, cx: &mut ViewContext<Wo
         .with_local_workspace(cx, move |_, cx| {
             cx.spawn(|workspace, mut cx| async move {
                 let markdown = markdown.await.log_err();
-                let response = client.get(&url, Default::default(), true).await;
-                let Some(mut response) = response.log_err() else {
-                    return;
-                };
-
-                let mut body = Vec::new();
-                response.body_mut().read_to_end(&mut body).await.ok();
-
-                let body: serde_json::Result<ReleaseNotesBody> =
-                    serde_json::from_slice(body.as_slice());
-
-                if let Ok(body) = body {
+                let notes = load_release_notes(
+                    &client,
+                    release_channel,
+                    &version,
+                    previous_version.as_deref(),
+                )
+                .await;
+
+                if let Some(body) = notes.first() {
+                    let release_notes = release_notes_markdown(&notes, previous_version.as_deref());
                     workspace
                         .update(&mut cx, |workspace, cx| {
                             let project = workspace.project().clone();
@@ -257,7 +472,7 @@ fn view_release_notes_locally(workspace: &mut Workspace, cx: &mut ViewContext<Wo
                                 project.create_local_buffer("", markdown, cx)
                             });
                             buffer.update(cx, |buffer, cx| {
-                                buffer.edit([(0..0, body.release_notes)], None, cx)
+                                buffer.edit([(0..0, release_notes)], None, cx)
                             });
                             let language_registry = project.read(cx).languages().clone();
 
@@ -287,23 +502,204 @@ fn view_release_notes_locally(workspace: &mut Workspace, cx: &mut ViewContext<Wo
         .detach();
 }
 
+/// Returns the release notes of the running version, followed by the cached
+/// notes of the versions that were downloaded since `previous_version`, newest
+/// first.
+async fn load_release_notes(
+    client: &Arc<HttpClientWithUrl>,
+    channel: ReleaseChannel,
+    version: &str,
+    previous_version: Option<&str>,
+) -> Vec<ReleaseNotesBody> {
+    let mut notes = Vec::new();
+    match cache_release_notes(client, channel, version).await {
+        Ok(body) => notes.push(body),
+        Err(error) => {
+            log::info!("failed to fetch release notes, using cache: {error:?}");
+            notes.extend(cached_release_notes(channel, version));
+        }
+    }
+    if let Some(previous_version) = previous_version {
+        let cached_versions = cached_release_notes_versions(channel);
+        for cached_version in versions_between(&cached_versions, previous_version, version) {
+            notes.extend(cached_release_notes(channel, &cached_version));
+        }
+    }
+    notes
+}
+
+/// Fetches the release notes of the version, and caches them so that they
+/// remain available offline.
+async fn cache_release_notes(
+    client: &Arc<HttpClientWithUrl>,
+    channel: ReleaseChannel,
+    version: &str,
+) -> Result<ReleaseNotesBody> {
+    let url = client.build_url(&format!(
+        "/api/release_notes/{}/{}",
+        channel.dev_name(),
+        version
+    ));
+    let body = fetch_release_notes(client, &url).await?;
+    KEY_VALUE_STORE
+        .write_kvp(
+            release_notes_key(channel, version),
+            serde_json::to_string(&body)?,
+        )
+        .await?;
+
+    let mut versions = cached_release_notes_versions(channel);
+    versions.retain(|cached_version| cached_version != version);
+    versions.push(version.to_string());
+    let evicted_count = versions.len().saturating_sub(MAX_CACHED_RELEASE_NOTES);
+    for evicted_version in versions.drain(..evicted_count) {
+        KEY_VALUE_STORE
+            .delete_kvp(release_notes_key(channel, &evicted_version))
+            .await?;
+    }
+    KEY_VALUE_STORE
+        .write_kvp(
+            format!("{RELEASE_NOTES_VERSIONS_KEY_PREFIX}-{}", channel.dev_name()),
+            serde_json::to_string(&versions)?,
+        )
+        .await?;
+    Ok(body)
+}
+
+fn cached_release_notes(channel: ReleaseChannel, version: &str) -> Option<ReleaseNotesBody> {
+    KEY_VALUE_STORE
+        .read_kvp(&release_notes_key(channel, version))
+        .log_err()
+        .flatten()
+        .and_then(|cached| serde_json::from_str(&cached).log_err())
+}
+
+/// Returns the versions whose release notes are cached, oldest first.
+fn cached_release_notes_versions(channel: ReleaseChannel) -> Vec<String> {
+    KEY_VALUE_STORE
+        .read_kvp(&format!(
+            "{RELEASE_NOTES_VERSIONS_KEY_PREFIX}-{}",
+            channel.dev_name()
+        ))
+        .log_err()
+        .flatten()
+        .and_then(|versions| serde_json::from_str(&versions).log_err())
+        .unwrap_or_default()
+}
+
+fn release_notes_key(channel: ReleaseChannel, version: &str) -> String {
+    format!(
+        "{RELEASE_NOTES_KEY_PREFIX}-{}-{version}",
+        channel.dev_name()
+    )
+}
+
+/// Returns the versions that are newer than `previous_version` and older than
+/// `current_version`, newest first. Versions that aren't semantic versions,
+/// like nightly builds' commit SHAs, are skipped.
+fn versions_between(
+    versions: &[String],
+    previous_version: &str,
+    current_version: &str,
+) -> Vec<String> {
+    let (Ok(previous_version), Ok(current_version)) = (
+        previous_version.parse::<SemanticVersion>(),
+        current_version.parse::<SemanticVersion>(),
+    ) else {
+        return Vec::new();
+    };
+    let mut versions = versions
+        .iter()
+        .filter_map(|version| Some((version.parse::<SemanticVersion>().ok()?, version)))
+        .filter(|(version, _)| previous_version < *version && *version < current_version)
+        .collect::<Vec<_>>();
+    versions.sort_by(|(a, _), (b, _)| b.cmp(a));
+    versions
+        .into_iter()
+        .map(|(_, version)| version.clone())
+        .collect()
+}
+
+async fn fetch_release_notes(
+    client: &Arc<HttpClientWithUrl>,
+    url: &str,
+) -> Result<ReleaseNotesBody> {
+    let mut response = client.get(url, Default::default(), true).await?;
+    anyhow::ensure!(
+        response.status().is_success(),
+        "unexpected status fetching release notes: {}",
+        response.status()
+    );
+
+    let mut body = Vec::new();
+    response.body_mut().read_to_end(&mut body).await?;
+    Ok(serde_json::from_slice(body.as_slice())?)
+}
+
+/// Prepends a summary of the changes that may require attention, such as
+/// breaking changes and changes to keymaps or settings, to the release notes.
+/// The notes of several versions are concatenated under their titles.
+fn release_notes_markdown(notes: &[ReleaseNotesBody], previous_version: Option<&str>) -> String {
+    let highlights = notes
+        .iter()
+        .flat_map(|body| body.release_notes.lines())
+        .filter(|line| {
+            let line = line.trim_start();
+            let is_list_item = line.starts_with("- ") || line.starts_with("* ");
+            let line = line.to_lowercase();
+            is_list_item
+                && ["breaking", "keymap", "keybinding", "key binding", "setting"]
+                    .iter()
+                    .any(|keyword| line.contains(keyword))
+        })
+        .map(str::trim_start)
+        .collect::<Vec<_>>();
+
+    let mut markdown = String::new();
+    if let Some(previous_version) = previous_version {
+        markdown.push_str(&format!("_Updated from version {previous_version}._\n\n"));
+    }
+    if !highlights.is_empty() {
+        markdown.push_str("## Breaking, keymap, and settings changes\n\n");
+        for highlight in highlights {
+            markdown.push_str(highlight);
+            markdown.push('\n');
+        }
+        markdown.push('\n');
+    }
+    if let [body] = notes {
+        markdown.push_str(&body.release_notes);
+    } else {
+        for body in notes {
+            markdown.push_str(&format!("## {}\n\n", body.title));
+            markdown.push_str(body.release_notes.trim_end());
+            markdown.push_str("\n\n");
+        }
+    }
+    markdown
+}
+
 pub fn notify_of_any_new_update(cx: &mut ViewContext<Workspace>) -> Option<()> {
     let updater = AutoUpdater::get(cx)?;
     let version = updater.read(cx).current_version;
     let should_show_notification = updater.read(cx).should_show_update_notification(cx);
 
     cx.spawn(|workspace, mut cx| async move {
-        let should_show_notification = should_show_notification.await?;
-        if should_show_notification {
+        if let Some(previous_version) = should_show_notification.await? {
             workspace.update(&mut cx, |workspace, cx| {
+                let workspace_handle = workspace.weak_handle();
                 workspace.show_notification(
                     NotificationId::unique::<UpdateNotification>(),
                     cx,
-                    |cx| cx.new_view(|_| UpdateNotification::new(version)),
+                    |cx| {
+                        cx.new_view(|_| {
+                            UpdateNotification::new(version, previous_version, workspace_handle)
+                        })
+                    },
                 );
                 updater
                     .read(cx)
-                    .set_should_show_update_notification(false, cx)
+                    .set_should_show_update_notification(None, cx)
                     .detach_and_log_err(cx);
             })?;
         }
@@ -338,6 +734,19 @@ impl AutoUpdater {
     }
 
     pub fn poll(&mut self, cx: &mut ModelContext<Self>) {
+        let Some(channel) = update_release_channel(cx) else {
+            return;
+        };
+        self.poll_release_channel(channel, cx);
+    }
+
+    /// Checks for an update on the given channel, installing its latest
+    /// release if it differs from the running build's channel.
+    pub fn poll_channel(&mut self, channel: UpdateChannel, cx: &mut ModelContext<Self>) {
+        self.poll_release_channel(channel.release_channel(), cx);
+    }
+
+    fn poll_release_channel(&mut self, channel: ReleaseChannel, cx: &mut ModelContext<Self>) {
         if self.pending_poll.is_some() || self.status.is_updated() {
             return;
         }
@@ -346,7 +755,7 @@ impl AutoUpdater {
         cx.notify();
 
         self.pending_poll = Some(cx.spawn(|this, mut cx| async move {
-            let result = Self::update(this.upgrade()?, cx.clone()).await;
+            let result = Self::update(this.upgrade()?, channel, cx.clone()).await;
             this.update(&mut cx, |this, cx| {
                 this.pending_poll = None;
                 if let Err(error) = result {
@@ -368,7 +777,66 @@ impl AutoUpdater {
         cx.notify();
     }
 
-    async fn update(this: Model<Self>, mut cx: AsyncAppContext) -> Result<()> {
+    /// Restores the installation that was replaced by the most recent update.
+    fn rollback(&mut self, rollback_info: RollbackInfo, cx: &mut ModelContext<Self>) {
+        if self.pending_poll.is_some() || self.status.is_updated() {
+            return;
+        }
+
+        self.status = AutoUpdateStatus::Installing;
+        cx.notify();
+
+        self.pending_poll = Some(cx.spawn(|this, mut cx| async move {
+            let result = Self::restore(rollback_info, cx.clone()).await;
+            this.update(&mut cx, |this, cx| {
+                this.pending_poll = None;
+                match result {
+                    Ok(binary_path) => {
+                        this.status = AutoUpdateStatus::Updated { binary_path };
+                    }
+                    Err(error) => {
+                        log::error!("auto-update rollback failed: error:{:?}", error);
+                        this.status = AutoUpdateStatus::Errored;
+                    }
+                }
+                cx.notify();
+            })
+            .ok()
+        }));
+    }
+
+    async fn restore(rollback_info: RollbackInfo, cx: AsyncAppContext) -> Result<PathBuf> {
+        let binary_path = cx.update(|cx| cx.app_path())??;
+
+        copy_app(&rollback_info.backup_path, &rollback_info.app_path).await?;
+
+        KEY_VALUE_STORE
+            .write_kvp(
+                SKIPPED_VERSION_KEY.to_string(),
+                rollback_info.updated_version.clone(),
+            )
+            .await?;
+        KEY_VALUE_STORE.delete_kvp(ROLLBACK_KEY.to_string()).await?;
+        fs::remove_dir_all(&rollback_info.backup_path)
+            .await
+            .log_err();
+
+        Ok(binary_path)
+    }
+
+    fn rollback_info() -> Result<Option<RollbackInfo>> {
+        let Some(rollback_info) = KEY_VALUE_STORE.read_kvp(ROLLBACK_KEY)? else {
+            return Ok(None);
+        };
+        let rollback_info: RollbackInfo = serde_json::from_str(&rollback_info)?;
+        Ok(rollback_info.backup_path.exists().then_some(rollback_info))
+    }
+
+    async fn update(
+        this: Model<Self>,
+        target_channel: ReleaseChannel,
+        mut cx: AsyncAppContext,
+    ) -> Result<()> {
         let (client, current_version) = this.read_with(&cx, |this, _| {
             (this.http_client.clone(), this.current_version)
         })?;
@@ -383,14 +851,10 @@ impl AutoUpdater {
             "/api/releases/latest?asset={}&os={}&arch={}",
             asset, OS, ARCH
         ));
-        cx.update(|cx| {
-            if let Some(param) = ReleaseChannel::try_global(cx)
-                .and_then(|release_channel| release_channel.release_query_param())
-            {
-                url_string += "&";
-                url_string += param;
-            }
-        })?;
+        if let Some(param) = target_channel.release_query_param() {
+            url_string += "&";
+            url_string += param;
+        }
 
         let mut response = client.get(&url_string, Default::default(), true).await?;
 
@@ -404,23 +868,52 @@ impl AutoUpdater {
         let release: JsonRelease =
             serde_json::from_slice(body.as_slice()).context("error deserializing release")?;
 
-        let should_download = match *RELEASE_CHANNEL {
-            ReleaseChannel::Nightly => cx
-                .update(|cx| AppCommitSha::try_global(cx).map(|sha| release.version != sha.0))
-                .ok()
-                .flatten()
-                .unwrap_or(true),
-            _ => release.version.parse::<SemanticVersion>()? > current_version,
+        let switching_channel = target_channel != *RELEASE_CHANNEL;
+        let skipped_version = cx
+            .background_executor()
+            .spawn(async { KEY_VALUE_STORE.read_kvp(SKIPPED_VERSION_KEY) })
+            .await?;
+        let should_download = if switching_channel {
+            // The release is only installed once, unless the installation was removed since.
+            let installed_version = cx
+                .background_executor()
+                .spawn(async move {
+                    KEY_VALUE_STORE.read_kvp(&installed_channel_version_key(target_channel))
+                })
+                .await?;
+            installed_version.as_ref() != Some(&release.version)
+                || !installed_app_path(target_channel, &cx)?.exists()
+        } else if skipped_version.as_ref() == Some(&release.version) {
+            false
+        } else {
+            match *RELEASE_CHANNEL {
+                ReleaseChannel::Nightly => cx
+                    .update(|cx| AppCommitSha::try_global(cx).map(|sha| release.version != sha.0))
+                    .ok()
+                    .flatten()
+                    .unwrap_or(true),
+                _ => release.version.parse::<SemanticVersion>()? > current_version,
+            }
         };
 
         if !should_download {
+            // The target channel's release is already installed, so only the
+            // restart into it is left to complete the switch.
+            let status = if switching_channel {
+                AutoUpdateStatus::Updated {
+                    binary_path: installed_binary_path(target_channel, &cx)?,
+                }
+            } else {
+                AutoUpdateStatus::Idle
+            };
             this.update(&mut cx, |this, cx| {
-                this.status = AutoUpdateStatus::Idle;
+                this.status = status;
                 cx.notify();
             })?;
             return Ok(());
         }
 
+        let release_version = release.version.clone();
         this.update(&mut cx, |this, cx| {
             this.status = AutoUpdateStatus::Downloading;
             cx.notify();
@@ -429,7 +922,14 @@ impl AutoUpdater {
         let temp_dir = tempfile::Builder::new()
             .prefix("zed-auto-update")
             .tempdir()?;
-        let downloaded_asset = download_release(&temp_dir, release, &asset, client, &cx).await?;
+        let downloaded_asset =
+            download_release(&temp_dir, release, &asset, client.clone(), &cx).await?;
+        // Cached now, so that the notes are shown after restarting into the
+        // update even when offline.
+        cache_release_notes(&client, target_channel, &release_version)
+            .await
+            .context("failed to cache release notes")
+            .log_err();
 
         this.update(&mut cx, |this, cx| {
             this.status = AutoUpdateStatus::Installing;
@@ -441,14 +941,39 @@ impl AutoUpdater {
         // So we cache it here, which allows us to then restart later on.
         let binary_path = cx.update(|cx| cx.app_path())??;
 
-        match OS {
-            "macos" => install_release_macos(&temp_dir, downloaded_asset, &cx).await,
-            "linux" => install_release_linux(&temp_dir, downloaded_asset, &cx).await,
+        if !switching_channel {
+            backup_running_app(current_version.to_string(), release_version.clone(), &cx)
+                .await
+                .context("failed to back up current installation")
+                .log_err();
+        }
+
+        let installed_path = match OS {
+            "macos" => {
+                install_release_macos(&temp_dir, downloaded_asset, target_channel, &cx).await
+            }
+            "linux" => {
+                install_release_linux(&temp_dir, downloaded_asset, target_channel, &cx).await
+            }
             _ => Err(anyhow!("not supported: {:?}", OS)),
         }?;
 
+        // When switching channels the new build is installed alongside the
+        // running one, so restart into it instead.
+        let binary_path = if switching_channel {
+            KEY_VALUE_STORE
+                .write_kvp(
+                    installed_channel_version_key(target_channel),
+                    release_version,
+                )
+                .await?;
+            installed_path
+        } else {
+            binary_path
+        };
+
         this.update(&mut cx, |this, cx| {
-            this.set_should_show_update_notification(true, cx)
+            this.set_should_show_update_notification(Some(current_version.to_string()), cx)
                 .detach_and_log_err(cx);
             this.status = AutoUpdateStatus::Updated { binary_path };
             cx.notify();
@@ -457,17 +982,19 @@ impl AutoUpdater {
         Ok(())
     }
 
+    /// Records whether to show the update notification on the next launch,
+    /// along with the version that was running before the update.
     fn set_should_show_update_notification(
         &self,
-        should_show: bool,
+        updated_from_version: Option<String>,
         cx: &AppContext,
     ) -> Task<Result<()>> {
         cx.background_executor().spawn(async move {
-            if should_show {
+            if let Some(updated_from_version) = updated_from_version {
                 KEY_VALUE_STORE
                     .write_kvp(
                         SHOULD_SHOW_UPDATE_NOTIFICATION_KEY.to_string(),
-                        "".to_string(),
+                        updated_from_version,
                     )
                     .await?;
             } else {
@@ -479,11 +1006,16 @@ impl AutoUpdater {
         })
     }
 
-    fn should_show_update_notification(&self, cx: &AppContext) -> Task<Result<bool>> {
+    /// Returns `Some` if the update notification should be shown, containing
+    /// the version that was updated from, if known.
+    fn should_show_update_notification(
+        &self,
+        cx: &AppContext,
+    ) -> Task<Result<Option<Option<String>>>> {
         cx.background_executor().spawn(async move {
             Ok(KEY_VALUE_STORE
                 .read_kvp(SHOULD_SHOW_UPDATE_NOTIFICATION_KEY)?
-                .is_some())
+                .map(|version| Some(version).filter(|version| !version.is_empty())))
         })
     }
 }
@@ -520,12 +1052,113 @@ async fn download_release(
     Ok(target_path)
 }
 
+/// Returns the location of the installation that updates on the given channel
+/// are installed into.
+fn installed_app_path(channel: ReleaseChannel, cx: &AsyncAppContext) -> Result<PathBuf> {
+    match OS {
+        "macos" => {
+            let running_app_path = ZED_APP_PATH
+                .clone()
+                .map_or_else(|| cx.update(|cx| cx.app_path())?, Ok)?;
+            if channel == *RELEASE_CHANNEL {
+                Ok(running_app_path)
+            } else {
+                Ok(running_app_path.with_file_name(format!("{}.app", channel.display_name())))
+            }
+        }
+        "linux" => {
+            let home_dir = PathBuf::from(env::var("HOME").context("no HOME env var set")?);
+            Ok(home_dir.join(".local").join(linux_app_folder_name(channel)))
+        }
+        _ => Err(anyhow!("not supported: {:?}", OS)),
+    }
+}
+
+/// Returns the binary to restart into after installing updates on the given channel.
+fn installed_binary_path(channel: ReleaseChannel, cx: &AsyncAppContext) -> Result<PathBuf> {
+    let installed_path = installed_app_path(channel, cx)?;
+    match OS {
+        "linux" => Ok(installed_path.join("libexec").join("zed-editor")),
+        _ => Ok(installed_path),
+    }
+}
+
+/// The key of the version that was last installed for the given channel, when
+/// switching to it from another one.
+fn installed_channel_version_key(channel: ReleaseChannel) -> String {
+    format!(
+        "{INSTALLED_CHANNEL_VERSION_KEY_PREFIX}-{}",
+        channel.dev_name()
+    )
+}
+
+fn linux_app_folder_name(channel: ReleaseChannel) -> String {
+    match channel {
+        ReleaseChannel::Stable => "zed.app".to_string(),
+        channel => format!("zed-{}.app", channel.dev_name()),
+    }
+}
+
+/// Copies the running installation aside so that it can be restored with
+/// [`Rollback`].
+async fn backup_running_app(
+    version: String,
+    updated_version: String,
+    cx: &AsyncAppContext,
+) -> Result<()> {
+    let app_path = installed_app_path(*RELEASE_CHANNEL, cx)?;
+    let app_filename = app_path
+        .file_name()
+        .ok_or_else(|| anyhow!("invalid running app path"))?;
+    let backup_path = paths::auto_update_backup_dir().join(app_filename);
+
+    fs::create_dir_all(paths::auto_update_backup_dir()).await?;
+    copy_app(&app_path, &backup_path).await?;
+
+    let rollback_info = serde_json::to_string(&RollbackInfo {
+        version,
+        updated_version,
+        app_path,
+        backup_path,
+    })?;
+    KEY_VALUE_STORE
+        .write_kvp(ROLLBACK_KEY.to_string(), rollback_info)
+        .await?;
+    KEY_VALUE_STORE
+        .delete_kvp(SKIPPED_VERSION_KEY.to_string())
+        .await?;
+
+    Ok(())
+}
+
+async fn copy_app(from: &Path, to: &Path) -> Result<()> {
+    let mut source: OsString = from.into();
+    source.push("/");
+
+    let output = Command::new("rsync")
+        .args(&["-a", "--delete"])
+        .arg(&source)
+        .arg(to)
+        .output()
+        .await?;
+
+    anyhow::ensure!(
+        output.status.success(),
+        "failed to copy {:?} to {:?}: {:?}",
+        from,
+        to,
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    Ok(())
+}
+
 async fn install_release_linux(
     temp_dir: &tempfile::TempDir,
     downloaded_tar_gz: PathBuf,
+    channel: ReleaseChannel,
     cx: &AsyncAppContext,
-) -> Result<()> {
-    let channel = cx.update(|cx| ReleaseChannel::global(cx).dev_name())?;
+) -> Result<PathBuf> {
     let home_dir = PathBuf::from(env::var("HOME").context("no HOME env var set")?);
 
     let extracted = temp_dir.path().join("zed");
@@ -549,12 +1182,7 @@ async fn install_release_linux(
         String::from_utf8_lossy(&output.stderr)
     );
 
-    let suffix = if channel != "stable" {
-        format!("-{}", channel)
-    } else {
-        String::default()
-    };
-    let app_folder_name = format!("zed{}.app", suffix);
+    let app_folder_name = linux_app_folder_name(channel);
 
     let from = extracted.join(&app_folder_name);
     let to = home_dir.join(".local");
@@ -574,23 +1202,27 @@ async fn install_release_linux(
         String::from_utf8_lossy(&output.stderr)
     );
 
-    Ok(())
+    installed_binary_path(channel, cx)
 }
 
 async fn install_release_macos(
     temp_dir: &tempfile::TempDir,
     downloaded_dmg: PathBuf,
+    channel: ReleaseChannel,
     cx: &AsyncAppContext,
-) -> Result<()> {
-    let running_app_path = ZED_APP_PATH
-        .clone()
-        .map_or_else(|| cx.update(|cx| cx.app_path())?, Ok)?;
-    let running_app_filename = running_app_path
+) -> Result<PathBuf> {
+    let installed_app_path = installed_app_path(channel, cx)?;
+    let installed_app_filename = installed_app_path
         .file_name()
         .ok_or_else(|| anyhow!("invalid running app path"))?;
+    let mounted_app_filename = if channel == *RELEASE_CHANNEL {
+        installed_app_filename.to_owned()
+    } else {
+        format!("{}.app", channel.display_name()).into()
+    };
 
     let mount_path = temp_dir.path().join("Zed");
-    let mut mounted_app_path: OsString = mount_path.join(running_app_filename).into();
+    let mut mounted_app_path: OsString = mount_path.join(mounted_app_filename).into();
 
     mounted_app_path.push("/");
     let output = Command::new("hdiutil")
@@ -610,7 +1242,7 @@ async fn install_release_macos(
     let output = Command::new("rsync")
         .args(&["-av", "--delete"])
         .arg(&mounted_app_path)
-        .arg(&running_app_path)
+        .arg(&installed_app_path)
         .output()
         .await?;
 
@@ -632,5 +1264,117 @@ async fn install_release_macos(
         String::from_utf8_lossy(&output.stderr)
     );
 
-    Ok(())
+    Ok(installed_app_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+
+    fn notes(title: &str, release_notes: &str) -> ReleaseNotesBody {
+        ReleaseNotesBody {
+            title: title.to_string(),
+            release_notes: release_notes.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_release_notes_markdown_of_one_version() {
+        let markdown = release_notes_markdown(
+            &[notes(
+                "Zed 0.2.0",
+                "- Added a setting for the tab size\n- Fixed a crash\n",
+            )],
+            Some("0.1.0"),
+        );
+        assert_eq!(
+            markdown,
+            "_Updated from version 0.1.0._\n\n\
+             ## Breaking, keymap, and settings changes\n\n\
+             - Added a setting for the tab size\n\n\
+             - Added a setting for the tab size\n- Fixed a crash\n"
+        );
+
+        let markdown = release_notes_markdown(&[notes("Zed 0.2.0", "- Fixed a crash\n")], None);
+        assert_eq!(markdown, "- Fixed a crash\n");
+    }
+
+    #[test]
+    fn test_release_notes_markdown_of_several_versions() {
+        let markdown = release_notes_markdown(
+            &[
+                notes("Zed 0.3.0", "- Breaking: removed the old keymap\n"),
+                notes("Zed 0.2.0", "- Fixed a crash\n"),
+            ],
+            Some("0.1.0"),
+        );
+        assert_eq!(
+            markdown,
+            "_Updated from version 0.1.0._\n\n\
+             ## Breaking, keymap, and settings changes\n\n\
+             - Breaking: removed the old keymap\n\n\
+             ## Zed 0.3.0\n\n\
+             - Breaking: removed the old keymap\n\n\
+             ## Zed 0.2.0\n\n\
+             - Fixed a crash\n\n"
+        );
+    }
+
+    #[gpui::test]
+    fn test_update_channel_of_each_release_channel(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            AutoUpdateChannelSetting::register(cx);
+        });
+        let update_channel = |running_channel, cx: &mut TestAppContext| {
+            cx.update(|cx| AutoUpdateChannelSetting::get_global(cx).update_channel(running_channel))
+        };
+
+        for channel in [
+            ReleaseChannel::Stable,
+            ReleaseChannel::Preview,
+            ReleaseChannel::Nightly,
+            ReleaseChannel::Dev,
+        ] {
+            assert_eq!(update_channel(channel, cx), channel);
+        }
+
+        // The channel chosen for stable builds doesn't apply to the builds of
+        // the other channels, which read the same settings.
+        cx.update(|cx| {
+            cx.update_global::<SettingsStore, _>(|store, cx| {
+                store
+                    .set_user_settings(r#"{ "auto_update_channel": { "stable": "preview" } }"#, cx)
+                    .unwrap();
+            })
+        });
+        assert_eq!(
+            update_channel(ReleaseChannel::Stable, cx),
+            ReleaseChannel::Preview
+        );
+        assert_eq!(
+            update_channel(ReleaseChannel::Preview, cx),
+            ReleaseChannel::Preview
+        );
+        assert_eq!(
+            update_channel(ReleaseChannel::Nightly, cx),
+            ReleaseChannel::Nightly
+        );
+        assert_eq!(update_channel(ReleaseChannel::Dev, cx), ReleaseChannel::Dev);
+    }
+
+    #[test]
+    fn test_versions_between() {
+        let versions = ["0.1.0", "0.3.0", "0.2.0", "0.4.0", "abc123"]
+            .map(String::from)
+            .to_vec();
+        assert_eq!(
+            versions_between(&versions, "0.1.0", "0.4.0"),
+            ["0.3.0", "0.2.0"]
+        );
+        assert!(versions_between(&versions, "0.3.0", "0.4.0").is_empty());
+        assert!(versions_between(&versions, "abc123", "0.4.0").is_empty());
+    }
 }
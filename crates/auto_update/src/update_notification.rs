@@ -1,13 +1,19 @@
 use gpui::{
     div, DismissEvent, EventEmitter, InteractiveElement, IntoElement, ParentElement, Render,
-    SemanticVersion, StatefulInteractiveElement, Styled, ViewContext,
+    SemanticVersion, StatefulInteractiveElement, Styled, ViewContext, WeakView,
 };
 use menu::Cancel;
 use release_channel::ReleaseChannel;
-use workspace::ui::{h_flex, v_flex, Icon, IconName, Label, StyledExt};
+use util::ResultExt;
+use workspace::{
+    ui::{h_flex, v_flex, Icon, IconName, Label, StyledExt},
+    Workspace,
+};
 
 pub struct UpdateNotification {
     version: SemanticVersion,
+    previous_version: Option<String>,
+    workspace: WeakView<Workspace>,
 }
 
 impl EventEmitter<DismissEvent> for UpdateNotification {}
@@ -41,7 +47,12 @@ impl Render for UpdateNotification {
                     .child(Label::new("View the release notes"))
                     .cursor_pointer()
                     .on_click(cx.listener(|this, _, cx| {
-                        crate::view_release_notes(&Default::default(), cx);
+                        let previous_version = this.previous_version.clone();
+                        this.workspace
+                            .update(cx, |workspace, cx| {
+                                crate::view_release_notes_locally(workspace, previous_version, cx);
+                            })
+                            .log_err();
                         this.dismiss(&menu::Cancel, cx)
                     })),
             )
@@ -49,8 +60,16 @@ impl Render for UpdateNotification {
 }
 
 impl UpdateNotification {
-    pub fn new(version: SemanticVersion) -> Self {
-        Self { version }
+    pub fn new(
+        version: SemanticVersion,
+        previous_version: Option<String>,
+        workspace: WeakView<Workspace>,
+    ) -> Self {
+        Self {
+            version,
+            previous_version,
+            workspace,
+        }
     }
 
     pub fn dismiss(&mut self, _: &Cancel, cx: &mut ViewContext<Self>) {
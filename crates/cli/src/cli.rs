@@ -14,6 +14,12 @@ pub enum CliRequest {
         wait: bool,
         open_new_workspace: Option<bool>,
         dev_server_token: Option<String>,
+        /// Text piped into the CLI, to be opened in a new buffer.
+        stdin_contents: Option<String>,
+        /// The name or file extension of the language of `stdin_contents`.
+        language: Option<String>,
+        /// The old and new paths of files to show the differences between.
+        diff: Option<(String, String)>,
     },
 }
 
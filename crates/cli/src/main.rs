@@ -15,31 +15,56 @@ struct Detect;
 
 trait InstalledApp {
     fn zed_version_string(&self) -> String;
-    fn launch(&self, ipc_url: String) -> anyhow::Result<()>;
+    /// Opens the URLs in Zed, starting it if it isn't running.
+    fn launch(&self, urls: &[String]) -> anyhow::Result<()>;
     fn run_foreground(&self, ipc_url: String) -> io::Result<ExitStatus>;
+    /// Runs the Zed binary with the given arguments, for commands that don't start the UI.
+    fn run_command(&self, args: &[String]) -> io::Result<ExitStatus>;
 }
 
 #[derive(Parser, Debug)]
-#[command(name = "zed", disable_version_flag = true)]
+#[command(
+    name = "zed",
+    disable_version_flag = true,
+    args_conflicts_with_subcommands = true
+)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
     /// Wait for all of the given paths to be opened/closed before exiting.
     #[arg(short, long)]
     wait: bool,
-    /// Add files to the currently open workspace
+    /// Add files to the most recently used workspace
     #[arg(short, long, overrides_with = "new")]
     add: bool,
-    /// Create a new workspace
-    #[arg(short, long, overrides_with = "add")]
+    /// Create a new workspace, in a new window
+    #[arg(short, long, visible_alias = "new-window", overrides_with = "add")]
     new: bool,
     /// A sequence of space-separated paths that you want to open.
     ///
     /// Use `path:line:row` syntax to open a file at a specific location.
     /// Non-existing paths and directories will ignore `:line:row` suffix.
+    /// Use `-` to open the text read from stdin in a new buffer.
     #[arg(value_parser = parse_path_with_position)]
     paths_with_position: Vec<PathLikeWithPosition<PathBuf>>,
+    /// Open the given files at this line, unless their paths specify one.
+    #[arg(long)]
+    line: Option<u32>,
+    /// Open the given files at this column of the line given with `--line`.
+    #[arg(long, requires = "line")]
+    column: Option<u32>,
+    /// The language of the text read from stdin, by name or file extension.
+    #[arg(long)]
+    language: Option<String>,
+    /// Open the second file with the changes from the first one to it shown inline.
+    #[arg(long, num_args = 2, value_names = ["OLD_PATH", "NEW_PATH"])]
+    diff: Vec<PathBuf>,
     /// Print Zed's version and the app path.
     #[arg(short, long)]
     version: bool,
+    /// Print the system specs that are attached to bug reports.
+    #[arg(long)]
+    system_specs: bool,
     /// Run zed in the foreground (useful for debugging)
     #[arg(long)]
     foreground: bool,
@@ -51,14 +76,71 @@ struct Args {
     dev_server_token: Option<String>,
 }
 
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Submit feedback along with the system specs, without starting the UI.
+    Feedback {
+        /// The feedback to send.
+        message: String,
+    },
+}
+
 fn parse_path_with_position(
     argument_str: &str,
 ) -> Result<PathLikeWithPosition<PathBuf>, std::convert::Infallible> {
+    // Desktops pass the files that Zed is opened with as `file://` URIs.
+    if let Some(path) = file_uri_to_path(argument_str) {
+        return Ok(PathLikeWithPosition {
+            path_like: path,
+            row: None,
+            column: None,
+        });
+    }
     PathLikeWithPosition::parse_str(argument_str, |path_str| {
         Ok(Path::new(path_str).to_path_buf())
     })
 }
 
+/// Returns the path of a `file://` URI, with its percent-encoded bytes decoded.
+fn file_uri_to_path(uri: &str) -> Option<PathBuf> {
+    let rest = uri.strip_prefix("file://")?;
+    // The host, usually empty or `localhost`, is followed by the absolute path.
+    let path = &rest[rest.find('/')?..];
+
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut encoded = path.bytes();
+    while let Some(byte) = encoded.next() {
+        if byte == b'%' {
+            let hex = [encoded.next()?, encoded.next()?];
+            let hex = std::str::from_utf8(&hex).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    Some(PathBuf::from(String::from_utf8_lossy(&bytes).into_owned()))
+}
+
+/// Canonicalizes the path, or only its parent directory for paths that don't exist yet.
+fn canonicalize(path: &Path, curdir: &Path) -> io::Result<PathBuf> {
+    match fs::canonicalize(path) {
+        Ok(path) => Ok(path),
+        Err(e) => {
+            if let Some(mut parent) = path.parent() {
+                if parent == Path::new("") {
+                    parent = curdir;
+                }
+                match fs::canonicalize(parent) {
+                    Ok(parent) => Ok(parent.join(path.file_name().unwrap())),
+                    Err(_) => Err(e),
+                }
+            } else {
+                Err(e)
+            }
+        }
+    }
+}
+
 fn main() -> Result<()> {
     // Exit flatpak sandbox if needed
     #[cfg(target_os = "linux")]
@@ -89,28 +171,54 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    let command_args = match &args.command {
+        Some(Command::Feedback { message }) => {
+            Some(vec!["--feedback".to_string(), message.clone()])
+        }
+        None if args.system_specs => Some(vec!["--system-specs".to_string()]),
+        None => None,
+    };
+    if let Some(command_args) = command_args {
+        let status = app.run_command(&command_args)?;
+        std::process::exit(status.code().unwrap_or(1));
+    }
+
+    // Links, like those that desktops open Zed with, are passed to Zed as they are.
+    let links = env::args()
+        .skip(1)
+        .filter(|arg| arg.starts_with("zed://"))
+        .collect::<Vec<_>>();
+    if !links.is_empty() {
+        app.launch(&links)?;
+        return Ok(());
+    }
+
     let curdir = env::current_dir()?;
     let mut paths = vec![];
-    for path in args.paths_with_position {
-        let canonicalized = path.map_path_like(|path| match fs::canonicalize(&path) {
-            Ok(path) => Ok(path),
-            Err(e) => {
-                if let Some(mut parent) = path.parent() {
-                    if parent == Path::new("") {
-                        parent = &curdir;
-                    }
-                    match fs::canonicalize(parent) {
-                        Ok(parent) => Ok(parent.join(path.file_name().unwrap())),
-                        Err(_) => Err(e),
-                    }
-                } else {
-                    Err(e)
-                }
+    let mut stdin_contents = None;
+    for mut path in args.paths_with_position {
+        if path.path_like == Path::new("-") {
+            if stdin_contents.is_none() {
+                stdin_contents = Some(io::read_to_string(io::stdin()).context("Reading stdin")?);
             }
-        })?;
+            continue;
+        }
+        if path.row.is_none() {
+            path.row = args.line;
+            path.column = args.column;
+        }
+        let canonicalized = path.map_path_like(|path| canonicalize(&path, &curdir))?;
         paths.push(canonicalized.to_string(|path| path.display().to_string()))
     }
 
+    let diff = match args.diff.as_slice() {
+        [old_path, new_path] => Some((
+            canonicalize(old_path, &curdir)?.display().to_string(),
+            canonicalize(new_path, &curdir)?.display().to_string(),
+        )),
+        _ => None,
+    };
+
     let (server, server_name) =
         IpcOneShotServer::<IpcHandshake>::new().context("Handshake before Zed spawn")?;
     let url = format!("zed-cli://{server_name}");
@@ -131,6 +239,9 @@ fn main() -> Result<()> {
             wait: args.wait,
             open_new_workspace,
             dev_server_token: args.dev_server_token,
+            stdin_contents,
+            language: args.language,
+            diff,
         })?;
 
         while let Ok(response) = rx.recv() {
@@ -148,7 +259,7 @@ fn main() -> Result<()> {
     if args.foreground {
         app.run_foreground(url)?;
     } else {
-        app.launch(url)?;
+        app.launch(&[url])?;
         sender.join().unwrap()?;
     }
 
@@ -219,13 +330,15 @@ mod linux {
             )
         }
 
-        fn launch(&self, ipc_url: String) -> anyhow::Result<()> {
+        fn launch(&self, urls: &[String]) -> anyhow::Result<()> {
             let sock_path = paths::support_dir().join(format!("zed-{}.sock", *RELEASE_CHANNEL));
             let sock = UnixDatagram::unbound()?;
             if sock.connect(&sock_path).is_err() {
-                self.boot_background(ipc_url)?;
+                self.boot_background(urls)?;
             } else {
-                sock.send(ipc_url.as_bytes())?;
+                for url in urls {
+                    sock.send(url.as_bytes())?;
+                }
             }
             Ok(())
         }
@@ -235,10 +348,16 @@ mod linux {
                 .arg(ipc_url)
                 .status()
         }
+
+        fn run_command(&self, args: &[String]) -> io::Result<ExitStatus> {
+            std::process::Command::new(self.0.clone())
+                .args(args)
+                .status()
+        }
     }
 
     impl App {
-        fn boot_background(&self, ipc_url: String) -> anyhow::Result<()> {
+        fn boot_background(&self, urls: &[String]) -> anyhow::Result<()> {
             let path = &self.0;
 
             match fork::fork() {
@@ -252,8 +371,10 @@ mod linux {
                     if let Err(_) = fork::close_fd() {
                         eprintln!("failed to close_fd: {}", std::io::Error::last_os_error());
                     }
-                    let error =
-                        exec::execvp(path.clone(), &[path.as_os_str(), &OsString::from(ipc_url)]);
+                    let args = std::iter::once(path.clone().into_os_string())
+                        .chain(urls.iter().map(OsString::from))
+                        .collect::<Vec<_>>();
+                    let error = exec::execvp(path.clone(), &args);
                     // if exec succeeded, we never get here.
                     eprintln!("failed to exec {:?}: {}", path, error);
                     process::exit(1)
@@ -399,12 +520,18 @@ mod windows {
         fn zed_version_string(&self) -> String {
             unimplemented!()
         }
-        fn launch(&self, _ipc_url: String) -> anyhow::Result<()> {
+        fn launch(&self, _urls: &[String]) -> anyhow::Result<()> {
             unimplemented!()
         }
         fn run_foreground(&self, _ipc_url: String) -> io::Result<ExitStatus> {
             unimplemented!()
         }
+        fn run_command(&self, _args: &[String]) -> io::Result<ExitStatus> {
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "running Zed commands isn't supported on Windows yet",
+            ))
+        }
     }
 
     impl Detect {
@@ -516,7 +643,7 @@ mod mac_os {
             )
         }
 
-        fn launch(&self, url: String) -> anyhow::Result<()> {
+        fn launch(&self, urls: &[String]) -> anyhow::Result<()> {
             match self {
                 Self::App { app_bundle, .. } => {
                     let app_path = app_bundle;
@@ -524,16 +651,25 @@ mod mac_os {
                     let status = unsafe {
                         let app_url = CFURL::from_path(app_path, true)
                             .with_context(|| format!("invalid app path {app_path:?}"))?;
-                        let url_to_open = CFURL::wrap_under_create_rule(CFURLCreateWithBytes(
-                            ptr::null(),
-                            url.as_ptr(),
-                            url.len() as CFIndex,
-                            kCFStringEncodingUTF8,
-                            ptr::null(),
-                        ));
+                        let urls_to_open = urls
+                            .iter()
+                            .map(|url| {
+                                CFURL::wrap_under_create_rule(CFURLCreateWithBytes(
+                                    ptr::null(),
+                                    url.as_ptr(),
+                                    url.len() as CFIndex,
+                                    kCFStringEncodingUTF8,
+                                    ptr::null(),
+                                ))
+                            })
+                            .collect::<Vec<_>>();
                         // equivalent to: open zed-cli:... -a /Applications/Zed\ Preview.app
-                        let urls_to_open =
-                            CFArray::from_copyable(&[url_to_open.as_concrete_TypeRef()]);
+                        let urls_to_open = CFArray::from_copyable(
+                            &urls_to_open
+                                .iter()
+                                .map(|url| url.as_concrete_TypeRef())
+                                .collect::<Vec<_>>(),
+                        );
                         LSOpenFromURLSpec(
                             &LSLaunchURLSpec {
                                 appURL: app_url.as_concrete_TypeRef(),
@@ -570,7 +706,7 @@ mod mac_os {
                         .env(FORCE_CLI_MODE_ENV_VAR_NAME, "")
                         .stderr(subprocess_stdout_file)
                         .stdout(subprocess_stdin_file)
-                        .arg(url);
+                        .args(urls);
 
                     command
                         .spawn()
@@ -582,16 +718,26 @@ mod mac_os {
         }
 
         fn run_foreground(&self, ipc_url: String) -> io::Result<ExitStatus> {
-            let path = match self {
-                Bundle::App { app_bundle, .. } => app_bundle.join("Contents/MacOS/zed"),
-                Bundle::LocalPath { executable, .. } => executable.clone(),
-            };
+            std::process::Command::new(self.executable())
+                .arg(ipc_url)
+                .status()
+        }
 
-            std::process::Command::new(path).arg(ipc_url).status()
+        fn run_command(&self, args: &[String]) -> io::Result<ExitStatus> {
+            std::process::Command::new(self.executable())
+                .args(args)
+                .status()
         }
     }
 
     impl Bundle {
+        fn executable(&self) -> PathBuf {
+            match self {
+                Bundle::App { app_bundle, .. } => app_bundle.join("Contents/MacOS/zed"),
+                Bundle::LocalPath { executable, .. } => executable.clone(),
+            }
+        }
+
         fn plist(&self) -> &InfoPlist {
             match self {
                 Self::App { plist, .. } => plist,
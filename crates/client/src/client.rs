@@ -19,7 +19,7 @@ use futures::{
 use gpui::{
     actions, AnyModel, AnyWeakModel, AppContext, AsyncAppContext, Global, Model, Task, WeakModel,
 };
-use http::{HttpClient, HttpClientWithUrl};
+use http::{HttpClient, HttpClientOptions, HttpClientWithUrl, RetryPolicy};
 use lazy_static::lazy_static;
 use parking_lot::RwLock;
 use postage::watch;
@@ -62,6 +62,33 @@ impl fmt::Display for DevServerToken {
     }
 }
 
+/// A token generated from a Zed account, which allows signing in without going through the browser.
+///
+/// Personal access tokens have the format `<user-id>.<token-id>.<secret>`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PersonalAccessToken(pub String);
+
+impl PersonalAccessToken {
+    /// Returns the id of the user this token belongs to.
+    pub fn user_id(&self) -> Result<u64> {
+        let user_id = self
+            .0
+            .split('.')
+            .next()
+            .filter(|_| self.0.matches('.').count() >= 2)
+            .ok_or_else(|| anyhow!("invalid personal access token format"))?;
+        user_id
+            .parse()
+            .context("invalid user id in personal access token")
+    }
+}
+
+impl fmt::Display for PersonalAccessToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 lazy_static! {
     static ref ZED_SERVER_URL: Option<String> = std::env::var("ZED_SERVER_URL").ok();
     static ref ZED_RPC_URL: Option<String> = std::env::var("ZED_RPC_URL").ok();
@@ -78,6 +105,11 @@ lazy_static! {
     pub static ref ADMIN_API_TOKEN: Option<String> = std::env::var("ZED_ADMIN_API_TOKEN")
         .ok()
         .and_then(|s| if s.is_empty() { None } else { Some(s) });
+    /// An environment variable containing a [`PersonalAccessToken`] to sign in with,
+    /// instead of the credentials stored in the keychain or the browser flow.
+    pub static ref ZED_ACCESS_TOKEN: Option<String> = std::env::var("ZED_ACCESS_TOKEN")
+        .ok()
+        .and_then(|s| if s.is_empty() { None } else { Some(s) });
     pub static ref ZED_APP_PATH: Option<PathBuf> =
         std::env::var("ZED_APP_PATH").ok().map(PathBuf::from);
     pub static ref ZED_ALWAYS_ACTIVE: bool =
@@ -87,6 +119,7 @@ lazy_static! {
 pub const INITIAL_RECONNECTION_DELAY: Duration = Duration::from_millis(500);
 pub const MAX_RECONNECTION_DELAY: Duration = Duration::from_secs(10);
 pub const CONNECTION_TIMEOUT: Duration = Duration::from_secs(20);
+pub const MAX_HTTP_RETRY_BACKOFF: Duration = Duration::from_secs(8);
 
 actions!(client, [SignIn, SignOut, Reconnect]);
 
@@ -139,10 +172,68 @@ impl Settings for ProxySettings {
     }
 }
 
+#[derive(Default, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HttpClientSettingsContent {
+    /// A path to a PEM file containing the certificate authorities used to verify servers,
+    /// replacing the ones trusted by the system.
+    ///
+    /// Default: null
+    pub ca_certificates: Option<PathBuf>,
+    /// Whether to use the proxy configured in the environment when `proxy` is not set.
+    ///
+    /// Default: true
+    pub use_system_proxy: Option<bool>,
+    /// How many times to retry requests failing because of transient errors.
+    ///
+    /// Default: 3
+    pub max_retries: Option<u32>,
+    /// The delay before the first retry in milliseconds, doubled after each subsequent attempt.
+    ///
+    /// Default: 500
+    pub retry_backoff_ms: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct HttpClientSettings {
+    pub ca_certificates: Option<PathBuf>,
+    pub use_system_proxy: bool,
+    pub max_retries: u32,
+    pub retry_backoff_ms: u64,
+}
+
+impl HttpClientSettings {
+    /// Returns the options to build an HTTP client with, sending its requests
+    /// through the given proxy.
+    pub fn http_client_options(&self, proxy: Option<String>) -> HttpClientOptions {
+        let retry_backoff = Duration::from_millis(self.retry_backoff_ms);
+        HttpClientOptions {
+            proxy,
+            use_system_proxy: self.use_system_proxy,
+            ca_certificates: self.ca_certificates.clone(),
+            retry_policy: RetryPolicy {
+                max_retries: self.max_retries,
+                initial_backoff: retry_backoff,
+                max_backoff: retry_backoff.max(MAX_HTTP_RETRY_BACKOFF),
+            },
+        }
+    }
+}
+
+impl Settings for HttpClientSettings {
+    const KEY: Option<&'static str> = Some("http_client");
+
+    type FileContent = HttpClientSettingsContent;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut AppContext) -> Result<Self> {
+        sources.json_merge()
+    }
+}
+
 pub fn init_settings(cx: &mut AppContext) {
     TelemetrySettings::register(cx);
     ClientSettings::register(cx);
     ProxySettings::register(cx);
+    HttpClientSettings::register(cx);
 }
 
 pub fn init(client: &Arc<Client>, cx: &mut AppContext) {
@@ -314,8 +405,17 @@ enum WeakSubscriber {
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Credentials {
-    DevServer { token: DevServerToken },
-    User { user_id: u64, access_token: String },
+    DevServer {
+        token: DevServerToken,
+    },
+    User {
+        user_id: u64,
+        access_token: String,
+    },
+    PersonalAccessToken {
+        user_id: u64,
+        token: PersonalAccessToken,
+    },
 }
 
 impl Credentials {
@@ -326,6 +426,17 @@ impl Credentials {
                 user_id,
                 access_token,
             } => format!("{} {}", user_id, access_token),
+            Credentials::PersonalAccessToken { token, .. } => {
+                format!("personal-access-token {}", token)
+            }
+        }
+    }
+
+    fn user_id(&self) -> Option<u64> {
+        match self {
+            Credentials::DevServer { .. } => None,
+            Credentials::User { user_id, .. }
+            | Credentials::PersonalAccessToken { user_id, .. } => Some(*user_id),
         }
     }
 }
@@ -532,9 +643,10 @@ impl Client {
 
     pub fn production(cx: &mut AppContext) -> Arc<Self> {
         let clock = Arc::new(clock::RealSystemClock);
-        let http = Arc::new(HttpClientWithUrl::new(
+        let http = Arc::new(HttpClientWithUrl::with_options(
             &ClientSettings::get_global(cx).server_url,
-            ProxySettings::get_global(cx).proxy.clone(),
+            HttpClientSettings::get_global(cx)
+                .http_client_options(ProxySettings::get_global(cx).proxy.clone()),
         ));
         Self::new(clock, http.clone(), cx)
     }
@@ -592,11 +704,11 @@ impl Client {
     }
 
     pub fn user_id(&self) -> Option<u64> {
-        if let Some(Credentials::User { user_id, .. }) = self.state.read().credentials.as_ref() {
-            Some(*user_id)
-        } else {
-            None
-        }
+        self.state
+            .read()
+            .credentials
+            .as_ref()
+            .and_then(Credentials::user_id)
     }
 
     pub fn peer_id(&self) -> Option<PeerId> {
@@ -849,6 +961,16 @@ impl Client {
         self
     }
 
+    /// Signs in with the given personal access token on the next connection attempt.
+    ///
+    /// Unlike the credentials obtained through the browser, personal access tokens are
+    /// never written to the keychain.
+    pub fn set_personal_access_token(&self, token: PersonalAccessToken) -> Result<&Self> {
+        let user_id = token.user_id()?;
+        self.state.write().credentials = Some(Credentials::PersonalAccessToken { user_id, token });
+        Ok(self)
+    }
+
     #[async_recursion(?Send)]
     pub async fn authenticate_and_connect(
         self: &Arc<Self>,
@@ -875,6 +997,20 @@ impl Client {
 
         let mut read_from_provider = false;
         let mut credentials = self.state.read().credentials.clone();
+        if credentials.is_none() {
+            if let Some(token) = ZED_ACCESS_TOKEN.clone() {
+                let token = PersonalAccessToken(token);
+                match token.user_id() {
+                    Ok(user_id) => {
+                        credentials = Some(Credentials::PersonalAccessToken { user_id, token })
+                    }
+                    Err(err) => {
+                        self.set_status(Status::ConnectionError, cx);
+                        return Err(err.context("invalid ZED_ACCESS_TOKEN"));
+                    }
+                }
+            }
+        }
         if credentials.is_none() && try_provider {
             credentials = self.credentials_provider.read_credentials(cx).await;
             read_from_provider = credentials.is_some();
@@ -899,8 +1035,8 @@ impl Client {
             }
         }
         let credentials = credentials.unwrap();
-        if let Credentials::User { user_id, .. } = &credentials {
-            self.set_id(*user_id);
+        if let Some(user_id) = credentials.user_id() {
+            self.set_id(user_id);
         }
 
         if was_disconnected {
@@ -1849,6 +1985,54 @@ mod tests {
         ));
     }
 
+    #[gpui::test]
+    async fn test_authenticating_with_personal_access_token(
+        cx: &mut TestAppContext,
+        executor: BackgroundExecutor,
+    ) {
+        init_test(cx);
+        let client = cx.update(|cx| {
+            Client::new(
+                Arc::new(FakeSystemClock::default()),
+                FakeHttpClient::with_404_response(),
+                cx,
+            )
+        });
+        client.override_authenticate(|_| unreachable!("browser authentication was attempted"));
+        let authorization_headers = Arc::new(Mutex::new(Vec::new()));
+        client.override_establish_connection({
+            let authorization_headers = authorization_headers.clone();
+            move |credentials, cx| {
+                authorization_headers
+                    .lock()
+                    .push(credentials.authorization_header());
+                cx.background_executor().spawn(async move {
+                    future::pending::<()>().await;
+                    unreachable!()
+                })
+            }
+        });
+
+        assert!(client
+            .set_personal_access_token(PersonalAccessToken("not-a-token".into()))
+            .is_err());
+        client
+            .set_personal_access_token(PersonalAccessToken("42.7.secret".into()))
+            .unwrap();
+        assert_eq!(client.user_id(), Some(42));
+
+        let _authenticate = cx.spawn({
+            let client = client.clone();
+            |cx| async move { client.authenticate_and_connect(false, &cx).await }
+        });
+        executor.run_until_parked();
+        assert_eq!(
+            *authorization_headers.lock(),
+            ["personal-access-token 42.7.secret"]
+        );
+        assert_eq!(client.id(), 42);
+    }
+
     #[gpui::test(iterations = 10)]
     async fn test_authenticating_more_than_once(
         cx: &mut TestAppContext,
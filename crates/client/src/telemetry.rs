@@ -277,6 +277,17 @@ impl Telemetry {
         Some(self.state.lock().log_file.as_ref()?.path().to_path_buf())
     }
 
+    /// Returns the events that have been reported but not sent yet, as JSON,
+    /// in the same format as the telemetry log.
+    pub fn queued_events(&self) -> Vec<String> {
+        self.state
+            .lock()
+            .events_queue
+            .iter()
+            .filter_map(|event| serde_json::to_string(event).log_err())
+            .collect()
+    }
+
     pub fn start(
         self: &Arc<Self>,
         installation_id: Option<String>,
@@ -719,6 +730,8 @@ mod tests {
                 })
             );
             assert_eq!(telemetry.state.lock().events_queue.len(), 3);
+            assert_eq!(telemetry.queued_events().len(), 3);
+            assert!(telemetry.queued_events()[2].contains(r#""operation":"test""#));
             assert!(telemetry.state.lock().flush_events_task.is_some());
             assert_eq!(
                 telemetry.state.lock().first_event_date_time,
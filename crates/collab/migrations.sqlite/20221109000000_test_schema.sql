@@ -20,7 +20,8 @@ CREATE TABLE "access_tokens" (
     "id" INTEGER PRIMARY KEY AUTOINCREMENT,
     "user_id" INTEGER REFERENCES users (id),
     "impersonated_user_id" INTEGER REFERENCES users (id),
-    "hash" VARCHAR(128)
+    "hash" VARCHAR(128),
+    "name" VARCHAR
 );
 CREATE INDEX "index_access_tokens_user_id" ON "access_tokens" ("user_id");
 
@@ -0,0 +1 @@
+ALTER TABLE access_tokens ADD COLUMN name VARCHAR;
@@ -26,6 +26,7 @@ use subtle::ConstantTimeEq;
 ///   <token> can be an access_token attached to that user, or an access token of an admin
 ///   or (in development) the string ADMIN:<config.api_token>.
 /// Authorization: "dev-server-token" <token>
+/// Authorization: "personal-access-token" <token>
 pub async fn validate_header<B>(mut req: Request<B>, next: Next<B>) -> impl IntoResponse {
     let mut auth_header = req
         .headers()
@@ -58,6 +59,26 @@ pub async fn validate_header<B>(mut req: Request<B>, next: Next<B>) -> impl Into
         return Ok::<_, Error>(next.run(req).await);
     }
 
+    if first == "personal-access-token" {
+        let personal_access_token = auth_header.next().ok_or_else(|| {
+            Error::Http(
+                StatusCode::BAD_REQUEST,
+                "missing personal-access-token token in authorization header".to_string(),
+            )
+        })?;
+        let user_id = verify_personal_access_token(personal_access_token, &state.db)
+            .await
+            .map_err(|e| Error::Http(StatusCode::UNAUTHORIZED, format!("{}", e)))?;
+        let user = state
+            .db
+            .get_user_by_id(user_id)
+            .await?
+            .ok_or_else(|| anyhow!("user {} not found", user_id))?;
+
+        req.extensions_mut().insert(Principal::User(user));
+        return Ok::<_, Error>(next.run(req).await);
+    }
+
     let user_id = UserId(first.parse().map_err(|_| {
         Error::Http(
             StatusCode::BAD_REQUEST,
@@ -266,6 +287,47 @@ pub fn split_dev_server_token(dev_server_token: &str) -> anyhow::Result<(DevServ
     Ok((id, token))
 }
 
+pub fn generate_personal_access_token(
+    user_id: UserId,
+    id: AccessTokenId,
+    access_token: String,
+) -> String {
+    format!("{}.{}.{}", user_id, id, access_token)
+}
+
+/// Checks that the given personal access token is valid, returning the user it belongs to.
+pub async fn verify_personal_access_token(
+    personal_access_token: &str,
+    db: &Arc<Database>,
+) -> anyhow::Result<UserId> {
+    let (user_id, id, token) = split_personal_access_token(personal_access_token)?;
+    let db_token = db.get_access_token(id).await?;
+    if db_token.user_id != user_id || db_token.name.is_none() {
+        return Err(anyhow!("no such personal access token"));
+    }
+
+    let token_hash = hash_access_token(token);
+    if db_token.hash.as_bytes().ct_eq(token_hash.as_ref()).into() {
+        Ok(user_id)
+    } else {
+        Err(anyhow!("wrong personal access token"))
+    }
+}
+
+// a personal access token has the format <user-id>.<id>.<base64>, the user id lets
+// the client know who it is signing in as before connecting.
+pub fn split_personal_access_token(
+    personal_access_token: &str,
+) -> anyhow::Result<(UserId, AccessTokenId, &str)> {
+    let mut parts = personal_access_token.splitn(3, '.');
+    let user_id = UserId(parts.next().unwrap_or_default().parse()?);
+    let id = AccessTokenId(parts.next().unwrap_or_default().parse()?);
+    let token = parts
+        .next()
+        .ok_or_else(|| anyhow!("invalid personal access token format"))?;
+    Ok((user_id, id, token))
+}
+
 #[cfg(test)]
 mod test {
     use rand::thread_rng;
@@ -366,6 +428,67 @@ mod test {
         ));
     }
 
+    #[gpui::test]
+    async fn test_verify_personal_access_token(cx: &mut gpui::TestAppContext) {
+        let test_db = crate::db::TestDb::sqlite(cx.executor().clone());
+        let db = test_db.db();
+
+        let user = db
+            .create_user(
+                "example@example.com",
+                false,
+                NewUserParams {
+                    github_login: "example".into(),
+                    github_user_id: 1,
+                },
+            )
+            .await
+            .unwrap();
+
+        let access_token = random_token();
+        let id = db
+            .create_personal_access_token(user.user_id, "ci", &hash_access_token(&access_token))
+            .await
+            .unwrap();
+        let token = generate_personal_access_token(user.user_id, id, access_token);
+        assert_eq!(
+            verify_personal_access_token(&token, &db).await.unwrap(),
+            user.user_id
+        );
+
+        // Signing in repeatedly does not evict personal access tokens.
+        for _ in 0..=MAX_ACCESS_TOKENS_TO_STORE {
+            create_access_token(&db, user.user_id, None).await.unwrap();
+        }
+        assert_eq!(
+            verify_personal_access_token(&token, &db).await.unwrap(),
+            user.user_id
+        );
+        assert_eq!(
+            db.get_personal_access_tokens(user.user_id)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|token| token.name)
+                .collect::<Vec<_>>(),
+            vec![Some("ci".to_string())]
+        );
+
+        assert!(verify_personal_access_token(&format!("{token}x"), &db)
+            .await
+            .is_err());
+        assert!(
+            verify_personal_access_token(&format!("{}.{id}.x", user.user_id.0 + 1), &db)
+                .await
+                .is_err()
+        );
+
+        db.delete_personal_access_token(id, user.user_id)
+            .await
+            .unwrap();
+        assert!(verify_personal_access_token(&token, &db).await.is_err());
+    }
+
     async fn create_previous_access_token(
         user_id: UserId,
         impersonated_user_id: Option<UserId>,
@@ -29,6 +29,7 @@ impl Database {
                             .column(access_token::Column::Id)
                             .from(access_token::Entity)
                             .and_where(access_token::Column::UserId.eq(user_id))
+                            .and_where(access_token::Column::Name.is_null())
                             .order_by(access_token::Column::Id, sea_orm::Order::Desc)
                             .limit(10000)
                             .offset(max_access_token_count as u64)
@@ -42,6 +43,68 @@ impl Database {
         .await
     }
 
+    /// Creates a new personal access token for the given user.
+    ///
+    /// Personal access tokens are never evicted when signing in again, they stay valid
+    /// until they get revoked via [`Database::delete_personal_access_token`].
+    pub async fn create_personal_access_token(
+        &self,
+        user_id: UserId,
+        name: &str,
+        access_token_hash: &str,
+    ) -> Result<AccessTokenId> {
+        self.transaction(|tx| async move {
+            let token = access_token::ActiveModel {
+                user_id: ActiveValue::set(user_id),
+                impersonated_user_id: ActiveValue::set(None),
+                hash: ActiveValue::set(access_token_hash.into()),
+                name: ActiveValue::set(Some(name.into())),
+                ..Default::default()
+            }
+            .insert(&*tx)
+            .await?;
+            Ok(token.id)
+        })
+        .await
+    }
+
+    /// Retrieves all the personal access tokens of the given user.
+    pub async fn get_personal_access_tokens(
+        &self,
+        user_id: UserId,
+    ) -> Result<Vec<access_token::Model>> {
+        self.transaction(|tx| async move {
+            Ok(access_token::Entity::find()
+                .filter(access_token::Column::UserId.eq(user_id))
+                .filter(access_token::Column::Name.is_not_null())
+                .order_by_asc(access_token::Column::Id)
+                .all(&*tx)
+                .await?)
+        })
+        .await
+    }
+
+    /// Revokes the personal access token with the given ID.
+    pub async fn delete_personal_access_token(
+        &self,
+        access_token_id: AccessTokenId,
+        user_id: UserId,
+    ) -> Result<()> {
+        self.transaction(|tx| async move {
+            let result = access_token::Entity::delete_many()
+                .filter(access_token::Column::Id.eq(access_token_id))
+                .filter(access_token::Column::UserId.eq(user_id))
+                .filter(access_token::Column::Name.is_not_null())
+                .exec(&*tx)
+                .await?;
+            if result.rows_affected == 0 {
+                return Err(anyhow!("no such personal access token"))?;
+            }
+            Ok(())
+        })
+        .await
+    }
+
     /// Retrieves the access token with the given ID.
     pub async fn get_access_token(
         &self,
@@ -9,6 +9,8 @@ pub struct Model {
     pub user_id: UserId,
     pub impersonated_user_id: Option<UserId>,
     pub hash: String,
+    /// The name given to a personal access token. Tokens created when signing in have no name.
+    pub name: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
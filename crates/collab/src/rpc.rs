@@ -3,11 +3,11 @@ mod connection_pool;
 use crate::{
     auth,
     db::{
-        self, dev_server, BufferId, Capability, Channel, ChannelId, ChannelRole, ChannelsForUser,
-        CreatedChannelMessage, Database, DevServerId, DevServerProjectId, InviteMemberResult,
-        MembershipUpdated, MessageId, NotificationId, PrincipalId, Project, ProjectId,
-        RejoinedProject, RemoveChannelMemberResult, ReplicaId, RespondToChannelInvite, RoomId,
-        ServerId, UpdatedChannelMessage, User, UserId,
+        self, dev_server, AccessTokenId, BufferId, Capability, Channel, ChannelId, ChannelRole,
+        ChannelsForUser, CreatedChannelMessage, Database, DevServerId, DevServerProjectId,
+        InviteMemberResult, MembershipUpdated, MessageId, NotificationId, PrincipalId, Project,
+        ProjectId, RejoinedProject, RemoveChannelMemberResult, ReplicaId, RespondToChannelInvite,
+        RoomId, ServerId, UpdatedChannelMessage, User, UserId,
     },
     executor::Executor,
     AppState, Error, RateLimit, RateLimiter, Result,
@@ -435,6 +435,9 @@ impl Server {
             .add_request_handler(user_handler(create_dev_server))
             .add_request_handler(user_handler(regenerate_dev_server_token))
             .add_request_handler(user_handler(rename_dev_server))
+            .add_request_handler(user_handler(create_personal_access_token))
+            .add_request_handler(user_handler(get_personal_access_tokens))
+            .add_request_handler(user_handler(revoke_personal_access_token))
             .add_request_handler(user_handler(delete_dev_server))
             .add_request_handler(dev_server_handler(share_dev_server_project))
             .add_request_handler(dev_server_handler(shutdown_dev_server))
@@ -2469,6 +2472,74 @@ async fn rename_dev_server(
     Ok(())
 }
 
+async fn create_personal_access_token(
+    request: proto::CreatePersonalAccessToken,
+    response: Response<proto::CreatePersonalAccessToken>,
+    session: UserSession,
+) -> Result<()> {
+    let name = request.name.trim();
+    if name.is_empty() {
+        return Err(proto::ErrorCode::Forbidden
+            .message("Personal access token name cannot be empty".to_string())
+            .anyhow())?;
+    }
+
+    let access_token = auth::random_token();
+    let hashed_access_token = auth::hash_access_token(&access_token);
+    let id = session
+        .db()
+        .await
+        .create_personal_access_token(session.user_id(), name, &hashed_access_token)
+        .await?;
+
+    response.send(proto::CreatePersonalAccessTokenResponse {
+        personal_access_token: Some(proto::PersonalAccessToken {
+            id: id.to_proto(),
+            name: name.to_string(),
+        }),
+        token: auth::generate_personal_access_token(session.user_id(), id, access_token),
+    })?;
+    Ok(())
+}
+
+async fn get_personal_access_tokens(
+    _: proto::GetPersonalAccessTokens,
+    response: Response<proto::GetPersonalAccessTokens>,
+    session: UserSession,
+) -> Result<()> {
+    let personal_access_tokens = session
+        .db()
+        .await
+        .get_personal_access_tokens(session.user_id())
+        .await?;
+
+    response.send(proto::GetPersonalAccessTokensResponse {
+        personal_access_tokens: personal_access_tokens
+            .into_iter()
+            .map(|token| proto::PersonalAccessToken {
+                id: token.id.to_proto(),
+                name: token.name.unwrap_or_default(),
+            })
+            .collect(),
+    })?;
+    Ok(())
+}
+
+async fn revoke_personal_access_token(
+    request: proto::RevokePersonalAccessToken,
+    response: Response<proto::RevokePersonalAccessToken>,
+    session: UserSession,
+) -> Result<()> {
+    session
+        .db()
+        .await
+        .delete_personal_access_token(AccessTokenId::from_proto(request.id), session.user_id())
+        .await?;
+
+    response.send(proto::Ack {})?;
+    Ok(())
+}
+
 async fn delete_dev_server(
     request: proto::DeleteDevServer,
     response: Response<proto::DeleteDevServer>,
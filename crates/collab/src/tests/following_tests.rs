@@ -2149,3 +2149,63 @@ async fn test_following_while_deactivated(cx_a: &mut TestAppContext, cx_b: &mut
         assert_eq!(editor.tab_description(0, cx).unwrap(), "2.js");
     });
 }
+
+#[gpui::test]
+async fn test_collaborator_presence(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext) {
+    let (_server, client_a, client_b, channel_id) = TestServer::start2(cx_a, cx_b).await;
+
+    let (workspace_a, cx_a) = client_a.build_test_workspace(cx_a).await;
+    client_a
+        .host_workspace(&workspace_a, channel_id, cx_a)
+        .await;
+    let (workspace_b, cx_b) = client_b.join_workspace(channel_id, cx_b).await;
+    let peer_id_b = client_b.peer_id().unwrap();
+    let project_a = workspace_a.update(cx_a, |workspace, _| workspace.project().clone());
+
+    let presence = |cx: &mut VisualTestContext| {
+        project_a.update(cx, |project, cx| {
+            let mut presence = project
+                .collaborators_by_path(cx)
+                .into_iter()
+                .map(|(path, collaborators)| {
+                    (
+                        path.path.to_string_lossy().to_string(),
+                        collaborators
+                            .into_iter()
+                            .map(|collaborator| collaborator.peer_id)
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            presence.sort();
+            presence
+        })
+    };
+    assert_eq!(presence(cx_a), vec![]);
+
+    // b opens 1.txt, which shows up for a
+    cx_b.simulate_keystrokes("cmd-p 1 enter");
+    cx_b.run_until_parked();
+    cx_a.run_until_parked();
+    assert_eq!(presence(cx_a), vec![("1.txt".to_string(), vec![peer_id_b])]);
+
+    // b switches to 2.js, moving away from 1.txt
+    cx_b.simulate_keystrokes("cmd-p 2 enter");
+    cx_b.run_until_parked();
+    cx_a.run_until_parked();
+    workspace_b.update(cx_b, |workspace, cx| {
+        let editor = workspace.active_item(cx).unwrap();
+        assert_eq!(editor.tab_description(0, cx).unwrap(), "2.js");
+    });
+    assert_eq!(presence(cx_a), vec![("2.js".to_string(), vec![peer_id_b])]);
+
+    // b leaves the call
+    cx_b.update(|cx| {
+        ActiveCall::global(cx)
+            .update(cx, |call, cx| call.hang_up(cx))
+            .detach_and_log_err(cx)
+    });
+    cx_b.run_until_parked();
+    cx_a.run_until_parked();
+    assert_eq!(presence(cx_a), vec![]);
+}
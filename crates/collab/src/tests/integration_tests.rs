@@ -4921,6 +4921,7 @@ async fn test_project_search(
             SearchResult::Buffer { buffer, ranges } => {
                 results.entry(buffer).or_insert(ranges);
             }
+            SearchResult::Progress { .. } => {}
             SearchResult::LimitReached => {
                 panic!("Unexpectedly reached search limit in tests. If you do want to assert limit-reached, change this panic call.")
             }
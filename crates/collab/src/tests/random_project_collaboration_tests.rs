@@ -1450,10 +1450,11 @@ fn generate_git_operation(rng: &mut StdRng, client: &TestClient) -> GitOperation
                 .map(|paths| {
                     (
                         paths,
-                        match rng.gen_range(0..3_u32) {
+                        match rng.gen_range(0..4_u32) {
                             0 => GitFileStatus::Added,
                             1 => GitFileStatus::Modified,
                             2 => GitFileStatus::Conflict,
+                            3 => GitFileStatus::Untracked,
                             _ => unreachable!(),
                         },
                     )
@@ -60,8 +60,15 @@ impl Render for CollabTitlebarItem {
         let workspace = self.workspace.upgrade();
 
         let platform_supported = cfg!(target_os = "macos");
+        let accent_color = workspace
+            .as_ref()
+            .and_then(|workspace| workspace.read(cx).accent_color());
 
         TitleBar::new("collab-titlebar", Box::new(workspace::CloseWindow))
+            .when_some(accent_color, |title_bar, accent_color| {
+                title_bar
+                    .background_color(cx.theme().colors().title_bar_background.blend(accent_color))
+            })
             // note: on windows titlebar behaviour is handled by the platform implementation
             .when(cfg!(not(windows)), |this| {
                 this.on_click(|event, cx| {
@@ -404,7 +411,7 @@ impl CollabTitlebarItem {
             PopoverMenu::new("application-menu")
                 .menu(move |cx| {
                     let width = width;
-                    ContextMenu::build(cx, move |menu, _cx| {
+                    ContextMenu::build(cx, move |menu, cx| {
                         let width = width;
                         menu.header("Workspace")
                             .action("Open Command Palette", Box::new(command_palette::Toggle))
@@ -534,7 +541,10 @@ impl CollabTitlebarItem {
                                     url: "https://zed.dev/docs".into(),
                                 }),
                             )
-                            .action("Give Feedback", Box::new(feedback::GiveFeedback))
+                            .action(
+                                i18n::t("feedback.give_feedback", cx),
+                                Box::new(feedback::GiveFeedback::default()),
+                            )
                             .action("Check for Updates", Box::new(auto_update::Check))
                             .action("View Telemetry", Box::new(zed_actions::OpenTelemetryLog))
                             .action(
@@ -7,7 +7,7 @@ use std::{
 use client::{parse_zed_link, telemetry::Telemetry};
 use collections::HashMap;
 use command_palette_hooks::{
-    CommandInterceptResult, CommandPaletteFilter, CommandPaletteInterceptor,
+    CommandInterceptResult, CommandPaletteFilter, CommandPaletteInterceptor, CommandPalettePreview,
 };
 use fuzzy::{StringMatch, StringMatchCandidate};
 use gpui::{
@@ -180,6 +180,7 @@ impl CommandPaletteDelegate {
     ) {
         self.updating_matches.take();
 
+        CommandPalettePreview::preview(&query, cx);
         let mut intercept_result = CommandPaletteInterceptor::try_global(cx)
             .and_then(|interceptor| interceptor.intercept(&query, cx));
 
@@ -344,6 +345,7 @@ impl PickerDelegate for CommandPaletteDelegate {
     }
 
     fn dismissed(&mut self, cx: &mut ViewContext<Picker<Self>>) {
+        CommandPalettePreview::preview("", cx);
         self.command_palette
             .update(cx, |_, cx| cx.emit(DismissEvent))
             .log_err();
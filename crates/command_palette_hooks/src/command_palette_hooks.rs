@@ -2,16 +2,17 @@
 
 #![deny(missing_docs)]
 
-use std::any::TypeId;
+use std::{any::TypeId, rc::Rc};
 
 use collections::HashSet;
 use derive_more::{Deref, DerefMut};
-use gpui::{Action, AppContext, BorrowAppContext, Global};
+use gpui::{Action, AppContext, BorrowAppContext, Global, WindowContext};
 
 /// Initializes the command palette hooks.
 pub fn init(cx: &mut AppContext) {
     cx.set_global(GlobalCommandPaletteFilter::default());
     cx.set_global(GlobalCommandPaletteInterceptor::default());
+    cx.set_global(GlobalCommandPalettePreview::default());
 }
 
 /// A filter for the command palette.
@@ -140,3 +141,47 @@ impl CommandPaletteInterceptor {
         self.0 = Some(handler);
     }
 }
+
+/// A hook notified whenever the query in the command palette changes, so that
+/// the effect of the command being typed can be previewed.
+///
+/// It is called with an empty query once the command palette closes.
+#[derive(Default)]
+pub struct CommandPalettePreview(Option<Rc<dyn Fn(&str, &mut WindowContext)>>);
+
+#[derive(Default)]
+struct GlobalCommandPalettePreview(CommandPalettePreview);
+
+impl Global for GlobalCommandPalettePreview {}
+
+impl CommandPalettePreview {
+    /// Updates the global [`CommandPalettePreview`] using the given closure.
+    pub fn update_global<F, R>(cx: &mut AppContext, update: F) -> R
+    where
+        F: FnOnce(&mut Self, &mut AppContext) -> R,
+    {
+        cx.update_global(|this: &mut GlobalCommandPalettePreview, cx| update(&mut this.0, cx))
+    }
+
+    /// Previews the given query from the command palette, if a preview handler is set.
+    pub fn preview(query: &str, cx: &mut WindowContext) {
+        let handler = cx
+            .try_global::<GlobalCommandPalettePreview>()
+            .and_then(|preview| preview.0 .0.clone());
+        if let Some(handler) = handler {
+            (handler)(query, cx)
+        }
+    }
+
+    /// Clears the global preview handler.
+    pub fn clear(&mut self) {
+        self.0 = None;
+    }
+
+    /// Sets the global preview handler.
+    ///
+    /// This will override the previous handler, if it exists.
+    pub fn set(&mut self, handler: Rc<dyn Fn(&str, &mut WindowContext)>) {
+        self.0 = Some(handler);
+    }
+}
@@ -171,4 +171,8 @@ impl StatusItemView for DiagnosticIndicator {
         }
         cx.notify();
     }
+
+    fn status_item_id() -> Option<&'static str> {
+        Some("diagnostics")
+    }
 }
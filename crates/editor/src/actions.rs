@@ -283,12 +283,14 @@ gpui::actions!(
         SelectUp,
         SelectPageDown,
         SelectPageUp,
+        SetMark,
         ShowCharacterPalette,
         ShowInlineCompletion,
         ShuffleLines,
         SortLinesCaseInsensitive,
         SortLinesCaseSensitive,
         SplitSelectionIntoLines,
+        SwapSelectionEnds,
         Tab,
         TabPrev,
         ToggleGitBlame,
@@ -392,6 +392,11 @@ impl DisplayMap {
             .update(cx, |map, cx| map.set_font_with_size(font, font_size, cx))
     }
 
+    pub fn set_letter_spacing(&self, letter_spacing: Pixels, cx: &mut ModelContext<Self>) -> bool {
+        self.wrap_map
+            .update(cx, |map, cx| map.set_letter_spacing(letter_spacing, cx))
+    }
+
     pub fn set_wrap_width(&self, width: Option<Pixels>, cx: &mut ModelContext<Self>) -> bool {
         self.wrap_map
             .update(cx, |map, cx| map.set_wrap_width(width, cx))
@@ -765,9 +770,14 @@ impl DisplaySnapshot {
         }
 
         let font_size = editor_style.text.font_size.to_pixels(*rem_size);
-        text_system
+        let layout = text_system
             .layout_line(&line, font_size, &runs)
-            .expect("we expect the font to be loaded because it's rendered by the editor")
+            .expect("we expect the font to be loaded because it's rendered by the editor");
+        if editor_style.letter_spacing == Pixels::ZERO {
+            layout
+        } else {
+            Arc::new(layout.with_letter_spacing(&line, editor_style.letter_spacing))
+        }
     }
 
     pub fn x_for_display_point(
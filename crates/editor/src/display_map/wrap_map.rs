@@ -26,6 +26,7 @@ pub struct WrapMap {
     wrap_width: Option<Pixels>,
     background_task: Option<Task<()>>,
     font_with_size: (Font, Pixels),
+    letter_spacing: Pixels,
 }
 
 #[derive(Clone)]
@@ -79,6 +80,7 @@ impl WrapMap {
         let handle = cx.new_model(|cx| {
             let mut this = Self {
                 font_with_size: (font, font_size),
+                letter_spacing: Pixels::ZERO,
                 wrap_width: None,
                 pending_edits: Default::default(),
                 interpolated_edits: Default::default(),
@@ -135,6 +137,20 @@ impl WrapMap {
         }
     }
 
+    pub fn set_letter_spacing(
+        &mut self,
+        letter_spacing: Pixels,
+        cx: &mut ModelContext<Self>,
+    ) -> bool {
+        if letter_spacing == self.letter_spacing {
+            false
+        } else {
+            self.letter_spacing = letter_spacing;
+            self.rewrap(cx);
+            true
+        }
+    }
+
     pub fn set_wrap_width(
         &mut self,
         wrap_width: Option<Pixels>,
@@ -159,8 +175,10 @@ impl WrapMap {
 
             let text_system = cx.text_system().clone();
             let (font, font_size) = self.font_with_size.clone();
+            let letter_spacing = self.letter_spacing;
             let task = cx.background_executor().spawn(async move {
                 let mut line_wrapper = text_system.line_wrapper(font, font_size);
+                line_wrapper.set_letter_spacing(letter_spacing);
                 let tab_snapshot = new_snapshot.tab_snapshot.clone();
                 let range = TabPoint::zero()..tab_snapshot.max_point();
                 let edits = new_snapshot
@@ -243,9 +261,11 @@ impl WrapMap {
                 let mut snapshot = self.snapshot.clone();
                 let text_system = cx.text_system().clone();
                 let (font, font_size) = self.font_with_size.clone();
+                let letter_spacing = self.letter_spacing;
                 let update_task = cx.background_executor().spawn(async move {
                     let mut edits = Patch::default();
                     let mut line_wrapper = text_system.line_wrapper(font, font_size);
+                    line_wrapper.set_letter_spacing(letter_spacing);
                     for (tab_snapshot, tab_edits) in pending_edits {
                         let wrap_edits = snapshot
                             .update(tab_snapshot, &tab_edits, wrap_width, &mut line_wrapper)
@@ -36,6 +36,7 @@ mod rust_analyzer_ext;
 pub mod scroll;
 mod selections_collection;
 pub mod tasks;
+pub mod todo_comments;
 
 #[cfg(test)]
 mod editor_tests;
@@ -54,7 +55,7 @@ use convert_case::{Case, Casing};
 use debounced_delay::DebouncedDelay;
 use display_map::*;
 pub use display_map::{DisplayPoint, FoldPlaceholder};
-pub use editor_settings::{CurrentLineHighlight, EditorSettings};
+pub use editor_settings::{CurrentLineHighlight, EditorSettings, TodoColor};
 use element::LineWithInvisibles;
 pub use element::{
     CursorLayout, EditorElement, HighlightedRange, HighlightedRangeLine, PointForPosition,
@@ -84,7 +85,7 @@ pub use items::MAX_TAB_TITLE_LEN;
 use itertools::Itertools;
 use language::{
     char_kind,
-    language_settings::{self, all_language_settings, InlayHintSettings},
+    language_settings::{self, all_language_settings, EmmetMode, InlayHintSettings},
     markdown, point_from_lsp, AutoindentMode, BracketPair, Buffer, Capability, CharKind, CodeLabel,
     CursorShape, Diagnostic, Documentation, IndentKind, IndentSize, Language, OffsetRangeExt,
     Point, Selection, SelectionGoal, TransactionId,
@@ -118,6 +119,7 @@ use serde::{Deserialize, Serialize};
 use settings::{update_settings_file, Settings, SettingsStore};
 use smallvec::SmallVec;
 use snippet::Snippet;
+use snippet_provider::SnippetProvider;
 use std::{
     any::TypeId,
     borrow::Cow,
@@ -126,7 +128,7 @@ use std::{
     mem,
     num::NonZeroU32,
     ops::{ControlFlow, Deref, DerefMut, Not as _, Range, RangeInclusive},
-    path::Path,
+    path::{Path, PathBuf},
     rc::Rc,
     sync::Arc,
     time::{Duration, Instant},
@@ -165,6 +167,8 @@ pub(crate) const CURSORS_VISIBLE_FOR: Duration = Duration::from_millis(2000);
 pub const CODE_ACTIONS_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(250);
 #[doc(hidden)]
 pub const DOCUMENT_HIGHLIGHTS_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(75);
+pub(crate) const FILE_POSITION_SERIALIZATION_DEBOUNCE_TIMEOUT: Duration =
+    Duration::from_millis(500);
 
 pub(crate) const FORMAT_TIMEOUT: Duration = Duration::from_secs(2);
 
@@ -358,6 +362,8 @@ pub struct EditorStyle {
     pub status: StatusColors,
     pub inlay_hints_style: HighlightStyle,
     pub suggestions_style: HighlightStyle,
+    /// The space added after each character of the buffer's text.
+    pub letter_spacing: Pixels,
 }
 
 impl Default for EditorStyle {
@@ -374,6 +380,7 @@ impl Default for EditorStyle {
             status: StatusColors::dark(),
             inlay_hints_style: HighlightStyle::default(),
             suggestions_style: HighlightStyle::default(),
+            letter_spacing: Pixels::ZERO,
         }
     }
 }
@@ -484,6 +491,7 @@ pub struct Editor {
     show_wrap_guides: Option<bool>,
     show_indent_guides: Option<bool>,
     placeholder_text: Option<Arc<str>>,
+    accessibility_value: Option<AccessibilityValue>,
     highlight_order: usize,
     highlighted_rows: HashMap<TypeId, Vec<RowHighlight>>,
     background_highlights: TreeMap<TypeId, BackgroundHighlight>,
@@ -500,9 +508,14 @@ pub struct Editor {
     available_code_actions: Option<(Location, Arc<[CodeAction]>)>,
     code_actions_task: Option<Task<()>>,
     document_highlights_task: Option<Task<()>>,
+    serialize_file_position_task: Option<Task<()>>,
     linked_editing_range_task: Option<Task<Option<()>>>,
+    todo_highlights: todo_comments::TodoHighlightState,
     linked_edit_ranges: linked_editing_ranges::LinkedEditingRanges,
     pending_rename: Option<RenameState>,
+    /// Whether the cursor movements extend the selections from a mark, as
+    /// with Emacs' `set-mark-command`.
+    selection_mark_mode: bool,
     searchable: bool,
     cursor_shape: CursorShape,
     current_line_highlight: Option<CurrentLineHighlight>,
@@ -513,6 +526,7 @@ pub struct Editor {
     input_enabled: bool,
     use_modal_editing: bool,
     read_only: bool,
+    tab_moves_focus: bool,
     leader_peer_id: Option<PeerId>,
     remote_id: Option<ViewId>,
     hover_state: HoverState,
@@ -640,6 +654,15 @@ struct HoveredCursor {
     selection_id: usize,
 }
 
+/// The text and newest selection that were last given to assistive
+/// technologies, which are kept until the buffer or the selection changes.
+struct AccessibilityValue {
+    edit_count: usize,
+    selection: Range<Anchor>,
+    text: SharedString,
+    selected_range: Range<usize>,
+}
+
 impl Default for SelectionHistoryMode {
     fn default() -> Self {
         Self::Normal
@@ -1781,6 +1804,7 @@ impl Editor {
             show_wrap_guides: None,
             show_indent_guides,
             placeholder_text: None,
+            accessibility_value: None,
             highlight_order: 0,
             highlighted_rows: HashMap::default(),
             background_highlights: Default::default(),
@@ -1798,8 +1822,11 @@ impl Editor {
             available_code_actions: Default::default(),
             code_actions_task: Default::default(),
             document_highlights_task: Default::default(),
+            serialize_file_position_task: None,
             linked_editing_range_task: Default::default(),
+            todo_highlights: Default::default(),
             pending_rename: Default::default(),
+            selection_mark_mode: false,
             searchable: true,
             cursor_shape: Default::default(),
             current_line_highlight: None,
@@ -1810,6 +1837,7 @@ impl Editor {
             input_enabled: true,
             use_modal_editing: mode == EditorMode::Full,
             read_only: false,
+            tab_moves_focus: false,
             use_autoclose: true,
             auto_replace_emoji_shortcode: false,
             leader_peer_id: None,
@@ -1867,6 +1895,7 @@ impl Editor {
             breadcrumb_header: None,
         };
         this.tasks_update_task = Some(this.refresh_runnables(cx));
+        todo_comments::refresh_todo_highlights(&mut this, cx);
         this._subscriptions.extend(project_subscriptions);
 
         this.end_selection(cx);
@@ -1904,6 +1933,9 @@ impl Editor {
         if self.pending_rename.is_some() {
             key_context.add("renaming");
         }
+        if self.selection_mark_mode {
+            key_context.add("selection_mark");
+        }
         if self.context_menu_visible() {
             match self.context_menu.read().as_ref() {
                 Some(ContextMenu::Completions(_)) => {
@@ -2119,6 +2151,31 @@ impl Editor {
         }
     }
 
+    /// Returns the text, and the range of the newest selection in UTF-16 code
+    /// units, to give to assistive technologies. They're only computed again
+    /// once the buffer or the newest selection changes.
+    pub(crate) fn accessibility_value(&mut self, cx: &AppContext) -> (SharedString, Range<usize>) {
+        let edit_count = self.buffer.read(cx).read(cx).edit_count();
+        let newest_selection = self.selections.newest_anchor();
+        let selection = newest_selection.start..newest_selection.end;
+        if let Some(value) = &self.accessibility_value {
+            if value.edit_count == edit_count && value.selection == selection {
+                return (value.text.clone(), value.selected_range.clone());
+            }
+        }
+
+        let text = SharedString::from(self.buffer.read(cx).read(cx).text());
+        let selected_range = self.selections.newest::<OffsetUtf16>(cx).range();
+        let selected_range = selected_range.start.0..selected_range.end.0;
+        self.accessibility_value = Some(AccessibilityValue {
+            edit_count,
+            selection,
+            text: text.clone(),
+            selected_range: selected_range.clone(),
+        });
+        (text, selected_range)
+    }
+
     pub fn set_cursor_shape(&mut self, cursor_shape: CursorShape, cx: &mut ViewContext<Self>) {
         self.cursor_shape = cursor_shape;
         cx.notify();
@@ -2184,6 +2241,12 @@ impl Editor {
         self.read_only = read_only;
     }
 
+    /// Makes `tab` and `shift-tab` move the focus out of the editor, like to
+    /// the next input of a modal, instead of indenting.
+    pub fn set_tab_moves_focus(&mut self, tab_moves_focus: bool) {
+        self.tab_moves_focus = tab_moves_focus;
+    }
+
     pub fn set_use_autoclose(&mut self, autoclose: bool) {
         self.use_autoclose = autoclose;
     }
@@ -2335,6 +2398,7 @@ impl Editor {
             if self.git_blame_inline_enabled {
                 self.start_inline_blame_timer(cx);
             }
+            self.schedule_file_position_serialization(cx);
         }
 
         self.blink_manager.update(cx, BlinkManager::pause_blinking);
@@ -2772,6 +2836,11 @@ impl Editor {
             return;
         }
 
+        if self.selection_mark_mode {
+            self.selection_mark_mode = false;
+            cx.notify();
+        }
+
         if self.mode == EditorMode::Full {
             if self.change_selections(Some(Autoscroll::fit()), cx, |s| s.try_cancel()) {
                 return;
@@ -5292,6 +5361,10 @@ impl Editor {
     }
 
     pub fn tab_prev(&mut self, _: &TabPrev, cx: &mut ViewContext<Self>) {
+        if self.tab_moves_focus {
+            cx.propagate();
+            return;
+        }
         if self.move_to_prev_snippet_tabstop(cx) {
             return;
         }
@@ -5300,9 +5373,16 @@ impl Editor {
     }
 
     pub fn tab(&mut self, _: &Tab, cx: &mut ViewContext<Self>) {
+        if self.tab_moves_focus {
+            cx.propagate();
+            return;
+        }
         if self.move_to_next_snippet_tabstop(cx) || self.read_only(cx) {
             return;
         }
+        if self.expand_emmet_abbreviation(cx) {
+            return;
+        }
 
         let mut selections = self.selections.all_adjusted(cx);
         let buffer = self.buffer.read(cx);
@@ -5378,6 +5458,76 @@ impl Editor {
         });
     }
 
+    /// Replaces the Emmet abbreviation before each cursor with its expansion,
+    /// if the language enables Emmet and every cursor follows the same one.
+    fn expand_emmet_abbreviation(&mut self, cx: &mut ViewContext<Self>) -> bool {
+        let selections = self.selections.all::<Point>(cx);
+        let buffer = self.buffer.read(cx);
+        let snapshot = buffer.snapshot(cx);
+
+        let mut snippet_source = None;
+        let mut insertion_ranges = Vec::new();
+        for selection in &selections {
+            if !selection.is_empty() {
+                return false;
+            }
+            let cursor = selection.head();
+            let settings = buffer.settings_at(cursor, cx);
+            let syntax = match settings.emmet {
+                EmmetMode::Off => return false,
+                EmmetMode::Html => emmet::Syntax::Html,
+                EmmetMode::Jsx => emmet::Syntax::Jsx,
+                EmmetMode::Css => emmet::Syntax::Css,
+            };
+            // Outside of JSX elements, JavaScript like `this.state` or `x*2`
+            // would be mistaken for abbreviations.
+            if syntax == emmet::Syntax::Jsx
+                && !snapshot
+                    .language_scope_at(cursor)
+                    .map_or(false, |scope| scope.override_name() == Some("element"))
+            {
+                return false;
+            }
+            let indent = if settings.hard_tabs {
+                "\t".to_string()
+            } else {
+                " ".repeat(settings.tab_size.get() as usize)
+            };
+
+            let line_prefix = snapshot
+                .text_for_range(Point::new(cursor.row, 0)..cursor)
+                .collect::<String>();
+            let Some(abbreviation) = emmet::extract_abbreviation(&line_prefix, syntax) else {
+                return false;
+            };
+            let base_indent_len = line_prefix.len() - line_prefix.trim_start().len();
+            let Some(source) = emmet::expand(
+                abbreviation,
+                syntax,
+                &indent,
+                &line_prefix[..base_indent_len],
+            ) else {
+                return false;
+            };
+            if *snippet_source.get_or_insert_with(|| source.clone()) != source {
+                return false;
+            }
+
+            let end = snapshot.point_to_offset(cursor);
+            insertion_ranges.push(end - abbreviation.len()..end);
+        }
+
+        let Some(snippet) = snippet_source.and_then(|source| Snippet::parse(&source).log_err())
+        else {
+            return false;
+        };
+        self.transact(cx, |this, cx| {
+            this.insert_snippet(&insertion_ranges, snippet, cx)
+                .log_err();
+        });
+        true
+    }
+
     pub fn indent(&mut self, _: &Indent, cx: &mut ViewContext<Self>) {
         if self.read_only(cx) {
             return;
@@ -6387,6 +6537,7 @@ impl Editor {
     }
 
     pub fn copy(&mut self, _: &Copy, cx: &mut ViewContext<Self>) {
+        self.selection_mark_mode = false;
         let selections = self.selections.all::<Point>(cx);
         let buffer = self.buffer.read(cx).read(cx);
         let mut text = String::new();
@@ -7313,6 +7464,20 @@ impl Editor {
         self.nav_history.as_ref()
     }
 
+    /// Records a jump from `origin` to the current cursor position in the navigation history,
+    /// unless the selection change already recorded it because the cursor moved far enough.
+    pub fn push_jump_to_nav_history(&mut self, origin: Anchor, cx: &mut ViewContext<Self>) {
+        let buffer = self.buffer.read(cx).read(cx);
+        let origin_position = origin.to_point(&buffer);
+        let new_position = self.selections.newest_anchor().head().to_point(&buffer);
+        drop(buffer);
+
+        let row_delta = (new_position.row as i64 - origin_position.row as i64).abs();
+        if origin_position != new_position && row_delta < MIN_NAVIGATION_HISTORY_ROW_DELTA {
+            self.push_to_nav_history(origin, None, cx);
+        }
+    }
+
     fn push_to_nav_history(
         &mut self,
         cursor_anchor: Anchor,
@@ -7354,6 +7519,30 @@ impl Editor {
         });
     }
 
+    /// Sets the mark at each cursor, so that subsequent movements extend the
+    /// selections until the mark is deactivated by editing, copying or
+    /// cancelling.
+    pub fn set_mark(&mut self, _: &SetMark, cx: &mut ViewContext<Self>) {
+        self.change_selections(None, cx, |s| {
+            s.move_with(|_, selection| {
+                selection.collapse_to(selection.head(), SelectionGoal::None)
+            });
+        });
+        self.selection_mark_mode = true;
+        cx.notify();
+    }
+
+    /// Moves each cursor to the other end of its selection.
+    pub fn swap_selection_ends(&mut self, _: &SwapSelectionEnds, cx: &mut ViewContext<Self>) {
+        self.change_selections(Some(Autoscroll::fit()), cx, |s| {
+            s.move_with(|_, selection| {
+                if !selection.is_empty() {
+                    selection.reversed = !selection.reversed;
+                }
+            });
+        });
+    }
+
     pub fn select_all(&mut self, _: &SelectAll, cx: &mut ViewContext<Self>) {
         let end = self.buffer.read(cx).read(cx).len();
         self.change_selections(None, cx, |s| {
@@ -9344,6 +9533,7 @@ impl Editor {
                                                     color: Some(cx.theme().status().predictive),
                                                     ..HighlightStyle::default()
                                                 },
+                                                letter_spacing: cx.editor_style.letter_spacing,
                                             },
                                         ))
                                         .into_any_element()
@@ -10061,7 +10251,8 @@ impl Editor {
                 style.text.font(),
                 style.text.font_size.to_pixels(rem_size),
                 cx,
-            )
+            );
+            map.set_letter_spacing(style.letter_spacing, cx);
         });
         self.style = Some(style);
     }
@@ -10903,6 +11094,7 @@ impl Editor {
             multi_buffer::Event::Edited {
                 singleton_buffer_edited,
             } => {
+                self.selection_mark_mode = false;
                 self.scrollbar_marker_state.dirty = true;
                 self.active_indent_guides_state.dirty = true;
                 self.refresh_active_diagnostics(cx);
@@ -10958,6 +11150,7 @@ impl Editor {
                     excerpts: excerpts.clone(),
                 });
                 self.refresh_inlay_hints(InlayHintRefreshReason::NewLinesShown, cx);
+                todo_comments::refresh_edited_todo_highlights(self, cx);
             }
             multi_buffer::Event::ExcerptsRemoved { ids } => {
                 self.refresh_inlay_hints(InlayHintRefreshReason::ExcerptsRemoved(ids.clone()), cx);
@@ -10967,15 +11160,18 @@ impl Editor {
                 cx.emit(EditorEvent::ExcerptsEdited { ids: ids.clone() })
             }
             multi_buffer::Event::ExcerptsExpanded { ids } => {
+                todo_comments::refresh_excerpt_todo_highlights(self, ids, cx);
                 cx.emit(EditorEvent::ExcerptsExpanded { ids: ids.clone() })
             }
             multi_buffer::Event::Reparsed(buffer_id) => {
                 self.tasks_update_task = Some(self.refresh_runnables(cx));
+                todo_comments::refresh_edited_todo_highlights(self, cx);
 
                 cx.emit(EditorEvent::Reparsed(*buffer_id));
             }
             multi_buffer::Event::LanguageChanged(buffer_id) => {
                 linked_editing_ranges::refresh_linked_ranges(self, cx);
+                todo_comments::refresh_todo_highlights(self, cx);
                 cx.emit(EditorEvent::Reparsed(*buffer_id));
                 cx.notify();
             }
@@ -11020,6 +11216,7 @@ impl Editor {
         let editor_settings = EditorSettings::get_global(cx);
         self.scroll_manager.vertical_scroll_margin = editor_settings.vertical_scroll_margin;
         self.show_breadcrumbs = editor_settings.toolbar.breadcrumbs;
+        todo_comments::refresh_todo_highlights(self, cx);
 
         if self.mode == EditorMode::Full {
             let inline_blame_enabled = ProjectSettings::get_global(cx).git.inline_blame_enabled();
@@ -11579,6 +11776,260 @@ pub trait CompletionProvider {
     ) -> bool;
 }
 
+/// The server id of completions for the user's snippets, which don't come from a language server.
+const SNIPPET_COMPLETIONS_SERVER_ID: LanguageServerId = LanguageServerId(usize::MAX);
+
+/// Returns completions for the user's snippets whose prefix starts with the text before the cursor.
+fn snippet_completions(
+    buffer: &Model<Buffer>,
+    buffer_position: text::Anchor,
+    cx: &mut ViewContext<Editor>,
+) -> Vec<Completion> {
+    let Some(provider) = SnippetProvider::try_global(cx) else {
+        return Vec::new();
+    };
+    let buffer = buffer.read(cx);
+    let snapshot = buffer.snapshot();
+    let language = snapshot.language_at(buffer_position).cloned();
+    let language_id = language
+        .as_ref()
+        .map(|language| snippet_provider::vs_code_language_id(&language.name()));
+    let snippets = provider.read(cx).snippets_for(language_id.as_deref());
+    if snippets.is_empty() {
+        return Vec::new();
+    }
+
+    let position = text::ToOffset::to_offset(&buffer_position, &snapshot);
+    let typed_text = snapshot
+        .reversed_chars_at(position)
+        .take_while(|c| !c.is_whitespace())
+        .collect::<String>()
+        .chars()
+        .rev()
+        .collect::<String>();
+    if typed_text.is_empty() {
+        return Vec::new();
+    }
+
+    let point = text::ToPoint::to_point(&buffer_position, &snapshot);
+    let line = snapshot
+        .text_for_range(
+            Point::new(point.row, 0)..Point::new(point.row, snapshot.line_len(point.row)),
+        )
+        .collect::<String>();
+    let file = buffer.file();
+    let file_path = file.map(|file| match file.as_local() {
+        Some(file) => file.abs_path(cx),
+        None => file.full_path(cx),
+    });
+    let clipboard = cx.read_from_clipboard().map(|item| item.text().clone());
+    let now = time::OffsetDateTime::now_utc().to_offset(cx.local_timezone());
+    let resolve_variable = |name: &str| -> Option<String> {
+        let scope = language.as_ref().map(|language| language.default_scope());
+        Some(match name {
+            "TM_SELECTED_TEXT" => String::new(),
+            "TM_CURRENT_LINE" => line.clone(),
+            "TM_LINE_INDEX" => point.row.to_string(),
+            "TM_LINE_NUMBER" => (point.row + 1).to_string(),
+            "TM_FILENAME" => file_path
+                .as_ref()?
+                .file_name()?
+                .to_string_lossy()
+                .into_owned(),
+            "TM_FILENAME_BASE" => file_path
+                .as_ref()?
+                .file_stem()?
+                .to_string_lossy()
+                .into_owned(),
+            "TM_DIRECTORY" => file_path.as_ref()?.parent()?.to_string_lossy().into_owned(),
+            "TM_FILEPATH" => file_path.as_ref()?.to_string_lossy().into_owned(),
+            "RELATIVE_FILEPATH" => file?.path().to_string_lossy().into_owned(),
+            "CLIPBOARD" => clipboard.clone()?,
+            "CURRENT_YEAR" => now.year().to_string(),
+            "CURRENT_YEAR_SHORT" => format!("{:02}", now.year() % 100),
+            "CURRENT_MONTH" => format!("{:02}", u8::from(now.month())),
+            "CURRENT_MONTH_NAME" => now.month().to_string(),
+            "CURRENT_MONTH_NAME_SHORT" => now.month().to_string()[..3].to_string(),
+            "CURRENT_DATE" => format!("{:02}", now.day()),
+            "CURRENT_DAY_NAME" => now.weekday().to_string(),
+            "CURRENT_DAY_NAME_SHORT" => now.weekday().to_string()[..3].to_string(),
+            "CURRENT_HOUR" => format!("{:02}", now.hour()),
+            "CURRENT_MINUTE" => format!("{:02}", now.minute()),
+            "CURRENT_SECOND" => format!("{:02}", now.second()),
+            "CURRENT_SECONDS_UNIX" => now.unix_timestamp().to_string(),
+            "LINE_COMMENT" => scope?
+                .line_comment_prefixes()
+                .first()?
+                .trim_end()
+                .to_string(),
+            "BLOCK_COMMENT_START" => scope?.block_comment_delimiters()?.0.trim_end().to_string(),
+            "BLOCK_COMMENT_END" => scope?
+                .block_comment_delimiters()?
+                .1
+                .trim_start()
+                .to_string(),
+            _ => return None,
+        })
+    };
+
+    let mut completions = Vec::new();
+    for snippet in snippets {
+        let matched_prefix = snippet.prefixes.iter().find_map(|prefix| {
+            let lowercase_prefix = prefix.to_lowercase();
+            // Match the longest typed suffix, so that `(pri` offers a `print` snippet.
+            typed_text
+                .char_indices()
+                .map(|(ix, _)| &typed_text[ix..])
+                .find(|suffix| lowercase_prefix.starts_with(&suffix.to_lowercase()))
+                .map(|suffix| (prefix, suffix))
+        });
+        let Some((prefix, typed_suffix)) = matched_prefix else {
+            continue;
+        };
+
+        let start = snapshot.anchor_before(position - typed_suffix.len());
+        let documentation = match &snippet.description {
+            Some(description) => format!("{description}\n\n{}", snippet.body),
+            None => snippet.body.clone(),
+        };
+        completions.push(Completion {
+            old_range: start..buffer_position,
+            new_text: snippet::expand_variables(&snippet.body, &resolve_variable),
+            label: CodeLabel::plain(prefix.clone(), None),
+            server_id: SNIPPET_COMPLETIONS_SERVER_ID,
+            documentation: Some(Documentation::MultiLinePlainText(documentation)),
+            lsp_completion: lsp::CompletionItem {
+                label: prefix.clone(),
+                kind: Some(lsp::CompletionItemKind::SNIPPET),
+                insert_text_format: Some(lsp::InsertTextFormat::SNIPPET),
+                ..Default::default()
+            },
+            confirm: None,
+            show_new_completions_on_confirm: false,
+        });
+    }
+    completions
+}
+
+/// The server id of completions for file paths, which don't come from a language server.
+const PATH_COMPLETIONS_SERVER_ID: LanguageServerId = LanguageServerId(usize::MAX - 1);
+
+/// Returns completions for the files and directories of the path before the cursor, when
+/// the cursor is inside a string literal. Paths are relative to the buffer's directory
+/// and, unless they start with `./` or `../`, to the roots of the project's worktrees.
+fn path_completions(
+    project: &Model<Project>,
+    buffer: &Model<Buffer>,
+    buffer_position: text::Anchor,
+    cx: &mut ViewContext<Editor>,
+) -> Vec<Completion> {
+    let buffer = buffer.read(cx);
+    let snapshot = buffer.snapshot();
+    let position = text::ToOffset::to_offset(&buffer_position, &snapshot);
+    let in_string = snapshot
+        .language_scope_at(position)
+        .map_or(false, |scope| scope.override_name() == Some("string"));
+    if !in_string {
+        return Vec::new();
+    }
+
+    let is_path_char = |c: char| !c.is_whitespace() && !"\"'`<>()[]{},;:=".contains(c);
+    let typed_text = snapshot
+        .reversed_chars_at(position)
+        .take_while(|c| is_path_char(*c))
+        .collect::<String>()
+        .chars()
+        .rev()
+        .collect::<String>();
+    let (typed_dir, typed_name) = match typed_text.rfind('/') {
+        Some(ix) => typed_text.split_at(ix + 1),
+        None => {
+            // Without a slash, only offer paths for text that starts the string,
+            // rather than for every word typed into one.
+            let starts_string = snapshot
+                .reversed_chars_at(position - typed_text.len())
+                .next()
+                .map_or(false, |c| "\"'`".contains(c));
+            if typed_text.is_empty() || !starts_string {
+                return Vec::new();
+            }
+            ("", typed_text.as_str())
+        }
+    };
+
+    let mut base_dirs = Vec::new();
+    if let Some(file) = project::File::from_dyn(buffer.file()) {
+        if !typed_dir.starts_with('/') {
+            let dir = file.path.parent().unwrap_or(Path::new("")).to_path_buf();
+            base_dirs.push((file.worktree.clone(), dir));
+        }
+    }
+    if !typed_dir.starts_with("./") && !typed_dir.starts_with("../") {
+        base_dirs.extend(
+            project
+                .read(cx)
+                .visible_worktrees(cx)
+                .map(|worktree| (worktree, PathBuf::new())),
+        );
+    }
+
+    let mut entries = BTreeMap::new();
+    for (worktree, base_dir) in base_dirs {
+        // Resolve the typed directory within the worktree, skipping it if it leaves the worktree.
+        let mut dir = base_dir;
+        let resolved = Path::new(typed_dir).components().all(|component| {
+            match component {
+                std::path::Component::ParentDir => return dir.pop(),
+                std::path::Component::Normal(name) => dir.push(name),
+                _ => {}
+            }
+            true
+        });
+        if !resolved {
+            continue;
+        }
+
+        let worktree = worktree.read(cx);
+        for entry in worktree.child_entries(&dir) {
+            if let Some(name) = entry.path.file_name() {
+                let full_path = Path::new(worktree.root_name()).join(&entry.path);
+                entries
+                    .entry(name.to_string_lossy().into_owned())
+                    .or_insert((entry.is_dir(), full_path));
+            }
+        }
+    }
+
+    let start = snapshot.anchor_before(position - typed_name.len());
+    entries
+        .into_iter()
+        .map(|(name, (is_dir, full_path))| {
+            let new_text = if is_dir { format!("{name}/") } else { name };
+            Completion {
+                old_range: start..buffer_position,
+                label: CodeLabel::plain(new_text.clone(), None),
+                server_id: PATH_COMPLETIONS_SERVER_ID,
+                documentation: Some(Documentation::SingleLine(
+                    full_path.to_string_lossy().into_owned(),
+                )),
+                lsp_completion: lsp::CompletionItem {
+                    label: new_text.clone(),
+                    kind: Some(if is_dir {
+                        lsp::CompletionItemKind::FOLDER
+                    } else {
+                        lsp::CompletionItemKind::FILE
+                    }),
+                    ..Default::default()
+                },
+                new_text,
+                confirm: None,
+                // Keep completing the path inside a directory.
+                show_new_completions_on_confirm: is_dir,
+            }
+        })
+        .collect()
+}
+
 impl CompletionProvider for Model<Project> {
     fn completions(
         &self,
@@ -11587,8 +12038,16 @@ impl CompletionProvider for Model<Project> {
         options: CompletionContext,
         cx: &mut ViewContext<Editor>,
     ) -> Task<Result<Vec<Completion>>> {
-        self.update(cx, |project, cx| {
+        let snippets = snippet_completions(buffer, buffer_position, cx);
+        let paths = path_completions(self, buffer, buffer_position, cx);
+        let project_completions = self.update(cx, |project, cx| {
             project.completions(&buffer, buffer_position, options, cx)
+        });
+        cx.background_executor().spawn(async move {
+            let mut completions = project_completions.await?;
+            completions.extend(snippets);
+            completions.extend(paths);
+            Ok(completions)
         })
     }
 
@@ -11611,6 +12070,11 @@ impl CompletionProvider for Model<Project> {
         push_to_history: bool,
         cx: &mut ViewContext<Editor>,
     ) -> Task<Result<Option<language::Transaction>>> {
+        if completion.server_id == SNIPPET_COMPLETIONS_SERVER_ID
+            || completion.server_id == PATH_COMPLETIONS_SERVER_ID
+        {
+            return Task::ready(Ok(None));
+        }
         self.update(cx, |project, cx| {
             project.apply_additional_edits_for_completion(buffer, completion, push_to_history, cx)
         })
@@ -11643,6 +12107,9 @@ impl CompletionProvider for Model<Project> {
         if trigger_in_words && char_kind(&scope, char) == CharKind::Word {
             return true;
         }
+        if char == '/' && scope.as_ref().and_then(|scope| scope.override_name()) == Some("string") {
+            return true;
+        }
 
         buffer
             .completion_triggers()
@@ -11961,7 +12428,17 @@ impl Render for Editor {
             EditorMode::Full => TextStyle {
                 color: cx.theme().colors().editor_foreground,
                 font_family: settings.buffer_font.family.clone(),
-                font_features: settings.buffer_font.features.clone(),
+                font_features: self
+                    .buffer
+                    .read(cx)
+                    .as_singleton()
+                    .and_then(|buffer| {
+                        let buffer = buffer.read(cx);
+                        language_settings::language_settings(buffer.language(), buffer.file(), cx)
+                            .buffer_font_features
+                            .clone()
+                    })
+                    .unwrap_or_else(|| settings.buffer_font.features.clone()),
                 font_size: settings.buffer_font_size(cx).into(),
                 font_weight: settings.buffer_font.weight,
                 font_style: FontStyle::Normal,
@@ -11979,6 +12456,11 @@ impl Render for Editor {
             EditorMode::Full => cx.theme().colors().editor_background,
         };
 
+        let letter_spacing = match self.mode {
+            EditorMode::SingleLine | EditorMode::AutoHeight { .. } => Pixels::ZERO,
+            EditorMode::Full => settings.buffer_letter_spacing(cx),
+        };
+
         EditorElement::new(
             cx.view(),
             EditorStyle {
@@ -11996,6 +12478,7 @@ impl Render for Editor {
                     color: Some(cx.theme().status().predictive),
                     ..HighlightStyle::default()
                 },
+                letter_spacing,
             },
         )
     }
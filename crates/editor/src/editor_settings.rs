@@ -1,3 +1,4 @@
+use collections::BTreeMap;
 use gpui::AppContext;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -17,13 +18,14 @@ pub struct EditorSettings {
     pub gutter: Gutter,
     pub vertical_scroll_margin: f32,
     pub scroll_sensitivity: f32,
-    pub relative_line_numbers: bool,
+    pub relative_line_numbers: RelativeLineNumbers,
     pub seed_search_query_from_cursor: SeedQuerySetting,
     pub multi_cursor_modifier: MultiCursorModifier,
     pub redact_private_values: bool,
     pub expand_excerpt_lines: u32,
     #[serde(default)]
     pub double_click_in_multibuffer: DoubleClickInMultibuffer,
+    pub todo_highlights: TodoHighlights,
 }
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
@@ -39,6 +41,36 @@ pub enum CurrentLineHighlight {
     All,
 }
 
+/// Whether the line numbers in the gutter are shown relative to the cursor.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(untagged)]
+pub enum RelativeLineNumbers {
+    /// `true` is the same as `"hybrid"`, `false` the same as `"absolute"`.
+    Enabled(bool),
+    Mode(RelativeLineNumbersMode),
+}
+
+impl RelativeLineNumbers {
+    pub fn mode(&self) -> RelativeLineNumbersMode {
+        match self {
+            RelativeLineNumbers::Enabled(true) => RelativeLineNumbersMode::Hybrid,
+            RelativeLineNumbers::Enabled(false) => RelativeLineNumbersMode::Absolute,
+            RelativeLineNumbers::Mode(mode) => *mode,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RelativeLineNumbersMode {
+    /// Show the absolute line number on every line.
+    Absolute,
+    /// Show the distance to the cursor on every line, and 0 on the cursor line.
+    Relative,
+    /// Show the distance to the cursor on every line, and the absolute line number on the cursor line.
+    Hybrid,
+}
+
 /// When to populate a new search's query based on the text under the cursor.
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -87,6 +119,23 @@ pub struct Gutter {
     pub folds: bool,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct TodoHighlights {
+    pub enabled: bool,
+    pub keywords: BTreeMap<String, TodoColor>,
+}
+
+/// The theme color that a keyword in comments is highlighted with.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoColor {
+    Info,
+    Hint,
+    Warning,
+    Error,
+    Success,
+}
+
 /// When to show the scrollbar in the editor.
 ///
 /// Default: auto
@@ -167,9 +216,10 @@ pub struct EditorSettingsContent {
     /// Default: 1.0
     pub scroll_sensitivity: Option<f32>,
     /// Whether the line numbers on editors gutter are relative or not.
+    /// Either `true`, `false`, or one of `"absolute"`, `"relative"` and `"hybrid"`.
     ///
     /// Default: false
-    pub relative_line_numbers: Option<bool>,
+    pub relative_line_numbers: Option<RelativeLineNumbers>,
     /// When to populate a new search's query based on the text under the cursor.
     ///
     /// Default: always
@@ -195,6 +245,9 @@ pub struct EditorSettingsContent {
     ///
     /// Default: select
     pub double_click_in_multibuffer: Option<DoubleClickInMultibuffer>,
+
+    /// Keywords to highlight inside comments, like TODO and FIXME.
+    pub todo_highlights: Option<TodoHighlightsContent>,
 }
 
 // Toolbar related settings
@@ -244,6 +297,21 @@ pub struct ScrollbarContent {
     pub cursors: Option<bool>,
 }
 
+/// Settings for keywords highlighted inside comments
+#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct TodoHighlightsContent {
+    /// Whether to highlight keywords inside comments.
+    ///
+    /// Default: true
+    pub enabled: Option<bool>,
+    /// The keywords to highlight, and the theme color to highlight each one with:
+    /// "info", "hint", "warning", "error" or "success".
+    /// Keywords are matched as whole, case-sensitive words.
+    ///
+    /// Default: {"TODO": "info", "FIXME": "error", "HACK": "warning", "XXX": "warning"}
+    pub keywords: Option<BTreeMap<String, TodoColor>>,
+}
+
 /// Gutter related settings
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct GutterContent {
@@ -2205,6 +2205,100 @@ async fn test_tab(cx: &mut gpui::TestAppContext) {
     "});
 }
 
+#[gpui::test]
+fn test_tab_moves_focus(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let editor = cx.add_window(|cx| {
+        let mut editor = Editor::single_line(cx);
+        editor.set_tab_moves_focus(true);
+        editor
+    });
+    _ = editor.update(cx, |editor, cx| {
+        editor.tab(&Tab, cx);
+        assert_eq!(editor.text(cx), "");
+
+        editor.set_tab_moves_focus(false);
+        editor.tab(&Tab, cx);
+        assert_ne!(editor.text(cx), "");
+    });
+}
+
+#[gpui::test]
+async fn test_tab_expands_emmet_abbreviations(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |settings| {
+        settings.defaults.tab_size = NonZeroU32::new(4);
+        settings.defaults.emmet = Some(EmmetMode::Html);
+    });
+
+    {
+        let mut cx = EditorTestContext::new(cx).await;
+        cx.set_state("  ul>li.item*2ˇ");
+        cx.update_editor(|e, cx| e.tab(&Tab, cx));
+        cx.assert_editor_state(indoc! {r#"
+              <ul>
+                  <li class="item">ˇ</li>
+                  <li class="item"></li>
+              </ul>"#
+        });
+        cx.update_editor(|e, cx| e.tab(&Tab, cx));
+        cx.assert_editor_state(indoc! {r#"
+              <ul>
+                  <li class="item"></li>
+                  <li class="item">ˇ</li>
+              </ul>"#
+        });
+
+        // Text that isn't an abbreviation is indented as usual.
+        cx.set_state("  Hello worldˇ");
+        cx.update_editor(|e, cx| e.tab(&Tab, cx));
+        cx.assert_editor_state("  Hello world   ˇ");
+    }
+
+    update_test_language_settings(cx, |settings| {
+        settings.defaults.emmet = Some(EmmetMode::Jsx);
+    });
+    {
+        let language = Arc::new(
+            Language::new(
+                LanguageConfig::default(),
+                Some(tree_sitter_typescript::language_tsx()),
+            )
+            .with_override_query("(jsx_element) @element")
+            .unwrap(),
+        );
+        let mut cx = EditorTestContext::new(cx).await;
+        cx.update_buffer(|buffer, cx| buffer.set_language(Some(language), cx));
+
+        cx.set_state(indoc! {"
+            const a = <div>
+                pˇ
+            </div>;"
+        });
+        cx.executor().run_until_parked();
+        cx.update_editor(|e, cx| e.tab(&Tab, cx));
+        cx.assert_editor_state(indoc! {"
+            const a = <div>
+                <p>ˇ</p>
+            </div>;"
+        });
+
+        // Only text inside JSX elements is expanded.
+        cx.set_state("const b = x*2ˇ");
+        cx.executor().run_until_parked();
+        cx.update_editor(|e, cx| e.tab(&Tab, cx));
+        cx.assert_editor_state("const b = x*2   ˇ");
+    }
+
+    update_test_language_settings(cx, |settings| {
+        settings.defaults.emmet = Some(EmmetMode::Off);
+    });
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state("  ulˇ");
+    cx.update_editor(|e, cx| e.tab(&Tab, cx));
+    cx.assert_editor_state("  ul    ˇ");
+}
+
 #[gpui::test]
 async fn test_tab_in_leading_whitespace_auto_indents_lines(cx: &mut gpui::TestAppContext) {
     init_test(cx, |_| {});
@@ -3883,6 +3977,29 @@ fn test_select_all(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_set_mark(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state("one «twoˇ» three");
+
+    cx.update_editor(|e, cx| {
+        e.set_mark(&SetMark, cx);
+        assert!(e.key_context(cx).contains("selection_mark"));
+        e.select_to_next_word_end(&SelectToNextWordEnd, cx);
+    });
+    cx.assert_editor_state("one two« threeˇ»");
+
+    cx.update_editor(|e, cx| e.swap_selection_ends(&SwapSelectionEnds, cx));
+    cx.assert_editor_state("one two«ˇ three»");
+
+    // Editing deactivates the mark.
+    cx.simulate_input("x");
+    cx.assert_editor_state("one twoxˇ");
+    cx.update_editor(|e, cx| assert!(!e.key_context(cx).contains("selection_mark")));
+}
+
 #[gpui::test]
 fn test_select_line(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -5711,6 +5828,80 @@ async fn test_auto_replace_emoji_shortcode(cx: &mut gpui::TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_path_completions_in_strings(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |_| {});
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/a",
+        json!({
+            "assets": { "logo.png": "" },
+            "src": {
+                "main.rs": "",
+                "util": { "mod.rs": "" },
+            },
+        }),
+    )
+    .await;
+    let project = Project::test(fs, ["/a".as_ref()], cx).await;
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees().next().unwrap().read(cx).id()
+    });
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, "src/main.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let language = Arc::new(
+        Language::new(
+            LanguageConfig::default(),
+            Some(tree_sitter_rust::language()),
+        )
+        .with_override_query("(string_literal) @string")
+        .unwrap(),
+    );
+    buffer.update(cx, |buffer, cx| {
+        buffer.set_language(Some(language), cx);
+        buffer.set_text(
+            indoc! {r#"
+                let a = "./u";
+                let b = "../assets/";
+                let c = "s";
+                let d = "an s";
+                let e = s;"#
+            },
+            cx,
+        );
+    });
+    let multi_buffer = cx.new_model(|cx| MultiBuffer::singleton(buffer.clone(), cx));
+    let editor = cx.add_window(|cx| build_editor(multi_buffer, cx));
+    cx.executor().run_until_parked();
+
+    _ = editor.update(cx, |_, cx| {
+        let mut completions_at = |row, column| {
+            let position = buffer.read(cx).anchor_before(Point::new(row, column));
+            path_completions(&project, &buffer, position, cx)
+                .into_iter()
+                .map(|completion| completion.new_text)
+                .collect::<Vec<_>>()
+        };
+
+        // Paths starting with `./` or `../` are relative to the buffer's directory.
+        assert_eq!(completions_at(0, 12), ["main.rs", "util/"]);
+        assert_eq!(completions_at(1, 19), ["logo.png"]);
+        // Other paths are relative to the buffer's directory or the worktree root.
+        assert_eq!(
+            completions_at(2, 10),
+            ["assets/", "main.rs", "src/", "util/"]
+        );
+        // Words that don't start a string, and code outside strings, aren't completed.
+        assert!(completions_at(3, 13).is_empty());
+        assert!(completions_at(4, 9).is_empty());
+    });
+}
+
 #[gpui::test]
 async fn test_snippets(cx: &mut gpui::TestAppContext) {
     init_test(cx, |_| {});
@@ -4,7 +4,8 @@ use crate::{
         BlockContext, BlockStyle, DisplaySnapshot, HighlightedChunk, ToDisplayPoint, TransformBlock,
     },
     editor_settings::{
-        CurrentLineHighlight, DoubleClickInMultibuffer, MultiCursorModifier, ShowScrollbar,
+        CurrentLineHighlight, DoubleClickInMultibuffer, MultiCursorModifier,
+        RelativeLineNumbersMode, ShowScrollbar,
     },
     git::{
         blame::{CommitDetails, GitBlame},
@@ -28,13 +29,13 @@ use collections::{BTreeMap, HashMap};
 use git::{blame::BlameEntry, diff::DiffHunkStatus, Oid};
 use gpui::{
     anchored, deferred, div, fill, outline, point, px, quad, relative, size, svg,
-    transparent_black, Action, AnchorCorner, AnyElement, AvailableSpace, Bounds, ClipboardItem,
-    ContentMask, Corners, CursorStyle, DispatchPhase, Edges, Element, ElementInputHandler, Entity,
-    FontId, GlobalElementId, Hitbox, Hsla, InteractiveElement, IntoElement, Length,
-    ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, PaintQuad,
-    ParentElement, Pixels, ScrollDelta, ScrollWheelEvent, ShapedLine, SharedString, Size,
-    StatefulInteractiveElement, Style, Styled, TextRun, TextStyle, TextStyleRefinement, View,
-    ViewContext, WeakView, WindowContext,
+    transparent_black, AccessibilityNodeProperties, AccessibilityRole, Action, AnchorCorner,
+    AnyElement, AvailableSpace, Bounds, ClipboardItem, ContentMask, Corners, CursorStyle,
+    DispatchPhase, Edges, Element, ElementInputHandler, Entity, FontId, GlobalElementId, Hitbox,
+    Hsla, InteractiveElement, IntoElement, Length, ModifiersChangedEvent, MouseButton,
+    MouseDownEvent, MouseMoveEvent, MouseUpEvent, PaintQuad, ParentElement, Pixels, ScrollDelta,
+    ScrollWheelEvent, ShapedLine, SharedString, Size, StatefulInteractiveElement, Style, Styled,
+    TextRun, TextStyle, TextStyleRefinement, View, ViewContext, WeakView, WindowContext,
 };
 use itertools::Itertools;
 use language::language_settings::{
@@ -259,6 +260,8 @@ impl EditorElement {
         register_action(view, cx, Editor::select_to_beginning);
         register_action(view, cx, Editor::select_to_end);
         register_action(view, cx, Editor::select_all);
+        register_action(view, cx, Editor::set_mark);
+        register_action(view, cx, Editor::swap_selection_ends);
         register_action(view, cx, |editor, action, cx| {
             editor.select_all_matches(action, cx).log_err();
         });
@@ -1705,11 +1708,11 @@ impl EditorElement {
         });
         let font_size = self.style.text.font_size.to_pixels(cx.rem_size());
 
-        let is_relative = EditorSettings::get_global(cx).relative_line_numbers;
-        let relative_to = if is_relative {
-            Some(newest_selection_head.row())
-        } else {
+        let relative_mode = EditorSettings::get_global(cx).relative_line_numbers.mode();
+        let relative_to = if relative_mode == RelativeLineNumbersMode::Absolute {
             None
+        } else {
+            Some(newest_selection_head.row())
         };
         let relative_rows = self.calculate_relative_line_numbers(snapshot, &rows, relative_to);
         let mut line_number = String::new();
@@ -1725,10 +1728,12 @@ impl EditorElement {
                     cx.theme().colors().editor_line_number
                 };
                 line_number.clear();
-                let default_number = multibuffer_row.0 + 1;
-                let number = relative_rows
-                    .get(&DisplayRow(ix as u32 + rows.start.0))
-                    .unwrap_or(&default_number);
+                // Only the cursor line has no relative number
+                let number = match relative_rows.get(&display_row) {
+                    Some(delta) => *delta,
+                    None if relative_mode == RelativeLineNumbersMode::Relative => 0,
+                    None => multibuffer_row.0 + 1,
+                };
                 write!(&mut line_number, "{number}").unwrap();
                 let run = TextRun {
                     len: line_number.len(),
@@ -1851,6 +1856,7 @@ impl EditorElement {
             LineWithInvisibles::from_chunks(
                 chunks,
                 &self.style.text,
+                self.style.letter_spacing,
                 MAX_LINE_LEN,
                 rows.len(),
                 line_number_layouts,
@@ -3742,7 +3748,7 @@ impl EditorElement {
             )
             .unwrap();
 
-        layout.width
+        layout.width + style.letter_spacing * column as f32
     }
 
     fn max_line_number_width(&self, snapshot: &EditorSnapshot, cx: &WindowContext) -> Pixels {
@@ -3964,6 +3970,7 @@ impl LineWithInvisibles {
     fn from_chunks<'a>(
         chunks: impl Iterator<Item = HighlightedChunk<'a>>,
         text_style: &TextStyle,
+        letter_spacing: Pixels,
         max_line_len: usize,
         max_line_count: usize,
         line_number_layouts: &[Option<ShapedLine>],
@@ -3995,7 +4002,8 @@ impl LineWithInvisibles {
                     let shaped_line = cx
                         .text_system()
                         .shape_line(line.clone().into(), font_size, &styles)
-                        .unwrap();
+                        .unwrap()
+                        .with_letter_spacing(letter_spacing);
                     width += shaped_line.width;
                     len += shaped_line.len;
                     fragments.push(LineFragment::Text(shaped_line));
@@ -4042,7 +4050,8 @@ impl LineWithInvisibles {
                         let shaped_line = cx
                             .text_system()
                             .shape_line(line.clone().into(), font_size, &styles)
-                            .unwrap();
+                            .unwrap()
+                            .with_letter_spacing(letter_spacing);
                         width += shaped_line.width;
                         len += shaped_line.len;
                         fragments.push(LineFragment::Text(shaped_line));
@@ -4407,6 +4416,32 @@ enum Invisible {
 }
 
 impl EditorElement {
+    /// Describes the editor to assistive technologies. Its text and selection are only given
+    /// while it's focused, since that's when screen readers read them.
+    fn accessibility_properties(&self, cx: &mut WindowContext) -> AccessibilityNodeProperties {
+        if !cx.accessibility_enabled() {
+            return AccessibilityNodeProperties::new(AccessibilityRole::TextEditor);
+        }
+
+        let editor = self.editor.read(cx);
+        let role = match editor.mode {
+            EditorMode::SingleLine | EditorMode::AutoHeight { .. } => AccessibilityRole::TextField,
+            EditorMode::Full => AccessibilityRole::TextEditor,
+        };
+        let focused = editor.is_focused(cx);
+        let mut properties = AccessibilityNodeProperties::new(role).focused(focused);
+        if let Some(placeholder_text) = editor.placeholder_text.clone() {
+            properties = properties.label(placeholder_text);
+        }
+        if focused {
+            let (text, selected_range) = self
+                .editor
+                .update(cx, |editor, cx| editor.accessibility_value(cx));
+            properties = properties.value(text, Some(selected_range));
+        }
+        properties
+    }
+
     /// Returns the rem size to use when rendering the [`EditorElement`].
     ///
     /// This allows UI elements to scale based on the `buffer_font_size`.
@@ -4535,12 +4570,14 @@ impl Element for EditorElement {
                         .typographic_bounds(font_id, font_size, 'm')
                         .unwrap()
                         .size
-                        .width;
+                        .width
+                        + style.letter_spacing;
                     let em_advance = cx
                         .text_system()
                         .advance(font_id, font_size, 'm')
                         .unwrap()
-                        .width;
+                        .width
+                        + style.letter_spacing;
 
                     let gutter_dimensions = snapshot.gutter_dimensions(
                         font_id,
@@ -5060,6 +5097,7 @@ impl Element for EditorElement {
         let focus_handle = self.editor.focus_handle(cx);
         let key_context = self.editor.read(cx).key_context(cx);
         cx.set_focus_handle(&focus_handle);
+        cx.set_tab_stop();
         cx.set_key_context(key_context);
         cx.handle_input(
             &focus_handle,
@@ -5101,34 +5139,37 @@ impl Element for EditorElement {
                 }
             });
         let rem_size = self.rem_size(cx);
-        cx.with_rem_size(rem_size, |cx| {
-            cx.with_text_style(Some(text_style), |cx| {
-                cx.with_content_mask(Some(ContentMask { bounds }), |cx| {
-                    self.paint_mouse_listeners(layout, hovered_hunk, cx);
-                    self.paint_background(layout, cx);
-                    self.paint_indent_guides(layout, cx);
-
-                    if layout.gutter_hitbox.size.width > Pixels::ZERO {
-                        self.paint_blamed_display_rows(layout, cx);
-                        self.paint_line_numbers(layout, cx);
-                    }
+        let accessibility_properties = self.accessibility_properties(cx);
+        cx.with_accessibility_node(accessibility_properties, bounds, |cx| {
+            cx.with_rem_size(rem_size, |cx| {
+                cx.with_text_style(Some(text_style), |cx| {
+                    cx.with_content_mask(Some(ContentMask { bounds }), |cx| {
+                        self.paint_mouse_listeners(layout, hovered_hunk, cx);
+                        self.paint_background(layout, cx);
+                        self.paint_indent_guides(layout, cx);
+
+                        if layout.gutter_hitbox.size.width > Pixels::ZERO {
+                            self.paint_blamed_display_rows(layout, cx);
+                            self.paint_line_numbers(layout, cx);
+                        }
 
-                    self.paint_text(layout, cx);
+                        self.paint_text(layout, cx);
 
-                    if !layout.blocks.is_empty() {
-                        cx.with_element_namespace("blocks", |cx| {
-                            self.paint_blocks(layout, cx);
-                        });
-                    }
+                        if !layout.blocks.is_empty() {
+                            cx.with_element_namespace("blocks", |cx| {
+                                self.paint_blocks(layout, cx);
+                            });
+                        }
 
-                    if layout.gutter_hitbox.size.width > Pixels::ZERO {
-                        self.paint_gutter_highlights(layout, cx);
-                        self.paint_gutter_indicators(layout, cx);
-                    }
+                        if layout.gutter_hitbox.size.width > Pixels::ZERO {
+                            self.paint_gutter_highlights(layout, cx);
+                            self.paint_gutter_indicators(layout, cx);
+                        }
 
-                    self.paint_scrollbar(layout, cx);
-                    self.paint_mouse_context_menu(layout, cx);
-                });
+                        self.paint_scrollbar(layout, cx);
+                        self.paint_mouse_context_menu(layout, cx);
+                    });
+                })
             })
         })
     }
@@ -5383,9 +5424,18 @@ fn layout_line(
     cx: &mut WindowContext,
 ) -> LineWithInvisibles {
     let chunks = snapshot.highlighted_chunks(row..row + DisplayRow(1), true, style);
-    LineWithInvisibles::from_chunks(chunks, &style.text, MAX_LINE_LEN, 1, &[], snapshot.mode, cx)
-        .pop()
-        .unwrap()
+    LineWithInvisibles::from_chunks(
+        chunks,
+        &style.text,
+        style.letter_spacing,
+        MAX_LINE_LEN,
+        1,
+        &[],
+        snapshot.mode,
+        cx,
+    )
+    .pop()
+    .unwrap()
 }
 
 #[derive(Debug)]
@@ -5672,12 +5722,14 @@ mod tests {
     use super::*;
     use crate::{
         display_map::{BlockDisposition, BlockProperties},
+        editor_settings::RelativeLineNumbers,
         editor_tests::{init_test, update_test_language_settings},
         Editor, MultiBuffer,
     };
     use gpui::{TestAppContext, VisualTestContext};
     use language::language_settings;
     use log::info;
+    use settings::SettingsStore;
     use std::num::NonZeroU32;
     use ui::Context;
     use util::test::sample_text;
@@ -5759,6 +5811,58 @@ mod tests {
         assert_eq!(relative_rows[&DisplayRow(2)], 3);
     }
 
+    #[gpui::test]
+    fn test_shape_relative_line_numbers(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        let window = cx.add_window(|cx| {
+            let buffer = MultiBuffer::build_simple(&sample_text(6, 6, 'a'), cx);
+            Editor::new(EditorMode::Full, buffer, None, true, cx)
+        });
+
+        let editor = window.root(cx).unwrap();
+        let style = cx.update(|cx| editor.read(cx).style().unwrap().clone());
+        let element = EditorElement::new(&editor, style);
+        let snapshot = window.update(cx, |editor, cx| editor.snapshot(cx)).unwrap();
+
+        let mut line_numbers = |mode: RelativeLineNumbersMode| {
+            cx.update(|cx| {
+                SettingsStore::update_global(cx, |store, cx| {
+                    store.update_user_settings::<EditorSettings>(cx, |settings| {
+                        settings.relative_line_numbers = Some(RelativeLineNumbers::Mode(mode));
+                    });
+                });
+            });
+            cx.update_window(*window, |_, cx| {
+                element
+                    .layout_line_numbers(
+                        DisplayRow(0)..DisplayRow(6),
+                        (0..6).map(MultiBufferRow).map(Some),
+                        &Default::default(),
+                        Some(DisplayPoint::new(DisplayRow(3), 0)),
+                        &snapshot,
+                        cx,
+                    )
+                    .into_iter()
+                    .map(|line| line.unwrap().text.to_string())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap()
+        };
+
+        assert_eq!(
+            line_numbers(RelativeLineNumbersMode::Absolute),
+            ["1", "2", "3", "4", "5", "6"]
+        );
+        assert_eq!(
+            line_numbers(RelativeLineNumbersMode::Relative),
+            ["3", "2", "1", "0", "1", "2"]
+        );
+        assert_eq!(
+            line_numbers(RelativeLineNumbersMode::Hybrid),
+            ["3", "2", "1", "4", "1", "2"]
+        );
+    }
+
     #[gpui::test]
     async fn test_vim_visual_selections(cx: &mut TestAppContext) {
         init_test(cx, |_| {});
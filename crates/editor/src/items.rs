@@ -1,7 +1,7 @@
 use crate::{
     editor_settings::SeedQuerySetting, persistence::DB, scroll::ScrollAnchor, Anchor, Autoscroll,
     Editor, EditorEvent, EditorSettings, ExcerptId, ExcerptRange, MultiBuffer, MultiBufferSnapshot,
-    NavigationData, SearchWithinRange, ToPoint as _,
+    NavigationData, SearchWithinRange, ToPoint as _, FILE_POSITION_SERIALIZATION_DEBOUNCE_TIMEOUT,
 };
 use anyhow::{anyhow, Context as _, Result};
 use collections::HashSet;
@@ -27,7 +27,7 @@ use std::{
     cmp::{self, Ordering},
     iter,
     ops::Range,
-    path::Path,
+    path::{Path, PathBuf},
     sync::Arc,
 };
 use text::{BufferId, Selection};
@@ -672,10 +672,12 @@ impl Item for Editor {
     fn deactivated(&mut self, cx: &mut ViewContext<Self>) {
         let selection = self.selections.newest_anchor();
         self.push_to_nav_history(selection.head(), None, cx);
+        self.serialize_file_position(cx);
     }
 
     fn workspace_deactivated(&mut self, cx: &mut ViewContext<Self>) {
         self.hide_hovered_link(cx);
+        self.serialize_file_position(cx);
     }
 
     fn is_dirty(&self, cx: &AppContext) -> bool {
@@ -853,6 +855,7 @@ impl Item for Editor {
         };
 
         let item_id = cx.view().item_id().as_u64() as ItemId;
+        self.restore_file_position(workspace_id, cx);
 
         fn serialize(
             buffer: Model<Buffer>,
@@ -987,6 +990,114 @@ impl ProjectItem for Editor {
     }
 }
 
+impl Editor {
+    /// Saves the position in the file once the selections and scroll position
+    /// stop changing for a while, rather than writing to the database on every
+    /// change.
+    pub(crate) fn schedule_file_position_serialization(&mut self, cx: &mut ViewContext<Self>) {
+        let Some((_, Some(_))) = self.workspace else {
+            return;
+        };
+        self.serialize_file_position_task = Some(cx.spawn(|editor, mut cx| async move {
+            cx.background_executor()
+                .timer(FILE_POSITION_SERIALIZATION_DEBOUNCE_TIMEOUT)
+                .await;
+            editor
+                .update(&mut cx, |editor, cx| editor.serialize_file_position(cx))
+                .ok();
+        }));
+    }
+
+    /// Remembers the selections and scroll position in the editor's file, so
+    /// that they're restored when the file is opened again in this workspace.
+    pub(crate) fn serialize_file_position(&mut self, cx: &mut ViewContext<Self>) {
+        self.serialize_file_position_task.take();
+        let Some((_, Some(workspace_id))) = self.workspace else {
+            return;
+        };
+        let Some(path) = self.local_file_path(cx) else {
+            return;
+        };
+
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let selections = self
+            .selections
+            .all::<usize>(cx)
+            .iter()
+            .map(|selection| format!("{}-{}", selection.tail(), selection.head()))
+            .collect::<Vec<_>>()
+            .join(",");
+        let scroll_anchor = self.scroll_manager.anchor();
+        let top_row = scroll_anchor.top_row(&snapshot);
+        cx.background_executor()
+            .spawn(async move {
+                DB.save_file_position(
+                    workspace_id,
+                    path,
+                    selections,
+                    top_row,
+                    scroll_anchor.offset.x,
+                    scroll_anchor.offset.y,
+                )
+                .await
+                .log_err()
+            })
+            .detach();
+    }
+
+    /// Restores the selections and scroll position that were last saved for
+    /// the editor's file, unless the editor has moved from the start of it.
+    fn restore_file_position(&mut self, workspace_id: WorkspaceId, cx: &mut ViewContext<Self>) {
+        let Some(path) = self.local_file_path(cx) else {
+            return;
+        };
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let at_start = self.selections.count() == 1
+            && self.selections.newest::<usize>(cx).range() == (0..0)
+            && self.scroll_manager.anchor().top_row(&snapshot) == 0;
+        if !at_start {
+            return;
+        }
+        let Some((selections, top_row, x, y)) =
+            DB.get_file_position(workspace_id, path).log_err().flatten()
+        else {
+            return;
+        };
+
+        let clip_offset = |offset: &str| -> Option<usize> {
+            let offset = offset.parse::<usize>().ok()?.min(snapshot.len());
+            Some(snapshot.clip_offset(offset, Bias::Left))
+        };
+        let selections = selections
+            .split(',')
+            .filter_map(|selection| {
+                let (tail, head) = selection.split_once('-')?;
+                Some(clip_offset(tail)?..clip_offset(head)?)
+            })
+            .collect::<Vec<_>>();
+        if !selections.is_empty() {
+            self.change_selections(None, cx, |s| s.select_ranges(selections));
+        }
+        let top_anchor = snapshot.anchor_at(
+            snapshot.clip_point(Point::new(top_row, 0), Bias::Left),
+            Bias::Left,
+        );
+        self.set_scroll_anchor(
+            ScrollAnchor {
+                offset: point(x, y),
+                anchor: top_anchor,
+            },
+            cx,
+        );
+    }
+
+    fn local_file_path(&self, cx: &AppContext) -> Option<PathBuf> {
+        let buffer = self.buffer.read(cx).as_singleton()?;
+        let file = buffer.read(cx).file()?.as_local()?;
+        Some(file.abs_path(cx))
+    }
+}
+
 impl EventEmitter<SearchEvent> for Editor {}
 
 pub(crate) enum BufferSearchHighlights {}
@@ -1278,7 +1389,7 @@ pub fn entry_git_aware_label_color(
         Color::Ignored
     } else {
         match git_status {
-            Some(GitFileStatus::Added) => Color::Created,
+            Some(GitFileStatus::Added) | Some(GitFileStatus::Untracked) => Color::Created,
             Some(GitFileStatus::Modified) => Color::Modified,
             Some(GitFileStatus::Conflict) => Color::Conflict,
             None => entry_label_color(selected),
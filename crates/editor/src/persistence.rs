@@ -1,10 +1,14 @@
 use std::path::PathBuf;
 
+use anyhow::Context as _;
 use db::sqlez_macros::sql;
 use db::{define_connection, query};
 
 use workspace::{ItemId, WorkspaceDb, WorkspaceId};
 
+/// The number of files per workspace whose positions are remembered.
+const MAX_FILE_POSITIONS: usize = 1000;
+
 define_connection!(
     // Current schema shape using pseudo-rust syntax:
     // editors(
@@ -15,6 +19,15 @@ define_connection!(
     //   scroll_vertical_offset: f32,
     //   scroll_horizontal_offset: f32,
     // )
+    // editor_file_positions(
+    //   workspace_id: usize,
+    //   path: PathBuf,
+    //   selections: String, // "tail-head" offsets, separated by commas
+    //   scroll_top_row: usize,
+    //   scroll_vertical_offset: f32,
+    //   scroll_horizontal_offset: f32,
+    //   timestamp: String, // UTC YYYY-MM-DD HH:MM:SS
+    // )
     pub static ref DB: EditorDb<WorkspaceDb> =
         &[sql! (
             CREATE TABLE editors(
@@ -31,6 +44,21 @@ define_connection!(
             ALTER TABLE editors ADD COLUMN scroll_top_row INTEGER NOT NULL DEFAULT 0;
             ALTER TABLE editors ADD COLUMN scroll_horizontal_offset REAL NOT NULL DEFAULT 0;
             ALTER TABLE editors ADD COLUMN scroll_vertical_offset REAL NOT NULL DEFAULT 0;
+        ),
+        sql! (
+            CREATE TABLE editor_file_positions(
+                workspace_id INTEGER NOT NULL,
+                path BLOB NOT NULL,
+                selections TEXT NOT NULL,
+                scroll_top_row INTEGER NOT NULL,
+                scroll_horizontal_offset REAL NOT NULL,
+                scroll_vertical_offset REAL NOT NULL,
+                timestamp TEXT DEFAULT CURRENT_TIMESTAMP NOT NULL,
+                PRIMARY KEY(workspace_id, path),
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+                ON UPDATE CASCADE
+            ) STRICT;
         )];
 );
 
@@ -80,4 +108,188 @@ impl EditorDb {
             WHERE item_id = ?1 AND workspace_id = ?2
         }
     }
+
+    // Returns the selections, scroll top row, and offset that were last saved for the file
+    query! {
+        pub fn get_file_position(workspace_id: WorkspaceId, path: PathBuf) -> Result<Option<(String, u32, f32, f32)>> {
+            SELECT selections, scroll_top_row, scroll_horizontal_offset, scroll_vertical_offset
+            FROM editor_file_positions
+            WHERE workspace_id = ? AND path = ?
+        }
+    }
+
+    /// Saves the position in the file, forgetting the least recently saved
+    /// files of the workspace beyond [`MAX_FILE_POSITIONS`].
+    pub async fn save_file_position(
+        &self,
+        workspace_id: WorkspaceId,
+        path: PathBuf,
+        selections: String,
+        top_row: u32,
+        horizontal_offset: f32,
+        vertical_offset: f32,
+    ) -> anyhow::Result<()> {
+        self.write(move |conn| {
+            conn.exec_bound(sql!(
+                INSERT INTO editor_file_positions(
+                    workspace_id,
+                    path,
+                    selections,
+                    scroll_top_row,
+                    scroll_horizontal_offset,
+                    scroll_vertical_offset,
+                    timestamp
+                )
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, CURRENT_TIMESTAMP)
+                ON CONFLICT DO
+                UPDATE SET
+                    selections = ?3,
+                    scroll_top_row = ?4,
+                    scroll_horizontal_offset = ?5,
+                    scroll_vertical_offset = ?6,
+                    timestamp = CURRENT_TIMESTAMP
+            ))?((
+                workspace_id,
+                path,
+                selections,
+                top_row,
+                horizontal_offset,
+                vertical_offset,
+            ))
+            .context("saving file position")?;
+
+            conn.exec_bound(sql!(
+                DELETE FROM editor_file_positions
+                WHERE workspace_id = ?1 AND path NOT IN (
+                    SELECT path FROM editor_file_positions
+                    WHERE workspace_id = ?1
+                    ORDER BY timestamp DESC
+                    LIMIT ?2
+                )
+            ))?((workspace_id, MAX_FILE_POSITIONS))
+            .context("forgetting old file positions")
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use db::open_test_db;
+
+    async fn new_workspace_id(db: &EditorDb) -> WorkspaceId {
+        db.write(|conn| {
+            conn.select_row(sql!(INSERT INTO workspaces DEFAULT VALUES RETURNING workspace_id))
+                .unwrap()()
+            .unwrap()
+            .unwrap()
+        })
+        .await
+    }
+
+    #[gpui::test]
+    async fn test_save_file_position() {
+        let db = EditorDb(open_test_db("test_save_file_position").await);
+        let workspace_id = new_workspace_id(&db).await;
+        let other_workspace_id = new_workspace_id(&db).await;
+        let path = PathBuf::from("/root/src/main.rs");
+
+        assert_eq!(
+            db.get_file_position(workspace_id, path.clone()).unwrap(),
+            None
+        );
+
+        db.save_file_position(workspace_id, path.clone(), "1-4".into(), 3, 0., 0.5)
+            .await
+            .unwrap();
+        assert_eq!(
+            db.get_file_position(workspace_id, path.clone()).unwrap(),
+            Some(("1-4".into(), 3, 0., 0.5))
+        );
+        assert_eq!(
+            db.get_file_position(other_workspace_id, path.clone())
+                .unwrap(),
+            None
+        );
+
+        db.save_file_position(workspace_id, path.clone(), "7-7,9-2".into(), 5, 1., 0.)
+            .await
+            .unwrap();
+        assert_eq!(
+            db.get_file_position(workspace_id, path).unwrap(),
+            Some(("7-7,9-2".into(), 5, 1., 0.))
+        );
+    }
+
+    #[gpui::test]
+    async fn test_forget_old_file_positions() {
+        let db = EditorDb(open_test_db("test_forget_old_file_positions").await);
+        let workspace_id = new_workspace_id(&db).await;
+        let other_workspace_id = new_workspace_id(&db).await;
+        let path = |ix: usize| PathBuf::from(format!("/root/src/{ix}.rs"));
+
+        // Positions saved within the same second have the same timestamp, so
+        // older ones are inserted with distinct timestamps in the past.
+        db.write(move |conn| {
+            let mut insert = conn
+                .exec_bound(sql!(
+                    INSERT INTO editor_file_positions(
+                        workspace_id,
+                        path,
+                        selections,
+                        scroll_top_row,
+                        scroll_horizontal_offset,
+                        scroll_vertical_offset,
+                        timestamp
+                    )
+                    VALUES (?1, ?2, ?3, 0, 0, 0, ?4)
+                ))
+                .unwrap();
+            for ix in 0..MAX_FILE_POSITIONS {
+                let timestamp = format!("2000-01-01 00:{:02}:{:02}", ix / 60, ix % 60);
+                insert((workspace_id, path(ix), "0-0", timestamp)).unwrap();
+            }
+            insert((
+                other_workspace_id,
+                path(0),
+                "0-0",
+                "1999-01-01 00:00:00".to_string(),
+            ))
+            .unwrap();
+        })
+        .await;
+
+        db.save_file_position(
+            workspace_id,
+            path(MAX_FILE_POSITIONS),
+            "0-0".into(),
+            0,
+            0.,
+            0.,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(db.get_file_position(workspace_id, path(0)).unwrap(), None);
+        assert!(db
+            .get_file_position(workspace_id, path(1))
+            .unwrap()
+            .is_some());
+        assert!(db
+            .get_file_position(workspace_id, path(MAX_FILE_POSITIONS))
+            .unwrap()
+            .is_some());
+        assert!(db
+            .get_file_position(other_workspace_id, path(0))
+            .unwrap()
+            .is_some());
+        let count = db
+            .select_row_bound::<WorkspaceId, usize>(sql!(
+                SELECT COUNT(*) FROM editor_file_positions WHERE workspace_id = ?
+            ))
+            .unwrap()(workspace_id)
+        .unwrap();
+        assert_eq!(count, Some(MAX_FILE_POSITIONS));
+    }
 }
@@ -404,6 +404,9 @@ impl Editor {
             workspace_id,
             cx,
         );
+        if local {
+            self.schedule_file_position_serialization(cx);
+        }
 
         self.refresh_inlay_hints(InlayHintRefreshReason::NewLinesShown, cx);
     }
@@ -422,6 +425,7 @@ impl Editor {
             .row;
         self.scroll_manager
             .set_anchor(scroll_anchor, top_row, true, false, workspace_id, cx);
+        self.schedule_file_position_serialization(cx);
     }
 
     pub(crate) fn set_scroll_anchor_remote(
@@ -155,6 +155,20 @@ impl EditorLspTestContext {
                 ("<" @open ">" @close)
                 ("\"" @open "\"" @close)
                 (closure_parameters "|" @open "|" @close)"#})),
+            text_objects: Some(Cow::from(indoc! {r#"
+                (function_item
+                    body: (_
+                        "{"
+                        (_)* @function.inside
+                        "}" )) @function.around
+
+                (impl_item
+                    body: (_
+                        "{"
+                        (_)* @class.inside
+                        "}" )) @class.around
+
+                (line_comment)+ @comment.around"#})),
             ..Default::default()
         })
         .expect("Could not parse queries");
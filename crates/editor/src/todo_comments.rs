@@ -0,0 +1,492 @@
+use std::{ops::Range, time::Duration};
+
+use collections::{HashMap, HashSet};
+use gpui::{FontWeight, HighlightStyle, Task};
+use language::{BufferSnapshot, OffsetRangeExt, Point};
+use multi_buffer::{Anchor, ExcerptId, MultiBufferSnapshot, ToOffset};
+use regex::Regex;
+use settings::Settings;
+use theme::{ActiveTheme, StatusColors, SyntaxTheme};
+use ui::ViewContext;
+
+use crate::{Editor, EditorMode, EditorSettings, TodoColor};
+
+const TODO_HIGHLIGHTS_DEBOUNCE: Duration = Duration::from_millis(100);
+
+enum TodoInfoHighlight {}
+enum TodoHintHighlight {}
+enum TodoWarningHighlight {}
+enum TodoErrorHighlight {}
+enum TodoSuccessHighlight {}
+
+/// A keyword, like TODO or FIXME, inside of a comment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TodoComment {
+    /// The range of the keyword in the buffer.
+    pub range: Range<usize>,
+    pub keyword: String,
+}
+
+/// Returns a regex pattern that matches any of the given keywords as a whole word,
+/// or `None` if there are no keywords.
+pub fn keywords_pattern<'a>(keywords: impl IntoIterator<Item = &'a str>) -> Option<String> {
+    let keywords = keywords
+        .into_iter()
+        .filter(|keyword| !keyword.is_empty())
+        .map(regex::escape)
+        .collect::<Vec<_>>();
+    if keywords.is_empty() {
+        None
+    } else {
+        Some(format!(r"\b(?:{})\b", keywords.join("|")))
+    }
+}
+
+/// Finds the keywords matched by `regex` that are inside of comments, as highlighted
+/// by the language of the buffer, within the given range.
+pub fn todo_comments(
+    buffer: &BufferSnapshot,
+    range: Range<usize>,
+    regex: &Regex,
+    theme: &SyntaxTheme,
+) -> Vec<TodoComment> {
+    let mut comment_ranges = Vec::<Range<usize>>::new();
+    let mut offset = range.start;
+    for chunk in buffer.chunks(range, true) {
+        let chunk_range = offset..offset + chunk.text.len();
+        offset = chunk_range.end;
+        let is_comment = chunk
+            .syntax_highlight_id
+            .and_then(|id| id.name(theme))
+            .map_or(false, |name| {
+                name == "comment" || name.starts_with("comment.")
+            });
+        if !is_comment {
+            continue;
+        }
+        match comment_ranges.last_mut() {
+            Some(last_range) if last_range.end == chunk_range.start => {
+                last_range.end = chunk_range.end;
+            }
+            _ => comment_ranges.push(chunk_range),
+        }
+    }
+
+    let mut todos = Vec::new();
+    for comment_range in comment_ranges {
+        let text = buffer
+            .text_for_range(comment_range.clone())
+            .collect::<String>();
+        for keyword in regex.find_iter(&text) {
+            todos.push(TodoComment {
+                range: comment_range.start + keyword.start()..comment_range.start + keyword.end(),
+                keyword: keyword.as_str().to_string(),
+            });
+        }
+    }
+    todos
+}
+
+/// The keywords highlighted in the comments of an editor, and the versions of the excerpts
+/// that were scanned for them, so that only the lines edited since are scanned again.
+#[derive(Default)]
+pub(super) struct TodoHighlightState {
+    task: Option<Task<()>>,
+    scanned_versions: HashMap<ExcerptId, clock::Global>,
+}
+
+struct TodoScan {
+    highlights: HashMap<TodoColor, Vec<Range<Anchor>>>,
+    /// The ranges that were scanned, whose previous highlights are replaced.
+    rescanned_ranges: Vec<Range<Anchor>>,
+    versions: HashMap<ExcerptId, clock::Global>,
+}
+
+/// Scans all of the excerpts of the editor for keywords again, like when the settings or
+/// the language change.
+pub(super) fn refresh_todo_highlights(editor: &mut Editor, cx: &mut ViewContext<Editor>) {
+    editor.todo_highlights.scanned_versions.clear();
+    refresh_edited_todo_highlights(editor, cx);
+}
+
+/// Scans the given excerpts for keywords again, like when they were expanded.
+pub(super) fn refresh_excerpt_todo_highlights(
+    editor: &mut Editor,
+    excerpt_ids: &[ExcerptId],
+    cx: &mut ViewContext<Editor>,
+) {
+    for excerpt_id in excerpt_ids {
+        editor.todo_highlights.scanned_versions.remove(excerpt_id);
+    }
+    refresh_edited_todo_highlights(editor, cx);
+}
+
+/// Scans the lines that were edited since the last scan, and the excerpts that weren't
+/// scanned yet, for keywords.
+pub(super) fn refresh_edited_todo_highlights(editor: &mut Editor, cx: &mut ViewContext<Editor>) {
+    let settings = &EditorSettings::get_global(cx).todo_highlights;
+    let keywords = settings.keywords.clone();
+    let regex = (settings.enabled && editor.mode == EditorMode::Full)
+        .then(|| keywords_pattern(keywords.keys().map(String::as_str)))
+        .flatten()
+        .and_then(|pattern| Regex::new(&pattern).ok());
+    let Some(regex) = regex else {
+        editor.todo_highlights = TodoHighlightState::default();
+        clear_todo_highlights(editor, cx);
+        return;
+    };
+
+    let snapshot = editor.buffer.read(cx).snapshot(cx);
+    let theme = cx.theme().syntax().clone();
+    let scanned_versions = editor.todo_highlights.scanned_versions.clone();
+    editor.todo_highlights.task = Some(cx.spawn(|editor, mut cx| async move {
+        cx.background_executor()
+            .timer(TODO_HIGHLIGHTS_DEBOUNCE)
+            .await;
+        let mut scan = cx
+            .background_executor()
+            .spawn(async move {
+                let mut scan = TodoScan {
+                    highlights: HashMap::default(),
+                    rescanned_ranges: Vec::new(),
+                    versions: HashMap::default(),
+                };
+                for (excerpt_id, buffer, excerpt_range) in snapshot.excerpts() {
+                    let ranges = match scanned_versions.get(&excerpt_id) {
+                        Some(version) => edited_lines(buffer, version, excerpt_range.context),
+                        None => vec![excerpt_range.context.to_offset(buffer)],
+                    };
+                    for range in ranges {
+                        for todo in todo_comments(buffer, range.clone(), &regex, &theme) {
+                            let Some(color) = keywords.get(&todo.keyword) else {
+                                continue;
+                            };
+                            let start = snapshot.anchor_in_excerpt(
+                                excerpt_id,
+                                buffer.anchor_after(todo.range.start),
+                            );
+                            let end = snapshot.anchor_in_excerpt(
+                                excerpt_id,
+                                buffer.anchor_before(todo.range.end),
+                            );
+                            if let Some((start, end)) = start.zip(end) {
+                                scan.highlights.entry(*color).or_default().push(start..end);
+                            }
+                        }
+                        let start = snapshot
+                            .anchor_in_excerpt(excerpt_id, buffer.anchor_before(range.start));
+                        let end =
+                            snapshot.anchor_in_excerpt(excerpt_id, buffer.anchor_after(range.end));
+                        if let Some((start, end)) = start.zip(end) {
+                            scan.rescanned_ranges.push(start..end);
+                        }
+                    }
+                    scan.versions.insert(excerpt_id, buffer.version().clone());
+                }
+                scan
+            })
+            .await;
+
+        editor
+            .update(&mut cx, |editor, cx| {
+                let snapshot = editor.buffer.read(cx).snapshot(cx);
+                let status = cx.theme().status().clone();
+                let rescanned_ranges = scan
+                    .rescanned_ranges
+                    .iter()
+                    .map(|range| range.start.to_offset(&snapshot)..range.end.to_offset(&snapshot))
+                    .collect::<Vec<_>>();
+                let excerpts = scan.versions.keys().copied().collect::<HashSet<_>>();
+                for color in [
+                    TodoColor::Info,
+                    TodoColor::Hint,
+                    TodoColor::Warning,
+                    TodoColor::Error,
+                    TodoColor::Success,
+                ] {
+                    let ranges = scan.highlights.remove(&color).unwrap_or_default();
+                    let style = highlight_style(color, &status);
+                    match color {
+                        TodoColor::Info => update_highlights::<TodoInfoHighlight>(
+                            editor,
+                            ranges,
+                            style,
+                            &rescanned_ranges,
+                            &excerpts,
+                            &snapshot,
+                            cx,
+                        ),
+                        TodoColor::Hint => update_highlights::<TodoHintHighlight>(
+                            editor,
+                            ranges,
+                            style,
+                            &rescanned_ranges,
+                            &excerpts,
+                            &snapshot,
+                            cx,
+                        ),
+                        TodoColor::Warning => update_highlights::<TodoWarningHighlight>(
+                            editor,
+                            ranges,
+                            style,
+                            &rescanned_ranges,
+                            &excerpts,
+                            &snapshot,
+                            cx,
+                        ),
+                        TodoColor::Error => update_highlights::<TodoErrorHighlight>(
+                            editor,
+                            ranges,
+                            style,
+                            &rescanned_ranges,
+                            &excerpts,
+                            &snapshot,
+                            cx,
+                        ),
+                        TodoColor::Success => update_highlights::<TodoSuccessHighlight>(
+                            editor,
+                            ranges,
+                            style,
+                            &rescanned_ranges,
+                            &excerpts,
+                            &snapshot,
+                            cx,
+                        ),
+                    }
+                }
+                editor.todo_highlights.scanned_versions = scan.versions;
+            })
+            .ok();
+    }));
+}
+
+/// Returns the whole lines within the range that were edited since the given version, to
+/// be scanned for keywords again.
+///
+/// Syntax changes that reach past the edited lines, like opening a block comment, are
+/// picked up by the next full scan.
+pub fn edited_lines(
+    buffer: &BufferSnapshot,
+    version: &clock::Global,
+    range: Range<text::Anchor>,
+) -> Vec<Range<usize>> {
+    let context = range.to_offset(buffer);
+    let mut lines = Vec::<Range<usize>>::new();
+    for edit in buffer.edits_since_in_range::<usize>(version, range) {
+        let start_row = buffer.offset_to_point(edit.new.start).row;
+        let end_row = buffer.offset_to_point(edit.new.end).row;
+        let start = buffer
+            .point_to_offset(Point::new(start_row, 0))
+            .max(context.start);
+        let end = buffer
+            .point_to_offset(Point::new(end_row, buffer.line_len(end_row)))
+            .min(context.end);
+        match lines.last_mut() {
+            Some(last_range) if last_range.end >= start => {
+                last_range.end = last_range.end.max(end);
+            }
+            _ => lines.push(start..end),
+        }
+    }
+    lines
+}
+
+fn highlight_style(color: TodoColor, status: &StatusColors) -> HighlightStyle {
+    HighlightStyle {
+        color: Some(match color {
+            TodoColor::Info => status.info,
+            TodoColor::Hint => status.hint,
+            TodoColor::Warning => status.warning,
+            TodoColor::Error => status.error,
+            TodoColor::Success => status.success,
+        }),
+        font_weight: Some(FontWeight::BOLD),
+        ..HighlightStyle::default()
+    }
+}
+
+/// Replaces the highlights within the rescanned ranges with the ones that were found, keeping
+/// the rest of them.
+fn update_highlights<T: 'static>(
+    editor: &mut Editor,
+    new_ranges: Vec<Range<Anchor>>,
+    style: HighlightStyle,
+    rescanned_ranges: &[Range<usize>],
+    excerpts: &HashSet<ExcerptId>,
+    snapshot: &MultiBufferSnapshot,
+    cx: &mut ViewContext<Editor>,
+) {
+    let mut ranges = editor
+        .text_highlights::<T>(cx)
+        .map(|(_, ranges)| {
+            ranges
+                .iter()
+                .filter(|range| {
+                    let start = range.start.to_offset(snapshot);
+                    let end = range.end.to_offset(snapshot);
+                    excerpts.contains(&range.start.excerpt_id)
+                        && !rescanned_ranges
+                            .iter()
+                            .any(|rescanned| start <= rescanned.end && end >= rescanned.start)
+                })
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    ranges.extend(new_ranges);
+    if ranges.is_empty() {
+        editor.clear_highlights::<T>(cx);
+    } else {
+        ranges.sort_by(|a, b| a.start.cmp(&b.start, snapshot));
+        editor.highlight_text::<T>(ranges, style, cx);
+    }
+}
+
+fn clear_todo_highlights(editor: &mut Editor, cx: &mut ViewContext<Editor>) {
+    editor.clear_highlights::<TodoInfoHighlight>(cx);
+    editor.clear_highlights::<TodoHintHighlight>(cx);
+    editor.clear_highlights::<TodoWarningHighlight>(cx);
+    editor.clear_highlights::<TodoErrorHighlight>(cx);
+    editor.clear_highlights::<TodoSuccessHighlight>(cx);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::editor_tests::init_test;
+    use gpui::{Context, Hsla, TestAppContext};
+    use language::{Buffer, Language, LanguageConfig};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_keywords_pattern() {
+        assert_eq!(keywords_pattern([]), None);
+        let regex = Regex::new(&keywords_pattern(["TODO", "FIXME", "A+"]).unwrap()).unwrap();
+        let matches = regex
+            .find_iter("TODO: fix the FIXME, not TODOS or A+B")
+            .map(|keyword| keyword.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(matches, ["TODO", "FIXME", "A+"]);
+    }
+
+    #[gpui::test]
+    async fn test_todo_comments(cx: &mut gpui::TestAppContext) {
+        let theme =
+            SyntaxTheme::new_test(vec![("comment", Hsla::red()), ("string", Hsla::green())]);
+        let language = test_language();
+        language.set_theme(&theme);
+
+        let text = "// TODO: rename\nfn main() { println!(\"TODO\"); } // FIXME and XXX\n";
+        let buffer = cx.new_model(|cx| Buffer::local(text, cx).with_language(language, cx));
+        cx.condition(&buffer, |buffer, _| !buffer.is_parsing())
+            .await;
+
+        let regex = Regex::new(&keywords_pattern(["TODO", "FIXME"]).unwrap()).unwrap();
+        let todos = buffer.update(cx, |buffer, _| {
+            todo_comments(&buffer.snapshot(), 0..text.len(), &regex, &theme)
+        });
+        assert_eq!(
+            todos
+                .iter()
+                .map(|todo| (&text[todo.range.clone()], todo.keyword.as_str()))
+                .collect::<Vec<_>>(),
+            [("TODO", "TODO"), ("FIXME", "FIXME")]
+        );
+        assert_eq!(todos[1].range.start, text.find("FIXME").unwrap());
+    }
+
+    #[gpui::test]
+    async fn test_todo_highlights_after_edits(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        let language = test_language();
+        cx.update(|cx| language.set_theme(cx.theme().syntax()));
+
+        let text = "// TODO: one\nfn a() {}\n// FIXME: two\n";
+        let buffer = cx.new_model(|cx| Buffer::local(text, cx).with_language(language, cx));
+        let editor = cx.add_window(|cx| Editor::for_buffer(buffer.clone(), None, cx));
+        finish_scan(cx);
+        _ = editor.update(cx, |editor, cx| {
+            assert_eq!(highlighted_text::<TodoInfoHighlight>(editor, cx), ["TODO"]);
+            assert_eq!(
+                highlighted_text::<TodoErrorHighlight>(editor, cx),
+                ["FIXME"]
+            );
+            // Only the edited lines are scanned again, so this highlight isn't brought back.
+            editor.clear_highlights::<TodoErrorHighlight>(cx);
+        });
+
+        buffer.update(cx, |buffer, cx| {
+            let offset = text.find("fn a").unwrap();
+            buffer.edit([(offset..offset, "// HACK: three\n")], None, cx);
+        });
+        finish_scan(cx);
+        _ = editor.update(cx, |editor, cx| {
+            assert_eq!(highlighted_text::<TodoInfoHighlight>(editor, cx), ["TODO"]);
+            assert_eq!(
+                highlighted_text::<TodoWarningHighlight>(editor, cx),
+                ["HACK"]
+            );
+            assert!(highlighted_text::<TodoErrorHighlight>(editor, cx).is_empty());
+        });
+
+        buffer.update(cx, |buffer, cx| {
+            let offset = text.find("TODO").unwrap();
+            buffer.edit([(offset..offset + "TODO".len(), "DONE")], None, cx);
+        });
+        finish_scan(cx);
+        _ = editor.update(cx, |editor, cx| {
+            assert!(highlighted_text::<TodoInfoHighlight>(editor, cx).is_empty());
+            assert_eq!(
+                highlighted_text::<TodoWarningHighlight>(editor, cx),
+                ["HACK"]
+            );
+            // Scanning everything again, like when the settings change, brings back the rest.
+            refresh_todo_highlights(editor, cx);
+        });
+        finish_scan(cx);
+        _ = editor.update(cx, |editor, cx| {
+            assert_eq!(
+                highlighted_text::<TodoErrorHighlight>(editor, cx),
+                ["FIXME"]
+            );
+        });
+    }
+
+    fn finish_scan(cx: &mut TestAppContext) {
+        cx.executor().run_until_parked();
+        cx.executor().advance_clock(TODO_HIGHLIGHTS_DEBOUNCE);
+        cx.executor().run_until_parked();
+    }
+
+    fn test_language() -> Arc<Language> {
+        Arc::new(
+            Language::new(
+                LanguageConfig {
+                    name: "Test".into(),
+                    ..Default::default()
+                },
+                Some(tree_sitter_rust::language()),
+            )
+            .with_highlights_query(
+                r#"
+                (line_comment) @comment
+                (string_literal) @string
+                "#,
+            )
+            .unwrap(),
+        )
+    }
+
+    fn highlighted_text<T: 'static>(editor: &Editor, cx: &ViewContext<Editor>) -> Vec<String> {
+        let snapshot = editor.buffer.read(cx).snapshot(cx);
+        editor
+            .text_highlights::<T>(cx)
+            .map(|(_, ranges)| {
+                ranges
+                    .iter()
+                    .map(|range| snapshot.text_for_range(range.clone()).collect())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
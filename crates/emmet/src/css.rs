@@ -0,0 +1,356 @@
+use crate::escape_snippet_text;
+
+/// The properties that abbreviations expand to, by abbreviation.
+const PROPERTIES: &[(&str, &str)] = &[
+    ("ai", "align-items"),
+    ("ac", "align-content"),
+    ("as", "align-self"),
+    ("b", "bottom"),
+    ("bd", "border"),
+    ("bdb", "border-bottom"),
+    ("bdc", "border-color"),
+    ("bdl", "border-left"),
+    ("bdr", "border-right"),
+    ("bdrs", "border-radius"),
+    ("bdt", "border-top"),
+    ("bg", "background"),
+    ("bgc", "background-color"),
+    ("bgi", "background-image"),
+    ("bxsz", "box-sizing"),
+    ("c", "color"),
+    ("cur", "cursor"),
+    ("d", "display"),
+    ("fd", "flex-direction"),
+    ("ff", "font-family"),
+    ("fl", "float"),
+    ("fw", "font-weight"),
+    ("fx", "flex"),
+    ("fxg", "flex-grow"),
+    ("fxs", "flex-shrink"),
+    ("fxw", "flex-wrap"),
+    ("fz", "font-size"),
+    ("g", "gap"),
+    ("gtc", "grid-template-columns"),
+    ("h", "height"),
+    ("jc", "justify-content"),
+    ("l", "left"),
+    ("lh", "line-height"),
+    ("m", "margin"),
+    ("mb", "margin-bottom"),
+    ("mah", "max-height"),
+    ("maw", "max-width"),
+    ("mih", "min-height"),
+    ("miw", "min-width"),
+    ("ml", "margin-left"),
+    ("mr", "margin-right"),
+    ("mt", "margin-top"),
+    ("op", "opacity"),
+    ("ord", "order"),
+    ("ov", "overflow"),
+    ("p", "padding"),
+    ("pb", "padding-bottom"),
+    ("pl", "padding-left"),
+    ("pos", "position"),
+    ("pr", "padding-right"),
+    ("pt", "padding-top"),
+    ("r", "right"),
+    ("t", "top"),
+    ("ta", "text-align"),
+    ("td", "text-decoration"),
+    ("tt", "text-transform"),
+    ("v", "visibility"),
+    ("w", "width"),
+    ("whs", "white-space"),
+    ("z", "z-index"),
+];
+
+/// The keywords that abbreviations expand to, by property and abbreviation.
+const KEYWORDS: &[(&str, &[(&str, &str)])] = &[
+    (
+        "display",
+        &[
+            ("b", "block"),
+            ("f", "flex"),
+            ("g", "grid"),
+            ("i", "inline"),
+            ("ib", "inline-block"),
+            ("if", "inline-flex"),
+            ("n", "none"),
+            ("t", "table"),
+        ],
+    ),
+    (
+        "position",
+        &[
+            ("a", "absolute"),
+            ("f", "fixed"),
+            ("r", "relative"),
+            ("s", "static"),
+            ("st", "sticky"),
+        ],
+    ),
+    (
+        "text-align",
+        &[
+            ("c", "center"),
+            ("j", "justify"),
+            ("l", "left"),
+            ("r", "right"),
+        ],
+    ),
+    ("float", &[("l", "left"), ("n", "none"), ("r", "right")]),
+    ("font-weight", &[("b", "bold"), ("n", "normal")]),
+    (
+        "overflow",
+        &[
+            ("a", "auto"),
+            ("h", "hidden"),
+            ("s", "scroll"),
+            ("v", "visible"),
+        ],
+    ),
+    (
+        "cursor",
+        &[("d", "default"), ("p", "pointer"), ("t", "text")],
+    ),
+    (
+        "justify-content",
+        &[
+            ("c", "center"),
+            ("fe", "flex-end"),
+            ("fs", "flex-start"),
+            ("sa", "space-around"),
+            ("sb", "space-between"),
+            ("se", "space-evenly"),
+        ],
+    ),
+    (
+        "align-items",
+        &[
+            ("b", "baseline"),
+            ("c", "center"),
+            ("fe", "flex-end"),
+            ("fs", "flex-start"),
+            ("s", "stretch"),
+        ],
+    ),
+    (
+        "flex-direction",
+        &[
+            ("c", "column"),
+            ("cr", "column-reverse"),
+            ("r", "row"),
+            ("rr", "row-reverse"),
+        ],
+    ),
+    ("flex-wrap", &[("nw", "nowrap"), ("w", "wrap")]),
+    ("visibility", &[("h", "hidden"), ("v", "visible")]),
+    (
+        "white-space",
+        &[("n", "normal"), ("nw", "nowrap"), ("p", "pre")],
+    ),
+    ("text-decoration", &[("n", "none"), ("u", "underline")]),
+    (
+        "text-transform",
+        &[("c", "capitalize"), ("l", "lowercase"), ("u", "uppercase")],
+    ),
+    ("box-sizing", &[("bb", "border-box"), ("cb", "content-box")]),
+];
+
+/// The keywords that any property's value can be abbreviated to.
+const COMMON_KEYWORDS: &[(&str, &str)] = &[("a", "auto"), ("i", "inherit"), ("n", "none")];
+
+/// The properties whose numbers have no unit unless one is given.
+const UNITLESS_PROPERTIES: &[&str] = &[
+    "flex",
+    "flex-grow",
+    "flex-shrink",
+    "font-weight",
+    "line-height",
+    "opacity",
+    "order",
+    "z-index",
+];
+
+pub(crate) fn extract_abbreviation(line_prefix: &str) -> Option<&str> {
+    let start = line_prefix
+        .rfind(|c: char| !(c.is_ascii_alphanumeric() || "-:#.!%+".contains(c)))
+        .map_or(0, |ix| ix + 1);
+    // Properties are only expanded where they start a line, so that values
+    // like `0 auto` aren't mistaken for abbreviations.
+    if !line_prefix[..start].trim().is_empty() {
+        return None;
+    }
+    let abbreviation = &line_prefix[start..];
+    (!abbreviation.is_empty()).then_some(abbreviation)
+}
+
+pub(crate) fn expand(abbreviation: &str, base_indent: &str) -> Option<String> {
+    let mut next_tabstop = 1;
+    let declarations = abbreviation
+        .split('+')
+        .map(|part| expand_declaration(part, &mut next_tabstop))
+        .collect::<Option<Vec<_>>>()?;
+    Some(declarations.join(&format!("\n{base_indent}")))
+}
+
+/// Expands an abbreviation like `m10-auto`, `d:f` or `c#f` into a declaration.
+fn expand_declaration(abbreviation: &str, next_tabstop: &mut usize) -> Option<String> {
+    let (abbreviation, important) = match abbreviation.strip_suffix('!') {
+        Some(abbreviation) => (abbreviation, " !important"),
+        None => (abbreviation, ""),
+    };
+
+    let (property, value) = match abbreviation.split_once(':') {
+        Some((property, keyword)) => {
+            let property = property_name(property)?;
+            (property, keyword_value(property, keyword))
+        }
+        None => {
+            let name_len = abbreviation
+                .find(|c: char| !c.is_ascii_lowercase())
+                .unwrap_or(abbreviation.len());
+            let property = property_name(&abbreviation[..name_len])?;
+            let value = &abbreviation[name_len..];
+            let value = if let Some(color) = value.strip_prefix('#') {
+                color_value(color)?
+            } else if value.is_empty() {
+                String::new()
+            } else {
+                numeric_value(property, value)?
+            };
+            (property, value)
+        }
+    };
+
+    let value = if value.is_empty() {
+        let tabstop = format!("${next_tabstop}");
+        *next_tabstop += 1;
+        tabstop
+    } else {
+        escape_snippet_text(&value)
+    };
+    Some(format!("{property}: {value}{important};"))
+}
+
+fn property_name(abbreviation: &str) -> Option<&'static str> {
+    PROPERTIES
+        .iter()
+        .find(|(name, property)| *name == abbreviation || *property == abbreviation)
+        .map(|(_, property)| *property)
+}
+
+fn keyword_value(property: &str, keyword: &str) -> String {
+    KEYWORDS
+        .iter()
+        .find(|(name, _)| *name == property)
+        .into_iter()
+        .flat_map(|(_, keywords)| keywords.iter())
+        .chain(COMMON_KEYWORDS)
+        .find(|(abbreviation, _)| *abbreviation == keyword)
+        .map_or_else(|| keyword.to_string(), |(_, keyword)| keyword.to_string())
+}
+
+/// Expands the shorthand of a hex color, like `f` for `#fff` or `e0` for `#e0e0e0`.
+fn color_value(color: &str) -> Option<String> {
+    if !color.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let color = match color.len() {
+        1 | 2 => color.repeat(3),
+        3 | 4 | 6 | 8 => color.to_string(),
+        _ => return None,
+    };
+    Some(format!("#{color}"))
+}
+
+/// Expands dash-separated numbers, like `10-20` for `10px 20px`. A number
+/// can have a unit, like `10p` for `10%`, `1.5e` for `1.5em` or `2r` for
+/// `2rem`, and be negative after a dash, like `10--5` for `10px -5px`.
+fn numeric_value(property: &str, value: &str) -> Option<String> {
+    let mut values = Vec::new();
+    let mut rest = value;
+    loop {
+        let (sign, unsigned) = match rest.strip_prefix('-') {
+            Some(unsigned) => ("-", unsigned),
+            None => ("", rest),
+        };
+        let number_len = unsigned
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(unsigned.len());
+        let unit_len = unsigned[number_len..]
+            .find(|c: char| !c.is_ascii_alphabetic() && c != '%')
+            .unwrap_or(unsigned.len() - number_len);
+        let number = &unsigned[..number_len];
+        let unit = &unsigned[number_len..number_len + unit_len];
+        if number.is_empty() {
+            // Keywords, like the `auto` of `m0-auto`.
+            if unit.is_empty() || !sign.is_empty() {
+                return None;
+            }
+            values.push(keyword_value(property, unit));
+        } else {
+            let unit = match unit {
+                "" if number.parse::<f64>().ok() == Some(0.) => "",
+                "" if UNITLESS_PROPERTIES.contains(&property) => "",
+                "" => "px",
+                "p" => "%",
+                "e" => "em",
+                "r" => "rem",
+                "x" => "ex",
+                unit => unit,
+            };
+            values.push(format!("{sign}{number}{unit}"));
+        }
+
+        rest = &unsigned[number_len + unit_len..];
+        if rest.is_empty() {
+            break;
+        }
+        rest = rest.strip_prefix('-')?;
+    }
+    Some(values.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_abbreviation() {
+        assert_eq!(extract_abbreviation("  m10-20"), Some("m10-20"));
+        assert_eq!(extract_abbreviation("\td:f+jc:c"), Some("d:f+jc:c"));
+        assert_eq!(extract_abbreviation("  margin: 0 a"), None);
+        assert_eq!(extract_abbreviation("  "), None);
+    }
+
+    #[test]
+    fn test_expand() {
+        let expand = |abbreviation| expand(abbreviation, "  ");
+        assert_eq!(expand("m").as_deref(), Some("margin: $1;"));
+        assert_eq!(expand("m10").as_deref(), Some("margin: 10px;"));
+        assert_eq!(expand("m0-a").as_deref(), Some("margin: 0 auto;"));
+        assert_eq!(
+            expand("p10-20p--5").as_deref(),
+            Some("padding: 10px 20% -5px;")
+        );
+        assert_eq!(expand("fz1.5r").as_deref(), Some("font-size: 1.5rem;"));
+        assert_eq!(expand("lh1.5").as_deref(), Some("line-height: 1.5;"));
+        assert_eq!(expand("w100vh").as_deref(), Some("width: 100vh;"));
+        assert_eq!(expand("d:f").as_deref(), Some("display: flex;"));
+        assert_eq!(expand("pos:a").as_deref(), Some("position: absolute;"));
+        assert_eq!(expand("ov:n").as_deref(), Some("overflow: none;"));
+        assert_eq!(expand("c#f").as_deref(), Some("color: #fff;"));
+        assert_eq!(
+            expand("bgc#e0").as_deref(),
+            Some("background-color: #e0e0e0;")
+        );
+        assert_eq!(expand("z10!").as_deref(), Some("z-index: 10 !important;"));
+        assert_eq!(
+            expand("d:f+jc:sb+g").as_deref(),
+            Some("display: flex;\n  justify-content: space-between;\n  gap: $1;")
+        );
+        assert_eq!(expand("nope10"), None);
+        assert_eq!(expand("c#xyz"), None);
+    }
+}
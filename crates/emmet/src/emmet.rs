@@ -0,0 +1,55 @@
+//! Expands [Emmet](https://emmet.io) abbreviations, like `ul>li.item*3`, into
+//! snippets whose tabstops visit the places left to fill in.
+
+mod css;
+mod markup;
+
+/// The flavor of abbreviations to expand.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Syntax {
+    /// HTML elements.
+    Html,
+    /// HTML elements, with JSX attribute names (`className`, `htmlFor`) and
+    /// self-closing void elements.
+    Jsx,
+    /// CSS properties, like `m10` for `margin: 10px;`.
+    Css,
+}
+
+/// Returns the abbreviation that ends at the end of `line_prefix`, the text of
+/// a line up to the cursor, if there is one to expand.
+pub fn extract_abbreviation(line_prefix: &str, syntax: Syntax) -> Option<&str> {
+    match syntax {
+        Syntax::Html | Syntax::Jsx => markup::extract_abbreviation(line_prefix),
+        Syntax::Css => css::extract_abbreviation(line_prefix),
+    }
+}
+
+/// Expands the abbreviation into the source of a snippet, using `$1`-style
+/// tabstops. Every line but the first is prefixed with `base_indent`, and
+/// nested elements are indented by `indent`.
+///
+/// Returns `None` if the text isn't an abbreviation this module understands.
+pub fn expand(
+    abbreviation: &str,
+    syntax: Syntax,
+    indent: &str,
+    base_indent: &str,
+) -> Option<String> {
+    match syntax {
+        Syntax::Html | Syntax::Jsx => markup::expand(abbreviation, syntax, indent, base_indent),
+        Syntax::Css => css::expand(abbreviation, base_indent),
+    }
+}
+
+/// Escapes the characters that have a meaning in snippet sources.
+fn escape_snippet_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '$' | '\\' | '}') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
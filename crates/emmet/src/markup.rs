@@ -0,0 +1,762 @@
+use crate::{escape_snippet_text, Syntax};
+
+/// The elements that have no contents or closing tag.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// The elements that are laid out inline, whose children are spans unless
+/// their tag is given.
+const INLINE_ELEMENTS: &[&str] = &[
+    "a", "abbr", "b", "bdi", "bdo", "cite", "code", "em", "i", "kbd", "label", "mark", "q", "s",
+    "samp", "small", "span", "strong", "sub", "sup", "u", "var",
+];
+
+/// The elements that are expanded when their tag is written on its own. Other
+/// words need an operator, an id, a class or attributes to be expanded, so that
+/// pressing tab after ordinary text keeps inserting a tab.
+const KNOWN_ELEMENTS: &[&str] = &[
+    "a",
+    "abbr",
+    "address",
+    "area",
+    "article",
+    "aside",
+    "audio",
+    "b",
+    "base",
+    "blockquote",
+    "body",
+    "br",
+    "button",
+    "canvas",
+    "caption",
+    "cite",
+    "code",
+    "col",
+    "colgroup",
+    "datalist",
+    "dd",
+    "del",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "embed",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hr",
+    "html",
+    "i",
+    "iframe",
+    "img",
+    "input",
+    "ins",
+    "kbd",
+    "label",
+    "legend",
+    "li",
+    "link",
+    "main",
+    "mark",
+    "menu",
+    "meta",
+    "nav",
+    "noscript",
+    "object",
+    "ol",
+    "optgroup",
+    "option",
+    "output",
+    "p",
+    "param",
+    "picture",
+    "pre",
+    "progress",
+    "q",
+    "s",
+    "samp",
+    "script",
+    "section",
+    "select",
+    "small",
+    "source",
+    "span",
+    "strong",
+    "style",
+    "sub",
+    "summary",
+    "sup",
+    "svg",
+    "table",
+    "tbody",
+    "td",
+    "template",
+    "textarea",
+    "tfoot",
+    "th",
+    "thead",
+    "time",
+    "title",
+    "tr",
+    "track",
+    "u",
+    "ul",
+    "var",
+    "video",
+    "wbr",
+];
+
+/// The attributes that elements are given unless the abbreviation sets them.
+/// Empty values become tabstops.
+const DEFAULT_ATTRIBUTES: &[(&str, &[(&str, &str)])] = &[
+    ("a", &[("href", "")]),
+    ("audio", &[("src", "")]),
+    ("form", &[("action", "")]),
+    ("iframe", &[("src", "")]),
+    ("img", &[("src", ""), ("alt", "")]),
+    ("input", &[("type", "text")]),
+    ("label", &[("for", "")]),
+    ("link", &[("rel", "stylesheet"), ("href", "")]),
+    ("option", &[("value", "")]),
+    ("video", &[("src", "")]),
+];
+
+#[derive(Debug, Default)]
+struct Element {
+    name: Option<String>,
+    id: Option<String>,
+    classes: Vec<String>,
+    /// Attributes without a value, like `disabled`, are `None`.
+    attributes: Vec<(String, Option<String>)>,
+    text: Option<String>,
+}
+
+#[derive(Debug)]
+enum NodeKind {
+    Group,
+    Element(Element),
+}
+
+#[derive(Debug)]
+struct Node {
+    kind: NodeKind,
+    multiplier: usize,
+    children: Vec<usize>,
+}
+
+pub(crate) fn extract_abbreviation(line_prefix: &str) -> Option<&str> {
+    let mut start = line_prefix.len();
+    // The number of brackets and braces that are open, scanning backwards.
+    let mut depth = 0;
+    for (ix, c) in line_prefix.char_indices().rev() {
+        match c {
+            ']' | '}' => depth += 1,
+            '[' | '{' if depth > 0 => depth -= 1,
+            _ if depth > 0 => {}
+            '>' if ends_tag(&line_prefix[..ix]) => break,
+            c if c.is_ascii_alphanumeric() || "-_:.#>+*^$@!()".contains(c) => {}
+            _ => break,
+        }
+        start = ix;
+    }
+    if depth > 0 {
+        return None;
+    }
+
+    // Leave out the parentheses that belong to the surrounding code, like in `return (div`.
+    let mut abbreviation = &line_prefix[start..];
+    while abbreviation.starts_with('(')
+        && abbreviation.matches('(').count() > abbreviation.matches(')').count()
+    {
+        abbreviation = &abbreviation[1..];
+    }
+
+    let preceding_char = line_prefix[..line_prefix.len() - abbreviation.len()]
+        .chars()
+        .next_back();
+    if !preceding_char.map_or(true, |c| c.is_whitespace() || c == '>' || c == '(') {
+        return None;
+    }
+    (!abbreviation.is_empty()).then_some(abbreviation)
+}
+
+/// Whether the text ends inside of a tag, so that a `>` following it closes the tag.
+fn ends_tag(text: &str) -> bool {
+    text.rfind('<') > text.rfind('>')
+}
+
+pub(crate) fn expand(
+    abbreviation: &str,
+    syntax: Syntax,
+    indent: &str,
+    base_indent: &str,
+) -> Option<String> {
+    if abbreviation == "!" {
+        return (syntax == Syntax::Html).then(|| html_document(indent, base_indent));
+    }
+
+    let nodes = Parser::parse(abbreviation)?;
+    if let [root] = nodes[0].children.as_slice() {
+        if let Node {
+            kind: NodeKind::Element(element),
+            multiplier: 1,
+            children,
+        } = &nodes[*root]
+        {
+            let is_bare_tag = element.id.is_none()
+                && element.classes.is_empty()
+                && element.attributes.is_empty()
+                && element.text.is_none()
+                && children.is_empty();
+            let is_known_tag = element
+                .name
+                .as_deref()
+                .map_or(false, |name| KNOWN_ELEMENTS.contains(&name));
+            if is_bare_tag && !is_known_tag {
+                return None;
+            }
+        }
+    }
+
+    let mut renderer = Renderer {
+        syntax,
+        indent,
+        base_indent,
+        output: String::new(),
+        next_tabstop: 1,
+    };
+    renderer.render_children(&nodes, &nodes[0].children, None, (0, 1), 0);
+    Some(renderer.output)
+}
+
+fn html_document(indent: &str, base_indent: &str) -> String {
+    let lines = [
+        (0, "<!DOCTYPE html>"),
+        (0, "<html lang=\"en\">"),
+        (0, "<head>"),
+        (1, "<meta charset=\"UTF-8\">"),
+        (
+            1,
+            "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">",
+        ),
+        (1, "<title>${1:Document}</title>"),
+        (0, "</head>"),
+        (0, "<body>"),
+        (1, "$0"),
+        (0, "</body>"),
+        (0, "</html>"),
+    ];
+    let mut document = String::new();
+    for (ix, (depth, line)) in lines.into_iter().enumerate() {
+        if ix > 0 {
+            document.push('\n');
+            document.push_str(base_indent);
+        }
+        document.push_str(&indent.repeat(depth));
+        document.push_str(line);
+    }
+    document
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    position: usize,
+    nodes: Vec<Node>,
+}
+
+impl<'a> Parser<'a> {
+    /// Parses the abbreviation into a tree of nodes, the first being its root.
+    fn parse(source: &'a str) -> Option<Vec<Node>> {
+        let mut parser = Parser {
+            source,
+            position: 0,
+            nodes: Vec::new(),
+        };
+        let root = parser.push_node(NodeKind::Group);
+        parser.parse_children(root)?;
+        (parser.position == source.len()).then_some(parser.nodes)
+    }
+
+    fn push_node(&mut self, kind: NodeKind) -> usize {
+        self.nodes.push(Node {
+            kind,
+            multiplier: 1,
+            children: Vec::new(),
+        });
+        self.nodes.len() - 1
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.source[self.position..].chars().next()
+    }
+
+    fn next_if(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.position += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let rest = &self.source[self.position..];
+        let len = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        self.position += len;
+        &rest[..len]
+    }
+
+    /// Parses items separated by the `>`, `+` and `^` operators.
+    fn parse_children(&mut self, parent: usize) -> Option<()> {
+        let mut parents = vec![parent];
+        loop {
+            let node = self.parse_item()?;
+            self.nodes[*parents.last()?].children.push(node);
+            match self.peek() {
+                Some('>') => {
+                    self.position += 1;
+                    parents.push(node);
+                }
+                Some('+') => self.position += 1,
+                Some('^') => {
+                    while self.next_if('^') {
+                        if parents.len() > 1 {
+                            parents.pop();
+                        }
+                    }
+                }
+                _ => return Some(()),
+            }
+        }
+    }
+
+    /// Parses an element or a parenthesized group, and its multiplier.
+    fn parse_item(&mut self) -> Option<usize> {
+        let node = if self.next_if('(') {
+            let group = self.push_node(NodeKind::Group);
+            self.parse_children(group)?;
+            if !self.next_if(')') {
+                return None;
+            }
+            group
+        } else {
+            let element = self.parse_element()?;
+            self.push_node(NodeKind::Element(element))
+        };
+
+        if self.next_if('*') {
+            let multiplier = self.take_while(|c| c.is_ascii_digit());
+            self.nodes[node].multiplier = multiplier.parse().ok().filter(|count| *count > 0)?;
+        }
+        Some(node)
+    }
+
+    fn parse_element(&mut self) -> Option<Element> {
+        let mut element = Element::default();
+        let name = self.take_while(is_name_char);
+        let mut is_empty = name.is_empty();
+        if !name.is_empty() {
+            element.name = Some(name.to_string());
+        }
+
+        loop {
+            if self.next_if('#') {
+                let id = self.take_while(is_name_char);
+                if id.is_empty() {
+                    return None;
+                }
+                element.id = Some(id.to_string());
+            } else if self.next_if('.') {
+                let class = self.take_while(is_name_char);
+                if class.is_empty() {
+                    return None;
+                }
+                element.classes.push(class.to_string());
+            } else if self.next_if('[') {
+                self.parse_attributes(&mut element)?;
+            } else if self.next_if('{') {
+                let len = self.source[self.position..].find('}')?;
+                element.text = Some(self.source[self.position..self.position + len].to_string());
+                self.position += len + 1;
+            } else {
+                break;
+            }
+            is_empty = false;
+        }
+
+        (!is_empty).then_some(element)
+    }
+
+    fn parse_attributes(&mut self, element: &mut Element) -> Option<()> {
+        loop {
+            self.take_while(char::is_whitespace);
+            if self.next_if(']') {
+                return Some(());
+            }
+
+            let name =
+                self.take_while(|c| !c.is_whitespace() && !matches!(c, '=' | ']' | '"' | '\''));
+            if name.is_empty() {
+                return None;
+            }
+            let value = if self.next_if('=') {
+                Some(self.parse_attribute_value()?)
+            } else {
+                None
+            };
+            element.attributes.push((name.to_string(), value));
+        }
+    }
+
+    fn parse_attribute_value(&mut self) -> Option<String> {
+        match self.peek() {
+            Some(quote @ ('"' | '\'')) => {
+                self.position += 1;
+                let len = self.source[self.position..].find(quote)?;
+                let value = self.source[self.position..self.position + len].to_string();
+                self.position += len + 1;
+                Some(value)
+            }
+            _ => Some(
+                self.take_while(|c| !c.is_whitespace() && c != ']')
+                    .to_string(),
+            ),
+        }
+    }
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '$' | '@')
+}
+
+struct Renderer<'a> {
+    syntax: Syntax,
+    indent: &'a str,
+    base_indent: &'a str,
+    output: String,
+    next_tabstop: usize,
+}
+
+impl Renderer<'_> {
+    /// Renders the given nodes, each repeated by its multiplier. `numbering`
+    /// is the index and count of the nearest repeated ancestor, for the `$`
+    /// placeholders of elements that aren't repeated themselves.
+    fn render_children(
+        &mut self,
+        nodes: &[Node],
+        children: &[usize],
+        parent_name: Option<&str>,
+        numbering: (usize, usize),
+        depth: usize,
+    ) {
+        for &child in children {
+            let node = &nodes[child];
+            for ix in 0..node.multiplier {
+                let numbering = if node.multiplier > 1 {
+                    (ix, node.multiplier)
+                } else {
+                    numbering
+                };
+                match &node.kind {
+                    NodeKind::Group => {
+                        self.render_children(nodes, &node.children, parent_name, numbering, depth)
+                    }
+                    NodeKind::Element(element) => self.render_element(
+                        nodes,
+                        element,
+                        &node.children,
+                        parent_name,
+                        numbering,
+                        depth,
+                    ),
+                }
+            }
+        }
+    }
+
+    fn render_element(
+        &mut self,
+        nodes: &[Node],
+        element: &Element,
+        children: &[usize],
+        parent_name: Option<&str>,
+        numbering: (usize, usize),
+        depth: usize,
+    ) {
+        let (index, count) = numbering;
+        let name = match &element.name {
+            Some(name) => number(name, index, count),
+            None => implicit_name(parent_name).to_string(),
+        };
+
+        let mut attributes = DEFAULT_ATTRIBUTES
+            .iter()
+            .find(|(element_name, _)| *element_name == name)
+            .map(|(_, attributes)| {
+                attributes
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), Some(value.to_string())))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let mut set_attribute = |name: &str, value: Option<String>| match attributes
+            .iter_mut()
+            .find(|(existing, _)| existing == name)
+        {
+            Some((_, existing_value)) => *existing_value = value,
+            None => attributes.push((name.to_string(), value)),
+        };
+        if let Some(id) = &element.id {
+            set_attribute("id", Some(number(id, index, count)));
+        }
+        if !element.classes.is_empty() {
+            let classes = element
+                .classes
+                .iter()
+                .map(|class| number(class, index, count))
+                .collect::<Vec<_>>();
+            set_attribute("class", Some(classes.join(" ")));
+        }
+        for (name, value) in &element.attributes {
+            set_attribute(
+                name,
+                value.as_ref().map(|value| number(value, index, count)),
+            );
+        }
+
+        self.start_line(depth);
+        self.output.push('<');
+        self.output.push_str(&name);
+        for (attribute_name, value) in attributes {
+            self.output.push(' ');
+            self.output.push_str(self.attribute_name(&attribute_name));
+            if let Some(value) = value {
+                self.output.push_str("=\"");
+                if value.is_empty() {
+                    self.push_tabstop();
+                } else {
+                    self.output.push_str(&escape_snippet_text(&value));
+                }
+                self.output.push('"');
+            }
+        }
+
+        if VOID_ELEMENTS.contains(&name.as_str()) {
+            self.output.push_str(if self.syntax == Syntax::Jsx {
+                " />"
+            } else {
+                ">"
+            });
+            return;
+        }
+
+        self.output.push('>');
+        let text = element
+            .text
+            .as_ref()
+            .map(|text| escape_snippet_text(&number(text, index, count)));
+        if children.is_empty() {
+            match text {
+                Some(text) => self.output.push_str(&text),
+                None => self.push_tabstop(),
+            }
+        } else {
+            if let Some(text) = text {
+                self.start_line(depth + 1);
+                self.output.push_str(&text);
+            }
+            self.render_children(nodes, children, Some(&name), numbering, depth + 1);
+            self.start_line(depth);
+        }
+        self.output.push_str("</");
+        self.output.push_str(&name);
+        self.output.push('>');
+    }
+
+    fn attribute_name<'b>(&self, name: &'b str) -> &'b str {
+        match (self.syntax, name) {
+            (Syntax::Jsx, "class") => "className",
+            (Syntax::Jsx, "for") => "htmlFor",
+            _ => name,
+        }
+    }
+
+    fn start_line(&mut self, depth: usize) {
+        if !self.output.is_empty() {
+            self.output.push('\n');
+            self.output.push_str(self.base_indent);
+        }
+        for _ in 0..depth {
+            self.output.push_str(self.indent);
+        }
+    }
+
+    fn push_tabstop(&mut self) {
+        self.output.push_str(&format!("${}", self.next_tabstop));
+        self.next_tabstop += 1;
+    }
+}
+
+/// The tag of elements written without one, like `.item`, which depends on
+/// the tag of their parent.
+fn implicit_name(parent_name: Option<&str>) -> &'static str {
+    match parent_name {
+        Some("ul" | "ol") => "li",
+        Some("table" | "tbody" | "thead" | "tfoot") => "tr",
+        Some("tr") => "td",
+        Some("select" | "optgroup" | "datalist") => "option",
+        Some(parent_name) if INLINE_ELEMENTS.contains(&parent_name) => "span",
+        _ => "div",
+    }
+}
+
+/// Replaces the `$` placeholders of repeated elements with their 1-based
+/// index, zero-padded to the number of `$`s. `$@-` counts down instead, and
+/// `$@3` starts counting from 3.
+fn number(text: &str, index: usize, count: usize) -> String {
+    let mut numbered = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(ix) = rest.find('$') {
+        numbered.push_str(&rest[..ix]);
+        rest = &rest[ix..];
+        let width = rest.len() - rest.trim_start_matches('$').len();
+        rest = &rest[width..];
+
+        let mut reverse = false;
+        let mut start = 1;
+        if let Some(modifiers) = rest.strip_prefix('@') {
+            rest = modifiers;
+            if let Some(modifiers) = rest.strip_prefix('-') {
+                reverse = true;
+                rest = modifiers;
+            }
+            let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            if let Ok(digits) = rest[..digits].parse() {
+                start = digits;
+            }
+            rest = &rest[digits..];
+        }
+
+        let value = if reverse {
+            start + count - 1 - index
+        } else {
+            start + index
+        };
+        numbered.push_str(&format!("{value:0width$}"));
+    }
+    numbered.push_str(rest);
+    numbered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand_html(abbreviation: &str) -> Option<String> {
+        expand(abbreviation, Syntax::Html, "  ", "")
+    }
+
+    #[test]
+    fn test_extract_abbreviation() {
+        assert_eq!(extract_abbreviation("ul>li*3"), Some("ul>li*3"));
+        assert_eq!(extract_abbreviation("  div.a+p"), Some("div.a+p"));
+        assert_eq!(extract_abbreviation("<div>span.x"), Some("span.x"));
+        assert_eq!(extract_abbreviation("return (div.card"), Some("div.card"));
+        assert_eq!(extract_abbreviation("(a+b)*2"), Some("(a+b)*2"));
+        assert_eq!(
+            extract_abbreviation("a[href=\"#\" title=x]{click me}"),
+            Some("a[href=\"#\" title=x]{click me}")
+        );
+        assert_eq!(extract_abbreviation("x=div"), None);
+        assert_eq!(extract_abbreviation("  "), None);
+    }
+
+    #[test]
+    fn test_expand_elements() {
+        assert_eq!(expand_html("div").as_deref(), Some("<div>$1</div>"));
+        assert_eq!(
+            expand_html("div#main.a.b").as_deref(),
+            Some("<div id=\"main\" class=\"a b\">$1</div>")
+        );
+        assert_eq!(
+            expand_html("a{Home}").as_deref(),
+            Some("<a href=\"$1\">Home</a>")
+        );
+        assert_eq!(
+            expand_html("img.logo").as_deref(),
+            Some("<img src=\"$1\" alt=\"$2\" class=\"logo\">")
+        );
+        assert_eq!(
+            expand_html("input[type=email required]").as_deref(),
+            Some("<input type=\"email\" required>")
+        );
+        assert_eq!(
+            expand_html("p{C:\\Users}").as_deref(),
+            Some("<p>C:\\\\Users</p>")
+        );
+        // Words that aren't tags are left alone.
+        assert_eq!(expand_html("hello"), None);
+        assert_eq!(expand_html("div>"), None);
+    }
+
+    #[test]
+    fn test_expand_nesting() {
+        assert_eq!(
+            expand_html("ul>li.item$*3").as_deref(),
+            Some(
+                "<ul>\n  <li class=\"item1\">$1</li>\n  <li class=\"item2\">$2</li>\n  <li class=\"item3\">$3</li>\n</ul>"
+            )
+        );
+        assert_eq!(
+            expand_html("div>p+span^h1").as_deref(),
+            Some("<div>\n  <p>$1</p>\n  <span>$2</span>\n</div>\n<h1>$3</h1>")
+        );
+        assert_eq!(
+            expand_html("(dt+dd)*2").as_deref(),
+            Some("<dt>$1</dt>\n<dd>$2</dd>\n<dt>$3</dt>\n<dd>$4</dd>")
+        );
+        assert_eq!(
+            expand_html("table>.row>.cell").as_deref(),
+            Some(
+                "<table>\n  <tr class=\"row\">\n    <td class=\"cell\">$1</td>\n  </tr>\n</table>"
+            )
+        );
+        assert_eq!(
+            expand("ul>li", Syntax::Html, "\t", "    ").as_deref(),
+            Some("<ul>\n    \t<li>$1</li>\n    </ul>")
+        );
+    }
+
+    #[test]
+    fn test_expand_jsx() {
+        assert_eq!(
+            expand("label.name+input", Syntax::Jsx, "  ", "").as_deref(),
+            Some("<label htmlFor=\"$1\" className=\"name\">$2</label>\n<input type=\"text\" />")
+        );
+        assert_eq!(expand("!", Syntax::Jsx, "  ", ""), None);
+    }
+
+    #[test]
+    fn test_number() {
+        assert_eq!(number("item$", 0, 3), "item1");
+        assert_eq!(number("item$$", 1, 3), "item02");
+        assert_eq!(number("item$@-", 0, 3), "item3");
+        assert_eq!(number("item$@3", 1, 3), "item4");
+        assert_eq!(number("plain", 1, 3), "plain");
+    }
+}
@@ -0,0 +1,20 @@
+use std::sync::Arc;
+
+/// A command that an extension declares in its manifest, which the extension runs when picked.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtensionCommand {
+    pub extension_id: Arc<str>,
+    pub id: Arc<str>,
+    pub name: String,
+}
+
+/// An item that an extension shows in the status bar.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtensionStatusBarItem {
+    pub extension_id: Arc<str>,
+    pub id: Arc<str>,
+    pub text: String,
+    pub tooltip: Option<String>,
+    /// The ID of the extension's command to run when the item is clicked.
+    pub command: Option<Arc<str>>,
+}
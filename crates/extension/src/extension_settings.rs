@@ -16,6 +16,12 @@ pub struct ExtensionSettings {
     pub auto_install_extensions: HashMap<Arc<str>, bool>,
     #[serde(default)]
     pub auto_update_extensions: HashMap<Arc<str>, bool>,
+    /// The installed extensions that should be loaded.
+    ///
+    /// Extensions that are set to `false` stay installed, but don't provide
+    /// any of their languages, themes, or language servers.
+    #[serde(default)]
+    pub enabled_extensions: HashMap<Arc<str>, bool>,
 }
 
 impl ExtensionSettings {
@@ -33,6 +39,14 @@ impl ExtensionSettings {
             .copied()
             .unwrap_or(true)
     }
+
+    /// Returns whether the given extension should be loaded, when it is installed.
+    pub fn is_enabled(&self, extension_id: &str) -> bool {
+        self.enabled_extensions
+            .get(extension_id)
+            .copied()
+            .unwrap_or(true)
+    }
 }
 
 impl Settings for ExtensionSettings {
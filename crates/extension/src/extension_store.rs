@@ -1,4 +1,5 @@
 pub mod extension_builder;
+mod extension_commands;
 mod extension_lsp_adapter;
 mod extension_manifest;
 mod extension_settings;
@@ -40,7 +41,7 @@ use project::ContextProviderWithTasks;
 use release_channel::ReleaseChannel;
 use semantic_version::SemanticVersion;
 use serde::{Deserialize, Serialize};
-use settings::Settings;
+use settings::{Settings, SettingsStore};
 use std::ops::RangeInclusive;
 use std::str::FromStr;
 use std::{
@@ -54,9 +55,10 @@ use url::Url;
 use util::{maybe, ResultExt};
 use wasm_host::{
     wit::{is_supported_wasm_api_version, wasm_api_version_range},
-    WasmExtension, WasmHost,
+    StatusBarItemUpdate, WasmExtension, WasmHost,
 };
 
+pub use extension_commands::{ExtensionCommand, ExtensionStatusBarItem};
 pub use extension_manifest::{
     ExtensionLibraryKind, ExtensionManifest, GrammarManifestEntry, OldExtensionManifest,
 };
@@ -112,8 +114,10 @@ pub struct ExtensionStore {
     theme_registry: Arc<ThemeRegistry>,
     slash_command_registry: Arc<SlashCommandRegistry>,
     modified_extensions: HashSet<Arc<str>>,
+    disabled_extensions: HashSet<Arc<str>>,
     wasm_host: Arc<WasmHost>,
     wasm_extensions: Vec<(Arc<ExtensionManifest>, WasmExtension)>,
+    status_bar_items: Vec<ExtensionStatusBarItem>,
     tasks: Vec<Task<()>>,
 }
 
@@ -229,6 +233,7 @@ impl ExtensionStore {
         let index_path = extensions_dir.join("index.json");
 
         let (reload_tx, mut reload_rx) = unbounded();
+        let (status_bar_item_tx, mut status_bar_item_rx) = unbounded();
         let mut this = Self {
             extension_index: Default::default(),
             installed_dir,
@@ -236,6 +241,7 @@ impl ExtensionStore {
             builder: Arc::new(ExtensionBuilder::new(build_dir)),
             outstanding_operations: Default::default(),
             modified_extensions: Default::default(),
+            disabled_extensions: Default::default(),
             reload_complete_senders: Vec::new(),
             wasm_host: WasmHost::new(
                 fs.clone(),
@@ -243,9 +249,11 @@ impl ExtensionStore {
                 node_runtime,
                 language_registry.clone(),
                 work_dir,
+                status_bar_item_tx,
                 cx,
             ),
             wasm_extensions: Vec::new(),
+            status_bar_items: Vec::new(),
             fs,
             http_client,
             telemetry,
@@ -305,6 +313,17 @@ impl ExtensionStore {
         })
         .detach();
 
+        this.tasks.push(cx.spawn(|this, mut cx| async move {
+            while let Some(update) = status_bar_item_rx.next().await {
+                if this
+                    .update(&mut cx, |this, cx| this.update_status_bar_item(update, cx))
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        }));
+
         // Perform all extension loading in a single task to ensure that we
         // never attempt to simultaneously load/unload extensions from multiple
         // parallel tasks.
@@ -343,6 +362,14 @@ impl ExtensionStore {
             .map(drop)
         }));
 
+        // Load or unload the extensions that are enabled or disabled in the settings.
+        cx.observe_global::<SettingsStore>(|this, cx| {
+            if this.disabled_extensions_in(&this.extension_index, cx) != this.disabled_extensions {
+                drop(this.reload(None, cx));
+            }
+        })
+        .detach();
+
         // Watch the installed extensions directory for changes. Whenever changes are
         // detected, rebuild the extension index, and load/unload any extensions that
         // have been added, removed, or modified.
@@ -402,6 +429,12 @@ impl ExtensionStore {
         &self.extension_index.extensions
     }
 
+    /// Returns whether the given installed extension is loaded.
+    pub fn is_extension_enabled(&self, extension_id: &str) -> bool {
+        self.extension_index.extensions.contains_key(extension_id)
+            && !self.disabled_extensions.contains(extension_id)
+    }
+
     pub fn dev_extensions(&self) -> impl Iterator<Item = &Arc<ExtensionManifest>> {
         self.extension_index
             .extensions
@@ -921,16 +954,27 @@ impl ExtensionStore {
         new_index: ExtensionIndex,
         cx: &mut ModelContext<Self>,
     ) -> Task<()> {
+        let disabled_extensions = self.disabled_extensions_in(&new_index, cx);
+        let old_disabled_extensions =
+            std::mem::replace(&mut self.disabled_extensions, disabled_extensions);
         let old_index = &self.extension_index;
 
         // Determine which extensions need to be loaded and unloaded, based
-        // on the changes to the manifest and the extensions that we know have been
-        // modified.
+        // on the changes to the manifest, the extensions that we know have been
+        // modified, and the extensions that are disabled.
         let mut extensions_to_unload = Vec::default();
         let mut extensions_to_load = Vec::default();
         {
-            let mut old_keys = old_index.extensions.iter().peekable();
-            let mut new_keys = new_index.extensions.iter().peekable();
+            let mut old_keys = old_index
+                .extensions
+                .iter()
+                .filter(|(id, _)| !old_disabled_extensions.contains(*id))
+                .peekable();
+            let mut new_keys = new_index
+                .extensions
+                .iter()
+                .filter(|(id, _)| !self.disabled_extensions.contains(*id))
+                .peekable();
             loop {
                 match (old_keys.peek(), new_keys.peek()) {
                     (None, None) => break,
@@ -963,6 +1007,7 @@ impl ExtensionStore {
         }
 
         if extensions_to_load.is_empty() && extensions_to_unload.is_empty() {
+            self.extension_index = new_index;
             return Task::ready(());
         }
 
@@ -1027,6 +1072,8 @@ impl ExtensionStore {
 
         self.wasm_extensions
             .retain(|(extension, _)| !extensions_to_unload.contains(&extension.id));
+        self.status_bar_items
+            .retain(|item| !extensions_to_unload.contains(&item.extension_id));
         self.theme_registry.remove_user_themes(&themes_to_remove);
         self.language_registry
             .remove_languages(&languages_to_remove, &grammars_to_remove);
@@ -1200,6 +1247,97 @@ impl ExtensionStore {
         })
     }
 
+    /// Returns the commands of the loaded extensions.
+    pub fn commands(&self) -> Vec<ExtensionCommand> {
+        self.wasm_extensions
+            .iter()
+            .flat_map(|(manifest, _)| {
+                manifest
+                    .commands
+                    .iter()
+                    .map(|(id, command)| ExtensionCommand {
+                        extension_id: manifest.id.clone(),
+                        id: id.clone(),
+                        name: command.name.clone(),
+                    })
+            })
+            .collect()
+    }
+
+    /// Runs a command of a loaded extension, which must be declared in its manifest.
+    pub fn run_command(
+        &self,
+        extension_id: &str,
+        command_id: Arc<str>,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let Some((_, extension)) = self.wasm_extensions.iter().find(|(manifest, _)| {
+            manifest.id.as_ref() == extension_id && manifest.commands.contains_key(&command_id)
+        }) else {
+            return Task::ready(Err(anyhow!(
+                "extension {extension_id} has no command {command_id}"
+            )));
+        };
+        let extension = extension.clone();
+        cx.background_executor().spawn(async move {
+            extension
+                .call(move |extension, store| {
+                    async move {
+                        extension
+                            .call_run_command(store, &command_id)
+                            .await?
+                            .map_err(|error| anyhow!("{error}"))
+                    }
+                    .boxed()
+                })
+                .await
+        })
+    }
+
+    /// Returns the items that the loaded extensions show in the status bar, in the order they
+    /// were first shown.
+    pub fn status_bar_items(&self) -> &[ExtensionStatusBarItem] {
+        &self.status_bar_items
+    }
+
+    fn update_status_bar_item(&mut self, update: StatusBarItemUpdate, cx: &mut ModelContext<Self>) {
+        match update {
+            StatusBarItemUpdate::Set(item) => {
+                // Items of extensions that were unloaded in the meantime are dropped.
+                if !self
+                    .extension_index
+                    .extensions
+                    .contains_key(&item.extension_id)
+                    || self.disabled_extensions.contains(&item.extension_id)
+                {
+                    return;
+                }
+                if let Some(existing_item) = self.status_bar_items.iter_mut().find(|existing| {
+                    existing.extension_id == item.extension_id && existing.id == item.id
+                }) {
+                    *existing_item = item;
+                } else {
+                    self.status_bar_items.push(item);
+                }
+            }
+            StatusBarItemUpdate::Remove { extension_id, id } => {
+                self.status_bar_items
+                    .retain(|item| item.extension_id != extension_id || item.id != id);
+            }
+        }
+        cx.notify();
+    }
+
+    fn disabled_extensions_in(&self, index: &ExtensionIndex, cx: &AppContext) -> HashSet<Arc<str>> {
+        let settings = ExtensionSettings::get_global(cx);
+        index
+            .extensions
+            .keys()
+            .filter(|id| !settings.is_enabled(id))
+            .cloned()
+            .collect()
+    }
+
     fn rebuild_extension_index(&self, cx: &mut ModelContext<Self>) -> Task<ExtensionIndex> {
         let fs = self.fs.clone();
         let work_dir = self.wasm_host.work_dir.clone();
@@ -1,9 +1,10 @@
 use crate::extension_manifest::SchemaVersion;
 use crate::extension_settings::ExtensionSettings;
+use crate::wasm_host::StatusBarItemUpdate;
 use crate::{
     Event, ExtensionIndex, ExtensionIndexEntry, ExtensionIndexLanguageEntry,
-    ExtensionIndexThemeEntry, ExtensionManifest, ExtensionStore, GrammarManifestEntry,
-    RELOAD_DEBOUNCE_DURATION,
+    ExtensionIndexThemeEntry, ExtensionManifest, ExtensionStatusBarItem, ExtensionStore,
+    GrammarManifestEntry, RELOAD_DEBOUNCE_DURATION,
 };
 use assistant_slash_command::SlashCommandRegistry;
 use async_compression::futures::bufread::GzipEncoder;
@@ -158,6 +159,7 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                         .collect(),
                         language_servers: BTreeMap::default(),
                         slash_commands: BTreeMap::default(),
+                        commands: BTreeMap::default(),
                     }),
                     dev: false,
                 },
@@ -182,6 +184,7 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                         grammars: BTreeMap::default(),
                         language_servers: BTreeMap::default(),
                         slash_commands: BTreeMap::default(),
+                        commands: BTreeMap::default(),
                     }),
                     dev: false,
                 },
@@ -339,6 +342,7 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                 grammars: BTreeMap::default(),
                 language_servers: BTreeMap::default(),
                 slash_commands: BTreeMap::default(),
+                commands: BTreeMap::default(),
             }),
             dev: false,
         },
@@ -422,6 +426,88 @@ async fn test_extension_store(cx: &mut TestAppContext) {
         assert_eq!(fs.metadata_call_count(), prev_fs_metadata_call_count + 2);
     });
 
+    // Status bar items are only kept for the extensions that are loaded.
+    store.update(cx, |store, _| {
+        let tx = &store.wasm_host.status_bar_item_tx;
+        for extension_id in ["zed-gruvbox", "zed-ruby", "zed-uninstalled"] {
+            tx.unbounded_send(StatusBarItemUpdate::Set(status_bar_item(
+                extension_id,
+                "one",
+            )))
+            .unwrap();
+        }
+        tx.unbounded_send(StatusBarItemUpdate::Set(status_bar_item(
+            "zed-gruvbox",
+            "two",
+        )))
+        .unwrap();
+        tx.unbounded_send(StatusBarItemUpdate::Remove {
+            extension_id: "zed-ruby".into(),
+            id: "status".into(),
+        })
+        .unwrap();
+    });
+    cx.executor().run_until_parked();
+    store.read_with(cx, |store, _| {
+        assert_eq!(
+            store.status_bar_items(),
+            [status_bar_item("zed-gruvbox", "two")]
+        );
+    });
+
+    // Disabling an extension unloads it, but keeps it installed.
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                settings
+                    .enabled_extensions
+                    .insert("zed-gruvbox".into(), false);
+            });
+        });
+    });
+
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    store.read_with(cx, |store, _| {
+        assert_eq!(store.extension_index, expected_index);
+        assert!(!store.is_extension_enabled("zed-gruvbox"));
+        assert!(store.is_extension_enabled("zed-ruby"));
+        assert!(store.status_bar_items().is_empty());
+        assert_eq!(
+            theme_registry.list_names(false),
+            [
+                "Monokai Dark",
+                "Monokai Light",
+                "Monokai Pro Dark",
+                "Monokai Pro Light",
+                "One Dark",
+            ]
+        );
+    });
+
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                settings.enabled_extensions.remove("zed-gruvbox");
+            });
+        });
+    });
+
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    store.read_with(cx, |store, _| {
+        assert!(store.is_extension_enabled("zed-gruvbox"));
+        assert_eq!(
+            theme_registry.list_names(false),
+            [
+                "Gruvbox",
+                "Monokai Dark",
+                "Monokai Light",
+                "Monokai Pro Dark",
+                "Monokai Pro Light",
+                "One Dark",
+            ]
+        );
+    });
+
     store.update(cx, |store, cx| {
         store.uninstall_extension("zed-ruby".into(), cx)
     });
@@ -729,6 +815,16 @@ async fn test_extension_store_with_gleam_extension(cx: &mut TestAppContext) {
     assert!(fs.metadata(&expected_server_path).await.unwrap().is_none());
 }
 
+fn status_bar_item(extension_id: &str, text: &str) -> ExtensionStatusBarItem {
+    ExtensionStatusBarItem {
+        extension_id: extension_id.into(),
+        id: "status".into(),
+        text: text.to_string(),
+        tooltip: None,
+        command: None,
+    }
+}
+
 fn init_test(cx: &mut TestAppContext) {
     cx.update(|cx| {
         let store = SettingsStore::test(cx);
@@ -1,6 +1,6 @@
 pub(crate) mod wit;
 
-use crate::ExtensionManifest;
+use crate::{ExtensionManifest, ExtensionStatusBarItem};
 use anyhow::{anyhow, bail, Context as _, Result};
 use fs::{normalize_path, Fs};
 use futures::future::LocalBoxFuture;
@@ -39,6 +39,17 @@ pub(crate) struct WasmHost {
     pub(crate) work_dir: PathBuf,
     _main_thread_message_task: Task<()>,
     main_thread_message_tx: mpsc::UnboundedSender<MainThreadCall>,
+    pub(crate) status_bar_item_tx: mpsc::UnboundedSender<StatusBarItemUpdate>,
+}
+
+/// A change that an extension makes to its status bar items, applied by the extension store.
+#[derive(Debug)]
+pub(crate) enum StatusBarItemUpdate {
+    Set(ExtensionStatusBarItem),
+    Remove {
+        extension_id: Arc<str>,
+        id: Arc<str>,
+    },
 }
 
 #[derive(Clone)]
@@ -83,6 +94,7 @@ impl WasmHost {
         node_runtime: Arc<dyn NodeRuntime>,
         language_registry: Arc<LanguageRegistry>,
         work_dir: PathBuf,
+        status_bar_item_tx: mpsc::UnboundedSender<StatusBarItemUpdate>,
         cx: &mut AppContext,
     ) -> Arc<Self> {
         let (tx, mut rx) = mpsc::unbounded::<MainThreadCall>();
@@ -101,6 +113,7 @@ impl WasmHost {
             release_channel: ReleaseChannel::global(cx),
             _main_thread_message_task: task,
             main_thread_message_tx: tx,
+            status_bar_item_tx,
         })
     }
 
@@ -289,6 +289,19 @@ impl Extension {
             }
         }
     }
+
+    pub async fn call_run_command(
+        &self,
+        store: &mut Store<WasmState>,
+        command_id: &str,
+    ) -> Result<Result<(), String>> {
+        match self {
+            Extension::V007(ext) => ext.call_run_command(store, command_id).await,
+            Extension::V001(_) | Extension::V004(_) | Extension::V006(_) => {
+                Err(anyhow!("`run_command` not available prior to v0.0.7"))
+            }
+        }
+    }
 }
 
 trait ToWasmtimeResult<T> {
@@ -1,4 +1,5 @@
-use crate::wasm_host::{wit::ToWasmtimeResult, WasmState};
+use crate::wasm_host::{wit::ToWasmtimeResult, StatusBarItemUpdate, WasmState};
+use crate::ExtensionStatusBarItem;
 use ::settings::Settings;
 use anyhow::{anyhow, bail, Result};
 use async_compression::futures::bufread::GzipDecoder;
@@ -230,6 +231,36 @@ impl slash_command::Host for WasmState {}
 
 #[async_trait]
 impl ExtensionImports for WasmState {
+    async fn set_status_bar_item(
+        &mut self,
+        id: String,
+        item: StatusBarItem,
+    ) -> wasmtime::Result<()> {
+        let item = ExtensionStatusBarItem {
+            extension_id: self.manifest.id.clone(),
+            id: id.into(),
+            text: item.text,
+            tooltip: item.tooltip,
+            command: item.command.map(Into::into),
+        };
+        self.host
+            .status_bar_item_tx
+            .unbounded_send(StatusBarItemUpdate::Set(item))
+            .ok();
+        Ok(())
+    }
+
+    async fn remove_status_bar_item(&mut self, id: String) -> wasmtime::Result<()> {
+        self.host
+            .status_bar_item_tx
+            .unbounded_send(StatusBarItemUpdate::Remove {
+                extension_id: self.manifest.id.clone(),
+                id: id.into(),
+            })
+            .ok();
+        Ok(())
+    }
+
     async fn get_settings(
         &mut self,
         location: Option<self::SettingsLocation>,
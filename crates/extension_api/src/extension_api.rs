@@ -14,7 +14,7 @@ pub use serde_json;
 // We explicitly enumerate the symbols we want to re-export, as there are some
 // that we may want to shadow to provide a cleaner Rust API.
 pub use wit::{
-    download_file, make_file_executable,
+    download_file, make_file_executable, remove_status_bar_item, set_status_bar_item,
     zed::extension::github::{
         github_release_by_tag_name, latest_github_release, GithubRelease, GithubReleaseAsset,
         GithubReleaseOptions,
@@ -26,7 +26,7 @@ pub use wit::{
     zed::extension::platform::{current_platform, Architecture, Os},
     zed::extension::slash_command::{SlashCommand, SlashCommandOutput, SlashCommandOutputSection},
     CodeLabel, CodeLabelSpan, CodeLabelSpanLiteral, Command, DownloadedFileType, EnvVars,
-    LanguageServerInstallationStatus, Range, Worktree,
+    LanguageServerInstallationStatus, Range, StatusBarItem, Worktree,
 };
 
 // Undocumented WIT re-exports.
@@ -126,6 +126,11 @@ pub trait Extension: Send + Sync {
     ) -> Result<SlashCommandOutput, String> {
         Err("`run_slash_command` not implemented".to_string())
     }
+
+    /// Runs the command with the given ID, as declared in the extension's manifest.
+    fn run_command(&self, _command_id: String) -> Result<(), String> {
+        Err("`run_command` not implemented".to_string())
+    }
 }
 
 /// Registers the provided type as a Zed extension.
@@ -248,6 +253,10 @@ impl wit::Guest for Component {
     ) -> Result<SlashCommandOutput, String> {
         extension().run_slash_command(command, argument, worktree)
     }
+
+    fn run_command(command_id: String) -> Result<(), String> {
+        extension().run_command(command_id)
+    }
 }
 
 /// The ID of a language server.
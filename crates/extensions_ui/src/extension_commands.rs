@@ -0,0 +1,241 @@
+use std::sync::Arc;
+
+use extension::{ExtensionCommand, ExtensionStore};
+use fuzzy::{match_strings, StringMatch, StringMatchCandidate};
+use gpui::{
+    prelude::*, AppContext, DismissEvent, EventEmitter, FocusableView, Model, Subscription, Task,
+    View, WeakView,
+};
+use picker::{Picker, PickerDelegate};
+use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing, Tooltip};
+use util::ResultExt;
+use workspace::{item::ItemHandle, notifications::NotifyTaskExt, ModalView, StatusItemView};
+
+/// Lets the user pick one of the commands of the loaded extensions to run.
+pub struct ExtensionCommandPicker {
+    picker: View<Picker<ExtensionCommandPickerDelegate>>,
+}
+
+impl ModalView for ExtensionCommandPicker {}
+
+impl EventEmitter<DismissEvent> for ExtensionCommandPicker {}
+
+impl FocusableView for ExtensionCommandPicker {
+    fn focus_handle(&self, cx: &AppContext) -> gpui::FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for ExtensionCommandPicker {
+    fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl ExtensionCommandPicker {
+    pub fn new(cx: &mut ViewContext<Self>) -> Self {
+        let commands = ExtensionStore::global(cx).read(cx).commands();
+        let delegate = ExtensionCommandPickerDelegate::new(cx.view().downgrade(), commands);
+        let picker = cx.new_view(|cx| Picker::uniform_list(delegate, cx));
+        Self { picker }
+    }
+}
+
+pub struct ExtensionCommandPickerDelegate {
+    view: WeakView<ExtensionCommandPicker>,
+    commands: Vec<ExtensionCommand>,
+    selected_index: usize,
+    matches: Vec<StringMatch>,
+}
+
+impl ExtensionCommandPickerDelegate {
+    fn new(view: WeakView<ExtensionCommandPicker>, mut commands: Vec<ExtensionCommand>) -> Self {
+        commands.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+        let matches = commands
+            .iter()
+            .enumerate()
+            .map(|(candidate_id, command)| StringMatch {
+                candidate_id,
+                score: 0.0,
+                positions: Default::default(),
+                string: command.name.clone(),
+            })
+            .collect();
+
+        Self {
+            view,
+            commands,
+            selected_index: 0,
+            matches,
+        }
+    }
+}
+
+impl PickerDelegate for ExtensionCommandPickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _cx: &mut WindowContext) -> Arc<str> {
+        "Run extension command...".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, ix: usize, _cx: &mut ViewContext<Picker<Self>>) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(&mut self, query: String, cx: &mut ViewContext<Picker<Self>>) -> Task<()> {
+        let background_executor = cx.background_executor().clone();
+        let candidates = self
+            .commands
+            .iter()
+            .enumerate()
+            .map(|(id, command)| StringMatchCandidate {
+                id,
+                char_bag: command.name.as_str().into(),
+                string: command.name.clone(),
+            })
+            .collect::<Vec<_>>();
+
+        cx.spawn(move |this, mut cx| async move {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    background_executor,
+                )
+                .await
+            };
+
+            this.update(&mut cx, |this, _cx| {
+                this.delegate.matches = matches;
+                this.delegate.selected_index = this
+                    .delegate
+                    .selected_index
+                    .min(this.delegate.matches.len().saturating_sub(1));
+            })
+            .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _secondary: bool, cx: &mut ViewContext<Picker<Self>>) {
+        if let Some(command_match) = self.matches.get(self.selected_index) {
+            let command = &self.commands[command_match.candidate_id];
+            run_command(&command.extension_id, command.id.clone(), cx);
+        }
+        self.dismissed(cx);
+    }
+
+    fn dismissed(&mut self, cx: &mut ViewContext<Picker<Self>>) {
+        self.view
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _cx: &mut ViewContext<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let command_match = &self.matches[ix];
+        let command = &self.commands[command_match.candidate_id];
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .selected(selected)
+                .child(HighlightedLabel::new(
+                    command_match.string.clone(),
+                    command_match.positions.clone(),
+                ))
+                .end_slot(Label::new(command.extension_id.to_string()).color(Color::Muted)),
+        )
+    }
+}
+
+/// Shows the status bar items of the loaded extensions.
+pub struct ExtensionStatusBar {
+    store: Option<Model<ExtensionStore>>,
+    _observe_store: Option<Subscription>,
+}
+
+impl ExtensionStatusBar {
+    pub fn new(cx: &mut ViewContext<Self>) -> Self {
+        let store = ExtensionStore::try_global(cx);
+        let observe_store = store
+            .as_ref()
+            .map(|store| cx.observe(store, |_, _, cx| cx.notify()));
+        Self {
+            store,
+            _observe_store: observe_store,
+        }
+    }
+}
+
+impl Render for ExtensionStatusBar {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let items = self
+            .store
+            .as_ref()
+            .map_or(&[][..], |store| store.read(cx).status_bar_items());
+        h_flex()
+            .gap_1()
+            .children(items.iter().enumerate().map(|(ix, item)| {
+                let extension_id = item.extension_id.clone();
+                let command = item.command.clone();
+                Button::new(("extension-status-bar-item", ix), item.text.clone())
+                    .label_size(LabelSize::Small)
+                    .disabled(command.is_none())
+                    .when_some(item.tooltip.clone(), |button, tooltip| {
+                        button.tooltip(move |cx| Tooltip::text(tooltip.clone(), cx))
+                    })
+                    .on_click(move |_, cx| {
+                        if let Some(command) = command.clone() {
+                            run_command(&extension_id, command, cx);
+                        }
+                    })
+            }))
+    }
+}
+
+impl StatusItemView for ExtensionStatusBar {
+    fn set_active_pane_item(
+        &mut self,
+        _active_pane_item: Option<&dyn ItemHandle>,
+        _cx: &mut ViewContext<Self>,
+    ) {
+    }
+
+    fn status_item_id() -> Option<&'static str> {
+        Some("extensions")
+    }
+}
+
+fn run_command(extension_id: &str, command_id: Arc<str>, cx: &mut WindowContext) {
+    ExtensionStore::global(cx)
+        .update(cx, |store, cx| {
+            store.run_command(extension_id, command_id, cx)
+        })
+        .detach_and_notify_err(cx);
+}
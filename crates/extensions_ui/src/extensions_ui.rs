@@ -1,15 +1,17 @@
 mod components;
+mod extension_commands;
 mod extension_suggest;
 mod extension_version_selector;
 
 use crate::components::ExtensionCard;
+use crate::extension_commands::ExtensionCommandPicker;
 use crate::extension_version_selector::{
     ExtensionVersionSelector, ExtensionVersionSelectorDelegate,
 };
 use client::telemetry::Telemetry;
 use client::ExtensionMetadata;
 use editor::{Editor, EditorElement, EditorStyle};
-use extension::{ExtensionManifest, ExtensionOperation, ExtensionStore};
+use extension::{ExtensionManifest, ExtensionOperation, ExtensionSettings, ExtensionStore};
 use fuzzy::{match_strings, StringMatchCandidate};
 use gpui::{
     actions, uniform_list, AnyElement, AppContext, EventEmitter, FocusableView, FontStyle,
@@ -18,7 +20,7 @@ use gpui::{
 };
 use num_format::{Locale, ToFormattedString};
 use release_channel::ReleaseChannel;
-use settings::Settings;
+use settings::{update_settings_file, Settings};
 use std::ops::DerefMut;
 use std::time::Duration;
 use std::{ops::Range, sync::Arc};
@@ -31,7 +33,9 @@ use workspace::{
     Workspace, WorkspaceId,
 };
 
-actions!(zed, [Extensions, InstallDevExtension]);
+pub use extension_commands::ExtensionStatusBar;
+
+actions!(zed, [Extensions, InstallDevExtension, RunExtensionCommand]);
 
 pub fn init(cx: &mut AppContext) {
     cx.observe_new_views(move |workspace: &mut Workspace, cx| {
@@ -71,6 +75,9 @@ pub fn init(cx: &mut AppContext) {
                         Some(())
                     })
                     .detach();
+            })
+            .register_action(|workspace, _: &RunExtensionCommand, cx| {
+                workspace.toggle_modal(cx, ExtensionCommandPicker::new);
             });
 
         cx.subscribe(workspace.project(), |_, _, event, cx| match event {
@@ -362,6 +369,7 @@ impl ExtensionsPage {
                                 .color(Color::Accent)
                                 .disabled(matches!(status, ExtensionStatus::Upgrading)),
                             )
+                            .child(self.render_enable_button(extension.id.clone(), cx))
                             .child(
                                 Button::new(SharedString::from(extension.id.clone()), "Uninstall")
                                     .on_click({
@@ -433,6 +441,8 @@ impl ExtensionsPage {
         let extension_id = extension.id.clone();
         let (install_or_uninstall_button, upgrade_button) =
             self.buttons_for_entry(extension, &status, has_dev_extension, cx);
+        let enable_button = (matches!(status, ExtensionStatus::Installed(_)) && !has_dev_extension)
+            .then(|| self.render_enable_button(extension.id.clone(), cx));
         let version = extension.manifest.version.clone();
         let repository_url = extension.manifest.repository.clone();
 
@@ -469,6 +479,7 @@ impl ExtensionsPage {
                             .gap_2()
                             .justify_between()
                             .children(upgrade_button)
+                            .children(enable_button)
                             .child(install_or_uninstall_button),
                     ),
             )
@@ -603,6 +614,30 @@ impl ExtensionsPage {
         .detach_and_log_err(cx);
     }
 
+    fn render_enable_button(&self, extension_id: Arc<str>, cx: &mut ViewContext<Self>) -> Button {
+        let is_enabled = ExtensionStore::global(cx)
+            .read(cx)
+            .is_extension_enabled(&extension_id);
+
+        Button::new(
+            SharedString::from(format!("enable-{}", extension_id)),
+            if is_enabled { "Disable" } else { "Enable" },
+        )
+        .color(Color::Accent)
+        .on_click(cx.listener(move |this, _, cx| {
+            let Some(workspace) = this.workspace.upgrade() else {
+                return;
+            };
+            let fs = workspace.read(cx).project().read(cx).fs().clone();
+            let extension_id = extension_id.clone();
+            update_settings_file::<ExtensionSettings>(fs, cx, move |settings| {
+                settings
+                    .enabled_extensions
+                    .insert(extension_id, !is_enabled);
+            });
+        }))
+    }
+
     fn buttons_for_entry(
         &self,
         extension: &ExtensionMetadata,
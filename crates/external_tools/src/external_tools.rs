@@ -0,0 +1,480 @@
+mod external_tools_settings;
+
+use std::{path::PathBuf, process::Stdio, sync::Arc};
+
+use anyhow::{anyhow, Context as _, Result};
+use editor::Editor;
+use futures::AsyncWriteExt;
+use fuzzy::{match_strings, StringMatch, StringMatchCandidate};
+use gpui::{
+    impl_actions, AppContext, BackgroundExecutor, DismissEvent, EventEmitter, FocusHandle,
+    FocusableView, ParentElement, Render, Styled, Task, View, ViewContext, VisualContext, WeakView,
+};
+use itertools::Itertools;
+use multi_buffer::MultiBuffer;
+use picker::{Picker, PickerDelegate};
+use serde::Deserialize;
+use settings::Settings;
+use task::{RevealStrategy, SpawnInTerminal, TaskId, TerminalWorkDir};
+use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing};
+use util::ResultExt;
+use workspace::{notifications::DetachAndPromptErr, ModalView, Workspace};
+
+pub use external_tools_settings::{ExternalTool, ExternalToolOutput, ExternalToolsSettings};
+
+/// Run an external tool by name, or open a list of the external tools.
+#[derive(PartialEq, Clone, Deserialize, Default)]
+pub struct Run {
+    /// The name of the tool to run.
+    /// If it is not set, a modal with a list of the tools is opened instead.
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+impl_actions!(external_tools, [Run]);
+
+pub fn init(cx: &mut AppContext) {
+    ExternalToolsSettings::register(cx);
+    cx.observe_new_views(
+        |workspace: &mut Workspace, _: &mut ViewContext<Workspace>| {
+            workspace.register_action(|workspace, action: &Run, cx| {
+                let tools = &ExternalToolsSettings::get_global(cx).external_tools;
+                match &action.name {
+                    Some(name) => match tools.iter().find(|tool| &tool.name == name) {
+                        Some(tool) => run_tool(workspace, tool.clone(), cx),
+                        None => log::error!("no external tool named {name:?}"),
+                    },
+                    None => ExternalToolsModal::toggle(workspace, cx),
+                }
+            });
+        },
+    )
+    .detach();
+}
+
+/// What the variables of a tool's arguments are replaced with.
+#[derive(Default)]
+struct ToolContext {
+    file: Option<PathBuf>,
+    selection: String,
+    workspace: Option<PathBuf>,
+}
+
+impl ToolContext {
+    fn substitute(&self, text: &str) -> Result<String> {
+        let mut result = String::new();
+        let mut rest = text;
+        while let Some(ix) = rest.find('$') {
+            result.push_str(&rest[..ix]);
+            rest = &rest[ix + 1..];
+            let name_len = rest
+                .find(|c: char| !c.is_ascii_uppercase() && c != '_')
+                .unwrap_or(rest.len());
+            let (name, remainder) = rest.split_at(name_len);
+            match name {
+                "FILE" => {
+                    let file = self.file.as_ref().context("$FILE is used without a file")?;
+                    result.push_str(&file.to_string_lossy());
+                }
+                "SELECTION" => result.push_str(&self.selection),
+                "WORKSPACE" => {
+                    let workspace = self
+                        .workspace
+                        .as_ref()
+                        .context("$WORKSPACE is used without a project")?;
+                    result.push_str(&workspace.to_string_lossy());
+                }
+                // Other variables are left for the command, like environment variables.
+                _ => {
+                    result.push('$');
+                    result.push_str(name);
+                }
+            }
+            rest = remainder;
+        }
+        result.push_str(rest);
+        Ok(result)
+    }
+}
+
+fn run_tool(workspace: &mut Workspace, tool: ExternalTool, cx: &mut ViewContext<Workspace>) {
+    let editor = workspace
+        .active_item(cx)
+        .and_then(|item| item.act_as::<Editor>(cx));
+    let worktree_roots = workspace
+        .visible_worktrees(cx)
+        .map(|worktree| worktree.read(cx).abs_path().to_path_buf())
+        .collect::<Vec<_>>();
+
+    let mut context = ToolContext::default();
+    // The text passed to the command, and the range that its output replaces.
+    let mut input = None;
+    if let Some(editor) = &editor {
+        let editor = editor.read(cx);
+        context.file = editor
+            .active_excerpt(cx)
+            .and_then(|(_, buffer, _)| buffer.read(cx).file()?.as_local()?.abs_path(cx).into());
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        let selection = editor.selections.newest::<usize>(cx);
+        context.selection = snapshot
+            .text_for_range(selection.range())
+            .collect::<String>();
+        let range = if selection.is_empty() {
+            0..snapshot.len()
+        } else {
+            selection.range()
+        };
+        input = Some((
+            snapshot.text_for_range(range.clone()).collect::<String>(),
+            Some(snapshot.anchor_before(range.start)..snapshot.anchor_after(range.end)),
+        ));
+    }
+    context.workspace = context
+        .file
+        .as_ref()
+        .and_then(|file| worktree_roots.iter().find(|root| file.starts_with(root)))
+        .or(worktree_roots.first())
+        .cloned();
+
+    let command = context.substitute(&tool.command);
+    let args = tool
+        .args
+        .iter()
+        .map(|arg| context.substitute(arg))
+        .collect::<Result<Vec<_>>>();
+    let (command, args) = match command.and_then(|command| Ok((command, args?))) {
+        Ok(command_and_args) => command_and_args,
+        Err(error) => {
+            Task::ready(Err(error)).detach_and_prompt_err(
+                &format!("Failed to run {}", tool.name),
+                cx,
+                |error, _| Some(format!("{error:#}")),
+            );
+            return;
+        }
+    };
+
+    if tool.output == ExternalToolOutput::Panel {
+        // Tasks are run by a shell, which shouldn't expand the substituted text.
+        let quoted_args = args
+            .iter()
+            .map(|arg| shlex::try_quote(arg).map(|arg| arg.into_owned()))
+            .collect::<Result<Vec<_>, _>>();
+        let quoted_args = match quoted_args {
+            Ok(quoted_args) => quoted_args,
+            Err(error) => {
+                Task::ready(Err(anyhow!(
+                    "the arguments can't be passed to a shell: {error}"
+                )))
+                .detach_and_prompt_err(
+                    &format!("Failed to run {}", tool.name),
+                    cx,
+                    |error, _| Some(format!("{error:#}")),
+                );
+                return;
+            }
+        };
+        let spawn_in_terminal = SpawnInTerminal {
+            id: TaskId(format!("external_tool_{}", tool.name)),
+            full_label: tool.name.clone(),
+            label: tool.name.clone(),
+            command_label: std::iter::once(&command).chain(&args).join(" "),
+            command,
+            args: quoted_args,
+            cwd: context.workspace.map(TerminalWorkDir::Local),
+            env: Default::default(),
+            use_new_terminal: false,
+            allow_concurrent_runs: false,
+            reveal: RevealStrategy::Always,
+        };
+        cx.emit(workspace::Event::SpawnTask(spawn_in_terminal));
+        return;
+    }
+
+    let Some((input, range)) = input else {
+        if tool.output == ExternalToolOutput::ReplaceSelection {
+            Task::ready(Err(anyhow!("there is no selection to replace"))).detach_and_prompt_err(
+                &format!("Failed to run {}", tool.name),
+                cx,
+                |error, _| Some(format!("{error:#}")),
+            );
+            return;
+        }
+        (String::new(), None)
+    };
+    let project = workspace.project().clone();
+    let cwd = context.workspace;
+    let executor = cx.background_executor().clone();
+    let output = executor.spawn(run_command(command, args, cwd, input, executor.clone()));
+    cx.spawn(|workspace, mut cx| async move {
+        let output = output.await?;
+        workspace.update(&mut cx, |workspace, cx| match tool.output {
+            ExternalToolOutput::ReplaceSelection => {
+                if let Some((editor, range)) = editor.zip(range) {
+                    editor.update(cx, |editor, cx| editor.edit([(range, output)], cx));
+                }
+            }
+            ExternalToolOutput::NewBuffer => {
+                let buffer = project.update(cx, |project, cx| {
+                    project.create_local_buffer(&output, None, cx)
+                });
+                let buffer = cx.new_model(|cx| {
+                    MultiBuffer::singleton(buffer, cx).with_title(tool.name.clone())
+                });
+                let editor =
+                    cx.new_view(|cx| Editor::for_multibuffer(buffer, Some(project), true, cx));
+                workspace.add_item_to_active_pane(Box::new(editor), None, cx);
+            }
+            ExternalToolOutput::Panel => {}
+        })
+    })
+    .detach_and_prompt_err(&format!("Failed to run {}", tool.name), cx, |error, _| {
+        Some(format!("{error:#}"))
+    });
+}
+
+/// Runs the command with the given input, returning its output.
+async fn run_command(
+    command: String,
+    args: Vec<String>,
+    cwd: Option<PathBuf>,
+    input: String,
+    executor: BackgroundExecutor,
+) -> Result<String> {
+    let mut process = smol::process::Command::new(&command);
+    process
+        .args(&args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(cwd) = cwd {
+        process.current_dir(cwd);
+    }
+    let mut child = process
+        .spawn()
+        .with_context(|| format!("failed to start {command:?}"))?;
+    // The input is written while the output is read, since commands may only read
+    // more of their input once their output has been read.
+    let stdin = child.stdin.take();
+    let write_input = executor.spawn(async move {
+        if let Some(mut stdin) = stdin {
+            // Commands that don't read their input may exit before it is written.
+            stdin.write_all(input.as_bytes()).await.log_err();
+        }
+    });
+    let output = child.output().await?;
+    write_input.await;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "{command:?} exited with {}:\n{}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    String::from_utf8(output.stdout).context("the output of the command isn't UTF-8")
+}
+
+/// A modal listing the external tools to run.
+struct ExternalToolsModal {
+    picker: View<Picker<ExternalToolsModalDelegate>>,
+}
+
+impl ExternalToolsModal {
+    fn toggle(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
+        let workspace_handle = workspace.weak_handle();
+        let tools = ExternalToolsSettings::get_global(cx).external_tools.clone();
+        workspace.toggle_modal(cx, move |cx| {
+            let delegate =
+                ExternalToolsModalDelegate::new(cx.view().downgrade(), workspace_handle, tools);
+            Self {
+                picker: cx.new_view(|cx| Picker::uniform_list(delegate, cx)),
+            }
+        });
+    }
+}
+
+impl Render for ExternalToolsModal {
+    fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl FocusableView for ExternalToolsModal {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for ExternalToolsModal {}
+impl ModalView for ExternalToolsModal {}
+
+struct ExternalToolsModalDelegate {
+    modal: WeakView<ExternalToolsModal>,
+    workspace: WeakView<Workspace>,
+    tools: Vec<ExternalTool>,
+    candidates: Vec<StringMatchCandidate>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl ExternalToolsModalDelegate {
+    fn new(
+        modal: WeakView<ExternalToolsModal>,
+        workspace: WeakView<Workspace>,
+        tools: Vec<ExternalTool>,
+    ) -> Self {
+        let candidates = tools
+            .iter()
+            .enumerate()
+            .map(|(candidate_id, tool)| StringMatchCandidate::new(candidate_id, tool.name.clone()))
+            .collect();
+        Self {
+            modal,
+            workspace,
+            tools,
+            candidates,
+            matches: Vec::new(),
+            selected_index: 0,
+        }
+    }
+}
+
+impl PickerDelegate for ExternalToolsModalDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _cx: &mut WindowContext) -> Arc<str> {
+        "Select an external tool to run...".into()
+    }
+
+    fn no_matches_text(&self, _cx: &mut WindowContext) -> SharedString {
+        if self.tools.is_empty() {
+            "No external tools, add them with the external_tools setting".into()
+        } else {
+            "No matches".into()
+        }
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn confirm(&mut self, _: bool, cx: &mut ViewContext<Picker<Self>>) {
+        if let Some(mat) = self.matches.get(self.selected_index) {
+            let tool = self.tools[mat.candidate_id].clone();
+            self.workspace
+                .update(cx, |workspace, cx| run_tool(workspace, tool, cx))
+                .log_err();
+        }
+        self.dismissed(cx);
+    }
+
+    fn dismissed(&mut self, cx: &mut ViewContext<Picker<Self>>) {
+        self.modal
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, ix: usize, _: &mut ViewContext<Picker<Self>>) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(&mut self, query: String, cx: &mut ViewContext<Picker<Self>>) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self.candidates.clone();
+        cx.spawn(|this, mut cx| async move {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, candidate)| StringMatch {
+                        candidate_id: index,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(&mut cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _cx: &mut ViewContext<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = &self.matches[ix];
+        let tool = &self.tools[mat.candidate_id];
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .selected(selected)
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .child(HighlightedLabel::new(
+                            mat.string.clone(),
+                            mat.positions.clone(),
+                        ))
+                        .child(
+                            Label::new(tool.command.clone())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_substitute() {
+        let context = ToolContext {
+            file: Some(PathBuf::from("/project/src/main.rs")),
+            selection: "selected text".to_string(),
+            workspace: Some(PathBuf::from("/project")),
+        };
+        assert_eq!(
+            context.substitute("--file=$FILE").unwrap(),
+            "--file=/project/src/main.rs"
+        );
+        assert_eq!(
+            context.substitute("$SELECTION in $WORKSPACE").unwrap(),
+            "selected text in /project"
+        );
+        assert_eq!(context.substitute("$HOME/$").unwrap(), "$HOME/$");
+
+        let context = ToolContext::default();
+        assert_eq!(context.substitute("$SELECTION").unwrap(), "");
+        assert!(context.substitute("$FILE").is_err());
+        assert!(context.substitute("$WORKSPACE").is_err());
+    }
+}
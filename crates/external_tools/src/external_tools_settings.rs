@@ -0,0 +1,64 @@
+use gpui::AppContext;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ExternalToolsSettings {
+    pub external_tools: Vec<ExternalTool>,
+}
+
+/// A command that can be run from the command palette, on the active file or selection.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct ExternalTool {
+    /// The name of the tool, as listed in the command palette.
+    pub name: String,
+    /// The command to run.
+    pub command: String,
+    /// The arguments of the command, in which `$FILE` is replaced with the path
+    /// of the active file, `$SELECTION` with the selected text, and `$WORKSPACE`
+    /// with the root of the project the file is in.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// What to do with the output of the command.
+    ///
+    /// Default: panel
+    #[serde(default)]
+    pub output: ExternalToolOutput,
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExternalToolOutput {
+    /// Run the command in the terminal panel.
+    #[default]
+    Panel,
+    /// Pass the selected text to the command, and replace it with the output.
+    /// The whole file is passed and replaced when nothing is selected.
+    ReplaceSelection,
+    /// Pass the selected text, or the whole file, to the command, and open
+    /// the output in a new buffer.
+    NewBuffer,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct ExternalToolsSettingsContent {
+    /// Commands that can be run from the command palette, like formatters,
+    /// converters and one-off scripts.
+    ///
+    /// Default: []
+    pub external_tools: Option<Vec<ExternalTool>>,
+}
+
+impl Settings for ExternalToolsSettings {
+    const KEY: Option<&'static str> = None;
+
+    type FileContent = ExternalToolsSettingsContent;
+
+    fn load(
+        sources: SettingsSources<Self::FileContent>,
+        _: &mut AppContext,
+    ) -> anyhow::Result<Self> {
+        sources.json_merge()
+    }
+}
@@ -0,0 +1,283 @@
+use std::path::Path;
+
+use anyhow::{Context as _, Result};
+use async_zip::{base::write::ZipFileWriter, Compression, ZipEntryBuilder};
+use futures::StreamExt;
+use gpui::{AppContext, PromptLevel, Task, ViewContext};
+use language_tools::LogStore;
+use serde::Serialize;
+use serde_json::Value;
+use settings::SettingsStore;
+use theme::{ActiveTheme, ThemeRegistry};
+use workspace::{notifications::DetachAndPromptErr, Workspace};
+
+use crate::SystemSpecs;
+
+/// The value that replaces the secrets in the bundled settings.
+const REDACTED: &str = "<redacted>";
+
+/// The words that mark a setting's value as a secret, when they are part of its key.
+const SECRET_KEY_WORDS: &[&str] = &[
+    "apikey",
+    "key",
+    "token",
+    "secret",
+    "password",
+    "passwd",
+    "credential",
+    "credentials",
+    "auth",
+    "authorization",
+];
+
+/// The base keymap used when the settings don't pick one.
+const DEFAULT_BASE_KEYMAP: &str = "VSCode";
+
+/// The diagnostics that can be sent along with feedback, to help reproduce what it reports.
+#[derive(Clone, Debug, Serialize)]
+pub struct FeedbackDiagnostics {
+    base_keymap: String,
+    extensions: Vec<String>,
+    /// The user's settings, which are the ones that differ from the defaults,
+    /// with their secrets redacted.
+    settings: Value,
+}
+
+impl FeedbackDiagnostics {
+    pub fn collect(cx: &AppContext) -> Task<Self> {
+        let mut settings = cx.global::<SettingsStore>().raw_user_settings().clone();
+        redact_value(&mut settings);
+        let base_keymap = settings
+            .get("base_keymap")
+            .and_then(Value::as_str)
+            .unwrap_or(DEFAULT_BASE_KEYMAP)
+            .to_string();
+        cx.background_executor().spawn(async move {
+            Self {
+                base_keymap,
+                extensions: installed_extensions().await,
+                settings,
+            }
+        })
+    }
+}
+
+/// Returns the ids of the installed extensions, sorted.
+async fn installed_extensions() -> Vec<String> {
+    let Ok(mut entries) = smol::fs::read_dir(paths::extensions_dir().join("installed")).await
+    else {
+        return Vec::new();
+    };
+    let mut extensions = Vec::new();
+    while let Some(entry) = entries.next().await {
+        if let Ok(entry) = entry {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !name.starts_with('.') {
+                extensions.push(name);
+            }
+        }
+    }
+    extensions.sort_unstable();
+    extensions
+}
+
+/// A file to add to a diagnostics bundle, with its name in the bundle.
+struct BundleFile {
+    name: String,
+    contents: String,
+}
+
+pub fn export_diagnostics_bundle(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
+    let specs = SystemSpecs::new(cx);
+    let languages = workspace.app_state().languages.language_names();
+    let bundle_files = bundle_files_from_app(cx, languages);
+    let path = cx.prompt_for_new_path(util::paths::home_dir());
+
+    cx.spawn(|_, mut cx| async move {
+        let Some(path) = path.await? else {
+            return Ok(());
+        };
+        let specs = specs.await;
+        let bundle = cx
+            .background_executor()
+            .spawn(async move {
+                let mut files = vec![BundleFile {
+                    name: "system_specs.txt".into(),
+                    contents: specs.to_string(),
+                }];
+                files.extend(read_bundle_files().await);
+                files.extend(bundle_files);
+                write_bundle(&path, files).await?;
+                anyhow::Ok(path)
+            })
+            .await?;
+
+        let reveal = cx.prompt(
+            PromptLevel::Info,
+            "Exported diagnostics bundle",
+            Some(&format!(
+                "The bundle was saved to {}. Review it before sharing it, and attach it to your issue or feedback.",
+                bundle.display()
+            )),
+            &["Show in Folder", "OK"],
+        );
+        if reveal.await? == 0 {
+            cx.update(|cx| cx.reveal_path(&bundle))?;
+        }
+        anyhow::Ok(())
+    })
+    .detach_and_prompt_err("Failed to export diagnostics bundle", cx, |error, _| {
+        Some(format!("{error:#}"))
+    });
+}
+
+/// Collects the parts of the bundle that are held by the app.
+fn bundle_files_from_app(cx: &AppContext, languages: Vec<String>) -> Vec<BundleFile> {
+    let mut files = Vec::new();
+
+    let theme_registry = ThemeRegistry::global(cx);
+    let themes = theme_registry.list_names(false);
+    files.push(BundleFile {
+        name: "languages_and_themes.txt".into(),
+        contents: format!(
+            "Active theme: {}\n\nInstalled themes:\n{}\n\nInstalled languages:\n{}\n",
+            cx.theme().name,
+            themes
+                .iter()
+                .map(|theme| format!("- {theme}"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            languages
+                .iter()
+                .map(|language| format!("- {language}"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ),
+    });
+
+    if let Some(log_store) = LogStore::global(cx) {
+        for (server_id, name, log) in log_store.read(cx).language_server_logs(cx) {
+            files.push(BundleFile {
+                name: format!("language_servers/{}-{}.log", server_id.0, name.0),
+                contents: log,
+            });
+        }
+    }
+
+    files
+}
+
+/// Reads the parts of the bundle that are stored in files: the logs and the user's settings.
+async fn read_bundle_files() -> Vec<BundleFile> {
+    let mut files = Vec::new();
+    for (name, path) in [
+        ("zed.log.old", paths::old_log_file()),
+        ("zed.log", paths::log_file()),
+    ] {
+        if let Ok(contents) = smol::fs::read_to_string(path).await {
+            files.push(BundleFile {
+                name: name.into(),
+                contents,
+            });
+        }
+    }
+    if let Ok(settings) = smol::fs::read_to_string(paths::settings_file()).await {
+        files.push(BundleFile {
+            name: "settings.json".into(),
+            contents: redact_settings(&settings).unwrap_or_else(|error| {
+                format!("// The settings could not be redacted, so they were left out: {error}")
+            }),
+        });
+    }
+    files
+}
+
+async fn write_bundle(path: &Path, files: Vec<BundleFile>) -> Result<()> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for file in files {
+        let entry = ZipEntryBuilder::new(file.name.into(), Compression::Deflate);
+        writer
+            .write_entry_whole(entry, file.contents.as_bytes())
+            .await?;
+    }
+    let bundle = writer.close().await?;
+    smol::fs::write(path, bundle)
+        .await
+        .with_context(|| format!("failed to write {path:?}"))
+}
+
+/// Returns the settings with the values of the settings that may be secrets, like API keys,
+/// replaced. The comments of the settings are removed.
+fn redact_settings(settings: &str) -> Result<String> {
+    let mut settings = settings::parse_json_with_comments::<Value>(settings)?;
+    redact_value(&mut settings);
+    Ok(serde_json::to_string_pretty(&settings)?)
+}
+
+fn redact_value(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                if value.is_string() && is_secret_key(key) {
+                    *value = Value::String(REDACTED.into());
+                } else {
+                    redact_value(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(redact_value),
+        _ => {}
+    }
+}
+
+fn is_secret_key(key: &str) -> bool {
+    let key = key.to_lowercase();
+    key.split(|c: char| !c.is_alphanumeric())
+        .any(|word| SECRET_KEY_WORDS.contains(&word))
+        || SECRET_KEY_WORDS
+            .iter()
+            .filter(|word| word.len() > "key".len())
+            .any(|word| key.ends_with(word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_settings() {
+        let settings = r#"{
+            // The theme.
+            "theme": "One Dark",
+            "base_keymap": "VSCode",
+            "openai_api_key": "sk-secret",
+            "lsp": {
+                "rust-analyzer": {
+                    "binary": {
+                        "env": { "GITHUB_TOKEN": "ghp-secret", "PATH": "/usr/bin" }
+                    }
+                }
+            },
+            "servers": [{ "url": "https://example.com", "authToken": "secret" }],
+            "use_auth": true
+        }"#;
+        let redacted: Value = serde_json::from_str(&redact_settings(settings).unwrap()).unwrap();
+        assert_eq!(
+            redacted,
+            serde_json::json!({
+                "theme": "One Dark",
+                "base_keymap": "VSCode",
+                "openai_api_key": REDACTED,
+                "lsp": {
+                    "rust-analyzer": {
+                        "binary": {
+                            "env": { "GITHUB_TOKEN": REDACTED, "PATH": "/usr/bin" }
+                        }
+                    }
+                },
+                "servers": [{ "url": "https://example.com", "authToken": REDACTED }],
+                "use_auth": true
+            })
+        );
+    }
+}
@@ -0,0 +1,152 @@
+//! A server that feedback is submitted to in tests, in the same process, so
+//! that submitting feedback is tested without reaching zed.dev.
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use futures::AsyncReadExt;
+use gpui::AppContext;
+use http::{AsyncBody, FakeHttpClient, HttpClientWithUrl, Method, Request, Response, StatusCode};
+use serde_json::{json, Value};
+
+/// How the fake server responds to a request.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FakeFeedbackResponse {
+    /// Accepts the feedback, creating a ticket with the given ID, or with a
+    /// new one when it's not given.
+    Submitted { ticket_id: Option<String> },
+    /// Responds with the status and an empty body, like a server error.
+    Status(StatusCode),
+    /// Fails to respond, like when the server can't be reached.
+    Unreachable,
+    /// Never responds, like a server that's stuck, so that the request runs
+    /// until it's cancelled.
+    Hang,
+}
+
+/// A request that the fake server received.
+#[derive(Clone, Debug)]
+pub struct FakeFeedbackRequest {
+    pub method: Method,
+    pub url: String,
+    pub authorization: Option<String>,
+    /// The JSON body of the request, or null when it has none.
+    pub body: Value,
+}
+
+#[derive(Default)]
+struct FakeFeedbackServerState {
+    responses: VecDeque<FakeFeedbackResponse>,
+    requests: Vec<FakeFeedbackRequest>,
+    next_ticket_id: usize,
+}
+
+/// Responds to feedback requests with the responses it's given, in order.
+/// Once it runs out of them, it accepts all feedback.
+#[derive(Clone)]
+pub struct FakeFeedbackServer {
+    state: Arc<Mutex<FakeFeedbackServerState>>,
+    http_client: Arc<HttpClientWithUrl>,
+}
+
+impl FakeFeedbackServer {
+    pub fn new() -> Self {
+        let state = Arc::new(Mutex::new(FakeFeedbackServerState::default()));
+        let http_client = FakeHttpClient::create({
+            let state = state.clone();
+            move |request| {
+                let state = state.clone();
+                async move { Self::handle_request(request, &state).await }
+            }
+        });
+        Self { state, http_client }
+    }
+
+    /// Makes feedback be sent to this server, instead of with the HTTP
+    /// client that Zed sends all of its requests with.
+    pub fn install(&self, cx: &mut AppContext) {
+        crate::feedback_http::set_http_client(self.http_client.clone(), cx);
+    }
+
+    pub fn http_client(&self) -> Arc<HttpClientWithUrl> {
+        self.http_client.clone()
+    }
+
+    /// Responds to the next request that hasn't been responded to yet with
+    /// the response, after the ones that were given before it.
+    pub fn respond_with(&self, response: FakeFeedbackResponse) {
+        self.state.lock().unwrap().responses.push_back(response);
+    }
+
+    /// Returns the requests that were received, oldest first.
+    pub fn requests(&self) -> Vec<FakeFeedbackRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    /// Returns the bodies of the feedback that was submitted, whether the
+    /// server accepted it or not, oldest first.
+    pub fn submissions(&self) -> Vec<Value> {
+        self.requests()
+            .into_iter()
+            .filter(|request| request.method == Method::POST)
+            .map(|request| request.body)
+            .collect()
+    }
+
+    async fn handle_request(
+        mut request: Request<AsyncBody>,
+        state: &Mutex<FakeFeedbackServerState>,
+    ) -> Result<Response<AsyncBody>, http::Error> {
+        let mut body = String::new();
+        request.body_mut().read_to_string(&mut body).await?;
+        let authorization = request
+            .headers()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+
+        let mut state = state.lock().unwrap();
+        state.requests.push(FakeFeedbackRequest {
+            method: request.method().clone(),
+            url: request.uri().to_string(),
+            authorization,
+            body: serde_json::from_str(&body).unwrap_or(Value::Null),
+        });
+        let response = state
+            .responses
+            .pop_front()
+            .unwrap_or(FakeFeedbackResponse::Submitted { ticket_id: None });
+        let (status, body) = match response {
+            FakeFeedbackResponse::Submitted { ticket_id } => {
+                let ticket_id = ticket_id.unwrap_or_else(|| {
+                    state.next_ticket_id += 1;
+                    format!("ticket-{}", state.next_ticket_id)
+                });
+                (
+                    StatusCode::OK,
+                    json!({ "ticket_id": ticket_id }).to_string(),
+                )
+            }
+            FakeFeedbackResponse::Status(status) => (status, String::new()),
+            FakeFeedbackResponse::Unreachable => {
+                return Err(isahc::error::ErrorKind::ConnectionFailed.into());
+            }
+            FakeFeedbackResponse::Hang => {
+                drop(state);
+                return futures::future::pending().await;
+            }
+        };
+        Ok(Response::builder()
+            .status(status)
+            .body(AsyncBody::from(body))
+            .unwrap())
+    }
+}
+
+impl Default for FakeFeedbackServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,18 +1,80 @@
-use gpui::{actions, AppContext, ClipboardItem, PromptLevel};
-use system_specs::SystemSpecs;
+use std::{collections::BTreeMap, sync::Arc};
+
+use client::Client;
+use gpui::{actions, impl_actions, AppContext, ClipboardItem, PromptLevel, ViewContext};
+use i18n::{t, t_with};
+use serde_derive::Deserialize;
+use settings::Settings;
 use util::ResultExt;
 use workspace::Workspace;
 
-pub mod feedback_modal;
+#[cfg(any(test, feature = "test-support"))]
+pub mod fake_feedback_server;
+mod feedback_draft;
+pub mod feedback_editor;
+mod feedback_history;
+mod feedback_http;
+mod feedback_queue;
+mod feedback_settings;
+mod feedback_toolbar;
+
+actions!(
+    feedback,
+    [
+        Cancel,
+        CancelSubmission,
+        CopySystemSpecsIntoClipboard,
+        DiscardDraft,
+        Dismiss,
+        FileOnGitHub,
+        OpenFeedbackHistory,
+        SubmitFeedback,
+        TestFeedbackEndpoint,
+        TogglePreview
+    ]
+);
+
+/// Opens the feedback editor. Other parts of Zed can fill in what went wrong
+/// when they hit an error, with its details attached to the feedback.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+pub struct GiveFeedback {
+    /// The description of what went wrong, which is selected in the editor
+    /// so that it can be edited or replaced.
+    #[serde(default)]
+    pub prefill: String,
+    /// The details of the error, like `"source": "language_server"`, sent in
+    /// the metadata of the feedback.
+    #[serde(default)]
+    pub context: BTreeMap<String, String>,
+}
 
-actions!(feedback, [GiveFeedback, SubmitFeedback]);
+impl_actions!(feedback, [GiveFeedback]);
 
+mod diagnostics_bundle;
+mod performance_snapshot;
+mod submission_limiter;
+mod submission_manager;
+mod survey;
 mod system_specs;
 
+#[cfg(test)]
+mod feedback_tests;
+
+pub use feedback_editor::{submit_feedback_without_ui, FeedbackEditor, SubmissionOutcome};
+pub use feedback_history::FeedbackHistoryModal;
+pub use feedback_queue::{FeedbackQueue, PendingFeedbackIndicator};
+pub use feedback_settings::FeedbackSettings;
+pub use feedback_toolbar::FeedbackToolbar;
+pub use performance_snapshot::{
+    set_performance_sampler, DurationPercentiles, PerformanceSampler, PerformanceSnapshot,
+};
+pub use submission_manager::{FeedbackSubmissionManager, UploadProgress};
+pub use system_specs::SystemSpecs;
+
 actions!(
     zed,
     [
-        CopySystemSpecsIntoClipboard,
+        ExportDiagnosticsBundle,
         FileBugReport,
         RequestFeature,
         OpenZedRepo
@@ -34,15 +96,111 @@ fn file_bug_report_url(specs: &SystemSpecs) -> String {
     )
 }
 
-pub fn init(cx: &mut AppContext) {
+/// GitHub truncates the URLs of new issues that are longer than this.
+const MAX_ISSUE_URL_LEN: usize = 8000;
+
+/// Returns the URL of a new issue whose body is the system specs, followed
+/// by the given description. Descriptions too long for the URL are truncated.
+fn github_issue_url(specs: &SystemSpecs, description: &str) -> String {
+    const URL: &str = "https://github.com/zed-industries/zed/issues/new?body=";
+    let body = |description: &str| {
+        let body = format!(
+            "### Environment\n\n{}\n\n### Description\n\n{description}",
+            specs.to_markdown()
+        );
+        format!("{URL}{}", urlencoding::encode(&body))
+    };
+
+    let mut description = description;
+    let mut url = body(description);
+    while url.len() > MAX_ISSUE_URL_LEN && !description.is_empty() {
+        let excess = (url.len() - MAX_ISSUE_URL_LEN) / 3 + 1;
+        let mut end = description.len().saturating_sub(excess);
+        while !description.is_char_boundary(end) {
+            end -= 1;
+        }
+        description = &description[..end];
+        url = body(&format!("{description}…"));
+    }
+    url
+}
+
+fn file_crash_report_url(specs: &SystemSpecs, crash: &str) -> String {
+    format!(
+        "https://github.com/zed-industries/zed/issues/new?assignees=&labels=admin+read%2Ctriage%2Cdefect%2Cpanic+%2F+crash&projects=&template=2_crash_report.yml&environment={}&crash={}",
+        urlencoding::encode(&specs.to_string()),
+        urlencoding::encode(crash)
+    )
+}
+
+/// Opens a new crash report, with the system specs and the given details of the crash filled in.
+pub fn file_crash_report(crash: String, cx: &mut AppContext) {
+    let specs = SystemSpecs::new(cx);
+    cx.spawn(|cx| async move {
+        let specs = specs.await;
+        cx.update(|cx| cx.open_url(&file_crash_report_url(&specs, &crash)))
+            .log_err();
+    })
+    .detach();
+}
+
+/// Checks that the configured feedback endpoint can be reached, and shows the result.
+fn test_feedback_endpoint(cx: &mut ViewContext<Workspace>) {
+    let endpoint = FeedbackSettings::get_global(cx).endpoint(&feedback_http::zed_http_client(cx));
+    let http_client = match &endpoint {
+        Ok(endpoint) => feedback_http::http_client(&endpoint.url, cx),
+        Err(_) => feedback_http::zed_http_client(cx),
+    };
+    cx.spawn(|_, mut cx| async move {
+        let result = match endpoint {
+            Ok(endpoint) => feedback_editor::test_endpoint(&endpoint, &http_client)
+                .await
+                .map(|status| (endpoint.url, status)),
+            Err(error) => Err(error),
+        };
+        let (level, message, detail, ok) = cx.update(|cx| {
+            let ok = t("common.ok", cx);
+            match result {
+                Ok((url, status)) => (
+                    PromptLevel::Info,
+                    t_with("feedback.endpoint_reachable", &[("url", &url)], cx),
+                    status.to_string(),
+                    ok,
+                ),
+                Err(error) => (
+                    PromptLevel::Critical,
+                    t("feedback.endpoint_unreachable", cx),
+                    format!("{error:#}"),
+                    ok,
+                ),
+            }
+        })?;
+        cx.prompt(level, &message, Some(&detail), &[&ok]).await.ok();
+        anyhow::Ok(())
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Opens the feedback editor to report a crash, with its details attached.
+pub fn report_crash(workspace: &mut Workspace, crash: String, cx: &mut ViewContext<Workspace>) {
+    FeedbackEditor::open(workspace, Some(crash), cx).detach();
+}
+
+pub fn init(client: Arc<Client>, cx: &mut AppContext) {
+    FeedbackSettings::register(cx);
+    FeedbackSubmissionManager::init(cx);
+    FeedbackQueue::init(client, cx);
+    survey::init(cx);
+
     cx.observe_new_views(|workspace: &mut Workspace, cx| {
-        feedback_modal::FeedbackModal::register(workspace, cx);
+        FeedbackEditor::register(workspace, cx);
+        FeedbackHistoryModal::register(workspace, cx);
         workspace
             .register_action(|_, _: &CopySystemSpecsIntoClipboard, cx| {
                 let specs = SystemSpecs::new(&cx);
 
                 cx.spawn(|_, mut cx| async move {
-                    let specs = specs.await.to_string();
+                    let specs = specs.await.to_markdown();
 
                     cx.update(|cx| cx.write_to_clipboard(ClipboardItem::new(specs.clone())))
                         .log_err();
@@ -58,6 +216,17 @@ pub fn init(cx: &mut AppContext) {
                 })
                 .detach();
             })
+            .register_action(|_, _: &CancelSubmission, cx| {
+                if let Some(manager) = FeedbackSubmissionManager::global(cx) {
+                    manager.update(cx, |manager, cx| manager.cancel(cx));
+                }
+            })
+            .register_action(|_, _: &TestFeedbackEndpoint, cx| {
+                test_feedback_endpoint(cx);
+            })
+            .register_action(|workspace, _: &ExportDiagnosticsBundle, cx| {
+                diagnostics_bundle::export_diagnostics_bundle(workspace, cx);
+            })
             .register_action(|_, _: &RequestFeature, cx| {
                 cx.open_url(request_feature_url());
             })
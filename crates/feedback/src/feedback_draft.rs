@@ -0,0 +1,64 @@
+use anyhow::Result;
+use db::{define_connection, query, sqlez_macros::sql};
+use workspace::{WorkspaceDb, WorkspaceId};
+
+define_connection!(
+    // Current schema shape using pseudo-rust syntax:
+    // feedback_drafts(
+    //   workspace_id: usize,
+    //   draft: String, // The feedback written so far, as markdown
+    // )
+    pub static ref FEEDBACK_DRAFT_DB: FeedbackDraftDb<WorkspaceDb> =
+        &[sql!(
+            CREATE TABLE feedback_drafts(
+                workspace_id INTEGER PRIMARY KEY,
+                draft TEXT NOT NULL,
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+                ON UPDATE CASCADE
+            ) STRICT;
+        )];
+);
+
+impl FeedbackDraftDb {
+    query! {
+        pub fn draft(workspace_id: WorkspaceId) -> Result<Option<String>> {
+            SELECT draft FROM feedback_drafts WHERE workspace_id = ?
+        }
+    }
+
+    query! {
+        pub async fn save_draft(workspace_id: WorkspaceId, draft: String) -> Result<()> {
+            INSERT INTO feedback_drafts(workspace_id, draft) VALUES (?1, ?2)
+            ON CONFLICT DO UPDATE SET draft = ?2
+        }
+    }
+
+    query! {
+        pub async fn delete_draft(workspace_id: WorkspaceId) -> Result<()> {
+            DELETE FROM feedback_drafts WHERE workspace_id = ?
+        }
+    }
+}
+
+/// Returns the draft of the feedback that was being written in the workspace.
+pub(crate) fn load_draft(workspace_id: Option<WorkspaceId>) -> Result<Option<String>> {
+    match workspace_id {
+        Some(workspace_id) => FEEDBACK_DRAFT_DB.draft(workspace_id),
+        None => Ok(None),
+    }
+}
+
+/// Saves the draft of the feedback that's being written in the workspace, or
+/// deletes it when there's no feedback. Drafts aren't kept for workspaces
+/// that aren't saved in the database.
+pub(crate) async fn store_draft(workspace_id: Option<WorkspaceId>, draft: String) -> Result<()> {
+    let Some(workspace_id) = workspace_id else {
+        return Ok(());
+    };
+    if draft.trim().is_empty() {
+        FEEDBACK_DRAFT_DB.delete_draft(workspace_id).await
+    } else {
+        FEEDBACK_DRAFT_DB.save_draft(workspace_id, draft).await
+    }
+}
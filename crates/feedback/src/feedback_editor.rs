@@ -0,0 +1,2221 @@
+use std::{
+    any::TypeId,
+    collections::BTreeMap,
+    mem,
+    ops::RangeInclusive,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail};
+use bitflags::bitflags;
+use client::{telemetry::Telemetry, Client, TelemetrySettings};
+use db::kvp::KEY_VALUE_STORE;
+use editor::{scroll::Autoscroll, Editor, EditorEvent, MultiBuffer};
+use futures::AsyncReadExt;
+use gpui::{
+    div, px, AnchorCorner, AnyElement, AnyView, AppContext, AsyncAppContext, Div, DragMoveEvent,
+    EventEmitter, FocusHandle, FocusableView, Model, MouseButton, MouseUpEvent, Pixels,
+    PromptLevel, Render, Stateful, Task, View, ViewContext, WeakFocusHandle, WeakView,
+    WindowContext,
+};
+use http::{AsyncBody, HttpClient, HttpClientWithUrl, StatusCode};
+use i18n::{t, t_plural, t_with};
+use isahc::Request;
+use language::Buffer;
+use lazy_static::lazy_static;
+use markdown_preview::{
+    markdown_elements::ParsedMarkdown, markdown_parser::parse_markdown,
+    markdown_renderer::render_parsed_markdown,
+};
+use project::Project;
+use regex::Regex;
+use serde_derive::{Deserialize, Serialize};
+use settings::{update_settings_file, Settings, SettingsStore};
+use ui::{
+    prelude::*, Button, ButtonStyle, CheckboxWithLabel, ContextMenu, IconPosition, PopoverMenu,
+    Selection, Tooltip,
+};
+use util::ResultExt;
+use workspace::item::{Item, ItemEvent, TabContentParams};
+use workspace::notifications::NotificationId;
+use workspace::{Toast, Workspace, WorkspaceId};
+
+use crate::{
+    diagnostics_bundle::FeedbackDiagnostics,
+    feedback_draft::{self, store_draft},
+    feedback_http::{self, FeedbackHttpError, FeedbackRejected},
+    feedback_queue::FeedbackQueue,
+    feedback_settings::{FeedbackEndpoint, FeedbackSettings, SettingsWithoutUi},
+    github_issue_url,
+    performance_snapshot::{self, PerformanceSnapshot},
+    submission_limiter::SubmissionLimiter,
+    submission_manager::{FeedbackSubmissionManager, ProgressReader},
+    system_specs::SystemSpecs,
+    Cancel, CopySystemSpecsIntoClipboard, DiscardDraft, Dismiss, FileOnGitHub, GiveFeedback,
+    OpenZedRepo, SubmitFeedback, TogglePreview,
+};
+
+// For UI testing purposes
+const SEND_SUCCESS_IN_DEV_MODE: bool = true;
+const SEND_TIME_IN_DEV_MODE: Duration = Duration::from_secs(2);
+const SEND_STEPS_IN_DEV_MODE: u32 = 10;
+
+// Tests submit to a fake server instead, see `fake_feedback_server`.
+#[cfg(all(debug_assertions, not(test)))]
+pub(crate) const DEV_MODE: bool = true;
+
+#[cfg(any(not(debug_assertions), test))]
+pub(crate) const DEV_MODE: bool = false;
+
+const DATABASE_KEY_NAME: &str = "email_address";
+const DRAFT_SAVE_DEBOUNCE: Duration = Duration::from_millis(300);
+const FEEDBACK_CHAR_LIMIT: RangeInclusive<i32> = 10..=5000;
+/// The number of lines that the text area of the feedback fits, at least and
+/// at most, as it grows with the feedback or is resized.
+const EDITOR_LINES: RangeInclusive<u32> = 8..=32;
+const RESIZE_HANDLE_SIZE: Pixels = Pixels(6.);
+
+lazy_static! {
+    static ref EMAIL_REGEX: Regex =
+        Regex::new(r"^[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}$").unwrap();
+}
+
+/// What feedback is about, so that it can be triaged.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedbackCategory {
+    Bug,
+    FeatureRequest,
+    Performance,
+    Crash,
+    #[default]
+    Other,
+}
+
+impl FeedbackCategory {
+    pub(crate) const ALL: [Self; 5] = [
+        Self::Bug,
+        Self::FeatureRequest,
+        Self::Performance,
+        Self::Crash,
+        Self::Other,
+    ];
+
+    pub(crate) fn label(self, cx: &AppContext) -> SharedString {
+        t(
+            match self {
+                Self::Bug => "feedback.category.bug",
+                Self::FeatureRequest => "feedback.category.feature_request",
+                Self::Performance => "feedback.category.performance",
+                Self::Crash => "feedback.category.crash",
+                Self::Other => "feedback.category.other",
+            },
+            cx,
+        )
+    }
+
+    /// Returns the name that the category is serialized as.
+    pub(crate) fn id(self) -> &'static str {
+        match self {
+            Self::Bug => "bug",
+            Self::FeatureRequest => "feature_request",
+            Self::Performance => "performance",
+            Self::Crash => "crash",
+            Self::Other => "other",
+        }
+    }
+
+    pub(crate) fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|category| category.id() == id)
+    }
+
+    /// Returns the placeholder of the feedback editor, which asks for the
+    /// details that help the most with feedback of this category.
+    fn placeholder(self, cx: &AppContext) -> SharedString {
+        t(
+            match self {
+                Self::Bug => "feedback.placeholder.bug",
+                Self::FeatureRequest => "feedback.placeholder.feature_request",
+                Self::Performance => "feedback.placeholder.performance",
+                Self::Crash => "feedback.placeholder.crash",
+                Self::Other => "feedback.editor_placeholder",
+            },
+            cx,
+        )
+    }
+}
+
+/// How much the reported issue gets in the way of using Zed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedbackSeverity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl FeedbackSeverity {
+    const ALL: [Self; 4] = [Self::Low, Self::Medium, Self::High, Self::Critical];
+
+    fn label(severity: Option<Self>, cx: &AppContext) -> SharedString {
+        t(
+            match severity {
+                None => "feedback.severity.unset",
+                Some(Self::Low) => "feedback.severity.low",
+                Some(Self::Medium) => "feedback.severity.medium",
+                Some(Self::High) => "feedback.severity.high",
+                Some(Self::Critical) => "feedback.severity.critical",
+            },
+            cx,
+        )
+    }
+}
+
+#[derive(Serialize)]
+struct FeedbackRequestBody<'a> {
+    feedback_text: &'a str,
+    category: FeedbackCategory,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    severity: Option<FeedbackSeverity>,
+    email: Option<String>,
+    metrics_id: Option<Arc<str>>,
+    installation_id: Option<Arc<str>>,
+    system_specs: SystemSpecs,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diagnostics: Option<FeedbackDiagnostics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    crash_report: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    performance_snapshot: Option<&'a PerformanceSnapshot>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    metadata: BTreeMap<String, String>,
+    is_staff: bool,
+}
+
+impl<'a> FeedbackRequestBody<'a> {
+    fn builder(
+        feedback_text: &'a str,
+        category: FeedbackCategory,
+        system_specs: SystemSpecs,
+    ) -> FeedbackRequestBuilder<'a> {
+        FeedbackRequestBuilder {
+            body: FeedbackRequestBody {
+                feedback_text,
+                category,
+                severity: None,
+                email: None,
+                metrics_id: None,
+                installation_id: None,
+                system_specs,
+                diagnostics: None,
+                crash_report: None,
+                performance_snapshot: None,
+                metadata: BTreeMap::default(),
+                is_staff: false,
+            },
+            anonymous: false,
+            always_anonymous: false,
+            metrics: true,
+        }
+    }
+}
+
+/// Builds the body of a feedback request, leaving out everything that
+/// identifies the user when the feedback is sent anonymously.
+struct FeedbackRequestBuilder<'a> {
+    body: FeedbackRequestBody<'a>,
+    anonymous: bool,
+    always_anonymous: bool,
+    metrics: bool,
+}
+
+impl<'a> FeedbackRequestBuilder<'a> {
+    fn severity(mut self, severity: Option<FeedbackSeverity>) -> Self {
+        self.body.severity = severity;
+        self
+    }
+
+    fn email(mut self, email: Option<String>) -> Self {
+        self.body.email = email;
+        self
+    }
+
+    fn installation_id(mut self, installation_id: Option<Arc<str>>) -> Self {
+        self.body.installation_id = installation_id;
+        self
+    }
+
+    /// Sets the metrics and installation IDs, and whether the user is staff.
+    fn telemetry(mut self, telemetry: &Arc<Telemetry>) -> Self {
+        self.body.metrics_id = telemetry.metrics_id();
+        self.body.installation_id = telemetry.installation_id();
+        self.body.is_staff = telemetry.is_staff().unwrap_or(false);
+        self
+    }
+
+    fn diagnostics(mut self, diagnostics: Option<FeedbackDiagnostics>) -> Self {
+        self.body.diagnostics = diagnostics;
+        self
+    }
+
+    fn crash_report(mut self, crash_report: Option<&'a str>) -> Self {
+        self.body.crash_report = crash_report;
+        self
+    }
+
+    fn performance_snapshot(mut self, snapshot: Option<&'a PerformanceSnapshot>) -> Self {
+        self.body.performance_snapshot = snapshot;
+        self
+    }
+
+    fn metadata(mut self, metadata: BTreeMap<String, String>) -> Self {
+        self.body.metadata = metadata;
+        self
+    }
+
+    fn anonymous(mut self, anonymous: bool) -> Self {
+        self.anonymous = anonymous;
+        self
+    }
+
+    /// Adds the metadata from the feedback settings, and sends the feedback
+    /// anonymously, without diagnostics or a crash report, when they say to
+    /// always do so.
+    fn settings(mut self, settings: &FeedbackSettings) -> Self {
+        self.body.metadata.extend(settings.metadata.clone());
+        self.always_anonymous = settings.always_anonymous;
+        self
+    }
+
+    /// Sets whether usage metrics are sent, without which the metrics ID isn't.
+    fn metrics(mut self, metrics: bool) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    fn build(self) -> FeedbackRequestBody<'a> {
+        let mut body = self.body;
+        if self.anonymous || self.always_anonymous {
+            body.email = None;
+            body.metrics_id = None;
+            body.installation_id = None;
+            body.is_staff = false;
+        }
+        if self.always_anonymous {
+            body.diagnostics = None;
+            body.crash_report = None;
+        }
+        if !self.metrics {
+            body.metrics_id = None;
+        }
+        body
+    }
+}
+
+#[derive(Deserialize)]
+struct FeedbackResponseBody {
+    ticket_id: Option<String>,
+}
+
+/// Sends the JSON body of a feedback request, returning the ID of the ticket
+/// that the server created for it, if it responded with one. The number of
+/// bytes uploaded so far is counted in `bytes_sent`.
+async fn send_feedback(
+    request_json: &str,
+    endpoint: &FeedbackEndpoint,
+    http_client: &HttpClientWithUrl,
+    bytes_sent: Option<Arc<AtomicU64>>,
+) -> anyhow::Result<Option<String>> {
+    let mut request = Request::post(&endpoint.url).header("content-type", "application/json");
+    if let Some(auth_token) = &endpoint.auth_token {
+        request = request.header("authorization", format!("Bearer {auth_token}"));
+    }
+    let body = request_json.as_bytes().to_vec();
+    let body = match bytes_sent {
+        Some(bytes_sent) => {
+            let len = body.len() as u64;
+            AsyncBody::from_reader_sized(ProgressReader::new(body, bytes_sent), len)
+        }
+        None => body.into(),
+    };
+    let request = request.body(body)?;
+    let mut response = http_client
+        .send(request)
+        .await
+        .map_err(|error| feedback_http::request_error(error, http_client))?;
+    let mut body = String::new();
+    response.body_mut().read_to_string(&mut body).await?;
+    let response_status = response.status();
+    if let Some(error) = FeedbackHttpError::from_status(response_status) {
+        return Err(error.into());
+    }
+    if !response_status.is_success() {
+        return Err(FeedbackRejected(response_status).into());
+    }
+    let ticket_id = serde_json::from_str::<FeedbackResponseBody>(&body)
+        .ok()
+        .and_then(|response| response.ticket_id);
+    Ok(ticket_id)
+}
+
+/// Checks that the endpoint can be reached and accepts its auth token, without
+/// submitting feedback. Returns the status that the endpoint responded with.
+pub(crate) async fn test_endpoint(
+    endpoint: &FeedbackEndpoint,
+    http_client: &HttpClientWithUrl,
+) -> anyhow::Result<StatusCode> {
+    let mut request = Request::get(&endpoint.url);
+    if let Some(auth_token) = &endpoint.auth_token {
+        request = request.header("authorization", format!("Bearer {auth_token}"));
+    }
+    let response = http_client
+        .send(request.body(AsyncBody::empty())?)
+        .await
+        .map_err(|error| feedback_http::request_error(error, http_client))?;
+    let status = response.status();
+    if let Some(error) = FeedbackHttpError::from_status(status) {
+        return Err(error.into());
+    }
+    if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+        bail!("The endpoint rejected the auth token: {status}");
+    }
+    // Other client errors, like 405 for an endpoint that only accepts
+    // submissions, still show that the endpoint exists.
+    if status == StatusCode::NOT_FOUND || status.is_server_error() {
+        bail!("The endpoint responded with an error: {status}");
+    }
+    Ok(status)
+}
+
+/// Sends the JSON body of a feedback request, or pretends to in development.
+/// Returns the ID of the ticket that the server created for the feedback.
+pub(crate) async fn deliver_feedback(
+    request_json: &str,
+    endpoint: &FeedbackEndpoint,
+    http_client: &HttpClientWithUrl,
+    bytes_sent: Option<Arc<AtomicU64>>,
+) -> anyhow::Result<Option<String>> {
+    if DEV_MODE {
+        // Pretends to upload gradually, so that the progress can be seen.
+        let total_bytes = request_json.len() as u64;
+        for step in 1..=SEND_STEPS_IN_DEV_MODE {
+            smol::Timer::after(SEND_TIME_IN_DEV_MODE / SEND_STEPS_IN_DEV_MODE).await;
+            if let Some(bytes_sent) = &bytes_sent {
+                bytes_sent.store(
+                    total_bytes * step as u64 / SEND_STEPS_IN_DEV_MODE as u64,
+                    Ordering::Relaxed,
+                );
+            }
+        }
+
+        if SEND_SUCCESS_IN_DEV_MODE {
+            return Ok(None);
+        } else {
+            return Err(anyhow!("Error submitting feedback"));
+        }
+    }
+
+    send_feedback(request_json, endpoint, http_client, bytes_sent).await
+}
+
+/// Submits feedback without the UI, so that issues can be reported from the
+/// terminal even when Zed fails to start. It's sent where, and how, the user's
+/// settings say, like feedback from the editor is.
+pub async fn submit_feedback_without_ui(
+    feedback_text: &str,
+    installation_id: Option<Arc<str>>,
+    system_specs: SystemSpecs,
+    user_settings: &str,
+) -> anyhow::Result<()> {
+    let character_count = feedback_text.chars().count() as i32;
+    if !FEEDBACK_CHAR_LIMIT.contains(&character_count) {
+        bail!(
+            "feedback must be between {} and {} characters long",
+            FEEDBACK_CHAR_LIMIT.start(),
+            FEEDBACK_CHAR_LIMIT.end()
+        );
+    }
+    let settings = SettingsWithoutUi::parse(user_settings)?;
+    let zed_client = HttpClientWithUrl::with_options(
+        settings.server_url.clone(),
+        settings
+            .http_client
+            .http_client_options(settings.proxy.clone()),
+    );
+    let endpoint = settings.feedback.endpoint(&zed_client)?;
+    let http_client =
+        feedback_http::http_client_without_ui(&endpoint.url, &settings).unwrap_or(zed_client);
+    let request = FeedbackRequestBody::builder(feedback_text, FeedbackCategory::Bug, system_specs)
+        .email(KEY_VALUE_STORE.read_kvp(DATABASE_KEY_NAME).ok().flatten())
+        .installation_id(installation_id)
+        .settings(&settings.feedback)
+        .build();
+    send_feedback(
+        &serde_json::to_string(&request)?,
+        &endpoint,
+        &http_client,
+        None,
+    )
+    .await?;
+    Ok(())
+}
+
+bitflags! {
+    #[derive(Debug, Clone, PartialEq)]
+    struct InvalidStateFlags: u8 {
+        const EmailAddress = 0b00000001;
+        const CharacterCount = 0b00000010;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum CannotSubmitReason {
+    InvalidState { flags: InvalidStateFlags },
+    AwaitingSubmission,
+}
+
+/// How an attempt to submit the feedback ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    /// The feedback was submitted, and the editor closed.
+    Submitted,
+    /// The feedback wasn't sent, because it's invalid, or sending it wasn't confirmed.
+    NotSent,
+    /// The submission failed, and can be retried.
+    Failed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum SubmissionState {
+    CanSubmit,
+    CannotSubmit { reason: CannotSubmitReason },
+}
+
+pub struct FeedbackEditor {
+    workspace: WeakView<Workspace>,
+    system_specs: SystemSpecs,
+    diagnostics: FeedbackDiagnostics,
+    include_diagnostics: bool,
+    send_anonymously: bool,
+    /// The details of a crash of the previous session, which the feedback reports.
+    crash_report: Option<String>,
+    include_performance_snapshot: bool,
+    /// How Zed performed while it was sampled, for performance feedback.
+    performance_snapshot: Option<PerformanceSnapshot>,
+    capture_performance_snapshot_task: Option<Task<()>>,
+    category: FeedbackCategory,
+    severity: Option<FeedbackSeverity>,
+    editor: View<Editor>,
+    email_address_editor: View<Editor>,
+    zed_repository_focus_handle: FocusHandle,
+    submission_state: Option<SubmissionState>,
+    /// The JSON body of the request that last failed to submit.
+    failed_request: Option<String>,
+    /// Why the request failed to submit, when the network's setup caused it.
+    failure_message: Option<SharedString>,
+    /// The details of the error that the feedback is about, when it was given
+    /// from where the error happened.
+    error_context: BTreeMap<String, String>,
+    /// The workspace that the feedback's draft is kept for.
+    workspace_id: Option<WorkspaceId>,
+    save_draft_task: Option<Task<()>>,
+    /// The feedback as it was last saved, which its draft goes back to when
+    /// the editor is closed without saving its changes.
+    saved_draft: String,
+    /// Whether the feedback was edited since it was saved or submitted, so
+    /// that closing the editor asks whether to keep its draft.
+    has_unsaved_changes: bool,
+    character_count: i32,
+    /// What had focus before feedback was given, to focus it again when the editor is closed.
+    previous_focus: Option<WeakFocusHandle>,
+    showing_preview: bool,
+    /// The feedback rendered as markdown, once parsed for the preview.
+    preview: Option<ParsedMarkdown>,
+    preview_focus_handle: FocusHandle,
+    parse_preview_task: Option<Task<()>>,
+    /// The height of the text area, once it's been resized with its drag handle.
+    /// Otherwise, the text area grows with the feedback.
+    editor_height: Option<Pixels>,
+}
+
+#[derive(Clone, Render)]
+struct DraggedResizeHandle;
+
+impl FocusableView for FeedbackEditor {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        if self.showing_preview {
+            self.preview_focus_handle.clone()
+        } else {
+            self.editor.focus_handle(cx)
+        }
+    }
+}
+
+impl EventEmitter<EditorEvent> for FeedbackEditor {}
+
+impl Item for FeedbackEditor {
+    type Event = EditorEvent;
+
+    fn tab_content(&self, params: TabContentParams, cx: &WindowContext) -> AnyElement {
+        let color = if params.selected {
+            Color::Default
+        } else {
+            Color::Muted
+        };
+        h_flex()
+            .gap_2()
+            .child(Icon::new(IconName::Envelope).color(color))
+            .child(Label::new(t("feedback.tab_title", cx)).color(color))
+            .into_any_element()
+    }
+
+    fn to_item_events(event: &EditorEvent, f: impl FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn is_dirty(&self, cx: &AppContext) -> bool {
+        self.has_unsaved_changes && self.has_draft(cx)
+    }
+
+    fn can_save(&self, _: &AppContext) -> bool {
+        true
+    }
+
+    /// Saves the feedback's draft, which is restored the next time feedback is given.
+    fn save(
+        &mut self,
+        _format: bool,
+        _project: Model<Project>,
+        cx: &mut ViewContext<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        self.flush_draft(cx)
+    }
+
+    fn deactivated(&mut self, cx: &mut ViewContext<Self>) {
+        self.update_email_in_store(cx);
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("feedback editor")
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a View<Self>,
+        _: &'a AppContext,
+    ) -> Option<AnyView> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.to_any())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.editor.to_any())
+        } else {
+            None
+        }
+    }
+}
+
+impl FeedbackEditor {
+    pub fn register(workspace: &mut Workspace, _: &mut ViewContext<Workspace>) {
+        workspace.register_action(move |workspace, action: &GiveFeedback, cx| {
+            if action.prefill.is_empty() && action.context.is_empty() {
+                Self::open(workspace, None, cx).detach();
+                return;
+            }
+            let action = action.clone();
+            let feedback_editor = Self::open(workspace, None, cx);
+            cx.spawn(|_, mut cx| async move {
+                if let Some(feedback_editor) = feedback_editor.await {
+                    feedback_editor.update(&mut cx, |this, cx| {
+                        this.prefill(action.prefill, action.context, cx)
+                    })?;
+                }
+                anyhow::Ok(())
+            })
+            .detach_and_log_err(cx);
+        });
+    }
+
+    /// Opens the feedback editor, or activates the one that's already open. When
+    /// given the details of a crash, the feedback reports the crash.
+    pub(crate) fn open(
+        workspace: &mut Workspace,
+        crash_report: Option<String>,
+        cx: &mut ViewContext<Workspace>,
+    ) -> Task<Option<View<Self>>> {
+        if let Some(existing) = workspace.item_of_type::<FeedbackEditor>(cx) {
+            if let Some(crash_report) = crash_report {
+                existing.update(cx, |this, cx| this.attach_crash_report(crash_report, cx));
+            }
+            workspace.activate_item(&existing, cx);
+            return Task::ready(Some(existing));
+        }
+
+        let markdown = workspace
+            .app_state()
+            .languages
+            .language_for_name("Markdown");
+
+        let project = workspace.project().clone();
+        let is_local_project = project.read(cx).is_local();
+
+        if !is_local_project {
+            struct FeedbackInRemoteProject;
+
+            workspace.show_toast(
+                Toast::new(
+                    NotificationId::unique::<FeedbackInRemoteProject>(),
+                    t("feedback.remote_project", cx).to_string(),
+                ),
+                cx,
+            );
+            return Task::ready(None);
+        }
+
+        let workspace_id = workspace.database_id();
+        let previous_focus = cx.focused().map(|focus_handle| focus_handle.downgrade());
+        let system_specs = SystemSpecs::new(cx);
+        let diagnostics = FeedbackDiagnostics::collect(cx);
+        cx.spawn(|workspace, mut cx| async move {
+            let markdown = markdown.await.log_err();
+            let buffer = project.update(&mut cx, |project, cx| {
+                let draft = feedback_draft::load_draft(workspace_id)
+                    .log_err()
+                    .flatten()
+                    .unwrap_or_default();
+                project.create_local_buffer(&draft, markdown, cx)
+            });
+            let buffer = buffer.log_err()?;
+            let system_specs = system_specs.await;
+            let diagnostics = diagnostics.await;
+
+            workspace
+                .update(&mut cx, |workspace, cx| {
+                    let workspace_handle = cx.view().downgrade();
+                    let feedback_editor = cx.new_view(|cx| {
+                        let mut this = FeedbackEditor::new(
+                            workspace_handle,
+                            workspace_id,
+                            system_specs,
+                            diagnostics,
+                            project,
+                            buffer,
+                            cx,
+                        );
+                        if let Some(crash_report) = crash_report {
+                            this.attach_crash_report(crash_report, cx);
+                        }
+                        this.previous_focus = previous_focus;
+                        this
+                    });
+                    workspace.add_item_to_active_pane(Box::new(feedback_editor.clone()), None, cx);
+                    feedback_editor
+                })
+                .log_err()
+        })
+    }
+
+    /// Opens the feedback editor with feedback that was submitted before, to
+    /// edit it and submit it again. Replacing the feedback written so far is
+    /// confirmed first.
+    pub(crate) fn resubmit(
+        workspace: &mut Workspace,
+        feedback_text: String,
+        category: FeedbackCategory,
+        cx: &mut ViewContext<Workspace>,
+    ) {
+        let feedback_editor = Self::open(workspace, None, cx);
+        cx.spawn(|_, mut cx| async move {
+            if let Some(feedback_editor) = feedback_editor.await {
+                let confirmed =
+                    feedback_editor.update(&mut cx, |this, cx| this.confirm_replace(cx))?;
+                if !confirmed.await {
+                    return Ok(());
+                }
+                feedback_editor.update(&mut cx, |this, cx| {
+                    this.editor
+                        .update(cx, |editor, cx| editor.set_text(feedback_text, cx));
+                    this.set_category(category, cx);
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Fills in a description of what went wrong, selected so that it can be
+    /// edited or replaced, after the feedback written so far, and attaches the
+    /// details of the error.
+    fn prefill(
+        &mut self,
+        description: String,
+        context: BTreeMap<String, String>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let has_draft = self.has_draft(cx);
+        self.editor.update(cx, |editor, cx| {
+            if description.is_empty() {
+                return;
+            }
+            let mut start = 0;
+            if has_draft {
+                let text = editor.text(cx);
+                start = text.trim_end().len() + 2;
+                editor.set_text(format!("{}\n\n{description}", text.trim_end()), cx);
+            } else {
+                editor.set_text(description.as_str(), cx);
+            }
+            let end = start + description.len();
+            editor.change_selections(Some(Autoscroll::fit()), cx, |selections| {
+                selections.select_ranges([start..end])
+            });
+            editor.focus(cx);
+        });
+        self.error_context.extend(
+            context
+                .into_iter()
+                .filter(|(key, _)| !key.trim().is_empty()),
+        );
+    }
+
+    pub fn new(
+        workspace: WeakView<Workspace>,
+        workspace_id: Option<WorkspaceId>,
+        system_specs: SystemSpecs,
+        diagnostics: FeedbackDiagnostics,
+        project: Model<Project>,
+        buffer: Model<Buffer>,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let email_address_editor = cx.new_view(|cx| {
+            let mut editor = Editor::single_line(cx);
+            editor.set_placeholder_text(t("feedback.email_placeholder", cx).to_string(), cx);
+
+            if let Ok(Some(email_address)) = KEY_VALUE_STORE.read_kvp(DATABASE_KEY_NAME) {
+                editor.set_text(email_address, cx)
+            }
+
+            editor
+        });
+
+        let editor = cx.new_view(|cx| {
+            let mut editor = Editor::for_buffer(buffer, Some(project.clone()), cx);
+            editor
+                .set_placeholder_text(FeedbackCategory::default().placeholder(cx).to_string(), cx);
+            editor.set_show_gutter(false, cx);
+            editor.set_show_indent_guides(false, cx);
+            editor.set_show_inline_completions(false);
+            editor.set_vertical_scroll_margin(5, cx);
+            editor
+        });
+
+        cx.subscribe(&editor, |this, _, event: &EditorEvent, cx| {
+            if matches!(event, EditorEvent::Edited { .. }) {
+                this.character_count = this.buffer_len(cx);
+                this.save_draft(cx);
+                this.set_has_unsaved_changes(true, cx);
+                cx.notify();
+            }
+        })
+        .detach();
+        cx.observe(&email_address_editor, |_, _, cx| cx.notify())
+            .detach();
+        if let Some(manager) = FeedbackSubmissionManager::global(cx) {
+            cx.observe(&manager, |_, _, cx| cx.notify()).detach();
+        }
+        // Whether usage metrics are sent can be changed in the settings file too.
+        cx.observe_global::<SettingsStore>(|_, cx| cx.notify())
+            .detach();
+        // Feedback that failed to submit isn't lost when the editor is closed.
+        // Otherwise, the draft goes back to what was last saved when the
+        // editor was closed without saving its changes, or the last edits are
+        // saved right away instead of once the draft's debounce is over.
+        cx.on_release(|this, _, cx| {
+            let draft = if let Some((request_json, queue)) =
+                this.failed_request.take().zip(FeedbackQueue::global(cx))
+            {
+                queue
+                    .update(cx, |queue, cx| queue.enqueue(request_json, cx))
+                    .detach_and_log_err(cx);
+                String::new()
+            } else if this.is_dirty(cx) {
+                mem::take(&mut this.saved_draft)
+            } else if this.save_draft_task.take().is_some() {
+                this.editor.read(cx).text(cx)
+            } else {
+                return;
+            };
+            cx.background_executor()
+                .spawn(store_draft(this.workspace_id, draft))
+                .detach_and_log_err(cx);
+        })
+        .detach();
+
+        let mut this = Self {
+            workspace,
+            system_specs: system_specs.clone(),
+            diagnostics,
+            include_diagnostics: false,
+            send_anonymously: false,
+            crash_report: None,
+            include_performance_snapshot: true,
+            performance_snapshot: None,
+            capture_performance_snapshot_task: None,
+            category: FeedbackCategory::default(),
+            severity: None,
+            editor,
+            email_address_editor,
+            zed_repository_focus_handle: cx.focus_handle(),
+            submission_state: None,
+            failed_request: None,
+            failure_message: None,
+            error_context: BTreeMap::default(),
+            workspace_id,
+            save_draft_task: None,
+            saved_draft: String::new(),
+            has_unsaved_changes: false,
+            character_count: 0,
+            previous_focus: None,
+            showing_preview: false,
+            preview: None,
+            preview_focus_handle: cx.focus_handle(),
+            parse_preview_task: None,
+            editor_height: None,
+        };
+        this.character_count = this.buffer_len(cx);
+        this.saved_draft = this.editor.read(cx).text(cx);
+        this
+    }
+
+    fn buffer_len(&self, cx: &AppContext) -> i32 {
+        self.editor
+            .read(cx)
+            .buffer()
+            .read(cx)
+            .as_singleton()
+            .expect("Feedback editor is never a multi-buffer")
+            .read(cx)
+            .len() as i32
+    }
+
+    /// Saves the feedback written so far, so that it's restored the next time
+    /// feedback is given if it isn't submitted.
+    fn save_draft(&mut self, cx: &mut ViewContext<Self>) {
+        let draft = self.editor.read(cx).text(cx);
+        let workspace_id = self.workspace_id;
+        self.save_draft_task = Some(cx.spawn(|_, cx| async move {
+            cx.background_executor().timer(DRAFT_SAVE_DEBOUNCE).await;
+            store_draft(workspace_id, draft).await.log_err();
+        }));
+    }
+
+    /// Saves the draft right away, rather than once the feedback hasn't been
+    /// edited for a while.
+    fn flush_draft(&mut self, cx: &mut ViewContext<Self>) -> Task<anyhow::Result<()>> {
+        self.save_draft_task = None;
+        self.set_has_unsaved_changes(false, cx);
+        let draft = self.editor.read(cx).text(cx);
+        self.saved_draft = draft.clone();
+        cx.background_executor()
+            .spawn(store_draft(self.workspace_id, draft))
+    }
+
+    fn set_has_unsaved_changes(&mut self, has_unsaved_changes: bool, cx: &mut ViewContext<Self>) {
+        if self.has_unsaved_changes != has_unsaved_changes {
+            self.has_unsaved_changes = has_unsaved_changes;
+            cx.emit(EditorEvent::DirtyChanged);
+        }
+    }
+
+    fn clear_draft(&mut self, cx: &mut ViewContext<Self>) {
+        self.save_draft_task = None;
+        self.set_has_unsaved_changes(false, cx);
+        self.saved_draft.clear();
+        cx.background_executor()
+            .spawn(store_draft(self.workspace_id, String::new()))
+            .detach_and_log_err(cx);
+    }
+
+    pub(crate) fn has_draft(&self, cx: &AppContext) -> bool {
+        self.character_count > 0 && !self.editor.read(cx).text(cx).trim().is_empty()
+    }
+
+    /// Clears the feedback written so far, along with its saved draft, once
+    /// discarding it is confirmed.
+    pub(crate) fn discard_draft(&mut self, _: &DiscardDraft, cx: &mut ViewContext<Self>) {
+        let confirmed = self.confirm_discard(cx);
+        cx.spawn(|this, mut cx| async move {
+            if confirmed.await {
+                this.update(&mut cx, |this, cx| this.clear_feedback(cx))?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Cancels the submission that's being uploaded. Otherwise, discards the
+    /// feedback once that's confirmed, and closes the editor.
+    fn cancel(&mut self, _: &Cancel, cx: &mut ViewContext<Self>) {
+        if self.awaiting_submission() {
+            self.cancel_submission(cx);
+            return;
+        }
+        let confirmed = self.confirm_discard(cx);
+        cx.spawn(|this, mut cx| async move {
+            if confirmed.await {
+                this.update(&mut cx, |this, cx| {
+                    this.clear_feedback(cx);
+                    this.close(cx);
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Asks whether to discard the feedback written so far, if there's any.
+    fn confirm_discard(&self, cx: &mut ViewContext<Self>) -> Task<bool> {
+        let (message, discard) = (t("feedback.discard_prompt", cx), t("feedback.discard", cx));
+        self.confirm_losing_feedback(message, discard, cx)
+    }
+
+    /// Asks whether to replace the feedback written so far, if there's any.
+    fn confirm_replace(&self, cx: &mut ViewContext<Self>) -> Task<bool> {
+        let (message, replace) = (t("feedback.replace_prompt", cx), t("feedback.replace", cx));
+        self.confirm_losing_feedback(message, replace, cx)
+    }
+
+    fn confirm_losing_feedback(
+        &self,
+        message: SharedString,
+        confirm: SharedString,
+        cx: &mut ViewContext<Self>,
+    ) -> Task<bool> {
+        if !self.has_draft(cx) {
+            return Task::ready(true);
+        }
+        let cancel = t("common.cancel", cx);
+        let answer = cx.prompt(PromptLevel::Warning, &message, None, &[&confirm, &cancel]);
+        cx.background_executor()
+            .spawn(async move { answer.await.ok() == Some(0) })
+    }
+
+    fn clear_feedback(&mut self, cx: &mut ViewContext<Self>) {
+        self.editor.update(cx, |editor, cx| editor.set_text("", cx));
+        self.crash_report = None;
+        self.failed_request = None;
+        self.error_context.clear();
+        self.severity = None;
+        self.performance_snapshot = None;
+        self.capture_performance_snapshot_task = None;
+        self.clear_draft(cx);
+        cx.notify();
+    }
+
+    /// Submits the feedback once that's confirmed, resolving when the
+    /// submission has ended. The editor is only closed once it succeeds.
+    pub fn submit(
+        &mut self,
+        cx: &mut ViewContext<Self>,
+    ) -> Task<anyhow::Result<SubmissionOutcome>> {
+        self.update_submission_state(cx);
+        if !self.can_submit() {
+            return Task::ready(Ok(SubmissionOutcome::NotSent));
+        }
+        if let Err(refusal) = SubmissionLimiter::check(self.feedback_hash(cx), cx) {
+            self.show_toast(refusal.message(cx), cx);
+            return Task::ready(Ok(SubmissionOutcome::NotSent));
+        }
+
+        let request_json = match self.feedback_request_json(cx) {
+            Ok(request_json) => request_json,
+            Err(error) => return Task::ready(Err(error)),
+        };
+        self.update_email_in_store(cx);
+
+        let (message, submit, no) = (
+            t("feedback.submit_prompt", cx),
+            t("feedback.submit_prompt_confirm", cx),
+            t("common.no", cx),
+        );
+        let answer = cx.prompt(PromptLevel::Info, &message, None, &[&submit, &no]);
+        cx.spawn(|this, mut cx| async move {
+            if answer.await.ok() != Some(0) {
+                return Ok(SubmissionOutcome::NotSent);
+            }
+            let submission =
+                this.update(&mut cx, |this, cx| this.send_request(request_json, cx))?;
+            Ok(submission.await)
+        })
+    }
+
+    /// Closes the editor, and focuses what had focus before feedback was given.
+    fn close(&mut self, cx: &mut ViewContext<Self>) {
+        cx.emit(EditorEvent::Closed);
+        let previous_focus = self.previous_focus.take().and_then(|focus| focus.upgrade());
+        if let Some(previous_focus) = previous_focus {
+            // Focused after the pane has activated another item in this one's place.
+            cx.window_context()
+                .defer(move |cx| cx.focus(&previous_focus));
+        }
+    }
+
+    fn dismiss(&mut self, _: &Dismiss, cx: &mut ViewContext<Self>) {
+        self.close(cx);
+    }
+
+    /// Sends the feedback request again, after it failed to submit, resolving
+    /// when the submission has ended.
+    pub(crate) fn retry_submission(
+        &mut self,
+        cx: &mut ViewContext<Self>,
+    ) -> Task<SubmissionOutcome> {
+        match self.failed_request.take() {
+            Some(request_json) => self.send_request(request_json, cx),
+            None => Task::ready(SubmissionOutcome::NotSent),
+        }
+    }
+
+    /// Saves the feedback request that failed to submit to the queue of
+    /// pending feedback, and closes the editor.
+    fn submit_later(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(queue) = FeedbackQueue::global(cx) else {
+            return;
+        };
+        let Some(request_json) = self.failed_request.take() else {
+            return;
+        };
+        let queued = queue.update(cx, |queue, cx| queue.enqueue(request_json.clone(), cx));
+        cx.spawn(|this, mut cx| async move {
+            let result = queued.await;
+            this.update(&mut cx, |this, cx| match result {
+                Ok(()) => {
+                    this.clear_draft(cx);
+                    this.show_toast(t("feedback.queued", cx), cx);
+                    this.close(cx);
+                }
+                Err(error) => {
+                    log::error!("failed to save feedback for later: {error}");
+                    this.failed_request = Some(request_json);
+                    cx.notify();
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn send_request(
+        &mut self,
+        request_json: String,
+        cx: &mut ViewContext<Self>,
+    ) -> Task<SubmissionOutcome> {
+        let Some(manager) = FeedbackSubmissionManager::global(cx) else {
+            return Task::ready(SubmissionOutcome::NotSent);
+        };
+        self.failure_message = None;
+        // Checked again, as the same feedback may have been submitted since
+        // it was confirmed, like when submit is clicked twice.
+        let http_client = feedback_http::zed_http_client(cx);
+        let endpoint = match FeedbackSettings::get_global(cx).endpoint(&http_client) {
+            Ok(endpoint) => endpoint,
+            Err(error) => {
+                log::error!("{error:#}");
+                self.failed_request = Some(request_json);
+                self.show_toast(format!("{error:#}").into(), cx);
+                cx.notify();
+                return Task::ready(SubmissionOutcome::Failed);
+            }
+        };
+        let feedback_hash = self.feedback_hash(cx);
+        if let Err(refusal) = SubmissionLimiter::record(feedback_hash, cx) {
+            self.failed_request = None;
+            self.show_toast(refusal.message(cx), cx);
+            cx.notify();
+            return Task::ready(SubmissionOutcome::NotSent);
+        }
+
+        self.submission_state = Some(SubmissionState::CannotSubmit {
+            reason: CannotSubmitReason::AwaitingSubmission,
+        });
+        self.failed_request = None;
+        // The submission goes on, or is queued when it fails, once the editor is closed.
+        self.set_has_unsaved_changes(false, cx);
+        cx.notify();
+
+        let result = manager.update(cx, |manager, cx| {
+            manager.submit(request_json.clone(), endpoint, cx)
+        });
+        cx.spawn(|this, mut cx| async move {
+            let result = result.await;
+            // The submission goes on when the editor is closed while it's uploading.
+            let outcome = match &result {
+                Ok(Ok(_)) => SubmissionOutcome::Submitted,
+                Ok(Err(_)) => SubmissionOutcome::Failed,
+                Err(_) => SubmissionOutcome::Cancelled,
+            };
+            let request_to_queue =
+                (outcome == SubmissionOutcome::Failed).then(|| request_json.clone());
+            let editor_updated = this.update(&mut cx, |this, cx| {
+                this.submission_state = Some(SubmissionState::CanSubmit);
+                match result {
+                    Ok(Ok(_)) => {
+                        this.clear_draft(cx);
+                        this.show_toast(t("feedback.submitted", cx), cx);
+                        this.close(cx);
+                    }
+                    Ok(Err(error)) => {
+                        log::error!("{:#}", error);
+                        SubmissionLimiter::forget(feedback_hash, cx);
+                        this.failed_request = Some(request_json);
+                        this.failure_message = error
+                            .downcast_ref::<FeedbackHttpError>()
+                            .map(|error| error.message(cx));
+                    }
+                    // Cancelled, so that the feedback can be edited and submitted again.
+                    Err(_) => {
+                        SubmissionLimiter::forget(feedback_hash, cx);
+                        this.show_toast(t("feedback.submission_cancelled", cx), cx);
+                    }
+                }
+                cx.notify();
+            });
+            // Without the editor, the failed feedback can't be retried from it, so it's saved to
+            // the queue of pending feedback instead.
+            if let (Err(_), Some(request_json)) = (editor_updated, request_to_queue) {
+                let queued = AsyncAppContext::update(&cx, |cx| {
+                    let queue = FeedbackQueue::global(cx)?;
+                    Some(queue.update(cx, |queue, cx| queue.enqueue(request_json, cx)))
+                });
+                if let Ok(Some(queued)) = queued {
+                    queued.await.log_err();
+                }
+            }
+            outcome
+        })
+    }
+
+    /// Cancels the submission of the feedback while it's being uploaded.
+    pub(crate) fn cancel_submission(&mut self, cx: &mut ViewContext<Self>) {
+        if let Some(manager) = FeedbackSubmissionManager::global(cx) {
+            manager.update(cx, |manager, cx| manager.cancel(cx));
+        }
+    }
+
+    /// Returns the percentage of the feedback that's been uploaded, while it's submitted.
+    pub(crate) fn upload_percentage(&self, cx: &AppContext) -> Option<u64> {
+        if !self.awaiting_submission() {
+            return None;
+        }
+        let manager = FeedbackSubmissionManager::global(cx)?;
+        let progress = manager.read(cx).progress()?;
+        Some(progress.percentage())
+    }
+
+    fn feedback_hash(&self, cx: &AppContext) -> u64 {
+        SubmissionLimiter::hash(&self.editor.read(cx).text(cx))
+    }
+
+    fn show_toast(&self, message: SharedString, cx: &mut ViewContext<Self>) {
+        struct FeedbackSubmission;
+
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.show_toast(
+                    Toast::new(
+                        NotificationId::unique::<FeedbackSubmission>(),
+                        message.to_string(),
+                    ),
+                    cx,
+                )
+            })
+            .ok();
+    }
+
+    /// Opens the request that submitting the feedback would send, in a read-only editor.
+    fn preview_request(&mut self, cx: &mut ViewContext<Self>) {
+        let preview = self.feedback_request_json(cx).and_then(|request_json| {
+            let request: serde_json::Value = serde_json::from_str(&request_json)?;
+            Ok(serde_json::to_string_pretty(&request)?)
+        });
+        let preview = match preview {
+            Ok(preview) => preview,
+            Err(error) => {
+                log::error!("failed to preview the feedback request: {error}");
+                return;
+            }
+        };
+        let title = t("feedback.request_preview_title", cx).to_string();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                let json = workspace.app_state().languages.language_for_name("JSON");
+                cx.spawn(|workspace, mut cx| async move {
+                    let json = json.await.log_err();
+                    workspace.update(&mut cx, |workspace, cx| {
+                        let project = workspace.project().clone();
+                        let buffer = project.update(cx, |project, cx| {
+                            project.create_local_buffer(&preview, json, cx)
+                        });
+                        let buffer =
+                            cx.new_model(|cx| MultiBuffer::singleton(buffer, cx).with_title(title));
+                        let editor = cx.new_view(|cx| {
+                            let mut editor =
+                                Editor::for_multibuffer(buffer, Some(project), true, cx);
+                            editor.set_read_only(true);
+                            editor
+                        });
+                        workspace.add_item_to_active_pane(Box::new(editor), None, cx);
+                    })
+                })
+                .detach_and_log_err(cx);
+            })
+            .ok();
+    }
+
+    fn feedback_request_json(&self, cx: &AppContext) -> anyhow::Result<String> {
+        let feedback_text = self.editor.read(cx).text(cx);
+        let request = FeedbackRequestBody::builder(
+            feedback_text.trim(),
+            self.category,
+            self.system_specs.clone(),
+        )
+        .severity(self.severity)
+        .email(self.email_address(cx))
+        .telemetry(Client::global(cx).telemetry())
+        .diagnostics(self.include_diagnostics.then(|| self.diagnostics.clone()))
+        .crash_report(self.crash_report.as_deref())
+        .performance_snapshot(self.performance_snapshot())
+        .metadata(self.error_context.clone())
+        .anonymous(self.send_anonymously)
+        .settings(FeedbackSettings::get_global(cx))
+        .metrics(TelemetrySettings::get_global(cx).metrics)
+        .build();
+        Ok(serde_json::to_string(&request)?)
+    }
+
+    fn update_submission_state(&mut self, cx: &mut ViewContext<Self>) {
+        if self.awaiting_submission() {
+            return;
+        }
+
+        let mut invalid_state_flags = InvalidStateFlags::empty();
+
+        // The email address isn't sent with anonymous feedback.
+        let valid_email_address = match self.email_address(cx) {
+            Some(_) if self.is_anonymous(cx) => true,
+            Some(email_address) => is_email_address(&email_address),
+            None => true,
+        };
+
+        if !valid_email_address {
+            invalid_state_flags |= InvalidStateFlags::EmailAddress;
+        }
+
+        if !FEEDBACK_CHAR_LIMIT.contains(&self.character_count) {
+            invalid_state_flags |= InvalidStateFlags::CharacterCount;
+        }
+
+        if invalid_state_flags.is_empty() {
+            self.submission_state = Some(SubmissionState::CanSubmit);
+        } else {
+            self.submission_state = Some(SubmissionState::CannotSubmit {
+                reason: CannotSubmitReason::InvalidState {
+                    flags: invalid_state_flags,
+                },
+            });
+        }
+    }
+
+    /// Returns the email address to follow up on the feedback with, if one was given.
+    fn email_address(&self, cx: &AppContext) -> Option<String> {
+        let email_address = self.email_address_editor.read(cx).text(cx);
+        let email_address = email_address.trim();
+        (!email_address.is_empty()).then(|| email_address.to_string())
+    }
+
+    /// Saves the email address to fill in the next time feedback is given,
+    /// once it's valid.
+    fn update_email_in_store(&self, cx: &mut ViewContext<Self>) {
+        let email = self.email_address(cx);
+        if email.as_ref().is_some_and(|email| !is_email_address(email)) {
+            return;
+        }
+
+        cx.spawn(|_, _| async move {
+            match email {
+                Some(email) => {
+                    KEY_VALUE_STORE
+                        .write_kvp(DATABASE_KEY_NAME.to_string(), email)
+                        .await
+                        .ok();
+                }
+                None => {
+                    KEY_VALUE_STORE
+                        .delete_kvp(DATABASE_KEY_NAME.to_string())
+                        .await
+                        .ok();
+                }
+            }
+        })
+        .detach();
+    }
+
+    /// Returns the label counting the characters of the feedback against its limit.
+    pub(crate) fn character_count_label(&self, cx: &WindowContext) -> Div {
+        div()
+            .text_ui(cx)
+            .text_color(if self.valid_character_count() {
+                cx.theme().colors().feedback_counter_text
+            } else {
+                cx.theme().status().error
+            })
+            .child(t_with(
+                "feedback.character_count",
+                &[
+                    ("count", &self.character_count),
+                    ("limit", FEEDBACK_CHAR_LIMIT.end()),
+                ],
+                cx,
+            ))
+    }
+
+    /// Returns why the feedback's length can't be submitted, once something has
+    /// been written.
+    fn character_count_error(&self, cx: &AppContext) -> Option<SharedString> {
+        if self.character_count == 0 || self.valid_character_count() {
+            None
+        } else if self.character_count < *FEEDBACK_CHAR_LIMIT.start() {
+            Some(t_plural(
+                "feedback.too_short",
+                *FEEDBACK_CHAR_LIMIT.start() as usize,
+                cx,
+            ))
+        } else {
+            Some(t_plural(
+                "feedback.too_long",
+                *FEEDBACK_CHAR_LIMIT.end() as usize,
+                cx,
+            ))
+        }
+    }
+
+    fn email_address_error(&self, cx: &AppContext) -> Option<SharedString> {
+        (!self.valid_email_address()).then(|| t("feedback.invalid_email", cx))
+    }
+
+    /// Whether the feedback is sent without what identifies the user, like
+    /// the email address and metrics ID.
+    fn is_anonymous(&self, cx: &AppContext) -> bool {
+        self.send_anonymously || FeedbackSettings::get_global(cx).always_anonymous
+    }
+
+    fn valid_email_address(&self) -> bool {
+        !self.in_invalid_state(InvalidStateFlags::EmailAddress)
+    }
+
+    fn valid_character_count(&self) -> bool {
+        !self.in_invalid_state(InvalidStateFlags::CharacterCount)
+    }
+
+    fn in_invalid_state(&self, flag: InvalidStateFlags) -> bool {
+        match self.submission_state {
+            Some(SubmissionState::CannotSubmit {
+                reason: CannotSubmitReason::InvalidState { ref flags },
+            }) => flags.contains(flag),
+            _ => false,
+        }
+    }
+
+    pub(crate) fn awaiting_submission(&self) -> bool {
+        matches!(
+            self.submission_state,
+            Some(SubmissionState::CannotSubmit {
+                reason: CannotSubmitReason::AwaitingSubmission
+            })
+        )
+    }
+
+    pub(crate) fn can_submit(&self) -> bool {
+        matches!(self.submission_state, Some(SubmissionState::CanSubmit))
+    }
+
+    fn set_category(&mut self, category: FeedbackCategory, cx: &mut ViewContext<Self>) {
+        self.category = category;
+        self.editor.update(cx, |editor, cx| {
+            editor.set_placeholder_text(category.placeholder(cx).to_string(), cx)
+        });
+        if category == FeedbackCategory::Performance {
+            self.capture_performance_snapshot(cx);
+        }
+        cx.notify();
+    }
+
+    /// Starts sampling how Zed performs, for the snapshot sent with performance
+    /// feedback. The snapshot is only captured once for each feedback.
+    fn capture_performance_snapshot(&mut self, cx: &mut ViewContext<Self>) {
+        if !self.include_performance_snapshot
+            || self.performance_snapshot.is_some()
+            || self.capture_performance_snapshot_task.is_some()
+        {
+            return;
+        }
+        let Some(capture) = performance_snapshot::capture(cx) else {
+            return;
+        };
+        self.capture_performance_snapshot_task = Some(cx.spawn(|this, mut cx| async move {
+            let snapshot = capture.await;
+            this.update(&mut cx, |this, cx| {
+                this.performance_snapshot = Some(snapshot);
+                this.capture_performance_snapshot_task = None;
+                cx.notify();
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    fn set_include_performance_snapshot(&mut self, include: bool, cx: &mut ViewContext<Self>) {
+        self.include_performance_snapshot = include;
+        if include {
+            self.capture_performance_snapshot(cx);
+        } else {
+            self.performance_snapshot = None;
+            self.capture_performance_snapshot_task = None;
+        }
+        cx.notify();
+    }
+
+    /// Returns the performance snapshot to send with the feedback, which is
+    /// only sent with performance feedback.
+    fn performance_snapshot(&self) -> Option<&PerformanceSnapshot> {
+        if self.category == FeedbackCategory::Performance && self.include_performance_snapshot {
+            self.performance_snapshot.as_ref()
+        } else {
+            None
+        }
+    }
+
+    /// Attaches the details of a crash to the feedback, which then reports the crash.
+    fn attach_crash_report(&mut self, crash_report: String, cx: &mut ViewContext<Self>) {
+        self.crash_report = Some(crash_report);
+        self.set_category(FeedbackCategory::Crash, cx);
+    }
+
+    fn remove_crash_report(&mut self, cx: &mut ViewContext<Self>) {
+        self.crash_report = None;
+        cx.notify();
+    }
+
+    fn set_severity(&mut self, severity: Option<FeedbackSeverity>, cx: &mut ViewContext<Self>) {
+        self.severity = severity;
+        cx.notify();
+    }
+
+    fn render_triage_menus(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let this = cx.view().clone();
+        let category = self.category;
+        let severity = self.severity;
+
+        let category_menu = PopoverMenu::new("feedback-category-menu")
+            .anchor(AnchorCorner::TopLeft)
+            .trigger(
+                Button::new(
+                    "feedback-category-menu-trigger",
+                    t_with(
+                        "feedback.category_label",
+                        &[("category", &category.label(cx))],
+                        cx,
+                    ),
+                )
+                .label_size(LabelSize::Small),
+            )
+            .menu({
+                let this = this.clone();
+                move |cx| {
+                    let this = this.clone();
+                    Some(ContextMenu::build(cx, move |mut menu, cx| {
+                        for option in FeedbackCategory::ALL {
+                            menu = menu.toggleable_entry(
+                                option.label(cx),
+                                option == category,
+                                None,
+                                cx.handler_for(&this, move |this, cx| {
+                                    this.set_category(option, cx)
+                                }),
+                            );
+                        }
+                        menu
+                    }))
+                }
+            });
+
+        let severity_menu = PopoverMenu::new("feedback-severity-menu")
+            .anchor(AnchorCorner::TopLeft)
+            .trigger(
+                Button::new(
+                    "feedback-severity-menu-trigger",
+                    t_with(
+                        "feedback.severity_label",
+                        &[("severity", &FeedbackSeverity::label(severity, cx))],
+                        cx,
+                    ),
+                )
+                .label_size(LabelSize::Small),
+            )
+            .menu(move |cx| {
+                let this = this.clone();
+                Some(ContextMenu::build(cx, move |mut menu, cx| {
+                    let options = std::iter::once(None).chain(FeedbackSeverity::ALL.map(Some));
+                    for option in options {
+                        menu = menu.toggleable_entry(
+                            FeedbackSeverity::label(option, cx),
+                            option == severity,
+                            None,
+                            cx.handler_for(&this, move |this, cx| this.set_severity(option, cx)),
+                        );
+                    }
+                    menu
+                }))
+            });
+
+        let picker_background = cx.theme().colors().feedback_category_picker_background;
+        h_flex()
+            .px_2()
+            .pt_2()
+            .gap_2()
+            .child(
+                div()
+                    .rounded_md()
+                    .bg(picker_background)
+                    .child(category_menu),
+            )
+            .child(
+                div()
+                    .rounded_md()
+                    .bg(picker_background)
+                    .child(severity_menu),
+            )
+    }
+
+    /// Turns sending usage metrics on or off in the settings.
+    fn set_metrics_enabled(&mut self, enabled: bool, cx: &mut ViewContext<Self>) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let fs = workspace.read(cx).app_state().fs.clone();
+        update_settings_file::<TelemetrySettings>(fs, cx, move |settings| {
+            settings.metrics = Some(enabled)
+        });
+    }
+
+    /// Renders whether the metrics ID is sent with the feedback, with the
+    /// toggle for sending usage metrics.
+    fn render_telemetry_footer(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let metrics = TelemetrySettings::get_global(cx).metrics;
+        let hint = if self.is_anonymous(cx) {
+            t("feedback.metrics_id_anonymous", cx)
+        } else if metrics {
+            t("feedback.metrics_id_sent", cx)
+        } else {
+            t("feedback.metrics_id_not_sent", cx)
+        };
+
+        h_flex()
+            .pt_1()
+            .gap_2()
+            .border_t_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(CheckboxWithLabel::new(
+                "share_usage_metrics",
+                Label::new(t("feedback.share_usage_metrics", cx)).size(LabelSize::Small),
+                if metrics {
+                    Selection::Selected
+                } else {
+                    Selection::Unselected
+                },
+                cx.listener(|this, selection, cx| {
+                    this.set_metrics_enabled(*selection == Selection::Selected, cx)
+                }),
+            ))
+            .child(Label::new(hint).size(LabelSize::Small).color(Color::Muted))
+    }
+
+    /// Resizes the text area to the given height, or lets it grow with the
+    /// feedback again.
+    fn resize_editor(&mut self, height: Option<Pixels>, cx: &mut ViewContext<Self>) {
+        self.editor_height = height;
+        cx.notify();
+    }
+
+    /// Renders the text area, or the preview in its place, with the drag handle
+    /// to resize it.
+    fn render_text_area(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let line_height = cx.line_height();
+        let height = self
+            .editor_height
+            .unwrap_or_else(|| {
+                let lines = self
+                    .editor
+                    .update(cx, |editor, cx| editor.max_point(cx).row().0 + 1);
+                line_height * lines as f32
+            })
+            .clamp(
+                line_height * *EDITOR_LINES.start() as f32,
+                line_height * *EDITOR_LINES.end() as f32,
+            );
+
+        v_flex()
+            .on_drag_move(
+                cx.listener(|this, event: &DragMoveEvent<DraggedResizeHandle>, cx| {
+                    let height = event.event.position.y - event.bounds.top();
+                    this.resize_editor(Some(height), cx);
+                }),
+            )
+            .map(|el| {
+                if self.showing_preview {
+                    el.child(self.render_preview(cx).h(height))
+                } else {
+                    el.child(div().h(height).p_2().child(self.editor.clone()))
+                }
+            })
+            .child(
+                div()
+                    .id("feedback-resize-handle")
+                    .w_full()
+                    .h(RESIZE_HANDLE_SIZE)
+                    .flex()
+                    .justify_center()
+                    .cursor_row_resize()
+                    .on_drag(DraggedResizeHandle, |handle, cx| {
+                        cx.stop_propagation();
+                        cx.new_view(|_| handle.clone())
+                    })
+                    .on_mouse_up(
+                        MouseButton::Left,
+                        cx.listener(|this, event: &MouseUpEvent, cx| {
+                            if event.click_count == 2 {
+                                this.resize_editor(None, cx);
+                                cx.stop_propagation();
+                            }
+                        }),
+                    )
+                    .child(
+                        div()
+                            .w_8()
+                            .h(px(2.))
+                            .rounded_sm()
+                            .bg(cx.theme().colors().border_variant),
+                    ),
+            )
+    }
+
+    fn render_performance_snapshot_checkbox(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let label = if self.capture_performance_snapshot_task.is_some() {
+            t("feedback.capturing_performance_snapshot", cx)
+        } else {
+            t("feedback.include_performance_snapshot", cx)
+        };
+        CheckboxWithLabel::new(
+            "include_performance_snapshot",
+            Label::new(label).size(LabelSize::Small),
+            if self.include_performance_snapshot {
+                Selection::Selected
+            } else {
+                Selection::Unselected
+            },
+            cx.listener(|this, selection, cx| {
+                this.set_include_performance_snapshot(*selection == Selection::Selected, cx)
+            }),
+        )
+    }
+
+    /// Opens a new GitHub issue with the feedback and the system specs, for
+    /// feedback that's better shared publicly.
+    fn file_on_github(&mut self, _: &FileOnGitHub, cx: &mut ViewContext<Self>) {
+        let feedback_text = self.editor.read(cx).text(cx);
+        cx.open_url(&github_issue_url(&self.system_specs, feedback_text.trim()));
+    }
+
+    fn submit_feedback_action(&mut self, _: &SubmitFeedback, cx: &mut ViewContext<Self>) {
+        self.submit(cx).detach_and_log_err(cx);
+    }
+
+    pub(crate) fn showing_preview(&self) -> bool {
+        self.showing_preview
+    }
+
+    /// Switches between writing the feedback and previewing it rendered as markdown.
+    pub(crate) fn toggle_preview(&mut self, _: &TogglePreview, cx: &mut ViewContext<Self>) {
+        self.showing_preview = !self.showing_preview;
+        if !self.showing_preview {
+            self.preview = None;
+            self.parse_preview_task = None;
+            cx.focus_view(&self.editor);
+            cx.notify();
+            return;
+        }
+
+        let text = self.editor.read(cx).text(cx);
+        let languages = self
+            .workspace
+            .upgrade()
+            .map(|workspace| workspace.read(cx).app_state().languages.clone());
+        self.parse_preview_task = Some(cx.spawn(|this, mut cx| async move {
+            let preview = cx
+                .background_executor()
+                .spawn(async move { parse_markdown(&text, None, languages).await })
+                .await;
+            this.update(&mut cx, |this, cx| {
+                if this.showing_preview {
+                    this.preview = Some(preview);
+                    cx.notify();
+                }
+            })
+            .ok();
+        }));
+        cx.focus(&self.preview_focus_handle);
+        cx.notify();
+    }
+
+    /// Pastes a URL as a markdown link: around the selected text, or else as
+    /// an autolink. Anything else is pasted as is.
+    fn paste_url_as_link(&mut self, _: &editor::actions::Paste, cx: &mut ViewContext<Self>) {
+        if !self.editor.focus_handle(cx).is_focused(cx) {
+            return;
+        }
+        let Some(clipboard_text) = cx.read_from_clipboard().map(|item| item.text().clone()) else {
+            return;
+        };
+        let Some(url) = pasted_url(&clipboard_text) else {
+            return;
+        };
+
+        let linked = self.editor.update(cx, |editor, cx| {
+            if editor.selections.count() != 1 {
+                return false;
+            }
+            let selection = editor.selections.newest::<usize>(cx);
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            // URLs that are pasted into a link's own syntax, like `[text](`, stay as they are.
+            if matches!(
+                snapshot.reversed_chars_at(selection.start).next(),
+                Some('(' | '<')
+            ) {
+                return false;
+            }
+            let selected_text = snapshot
+                .text_for_range(selection.range())
+                .collect::<String>();
+            editor.insert(&markdown_link(&selected_text, url), cx);
+            true
+        });
+        if linked {
+            cx.stop_propagation();
+        }
+    }
+
+    fn render_preview(&self, cx: &mut ViewContext<Self>) -> Stateful<Div> {
+        div()
+            .id("feedback-preview")
+            .track_focus(&self.preview_focus_handle)
+            .p_2()
+            .overflow_y_scroll()
+            .children(self.preview.as_ref().map(|preview| {
+                v_flex().gap_2().children(render_parsed_markdown(
+                    preview,
+                    Some(self.workspace.clone()),
+                    cx,
+                ))
+            }))
+    }
+}
+
+fn is_email_address(text: &str) -> bool {
+    EMAIL_REGEX.is_match(text)
+}
+
+/// Returns the URL that the pasted text is, if it's a single web URL.
+fn pasted_url(text: &str) -> Option<&str> {
+    let text = text.trim();
+    let host_and_path = text
+        .strip_prefix("https://")
+        .or_else(|| text.strip_prefix("http://"))?;
+    let is_url = !host_and_path.is_empty() && !host_and_path.contains(char::is_whitespace);
+    is_url.then_some(text)
+}
+
+fn markdown_link(text: &str, url: &str) -> String {
+    if text.is_empty() {
+        format!("<{url}>")
+    } else {
+        format!("[{text}]({url})")
+    }
+}
+
+impl Render for FeedbackEditor {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        self.update_submission_state(cx);
+
+        let open_zed_repo = cx.listener(|_, _, cx| cx.dispatch_action(Box::new(OpenZedRepo)));
+        let always_anonymous = FeedbackSettings::get_global(cx).always_anonymous;
+
+        v_flex()
+            .key_context("GiveFeedback")
+            .on_action(cx.listener(Self::submit_feedback_action))
+            .on_action(cx.listener(Self::dismiss))
+            .on_action(cx.listener(Self::cancel))
+            // Escape cancels like `feedback::Cancel` once there are no selections or menus to cancel.
+            .on_action(
+                cx.listener(|this, _: &editor::actions::Cancel, cx| this.cancel(&Cancel, cx)),
+            )
+            .on_action(cx.listener(Self::discard_draft))
+            .on_action(cx.listener(Self::file_on_github))
+            .on_action(cx.listener(Self::toggle_preview))
+            .capture_action(cx.listener(Self::paste_url_as_link))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .when(self.failed_request.is_some(), |el| {
+                el.child(
+                    h_flex()
+                        .justify_between()
+                        .gap_2()
+                        .p_2()
+                        .bg(cx.theme().colors().feedback_error_banner_background)
+                        .border_b_1()
+                        .border_color(cx.theme().status().error_border)
+                        .child(
+                            h_flex()
+                                .gap_1()
+                                .child(Icon::new(IconName::XCircle).color(Color::Error))
+                                .child(Label::new(
+                                    self.failure_message
+                                        .clone()
+                                        .unwrap_or_else(|| t("feedback.submission_error", cx)),
+                                )),
+                        )
+                        .child(
+                            h_flex()
+                                .gap_1()
+                                .child(
+                                    Button::new("retry_feedback", t("feedback.retry", cx))
+                                        .style(ButtonStyle::Filled)
+                                        .on_click(cx.listener(|this, _, cx| {
+                                            this.retry_submission(cx).detach()
+                                        })),
+                                )
+                                .child(
+                                    Button::new(
+                                        "submit_feedback_later",
+                                        t("feedback.submit_later", cx),
+                                    )
+                                    .style(ButtonStyle::Subtle)
+                                    .on_click(cx.listener(|this, _, cx| this.submit_later(cx))),
+                                ),
+                        ),
+                )
+            })
+            .child(self.render_triage_menus(cx))
+            .when(self.crash_report.is_some() && !always_anonymous, |el| {
+                el.child(
+                    h_flex()
+                        .mx_2()
+                        .mt_2()
+                        .p_1()
+                        .justify_between()
+                        .rounded_md()
+                        .bg(cx.theme().colors().element_background)
+                        .child(
+                            h_flex()
+                                .gap_1()
+                                .child(
+                                    Icon::new(IconName::ExclamationTriangle)
+                                        .size(IconSize::Small)
+                                        .color(Color::Warning),
+                                )
+                                .child(
+                                    Label::new(t("feedback.crash_report_attached", cx))
+                                        .size(LabelSize::Small),
+                                ),
+                        )
+                        .child(
+                            Button::new("remove_crash_report", t("feedback.remove", cx))
+                                .style(ButtonStyle::Subtle)
+                                .label_size(LabelSize::Small)
+                                .on_click(cx.listener(|this, _, cx| this.remove_crash_report(cx))),
+                        ),
+                )
+            })
+            .child(self.render_text_area(cx))
+            .children(self.character_count_error(cx).map(|error| {
+                h_flex()
+                    .px_2()
+                    .pb_1()
+                    .gap_1()
+                    .child(
+                        Icon::new(IconName::ExclamationTriangle)
+                            .size(IconSize::Small)
+                            .color(Color::Error),
+                    )
+                    .child(Label::new(error).size(LabelSize::Small).color(Color::Error))
+            }))
+            .child(
+                v_flex()
+                    .p_2()
+                    .gap_1()
+                    .border_t_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(
+                        h_flex()
+                            .p_2()
+                            .border_1()
+                            .rounded_md()
+                            .border_color(if self.valid_email_address() {
+                                cx.theme().colors().border
+                            } else {
+                                cx.theme().status().error_border
+                            })
+                            .child(self.email_address_editor.clone()),
+                    )
+                    .children(
+                        self.email_address_error(cx).map(|error| {
+                            Label::new(error).size(LabelSize::Small).color(Color::Error)
+                        }),
+                    )
+                    .child(
+                        h_flex()
+                            .justify_between()
+                            .child(
+                                v_flex()
+                                    .gap_1()
+                                    .when(!always_anonymous, |el| {
+                                        el.child(CheckboxWithLabel::new(
+                                            "include_diagnostics",
+                                            Label::new(t("feedback.include_diagnostics", cx))
+                                                .size(LabelSize::Small),
+                                            if self.include_diagnostics {
+                                                Selection::Selected
+                                            } else {
+                                                Selection::Unselected
+                                            },
+                                            cx.listener(|this, selection, cx| {
+                                                this.include_diagnostics =
+                                                    *selection == Selection::Selected;
+                                                cx.notify();
+                                            }),
+                                        ))
+                                    })
+                                    .when(self.category == FeedbackCategory::Performance, |el| {
+                                        el.child(self.render_performance_snapshot_checkbox(cx))
+                                    })
+                                    // The setting can't be overridden for a single submission.
+                                    .when(!always_anonymous, |el| {
+                                        el.child(CheckboxWithLabel::new(
+                                            "send_anonymously",
+                                            Label::new(t("feedback.send_anonymously", cx))
+                                                .size(LabelSize::Small),
+                                            if self.send_anonymously {
+                                                Selection::Selected
+                                            } else {
+                                                Selection::Unselected
+                                            },
+                                            cx.listener(|this, selection, cx| {
+                                                this.send_anonymously =
+                                                    *selection == Selection::Selected;
+                                                cx.notify();
+                                            }),
+                                        ))
+                                    }),
+                            )
+                            .child(
+                                h_flex()
+                                    .gap_1()
+                                    .child(
+                                        Button::new(
+                                            "copy_system_specs",
+                                            t("feedback.copy_system_specs", cx),
+                                        )
+                                        .style(ButtonStyle::Subtle)
+                                        .label_size(LabelSize::Small)
+                                        .on_click(
+                                            |_, cx| {
+                                                cx.dispatch_action(Box::new(
+                                                    CopySystemSpecsIntoClipboard,
+                                                ))
+                                            },
+                                        ),
+                                    )
+                                    .child(
+                                        Button::new(
+                                            "preview_request",
+                                            t("feedback.preview_request", cx),
+                                        )
+                                        .style(ButtonStyle::Subtle)
+                                        .label_size(LabelSize::Small)
+                                        .on_click(
+                                            cx.listener(|this, _, cx| this.preview_request(cx)),
+                                        ),
+                                    ),
+                            ),
+                    )
+                    .child(
+                        h_flex()
+                            .justify_between()
+                            .child(
+                                Label::new(if self.is_anonymous(cx) {
+                                    t("feedback.anonymous_hint", cx)
+                                } else {
+                                    t("feedback.email_hint", cx)
+                                })
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                            )
+                            .child(
+                                h_flex()
+                                    .gap_1()
+                                    .child(
+                                        Button::new(
+                                            "file_on_github",
+                                            t("feedback.file_on_github", cx),
+                                        )
+                                        .style(ButtonStyle::Transparent)
+                                        .icon(IconName::Github)
+                                        .icon_position(IconPosition::Start)
+                                        .icon_size(IconSize::Small)
+                                        .tooltip(|cx| {
+                                            Tooltip::text(
+                                                t("feedback.file_on_github_tooltip", cx),
+                                                cx,
+                                            )
+                                        })
+                                        .on_click(
+                                            cx.listener(|this, _, cx| {
+                                                this.file_on_github(&FileOnGitHub, cx)
+                                            }),
+                                        ),
+                                    )
+                                    .child(
+                                        Button::new(
+                                            "zed_repository",
+                                            t("feedback.zed_repository", cx),
+                                        )
+                                        .style(ButtonStyle::Transparent)
+                                        .icon(IconName::ExternalLink)
+                                        .icon_position(IconPosition::End)
+                                        .icon_size(IconSize::Small)
+                                        .track_focus(&self.zed_repository_focus_handle)
+                                        .on_click(open_zed_repo),
+                                    ),
+                            ),
+                    )
+                    .child(self.render_telemetry_footer(cx)),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::SemanticVersion;
+    use release_channel::ReleaseChannel;
+
+    #[test]
+    fn test_anonymous_feedback_request() {
+        let system_specs =
+            SystemSpecs::new_stateless(SemanticVersion::new(1, 0, 0), None, ReleaseChannel::Dev);
+        let builder = || {
+            FeedbackRequestBody::builder("Feedback", FeedbackCategory::Bug, system_specs.clone())
+                .email(Some("user@example.com".into()))
+                .installation_id(Some("installation".into()))
+                .crash_report(Some("Crash"))
+        };
+
+        let request = builder().build();
+        assert_eq!(request.email.as_deref(), Some("user@example.com"));
+        assert_eq!(request.installation_id.as_deref(), Some("installation"));
+
+        let request = builder().anonymous(true).build();
+        assert_eq!(request.email, None);
+        assert_eq!(request.metrics_id, None);
+        assert_eq!(request.installation_id, None);
+        assert!(!request.is_staff);
+        assert_eq!(request.feedback_text, "Feedback");
+        assert_eq!(request.crash_report, Some("Crash"));
+
+        let mut with_metrics_id = builder();
+        with_metrics_id.body.metrics_id = Some("metrics".into());
+        let request = with_metrics_id.metrics(false).build();
+        assert_eq!(request.metrics_id, None);
+        assert_eq!(request.installation_id.as_deref(), Some("installation"));
+    }
+
+    #[test]
+    fn test_feedback_request_with_settings() {
+        let system_specs =
+            SystemSpecs::new_stateless(SemanticVersion::new(1, 0, 0), None, ReleaseChannel::Dev);
+        let mut settings = SettingsWithoutUi::parse("").unwrap().feedback;
+        settings.metadata.insert("team".into(), "platform".into());
+        let builder = || {
+            FeedbackRequestBody::builder("Feedback", FeedbackCategory::Bug, system_specs.clone())
+                .email(Some("user@example.com".into()))
+                .installation_id(Some("installation".into()))
+                .metadata(BTreeMap::from_iter([("action".into(), "save".into())]))
+                .crash_report(Some("Crash"))
+        };
+
+        let request = builder().settings(&settings).build();
+        assert_eq!(request.email.as_deref(), Some("user@example.com"));
+        assert_eq!(request.crash_report, Some("Crash"));
+        assert_eq!(
+            request.metadata,
+            BTreeMap::from_iter([
+                ("action".into(), "save".into()),
+                ("team".into(), "platform".into())
+            ])
+        );
+
+        // Sending anonymously can't be turned off when the settings say to
+        // always do so.
+        settings.always_anonymous = true;
+        let request = builder().settings(&settings).anonymous(false).build();
+        assert_eq!(request.email, None);
+        assert_eq!(request.installation_id, None);
+        assert_eq!(request.crash_report, None);
+    }
+
+    #[test]
+    fn test_pasted_urls_as_links() {
+        assert_eq!(
+            pasted_url(" https://zed.dev/docs\n"),
+            Some("https://zed.dev/docs")
+        );
+        assert_eq!(
+            pasted_url("http://localhost:8080"),
+            Some("http://localhost:8080")
+        );
+        assert_eq!(pasted_url("zed.dev"), None);
+        assert_eq!(pasted_url("see https://zed.dev"), None);
+        assert_eq!(pasted_url("file:///etc/hosts"), None);
+        assert_eq!(pasted_url("https://"), None);
+
+        assert_eq!(
+            markdown_link("the docs", "https://zed.dev/docs"),
+            "[the docs](https://zed.dev/docs)"
+        );
+        assert_eq!(markdown_link("", "https://zed.dev"), "<https://zed.dev>");
+    }
+
+    #[test]
+    fn test_email_addresses() {
+        assert!(is_email_address("user@example.com"));
+        assert!(is_email_address("first.last+zed@mail.example.co"));
+        assert!(!is_email_address("user@example"));
+        assert!(!is_email_address("user example.com"));
+        assert!(!is_email_address("reach me at user@example.com"));
+        assert!(!is_email_address("user@example.c|m"));
+    }
+}
@@ -0,0 +1,338 @@
+use anyhow::Result;
+use db::{define_connection, query, sqlez_macros::sql};
+use gpui::{
+    AppContext, ClipboardItem, DismissEvent, EventEmitter, FocusHandle, FocusableView, Render,
+    ScrollHandle, ViewContext, WeakView,
+};
+use i18n::{t, t_with};
+use serde_derive::Deserialize;
+use time::OffsetDateTime;
+use time_format::TimestampFormat;
+use ui::{prelude::*, Button, ButtonStyle, ElevationIndex, Modal, ModalHeader, Section, Tooltip};
+use util::ResultExt;
+use workspace::{ModalView, Workspace};
+
+use crate::{
+    feedback_editor::{FeedbackCategory, FeedbackEditor},
+    OpenFeedbackHistory,
+};
+
+define_connection!(
+    // Current schema shape using pseudo-rust syntax:
+    // submitted_feedback(
+    //   id: i64,
+    //   feedback_text: String,
+    //   category: String, // The ID of the FeedbackCategory
+    //   submitted_at: i64, // Unix timestamp, in seconds
+    //   ticket_id: Option<String>, // The ID that the server returned for the feedback
+    // )
+    pub static ref FEEDBACK_HISTORY_DB: FeedbackHistoryDb<()> =
+        &[sql!(
+            CREATE TABLE submitted_feedback(
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                feedback_text TEXT NOT NULL,
+                category TEXT NOT NULL,
+                submitted_at INTEGER NOT NULL,
+                ticket_id TEXT
+            ) STRICT;
+        )];
+);
+
+impl FeedbackHistoryDb {
+    query! {
+        pub fn submitted_feedback() -> Result<Vec<(String, String, i64, Option<String>)>> {
+            SELECT feedback_text, category, submitted_at, ticket_id FROM submitted_feedback
+            ORDER BY id DESC
+        }
+    }
+
+    query! {
+        pub async fn record_submission(
+            feedback_text: String,
+            category: String,
+            submitted_at: i64,
+            ticket_id: Option<String>
+        ) -> Result<()> {
+            INSERT INTO submitted_feedback(feedback_text, category, submitted_at, ticket_id)
+            VALUES (?, ?, ?, ?)
+        }
+    }
+}
+
+/// Feedback that was submitted successfully.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct SubmittedFeedback {
+    pub feedback_text: String,
+    pub category: FeedbackCategory,
+    pub submitted_at: OffsetDateTime,
+    pub ticket_id: Option<String>,
+}
+
+/// The fields of a feedback request that are kept in the history.
+#[derive(Deserialize)]
+struct FeedbackRequestSummary {
+    feedback_text: String,
+    category: FeedbackCategory,
+}
+
+/// Adds feedback to the history once it's submitted, given the JSON body of
+/// its request and the ticket ID that the server returned, if any.
+pub(crate) async fn record_submission(request_json: &str, ticket_id: Option<String>) -> Result<()> {
+    let request: FeedbackRequestSummary = serde_json::from_str(request_json)?;
+    FEEDBACK_HISTORY_DB
+        .record_submission(
+            request.feedback_text,
+            request.category.id().to_string(),
+            OffsetDateTime::now_utc().unix_timestamp(),
+            ticket_id,
+        )
+        .await
+}
+
+/// Returns the feedback submitted so far, most recent first.
+fn submitted_feedback() -> Result<Vec<SubmittedFeedback>> {
+    Ok(FEEDBACK_HISTORY_DB
+        .submitted_feedback()?
+        .into_iter()
+        .map(
+            |(feedback_text, category, submitted_at, ticket_id)| SubmittedFeedback {
+                feedback_text,
+                category: FeedbackCategory::from_id(&category).unwrap_or_default(),
+                submitted_at: OffsetDateTime::from_unix_timestamp(submitted_at)
+                    .unwrap_or(OffsetDateTime::UNIX_EPOCH),
+                ticket_id,
+            },
+        )
+        .collect())
+}
+
+/// Lists the feedback submitted before, to copy its ticket ID or to submit
+/// an edited version of it again.
+pub struct FeedbackHistoryModal {
+    workspace: WeakView<Workspace>,
+    /// The submitted feedback, once loaded from the database.
+    entries: Option<Vec<SubmittedFeedback>>,
+    focus_handle: FocusHandle,
+    scroll_handle: ScrollHandle,
+}
+
+impl ModalView for FeedbackHistoryModal {}
+
+impl EventEmitter<DismissEvent> for FeedbackHistoryModal {}
+
+impl FocusableView for FeedbackHistoryModal {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl FeedbackHistoryModal {
+    pub fn register(workspace: &mut Workspace, _: &mut ViewContext<Workspace>) {
+        workspace.register_action(|workspace, _: &OpenFeedbackHistory, cx| {
+            let handle = cx.view().downgrade();
+            workspace.toggle_modal(cx, |cx| Self::new(handle, cx));
+        });
+    }
+
+    fn new(workspace: WeakView<Workspace>, cx: &mut ViewContext<Self>) -> Self {
+        cx.spawn(|this, mut cx| async move {
+            let entries = cx
+                .background_executor()
+                .spawn(async { submitted_feedback().log_err().unwrap_or_default() })
+                .await;
+            this.update(&mut cx, |this, cx| {
+                this.entries = Some(entries);
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+
+        Self {
+            workspace,
+            entries: None,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: ScrollHandle::new(),
+        }
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, cx: &mut ViewContext<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    /// Opens the feedback editor with the feedback, to edit it and submit it again.
+    fn resubmit(&mut self, entry: &SubmittedFeedback, cx: &mut ViewContext<Self>) {
+        let (feedback_text, category) = (entry.feedback_text.clone(), entry.category);
+        cx.emit(DismissEvent);
+        self.workspace
+            .update(cx, |workspace, cx| {
+                FeedbackEditor::resubmit(workspace, feedback_text, category, cx)
+            })
+            .ok();
+    }
+
+    fn render_entry(
+        &self,
+        ix: usize,
+        entry: &SubmittedFeedback,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let submitted_at = time_format::format_localized_timestamp(
+            entry.submitted_at,
+            OffsetDateTime::now_utc(),
+            cx.local_timezone(),
+            TimestampFormat::EnhancedAbsolute,
+        );
+        let summary = entry
+            .feedback_text
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_string();
+
+        v_flex()
+            .gap_1()
+            .py_1()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Label::new(entry.category.label(cx))
+                            .size(LabelSize::Small)
+                            .color(Color::Accent),
+                    )
+                    .child(
+                        Label::new(submitted_at)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .children(entry.ticket_id.as_ref().map(|ticket_id| {
+                        Label::new(t_with("feedback.history.ticket", &[("id", ticket_id)], cx))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                    })),
+            )
+            .child(Label::new(summary).single_line())
+            .child(
+                h_flex()
+                    .gap_1()
+                    .children(entry.ticket_id.clone().map(|ticket_id| {
+                        Button::new(
+                            ("copy-ticket-id", ix),
+                            t("feedback.history.copy_ticket_id", cx),
+                        )
+                        .style(ButtonStyle::Subtle)
+                        .layer(ElevationIndex::ModalSurface)
+                        .label_size(LabelSize::Small)
+                        .on_click(move |_, cx| {
+                            cx.write_to_clipboard(ClipboardItem::new(ticket_id.clone()))
+                        })
+                    }))
+                    .child(
+                        Button::new(
+                            ("resubmit-feedback", ix),
+                            t("feedback.history.resubmit", cx),
+                        )
+                        .style(ButtonStyle::Subtle)
+                        .layer(ElevationIndex::ModalSurface)
+                        .label_size(LabelSize::Small)
+                        .tooltip(|cx| Tooltip::text(t("feedback.history.resubmit_tooltip", cx), cx))
+                        .on_click(cx.listener({
+                            let entry = entry.clone();
+                            move |this, _, cx| this.resubmit(&entry, cx)
+                        })),
+                    ),
+            )
+    }
+}
+
+impl Render for FeedbackHistoryModal {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let entries = self.entries.clone().unwrap_or_default();
+        let section = if self.entries.is_some() && entries.is_empty() {
+            Section::new().child(Label::new(t("feedback.history.empty", cx)).color(Color::Muted))
+        } else {
+            Section::new().children(
+                entries
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, entry)| self.render_entry(ix, entry, cx)),
+            )
+        };
+
+        div()
+            .track_focus(&self.focus_handle)
+            .key_context("FeedbackHistory")
+            .elevation_3(cx)
+            .on_action(cx.listener(Self::cancel))
+            .occlude()
+            .w(rems(34.))
+            .max_h(rems(40.))
+            .pb_4()
+            .child(
+                Modal::new("feedback-history", Some(self.scroll_handle.clone()))
+                    .header(
+                        ModalHeader::new()
+                            .show_dismiss_button(true)
+                            .headline(t("feedback.history.title", cx)),
+                    )
+                    .section(section),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_summary() {
+        let request: FeedbackRequestSummary = serde_json::from_str(
+            r#"{
+                "feedback_text": "Feedback",
+                "category": "feature_request",
+                "email": null,
+                "is_staff": false
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(request.feedback_text, "Feedback");
+        assert_eq!(request.category, FeedbackCategory::FeatureRequest);
+
+        for category in FeedbackCategory::ALL {
+            assert_eq!(FeedbackCategory::from_id(category.id()), Some(category));
+        }
+    }
+
+    #[test]
+    fn test_record_submission() {
+        let request_json = |feedback_text: &str| {
+            serde_json::json!({ "feedback_text": feedback_text, "category": "bug" }).to_string()
+        };
+        let recorded_at = OffsetDateTime::now_utc().unix_timestamp();
+        smol::block_on(record_submission(
+            &request_json("The history round-trips"),
+            Some("ZED-1".into()),
+        ))
+        .unwrap();
+        smol::block_on(record_submission(
+            &request_json("The history round-trips again"),
+            None,
+        ))
+        .unwrap();
+
+        // Other tests submit feedback to the same database.
+        let entries = submitted_feedback()
+            .unwrap()
+            .into_iter()
+            .filter(|entry| entry.feedback_text.starts_with("The history round-trips"))
+            .collect::<Vec<_>>();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].feedback_text, "The history round-trips again");
+        assert_eq!(entries[0].ticket_id, None);
+        assert_eq!(entries[1].feedback_text, "The history round-trips");
+        assert_eq!(entries[1].category, FeedbackCategory::Bug);
+        assert_eq!(entries[1].ticket_id.as_deref(), Some("ZED-1"));
+        assert!(entries[1].submitted_at.unix_timestamp() >= recorded_at);
+    }
+}
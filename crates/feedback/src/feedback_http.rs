@@ -0,0 +1,269 @@
+use std::{fmt, sync::Arc};
+
+use client::{Client, HttpClientSettings, ProxySettings};
+use gpui::{AppContext, Global, SharedString};
+use http::{HttpClient, HttpClientOptions, HttpClientWithUrl, StatusCode};
+use i18n::{t, t_with};
+use isahc::error::ErrorKind;
+use settings::Settings;
+
+use crate::feedback_settings::{FeedbackHttpConfig, FeedbackSettings, SettingsWithoutUi};
+
+struct GlobalFeedbackHttpClient {
+    config: FeedbackHttpConfig,
+    client: Arc<HttpClientWithUrl>,
+}
+
+impl Global for GlobalFeedbackHttpClient {}
+
+#[cfg(any(test, feature = "test-support"))]
+struct GlobalFeedbackHttpClientOverride(Arc<HttpClientWithUrl>);
+
+#[cfg(any(test, feature = "test-support"))]
+impl Global for GlobalFeedbackHttpClientOverride {}
+
+/// Sets the HTTP client that feedback is sent with in tests, like a fake
+/// server's, instead of the one Zed sends all of its requests with. The proxy
+/// and certificates configured for feedback don't apply to it.
+#[cfg(any(test, feature = "test-support"))]
+pub(crate) fn set_http_client(http_client: Arc<HttpClientWithUrl>, cx: &mut AppContext) {
+    cx.set_global(GlobalFeedbackHttpClientOverride(http_client));
+}
+
+#[cfg(any(test, feature = "test-support"))]
+fn http_client_override(cx: &AppContext) -> Option<Arc<HttpClientWithUrl>> {
+    cx.try_global::<GlobalFeedbackHttpClientOverride>()
+        .map(|http_client| http_client.0.clone())
+}
+
+#[cfg(not(any(test, feature = "test-support")))]
+fn http_client_override(_: &AppContext) -> Option<Arc<HttpClientWithUrl>> {
+    None
+}
+
+/// Returns the HTTP client that Zed sends all of its requests with, or the
+/// one that was set for feedback in tests instead.
+pub(crate) fn zed_http_client(cx: &AppContext) -> Arc<HttpClientWithUrl> {
+    http_client_override(cx).unwrap_or_else(|| Client::global(cx).http_client())
+}
+
+/// Returns the HTTP client to send feedback to the URL with. That's the one
+/// Zed sends all of its requests with, unless a proxy or certificates are
+/// configured for feedback.
+pub(crate) fn http_client(url: &str, cx: &mut AppContext) -> Arc<HttpClientWithUrl> {
+    if let Some(http_client) = http_client_override(cx) {
+        return http_client;
+    }
+    let zed_client = zed_http_client(cx);
+    let Some(config) = FeedbackSettings::get_global(cx).http_config(url) else {
+        return zed_client;
+    };
+    if let Some(global) = cx.try_global::<GlobalFeedbackHttpClient>() {
+        if global.config == config {
+            return global.client.clone();
+        }
+    }
+
+    let options = http_client_options(
+        &config,
+        ProxySettings::get_global(cx).proxy.clone(),
+        HttpClientSettings::get_global(cx),
+    );
+    let client = Arc::new(HttpClientWithUrl::with_options(
+        zed_client.base_url(),
+        options,
+    ));
+    cx.set_global(GlobalFeedbackHttpClient {
+        config,
+        client: client.clone(),
+    });
+    client
+}
+
+/// Returns the HTTP client to send feedback to the URL with when there's no
+/// UI, like [`http_client`] does, or `None` when the one that Zed sends all
+/// of its requests with is used.
+pub(crate) fn http_client_without_ui(
+    url: &str,
+    settings: &SettingsWithoutUi,
+) -> Option<HttpClientWithUrl> {
+    let config = settings.feedback.http_config(url)?;
+    let options = http_client_options(&config, settings.proxy.clone(), &settings.http_client);
+    Some(HttpClientWithUrl::with_options(
+        settings.server_url.clone(),
+        options,
+    ))
+}
+
+/// Returns the options to build the HTTP client that feedback is sent with,
+/// falling back to the proxy that Zed uses when none is set for feedback.
+fn http_client_options(
+    config: &FeedbackHttpConfig,
+    zed_proxy: Option<String>,
+    http_client_settings: &HttpClientSettings,
+) -> HttpClientOptions {
+    let proxy = config.proxy.clone().or(zed_proxy);
+    let mut options = http_client_settings.http_client_options(proxy);
+    if config.ca_certificates.is_some() {
+        options.ca_certificates.clone_from(&config.ca_certificates);
+    }
+    options
+}
+
+/// Returns the error that a request sent through the HTTP client failed with,
+/// with how the network's setup caused it as its context, if it did.
+pub(crate) fn request_error(error: http::Error, http_client: &HttpClientWithUrl) -> anyhow::Error {
+    match FeedbackHttpError::from_request_error(&error, http_client) {
+        Some(http_error) => anyhow::Error::new(error).context(http_error),
+        None => error.into(),
+    }
+}
+
+/// Why feedback couldn't reach the server, when it's because of how the
+/// network between them is set up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum FeedbackHttpError {
+    /// The proxy requires credentials, which weren't given or were refused.
+    ProxyAuthenticationRequired,
+    /// The proxy couldn't be connected to.
+    ProxyUnreachable { proxy: String },
+    /// The server's certificate couldn't be verified, like when the network
+    /// intercepts TLS traffic with its own certificate authority.
+    TlsVerificationFailed,
+}
+
+impl FeedbackHttpError {
+    /// Returns the error that a request sent through the HTTP client failed
+    /// with, if it's because of the network's setup.
+    pub(crate) fn from_request_error(
+        error: &http::Error,
+        http_client: &HttpClientWithUrl,
+    ) -> Option<Self> {
+        let proxy = http_client.proxy();
+        match error.kind() {
+            ErrorKind::BadServerCertificate | ErrorKind::TlsEngine => {
+                Some(Self::TlsVerificationFailed)
+            }
+            ErrorKind::InvalidCredentials if proxy.is_some() => {
+                Some(Self::ProxyAuthenticationRequired)
+            }
+            // Proxies refuse to open a tunnel to https URLs without credentials
+            // before there's a response, so only the error's message tells.
+            _ if proxy.is_some() && error.to_string().contains("407") => {
+                Some(Self::ProxyAuthenticationRequired)
+            }
+            ErrorKind::ConnectionFailed | ErrorKind::NameResolution => {
+                proxy.map(|proxy| Self::ProxyUnreachable {
+                    proxy: proxy.to_string(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the error that a response's status shows, if it's because of
+    /// the network's setup.
+    pub(crate) fn from_status(status: StatusCode) -> Option<Self> {
+        (status == StatusCode::PROXY_AUTHENTICATION_REQUIRED)
+            .then_some(Self::ProxyAuthenticationRequired)
+    }
+
+    pub(crate) fn message(&self, cx: &AppContext) -> SharedString {
+        match self {
+            Self::ProxyAuthenticationRequired => t("feedback.proxy_authentication_required", cx),
+            Self::ProxyUnreachable { proxy } => {
+                t_with("feedback.proxy_unreachable", &[("proxy", proxy)], cx)
+            }
+            Self::TlsVerificationFailed => t("feedback.tls_verification_failed", cx),
+        }
+    }
+}
+
+impl fmt::Display for FeedbackHttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ProxyAuthenticationRequired => write!(f, "proxy authentication required"),
+            Self::ProxyUnreachable { proxy } => write!(f, "couldn't connect to the proxy {proxy}"),
+            Self::TlsVerificationFailed => write!(f, "TLS verification failed"),
+        }
+    }
+}
+
+impl std::error::Error for FeedbackHttpError {}
+
+/// The error status that the feedback endpoint responded to a submission with.
+#[derive(Debug, PartialEq)]
+pub(crate) struct FeedbackRejected(pub StatusCode);
+
+impl FeedbackRejected {
+    /// Returns whether submitting the same feedback again would be rejected
+    /// too, like for client errors other than those asking to retry later.
+    pub(crate) fn is_permanent(&self) -> bool {
+        self.0.is_client_error()
+            && self.0 != StatusCode::REQUEST_TIMEOUT
+            && self.0 != StatusCode::TOO_MANY_REQUESTS
+    }
+}
+
+impl fmt::Display for FeedbackRejected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Feedback API failed with error: {}", self.0)
+    }
+}
+
+impl std::error::Error for FeedbackRejected {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HttpClientOptions;
+
+    #[test]
+    fn test_feedback_rejected_is_permanent() {
+        assert!(FeedbackRejected(StatusCode::BAD_REQUEST).is_permanent());
+        assert!(FeedbackRejected(StatusCode::UNAUTHORIZED).is_permanent());
+        assert!(!FeedbackRejected(StatusCode::TOO_MANY_REQUESTS).is_permanent());
+        assert!(!FeedbackRejected(StatusCode::REQUEST_TIMEOUT).is_permanent());
+        assert!(!FeedbackRejected(StatusCode::BAD_GATEWAY).is_permanent());
+    }
+
+    #[test]
+    fn test_from_request_error() {
+        let direct = HttpClientWithUrl::with_options(
+            "https://zed.dev",
+            HttpClientOptions {
+                use_system_proxy: false,
+                ..Default::default()
+            },
+        );
+        let proxied = HttpClientWithUrl::new("https://zed.dev", Some("http://proxy:8080".into()));
+        let error = |kind| http::Error::from(kind);
+
+        assert_eq!(
+            FeedbackHttpError::from_request_error(&error(ErrorKind::BadServerCertificate), &direct),
+            Some(FeedbackHttpError::TlsVerificationFailed)
+        );
+        assert_eq!(
+            FeedbackHttpError::from_request_error(&error(ErrorKind::InvalidCredentials), &proxied),
+            Some(FeedbackHttpError::ProxyAuthenticationRequired)
+        );
+        assert_eq!(
+            FeedbackHttpError::from_request_error(&error(ErrorKind::ConnectionFailed), &proxied),
+            Some(FeedbackHttpError::ProxyUnreachable {
+                proxy: "http://proxy:8080/".into()
+            })
+        );
+        assert_eq!(
+            FeedbackHttpError::from_request_error(&error(ErrorKind::ConnectionFailed), &direct),
+            None
+        );
+        assert_eq!(
+            FeedbackHttpError::from_status(StatusCode::PROXY_AUTHENTICATION_REQUIRED),
+            Some(FeedbackHttpError::ProxyAuthenticationRequired)
+        );
+        assert_eq!(
+            FeedbackHttpError::from_status(StatusCode::BAD_GATEWAY),
+            None
+        );
+    }
+}
@@ -0,0 +1,353 @@
+use std::{mem, sync::Arc, time::Duration};
+
+use anyhow::Result;
+use client::Client;
+use db::{define_connection, query, sqlez_macros::sql};
+use futures::{FutureExt, StreamExt};
+use gpui::{
+    AppContext, Context, Global, Model, ModelContext, Render, Subscription, Task, ViewContext,
+};
+use i18n::{t, t_plural, t_with};
+use settings::Settings;
+use ui::{prelude::*, Button, IconButton, IconPosition, Tooltip};
+use util::ResultExt;
+use workspace::{
+    item::ItemHandle,
+    notifications::{NotificationId, Toast},
+    StatusItemView, Workspace,
+};
+
+use crate::{
+    feedback_editor::deliver_feedback,
+    feedback_history,
+    feedback_http::{self, FeedbackRejected},
+    feedback_settings::FeedbackSettings,
+    submission_manager::FeedbackSubmissionManager,
+};
+
+/// The delay before the first retry of a submission, doubled after each failed retry.
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(30);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60 * 60);
+/// How many times a submission may fail, including its first submission,
+/// before it's given up on.
+const MAX_ATTEMPTS: u32 = 10;
+
+define_connection!(
+    // Current schema shape using pseudo-rust syntax:
+    // pending_feedback(
+    //   id: i64,
+    //   request: String, // The JSON body of the feedback request
+    //   attempts: u32,
+    // )
+    pub static ref FEEDBACK_QUEUE_DB: FeedbackQueueDb<()> =
+        &[sql!(
+            CREATE TABLE pending_feedback(
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                request TEXT NOT NULL,
+                attempts INTEGER NOT NULL
+            ) STRICT;
+        )];
+);
+
+impl FeedbackQueueDb {
+    query! {
+        pub fn pending_feedback() -> Result<Vec<(i64, String, u32)>> {
+            SELECT id, request, attempts FROM pending_feedback ORDER BY id
+        }
+    }
+
+    query! {
+        pub async fn enqueue_feedback(request: String) -> Result<()> {
+            INSERT INTO pending_feedback(request, attempts) VALUES (?, 1)
+        }
+    }
+
+    query! {
+        pub async fn record_failed_attempt(id: i64) -> Result<()> {
+            UPDATE pending_feedback SET attempts = attempts + 1 WHERE id = ?
+        }
+    }
+
+    query! {
+        pub async fn remove_feedback(id: i64) -> Result<()> {
+            DELETE FROM pending_feedback WHERE id = ?
+        }
+    }
+}
+
+struct GlobalFeedbackQueue(Model<FeedbackQueue>);
+
+impl Global for GlobalFeedbackQueue {}
+
+/// Feedback whose submission failed, kept in the database and retried with
+/// exponential backoff until it's delivered, or until it's rejected or has
+/// failed too many times.
+pub struct FeedbackQueue {
+    client: Arc<Client>,
+    pending_count: usize,
+    _retry_task: Option<Task<()>>,
+}
+
+impl FeedbackQueue {
+    pub(crate) fn init(client: Arc<Client>, cx: &mut AppContext) {
+        let queue = cx.new_model(|cx| {
+            let mut queue = Self {
+                client,
+                pending_count: 0,
+                _retry_task: None,
+            };
+            queue.retry(false, cx);
+            queue
+        });
+        cx.set_global(GlobalFeedbackQueue(queue));
+    }
+
+    pub fn global(cx: &AppContext) -> Option<Model<Self>> {
+        cx.try_global::<GlobalFeedbackQueue>()
+            .map(|queue| queue.0.clone())
+    }
+
+    /// Returns how many submissions are waiting to be retried.
+    pub fn pending_count(&self) -> usize {
+        self.pending_count
+    }
+
+    /// Saves the JSON body of a feedback request that failed to submit, so
+    /// that it's retried later.
+    pub(crate) fn enqueue(
+        &mut self,
+        request: String,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        cx.spawn(|this, mut cx| async move {
+            FEEDBACK_QUEUE_DB.enqueue_feedback(request).await?;
+            this.update(&mut cx, |this, cx| this.retry(false, cx))
+        })
+    }
+
+    /// Retries the pending submissions without waiting for their backoff.
+    pub fn retry_now(&mut self, cx: &mut ModelContext<Self>) {
+        self.retry(true, cx);
+    }
+
+    fn retry(&mut self, skip_delay: bool, cx: &mut ModelContext<Self>) {
+        let mut status = self.client.status();
+        self._retry_task = Some(cx.spawn(|this, mut cx| async move {
+            let mut skip_delay = skip_delay;
+            loop {
+                let Some(pending) = FEEDBACK_QUEUE_DB.pending_feedback().log_err() else {
+                    break;
+                };
+                let updated = this.update(&mut cx, |this, cx| {
+                    this.pending_count = pending.len();
+                    cx.notify();
+                });
+                let (Ok(()), Some((id, request, attempts))) = (updated, pending.into_iter().next())
+                else {
+                    break;
+                };
+
+                if !mem::take(&mut skip_delay) {
+                    // Wait out the backoff, unless the connection to the server comes
+                    // back in the meantime.
+                    let mut connected = status.borrow().is_connected();
+                    let reconnected = async {
+                        while let Some(status) = status.next().await {
+                            if status.is_connected() && !connected {
+                                return;
+                            }
+                            connected = status.is_connected();
+                        }
+                        futures::future::pending::<()>().await
+                    };
+                    let timer = cx.background_executor().timer(retry_delay(attempts));
+                    futures::select_biased! {
+                        _ = reconnected.fuse() => {}
+                        _ = timer.fuse() => {}
+                    }
+                }
+
+                // The endpoint is read at each attempt, so that fixing its
+                // settings applies to the pending submissions.
+                let Ok(endpoint) = this.update(&mut cx, |_, cx| {
+                    FeedbackSettings::get_global(cx)
+                        .endpoint(&feedback_http::zed_http_client(cx))
+                        .map(|endpoint| {
+                            let http_client = feedback_http::http_client(&endpoint.url, cx);
+                            (endpoint, http_client)
+                        })
+                }) else {
+                    break;
+                };
+                let delivered = match endpoint {
+                    Ok((endpoint, http_client)) => {
+                        deliver_feedback(&request, &endpoint, &http_client, None).await
+                    }
+                    Err(error) => Err(error),
+                };
+                let result = match delivered {
+                    Ok(ticket_id) => {
+                        feedback_history::record_submission(&request, ticket_id)
+                            .await
+                            .log_err();
+                        FEEDBACK_QUEUE_DB.remove_feedback(id).await
+                    }
+                    Err(error) => {
+                        let rejected = error
+                            .downcast_ref::<FeedbackRejected>()
+                            .map_or(false, FeedbackRejected::is_permanent);
+                        if rejected || attempts + 1 >= MAX_ATTEMPTS {
+                            log::error!("giving up on resubmitting feedback: {error}");
+                            this.update(&mut cx, |_, cx| notify_of_dropped_feedback(&error, cx))
+                                .ok();
+                            FEEDBACK_QUEUE_DB.remove_feedback(id).await
+                        } else {
+                            log::warn!("failed to resubmit feedback: {error}");
+                            FEEDBACK_QUEUE_DB.record_failed_attempt(id).await
+                        }
+                    }
+                };
+                if result.log_err().is_none() {
+                    break;
+                }
+            }
+        }));
+    }
+}
+
+/// Tells the user that a submission won't be retried anymore, in the first
+/// workspace window.
+fn notify_of_dropped_feedback(error: &anyhow::Error, cx: &mut AppContext) {
+    struct DroppedFeedback;
+
+    let Some(workspace) = cx
+        .windows()
+        .into_iter()
+        .find_map(|window| window.downcast::<Workspace>())
+    else {
+        return;
+    };
+    let message = t_with("feedback.dropped", &[("error", &format!("{error:#}"))], cx);
+    workspace
+        .update(cx, |workspace, cx| {
+            workspace.show_toast(
+                Toast::new(
+                    NotificationId::unique::<DroppedFeedback>(),
+                    message.to_string(),
+                ),
+                cx,
+            )
+        })
+        .log_err();
+}
+
+/// Returns how long to wait before retrying a submission that already
+/// failed the given number of times.
+fn retry_delay(attempts: u32) -> Duration {
+    let exponent = attempts.saturating_sub(1).min(16);
+    INITIAL_RETRY_DELAY
+        .saturating_mul(1 << exponent)
+        .min(MAX_RETRY_DELAY)
+}
+
+/// Shows the progress of the feedback submission that's being uploaded, and
+/// how many feedback submissions are waiting to be retried.
+pub struct PendingFeedbackIndicator {
+    queue: Option<Model<FeedbackQueue>>,
+    submission_manager: Option<Model<FeedbackSubmissionManager>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl PendingFeedbackIndicator {
+    pub fn new(cx: &mut ViewContext<Self>) -> Self {
+        let queue = FeedbackQueue::global(cx);
+        let submission_manager = FeedbackSubmissionManager::global(cx);
+        let mut _subscriptions = Vec::new();
+        if let Some(queue) = &queue {
+            _subscriptions.push(cx.observe(queue, |_, _, cx| cx.notify()));
+        }
+        if let Some(submission_manager) = &submission_manager {
+            _subscriptions.push(cx.observe(submission_manager, |_, _, cx| cx.notify()));
+        }
+        Self {
+            queue,
+            submission_manager,
+            _subscriptions,
+        }
+    }
+
+    fn render_upload_progress(&self, cx: &mut ViewContext<Self>) -> Option<impl IntoElement> {
+        let submission_manager = self.submission_manager.clone()?;
+        let progress = submission_manager.read(cx).progress()?;
+        Some(
+            h_flex()
+                .gap_1()
+                .child(
+                    Label::new(t_with(
+                        "feedback.submitting_progress",
+                        &[("percentage", &progress.percentage())],
+                        cx,
+                    ))
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+                )
+                .child(
+                    IconButton::new("cancel-feedback-submission", IconName::XCircle)
+                        .icon_size(IconSize::Small)
+                        .on_click(move |_, cx| {
+                            submission_manager.update(cx, |manager, cx| manager.cancel(cx))
+                        })
+                        .tooltip(|cx| Tooltip::text(t("feedback.cancel_submission", cx), cx)),
+                ),
+        )
+    }
+}
+
+impl Render for PendingFeedbackIndicator {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let upload_progress = self.render_upload_progress(cx);
+        let Some(queue) = self.queue.clone() else {
+            return h_flex().children(upload_progress);
+        };
+        let pending_count = queue.read(cx).pending_count();
+        h_flex()
+            .gap_2()
+            .children(upload_progress)
+            .when(pending_count > 0, |el| {
+                el.child(
+                    Button::new(
+                        "pending-feedback",
+                        t_plural("feedback.pending", pending_count, cx),
+                    )
+                    .label_size(LabelSize::Small)
+                    .icon(IconName::Envelope)
+                    .icon_position(IconPosition::Start)
+                    .icon_size(IconSize::Small)
+                    .icon_color(Color::Warning)
+                    .on_click(move |_, cx| queue.update(cx, |queue, cx| queue.retry_now(cx)))
+                    .tooltip(|cx| Tooltip::text(t("feedback.pending_tooltip", cx), cx)),
+                )
+            })
+    }
+}
+
+impl StatusItemView for PendingFeedbackIndicator {
+    fn set_active_pane_item(&mut self, _: Option<&dyn ItemHandle>, _: &mut ViewContext<Self>) {}
+
+    fn status_item_id() -> Option<&'static str> {
+        Some("feedback")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay() {
+        assert_eq!(retry_delay(1), Duration::from_secs(30));
+        assert_eq!(retry_delay(2), Duration::from_secs(60));
+        assert_eq!(retry_delay(4), Duration::from_secs(240));
+        assert_eq!(retry_delay(100), MAX_RETRY_DELAY);
+    }
+}
@@ -0,0 +1,377 @@
+use std::{collections::BTreeMap, env, path::PathBuf};
+
+use anyhow::{bail, Context as _, Result};
+use client::{ClientSettings, HttpClientSettings, ProxySettings};
+use http::{HttpClientWithUrl, Url};
+use schemars::JsonSchema;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use settings::{parse_json_with_comments, Settings, SettingsSources};
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct FeedbackSettings {
+    pub duplicate_window: u64,
+    pub max_submissions_per_hour: usize,
+    pub endpoint: Option<String>,
+    pub auth_token: Option<String>,
+    pub metadata: BTreeMap<String, String>,
+    pub surveys: bool,
+    pub always_anonymous: bool,
+    pub http_proxy: Option<String>,
+    pub https_proxy: Option<String>,
+    pub ca_certificates: Option<PathBuf>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct FeedbackSettingsContent {
+    /// How long, in seconds, to refuse submitting feedback identical to
+    /// feedback that was just submitted.
+    ///
+    /// Default: 600
+    pub duplicate_window: Option<u64>,
+    /// The number of times feedback can be submitted in an hour.
+    ///
+    /// Default: 5
+    pub max_submissions_per_hour: Option<usize>,
+    /// The URL that feedback is submitted to, for collecting it with your own
+    /// service. When null, feedback is submitted to the Zed server.
+    ///
+    /// Default: null
+    pub endpoint: Option<String>,
+    /// The token sent as a bearer token in the `Authorization` header of
+    /// submissions to `endpoint`.
+    ///
+    /// Default: null
+    pub auth_token: Option<String>,
+    /// Fields added to the `metadata` of every submission, like the team or
+    /// the build of Zed.
+    ///
+    /// Default: {}
+    pub metadata: Option<BTreeMap<String, String>>,
+    /// Whether to occasionally ask a one-question survey about Zed, at most
+    /// once every few months.
+    ///
+    /// Default: true
+    pub surveys: Option<bool>,
+    /// Whether to always send feedback and survey responses anonymously,
+    /// without the email address, metrics ID and installation ID, and to send
+    /// feedback without diagnostics or a crash report.
+    ///
+    /// Default: false
+    pub always_anonymous: Option<bool>,
+    /// The proxy that feedback is sent through to http URLs, and to https
+    /// URLs when `https_proxy` isn't set. When null, feedback is sent through
+    /// the `proxy` that Zed uses.
+    ///
+    /// Default: null
+    pub http_proxy: Option<String>,
+    /// The proxy that feedback is sent through to https URLs.
+    ///
+    /// Default: null
+    pub https_proxy: Option<String>,
+    /// A path to a PEM file with the certificate authorities used to verify
+    /// the server that feedback is sent to. When null, the certificates set
+    /// in `http_client.ca_certificates` are used.
+    ///
+    /// Default: null
+    pub ca_certificates: Option<PathBuf>,
+}
+
+/// Where feedback is submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FeedbackEndpoint {
+    pub url: String,
+    pub auth_token: Option<String>,
+}
+
+/// The proxy and certificates that feedback is sent with, when they're
+/// configured for feedback rather than for all of Zed's requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FeedbackHttpConfig {
+    pub proxy: Option<String>,
+    pub ca_certificates: Option<PathBuf>,
+}
+
+/// The settings that feedback is sent with, read from the user's settings
+/// file rather than the settings store, for when feedback is submitted
+/// without the UI.
+pub(crate) struct SettingsWithoutUi {
+    pub feedback: FeedbackSettings,
+    pub server_url: String,
+    pub proxy: Option<String>,
+    pub http_client: HttpClientSettings,
+}
+
+impl SettingsWithoutUi {
+    /// Merges the user's settings into the default ones, like the settings
+    /// store does.
+    pub(crate) fn parse(user_settings: &str) -> Result<Self> {
+        let default = parse_json_with_comments::<Value>(&settings::default_settings())?;
+        let user = if user_settings.trim().is_empty() {
+            Value::Null
+        } else {
+            parse_json_with_comments::<Value>(user_settings)
+                .context("failed to parse the settings file")?
+        };
+        let mut client = merge_settings::<ClientSettings>(&default, &user)?;
+        if let Ok(server_url) = env::var("ZED_SERVER_URL") {
+            client.server_url = server_url;
+        }
+        Ok(Self {
+            feedback: merge_settings::<FeedbackSettings>(&default, &user)?,
+            server_url: client.server_url,
+            proxy: merge_settings::<ProxySettings>(&default, &user)?.proxy,
+            http_client: merge_settings::<HttpClientSettings>(&default, &user)?,
+        })
+    }
+}
+
+/// Returns the setting with the user's value merged into the default one.
+fn merge_settings<T: Settings + DeserializeOwned>(default: &Value, user: &Value) -> Result<T> {
+    let content = |settings: &Value| -> Result<T::FileContent> {
+        let value = match T::KEY {
+            Some(key) => settings.get(key),
+            None => Some(settings),
+        };
+        match value {
+            Some(value) if !value.is_null() => Ok(serde_json::from_value(value.clone())?),
+            _ => Ok(T::FileContent::default()),
+        }
+    };
+    let default = content(default)?;
+    let user = content(user)?;
+    SettingsSources::<T::FileContent>::json_merge_with([&default, &user].into_iter())
+}
+
+impl FeedbackEndpoint {
+    /// Returns the feedback API of the Zed server.
+    pub(crate) fn zed(http_client: &HttpClientWithUrl) -> Self {
+        Self {
+            url: http_client.build_url("/api/feedback"),
+            auth_token: None,
+        }
+    }
+}
+
+impl FeedbackSettings {
+    /// Returns where feedback is submitted, or an error describing why the
+    /// configured endpoint is invalid.
+    pub(crate) fn endpoint(&self, http_client: &HttpClientWithUrl) -> Result<FeedbackEndpoint> {
+        self.validate()?;
+        Ok(match &self.endpoint {
+            Some(url) => FeedbackEndpoint {
+                url: url.clone(),
+                auth_token: self.auth_token.clone(),
+            },
+            None => FeedbackEndpoint::zed(http_client),
+        })
+    }
+
+    /// Returns the proxy and certificates configured for sending feedback to
+    /// the URL, if any.
+    pub(crate) fn http_config(&self, url: &str) -> Option<FeedbackHttpConfig> {
+        let proxy = if url.starts_with("https://") {
+            self.https_proxy.as_ref().or(self.http_proxy.as_ref())
+        } else {
+            self.http_proxy.as_ref()
+        };
+        if proxy.is_none() && self.ca_certificates.is_none() {
+            return None;
+        }
+        Some(FeedbackHttpConfig {
+            proxy: proxy.cloned(),
+            ca_certificates: self.ca_certificates.clone(),
+        })
+    }
+
+    fn validate(&self) -> Result<()> {
+        match &self.endpoint {
+            Some(endpoint) => {
+                let url = Url::parse(endpoint)
+                    .with_context(|| format!("`feedback.endpoint` isn't a URL: {endpoint:?}"))?;
+                if !matches!(url.scheme(), "http" | "https") {
+                    bail!("`feedback.endpoint` must be an http or https URL: {endpoint:?}");
+                }
+            }
+            // The token is meant for your own service, so it's never sent to Zed's.
+            None if self.auth_token.is_some() => {
+                bail!(
+                    "`feedback.auth_token` is only used with `feedback.endpoint`, which isn't set"
+                )
+            }
+            None => {}
+        }
+        if self
+            .auth_token
+            .as_ref()
+            .is_some_and(|token| token.trim().is_empty())
+        {
+            bail!("`feedback.auth_token` is empty");
+        }
+        if self.metadata.keys().any(|key| key.trim().is_empty()) {
+            bail!("`feedback.metadata` has a field without a name");
+        }
+        for (key, proxy) in [
+            ("http_proxy", &self.http_proxy),
+            ("https_proxy", &self.https_proxy),
+        ] {
+            if let Some(proxy) = proxy {
+                proxy
+                    .parse::<http::Uri>()
+                    .with_context(|| format!("`feedback.{key}` isn't a URL: {proxy:?}"))?;
+            }
+        }
+        if let Some(ca_certificates) = &self.ca_certificates {
+            if !ca_certificates.is_file() {
+                bail!("`feedback.ca_certificates` isn't a file: {ca_certificates:?}");
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Settings for FeedbackSettings {
+    const KEY: Option<&'static str> = Some("feedback");
+
+    type FileContent = FeedbackSettingsContent;
+
+    fn load(
+        sources: SettingsSources<Self::FileContent>,
+        _: &mut gpui::AppContext,
+    ) -> anyhow::Result<Self> {
+        sources.json_merge()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint() {
+        let http_client = HttpClientWithUrl::new("https://zed.dev", None);
+        let settings = |endpoint: Option<&str>, auth_token: Option<&str>| FeedbackSettings {
+            duplicate_window: 600,
+            max_submissions_per_hour: 5,
+            endpoint: endpoint.map(Into::into),
+            auth_token: auth_token.map(Into::into),
+            metadata: BTreeMap::default(),
+            surveys: true,
+            always_anonymous: false,
+            http_proxy: None,
+            https_proxy: None,
+            ca_certificates: None,
+        };
+
+        assert_eq!(
+            settings(None, None).endpoint(&http_client).unwrap(),
+            FeedbackEndpoint {
+                url: "https://zed.dev/api/feedback".into(),
+                auth_token: None,
+            }
+        );
+        assert_eq!(
+            settings(Some("https://feedback.example.com/zed"), Some("secret"))
+                .endpoint(&http_client)
+                .unwrap(),
+            FeedbackEndpoint {
+                url: "https://feedback.example.com/zed".into(),
+                auth_token: Some("secret".into()),
+            }
+        );
+        assert!(settings(Some("feedback.example.com"), None)
+            .endpoint(&http_client)
+            .is_err());
+        assert!(settings(Some("ftp://feedback.example.com"), None)
+            .endpoint(&http_client)
+            .is_err());
+        assert!(settings(None, Some("secret"))
+            .endpoint(&http_client)
+            .is_err());
+        assert!(settings(Some("https://feedback.example.com"), Some(" "))
+            .endpoint(&http_client)
+            .is_err());
+    }
+
+    #[test]
+    fn test_settings_without_ui() {
+        let settings = SettingsWithoutUi::parse(
+            r#"{
+                // Comments are allowed, like in the settings file.
+                "proxy": "http://proxy:8080",
+                "feedback": {
+                    "endpoint": "https://feedback.example.com/zed",
+                    "always_anonymous": true,
+                    "metadata": { "team": "platform" }
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            settings.feedback.endpoint.as_deref(),
+            Some("https://feedback.example.com/zed")
+        );
+        assert!(settings.feedback.always_anonymous);
+        assert_eq!(settings.feedback.metadata["team"], "platform");
+        // What isn't set by the user is the default.
+        assert_eq!(settings.feedback.max_submissions_per_hour, 5);
+        assert_eq!(settings.proxy.as_deref(), Some("http://proxy:8080"));
+
+        let settings = SettingsWithoutUi::parse("").unwrap();
+        assert_eq!(settings.feedback.endpoint, None);
+        assert!(!settings.feedback.always_anonymous);
+        assert_eq!(settings.proxy, None);
+
+        assert!(SettingsWithoutUi::parse("{ \"feedback\": 1 }").is_err());
+    }
+
+    #[test]
+    fn test_http_config() {
+        let settings = |http_proxy: Option<&str>, https_proxy: Option<&str>| FeedbackSettings {
+            duplicate_window: 600,
+            max_submissions_per_hour: 5,
+            endpoint: None,
+            auth_token: None,
+            metadata: BTreeMap::default(),
+            surveys: true,
+            always_anonymous: false,
+            http_proxy: http_proxy.map(Into::into),
+            https_proxy: https_proxy.map(Into::into),
+            ca_certificates: None,
+        };
+        let proxy = |settings: &FeedbackSettings, url| {
+            settings.http_config(url).and_then(|config| config.proxy)
+        };
+
+        assert_eq!(settings(None, None).http_config("https://zed.dev"), None);
+
+        let both = settings(Some("http://proxy:8080"), Some("http://secure-proxy:8443"));
+        assert_eq!(
+            proxy(&both, "http://localhost:3000").as_deref(),
+            Some("http://proxy:8080")
+        );
+        assert_eq!(
+            proxy(&both, "https://zed.dev").as_deref(),
+            Some("http://secure-proxy:8443")
+        );
+
+        let http_only = settings(Some("http://proxy:8080"), None);
+        assert_eq!(
+            proxy(&http_only, "https://zed.dev").as_deref(),
+            Some("http://proxy:8080")
+        );
+
+        let https_only = settings(None, Some("http://secure-proxy:8443"));
+        assert_eq!(https_only.http_config("http://localhost:3000"), None);
+
+        let mut with_certificates = settings(None, None);
+        with_certificates.ca_certificates = Some(PathBuf::from("/etc/ssl/corporate.pem"));
+        assert_eq!(
+            with_certificates.http_config("https://zed.dev"),
+            Some(FeedbackHttpConfig {
+                proxy: None,
+                ca_certificates: Some(PathBuf::from("/etc/ssl/corporate.pem")),
+            })
+        );
+    }
+}
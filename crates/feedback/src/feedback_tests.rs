@@ -0,0 +1,602 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use client::Client;
+use db::sqlez_macros::sql;
+use editor::Editor;
+use gpui::{SemanticVersion, TestAppContext, View, VisualTestContext};
+use http::StatusCode;
+use project::{FakeFs, Project};
+use serde_json::json;
+use settings::SettingsStore;
+use workspace::{item::Item, AppState, Workspace, WorkspaceId};
+
+use crate::{
+    fake_feedback_server::{FakeFeedbackResponse, FakeFeedbackServer},
+    feedback_draft::FEEDBACK_DRAFT_DB,
+    feedback_editor::deliver_feedback,
+    feedback_http::FeedbackHttpError,
+    feedback_queue::FEEDBACK_QUEUE_DB,
+    feedback_settings::FeedbackEndpoint,
+    Dismiss, FeedbackEditor, FeedbackQueue, FeedbackSubmissionManager, GiveFeedback,
+    SubmissionOutcome,
+};
+
+#[test]
+fn test_deliver_feedback() {
+    let server = FakeFeedbackServer::new();
+    let http_client = server.http_client();
+    let endpoint = FeedbackEndpoint {
+        url: "https://feedback.example.com/api/feedback".into(),
+        auth_token: Some("secret".into()),
+    };
+    let request_json = json!({ "feedback_text": "The editor froze" }).to_string();
+    let deliver = |bytes_sent| {
+        smol::block_on(deliver_feedback(
+            &request_json,
+            &endpoint,
+            &http_client,
+            bytes_sent,
+        ))
+    };
+
+    server.respond_with(FakeFeedbackResponse::Submitted {
+        ticket_id: Some("ZED-1".into()),
+    });
+    let bytes_sent = Arc::new(AtomicU64::new(0));
+    assert_eq!(
+        deliver(Some(bytes_sent.clone())).unwrap().as_deref(),
+        Some("ZED-1")
+    );
+    assert_eq!(
+        bytes_sent.load(Ordering::Relaxed),
+        request_json.len() as u64
+    );
+
+    server.respond_with(FakeFeedbackResponse::Status(
+        StatusCode::INTERNAL_SERVER_ERROR,
+    ));
+    let error = deliver(None).unwrap_err();
+    assert!(error.to_string().contains("500"), "{error}");
+
+    server.respond_with(FakeFeedbackResponse::Status(
+        StatusCode::PROXY_AUTHENTICATION_REQUIRED,
+    ));
+    let error = deliver(None).unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<FeedbackHttpError>(),
+        Some(&FeedbackHttpError::ProxyAuthenticationRequired)
+    );
+
+    server.respond_with(FakeFeedbackResponse::Unreachable);
+    assert!(deliver(None).is_err());
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 4);
+    assert_eq!(requests[0].url, endpoint.url);
+    assert_eq!(requests[0].authorization.as_deref(), Some("Bearer secret"));
+    assert_eq!(
+        requests[0].body,
+        json!({ "feedback_text": "The editor froze" })
+    );
+}
+
+#[gpui::test]
+async fn test_character_limit(cx: &mut TestAppContext) {
+    let server = init_test(cx);
+    let (workspace, cx) = open_workspace(cx).await;
+    let feedback_editor = open_feedback_editor(&workspace, cx).await;
+    let text_editor = text_editor(&workspace, cx);
+
+    for (len, can_submit) in [
+        (0, false),
+        (9, false),
+        (10, true),
+        (5000, true),
+        (5001, false),
+    ] {
+        text_editor.update(cx, |editor, cx| editor.set_text("a".repeat(len), cx));
+        let outcome = feedback_editor.update(cx, |feedback_editor, cx| feedback_editor.submit(cx));
+        cx.run_until_parked();
+        assert_eq!(
+            feedback_editor.read_with(cx, |feedback_editor, _| feedback_editor.can_submit()),
+            can_submit,
+            "feedback of {len} characters"
+        );
+        // Valid feedback is only submitted once that's confirmed.
+        assert_eq!(
+            cx.has_pending_prompt(),
+            can_submit,
+            "feedback of {len} characters"
+        );
+        if can_submit {
+            cx.simulate_prompt_answer(1);
+        }
+        assert_eq!(outcome.await.unwrap(), SubmissionOutcome::NotSent);
+    }
+    assert!(server.requests().is_empty());
+}
+
+#[gpui::test]
+async fn test_submit_feedback(cx: &mut TestAppContext) {
+    let server = init_test(cx);
+    let (workspace, cx) = open_workspace(cx).await;
+    let feedback_editor = open_feedback_editor(&workspace, cx).await;
+    text_editor(&workspace, cx).update(cx, |editor, cx| {
+        editor.set_text("Completions don't show up in Rust files.", cx)
+    });
+
+    let outcome = feedback_editor.update(cx, |feedback_editor, cx| feedback_editor.submit(cx));
+    cx.run_until_parked();
+    cx.simulate_prompt_answer(0);
+    assert_eq!(outcome.await.unwrap(), SubmissionOutcome::Submitted);
+    cx.run_until_parked();
+
+    let submissions = server.submissions();
+    assert_eq!(submissions.len(), 1);
+    assert_eq!(
+        submissions[0]["feedback_text"],
+        "Completions don't show up in Rust files."
+    );
+    assert_eq!(server.requests()[0].url, "http://test.example/api/feedback");
+    // The editor is closed once the feedback is submitted.
+    workspace.update(cx, |workspace, cx| {
+        assert!(workspace.item_of_type::<FeedbackEditor>(cx).is_none())
+    });
+}
+
+#[gpui::test]
+async fn test_retry_failed_submission(cx: &mut TestAppContext) {
+    let server = init_test(cx);
+    let (workspace, cx) = open_workspace(cx).await;
+    let feedback_editor = open_feedback_editor(&workspace, cx).await;
+    text_editor(&workspace, cx).update(cx, |editor, cx| {
+        editor.set_text("The terminal doesn't resize with its panel.", cx)
+    });
+
+    server.respond_with(FakeFeedbackResponse::Status(StatusCode::BAD_GATEWAY));
+    let outcome = feedback_editor.update(cx, |feedback_editor, cx| feedback_editor.submit(cx));
+    cx.run_until_parked();
+    cx.simulate_prompt_answer(0);
+    assert_eq!(outcome.await.unwrap(), SubmissionOutcome::Failed);
+    cx.run_until_parked();
+
+    // The editor stays open after a failure, so that the feedback isn't lost.
+    workspace.update(cx, |workspace, cx| {
+        assert!(workspace.item_of_type::<FeedbackEditor>(cx).is_some())
+    });
+    feedback_editor.read_with(cx, |feedback_editor, _| {
+        assert!(feedback_editor.can_submit())
+    });
+
+    // The same request is sent again, without being refused as a duplicate.
+    let outcome = feedback_editor.update(cx, |feedback_editor, cx| {
+        feedback_editor.retry_submission(cx)
+    });
+    assert_eq!(outcome.await, SubmissionOutcome::Submitted);
+    cx.run_until_parked();
+
+    let submissions = server.submissions();
+    assert_eq!(submissions.len(), 2);
+    assert_eq!(submissions[0], submissions[1]);
+    workspace.update(cx, |workspace, cx| {
+        assert!(workspace.item_of_type::<FeedbackEditor>(cx).is_none())
+    });
+}
+
+#[gpui::test]
+async fn test_queue_failed_submission_of_closed_editor(cx: &mut TestAppContext) {
+    let server = init_test(cx);
+    let manager = cx
+        .update(|cx| FeedbackSubmissionManager::global(cx))
+        .unwrap();
+    let (workspace, cx) = open_workspace(cx).await;
+    let feedback_editor = open_feedback_editor(&workspace, cx).await;
+    let feedback_text = "Closing the feedback editor loses failed feedback.";
+    text_editor(&workspace, cx).update(cx, |editor, cx| editor.set_text(feedback_text, cx));
+
+    // The feedback waits for a submission that's stuck, while the editor is closed.
+    server.respond_with(FakeFeedbackResponse::Hang);
+    server.respond_with(FakeFeedbackResponse::Status(StatusCode::BAD_GATEWAY));
+    let stuck_submission = manager.update(cx, |manager, cx| {
+        manager.submit(
+            json!({ "feedback_text": "Stuck" }).to_string(),
+            FeedbackEndpoint {
+                url: "http://test.example/api/feedback".into(),
+                auth_token: None,
+            },
+            cx,
+        )
+    });
+    let outcome = feedback_editor.update(cx, |feedback_editor, cx| feedback_editor.submit(cx));
+    cx.run_until_parked();
+    cx.simulate_prompt_answer(0);
+    cx.run_until_parked();
+    cx.dispatch_action(Dismiss);
+    drop(feedback_editor);
+
+    manager.update(cx, |manager, cx| manager.cancel(cx));
+    assert!(stuck_submission.await.is_err());
+    assert_eq!(outcome.await.unwrap(), SubmissionOutcome::Failed);
+    cx.run_until_parked();
+    let pending_feedback = FEEDBACK_QUEUE_DB.pending_feedback().unwrap();
+    assert!(pending_feedback.iter().any(|(_, request, _)| {
+        serde_json::from_str::<serde_json::Value>(request).unwrap()["feedback_text"]
+            == feedback_text
+    }));
+}
+
+#[gpui::test]
+async fn test_retry_queued_feedback(cx: &mut TestAppContext) {
+    let server = init_test(cx);
+    let queue = cx.update(|cx| FeedbackQueue::global(cx)).unwrap();
+    let request_json = json!({ "feedback_text": "Queued feedback" }).to_string();
+    let attempts = || {
+        FEEDBACK_QUEUE_DB
+            .pending_feedback()
+            .unwrap()
+            .into_iter()
+            .find(|(_, request, _)| *request == request_json)
+            .map(|(_, _, attempts)| attempts)
+    };
+
+    server.respond_with(FakeFeedbackResponse::Unreachable);
+    queue
+        .update(cx, |queue, cx| queue.enqueue(request_json.clone(), cx))
+        .await
+        .unwrap();
+    cx.run_until_parked();
+    assert_eq!(attempts(), Some(1));
+    assert!(server.submissions().is_empty());
+
+    // The first retry fails, which doubles the delay before the next one.
+    cx.executor().advance_clock(Duration::from_secs(30));
+    cx.condition(&queue, |_, _| attempts() == Some(2)).await;
+    assert_eq!(server.submissions().len(), 1);
+
+    cx.executor().advance_clock(Duration::from_secs(30));
+    cx.run_until_parked();
+    assert_eq!(server.submissions().len(), 1);
+
+    cx.executor().advance_clock(Duration::from_secs(30));
+    cx.condition(&queue, |_, _| attempts().is_none()).await;
+    assert_eq!(server.submissions().len(), 2);
+    assert_eq!(
+        server.submissions()[1],
+        json!({ "feedback_text": "Queued feedback" })
+    );
+}
+
+#[gpui::test]
+async fn test_drop_rejected_queued_feedback(cx: &mut TestAppContext) {
+    let server = init_test(cx);
+    let (workspace, cx) = open_workspace(cx).await;
+    let queue = cx.update(|cx| FeedbackQueue::global(cx)).unwrap();
+    let request_json = json!({ "feedback_text": "Rejected feedback" }).to_string();
+    let is_pending = || {
+        FEEDBACK_QUEUE_DB
+            .pending_feedback()
+            .unwrap()
+            .iter()
+            .any(|(_, request, _)| *request == request_json)
+    };
+
+    // Retrying feedback that the server rejects wouldn't deliver it.
+    server.respond_with(FakeFeedbackResponse::Status(StatusCode::BAD_REQUEST));
+    queue
+        .update(cx, |queue, cx| queue.enqueue(request_json.clone(), cx))
+        .await
+        .unwrap();
+    cx.executor().advance_clock(Duration::from_secs(30));
+    cx.condition(&queue, |_, _| !is_pending()).await;
+    assert_eq!(server.submissions().len(), 1);
+    workspace.update(cx, |workspace, _| {
+        assert_eq!(workspace.notification_ids().len(), 1)
+    });
+}
+
+#[gpui::test]
+async fn test_drop_queued_feedback_after_too_many_attempts(cx: &mut TestAppContext) {
+    let server = init_test(cx);
+    let (workspace, cx) = open_workspace(cx).await;
+    let queue = cx.update(|cx| FeedbackQueue::global(cx)).unwrap();
+    let request_json = json!({ "feedback_text": "Undeliverable feedback" }).to_string();
+    let is_pending = || {
+        FEEDBACK_QUEUE_DB
+            .pending_feedback()
+            .unwrap()
+            .iter()
+            .any(|(_, request, _)| *request == request_json)
+    };
+
+    // Errors that may be temporary are retried, until the submission has
+    // failed 10 times, counting its first submission.
+    for _ in 0..9 {
+        server.respond_with(FakeFeedbackResponse::Status(StatusCode::TOO_MANY_REQUESTS));
+    }
+    queue
+        .update(cx, |queue, cx| queue.enqueue(request_json.clone(), cx))
+        .await
+        .unwrap();
+    for _ in 0..8 {
+        cx.executor().advance_clock(Duration::from_secs(60 * 60));
+        cx.run_until_parked();
+        assert!(is_pending());
+    }
+    workspace.update(cx, |workspace, _| {
+        assert!(workspace.notification_ids().is_empty())
+    });
+
+    cx.executor().advance_clock(Duration::from_secs(60 * 60));
+    cx.condition(&queue, |_, _| !is_pending()).await;
+    assert_eq!(server.submissions().len(), 9);
+    workspace.update(cx, |workspace, _| {
+        assert_eq!(workspace.notification_ids().len(), 1)
+    });
+}
+
+#[gpui::test]
+async fn test_submissions_are_uploaded_one_at_a_time(cx: &mut TestAppContext) {
+    let server = init_test(cx);
+    let manager = cx
+        .update(|cx| FeedbackSubmissionManager::global(cx))
+        .unwrap();
+    let endpoint = FeedbackEndpoint {
+        url: "https://feedback.example.com/api/feedback".into(),
+        auth_token: None,
+    };
+    let submit = |text: &str, cx: &mut TestAppContext| {
+        let request_json = json!({ "feedback_text": text }).to_string();
+        manager.update(cx, |manager, cx| {
+            manager.submit(request_json, endpoint.clone(), cx)
+        })
+    };
+
+    let first = submit("First", cx);
+    let second = submit("Second", cx);
+    let third = submit("Third", cx);
+    // Cancelling the first submission starts uploading the next one.
+    manager.update(cx, |manager, cx| manager.cancel(cx));
+    assert!(first.await.is_err());
+    assert!(second.await.unwrap().is_ok());
+    assert!(third.await.unwrap().is_ok());
+    assert_eq!(
+        server.submissions(),
+        [
+            json!({ "feedback_text": "Second" }),
+            json!({ "feedback_text": "Third" })
+        ]
+    );
+    manager.read_with(cx, |manager, _| assert_eq!(manager.progress(), None));
+}
+
+#[gpui::test]
+async fn test_open_and_close_feedback_editor(cx: &mut TestAppContext) {
+    init_test(cx);
+    let (workspace, cx) = open_workspace(cx).await;
+    let feedback_editor = open_feedback_editor(&workspace, cx).await;
+
+    // Giving feedback again activates the editor that's already open.
+    cx.dispatch_action(GiveFeedback {
+        prefill: "The tab bar flickers.".into(),
+        ..Default::default()
+    });
+    workspace.update(cx, |workspace, cx| {
+        assert_eq!(workspace.items_of_type::<FeedbackEditor>(cx).count(), 1);
+        assert_eq!(
+            workspace.active_item_as::<FeedbackEditor>(cx),
+            Some(feedback_editor.clone())
+        );
+    });
+    assert_eq!(
+        text_editor(&workspace, cx).read_with(cx, |editor, cx| editor.text(cx)),
+        "The tab bar flickers."
+    );
+
+    // Closing the editor with feedback that wasn't saved or submitted asks whether to save it.
+    assert!(feedback_editor.read_with(cx, |feedback_editor, cx| feedback_editor.is_dirty(cx)));
+    cx.dispatch_action(Dismiss);
+    cx.run_until_parked();
+    cx.simulate_prompt_answer(2);
+    cx.run_until_parked();
+    workspace.update(cx, |workspace, cx| {
+        assert!(workspace.item_of_type::<FeedbackEditor>(cx).is_some())
+    });
+
+    cx.dispatch_action(Dismiss);
+    cx.run_until_parked();
+    cx.simulate_prompt_answer(0);
+    cx.run_until_parked();
+    workspace.update(cx, |workspace, cx| {
+        assert!(workspace.item_of_type::<FeedbackEditor>(cx).is_none())
+    });
+}
+
+#[gpui::test]
+async fn test_give_feedback_about_an_error(cx: &mut TestAppContext) {
+    let server = init_test(cx);
+    let (workspace, cx) = open_workspace(cx).await;
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            store
+                .set_user_settings(
+                    r#"{ "feedback": { "metadata": { "team": "platform", "source": "settings" } } }"#,
+                    cx,
+                )
+                .unwrap();
+        })
+    });
+    let feedback_editor = open_feedback_editor(&workspace, cx).await;
+    let text_editor = text_editor(&workspace, cx);
+    text_editor.update(cx, |editor, cx| {
+        editor.set_text("Rust files don't open.", cx)
+    });
+
+    // The description is added after the feedback written so far, and selected.
+    let prefill = "Zed failed to download the rust-analyzer language server.";
+    cx.dispatch_action(GiveFeedback {
+        prefill: prefill.into(),
+        context: [
+            ("source".to_string(), "language_server".to_string()),
+            ("error".to_string(), "connection refused".to_string()),
+            (" ".to_string(), "ignored".to_string()),
+        ]
+        .into_iter()
+        .collect(),
+    });
+    cx.run_until_parked();
+    let start = "Rust files don't open.\n\n".len();
+    text_editor.update(cx, |editor, cx| {
+        assert_eq!(
+            editor.text(cx),
+            format!("Rust files don't open.\n\n{prefill}")
+        );
+        assert_eq!(
+            editor.selections.ranges::<usize>(cx),
+            [start..start + prefill.len()]
+        );
+    });
+
+    // The details of the error are sent in the metadata, after the fields
+    // configured in the settings, which take precedence.
+    let outcome = feedback_editor.update(cx, |feedback_editor, cx| feedback_editor.submit(cx));
+    cx.run_until_parked();
+    cx.simulate_prompt_answer(0);
+    assert_eq!(outcome.await.unwrap(), SubmissionOutcome::Submitted);
+    assert_eq!(
+        server.submissions()[0]["metadata"],
+        json!({
+            "error": "connection refused",
+            "source": "settings",
+            "team": "platform"
+        })
+    );
+}
+
+#[gpui::test]
+async fn test_feedback_draft(cx: &mut TestAppContext) {
+    init_test(cx);
+    let (workspace, cx) = open_workspace(cx).await;
+    let workspace_id = FEEDBACK_DRAFT_DB
+        .write(|conn| {
+            conn.select_row::<WorkspaceId>(sql!(
+                INSERT INTO workspaces DEFAULT VALUES RETURNING workspace_id
+            ))
+            .unwrap()()
+            .unwrap()
+            .unwrap()
+        })
+        .await;
+    workspace.update(cx, |workspace, _| workspace.set_database_id(workspace_id));
+    let set_text = |text: &str, cx: &mut VisualTestContext| {
+        text_editor(&workspace, cx).update(cx, |editor, cx| editor.set_text(text, cx))
+    };
+    let draft = || FEEDBACK_DRAFT_DB.draft(workspace_id).unwrap();
+
+    // Saving the feedback when closing the editor keeps its draft for the workspace.
+    open_feedback_editor(&workspace, cx).await;
+    set_text("The tab bar flickers.", cx);
+    cx.dispatch_action(Dismiss);
+    cx.run_until_parked();
+    cx.simulate_prompt_answer(0);
+    cx.run_until_parked();
+    assert_eq!(draft().as_deref(), Some("The tab bar flickers."));
+
+    // Closing it without saving its changes keeps the draft that was saved.
+    open_feedback_editor(&workspace, cx).await;
+    assert_eq!(
+        text_editor(&workspace, cx).read_with(cx, |editor, cx| editor.text(cx)),
+        "The tab bar flickers."
+    );
+    set_text("The tab bar flickers when scrolling.", cx);
+    cx.dispatch_action(Dismiss);
+    cx.run_until_parked();
+    cx.simulate_prompt_answer(1);
+    cx.run_until_parked();
+    assert_eq!(draft().as_deref(), Some("The tab bar flickers."));
+
+    // Edits are saved when the editor is closed before the draft is saved
+    // for them, so clearing the feedback deletes its draft.
+    open_feedback_editor(&workspace, cx).await;
+    set_text("", cx);
+    cx.dispatch_action(Dismiss);
+    cx.run_until_parked();
+    workspace.update(cx, |workspace, cx| {
+        assert!(workspace.item_of_type::<FeedbackEditor>(cx).is_none())
+    });
+    assert_eq!(draft(), None);
+}
+
+#[gpui::test]
+async fn test_escape_discards_feedback(cx: &mut TestAppContext) {
+    init_test(cx);
+    let (workspace, cx) = open_workspace(cx).await;
+    open_feedback_editor(&workspace, cx).await;
+    text_editor(&workspace, cx).update(cx, |editor, cx| {
+        editor.set_text("Escape closes the feedback editor right away.", cx)
+    });
+
+    // Escape asks whether to discard the feedback, like `feedback::Cancel`.
+    cx.dispatch_action(editor::actions::Cancel);
+    cx.run_until_parked();
+    assert!(cx.has_pending_prompt());
+    cx.simulate_prompt_answer(0);
+    cx.run_until_parked();
+    assert!(!cx.has_pending_prompt());
+    workspace.update(cx, |workspace, cx| {
+        assert!(workspace.item_of_type::<FeedbackEditor>(cx).is_none())
+    });
+}
+
+/// Initializes what feedback is given with, sending it to a fake server.
+fn init_test(cx: &mut TestAppContext) -> FakeFeedbackServer {
+    // The database and the diagnostics are read off the main thread.
+    cx.executor().allow_parking();
+    let server = FakeFeedbackServer::new();
+    cx.update(|cx| {
+        let app_state = AppState::test(cx);
+        release_channel::init(SemanticVersion::default(), cx);
+        client::init_settings(cx);
+        Client::set_global(app_state.client.clone(), cx);
+        language::init(cx);
+        editor::init(cx);
+        workspace::init_settings(cx);
+        Project::init_settings(cx);
+        crate::init(app_state.client.clone(), cx);
+        server.install(cx);
+    });
+    server
+}
+
+async fn open_workspace(cx: &mut TestAppContext) -> (View<Workspace>, &mut VisualTestContext) {
+    let fs = FakeFs::new(cx.executor());
+    let project = Project::test(fs, [], cx).await;
+    cx.add_window_view(|cx| Workspace::test_new(project, cx))
+}
+
+async fn open_feedback_editor(
+    workspace: &View<Workspace>,
+    cx: &mut VisualTestContext,
+) -> View<FeedbackEditor> {
+    workspace
+        .update(cx, |workspace, cx| {
+            FeedbackEditor::open(workspace, None, cx)
+        })
+        .await
+        .expect("the feedback editor should open in a local project")
+}
+
+/// Returns the editor that the feedback is written in.
+fn text_editor(workspace: &View<Workspace>, cx: &mut VisualTestContext) -> View<Editor> {
+    workspace.update(cx, |workspace, cx| {
+        workspace
+            .active_item(cx)
+            .and_then(|item| item.act_as::<Editor>(cx))
+            .expect("the feedback editor should be active")
+    })
+}
@@ -0,0 +1,159 @@
+use std::time::Duration;
+
+use gpui::{
+    percentage, Animation, AnimationExt, EventEmitter, ParentElement, Render, Subscription,
+    Transformation, View, ViewContext,
+};
+use i18n::{t, t_with};
+use ui::prelude::*;
+use ui::{Button, ButtonStyle, Tooltip};
+use workspace::{item::ItemHandle, ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView};
+
+use crate::{feedback_editor::FeedbackEditor, DiscardDraft, TogglePreview};
+
+/// Shows the character count of the active feedback editor, with buttons to
+/// preview and submit the feedback.
+pub struct FeedbackToolbar {
+    feedback_editor: Option<View<FeedbackEditor>>,
+    _subscription: Option<Subscription>,
+}
+
+impl Render for FeedbackToolbar {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let Some(feedback_editor) = self.feedback_editor.clone() else {
+            return div();
+        };
+        let editor = feedback_editor.read(cx);
+        let can_submit = editor.can_submit();
+        let awaiting_submission = editor.awaiting_submission();
+        let has_draft = editor.has_draft(cx);
+        let preview_button_text = if editor.showing_preview() {
+            t("feedback.edit", cx)
+        } else {
+            t("feedback.preview", cx)
+        };
+        let submit_button_text = match editor.upload_percentage(cx) {
+            Some(percentage) => t_with(
+                "feedback.submitting_progress",
+                &[("percentage", &percentage)],
+                cx,
+            ),
+            None if awaiting_submission => t("feedback.submitting", cx),
+            None => t("feedback.submit", cx),
+        };
+
+        div().child(
+            h_flex()
+                .gap_2()
+                .child(editor.character_count_label(cx))
+                .when(has_draft && !awaiting_submission, |el| {
+                    el.child(
+                        Button::new("discard_feedback_draft", t("feedback.discard_draft", cx))
+                            .style(ButtonStyle::Subtle)
+                            .on_click({
+                                let feedback_editor = feedback_editor.clone();
+                                move |_, cx| {
+                                    feedback_editor.update(cx, |editor, cx| {
+                                        editor.discard_draft(&DiscardDraft, cx)
+                                    })
+                                }
+                            }),
+                    )
+                })
+                .child(
+                    Button::new("preview_feedback", preview_button_text)
+                        .style(ButtonStyle::Subtle)
+                        .on_click({
+                            let feedback_editor = feedback_editor.clone();
+                            move |_, cx| {
+                                feedback_editor.update(cx, |editor, cx| {
+                                    editor.toggle_preview(&TogglePreview, cx)
+                                })
+                            }
+                        }),
+                )
+                .when(awaiting_submission, |el| {
+                    el.child(
+                        Icon::new(IconName::ArrowCircle)
+                            .size(IconSize::Small)
+                            .color(Color::Muted)
+                            .with_animation(
+                                "feedback-submitting",
+                                Animation::new(Duration::from_secs(2)).repeat(),
+                                |icon, delta| {
+                                    icon.transform(Transformation::rotate(percentage(delta)))
+                                },
+                            ),
+                    )
+                    .child(
+                        Button::new("cancel_feedback_submission", t("common.cancel", cx))
+                            .style(ButtonStyle::Subtle)
+                            .on_click({
+                                let feedback_editor = feedback_editor.clone();
+                                move |_, cx| {
+                                    feedback_editor
+                                        .update(cx, |editor, cx| editor.cancel_submission(cx))
+                                }
+                            }),
+                    )
+                })
+                .child(
+                    div()
+                        .rounded_md()
+                        .when(!can_submit, |el| {
+                            el.bg(cx.theme().colors().feedback_submit_disabled_background)
+                        })
+                        .child(
+                            Button::new("submit_feedback", submit_button_text)
+                                .color(Color::Accent)
+                                // A subtle button shows the themed background while it's disabled.
+                                .style(if can_submit {
+                                    ButtonStyle::Filled
+                                } else {
+                                    ButtonStyle::Subtle
+                                })
+                                .on_click(move |_, cx| {
+                                    feedback_editor.update(cx, |editor, cx| {
+                                        editor.submit(cx).detach_and_log_err(cx)
+                                    });
+                                })
+                                .tooltip(move |cx| {
+                                    Tooltip::text(t("feedback.submit_tooltip", cx), cx)
+                                })
+                                .when(!can_submit, |this| this.disabled(true)),
+                        ),
+                ),
+        )
+    }
+}
+
+impl EventEmitter<ToolbarItemEvent> for FeedbackToolbar {}
+
+impl ToolbarItemView for FeedbackToolbar {
+    fn set_active_pane_item(
+        &mut self,
+        active_pane_item: Option<&dyn ItemHandle>,
+        cx: &mut ViewContext<Self>,
+    ) -> ToolbarItemLocation {
+        let feedback_editor = active_pane_item.and_then(|item| item.downcast::<FeedbackEditor>());
+        self._subscription = feedback_editor
+            .as_ref()
+            .map(|editor| cx.observe(editor, |_, _, cx| cx.notify()));
+        self.feedback_editor = feedback_editor;
+        cx.notify();
+        if self.feedback_editor.is_some() {
+            ToolbarItemLocation::PrimaryRight
+        } else {
+            ToolbarItemLocation::Hidden
+        }
+    }
+}
+
+impl FeedbackToolbar {
+    pub fn new() -> Self {
+        Self {
+            feedback_editor: None,
+            _subscription: None,
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use gpui::{AppContext, Global, Task, WindowContext};
+use serde_derive::Serialize;
+
+/// How Zed performed while it was sampled for a few seconds, which is sent
+/// with feedback about its performance.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct PerformanceSnapshot {
+    /// How long Zed was sampled for, in milliseconds.
+    pub sample_duration_ms: u64,
+    /// The number of frames drawn each second while sampling.
+    pub frames_per_second: f64,
+    /// The time spent on each of the window's recent frames.
+    pub frame_time: DurationPercentiles,
+    /// The time spent laying out each of the window's recent frames.
+    pub layout_time: DurationPercentiles,
+    /// The time spent painting each of the window's recent frames.
+    pub paint_time: DurationPercentiles,
+    /// How long the main thread recently stalled for, in milliseconds, longest first.
+    pub main_thread_stalls_ms: Vec<f64>,
+    /// The memory used by Zed's process at each sample, in bytes.
+    pub memory_samples: Vec<u64>,
+}
+
+/// The 50th, 95th and 99th percentiles of a duration, in milliseconds.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+pub struct DurationPercentiles {
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+}
+
+/// Samples how Zed performs in a window, returning the snapshot once it's done.
+pub type PerformanceSampler = fn(&mut WindowContext) -> Task<PerformanceSnapshot>;
+
+struct GlobalPerformanceSampler(PerformanceSampler);
+
+impl Global for GlobalPerformanceSampler {}
+
+/// Sets how performance snapshots are captured for performance feedback.
+/// Without a sampler, performance feedback is sent without a snapshot.
+pub fn set_performance_sampler(sampler: PerformanceSampler, cx: &mut AppContext) {
+    cx.set_global(GlobalPerformanceSampler(sampler));
+}
+
+/// Starts capturing a performance snapshot of the window, if there's a sampler.
+pub(crate) fn capture(cx: &mut WindowContext) -> Option<Task<PerformanceSnapshot>> {
+    let sampler = cx.try_global::<GlobalPerformanceSampler>()?.0;
+    Some(sampler(cx))
+}
@@ -0,0 +1,188 @@
+use std::{
+    collections::{hash_map::DefaultHasher, VecDeque},
+    hash::{Hash, Hasher},
+    time::{Duration, Instant},
+};
+
+use gpui::{AppContext, Global, SharedString};
+use i18n::{t, t_plural};
+use settings::Settings;
+
+use crate::feedback_settings::FeedbackSettings;
+
+const RATE_LIMIT_PERIOD: Duration = Duration::from_secs(60 * 60);
+
+/// Why feedback can't be submitted right now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SubmissionRefusal {
+    /// Identical feedback was submitted within the duplicate window.
+    Duplicate,
+    /// Too much feedback was submitted in the last hour. Submitting is
+    /// possible again after the given delay.
+    RateLimited { retry_after: Duration },
+}
+
+impl SubmissionRefusal {
+    pub(crate) fn message(self, cx: &AppContext) -> SharedString {
+        match self {
+            Self::Duplicate => t("feedback.duplicate", cx),
+            Self::RateLimited { retry_after } => {
+                let minutes = retry_after.as_secs().div_ceil(60).max(1);
+                t_plural("feedback.rate_limited", minutes as usize, cx)
+            }
+        }
+    }
+}
+
+/// The feedback submitted recently, used to refuse duplicate submissions, like
+/// from a double-click, and to throttle how often feedback is submitted.
+#[derive(Default)]
+pub(crate) struct SubmissionLimiter {
+    /// The times feedback was submitted, with the hash of its text, oldest first.
+    submissions: VecDeque<(Instant, u64)>,
+}
+
+impl Global for SubmissionLimiter {}
+
+impl SubmissionLimiter {
+    /// Returns the hash that identifies feedback by its text.
+    pub(crate) fn hash(feedback_text: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        feedback_text.trim().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Checks whether the feedback can be submitted according to the settings.
+    pub(crate) fn check(feedback_hash: u64, cx: &AppContext) -> Result<(), SubmissionRefusal> {
+        let settings = FeedbackSettings::get_global(cx);
+        match cx.try_global::<Self>() {
+            Some(this) => this.check_at(feedback_hash, Instant::now(), settings),
+            None => Ok(()),
+        }
+    }
+
+    /// Records a submission of the feedback, if it can be submitted.
+    pub(crate) fn record(feedback_hash: u64, cx: &mut AppContext) -> Result<(), SubmissionRefusal> {
+        let settings = FeedbackSettings::get_global(cx).clone();
+        let this = cx.default_global::<Self>();
+        let now = Instant::now();
+        this.check_at(feedback_hash, now, &settings)?;
+        this.record_at(feedback_hash, now, &settings);
+        Ok(())
+    }
+
+    /// Forgets the latest submission of the feedback, after it failed, so
+    /// that submitting it again isn't refused as a duplicate.
+    pub(crate) fn forget(feedback_hash: u64, cx: &mut AppContext) {
+        let this = cx.default_global::<Self>();
+        if let Some(ix) = this
+            .submissions
+            .iter()
+            .rposition(|(_, hash)| *hash == feedback_hash)
+        {
+            this.submissions.remove(ix);
+        }
+    }
+
+    fn check_at(
+        &self,
+        feedback_hash: u64,
+        now: Instant,
+        settings: &FeedbackSettings,
+    ) -> Result<(), SubmissionRefusal> {
+        let duplicate_window = Duration::from_secs(settings.duplicate_window);
+        let max_submissions_per_hour = settings.max_submissions_per_hour;
+        let is_duplicate = self.submissions.iter().any(|(submitted_at, hash)| {
+            *hash == feedback_hash && now.duration_since(*submitted_at) < duplicate_window
+        });
+        if is_duplicate {
+            return Err(SubmissionRefusal::Duplicate);
+        }
+
+        let recent = self
+            .submissions
+            .iter()
+            .filter(|(submitted_at, _)| now.duration_since(*submitted_at) < RATE_LIMIT_PERIOD)
+            .collect::<Vec<_>>();
+        if recent.len() >= max_submissions_per_hour {
+            // Submitting is possible again once enough of the recent
+            // submissions are older than an hour.
+            let retry_after = recent.get(recent.len() - max_submissions_per_hour).map_or(
+                RATE_LIMIT_PERIOD,
+                |(submitted_at, _)| {
+                    RATE_LIMIT_PERIOD.saturating_sub(now.duration_since(*submitted_at))
+                },
+            );
+            return Err(SubmissionRefusal::RateLimited { retry_after });
+        }
+
+        Ok(())
+    }
+
+    fn record_at(&mut self, feedback_hash: u64, now: Instant, settings: &FeedbackSettings) {
+        self.submissions.push_back((now, feedback_hash));
+
+        // Forget the submissions that are too old to be refused or throttled.
+        let max_age = RATE_LIMIT_PERIOD.max(Duration::from_secs(settings.duplicate_window));
+        while let Some((submitted_at, _)) = self.submissions.front() {
+            if now.duration_since(*submitted_at) < max_age {
+                break;
+            }
+            self.submissions.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_submission_limits() {
+        let settings = FeedbackSettings {
+            duplicate_window: 600,
+            max_submissions_per_hour: 2,
+            endpoint: None,
+            auth_token: None,
+            metadata: Default::default(),
+            surveys: true,
+            always_anonymous: false,
+            http_proxy: None,
+            https_proxy: None,
+            ca_certificates: None,
+        };
+        let minutes = |count| Duration::from_secs(count * 60);
+        let start = Instant::now();
+        let mut limiter = SubmissionLimiter::default();
+        let first = SubmissionLimiter::hash("The first feedback");
+        let second = SubmissionLimiter::hash("The second feedback");
+        let third = SubmissionLimiter::hash("The third feedback");
+
+        assert_eq!(limiter.check_at(first, start, &settings), Ok(()));
+        limiter.record_at(first, start, &settings);
+        assert_eq!(
+            limiter.check_at(
+                SubmissionLimiter::hash("  The first feedback\n"),
+                start,
+                &settings
+            ),
+            Err(SubmissionRefusal::Duplicate)
+        );
+        assert_eq!(
+            limiter.check_at(first, start + minutes(10), &settings),
+            Ok(())
+        );
+
+        limiter.record_at(second, start + minutes(20), &settings);
+        assert_eq!(
+            limiter.check_at(third, start + minutes(30), &settings),
+            Err(SubmissionRefusal::RateLimited {
+                retry_after: minutes(30)
+            })
+        );
+        assert_eq!(
+            limiter.check_at(third, start + minutes(60), &settings),
+            Ok(())
+        );
+    }
+}
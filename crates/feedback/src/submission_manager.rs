@@ -0,0 +1,243 @@
+use std::{
+    collections::VecDeque,
+    io,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::{channel::oneshot, io::Cursor, AsyncRead, FutureExt};
+use gpui::{AppContext, Context as _, Global, Model, ModelContext, Task};
+use util::{post_inc, ResultExt};
+
+use crate::{
+    feedback_editor::deliver_feedback, feedback_history, feedback_http,
+    feedback_settings::FeedbackEndpoint,
+};
+
+/// How often the progress of an upload is shown again while it's running.
+const PROGRESS_UPDATE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How much of a submission's request has been uploaded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UploadProgress {
+    pub bytes_sent: u64,
+    pub total_bytes: u64,
+}
+
+impl UploadProgress {
+    /// Returns the percentage of the request that has been uploaded, from 0 to 100.
+    pub fn percentage(&self) -> u64 {
+        if self.total_bytes == 0 {
+            return 100;
+        }
+        self.bytes_sent.min(self.total_bytes) * 100 / self.total_bytes
+    }
+}
+
+/// Reads the body of a request, counting the bytes that have been read so
+/// far to show the progress of its upload.
+pub(crate) struct ProgressReader {
+    body: Cursor<Vec<u8>>,
+    bytes_read: Arc<AtomicU64>,
+}
+
+impl ProgressReader {
+    pub(crate) fn new(body: Vec<u8>, bytes_read: Arc<AtomicU64>) -> Self {
+        Self {
+            body: Cursor::new(body),
+            bytes_read,
+        }
+    }
+}
+
+impl AsyncRead for ProgressReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let poll = Pin::new(&mut self.body).poll_read(cx, buf);
+        if let Poll::Ready(Ok(read)) = poll {
+            self.bytes_read.fetch_add(read as u64, Ordering::Relaxed);
+        }
+        poll
+    }
+}
+
+/// The result of a submission's upload: the ticket ID that the server
+/// returned, or an error. Cancelled submissions have no result.
+pub(crate) type SubmissionResult = oneshot::Receiver<anyhow::Result<Option<String>>>;
+
+struct QueuedSubmission {
+    request_json: String,
+    endpoint: FeedbackEndpoint,
+    result_tx: oneshot::Sender<anyhow::Result<Option<String>>>,
+}
+
+struct InFlightSubmission {
+    id: usize,
+    bytes_sent: Arc<AtomicU64>,
+    total_bytes: u64,
+    _task: Task<()>,
+}
+
+struct GlobalFeedbackSubmissionManager(Model<FeedbackSubmissionManager>);
+
+impl Global for GlobalFeedbackSubmissionManager {}
+
+/// Uploads feedback submissions in the background, so that they keep going
+/// when the feedback editor is closed and can be cancelled while they run.
+/// They're uploaded one at a time, in the order they were submitted.
+pub struct FeedbackSubmissionManager {
+    submission: Option<InFlightSubmission>,
+    queued_submissions: VecDeque<QueuedSubmission>,
+    next_submission_id: usize,
+}
+
+impl FeedbackSubmissionManager {
+    pub(crate) fn init(cx: &mut AppContext) {
+        let manager = cx.new_model(|_| Self {
+            submission: None,
+            queued_submissions: VecDeque::new(),
+            next_submission_id: 0,
+        });
+        cx.set_global(GlobalFeedbackSubmissionManager(manager));
+    }
+
+    pub fn global(cx: &AppContext) -> Option<Model<Self>> {
+        cx.try_global::<GlobalFeedbackSubmissionManager>()
+            .map(|manager| manager.0.clone())
+    }
+
+    /// Returns the progress of the submission that's being uploaded, if any.
+    pub fn progress(&self) -> Option<UploadProgress> {
+        self.submission.as_ref().map(|submission| UploadProgress {
+            bytes_sent: submission.bytes_sent.load(Ordering::Relaxed),
+            total_bytes: submission.total_bytes,
+        })
+    }
+
+    /// Uploads the JSON body of a feedback request once the submissions before
+    /// it have ended, and adds it to the history once it's submitted.
+    pub(crate) fn submit(
+        &mut self,
+        request_json: String,
+        endpoint: FeedbackEndpoint,
+        cx: &mut ModelContext<Self>,
+    ) -> SubmissionResult {
+        let (result_tx, result_rx) = oneshot::channel();
+        self.queued_submissions.push_back(QueuedSubmission {
+            request_json,
+            endpoint,
+            result_tx,
+        });
+        if self.submission.is_none() {
+            self.start_next_submission(cx);
+        }
+        result_rx
+    }
+
+    fn start_next_submission(&mut self, cx: &mut ModelContext<Self>) {
+        let Some(QueuedSubmission {
+            request_json,
+            endpoint,
+            result_tx,
+        }) = self.queued_submissions.pop_front()
+        else {
+            return;
+        };
+        let id = post_inc(&mut self.next_submission_id);
+        let bytes_sent = Arc::new(AtomicU64::new(0));
+        let total_bytes = request_json.len() as u64;
+        let http_client = feedback_http::http_client(&endpoint.url, cx);
+        let upload = cx.background_executor().spawn({
+            let bytes_sent = bytes_sent.clone();
+            async move {
+                let result =
+                    deliver_feedback(&request_json, &endpoint, &http_client, Some(bytes_sent))
+                        .await;
+                if let Ok(ticket_id) = &result {
+                    feedback_history::record_submission(&request_json, ticket_id.clone())
+                        .await
+                        .log_err();
+                }
+                result
+            }
+        });
+
+        let task = cx.spawn(|this, mut cx| async move {
+            let mut upload = upload.fuse();
+            let result = loop {
+                let timer = cx.background_executor().timer(PROGRESS_UPDATE_INTERVAL);
+                futures::select_biased! {
+                    result = upload => break result,
+                    _ = timer.fuse() => {}
+                }
+                if this.update(&mut cx, |_, cx| cx.notify()).is_err() {
+                    return;
+                }
+            };
+            result_tx.send(result).ok();
+            this.update(&mut cx, |this, cx| {
+                if this.submission.as_ref().map(|submission| submission.id) == Some(id) {
+                    this.submission = None;
+                    this.start_next_submission(cx);
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+
+        self.submission = Some(InFlightSubmission {
+            id,
+            bytes_sent,
+            total_bytes,
+            _task: task,
+        });
+        cx.notify();
+    }
+
+    /// Cancels the submission that's being uploaded, if any, and starts
+    /// uploading the next one.
+    pub fn cancel(&mut self, cx: &mut ModelContext<Self>) {
+        if self.submission.take().is_some() {
+            self.start_next_submission(cx);
+            cx.notify();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::AsyncReadExt;
+
+    #[test]
+    fn test_upload_progress() {
+        let progress = |bytes_sent, total_bytes| {
+            UploadProgress {
+                bytes_sent,
+                total_bytes,
+            }
+            .percentage()
+        };
+        assert_eq!(progress(0, 200), 0);
+        assert_eq!(progress(50, 200), 25);
+        assert_eq!(progress(300, 200), 100);
+        assert_eq!(progress(0, 0), 100);
+
+        let bytes_read = Arc::new(AtomicU64::new(0));
+        let mut reader = ProgressReader::new(vec![0; 10], bytes_read.clone());
+        let mut buf = [0; 4];
+        smol::block_on(reader.read(&mut buf)).unwrap();
+        assert_eq!(bytes_read.load(Ordering::Relaxed), 4);
+        let mut rest = Vec::new();
+        smol::block_on(reader.read_to_end(&mut rest)).unwrap();
+        assert_eq!(bytes_read.load(Ordering::Relaxed), 10);
+    }
+}
@@ -0,0 +1,334 @@
+//! Occasional one-question surveys, which ask how likely Zed is to be
+//! recommended on a scale from 0 to 10, with an optional comment.
+
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use client::{Client, TelemetrySettings};
+use db::kvp::KEY_VALUE_STORE;
+use editor::Editor;
+use fs::Fs;
+use gpui::{
+    AppContext, AsyncAppContext, DismissEvent, EventEmitter, Render, View, ViewContext,
+    VisualContext,
+};
+use http::{HttpClient, HttpClientWithUrl};
+use i18n::t;
+use isahc::Request;
+use serde_derive::Serialize;
+use settings::{update_settings_file, Settings};
+use ui::{prelude::*, Button, ButtonStyle, IconButton};
+use util::ResultExt;
+use workspace::{notifications::NotificationId, Workspace};
+
+use crate::{
+    feedback_editor::DEV_MODE,
+    feedback_http,
+    feedback_settings::{FeedbackSettings, FeedbackSettingsContent},
+};
+
+const FIRST_SEEN_KEY: &str = "survey_first_seen";
+const LAST_ASKED_KEY: &str = "survey_last_asked";
+/// How long Zed is used before it asks the first survey.
+const FIRST_SURVEY_DELAY: Duration = Duration::from_secs(14 * 24 * 60 * 60);
+/// The least time between two surveys, whether the last one was answered or not.
+const SURVEY_INTERVAL: Duration = Duration::from_secs(90 * 24 * 60 * 60);
+/// How long after startup to ask, so that surveys don't interrupt opening a project.
+const STARTUP_DELAY: Duration = Duration::from_secs(10 * 60);
+const MAX_SCORE: u8 = 10;
+
+#[derive(Serialize)]
+struct SurveyResponse<'a> {
+    survey: &'static str,
+    score: u8,
+    comment: Option<&'a str>,
+    metrics_id: Option<Arc<str>>,
+    installation_id: Option<Arc<str>>,
+    is_staff: bool,
+}
+
+impl<'a> SurveyResponse<'a> {
+    /// Leaves out what identifies the user when feedback is always sent
+    /// anonymously, and the metrics ID when usage metrics aren't sent.
+    fn anonymize(mut self, always_anonymous: bool, metrics: bool) -> Self {
+        if always_anonymous {
+            self.metrics_id = None;
+            self.installation_id = None;
+            self.is_staff = false;
+        }
+        if !metrics {
+            self.metrics_id = None;
+        }
+        self
+    }
+}
+
+pub(crate) fn init(cx: &mut AppContext) {
+    cx.spawn(|mut cx| async move {
+        cx.background_executor().timer(STARTUP_DELAY).await;
+        if should_ask_now(&mut cx).await.unwrap_or(false) {
+            cx.update(show_survey).log_err();
+        }
+    })
+    .detach();
+}
+
+/// Returns whether to ask a survey now, recording that it's asked if so.
+async fn should_ask_now(cx: &mut AsyncAppContext) -> anyhow::Result<bool> {
+    if !cx.update(|cx| FeedbackSettings::get_global(cx).surveys)? {
+        return Ok(false);
+    }
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+    let read_timestamp = |key| {
+        KEY_VALUE_STORE
+            .read_kvp(key)
+            .log_err()
+            .flatten()
+            .and_then(|timestamp| timestamp.parse().ok())
+            .map(Duration::from_secs)
+    };
+    let Some(first_seen) = read_timestamp(FIRST_SEEN_KEY) else {
+        KEY_VALUE_STORE
+            .write_kvp(FIRST_SEEN_KEY.to_string(), now.as_secs().to_string())
+            .await?;
+        return Ok(false);
+    };
+    if !is_due(now, first_seen, read_timestamp(LAST_ASKED_KEY)) {
+        return Ok(false);
+    }
+
+    KEY_VALUE_STORE
+        .write_kvp(LAST_ASKED_KEY.to_string(), now.as_secs().to_string())
+        .await?;
+    Ok(true)
+}
+
+/// Returns whether a survey is due, given when Zed was first used and when a
+/// survey was last asked, as durations since the Unix epoch.
+fn is_due(now: Duration, first_seen: Duration, last_asked: Option<Duration>) -> bool {
+    let used_for = now.saturating_sub(first_seen);
+    let since_asked = last_asked.map(|last_asked| now.saturating_sub(last_asked));
+    used_for >= FIRST_SURVEY_DELAY && since_asked.map_or(true, |since| since >= SURVEY_INTERVAL)
+}
+
+fn show_survey(cx: &mut AppContext) {
+    let workspace = cx
+        .active_window()
+        .and_then(|window| window.downcast::<Workspace>())
+        .or_else(|| {
+            cx.windows()
+                .into_iter()
+                .find_map(|window| window.downcast::<Workspace>())
+        });
+    let Some(workspace) = workspace else {
+        return;
+    };
+    workspace
+        .update(cx, |workspace, cx| {
+            struct Survey;
+
+            let fs = workspace.app_state().fs.clone();
+            workspace.show_notification(NotificationId::unique::<Survey>(), cx, |cx| {
+                cx.new_view(|cx| SurveyPrompt::new(fs, cx))
+            });
+        })
+        .log_err();
+}
+
+/// A notification that asks how likely Zed is to be recommended.
+pub struct SurveyPrompt {
+    score: Option<u8>,
+    comment_editor: View<Editor>,
+    fs: Arc<dyn Fs>,
+}
+
+impl EventEmitter<DismissEvent> for SurveyPrompt {}
+
+impl SurveyPrompt {
+    fn new(fs: Arc<dyn Fs>, cx: &mut ViewContext<Self>) -> Self {
+        let comment_editor = cx.new_view(|cx| {
+            let mut editor = Editor::auto_height(3, cx);
+            editor
+                .set_placeholder_text(t("feedback.survey.comment_placeholder", cx).to_string(), cx);
+            editor
+        });
+        Self {
+            score: None,
+            comment_editor,
+            fs,
+        }
+    }
+
+    fn submit(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(score) = self.score else {
+            return;
+        };
+        let comment = self.comment_editor.read(cx).text(cx);
+        let telemetry = Client::global(cx).telemetry().clone();
+        let request = SurveyResponse {
+            survey: "nps",
+            score,
+            comment: Some(comment.trim()).filter(|comment| !comment.is_empty()),
+            metrics_id: telemetry.metrics_id(),
+            installation_id: telemetry.installation_id(),
+            is_staff: telemetry.is_staff().unwrap_or(false),
+        }
+        .anonymize(
+            FeedbackSettings::get_global(cx).always_anonymous,
+            TelemetrySettings::get_global(cx).metrics,
+        );
+        let request_json = serde_json::to_string(&request);
+        let url = feedback_http::zed_http_client(cx).build_url("/api/surveys");
+        let http_client = feedback_http::http_client(&url, cx);
+        cx.background_executor()
+            .spawn(async move { send_survey_response(&request_json?, &http_client).await })
+            .detach_and_log_err(cx);
+        cx.emit(DismissEvent);
+    }
+
+    /// Turns surveys off in the settings.
+    fn never_ask(&mut self, cx: &mut ViewContext<Self>) {
+        update_settings_file::<FeedbackSettings>(
+            self.fs.clone(),
+            cx,
+            |settings: &mut FeedbackSettingsContent| settings.surveys = Some(false),
+        );
+        cx.emit(DismissEvent);
+    }
+}
+
+async fn send_survey_response(
+    request_json: &str,
+    http_client: &HttpClientWithUrl,
+) -> anyhow::Result<()> {
+    if DEV_MODE {
+        log::info!("not sending a survey response in development: {request_json}");
+        return Ok(());
+    }
+
+    let request = Request::post(http_client.build_url("/api/surveys"))
+        .header("content-type", "application/json")
+        .body(request_json.as_bytes().to_vec().into())?;
+    let response = http_client
+        .send(request)
+        .await
+        .map_err(|error| feedback_http::request_error(error, http_client))?;
+    if !response.status().is_success() {
+        anyhow::bail!("Survey API failed with error: {}", response.status());
+    }
+    Ok(())
+}
+
+impl Render for SurveyPrompt {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let scores = (0..=MAX_SCORE).map(|score| {
+            Button::new(("survey-score", score as usize), score.to_string())
+                .style(ButtonStyle::Filled)
+                .selected(self.score == Some(score))
+                .on_click(cx.listener(move |this, _, cx| {
+                    this.score = Some(score);
+                    cx.notify();
+                }))
+        });
+
+        v_flex()
+            .elevation_3(cx)
+            .p_4()
+            .gap_2()
+            .child(
+                h_flex()
+                    .justify_between()
+                    .gap_2()
+                    .child(Label::new(t("feedback.survey.question", cx)))
+                    .child(
+                        IconButton::new("dismiss-survey", IconName::Close)
+                            .on_click(cx.listener(|_, _, cx| cx.emit(DismissEvent))),
+                    ),
+            )
+            .child(h_flex().gap_1().children(scores))
+            .child(
+                h_flex()
+                    .justify_between()
+                    .child(
+                        Label::new(t("feedback.survey.not_likely", cx))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(
+                        Label::new(t("feedback.survey.very_likely", cx))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+            )
+            .when(self.score.is_some(), |el| {
+                el.child(
+                    div()
+                        .p_2()
+                        .border_1()
+                        .rounded_md()
+                        .border_color(cx.theme().colors().border)
+                        .child(self.comment_editor.clone()),
+                )
+            })
+            .child(
+                h_flex()
+                    .justify_between()
+                    .child(
+                        Button::new("never-ask-survey", t("feedback.survey.never_ask", cx))
+                            .style(ButtonStyle::Subtle)
+                            .label_size(LabelSize::Small)
+                            .on_click(cx.listener(|this, _, cx| this.never_ask(cx))),
+                    )
+                    .child(
+                        Button::new("submit-survey", t("feedback.submit", cx))
+                            .style(ButtonStyle::Filled)
+                            .disabled(self.score.is_none())
+                            .on_click(cx.listener(|this, _, cx| this.submit(cx))),
+                    ),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_due() {
+        let days = |count| Duration::from_secs(count * 24 * 60 * 60);
+        let first_seen = days(1000);
+        assert!(!is_due(days(1010), first_seen, None));
+        assert!(is_due(days(1014), first_seen, None));
+        assert!(!is_due(days(1100), first_seen, Some(days(1050))));
+        assert!(is_due(days(1140), first_seen, Some(days(1050))));
+    }
+
+    #[test]
+    fn test_anonymous_survey_response() {
+        let response = || SurveyResponse {
+            survey: "nps",
+            score: 9,
+            comment: None,
+            metrics_id: Some("metrics".into()),
+            installation_id: Some("installation".into()),
+            is_staff: true,
+        };
+
+        let request = response().anonymize(false, true);
+        assert_eq!(request.metrics_id.as_deref(), Some("metrics"));
+        assert_eq!(request.installation_id.as_deref(), Some("installation"));
+
+        let request = response().anonymize(false, false);
+        assert_eq!(request.metrics_id, None);
+        assert_eq!(request.installation_id.as_deref(), Some("installation"));
+
+        let request = response().anonymize(true, true);
+        assert_eq!(request.metrics_id, None);
+        assert_eq!(request.installation_id, None);
+        assert!(!request.is_staff);
+        assert_eq!(request.score, 9);
+    }
+}
@@ -1,5 +1,5 @@
 use client::telemetry;
-use gpui::{AppContext, Task};
+use gpui::{AppContext, SemanticVersion, Task};
 use human_bytes::human_bytes;
 use release_channel::{AppCommitSha, AppVersion, ReleaseChannel};
 use serde::Serialize;
@@ -15,60 +15,88 @@ pub struct SystemSpecs {
     memory: u64,
     architecture: &'static str,
     commit_sha: Option<String>,
+    renderer: Option<String>,
 }
 
 impl SystemSpecs {
     pub fn new(cx: &AppContext) -> Task<Self> {
-        let app_version = AppVersion::global(cx).to_string();
+        let app_version = AppVersion::global(cx);
         let release_channel = ReleaseChannel::global(cx);
+        let commit_sha = AppCommitSha::try_global(cx);
+        cx.background_executor()
+            .spawn(async move { Self::new_stateless(app_version, commit_sha, release_channel) })
+    }
+
+    /// Collects the specs without an [`AppContext`], for when the UI can't be started.
+    pub fn new_stateless(
+        app_version: SemanticVersion,
+        commit_sha: Option<AppCommitSha>,
+        release_channel: ReleaseChannel,
+    ) -> Self {
         let os_name = telemetry::os_name();
+        let os_version = telemetry::os_version();
         let system = System::new_with_specifics(
             RefreshKind::new().with_memory(MemoryRefreshKind::everything()),
         );
         let memory = system.total_memory();
         let architecture = env::consts::ARCH;
         let commit_sha = match release_channel {
-            ReleaseChannel::Dev | ReleaseChannel::Nightly => {
-                AppCommitSha::try_global(cx).map(|sha| sha.0.clone())
-            }
+            ReleaseChannel::Dev | ReleaseChannel::Nightly => commit_sha.map(|sha| sha.0),
             _ => None,
         };
+        let renderer = gpui::active_renderer().map(|renderer| renderer.to_string());
 
-        cx.background_executor().spawn(async move {
-            let os_version = telemetry::os_version();
-            SystemSpecs {
-                app_version,
-                release_channel: release_channel.display_name(),
-                os_name,
-                os_version,
-                memory,
-                architecture,
-                commit_sha,
-            }
-        })
+        SystemSpecs {
+            app_version: app_version.to_string(),
+            release_channel: release_channel.display_name(),
+            os_name,
+            os_version,
+            memory,
+            architecture,
+            commit_sha,
+            renderer,
+        }
     }
-}
 
-impl Display for SystemSpecs {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let os_information = format!("OS: {} {}", self.os_name, self.os_version);
+    /// Returns the specs as a Markdown list, for pasting into issues and chats.
+    pub fn to_markdown(&self) -> String {
+        self.fields()
+            .into_iter()
+            .map(|(name, value)| format!("- **{name}:** {value}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn fields(&self) -> Vec<(&'static str, String)> {
         let app_version_information = format!(
-            "Zed: v{} ({})",
+            "v{} ({})",
             self.app_version,
             match &self.commit_sha {
                 Some(commit_sha) => format!("{} {}", self.release_channel, commit_sha),
                 None => self.release_channel.to_string(),
             }
         );
-        let system_specs = [
-            app_version_information,
-            os_information,
-            format!("Memory: {}", human_bytes(self.memory as f64)),
-            format!("Architecture: {}", self.architecture),
-        ]
-        .into_iter()
-        .collect::<Vec<String>>()
-        .join("\n");
+        let mut fields = vec![
+            ("Zed", app_version_information),
+            ("OS", format!("{} {}", self.os_name, self.os_version)),
+            ("Memory", human_bytes(self.memory as f64)),
+            ("Architecture", self.architecture.to_string()),
+        ];
+        if let Some(renderer) = &self.renderer {
+            fields.push(("Renderer", renderer.clone()));
+        }
+        fields
+    }
+}
+
+impl Display for SystemSpecs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let system_specs = self
+            .fields()
+            .into_iter()
+            .map(|(name, value)| format!("{name}: {value}"))
+            .collect::<Vec<_>>()
+            .join("\n");
 
         write!(f, "{system_specs}")
     }
@@ -17,7 +17,7 @@ use picker::{Picker, PickerDelegate};
 use project::{PathMatchCandidateSet, Project, ProjectPath, WorktreeId};
 use settings::Settings;
 use std::{
-    cmp,
+    cmp, mem,
     path::{Path, PathBuf},
     sync::{
         atomic::{self, AtomicBool},
@@ -27,7 +27,7 @@ use std::{
 use text::Point;
 use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing};
 use util::{paths::PathLikeWithPosition, post_inc, ResultExt};
-use workspace::{item::PreviewTabsSettings, ModalView, Workspace};
+use workspace::{item::PreviewTabsSettings, pane, ModalView, SplitDirection, Workspace};
 
 actions!(file_finder, [SelectPrev]);
 
@@ -127,6 +127,13 @@ impl FileFinder {
         self.init_modifiers = Some(cx.modifiers());
         cx.dispatch_action(Box::new(menu::SelectPrev));
     }
+
+    fn open_in_split(&mut self, direction: SplitDirection, cx: &mut ViewContext<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            picker.delegate.split_direction = direction;
+            picker.delegate.confirm(true, cx);
+        });
+    }
 }
 
 impl EventEmitter<DismissEvent> for FileFinder {}
@@ -144,6 +151,20 @@ impl Render for FileFinder {
             .w(rems(34.))
             .on_modifiers_changed(cx.listener(Self::handle_modifiers_changed))
             .on_action(cx.listener(Self::handle_select_prev))
+            .on_action(cx.listener(|this, _: &pane::SplitLeft, cx| {
+                this.open_in_split(SplitDirection::Left, cx)
+            }))
+            .on_action(
+                cx.listener(|this, _: &pane::SplitUp, cx| {
+                    this.open_in_split(SplitDirection::Up, cx)
+                }),
+            )
+            .on_action(cx.listener(|this, _: &pane::SplitRight, cx| {
+                this.open_in_split(SplitDirection::Right, cx)
+            }))
+            .on_action(cx.listener(|this, _: &pane::SplitDown, cx| {
+                this.open_in_split(SplitDirection::Down, cx)
+            }))
             .child(self.picker.clone())
     }
 }
@@ -163,6 +184,8 @@ pub struct FileFinderDelegate {
     cancel_flag: Arc<AtomicBool>,
     history_items: Vec<FoundPath>,
     separate_history: bool,
+    /// The direction in which the selected match is opened when splitting.
+    split_direction: SplitDirection,
 }
 
 /// Use a custom ordering for file finder: the regular one
@@ -434,6 +457,7 @@ impl FileFinderDelegate {
             cancel_flag: Arc::new(AtomicBool::new(false)),
             history_items,
             separate_history,
+            split_direction: SplitDirection::Right,
         }
     }
 
@@ -814,6 +838,7 @@ impl PickerDelegate for FileFinderDelegate {
     }
 
     fn confirm(&mut self, secondary: bool, cx: &mut ViewContext<Picker<FileFinderDelegate>>) {
+        let split_direction = mem::replace(&mut self.split_direction, SplitDirection::Right);
         if let Some(m) = self.matches.get(self.selected_index()) {
             if let Some(workspace) = self.workspace.upgrade() {
                 let open_task = workspace.update(cx, move |workspace, cx| {
@@ -824,7 +849,12 @@ impl PickerDelegate for FileFinderDelegate {
                             let allow_preview =
                                 PreviewTabsSettings::get_global(cx).enable_preview_from_file_finder;
                             if secondary {
-                                workspace.split_path_preview(project_path, allow_preview, cx)
+                                workspace.split_path_in_direction(
+                                    project_path,
+                                    split_direction,
+                                    allow_preview,
+                                    cx,
+                                )
                             } else {
                                 workspace.open_path_preview(
                                     project_path,
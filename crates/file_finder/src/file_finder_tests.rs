@@ -83,6 +83,44 @@ async fn test_matching_paths(cx: &mut TestAppContext) {
     }
 }
 
+#[gpui::test]
+async fn test_open_in_split(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            "/root",
+            json!({
+                "a": {
+                    "banana": "",
+                    "bandana": "",
+                }
+            }),
+        )
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), ["/root".as_ref()], cx).await;
+
+    let (_, workspace, cx) = build_find_picker(project, cx);
+
+    cx.simulate_input("banana");
+    cx.dispatch_action(Confirm);
+    cx.run_until_parked();
+    workspace.update(cx, |workspace, _| assert_eq!(workspace.panes().len(), 1));
+
+    open_file_picker(&workspace, cx);
+    cx.simulate_input("bandana");
+    cx.dispatch_action(pane::SplitDown);
+    cx.run_until_parked();
+    workspace.update(cx, |workspace, cx| {
+        assert_eq!(workspace.panes().len(), 2);
+        let active_editor = workspace.active_item_as::<Editor>(cx).unwrap();
+        assert_eq!(active_editor.read(cx).title(cx), "bandana");
+        assert!(workspace.active_modal::<FileFinder>(cx).is_none());
+    });
+}
+
 #[gpui::test]
 async fn test_absolute_paths(cx: &mut TestAppContext) {
     let app_state = init_test(cx);
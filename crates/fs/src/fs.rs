@@ -66,6 +66,10 @@ pub trait Fs: Send + Sync {
     async fn trash_file(&self, path: &Path, options: RemoveOptions) -> Result<()> {
         self.remove_file(path, options).await
     }
+    /// Moves a file or directory that was moved to the trash back to where it was.
+    async fn restore_from_trash(&self, path: &Path) -> Result<()> {
+        Err(anyhow!("{path:?} can't be restored from the trash"))
+    }
     async fn open_sync(&self, path: &Path) -> Result<Box<dyn io::Read>>;
     async fn load(&self, path: &Path) -> Result<String>;
     async fn atomic_write(&self, path: PathBuf, text: String) -> Result<()>;
@@ -126,12 +130,16 @@ pub struct Metadata {
     pub mtime: SystemTime,
     pub is_symlink: bool,
     pub is_dir: bool,
+    pub len: u64,
 }
 
 #[derive(Default)]
 pub struct RealFs {
     git_hosting_provider_registry: Arc<GitHostingProviderRegistry>,
     git_binary_path: Option<PathBuf>,
+    /// Where the paths that were moved to the trash ended up in it.
+    #[cfg(target_os = "macos")]
+    trashed_paths: parking_lot::Mutex<collections::HashMap<PathBuf, PathBuf>>,
 }
 
 pub struct RealWatcher {
@@ -147,10 +155,65 @@ impl RealFs {
         Self {
             git_hosting_provider_registry,
             git_binary_path,
+            #[cfg(target_os = "macos")]
+            trashed_paths: Default::default(),
         }
     }
 }
 
+/// The trash that files are moved to on Linux, as described by the
+/// [FreeDesktop trash specification](https://specifications.freedesktop.org/trash-spec/trashspec-latest.html).
+#[cfg(target_os = "linux")]
+fn home_trash_dir() -> PathBuf {
+    match std::env::var_os("XDG_DATA_HOME") {
+        Some(data_dir) if !data_dir.is_empty() => PathBuf::from(data_dir),
+        _ => util::paths::home_dir().join(".local/share"),
+    }
+    .join("Trash")
+}
+
+/// Finds the most recently trashed item that was at `path`, returning where
+/// it is in the trash and its trash info file.
+#[cfg(target_os = "linux")]
+async fn find_in_trash(trash_dir: &Path, path: &Path) -> Result<(PathBuf, PathBuf)> {
+    let mut latest: Option<(String, PathBuf)> = None;
+    let mut info_paths = smol::fs::read_dir(trash_dir.join("info")).await?;
+    while let Some(info_path) = info_paths.next().await {
+        let info_path = info_path?.path();
+        if info_path.extension() != Some("trashinfo".as_ref()) {
+            continue;
+        }
+        let Ok(info) = smol::fs::read_to_string(&info_path).await else {
+            continue;
+        };
+        let mut trashed_path = None;
+        let mut deletion_date = String::new();
+        for line in info.lines() {
+            if let Some(value) = line.strip_prefix("Path=") {
+                // Paths are percent-encoded like the path of a file URL.
+                trashed_path = url::Url::parse(&format!("file://{value}"))
+                    .ok()
+                    .and_then(|url| url.to_file_path().ok());
+            } else if let Some(value) = line.strip_prefix("DeletionDate=") {
+                deletion_date = value.to_string();
+            }
+        }
+        if trashed_path.as_deref() == Some(path)
+            && latest
+                .as_ref()
+                .map_or(true, |(latest_date, _)| deletion_date >= *latest_date)
+        {
+            latest = Some((deletion_date, info_path));
+        }
+    }
+
+    let (_, info_path) = latest.ok_or_else(|| anyhow!("{path:?} is not in the trash"))?;
+    let file_name = info_path
+        .file_stem()
+        .ok_or_else(|| anyhow!("invalid trash info file {info_path:?}"))?;
+    Ok((trash_dir.join("files").join(file_name), info_path))
+}
+
 #[async_trait::async_trait]
 impl Fs for RealFs {
     async fn create_dir(&self, path: &Path) -> Result<()> {
@@ -271,22 +334,34 @@ impl Fs for RealFs {
     #[cfg(target_os = "macos")]
     async fn trash_file(&self, path: &Path, _options: RemoveOptions) -> Result<()> {
         use cocoa::{
-            base::{id, nil},
+            base::{id, nil, BOOL, NO},
             foundation::{NSAutoreleasePool, NSString},
         };
         use objc::{class, msg_send, sel, sel_impl};
+        use std::ffi::CStr;
 
-        unsafe {
+        let trashed_path = unsafe {
             unsafe fn ns_string(string: &str) -> id {
                 NSString::alloc(nil).init_str(string).autorelease()
             }
 
             let url: id = msg_send![class!(NSURL), fileURLWithPath: ns_string(path.to_string_lossy().as_ref())];
-            let array: id = msg_send![class!(NSArray), arrayWithObject: url];
-            let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
-
-            let _: id = msg_send![workspace, recycleURLs: array completionHandler: nil];
-        }
+            let file_manager: id = msg_send![class!(NSFileManager), defaultManager];
+            let mut trashed_url: id = nil;
+            let trashed: BOOL = msg_send![file_manager, trashItemAtURL: url resultingItemURL: &mut trashed_url error: nil];
+            if trashed == NO || trashed_url == nil {
+                return Err(anyhow!("failed to move {path:?} to the trash"));
+            }
+            let trashed_path: id = msg_send![trashed_url, path];
+            PathBuf::from(
+                CStr::from_ptr(trashed_path.UTF8String())
+                    .to_string_lossy()
+                    .into_owned(),
+            )
+        };
+        self.trashed_paths
+            .lock()
+            .insert(path.to_path_buf(), trashed_path);
         Ok(())
     }
 
@@ -309,6 +384,33 @@ impl Fs for RealFs {
         self.trash_file(path, options).await
     }
 
+    #[cfg(target_os = "macos")]
+    async fn restore_from_trash(&self, path: &Path) -> Result<()> {
+        let trashed_path = self
+            .trashed_paths
+            .lock()
+            .get(path)
+            .cloned()
+            .ok_or_else(|| anyhow!("{path:?} is not in the trash"))?;
+        if smol::fs::symlink_metadata(path).await.is_ok() {
+            return Err(anyhow!("{path:?} already exists"));
+        }
+        smol::fs::rename(&trashed_path, path).await?;
+        self.trashed_paths.lock().remove(path);
+        Ok(())
+    }
+
+    #[cfg(target_os = "linux")]
+    async fn restore_from_trash(&self, path: &Path) -> Result<()> {
+        let (trashed_path, info_path) = find_in_trash(&home_trash_dir(), path).await?;
+        if smol::fs::symlink_metadata(path).await.is_ok() {
+            return Err(anyhow!("{path:?} already exists"));
+        }
+        smol::fs::rename(&trashed_path, path).await?;
+        smol::fs::remove_file(&info_path).await.log_err();
+        Ok(())
+    }
+
     async fn open_sync(&self, path: &Path) -> Result<Box<dyn io::Read>> {
         Ok(Box::new(std::fs::File::open(path)?))
     }
@@ -398,6 +500,7 @@ impl Fs for RealFs {
             mtime: metadata.modified().unwrap(),
             is_symlink,
             is_dir: metadata.file_type().is_dir(),
+            len: metadata.len(),
         }))
     }
 
@@ -513,7 +616,7 @@ impl Fs for RealFs {
     async fn watch(
         &self,
         path: &Path,
-        _latency: Duration,
+        latency: Duration,
     ) -> (
         Pin<Box<dyn Send + Stream<Item = Vec<PathBuf>>>>,
         Arc<dyn Watcher>,
@@ -526,7 +629,9 @@ impl Fs for RealFs {
             let tx = tx.clone();
             move |event: Result<notify::Event, _>| {
                 if let Some(event) = event.log_err() {
-                    tx.try_send(event.paths).ok();
+                    if !event.paths.is_empty() {
+                        tx.try_send(event.paths).ok();
+                    }
                 }
             }
         })
@@ -536,8 +641,10 @@ impl Fs for RealFs {
             .watch(path, notify::RecursiveMode::Recursive)
             .log_err();
 
+        let batched_paths = batch_paths(rx, latency, smol::Timer::after);
+
         (
-            Box::pin(rx.chain(futures::stream::once(async move {
+            Box::pin(batched_paths.chain(futures::stream::once(async move {
                 drop(file_watcher);
                 vec![]
             }))),
@@ -648,6 +755,8 @@ struct FakeFsState {
     buffered_events: Vec<PathBuf>,
     metadata_call_count: usize,
     read_dir_call_count: usize,
+    /// The entries that were moved to the trash, along with where they were.
+    trash: Vec<(PathBuf, Arc<Mutex<FakeFsEntry>>)>,
 }
 
 #[cfg(any(test, feature = "test-support"))]
@@ -799,6 +908,7 @@ impl FakeFs {
                 events_paused: false,
                 read_dir_call_count: 0,
                 metadata_call_count: 0,
+                trash: Vec::new(),
             }),
         })
     }
@@ -1423,6 +1533,47 @@ impl Fs for FakeFs {
         Ok(())
     }
 
+    async fn trash_dir(&self, path: &Path, options: RemoveOptions) -> Result<()> {
+        let entry = self.state.lock().read_path(&normalize_path(path)).ok();
+        self.remove_dir(path, options).await?;
+        if let Some(entry) = entry {
+            self.state.lock().trash.push((normalize_path(path), entry));
+        }
+        Ok(())
+    }
+
+    async fn trash_file(&self, path: &Path, options: RemoveOptions) -> Result<()> {
+        let entry = self.state.lock().read_path(&normalize_path(path)).ok();
+        self.remove_file(path, options).await?;
+        if let Some(entry) = entry {
+            self.state.lock().trash.push((normalize_path(path), entry));
+        }
+        Ok(())
+    }
+
+    async fn restore_from_trash(&self, path: &Path) -> Result<()> {
+        self.simulate_random_delay().await;
+
+        let path = normalize_path(path);
+        let mut state = self.state.lock();
+        let ix = state
+            .trash
+            .iter()
+            .rposition(|(trashed_path, _)| *trashed_path == path)
+            .ok_or_else(|| anyhow!("{path:?} is not in the trash"))?;
+        let entry = state.trash[ix].1.clone();
+        state.write_path(&path, |e| match e {
+            btree_map::Entry::Occupied(_) => Err(anyhow!("{path:?} already exists")),
+            btree_map::Entry::Vacant(e) => {
+                e.insert(entry);
+                Ok(())
+            }
+        })?;
+        state.trash.remove(ix);
+        state.emit_event(&[path]);
+        Ok(())
+    }
+
     async fn open_sync(&self, path: &Path) -> Result<Box<dyn io::Read>> {
         let bytes = self.load_internal(path).await?;
         Ok(Box::new(io::Cursor::new(bytes)))
@@ -1496,17 +1647,23 @@ impl Fs for FakeFs {
 
             let entry = entry.lock();
             Ok(Some(match &*entry {
-                FakeFsEntry::File { inode, mtime, .. } => Metadata {
+                FakeFsEntry::File {
+                    inode,
+                    mtime,
+                    content,
+                } => Metadata {
                     inode: *inode,
                     mtime: *mtime,
                     is_dir: false,
                     is_symlink,
+                    len: content.len() as u64,
                 },
                 FakeFsEntry::Dir { inode, mtime, .. } => Metadata {
                     inode: *inode,
                     mtime: *mtime,
                     is_dir: true,
                     is_symlink,
+                    len: 0,
                 },
                 FakeFsEntry::Symlink { .. } => unreachable!(),
             }))
@@ -1698,6 +1855,34 @@ pub fn copy_recursive<'a>(
 // todo(windows)
 // can we get file id not open the file twice?
 // https://github.com/rust-lang/rust/issues/63010
+/// Batches the paths of the file events received within `latency` of the
+/// first one, like on the other platforms, rather than waking up the worktree
+/// for every single one of them.
+#[cfg(any(target_os = "windows", test))]
+fn batch_paths<T: std::future::Future>(
+    events: impl Stream<Item = Vec<PathBuf>> + Unpin,
+    latency: Duration,
+    timer: impl Fn(Duration) -> T,
+) -> impl Stream<Item = Vec<PathBuf>> {
+    use futures::future::{self, Either};
+
+    futures::stream::unfold((events, timer), move |(mut events, timer)| async move {
+        let mut paths = events.next().await?;
+        paths.sort();
+        let mut timer_elapsed = std::pin::pin!(timer(latency));
+        loop {
+            match future::select(timer_elapsed.as_mut(), events.next()).await {
+                Either::Right((Some(mut more_paths), _)) => {
+                    more_paths.sort();
+                    util::extend_sorted(&mut paths, more_paths, usize::MAX, PathBuf::cmp);
+                }
+                Either::Left(_) | Either::Right((None, _)) => break,
+            }
+        }
+        Some((paths, (events, timer)))
+    })
+}
+
 #[cfg(target_os = "windows")]
 async fn file_id(path: impl AsRef<Path>) -> Result<u64> {
     use std::os::windows::io::AsRawHandle;
@@ -1784,4 +1969,58 @@ mod tests {
             "D",
         );
     }
+
+    #[gpui::test]
+    async fn test_batch_paths(executor: BackgroundExecutor) {
+        let fs = FakeFs::new(executor.clone());
+        fs.insert_tree("/root", json!({ "a": "", "b": "" })).await;
+
+        let latency = Duration::from_millis(100);
+        let (events, _watcher) = fs.watch("/root".as_ref(), latency).await;
+        let mut batched_paths = Box::pin(batch_paths(events, latency, {
+            let executor = executor.clone();
+            move |duration| executor.timer(duration)
+        }));
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        executor
+            .spawn({
+                let batches = batches.clone();
+                async move {
+                    while let Some(paths) = batched_paths.next().await {
+                        batches.lock().push(paths);
+                    }
+                }
+            })
+            .detach();
+
+        // The events received within the latency are reported together.
+        fs.save("/root/b".as_ref(), &"B".into(), LineEnding::Unix)
+            .await
+            .unwrap();
+        fs.save("/root/a".as_ref(), &"A".into(), LineEnding::Unix)
+            .await
+            .unwrap();
+        fs.save("/root/b".as_ref(), &"BB".into(), LineEnding::Unix)
+            .await
+            .unwrap();
+        executor.run_until_parked();
+        assert!(batches.lock().is_empty());
+
+        executor.advance_clock(latency);
+        executor.run_until_parked();
+        assert_eq!(
+            batches.lock().drain(..).collect::<Vec<_>>(),
+            vec![vec![PathBuf::from("/root/a"), PathBuf::from("/root/b")]]
+        );
+
+        // Later events are reported in a batch of their own.
+        fs.insert_file("/root/c", Vec::new()).await;
+        executor.run_until_parked();
+        executor.advance_clock(latency);
+        executor.run_until_parked();
+        assert_eq!(
+            batches.lock().drain(..).collect::<Vec<_>>(),
+            vec![vec![PathBuf::from("/root/c")]]
+        );
+    }
 }
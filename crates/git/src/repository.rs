@@ -321,6 +321,7 @@ pub enum GitFileStatus {
     Added,
     Modified,
     Conflict,
+    Untracked,
 }
 
 impl GitFileStatus {
@@ -343,6 +344,9 @@ impl GitFileStatus {
             (Some(GitFileStatus::Added), _) | (_, Some(GitFileStatus::Added)) => {
                 Some(GitFileStatus::Added)
             }
+            (Some(GitFileStatus::Untracked), _) | (_, Some(GitFileStatus::Untracked)) => {
+                Some(GitFileStatus::Untracked)
+            }
             _ => None,
         }
     }
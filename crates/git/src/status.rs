@@ -66,7 +66,8 @@ impl GitStatus {
                     Some((
                         RepoPath(PathBuf::from(path)),
                         match status {
-                            "A" | "??" => GitFileStatus::Added,
+                            "A" => GitFileStatus::Added,
+                            "??" => GitFileStatus::Untracked,
                             "M" => GitFileStatus::Modified,
                             _ => return None,
                         },
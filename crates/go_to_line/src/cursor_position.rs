@@ -162,6 +162,10 @@ impl StatusItemView for CursorPosition {
 
         cx.notify();
     }
+
+    fn status_item_id() -> Option<&'static str> {
+        Some("cursor_position")
+    }
 }
 
 #[derive(Clone, Copy, Default, PartialEq, JsonSchema, Deserialize, Serialize)]
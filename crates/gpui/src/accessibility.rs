@@ -0,0 +1,241 @@
+use std::ops::Range;
+
+use collections::{FxHashMap, FxHashSet};
+
+use crate::{Bounds, Pixels, SharedString};
+
+/// What an element is, for assistive technologies like screen readers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AccessibilityRole {
+    /// An element that groups other elements, like a panel or a pane.
+    Group,
+    /// An element that performs an action when it's clicked.
+    Button,
+    /// A button that can be toggled on and off.
+    CheckBox,
+    /// An editable, multi-line text.
+    TextEditor,
+    /// An editable, single-line text.
+    TextField,
+    /// Text that can't be edited.
+    StaticText,
+    /// A list of items.
+    List,
+    /// An item of a list.
+    ListItem,
+    /// A menu of actions.
+    Menu,
+    /// An action in a menu.
+    MenuItem,
+    /// A floating element that is shown over other elements.
+    Popover,
+    /// A list of tabs.
+    TabList,
+    /// A tab in a list of tabs.
+    Tab,
+    /// A bar of controls.
+    Toolbar,
+}
+
+/// Identifies an accessibility node across frames, as long as the element that adds it
+/// keeps its place in the element tree.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AccessibilityNodeId(pub(crate) u64);
+
+/// Describes an element to assistive technologies.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessibilityNode {
+    /// The node's identity.
+    pub id: AccessibilityNodeId,
+    /// The node that contains this node, if any.
+    pub parent: Option<AccessibilityNodeId>,
+    /// What the element is.
+    pub role: AccessibilityRole,
+    /// A short description of the element, like a button's text.
+    pub label: Option<SharedString>,
+    /// The element's value, like a text editor's text.
+    pub value: Option<SharedString>,
+    /// The selected range of the element's value, in UTF-16 code units.
+    pub selected_range: Option<Range<usize>>,
+    /// Where the element is, in the window.
+    pub bounds: Bounds<Pixels>,
+    /// Whether the element has the keyboard focus.
+    pub focused: bool,
+}
+
+/// The properties of an accessibility node, as they are given by an element.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessibilityNodeProperties {
+    /// What the element is.
+    pub role: AccessibilityRole,
+    /// A short description of the element, like a button's text.
+    pub label: Option<SharedString>,
+    /// The element's value, like a text editor's text.
+    pub value: Option<SharedString>,
+    /// The selected range of the element's value, in UTF-16 code units.
+    pub selected_range: Option<Range<usize>>,
+    /// Whether the element has the keyboard focus.
+    pub focused: bool,
+}
+
+impl AccessibilityNodeProperties {
+    /// Creates properties with the given role, and no label or value.
+    pub fn new(role: AccessibilityRole) -> Self {
+        Self {
+            role,
+            label: None,
+            value: None,
+            selected_range: None,
+            focused: false,
+        }
+    }
+
+    /// Sets the label of the node.
+    pub fn label(mut self, label: impl Into<SharedString>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Sets the value of the node, and the selected range of the value.
+    pub fn value(
+        mut self,
+        value: impl Into<SharedString>,
+        selected_range: Option<Range<usize>>,
+    ) -> Self {
+        self.value = Some(value.into());
+        self.selected_range = selected_range;
+        self
+    }
+
+    /// Sets whether the node has the keyboard focus.
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+}
+
+/// The accessibility nodes of a window's frame, in the order that they were painted,
+/// so that each node comes after the node that contains it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AccessibilityTree {
+    nodes: Vec<AccessibilityNode>,
+}
+
+impl AccessibilityTree {
+    pub(crate) fn new(nodes: Vec<AccessibilityNode>) -> Self {
+        Self { nodes }
+    }
+
+    /// All of the nodes in the tree.
+    pub fn nodes(&self) -> &[AccessibilityNode] {
+        &self.nodes
+    }
+
+    /// Whether the tree has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// The nodes contained by the given node, or that aren't contained by any node when
+    /// `parent` is `None`.
+    pub fn children(
+        &self,
+        parent: Option<AccessibilityNodeId>,
+    ) -> impl Iterator<Item = &AccessibilityNode> {
+        self.nodes.iter().filter(move |node| node.parent == parent)
+    }
+
+    /// The node that has the keyboard focus, if any.
+    pub fn focused(&self) -> Option<&AccessibilityNode> {
+        self.nodes.iter().rev().find(|node| node.focused)
+    }
+}
+
+/// Assigns identities to the accessibility nodes of a frame.
+#[derive(Default)]
+pub(crate) struct AccessibilityNodeIds {
+    /// The number of nodes that were given each base identity in the current frame.
+    counts: FxHashMap<u64, u64>,
+    /// The identities of the nodes in the current frame.
+    used: FxHashSet<AccessibilityNodeId>,
+}
+
+impl AccessibilityNodeIds {
+    /// Returns the identity for a node, given a hash of what identifies it in the element tree.
+    /// Nodes with the same hash are told apart by the order in which they are painted.
+    pub(crate) fn next(&mut self, base: u64) -> AccessibilityNodeId {
+        loop {
+            let count = self.counts.entry(base).or_default();
+            let id =
+                AccessibilityNodeId(base.wrapping_add(count.wrapping_mul(0x9e37_79b9_7f4a_7c15)));
+            *count += 1;
+            if self.used.insert(id) {
+                return id;
+            }
+        }
+    }
+
+    /// Keeps the identity of a node whose paint was reused from the previous frame from being
+    /// given to another node.
+    pub(crate) fn reserve(&mut self, id: AccessibilityNodeId) {
+        self.used.insert(id);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.counts.clear();
+        self.used.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{point, px, size};
+
+    #[test]
+    fn test_accessibility_tree() {
+        let node = |id, parent, role, focused| AccessibilityNode {
+            id: AccessibilityNodeId(id),
+            parent: parent.map(AccessibilityNodeId),
+            role,
+            label: None,
+            value: None,
+            selected_range: None,
+            bounds: Bounds::new(point(px(0.), px(0.)), size(px(10.), px(10.))),
+            focused,
+        };
+        let tree = AccessibilityTree::new(vec![
+            node(1, None, AccessibilityRole::Group, false),
+            node(2, Some(1), AccessibilityRole::Button, false),
+            node(3, Some(1), AccessibilityRole::TextEditor, true),
+            node(4, None, AccessibilityRole::Popover, false),
+        ]);
+        assert_eq!(
+            tree.children(None)
+                .map(|node| node.id.0)
+                .collect::<Vec<_>>(),
+            [1, 4]
+        );
+        assert_eq!(
+            tree.children(Some(AccessibilityNodeId(1)))
+                .map(|node| node.id.0)
+                .collect::<Vec<_>>(),
+            [2, 3]
+        );
+        assert_eq!(tree.focused().map(|node| node.id.0), Some(3));
+
+        let mut ids = AccessibilityNodeIds::default();
+        let first = ids.next(7);
+        let second = ids.next(7);
+        assert_ne!(first, second);
+        ids.clear();
+        assert_eq!(ids.next(7), first);
+
+        // Identities of reused nodes aren't given to new nodes.
+        ids.clear();
+        ids.reserve(first);
+        let next = ids.next(7);
+        assert_ne!(next, first);
+        assert_eq!(next, second);
+    }
+}
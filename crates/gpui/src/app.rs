@@ -235,6 +235,7 @@ pub struct AppContext {
     pub(crate) layout_id_buffer: Vec<LayoutId>, // We recycle this memory across layout requests.
     pub(crate) propagate_event: bool,
     pub(crate) prompt_builder: Option<PromptBuilder>,
+    pub(crate) reduce_motion: Option<bool>,
 }
 
 impl AppContext {
@@ -289,6 +290,7 @@ impl AppContext {
                 layout_id_buffer: Default::default(),
                 propagate_event: true,
                 prompt_builder: Some(PromptBuilder::Default),
+                reduce_motion: None,
             }),
         });
 
@@ -637,6 +639,21 @@ impl AppContext {
         self.platform.should_auto_hide_scrollbars()
     }
 
+    /// Returns whether animations should be skipped, either because the user has asked for
+    /// reduced motion at the platform level, or because it was overridden with [`Self::set_reduce_motion`].
+    pub fn should_reduce_motion(&self) -> bool {
+        self.reduce_motion
+            .unwrap_or_else(|| self.platform.should_reduce_motion())
+    }
+
+    /// Overrides the platform's reduced motion preference. Pass `None` to follow the platform again.
+    pub fn set_reduce_motion(&mut self, reduce_motion: Option<bool>) {
+        if self.reduce_motion != reduce_motion {
+            self.reduce_motion = reduce_motion;
+            self.refresh();
+        }
+    }
+
     /// Restart the application.
     pub fn restart(&self, binary_path: Option<PathBuf>) {
         self.platform.restart(binary_path)
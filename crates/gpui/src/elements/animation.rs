@@ -41,7 +41,10 @@ impl Animation {
     }
 }
 
-/// An extension trait for adding the animation wrapper to both Elements and Components
+/// An extension trait for adding the animation wrapper to both Elements and Components.
+///
+/// When the app should reduce motion (see [`crate::AppContext::should_reduce_motion`]),
+/// one-shot animations are rendered at their end, and repeating animations at their start.
 pub trait AnimationExt {
     /// Render this component or element with an animation
     fn with_animation(
@@ -114,7 +117,10 @@ impl<E: IntoElement + 'static> Element for AnimationElement<E> {
                 state.start.elapsed().as_secs_f32() / self.animation.duration.as_secs_f32();
 
             let mut done = false;
-            if delta > 1.0 {
+            if cx.should_reduce_motion() {
+                done = true;
+                delta = if self.animation.oneshot { 1.0 } else { 0.0 };
+            } else if delta > 1.0 {
                 if self.animation.oneshot {
                     done = true;
                     delta = 1.0;
@@ -16,12 +16,13 @@
 //! constructed by combining these two systems into an all-in-one element.
 
 use crate::{
-    point, px, size, Action, AnyDrag, AnyElement, AnyTooltip, AnyView, AppContext, Bounds,
-    ClickEvent, DispatchPhase, Element, ElementId, FocusHandle, Global, GlobalElementId, Hitbox,
-    HitboxId, IntoElement, IsZero, KeyContext, KeyDownEvent, KeyUpEvent, LayoutId,
-    ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
-    ParentElement, Pixels, Point, Render, ScrollWheelEvent, SharedString, Size, Style,
-    StyleRefinement, Styled, Task, TooltipId, View, Visibility, WindowContext,
+    point, px, size, AccessibilityNodeProperties, AccessibilityRole, Action, AnyDrag, AnyElement,
+    AnyTooltip, AnyView, AppContext, Bounds, ClickEvent, DispatchPhase, Element, ElementId,
+    FocusHandle, Global, GlobalElementId, Hitbox, HitboxId, IntoElement, IsZero, KeyContext,
+    KeyDownEvent, KeyUpEvent, LayoutId, ModifiersChangedEvent, MouseButton, MouseDownEvent,
+    MouseMoveEvent, MouseUpEvent, ParentElement, Pixels, Point, Render, ScrollWheelEvent,
+    SharedString, Size, Style, StyleRefinement, Styled, Task, TooltipId, View, Visibility,
+    WindowContext,
 };
 use collections::HashMap;
 use refineable::Refineable;
@@ -545,6 +546,19 @@ pub trait InteractiveElement: Sized {
         Focusable { element: self }
     }
 
+    /// Describe this element to assistive technologies, like screen readers, with the given role.
+    fn accessibility_role(mut self, role: AccessibilityRole) -> Self {
+        self.interactivity().accessibility_role = Some(role);
+        self
+    }
+
+    /// Describe this element to assistive technologies, like screen readers, with the given label.
+    /// The label is only used when the element also has a role.
+    fn accessibility_label(mut self, label: impl Into<SharedString>) -> Self {
+        self.interactivity().accessibility_label = Some(label.into());
+        self
+    }
+
     /// Set the keymap context for this element. This will be used to determine
     /// which action to dispatch from the keymap.
     fn key_context<C, E>(mut self, key_context: C) -> Self
@@ -1029,6 +1043,27 @@ pub trait FocusableElement: InteractiveElement {
         self.interactivity().in_focus_style = Some(Box::new(f(StyleRefinement::default())));
         self
     }
+
+    /// Make this element a stop for [`WindowContext::focus_next`] and
+    /// [`WindowContext::focus_previous`], which usually move the focus on tab and shift-tab.
+    fn tab_stop(mut self) -> Self
+    where
+        Self: Sized,
+    {
+        self.interactivity().tab_stop = true;
+        self
+    }
+
+    /// Trap the focus within this element: while it's within this element,
+    /// [`WindowContext::focus_next`] and [`WindowContext::focus_previous`] only move it
+    /// between the tab stops within this element.
+    fn focus_trap(mut self) -> Self
+    where
+        Self: Sized,
+    {
+        self.interactivity().focus_trap = true;
+        self
+    }
 }
 
 pub(crate) type MouseDownListener =
@@ -1276,6 +1311,10 @@ pub struct Interactivity {
     pub(crate) hover_listener: Option<Box<dyn Fn(&bool, &mut WindowContext)>>,
     pub(crate) tooltip_builder: Option<TooltipBuilder>,
     pub(crate) occlude_mouse: bool,
+    pub(crate) accessibility_role: Option<AccessibilityRole>,
+    pub(crate) accessibility_label: Option<SharedString>,
+    pub(crate) tab_stop: bool,
+    pub(crate) focus_trap: bool,
 
     #[cfg(debug_assertions)]
     pub(crate) location: Option<core::panic::Location<'static>>,
@@ -1519,7 +1558,18 @@ impl Interactivity {
                             }
 
                             self.paint_keyboard_listeners(cx);
-                            f(&style, cx);
+                            if let Some(role) = self.accessibility_role {
+                                let mut properties = AccessibilityNodeProperties::new(role)
+                                    .focused(
+                                        self.tracked_focus_handle
+                                            .as_ref()
+                                            .map_or(false, |handle| handle.is_focused(cx)),
+                                    );
+                                properties.label = self.accessibility_label.clone();
+                                cx.with_accessibility_node(properties, bounds, |cx| f(&style, cx));
+                            } else {
+                                f(&style, cx);
+                            }
 
                             if hitbox.is_some() {
                                 if let Some(group) = self.group.as_ref() {
@@ -2000,6 +2050,12 @@ impl Interactivity {
         }
         if let Some(focus_handle) = self.tracked_focus_handle.as_ref() {
             cx.set_focus_handle(focus_handle);
+            if self.tab_stop {
+                cx.set_tab_stop();
+            }
+            if self.focus_trap {
+                cx.set_focus_trap();
+            }
         }
 
         for listener in key_down_listeners {
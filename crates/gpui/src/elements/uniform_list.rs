@@ -11,7 +11,12 @@ use crate::{
     ViewContext, WindowContext,
 };
 use smallvec::SmallVec;
-use std::{cell::RefCell, cmp, ops::Range, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    cmp,
+    ops::Range,
+    rc::Rc,
+};
 use taffy::style::Overflow;
 
 /// uniform_list provides lazy rendering for a set of items that are of uniform height.
@@ -83,6 +88,7 @@ pub struct UniformListFrameState {
 pub struct UniformListScrollHandle {
     base_handle: ScrollHandle,
     deferred_scroll_to_item: Rc<RefCell<Option<usize>>>,
+    item_height: Rc<Cell<Option<Pixels>>>,
 }
 
 impl UniformListScrollHandle {
@@ -91,6 +97,7 @@ impl UniformListScrollHandle {
         Self {
             base_handle: ScrollHandle::new(),
             deferred_scroll_to_item: Rc::new(RefCell::new(None)),
+            item_height: Rc::new(Cell::new(None)),
         }
     }
 
@@ -101,9 +108,26 @@ impl UniformListScrollHandle {
 
     /// Get the index of the topmost visible child.
     pub fn logical_scroll_top_index(&self) -> usize {
-        self.deferred_scroll_to_item
-            .borrow()
-            .unwrap_or_else(|| self.base_handle.logical_scroll_top().0)
+        if let Some(ix) = *self.deferred_scroll_to_item.borrow() {
+            return ix;
+        }
+        match self.item_height.get() {
+            Some(item_height) if item_height > px(0.) => {
+                (-self.base_handle.offset().y / item_height).floor() as usize
+            }
+            _ => 0,
+        }
+    }
+
+    /// Scroll the list by a number of items, e.g. to keep showing the same items after
+    /// items were inserted or removed above them. Does nothing before the list is laid out.
+    pub fn scroll_by_items(&self, delta: isize) {
+        let Some(item_height) = self.item_height.get() else {
+            return;
+        };
+        let mut offset = self.base_handle.offset();
+        offset.y = (offset.y - item_height * delta as f32).min(px(0.));
+        self.base_handle.set_offset(offset);
     }
 }
 
@@ -195,6 +219,9 @@ impl Element for UniformList {
         let shared_scroll_offset = self.interactivity.scroll_offset.clone().unwrap();
 
         let item_height = self.measure_item(Some(padded_bounds.size.width), cx).height;
+        if let Some(handle) = self.scroll_handle.as_ref() {
+            handle.item_height.set(Some(item_height));
+        }
         let shared_scroll_to_item = self
             .scroll_handle
             .as_mut()
@@ -337,3 +364,58 @@ impl InteractiveElement for UniformList {
         &mut self.interactivity
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{self as gpui, TestAppContext};
+
+    #[gpui::test]
+    fn test_scroll_by_items(cx: &mut TestAppContext) {
+        use crate::{
+            div, point, px, size, uniform_list, IntoElement, Render, Styled,
+            UniformListScrollHandle, ViewContext,
+        };
+
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+                div()
+            }
+        }
+
+        let (view, cx) = cx.add_window_view(|_| TestView);
+        let handle = UniformListScrollHandle::new();
+        let draw = |cx: &mut gpui::VisualTestContext| {
+            let view = view.clone();
+            let handle = handle.clone();
+            cx.draw(point(px(0.), px(0.)), size(px(100.), px(30.)), |_| {
+                uniform_list(view, "list", 10, |_, range, _| {
+                    range.map(|_| div().h(px(10.))).collect()
+                })
+                .w_full()
+                .h_full()
+                .track_scroll(handle)
+            });
+        };
+
+        // The item height isn't known before the list is laid out.
+        handle.scroll_by_items(3);
+        assert_eq!(handle.logical_scroll_top_index(), 0);
+
+        draw(cx);
+        assert_eq!(handle.logical_scroll_top_index(), 0);
+
+        handle.scroll_by_items(3);
+        assert_eq!(handle.logical_scroll_top_index(), 3);
+        draw(cx);
+        assert_eq!(handle.logical_scroll_top_index(), 3);
+
+        handle.scroll_by_items(-1);
+        assert_eq!(handle.logical_scroll_top_index(), 2);
+
+        // Scrolling stops at the top of the list.
+        handle.scroll_by_items(-5);
+        assert_eq!(handle.logical_scroll_top_index(), 0);
+    }
+}
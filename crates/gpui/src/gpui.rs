@@ -65,6 +65,7 @@
 #![allow(unused_mut)] // False positives in platform specific code
 
 #[macro_use]
+mod accessibility;
 mod action;
 mod app;
 
@@ -114,6 +115,7 @@ mod seal {
     pub trait Sealed {}
 }
 
+pub use accessibility::*;
 pub use action::*;
 pub use anyhow::Result;
 pub use app::*;
@@ -87,6 +87,8 @@ pub(crate) struct DispatchNode {
     pub focus_id: Option<FocusId>,
     view_id: Option<EntityId>,
     parent: Option<DispatchNodeId>,
+    tab_stop: bool,
+    focus_trap: bool,
 }
 
 pub(crate) struct ReusedSubtree {
@@ -204,6 +206,14 @@ impl DispatchTree {
         self.focusable_node_ids.insert(focus_id, node_id);
     }
 
+    pub fn set_tab_stop(&mut self) {
+        self.active_node().tab_stop = true;
+    }
+
+    pub fn set_focus_trap(&mut self) {
+        self.active_node().focus_trap = true;
+    }
+
     pub fn parent_view_id(&mut self) -> Option<EntityId> {
         self.view_stack.last().copied()
     }
@@ -241,6 +251,8 @@ impl DispatchTree {
         }
 
         let target = self.active_node();
+        target.tab_stop = source.tab_stop;
+        target.focus_trap = source.focus_trap;
         target.key_listeners = mem::take(&mut source.key_listeners);
         target.action_listeners = mem::take(&mut source.action_listeners);
         target.modifiers_changed_listeners = mem::take(&mut source.modifiers_changed_listeners);
@@ -345,6 +357,43 @@ impl DispatchTree {
             });
     }
 
+    /// Returns the focusable tab stops that tabbing from the given focused element moves
+    /// between, in the order that they were painted. When the focused element is within a
+    /// focus trap, only the tab stops within the innermost trap are returned.
+    pub fn tab_stops(&self, focused: Option<FocusId>) -> Vec<FocusId> {
+        let focus_trap = focused
+            .and_then(|focused| self.focusable_node_id(focused))
+            .and_then(|focused_node_id| {
+                self.dispatch_path(focused_node_id)
+                    .into_iter()
+                    .rev()
+                    .find(|node_id| self.nodes[node_id.0].focus_trap)
+            });
+
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.tab_stop)
+            .filter(|(node_id, _)| {
+                focus_trap.map_or(true, |focus_trap| {
+                    self.is_ancestor_or_self(focus_trap, DispatchNodeId(*node_id))
+                })
+            })
+            .filter_map(|(_, node)| node.focus_id)
+            .collect()
+    }
+
+    fn is_ancestor_or_self(&self, ancestor: DispatchNodeId, node_id: DispatchNodeId) -> bool {
+        let mut current_node_id = Some(node_id);
+        while let Some(node_id) = current_node_id {
+            if node_id == ancestor {
+                return true;
+            }
+            current_node_id = self.nodes[node_id.0].parent;
+        }
+        false
+    }
+
     pub fn focus_contains(&self, parent: FocusId, child: FocusId) -> bool {
         if parent == child {
             return true;
@@ -540,7 +589,9 @@ impl DispatchTree {
 mod tests {
     use std::{cell::RefCell, rc::Rc};
 
-    use crate::{Action, ActionRegistry, DispatchTree, KeyBinding, KeyContext, Keymap};
+    use slotmap::SlotMap;
+
+    use crate::{Action, ActionRegistry, DispatchTree, FocusId, KeyBinding, KeyContext, Keymap};
 
     #[derive(PartialEq, Eq)]
     struct TestAction;
@@ -605,4 +656,37 @@ mod tests {
 
         assert!(keybinding[0].action.partial_eq(&TestAction))
     }
+
+    #[test]
+    fn test_tab_stops_within_focus_trap() {
+        let mut focus_ids = SlotMap::<FocusId, ()>::with_key();
+        let [root, outside, modal, first, second] = [(); 5].map(|_| focus_ids.insert(()));
+        let mut tree = DispatchTree::new(
+            Rc::new(RefCell::new(Keymap::default())),
+            Rc::new(ActionRegistry::default()),
+        );
+
+        tree.push_node();
+        tree.set_focus_id(root);
+        tree.push_node();
+        tree.set_focus_id(outside);
+        tree.set_tab_stop();
+        tree.pop_node();
+        tree.push_node();
+        tree.set_focus_id(modal);
+        tree.set_focus_trap();
+        for focus_id in [first, second] {
+            tree.push_node();
+            tree.set_focus_id(focus_id);
+            tree.set_tab_stop();
+            tree.pop_node();
+        }
+        tree.pop_node();
+        tree.pop_node();
+
+        assert_eq!(tree.tab_stops(Some(root)), [outside, first, second]);
+        assert_eq!(tree.tab_stops(None), [outside, first, second]);
+        assert_eq!(tree.tab_stops(Some(first)), [first, second]);
+        assert_eq!(tree.tab_stops(Some(modal)), [first, second]);
+    }
 }
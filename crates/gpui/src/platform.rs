@@ -23,21 +23,23 @@ mod test;
 mod windows;
 
 use crate::{
-    point, Action, AnyWindowHandle, AsyncWindowContext, BackgroundExecutor, Bounds, DevicePixels,
-    DispatchEventResult, Font, FontId, FontMetrics, FontRun, ForegroundExecutor, GlyphId, Keymap,
-    LineLayout, Pixels, PlatformInput, Point, RenderGlyphParams, RenderImageParams,
-    RenderSvgParams, Scene, SharedString, Size, Task, TaskLabel, WindowContext,
-    DEFAULT_WINDOW_SIZE,
+    point, AccessibilityTree, Action, AnyWindowHandle, AsyncWindowContext, BackgroundExecutor,
+    Bounds, DevicePixels, DispatchEventResult, Font, FontId, FontMetrics, FontRun,
+    ForegroundExecutor, GlyphId, Keymap, LineLayout, Pixels, PlatformInput, Point,
+    RenderGlyphParams, RenderImageParams, RenderSvgParams, Scene, SharedString, Size, Task,
+    TaskLabel, WindowContext, DEFAULT_WINDOW_SIZE,
 };
 use anyhow::Result;
 use async_task::Runnable;
 use futures::channel::oneshot;
 use parking::Unparker;
+use parking_lot::Mutex;
 use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
 use seahash::SeaHasher;
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicBool, Ordering::SeqCst};
 use std::time::{Duration, Instant};
 use std::{
     fmt::{self, Debug},
@@ -104,6 +106,30 @@ pub(crate) fn current_platform() -> Rc<dyn Platform> {
     Rc::new(WindowsPlatform::new())
 }
 
+static SOFTWARE_RENDERING: AtomicBool = AtomicBool::new(false);
+static ACTIVE_RENDERER: Mutex<Option<SharedString>> = Mutex::new(None);
+
+/// Makes windows opened from now on render without the GPU where the platform
+/// supports it, for machines whose graphics drivers can't be used.
+pub fn set_software_rendering(enabled: bool) {
+    SOFTWARE_RENDERING.store(enabled, SeqCst);
+}
+
+pub(crate) fn software_rendering() -> bool {
+    SOFTWARE_RENDERING.load(SeqCst)
+}
+
+/// Returns a description of the renderer used by the most recently opened window.
+pub fn active_renderer() -> Option<SharedString> {
+    ACTIVE_RENDERER.lock().clone()
+}
+
+pub(crate) fn set_active_renderer(renderer: impl Into<SharedString>) {
+    let renderer = renderer.into();
+    log::info!("rendering with {renderer}");
+    *ACTIVE_RENDERER.lock() = Some(renderer);
+}
+
 pub(crate) trait Platform: 'static {
     fn background_executor(&self) -> BackgroundExecutor;
     fn foreground_executor(&self) -> ForegroundExecutor;
@@ -164,6 +190,9 @@ pub(crate) trait Platform: 'static {
 
     fn set_cursor_style(&self, style: CursorStyle);
     fn should_auto_hide_scrollbars(&self) -> bool;
+    fn should_reduce_motion(&self) -> bool {
+        false
+    }
 
     #[cfg(target_os = "linux")]
     fn write_to_primary(&self, item: ClipboardItem);
@@ -250,6 +279,11 @@ pub(crate) trait PlatformWindow: HasWindowHandle + HasDisplayHandle {
     fn on_appearance_changed(&self, callback: Box<dyn FnMut()>);
     fn draw(&self, scene: &Scene);
     fn completed_frame(&self) {}
+    fn accessibility_enabled(&self) -> bool {
+        false
+    }
+    fn update_accessibility_tree(&self, _tree: &AccessibilityTree) {}
+    fn announce(&self, _message: &str) {}
     fn sprite_atlas(&self) -> Arc<dyn PlatformAtlas>;
 
     #[cfg(target_os = "windows")]
@@ -458,7 +492,10 @@ impl PlatformInputHandler {
 
     fn bounds_for_range(&mut self, range_utf16: Range<usize>) -> Option<Bounds<Pixels>> {
         self.cx
-            .update(|cx| self.handler.bounds_for_range(range_utf16, cx))
+            .update(|cx| {
+                let bounds = self.handler.bounds_for_range(range_utf16, cx)?;
+                Some(bounds * cx.ui_scale())
+            })
             .ok()
             .flatten()
     }
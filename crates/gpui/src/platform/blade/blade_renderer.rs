@@ -2,6 +2,8 @@
 #![allow(irrefutable_let_patterns)]
 
 use super::{BladeAtlas, PATH_TEXTURE_FORMAT};
+#[cfg(not(target_os = "macos"))]
+use crate::platform::{set_active_renderer, set_software_rendering, software_rendering};
 use crate::{
     AtlasTextureKind, AtlasTile, Bounds, ContentMask, DevicePixels, Hsla, MonochromeSprite, Path,
     PathId, PathVertex, PolychromeSprite, PrimitiveBatch, Quad, ScaledPixels, Scene, Shadow, Size,
@@ -79,6 +81,90 @@ pub unsafe fn new_renderer(
     )
 }
 
+/// Creates the GPU context for a window. When software rendering was requested, or the GPU
+/// driver fails to initialize, the Vulkan loader is pointed at Mesa's CPU implementation
+/// (lavapipe) instead, if it's installed.
+#[cfg(not(target_os = "macos"))]
+pub unsafe fn init_gpu_context<W>(window: &W) -> anyhow::Result<gpu::Context>
+where
+    W: raw_window_handle::HasWindowHandle + raw_window_handle::HasDisplayHandle,
+{
+    let init = || {
+        gpu::Context::init_windowed(
+            window,
+            gpu::ContextDesc {
+                validation: false,
+                capture: false,
+                overlay: false,
+            },
+        )
+        .map_err(|e| anyhow::anyhow!("{:?}", e))
+    };
+
+    let software_driver = software_vulkan_driver();
+    if software_rendering() {
+        if let Some(driver) = &software_driver {
+            use_vulkan_driver(driver);
+            let context = init()?;
+            set_active_renderer("Vulkan (software)");
+            return Ok(context);
+        }
+        log::warn!("software rendering was requested, but no software Vulkan driver is installed");
+    }
+
+    match init() {
+        Ok(context) => {
+            set_active_renderer("Vulkan");
+            Ok(context)
+        }
+        Err(error) => {
+            let Some(driver) = software_driver else {
+                return Err(error);
+            };
+            log::error!(
+                "failed to initialize the GPU, falling back to software rendering: {error}"
+            );
+            use_vulkan_driver(&driver);
+            let context = init()?;
+            // Don't retry the GPU for every window that's opened after this one.
+            set_software_rendering(true);
+            set_active_renderer("Vulkan (software)");
+            Ok(context)
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn software_vulkan_driver() -> Option<std::path::PathBuf> {
+    [
+        "/usr/share/vulkan/icd.d",
+        "/usr/local/share/vulkan/icd.d",
+        "/etc/vulkan/icd.d",
+    ]
+    .into_iter()
+    .filter_map(|dir| std::fs::read_dir(dir).ok())
+    .flatten()
+    .filter_map(|entry| Some(entry.ok()?.path()))
+    .find(|path| {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .map_or(false, |name| name.starts_with("lvp_icd"))
+    })
+}
+
+#[cfg(all(not(target_os = "linux"), not(target_os = "macos")))]
+fn software_vulkan_driver() -> Option<std::path::PathBuf> {
+    None
+}
+
+/// The Vulkan loader reads these when the context is created, so they only
+/// affect contexts created after this is called.
+#[cfg(not(target_os = "macos"))]
+fn use_vulkan_driver(path: &std::path::Path) {
+    std::env::set_var("VK_DRIVER_FILES", path);
+    std::env::set_var("VK_ICD_FILENAMES", path);
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Pod, Zeroable)]
 struct GlobalParams {
@@ -45,9 +45,14 @@ use crate::platform::linux::xdg_desktop_portal::{Event as XDPEvent, XDPEventSour
 
 pub(super) const XINPUT_MASTER_DEVICE: u16 = 1;
 
+/// How often frames are requested for windows that are minimized or fully covered by
+/// other windows. Frames are still requested so that frame callbacks keep running.
+const HIDDEN_WINDOW_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
 pub(crate) struct WindowRef {
     window: X11WindowStatePtr,
     refresh_event_token: RegistrationToken,
+    hidden: bool,
 }
 
 impl WindowRef {
@@ -493,6 +498,19 @@ impl X11Client {
                 let window = self.get_window(event.window)?;
                 window.refresh();
             }
+            Event::VisibilityNotify(event) => {
+                let mut state = self.0.borrow_mut();
+                let window = state.windows.get_mut(&event.window)?;
+                window.hidden = event.state == xproto::Visibility::FULLY_OBSCURED;
+            }
+            Event::MapNotify(event) => {
+                let mut state = self.0.borrow_mut();
+                state.windows.get_mut(&event.window)?.hidden = false;
+            }
+            Event::UnmapNotify(event) => {
+                let mut state = self.0.borrow_mut();
+                state.windows.get_mut(&event.window)?.hidden = true;
+            }
             Event::FocusIn(event) => {
                 let window = self.get_window(event.event)?;
                 window.set_focused(true);
@@ -1005,6 +1023,15 @@ impl LinuxClient for X11Client {
                         )
                         .unwrap();
                     let _ = state.xcb_connection.flush().unwrap();
+                    if state
+                        .windows
+                        .get(&x_window)
+                        .map_or(false, |window| window.hidden)
+                    {
+                        return calloop::timer::TimeoutAction::ToDuration(
+                            HIDDEN_WINDOW_REFRESH_INTERVAL,
+                        );
+                    }
                     // Take into account that some frames have been skipped
                     let now = Instant::now();
                     while instant < now {
@@ -1018,6 +1045,7 @@ impl LinuxClient for X11Client {
         let window_ref = WindowRef {
             window: window.0.clone(),
             refresh_event_token,
+            hidden: false,
         };
 
         state.windows.insert(x_window, window_ref);
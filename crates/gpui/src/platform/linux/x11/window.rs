@@ -1,7 +1,7 @@
 use anyhow::Context;
 
 use crate::{
-    platform::blade::{BladeRenderer, BladeSurfaceConfig},
+    platform::blade::{init_gpu_context, BladeRenderer, BladeSurfaceConfig},
     px, size, AnyWindowHandle, Bounds, DevicePixels, ForegroundExecutor, Modifiers, Pixels,
     PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler, PlatformWindow, Point,
     PromptLevel, Scene, Size, WindowAppearance, WindowBackgroundAppearance, WindowBounds,
@@ -263,6 +263,7 @@ impl X11WindowState {
             .colormap(colormap)
             .event_mask(
                 xproto::EventMask::EXPOSURE
+                    | xproto::EventMask::VISIBILITY_CHANGE
                     | xproto::EventMask::STRUCTURE_NOTIFY
                     | xproto::EventMask::FOCUS_CHANGE
                     | xproto::EventMask::KEY_PRESS
@@ -367,19 +368,7 @@ impl X11WindowState {
             window_id: x_window,
             visual_id: visual.id,
         };
-        let gpu = Arc::new(
-            unsafe {
-                gpu::Context::init_windowed(
-                    &raw,
-                    gpu::ContextDesc {
-                        validation: false,
-                        capture: false,
-                        overlay: false,
-                    },
-                )
-            }
-            .map_err(|e| anyhow::anyhow!("{:?}", e))?,
-        );
+        let gpu = Arc::new(unsafe { init_gpu_context(&raw) }?);
 
         let config = BladeSurfaceConfig {
             // Note: this has to be done after the GPU init, or otherwise
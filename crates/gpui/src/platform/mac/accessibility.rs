@@ -0,0 +1,226 @@
+//! Exposes a window's [`AccessibilityTree`] to VoiceOver and other assistive technologies,
+//! by mirroring its nodes with `NSAccessibilityElement`s that are children of the window's view.
+
+use super::{ns_string, NSRange};
+use crate::{point, px, AccessibilityNodeId, AccessibilityRole, AccessibilityTree, Bounds, Pixels};
+use cocoa::{
+    appkit::NSView,
+    base::{id, nil},
+    foundation::{NSArray, NSDictionary, NSInteger, NSPoint, NSRect, NSSize},
+};
+use collections::HashMap;
+use objc::{
+    class, msg_send,
+    runtime::{NO, YES},
+    sel, sel_impl,
+};
+
+#[link(name = "AppKit", kind = "framework")]
+extern "C" {
+    fn NSAccessibilityPostNotification(element: id, notification: id);
+    fn NSAccessibilityPostNotificationWithUserInfo(element: id, notification: id, user_info: id);
+}
+
+// The values of AppKit's `NSAccessibility` string constants.
+const FOCUSED_ELEMENT_CHANGED_NOTIFICATION: &str = "AXFocusedUIElementChanged";
+const VALUE_CHANGED_NOTIFICATION: &str = "AXValueChanged";
+const SELECTED_TEXT_CHANGED_NOTIFICATION: &str = "AXSelectedTextChanged";
+const ANNOUNCEMENT_REQUESTED_NOTIFICATION: &str = "AXAnnouncementRequested";
+const ANNOUNCEMENT_KEY: &str = "AXAnnouncementKey";
+const PRIORITY_KEY: &str = "AXPriorityKey";
+const PRIORITY_HIGH: NSInteger = 90;
+
+#[derive(Default)]
+pub(crate) struct MacAccessibility {
+    elements: HashMap<AccessibilityNodeId, id>,
+    tree: AccessibilityTree,
+}
+
+impl MacAccessibility {
+    /// Updates the elements of the view to match the given tree, and notifies assistive
+    /// technologies of changes to the focused element.
+    pub(crate) unsafe fn update(&mut self, view: id, tree: &AccessibilityTree) {
+        let mut elements = HashMap::default();
+        for node in tree.nodes() {
+            let element = self.elements.remove(&node.id).unwrap_or_else(|| {
+                let element: id = msg_send![class!(NSAccessibilityElement), new];
+                element
+            });
+            elements.insert(node.id, element);
+
+            let parent = node
+                .parent
+                .and_then(|parent| elements.get(&parent).copied())
+                .unwrap_or(view);
+            let _: () = msg_send![element, setAccessibilityParent: parent];
+            let _: () = msg_send![element, setAccessibilityRole: ns_string(role(node.role))];
+            let label = node.label.as_ref().map_or(nil, |label| ns_string(label));
+            let _: () = msg_send![element, setAccessibilityLabel: label];
+            let value = match (&node.value, node.role) {
+                (Some(value), _) => ns_string(value),
+                (None, AccessibilityRole::StaticText) => label,
+                (None, _) => nil,
+            };
+            let _: () = msg_send![element, setAccessibilityValue: value];
+            if let Some(value) = &node.value {
+                let length = value.encode_utf16().count();
+                let _: () =
+                    msg_send![element, setAccessibilityNumberOfCharacters: length as NSInteger];
+            }
+            if let Some(selected_range) = node.selected_range.clone() {
+                let _: () = msg_send![
+                    element,
+                    setAccessibilitySelectedTextRange: NSRange::from(selected_range)
+                ];
+            }
+            let _: () = msg_send![
+                element,
+                setAccessibilityFocused: if node.focused { YES } else { NO }
+            ];
+            let _: () = msg_send![element, setAccessibilityFrame: screen_frame(view, node.bounds)];
+        }
+
+        for node in tree.nodes() {
+            let children = tree
+                .children(Some(node.id))
+                .map(|child| elements[&child.id])
+                .collect::<Vec<_>>();
+            let children = NSArray::arrayWithObjects(nil, &children);
+            let _: () = msg_send![elements[&node.id], setAccessibilityChildren: children];
+        }
+        let roots = tree
+            .children(None)
+            .map(|node| elements[&node.id])
+            .collect::<Vec<_>>();
+        let _: () =
+            msg_send![view, setAccessibilityChildren: NSArray::arrayWithObjects(nil, &roots)];
+
+        let old_focused = self.tree.focused().cloned();
+        if let Some(focused) = tree.focused() {
+            let element = elements[&focused.id];
+            match old_focused {
+                Some(old_focused) if old_focused.id == focused.id => {
+                    if old_focused.value != focused.value {
+                        NSAccessibilityPostNotification(
+                            element,
+                            ns_string(VALUE_CHANGED_NOTIFICATION),
+                        );
+                    }
+                    if old_focused.selected_range != focused.selected_range {
+                        NSAccessibilityPostNotification(
+                            element,
+                            ns_string(SELECTED_TEXT_CHANGED_NOTIFICATION),
+                        );
+                    }
+                }
+                _ => NSAccessibilityPostNotification(
+                    element,
+                    ns_string(FOCUSED_ELEMENT_CHANGED_NOTIFICATION),
+                ),
+            }
+        }
+
+        for (_, element) in self.elements.drain() {
+            let _: () = msg_send![element, release];
+        }
+        self.elements = elements;
+        self.tree = tree.clone();
+    }
+
+    /// Updates the screen frames of the elements, after the window has moved.
+    pub(crate) unsafe fn update_frames(&self, view: id) {
+        for node in self.tree.nodes() {
+            let _: () = msg_send![
+                self.elements[&node.id],
+                setAccessibilityFrame: screen_frame(view, node.bounds)
+            ];
+        }
+    }
+
+    /// The element that has the keyboard focus, or the view when there is none.
+    pub(crate) fn focused_element(&self, view: id) -> id {
+        self.tree
+            .focused()
+            .map_or(view, |focused| self.elements[&focused.id])
+    }
+
+    /// The innermost element at the given point on the screen, or the view when there is none.
+    pub(crate) unsafe fn hit_test(&self, view: id, screen_point: NSPoint) -> id {
+        let window: id = msg_send![view, window];
+        let window_rect: NSRect = msg_send![
+            window,
+            convertRectFromScreen: NSRect::new(screen_point, NSSize::new(0., 0.))
+        ];
+        let view_point: NSPoint = msg_send![view, convertPoint: window_rect.origin fromView: nil];
+        let position = point(
+            px(view_point.x as f32),
+            px((NSView::frame(view).size.height - view_point.y) as f32),
+        );
+        self.tree
+            .nodes()
+            .iter()
+            .rev()
+            .find(|node| node.bounds.contains(&position))
+            .map_or(view, |node| self.elements[&node.id])
+    }
+
+    /// Asks assistive technologies to announce the given message.
+    pub(crate) unsafe fn announce(message: &str) {
+        let app: id = msg_send![class!(NSApplication), sharedApplication];
+        let priority: id = msg_send![class!(NSNumber), numberWithInteger: PRIORITY_HIGH];
+        let user_info = NSDictionary::dictionaryWithObjects_forKeys_(
+            nil,
+            NSArray::arrayWithObjects(nil, &[ns_string(message), priority]),
+            NSArray::arrayWithObjects(nil, &[ns_string(ANNOUNCEMENT_KEY), ns_string(PRIORITY_KEY)]),
+        );
+        NSAccessibilityPostNotificationWithUserInfo(
+            app,
+            ns_string(ANNOUNCEMENT_REQUESTED_NOTIFICATION),
+            user_info,
+        );
+    }
+}
+
+impl Drop for MacAccessibility {
+    fn drop(&mut self) {
+        for (_, element) in self.elements.drain() {
+            unsafe {
+                let _: () = msg_send![element, release];
+            }
+        }
+    }
+}
+
+fn role(role: AccessibilityRole) -> &'static str {
+    match role {
+        AccessibilityRole::Group | AccessibilityRole::ListItem => "AXGroup",
+        AccessibilityRole::Button => "AXButton",
+        AccessibilityRole::CheckBox => "AXCheckBox",
+        AccessibilityRole::TextEditor => "AXTextArea",
+        AccessibilityRole::TextField => "AXTextField",
+        AccessibilityRole::StaticText => "AXStaticText",
+        AccessibilityRole::List => "AXList",
+        AccessibilityRole::Menu => "AXMenu",
+        AccessibilityRole::MenuItem => "AXMenuItem",
+        AccessibilityRole::Popover => "AXPopover",
+        AccessibilityRole::TabList => "AXTabGroup",
+        // Tabs are radio buttons in a tab group.
+        AccessibilityRole::Tab => "AXRadioButton",
+        AccessibilityRole::Toolbar => "AXToolbar",
+    }
+}
+
+/// Converts bounds in the view, whose origin is at its top left, to a frame on the screen.
+unsafe fn screen_frame(view: id, bounds: Bounds<Pixels>) -> NSRect {
+    let view_height = NSView::frame(view).size.height;
+    let view_rect = NSRect::new(
+        NSPoint::new(
+            bounds.origin.x.0 as f64,
+            view_height - (bounds.origin.y + bounds.size.height).0 as f64,
+        ),
+        NSSize::new(bounds.size.width.0 as f64, bounds.size.height.0 as f64),
+    );
+    let window_rect: NSRect = msg_send![view, convertRect: view_rect toView: nil];
+    let window: id = msg_send![view, window];
+    msg_send![window, convertRectToScreen: window_rect]
+}
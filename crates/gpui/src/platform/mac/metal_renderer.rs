@@ -1,4 +1,5 @@
 use super::metal_atlas::MetalAtlas;
+use crate::platform::{set_active_renderer, software_rendering};
 use crate::{
     point, size, AtlasTextureId, AtlasTextureKind, AtlasTile, Bounds, ContentMask, DevicePixels,
     Hsla, MonochromeSprite, Path, PathId, PathVertex, PolychromeSprite, PrimitiveBatch, Quad,
@@ -109,12 +110,13 @@ pub(crate) struct MetalRenderer {
 
 impl MetalRenderer {
     pub fn new(instance_buffer_pool: Arc<Mutex<InstanceBufferPool>>) -> Self {
-        let device: metal::Device = if let Some(device) = metal::Device::system_default() {
+        let device: metal::Device = if let Some(device) = Self::choose_device() {
             device
         } else {
             log::error!("unable to access a compatible graphics device");
             std::process::exit(1);
         };
+        set_active_renderer(format!("Metal ({})", device.name()));
 
         let layer = metal::MetalLayer::new();
         layer.set_device(&device);
@@ -250,6 +252,19 @@ impl MetalRenderer {
         }
     }
 
+    /// Metal has no CPU implementation, so when software rendering is requested, or there's
+    /// no default device (as can happen in VMs), the least demanding device is used instead.
+    fn choose_device() -> Option<metal::Device> {
+        if !software_rendering() {
+            if let Some(device) = metal::Device::system_default() {
+                return Some(device);
+            }
+        }
+        let mut devices = metal::Device::all();
+        devices.sort_by_key(|device| !device.is_low_power());
+        devices.into_iter().next()
+    }
+
     pub fn layer(&self) -> &metal::MetalLayerRef {
         &self.layer
     }
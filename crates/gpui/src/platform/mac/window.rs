@@ -1,10 +1,13 @@
-use super::{ns_string, renderer, MacDisplay, NSRange, NSStringExt};
+use super::{
+    accessibility::MacAccessibility, ns_string, renderer, MacDisplay, NSRange, NSStringExt,
+};
 use crate::{
-    platform::PlatformInputHandler, point, px, size, AnyWindowHandle, Bounds, DisplayLink,
-    ExternalPaths, FileDropEvent, ForegroundExecutor, KeyDownEvent, Keystroke, Modifiers,
-    ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels,
-    PlatformAtlas, PlatformDisplay, PlatformInput, PlatformWindow, Point, PromptLevel, Size, Timer,
-    WindowAppearance, WindowBackgroundAppearance, WindowBounds, WindowKind, WindowParams,
+    platform::PlatformInputHandler, point, px, size, AccessibilityTree, AnyWindowHandle, Bounds,
+    DisplayLink, ExternalPaths, FileDropEvent, ForegroundExecutor, KeyDownEvent, Keystroke,
+    Modifiers, ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
+    Pixels, PlatformAtlas, PlatformDisplay, PlatformInput, PlatformWindow, Point, PromptLevel,
+    Size, Timer, WindowAppearance, WindowBackgroundAppearance, WindowBounds, WindowKind,
+    WindowParams,
 };
 use block::ConcreteBlock;
 use cocoa::{
@@ -226,6 +229,15 @@ unsafe fn build_classes() {
             accepts_first_mouse as extern "C" fn(&Object, Sel, id) -> BOOL,
         );
 
+        decl.add_method(
+            sel!(accessibilityFocusedUIElement),
+            accessibility_focused_ui_element as extern "C" fn(&Object, Sel) -> id,
+        );
+        decl.add_method(
+            sel!(accessibilityHitTest:),
+            accessibility_hit_test as extern "C" fn(&Object, Sel, NSPoint) -> id,
+        );
+
         decl.register()
     };
 }
@@ -344,6 +356,7 @@ struct MacWindowState {
     // Whether the next left-mouse click is also the focusing click.
     first_mouse: bool,
     fullscreen_restore_bounds: Bounds<Pixels>,
+    accessibility: MacAccessibility,
 }
 
 impl MacWindowState {
@@ -623,6 +636,7 @@ impl MacWindow {
                 external_files_dragged: false,
                 first_mouse: false,
                 fullscreen_restore_bounds: Bounds::default(),
+                accessibility: MacAccessibility::default(),
             })));
 
             (*native_window).set_ivar(
@@ -1090,6 +1104,24 @@ impl PlatformWindow for MacWindow {
     fn should_render_window_controls(&self) -> bool {
         false
     }
+
+    fn accessibility_enabled(&self) -> bool {
+        unsafe {
+            let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+            let voice_over_enabled: BOOL = msg_send![workspace, isVoiceOverEnabled];
+            voice_over_enabled == YES
+        }
+    }
+
+    fn update_accessibility_tree(&self, tree: &AccessibilityTree) {
+        let mut this = self.0.lock();
+        let view = this.native_view.as_ptr();
+        unsafe { this.accessibility.update(view, tree) }
+    }
+
+    fn announce(&self, message: &str) {
+        unsafe { MacAccessibility::announce(message) }
+    }
 }
 
 impl rwh::HasWindowHandle for MacWindow {
@@ -1465,6 +1497,9 @@ extern "C" fn window_will_enter_fullscreen(this: &Object, _: Sel, _: id) {
 extern "C" fn window_did_move(this: &Object, _: Sel, _: id) {
     let window_state = unsafe { get_window_state(this) };
     let mut lock = window_state.as_ref().lock();
+    unsafe {
+        lock.accessibility.update_frames(lock.native_view.as_ptr());
+    }
     if let Some(mut callback) = lock.moved_callback.take() {
         drop(lock);
         callback();
@@ -1942,3 +1977,19 @@ unsafe fn display_id_for_screen(screen: id) -> CGDirectDisplayID {
     let screen_number: NSUInteger = msg_send![screen_number, unsignedIntegerValue];
     screen_number as CGDirectDisplayID
 }
+
+extern "C" fn accessibility_focused_ui_element(this: &Object, _: Sel) -> id {
+    let window_state = unsafe { get_window_state(this) };
+    let lock = window_state.lock();
+    lock.accessibility
+        .focused_element(lock.native_view.as_ptr())
+}
+
+extern "C" fn accessibility_hit_test(this: &Object, _: Sel, point: NSPoint) -> id {
+    let window_state = unsafe { get_window_state(this) };
+    let lock = window_state.lock();
+    unsafe {
+        lock.accessibility
+            .hit_test(lock.native_view.as_ptr(), point)
+    }
+}
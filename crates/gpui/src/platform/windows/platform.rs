@@ -104,6 +104,10 @@ impl WindowsPlatform {
 
     fn redraw_all(&self) {
         for handle in self.raw_window_handles.read().iter() {
+            // Minimized windows aren't visible, and get repainted when they're restored.
+            if unsafe { IsIconic(*handle) }.as_bool() {
+                continue;
+            }
             unsafe {
                 RedrawWindow(
                     *handle,
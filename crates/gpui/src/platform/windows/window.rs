@@ -958,24 +958,12 @@ mod windows_renderer {
 
     use crate::{
         get_window_long,
-        platform::blade::{BladeRenderer, BladeSurfaceConfig},
+        platform::blade::{init_gpu_context, BladeRenderer, BladeSurfaceConfig},
     };
 
     pub(super) fn windows_renderer(hwnd: HWND, transparent: bool) -> BladeRenderer {
         let raw = RawWindow { hwnd: hwnd.0 };
-        let gpu: Arc<gpu::Context> = Arc::new(
-            unsafe {
-                gpu::Context::init_windowed(
-                    &raw,
-                    gpu::ContextDesc {
-                        validation: false,
-                        capture: false,
-                        overlay: false,
-                    },
-                )
-            }
-            .unwrap(),
-        );
+        let gpu: Arc<gpu::Context> = Arc::new(unsafe { init_gpu_context(&raw) }.unwrap());
         let config = BladeSurfaceConfig {
             size: gpu::Extent::default(),
             transparent,
@@ -530,7 +530,8 @@ pub struct LineWrapperHandle {
 impl Drop for LineWrapperHandle {
     fn drop(&mut self) {
         let mut state = self.text_system.wrapper_pool.lock();
-        let wrapper = self.wrapper.take().unwrap();
+        let mut wrapper = self.wrapper.take().unwrap();
+        wrapper.letter_spacing = px(0.);
         state
             .get_mut(&FontIdWithSize {
                 font_id: wrapper.font_id,
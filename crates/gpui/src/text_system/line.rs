@@ -43,6 +43,14 @@ impl ShapedLine {
         self.layout.len
     }
 
+    /// Adds the given amount of space after each character of this line.
+    pub fn with_letter_spacing(mut self, letter_spacing: Pixels) -> Self {
+        if letter_spacing != Pixels::ZERO {
+            self.layout = Arc::new(self.layout.with_letter_spacing(&self.text, letter_spacing));
+        }
+        self
+    }
+
     /// Paint the line of text to the window.
     pub fn paint(
         &self,
@@ -115,6 +115,38 @@ impl LineLayout {
         None
     }
 
+    /// Returns a copy of this layout with the given amount of space added after each
+    /// character of `text`, which must be the text that this layout was shaped from.
+    pub fn with_letter_spacing(&self, text: &str, letter_spacing: Pixels) -> LineLayout {
+        let mut chars = text.char_indices().peekable();
+        let mut preceding_chars = 0;
+        let mut runs = Vec::with_capacity(self.runs.len());
+        for run in &self.runs {
+            let mut glyphs = SmallVec::with_capacity(run.glyphs.len());
+            for glyph in &run.glyphs {
+                while chars.next_if(|(ix, _)| *ix < glyph.index).is_some() {
+                    preceding_chars += 1;
+                }
+                let mut glyph = glyph.clone();
+                glyph.position.x += letter_spacing * preceding_chars as f32;
+                glyphs.push(glyph);
+            }
+            runs.push(ShapedRun {
+                font_id: run.font_id,
+                glyphs,
+            });
+        }
+
+        LineLayout {
+            font_size: self.font_size,
+            width: self.width + letter_spacing * text.chars().count() as f32,
+            ascent: self.ascent,
+            descent: self.descent,
+            runs,
+            len: self.len,
+        }
+    }
+
     fn compute_wrap_boundaries(
         &self,
         text: &str,
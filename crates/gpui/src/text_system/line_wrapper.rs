@@ -7,6 +7,7 @@ pub struct LineWrapper {
     platform_text_system: Arc<dyn PlatformTextSystem>,
     pub(crate) font_id: FontId,
     pub(crate) font_size: Pixels,
+    pub(crate) letter_spacing: Pixels,
     cached_ascii_char_widths: [Option<Pixels>; 128],
     cached_other_char_widths: HashMap<char, Pixels>,
 }
@@ -24,11 +25,17 @@ impl LineWrapper {
             platform_text_system: text_system,
             font_id,
             font_size,
+            letter_spacing: px(0.),
             cached_ascii_char_widths: [None; 128],
             cached_other_char_widths: HashMap::default(),
         }
     }
 
+    /// Set the amount of space added after each character, like [`crate::ShapedLine::with_letter_spacing`].
+    pub fn set_letter_spacing(&mut self, letter_spacing: Pixels) {
+        self.letter_spacing = letter_spacing;
+    }
+
     /// Wrap a line of text to the given width with this wrapper's font and font size.
     pub fn wrap_line<'a>(
         &'a mut self,
@@ -92,6 +99,11 @@ impl LineWrapper {
 
     #[inline(always)]
     fn width_for_char(&mut self, c: char) -> Pixels {
+        self.advance_for_char(c) + self.letter_spacing
+    }
+
+    #[inline(always)]
+    fn advance_for_char(&mut self, c: char) -> Pixels {
         if (c as u32) < 128 {
             if let Some(cached_width) = self.cached_ascii_char_widths[c as usize] {
                 cached_width
@@ -219,6 +231,31 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_wrap_line_with_letter_spacing() {
+        let dispatcher = TestDispatcher::new(StdRng::seed_from_u64(0));
+        let cx = TestAppContext::new(dispatcher, None);
+        cx.text_system()
+            .add_fonts(vec![std::fs::read(
+                "../../assets/fonts/zed-mono/zed-mono-extended.ttf",
+            )
+            .unwrap()
+            .into()])
+            .unwrap();
+        let id = cx.text_system().font_id(&font("Zed Mono")).unwrap();
+
+        cx.update(|cx| {
+            let text_system = cx.text_system().clone();
+            let mut wrapper =
+                LineWrapper::new(id, px(16.), text_system.platform_text_system.clone());
+            wrapper.set_letter_spacing(px(6.4));
+            assert_eq!(
+                wrapper.wrap_line("aaaaaaaaaa", px(72.)).collect::<Vec<_>>(),
+                &[Boundary::new(4, 0), Boundary::new(8, 0)],
+            );
+        });
+    }
+
     // For compatibility with the test macro
     #[cfg(target_os = "macos")]
     use crate as gpui;
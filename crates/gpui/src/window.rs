@@ -1,19 +1,20 @@
 use crate::{
-    hash, point, prelude::*, px, size, transparent_black, Action, AnyDrag, AnyElement, AnyTooltip,
-    AnyView, AppContext, Arena, Asset, AsyncWindowContext, AvailableSpace, Bounds, BoxShadow,
-    Context, Corners, CursorStyle, DevicePixels, DispatchActionListener, DispatchNodeId,
-    DispatchTree, DisplayId, Edges, Effect, Entity, EntityId, EventEmitter, FileDropEvent, Flatten,
-    FontId, Global, GlobalElementId, GlyphId, Hsla, ImageData, InputHandler, IsZero, KeyBinding,
-    KeyContext, KeyDownEvent, KeyEvent, KeyMatch, KeymatchResult, Keystroke, KeystrokeEvent,
-    LayoutId, LineLayoutIndex, Model, ModelContext, Modifiers, ModifiersChangedEvent,
-    MonochromeSprite, MouseButton, MouseEvent, MouseMoveEvent, MouseUpEvent, Path, Pixels,
-    PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler, PlatformWindow, Point,
-    PolychromeSprite, PromptLevel, Quad, Render, RenderGlyphParams, RenderImageParams,
-    RenderSvgParams, ScaledPixels, Scene, Shadow, SharedString, Size, StrikethroughStyle, Style,
-    SubscriberSet, Subscription, TaffyLayoutEngine, Task, TextStyle, TextStyleRefinement,
-    TransformationMatrix, Underline, UnderlineStyle, View, VisualContext, WeakView,
-    WindowAppearance, WindowBackgroundAppearance, WindowBounds, WindowOptions, WindowParams,
-    WindowTextSystem, SUBPIXEL_VARIANTS,
+    hash, point, prelude::*, px, size, transparent_black, AccessibilityNode, AccessibilityNodeId,
+    AccessibilityNodeIds, AccessibilityNodeProperties, AccessibilityTree, Action, AnyDrag,
+    AnyElement, AnyTooltip, AnyView, AppContext, Arena, Asset, AsyncWindowContext, AvailableSpace,
+    Bounds, BoxShadow, Context, Corners, CursorStyle, DevicePixels, DispatchActionListener,
+    DispatchNodeId, DispatchTree, DisplayId, Edges, Effect, Entity, EntityId, EventEmitter,
+    FileDropEvent, Flatten, FontId, Global, GlobalElementId, GlyphId, Hsla, ImageData,
+    InputHandler, IsZero, KeyBinding, KeyContext, KeyDownEvent, KeyEvent, KeyMatch, KeymatchResult,
+    Keystroke, KeystrokeEvent, LayoutId, LineLayoutIndex, Model, ModelContext, Modifiers,
+    ModifiersChangedEvent, MonochromeSprite, MouseButton, MouseEvent, MouseMoveEvent, MouseUpEvent,
+    Path, Pixels, PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler,
+    PlatformWindow, Point, PolychromeSprite, PromptLevel, Quad, Render, RenderGlyphParams,
+    RenderImageParams, RenderSvgParams, ScaledPixels, Scene, ScrollDelta, Shadow, SharedString,
+    Size, StrikethroughStyle, Style, SubscriberSet, Subscription, TaffyLayoutEngine, Task,
+    TextStyle, TextStyleRefinement, TransformationMatrix, Underline, UnderlineStyle, View,
+    VisualContext, WeakView, WindowAppearance, WindowBackgroundAppearance, WindowBounds,
+    WindowOptions, WindowParams, WindowTextSystem, SUBPIXEL_VARIANTS,
 };
 use anyhow::{anyhow, Context as _, Result};
 use collections::{FxHashMap, FxHashSet};
@@ -48,8 +49,10 @@ use util::post_inc;
 use util::{measure, ResultExt};
 use uuid::Uuid;
 
+mod frame_timings;
 mod prompts;
 
+pub use frame_timings::*;
 pub use prompts::*;
 
 pub(crate) const DEFAULT_WINDOW_SIZE: Size<Pixels> = size(px(1024.), px(700.));
@@ -397,6 +400,7 @@ pub(crate) struct Frame {
     pub(crate) input_handlers: Vec<Option<PlatformInputHandler>>,
     pub(crate) tooltip_requests: Vec<Option<TooltipRequest>>,
     pub(crate) cursor_styles: Vec<CursorStyleRequest>,
+    pub(crate) accessibility_nodes: Vec<AccessibilityNode>,
     #[cfg(any(test, feature = "test-support"))]
     pub(crate) debug_bounds: FxHashMap<String, Bounds<Pixels>>,
 }
@@ -417,6 +421,7 @@ pub(crate) struct PaintIndex {
     mouse_listeners_index: usize,
     input_handlers_index: usize,
     cursor_styles_index: usize,
+    accessibility_nodes_index: usize,
     accessed_element_states_index: usize,
     line_layout_index: LineLayoutIndex,
 }
@@ -436,6 +441,7 @@ impl Frame {
             input_handlers: Vec::new(),
             tooltip_requests: Vec::new(),
             cursor_styles: Vec::new(),
+            accessibility_nodes: Vec::new(),
 
             #[cfg(any(test, feature = "test-support"))]
             debug_bounds: FxHashMap::default(),
@@ -451,6 +457,7 @@ impl Frame {
         self.input_handlers.clear();
         self.tooltip_requests.clear();
         self.cursor_styles.clear();
+        self.accessibility_nodes.clear();
         self.hitboxes.clear();
         self.deferred_draws.clear();
     }
@@ -526,6 +533,8 @@ pub struct Window {
     mouse_hit_test: HitTest,
     modifiers: Modifiers,
     scale_factor: f32,
+    ui_scale: f32,
+    requested_ui_scale: f32,
     bounds_observers: SubscriberSet<(), AnyObserver>,
     appearance: WindowAppearance,
     appearance_observers: SubscriberSet<(), AnyObserver>,
@@ -541,6 +550,17 @@ pub struct Window {
     pending_input: Option<PendingInput>,
     pending_input_observers: SubscriberSet<(), AnyObserver>,
     prompt: Option<RenderablePromptHandle>,
+    frame_timings: FrameTimings,
+    /// The layout and paint durations of the frame that was drawn last, which
+    /// are recorded once the frame is presented.
+    pending_draw_durations: Option<(Duration, Duration)>,
+    /// Whether assistive technologies are in use, as of the frame being drawn.
+    accessibility_enabled: bool,
+    /// The nodes that contain the accessibility nodes being painted.
+    accessibility_stack: Vec<AccessibilityNodeId>,
+    accessibility_node_ids: AccessibilityNodeIds,
+    /// The accessibility tree that was last given to the platform.
+    accessibility_tree: AccessibilityTree,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -696,8 +716,10 @@ impl Window {
                     measure("frame duration", || {
                         handle
                             .update(&mut cx, |_, cx| {
+                                let frame_start = Instant::now();
                                 cx.draw();
                                 cx.present();
+                                cx.record_frame_timing(frame_start);
                             })
                             .log_err();
                     })
@@ -798,6 +820,8 @@ impl Window {
             mouse_hit_test: HitTest::default(),
             modifiers,
             scale_factor,
+            ui_scale: 1.,
+            requested_ui_scale: 1.,
             bounds_observers: SubscriberSet::new(),
             appearance,
             appearance_observers: SubscriberSet::new(),
@@ -813,6 +837,12 @@ impl Window {
             pending_input: None,
             pending_input_observers: SubscriberSet::new(),
             prompt: None,
+            frame_timings: FrameTimings::default(),
+            pending_draw_durations: None,
+            accessibility_enabled: false,
+            accessibility_stack: Vec::new(),
+            accessibility_node_ids: AccessibilityNodeIds::default(),
+            accessibility_tree: AccessibilityTree::default(),
         })
     }
     fn new_focus_listener(
@@ -1134,8 +1164,7 @@ impl<'a> WindowContext<'a> {
     }
 
     fn bounds_changed(&mut self) {
-        self.window.scale_factor = self.window.platform_window.scale_factor();
-        self.window.viewport_size = self.window.platform_window.content_size();
+        self.update_scale_factor_and_viewport_size();
         self.window.display_id = self
             .window
             .platform_window
@@ -1150,6 +1179,12 @@ impl<'a> WindowContext<'a> {
             .retain(&(), |callback| callback(self));
     }
 
+    fn update_scale_factor_and_viewport_size(&mut self) {
+        let ui_scale = self.window.ui_scale;
+        self.window.scale_factor = self.window.platform_window.scale_factor() * ui_scale;
+        self.window.viewport_size = self.window.platform_window.content_size() * (1. / ui_scale);
+    }
+
     /// Returns the bounds of the current window in the global coordinate space, which could span across multiple displays.
     pub fn bounds(&self) -> Bounds<Pixels> {
         self.window.platform_window.bounds()
@@ -1248,6 +1283,21 @@ impl<'a> WindowContext<'a> {
         self.window.scale_factor
     }
 
+    /// How much everything in the window is scaled, on top of the display's scale factor.
+    /// Zooming in on the whole window makes each of its logical pixels bigger, so that
+    /// there are fewer of them in the viewport.
+    pub fn ui_scale(&self) -> f32 {
+        self.window.ui_scale
+    }
+
+    /// Sets how much everything in the window is scaled, from the next frame on.
+    pub fn set_ui_scale(&mut self, ui_scale: f32) {
+        if self.window.requested_ui_scale != ui_scale {
+            self.window.requested_ui_scale = ui_scale;
+            self.refresh();
+        }
+    }
+
     /// The size of an em for the base font of the application. Adjusting this value allows the
     /// UI to scale, just like zooming a web page.
     pub fn rem_size(&self) -> Pixels {
@@ -1336,6 +1386,22 @@ impl<'a> WindowContext<'a> {
         self.window.modifiers
     }
 
+    /// The timings of the frames that this window drew most recently.
+    pub fn frame_timings(&self) -> &FrameTimings {
+        &self.window.frame_timings
+    }
+
+    fn record_frame_timing(&mut self, frame_start: Instant) {
+        if let Some((layout, paint)) = self.window.pending_draw_durations.take() {
+            self.window.frame_timings.push(FrameTiming {
+                start: frame_start,
+                layout,
+                paint,
+                total: frame_start.elapsed(),
+            });
+        }
+    }
+
     fn complete_frame(&self) {
         self.window.platform_window.completed_frame();
     }
@@ -1344,8 +1410,20 @@ impl<'a> WindowContext<'a> {
     /// the contents of the new [Scene], use [present].
     #[profiling::function]
     pub fn draw(&mut self) {
+        let draw_start = Instant::now();
         self.window.dirty.set(false);
         self.window.requested_autoscroll = None;
+        let accessibility_enabled = self.window.platform_window.accessibility_enabled();
+        if accessibility_enabled != self.window.accessibility_enabled {
+            // Cached views painted without accessibility nodes, or with stale ones.
+            self.window.accessibility_enabled = accessibility_enabled;
+            self.window.refreshing = true;
+        }
+        self.window.accessibility_node_ids.clear();
+        if self.window.ui_scale != self.window.requested_ui_scale {
+            self.window.ui_scale = self.window.requested_ui_scale;
+            self.update_scale_factor_and_viewport_size();
+        }
 
         // Restore the previously-used input handler.
         if let Some(input_handler) = self.window.platform_window.take_input_handler() {
@@ -1355,7 +1433,9 @@ impl<'a> WindowContext<'a> {
                 .push(Some(input_handler));
         }
 
-        self.draw_roots();
+        let layout_duration = self.draw_roots();
+        let paint_duration = draw_start.elapsed().saturating_sub(layout_duration);
+        self.window.pending_draw_durations = Some((layout_duration, paint_duration));
         self.window.dirty_views.clear();
 
         self.window
@@ -1393,6 +1473,7 @@ impl<'a> WindowContext<'a> {
         let previous_window_active = self.window.rendered_frame.window_active;
         mem::swap(&mut self.window.rendered_frame, &mut self.window.next_frame);
         self.window.next_frame.clear();
+        self.update_accessibility_tree();
         let current_focus_path = self.window.rendered_frame.focus_path();
         let current_window_active = self.window.rendered_frame.window_active;
 
@@ -1439,7 +1520,9 @@ impl<'a> WindowContext<'a> {
         profiling::finish_frame!();
     }
 
-    fn draw_roots(&mut self) {
+    /// Draws the window's root elements, returning the time spent before they were painted.
+    fn draw_roots(&mut self) -> Duration {
+        let prepaint_start = Instant::now();
         self.window.draw_phase = DrawPhase::Prepaint;
         self.window.tooltip_bounds.take();
 
@@ -1473,6 +1556,7 @@ impl<'a> WindowContext<'a> {
         self.window.mouse_hit_test = self.window.next_frame.hit_test(self.window.mouse_position);
 
         // Now actually paint the elements.
+        let layout_duration = prepaint_start.elapsed();
         self.window.draw_phase = DrawPhase::Paint;
         root_element.paint(self);
 
@@ -1485,6 +1569,8 @@ impl<'a> WindowContext<'a> {
         } else if let Some(mut tooltip_element) = tooltip_element {
             tooltip_element.paint(self);
         }
+
+        layout_duration
     }
 
     fn prepaint_tooltip(&mut self) -> Option<AnyElement> {
@@ -1659,6 +1745,7 @@ impl<'a> WindowContext<'a> {
             mouse_listeners_index: self.window.next_frame.mouse_listeners.len(),
             input_handlers_index: self.window.next_frame.input_handlers.len(),
             cursor_styles_index: self.window.next_frame.cursor_styles.len(),
+            accessibility_nodes_index: self.window.next_frame.accessibility_nodes.len(),
             accessed_element_states_index: self.window.next_frame.accessed_element_states.len(),
             line_layout_index: self.window.text_system.layout_index(),
         }
@@ -1667,6 +1754,32 @@ impl<'a> WindowContext<'a> {
     pub(crate) fn reuse_paint(&mut self, range: Range<PaintIndex>) {
         let window = &mut self.window;
 
+        // The reused nodes keep their identities, and the outermost of them are
+        // contained by the node that contains the reused element.
+        let reused_nodes = &window.rendered_frame.accessibility_nodes
+            [range.start.accessibility_nodes_index..range.end.accessibility_nodes_index];
+        let reused_node_ids = reused_nodes
+            .iter()
+            .map(|node| node.id)
+            .collect::<FxHashSet<_>>();
+        let parent = window.accessibility_stack.last().copied();
+        for id in &reused_node_ids {
+            window.accessibility_node_ids.reserve(*id);
+        }
+        window
+            .next_frame
+            .accessibility_nodes
+            .extend(reused_nodes.iter().map(|node| {
+                let mut node = node.clone();
+                if !node
+                    .parent
+                    .map_or(false, |parent| reused_node_ids.contains(&parent))
+                {
+                    node.parent = parent;
+                }
+                node
+            }));
+
         window.next_frame.cursor_styles.extend(
             window.rendered_frame.cursor_styles
                 [range.start.cursor_styles_index..range.end.cursor_styles_index]
@@ -1700,6 +1813,79 @@ impl<'a> WindowContext<'a> {
         );
     }
 
+    /// Whether assistive technologies, like screen readers, are in use. Elements only need
+    /// to describe themselves with [`Self::with_accessibility_node`] when they are.
+    pub fn accessibility_enabled(&self) -> bool {
+        self.window.accessibility_enabled
+    }
+
+    /// Adds a node describing an element with the given bounds to the window's accessibility
+    /// tree, which is given to assistive technologies like screen readers. The nodes added by
+    /// `f` are contained by this node. This method should only be called as part of the paint
+    /// phase of element drawing.
+    pub fn with_accessibility_node<R>(
+        &mut self,
+        properties: AccessibilityNodeProperties,
+        bounds: Bounds<Pixels>,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        debug_assert_eq!(
+            self.window.draw_phase,
+            DrawPhase::Paint,
+            "this method can only be called during paint"
+        );
+        if !self.window.accessibility_enabled {
+            return f(self);
+        }
+
+        let parent = self.window.accessibility_stack.last().copied();
+        let id = self.window.accessibility_node_ids.next(hash(&(
+            parent,
+            &self.window.element_id_stack,
+            properties.role,
+        )));
+        self.window
+            .next_frame
+            .accessibility_nodes
+            .push(AccessibilityNode {
+                id,
+                parent,
+                role: properties.role,
+                label: properties.label,
+                value: properties.value,
+                selected_range: properties.selected_range,
+                bounds,
+                focused: properties.focused,
+            });
+        self.window.accessibility_stack.push(id);
+        let result = f(self);
+        self.window.accessibility_stack.pop();
+        result
+    }
+
+    /// Asks assistive technologies, like screen readers, to announce the given message.
+    pub fn announce(&self, message: &str) {
+        self.window.platform_window.announce(message);
+    }
+
+    fn update_accessibility_tree(&mut self) {
+        let tree = if self.window.accessibility_enabled {
+            // Assistive technologies expect the platform's logical pixels.
+            let ui_scale = self.window.ui_scale;
+            let mut nodes = self.window.rendered_frame.accessibility_nodes.clone();
+            for node in &mut nodes {
+                node.bounds = node.bounds * ui_scale;
+            }
+            AccessibilityTree::new(nodes)
+        } else {
+            AccessibilityTree::default()
+        };
+        if tree != self.window.accessibility_tree {
+            self.window.platform_window.update_accessibility_tree(&tree);
+            self.window.accessibility_tree = tree;
+        }
+    }
+
     /// Push a text style onto the stack, and call a function with that style active.
     /// Use [`AppContext::text_style`] to get the current, combined text style. This method
     /// should only be called as part of element drawing.
@@ -2763,6 +2949,75 @@ impl<'a> WindowContext<'a> {
             .set_focus_id(focus_handle.id);
     }
 
+    /// Makes the current element, whose focus handle must have been set with
+    /// [`Self::set_focus_handle`], a stop for [`Self::focus_next`] and [`Self::focus_previous`].
+    ///
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn set_tab_stop(&mut self) {
+        debug_assert_eq!(
+            self.window.draw_phase,
+            DrawPhase::Paint,
+            "this method can only be called during paint"
+        );
+        self.window.next_frame.dispatch_tree.set_tab_stop();
+    }
+
+    /// Makes the current element a focus trap: while the focus is within it,
+    /// [`Self::focus_next`] and [`Self::focus_previous`] only move between the tab stops within it.
+    ///
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn set_focus_trap(&mut self) {
+        debug_assert_eq!(
+            self.window.draw_phase,
+            DrawPhase::Paint,
+            "this method can only be called during paint"
+        );
+        self.window.next_frame.dispatch_tree.set_focus_trap();
+    }
+
+    /// Moves the focus to the tab stop after the focused element, wrapping around to the first
+    /// one. Only the tab stops within the innermost focus trap around the focused element are
+    /// considered.
+    pub fn focus_next(&mut self) {
+        self.move_focus_between_tab_stops(true);
+    }
+
+    /// Moves the focus to the tab stop before the focused element, wrapping around to the last
+    /// one. Only the tab stops within the innermost focus trap around the focused element are
+    /// considered.
+    pub fn focus_previous(&mut self) {
+        self.move_focus_between_tab_stops(false);
+    }
+
+    fn move_focus_between_tab_stops(&mut self, forward: bool) {
+        let tab_stops = self
+            .window
+            .rendered_frame
+            .dispatch_tree
+            .tab_stops(self.window.focus);
+        if tab_stops.is_empty() {
+            return;
+        }
+
+        let current_ix = self.window.focus.and_then(|focused| {
+            tab_stops.iter().position(|tab_stop| {
+                self.window
+                    .rendered_frame
+                    .dispatch_tree
+                    .focus_contains(*tab_stop, focused)
+            })
+        });
+        let next_ix = match (current_ix, forward) {
+            (Some(ix), true) => (ix + 1) % tab_stops.len(),
+            (Some(ix), false) => (ix + tab_stops.len() - 1) % tab_stops.len(),
+            (None, true) => 0,
+            (None, false) => tab_stops.len() - 1,
+        };
+        if let Some(handle) = FocusHandle::for_id(tab_stops[next_ix], &self.window.focus_handles) {
+            self.focus(&handle);
+        }
+    }
+
     /// Sets the view id for the current element, which will be used to manage view caching.
     ///
     /// This method should only be called as part of element prepaint. We plan on removing this
@@ -2947,6 +3202,7 @@ impl<'a> WindowContext<'a> {
         // Handlers may set this to true by calling `prevent_default`.
         self.window.default_prevented = false;
 
+        let event = unscale_event(event, self.window.ui_scale);
         let event = match event {
             // Track the mouse position with our own state, since accessing the platform
             // API for the mouse position can only occur on the main thread.
@@ -3777,6 +4033,58 @@ impl<'a> BorrowMut<AppContext> for WindowContext<'a> {
     }
 }
 
+/// Converts the positions of an event from the platform's logical pixels to the window's,
+/// which are bigger when the window's UI is scaled up.
+fn unscale_event(event: PlatformInput, ui_scale: f32) -> PlatformInput {
+    if ui_scale == 1. {
+        return event;
+    }
+
+    let unscale = |position: Point<Pixels>| position * (1. / ui_scale);
+    match event {
+        PlatformInput::MouseDown(mut event) => {
+            event.position = unscale(event.position);
+            PlatformInput::MouseDown(event)
+        }
+        PlatformInput::MouseUp(mut event) => {
+            event.position = unscale(event.position);
+            PlatformInput::MouseUp(event)
+        }
+        PlatformInput::MouseMove(mut event) => {
+            event.position = unscale(event.position);
+            PlatformInput::MouseMove(event)
+        }
+        PlatformInput::MouseExited(mut event) => {
+            event.position = unscale(event.position);
+            PlatformInput::MouseExited(event)
+        }
+        PlatformInput::ScrollWheel(mut event) => {
+            event.position = unscale(event.position);
+            if let ScrollDelta::Pixels(delta) = &mut event.delta {
+                *delta = unscale(*delta);
+            }
+            PlatformInput::ScrollWheel(event)
+        }
+        PlatformInput::FileDrop(FileDropEvent::Entered { position, paths }) => {
+            PlatformInput::FileDrop(FileDropEvent::Entered {
+                position: unscale(position),
+                paths,
+            })
+        }
+        PlatformInput::FileDrop(FileDropEvent::Pending { position }) => {
+            PlatformInput::FileDrop(FileDropEvent::Pending {
+                position: unscale(position),
+            })
+        }
+        PlatformInput::FileDrop(FileDropEvent::Submit { position }) => {
+            PlatformInput::FileDrop(FileDropEvent::Submit {
+                position: unscale(position),
+            })
+        }
+        event => event,
+    }
+}
+
 /// This trait contains functionality that is shared across [`ViewContext`] and [`WindowContext`]
 pub trait BorrowWindow: BorrowMut<Window> + BorrowMut<AppContext> {
     #[doc(hidden)]
@@ -0,0 +1,111 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// The number of frames whose timings are kept for each window.
+const MAX_FRAME_TIMINGS: usize = 240;
+
+/// How long a window took to produce one of its frames.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrameTiming {
+    /// When the window started drawing the frame.
+    pub start: Instant,
+    /// The time spent rendering views, and laying out and prepainting their elements.
+    pub layout: Duration,
+    /// The time spent painting elements into the frame's scene.
+    pub paint: Duration,
+    /// The time spent on the whole frame, including presenting its scene.
+    pub total: Duration,
+}
+
+/// The timings of the most recent frames drawn by a window.
+#[derive(Debug, Default)]
+pub struct FrameTimings {
+    timings: VecDeque<FrameTiming>,
+}
+
+impl FrameTimings {
+    pub(crate) fn push(&mut self, timing: FrameTiming) {
+        if self.timings.len() == MAX_FRAME_TIMINGS {
+            self.timings.pop_front();
+        }
+        self.timings.push_back(timing);
+    }
+
+    /// The timings of the recent frames, from the oldest to the newest.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &FrameTiming> {
+        self.timings.iter()
+    }
+
+    /// The number of frames that were started in the second before `now`.
+    pub fn frames_per_second(&self, now: Instant) -> usize {
+        self.timings
+            .iter()
+            .rev()
+            .take_while(|timing| {
+                now.saturating_duration_since(timing.start) < Duration::from_secs(1)
+            })
+            .count()
+    }
+
+    /// Returns the duration that the given fraction of the recent frames took no longer than,
+    /// measuring the part of each frame that `duration` returns. For example, a `percentile`
+    /// of `0.99` returns the duration of the 99th percentile.
+    pub fn percentile(
+        &self,
+        percentile: f32,
+        duration: impl Fn(&FrameTiming) -> Duration,
+    ) -> Option<Duration> {
+        if self.timings.is_empty() {
+            return None;
+        }
+        let mut durations = self.timings.iter().map(duration).collect::<Vec<_>>();
+        durations.sort_unstable();
+        let ix = ((durations.len() as f32 * percentile.clamp(0., 1.)).ceil() as usize)
+            .saturating_sub(1)
+            .min(durations.len() - 1);
+        Some(durations[ix])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_timings() {
+        let mut timings = FrameTimings::default();
+        assert_eq!(timings.percentile(0.5, |timing| timing.total), None);
+
+        let start = Instant::now();
+        for ix in 1..=MAX_FRAME_TIMINGS as u64 + 100 {
+            timings.push(FrameTiming {
+                start: start + Duration::from_millis(ix * 10),
+                layout: Duration::ZERO,
+                paint: Duration::ZERO,
+                total: Duration::from_millis(ix),
+            });
+        }
+        assert_eq!(timings.iter().count(), MAX_FRAME_TIMINGS);
+        assert_eq!(
+            timings.percentile(0.5, |timing| timing.total),
+            Some(Duration::from_millis(220))
+        );
+        assert_eq!(
+            timings.percentile(1., |timing| timing.total),
+            Some(Duration::from_millis(340))
+        );
+        assert_eq!(
+            timings.percentile(0., |timing| timing.total),
+            Some(Duration::from_millis(101))
+        );
+
+        let last_start = timings.iter().last().unwrap().start;
+        assert_eq!(timings.frames_per_second(last_start), 100);
+        assert_eq!(
+            timings.frames_per_second(last_start + Duration::from_secs(2)),
+            0
+        );
+    }
+}
@@ -1,22 +1,51 @@
 pub mod github;
+mod retry;
 
 pub use anyhow::{anyhow, Result};
 use futures::future::BoxFuture;
 use futures_lite::FutureExt;
-use isahc::config::{Configurable, RedirectPolicy};
+use isahc::config::{CaCertificate, Configurable, RedirectPolicy};
 pub use isahc::{
     http::{Method, StatusCode, Uri},
     AsyncBody, Error, HttpClient as IsahcHttpClient, Request, Response,
 };
+pub use retry::{RetryNonIdempotent, RetryPolicy, RetryingHttpClient};
 #[cfg(feature = "test-support")]
 use std::fmt;
 use std::{
+    path::PathBuf,
     sync::{Arc, Mutex},
     time::Duration,
 };
 pub use url::Url;
 
-fn get_proxy(proxy: Option<String>) -> Option<isahc::http::Uri> {
+/// Options used to build the [`HttpClient`] of an [`HttpClientWithUrl`].
+#[derive(Clone, Debug)]
+pub struct HttpClientOptions {
+    /// The proxy to send requests through.
+    pub proxy: Option<String>,
+    /// Whether to fall back to the proxy configured in the environment (e.g. `HTTPS_PROXY`)
+    /// when no proxy is given.
+    pub use_system_proxy: bool,
+    /// A PEM bundle with the certificate authorities used to verify servers,
+    /// instead of the ones trusted by the system.
+    pub ca_certificates: Option<PathBuf>,
+    /// How to retry requests failing because of transient errors.
+    pub retry_policy: RetryPolicy,
+}
+
+impl Default for HttpClientOptions {
+    fn default() -> Self {
+        Self {
+            proxy: None,
+            use_system_proxy: true,
+            ca_certificates: None,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+}
+
+fn get_proxy(proxy: Option<String>, use_system_proxy: bool) -> Option<isahc::http::Uri> {
     macro_rules! try_env {
         ($($env:literal),+) => {
             $(
@@ -35,6 +64,9 @@ fn get_proxy(proxy: Option<String>) -> Option<isahc::http::Uri> {
                 .ok()
         })
         .or_else(|| {
+            if !use_system_proxy {
+                return None;
+            }
             try_env!(
                 "ALL_PROXY",
                 "all_proxy",
@@ -57,7 +89,18 @@ pub struct HttpClientWithUrl {
 impl HttpClientWithUrl {
     /// Returns a new [`HttpClientWithUrl`] with the given base URL.
     pub fn new(base_url: impl Into<String>, unparsed_proxy: Option<String>) -> Self {
-        let parsed_proxy = get_proxy(unparsed_proxy);
+        Self::with_options(
+            base_url,
+            HttpClientOptions {
+                proxy: unparsed_proxy,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Returns a new [`HttpClientWithUrl`] with the given base URL, configured using the given options.
+    pub fn with_options(base_url: impl Into<String>, options: HttpClientOptions) -> Self {
+        let parsed_proxy = get_proxy(options.proxy, options.use_system_proxy);
         let proxy_string = parsed_proxy.as_ref().map(|p| {
             // Map proxy settings from `http://localhost:10809` to `http://127.0.0.1:10809`
             // NodeRuntime without environment information can not parse `localhost`
@@ -67,9 +110,25 @@ impl HttpClientWithUrl {
                 .to_ascii_lowercase()
                 .replace("localhost", "127.0.0.1")
         });
+        let ca_certificates = options.ca_certificates.filter(|path| {
+            let exists = path.is_file();
+            if !exists {
+                log::error!("CA certificates file {path:?} does not exist, ignoring it");
+            }
+            exists
+        });
+        let isahc_client = build_isahc_client(parsed_proxy, ca_certificates).unwrap();
+        let client: Arc<dyn HttpClient> = if options.retry_policy.max_retries > 0 {
+            Arc::new(RetryingHttpClient::new(
+                Arc::new(isahc_client),
+                options.retry_policy,
+            ))
+        } else {
+            Arc::new(isahc_client)
+        };
         Self {
             base_url: Mutex::new(base_url.into()),
-            client: client(parsed_proxy),
+            client,
             proxy: proxy_string,
         }
     }
@@ -187,14 +246,21 @@ pub trait HttpClient: Send + Sync {
 }
 
 pub fn client(proxy: Option<isahc::http::Uri>) -> Arc<dyn HttpClient> {
-    Arc::new(
-        isahc::HttpClient::builder()
-            .connect_timeout(Duration::from_secs(5))
-            .low_speed_timeout(100, Duration::from_secs(5))
-            .proxy(proxy)
-            .build()
-            .unwrap(),
-    )
+    Arc::new(build_isahc_client(proxy, None).unwrap())
+}
+
+fn build_isahc_client(
+    proxy: Option<isahc::http::Uri>,
+    ca_certificates: Option<PathBuf>,
+) -> Result<isahc::HttpClient, Error> {
+    let mut builder = isahc::HttpClient::builder()
+        .connect_timeout(Duration::from_secs(5))
+        .low_speed_timeout(100, Duration::from_secs(5))
+        .proxy(proxy);
+    if let Some(ca_certificates) = ca_certificates {
+        builder = builder.ssl_ca_certificate(CaCertificate::file(ca_certificates));
+    }
+    builder.build()
 }
 
 impl HttpClient for isahc::HttpClient {
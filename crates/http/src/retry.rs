@@ -0,0 +1,245 @@
+use crate::{AsyncBody, Error, HttpClient, Method, Request, Response, StatusCode};
+use futures::{future::BoxFuture, AsyncReadExt};
+use isahc::config::{Configurable, RedirectPolicy};
+use std::{sync::Arc, time::Duration};
+
+/// Controls how often and how quickly a failed request is retried by a [`RetryingHttpClient`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The maximum number of times a request is retried after the initial attempt.
+    pub max_retries: u32,
+    /// The delay before the first retry, doubled for every subsequent one.
+    pub initial_backoff: Duration,
+    /// The upper bound for the delay between two attempts.
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// A policy that never retries requests.
+    pub const NONE: Self = Self {
+        max_retries: 0,
+        initial_backoff: Duration::ZERO,
+        max_backoff: Duration::ZERO,
+    };
+
+    /// Returns the delay to wait for before performing the given retry, starting at 0.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(8),
+        }
+    }
+}
+
+/// A request extension allowing a [`RetryingHttpClient`] to retry a request whose method isn't
+/// idempotent, like a `POST` that the server deduplicates.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RetryNonIdempotent;
+
+/// An [`HttpClient`] that retries requests failing because of transient network errors,
+/// timeouts, or server errors, waiting for an exponentially increasing delay between attempts.
+///
+/// Only requests with an idempotent method (`GET`, `HEAD`, `PUT`, `DELETE` or `OPTIONS`) are
+/// retried, unless they carry the [`RetryNonIdempotent`] extension, since sending the others
+/// again could repeat their effect. Requests whose body can't be replayed are sent once.
+pub struct RetryingHttpClient {
+    client: Arc<dyn HttpClient>,
+    policy: RetryPolicy,
+}
+
+impl RetryingHttpClient {
+    pub fn new(client: Arc<dyn HttpClient>, policy: RetryPolicy) -> Self {
+        Self { client, policy }
+    }
+}
+
+impl HttpClient for RetryingHttpClient {
+    fn send(
+        &self,
+        req: Request<AsyncBody>,
+    ) -> BoxFuture<'static, Result<Response<AsyncBody>, Error>> {
+        let client = self.client.clone();
+        let policy = self.policy;
+        Box::pin(async move {
+            let (parts, mut body) = req.into_parts();
+            // Per-request configuration is stored in the request's extensions, which can't be
+            // copied. Only the redirect policy is known to us, so requests carrying anything
+            // else are not retried.
+            let redirect_policy = parts.extensions.get::<RedirectPolicy>().copied();
+            let retry_non_idempotent = parts.extensions.get::<RetryNonIdempotent>().is_some();
+            let known_extensions =
+                usize::from(redirect_policy.is_some()) + usize::from(retry_non_idempotent);
+            let is_replayable = body.reset() && parts.extensions.len() == known_extensions;
+            let is_idempotent = matches!(
+                parts.method,
+                Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS
+            );
+            if policy.max_retries == 0 || !is_replayable || !(is_idempotent || retry_non_idempotent)
+            {
+                return client.send(Request::from_parts(parts, body)).await;
+            }
+
+            let mut body_bytes = Vec::new();
+            body.read_to_end(&mut body_bytes).await?;
+
+            let mut retry = 0;
+            loop {
+                let mut request = Request::builder()
+                    .method(parts.method.clone())
+                    .uri(parts.uri.clone())
+                    .version(parts.version);
+                if let Some(redirect_policy) = redirect_policy {
+                    request = request.redirect_policy(redirect_policy);
+                }
+                let mut request = request.body(AsyncBody::from(body_bytes.clone()))?;
+                *request.headers_mut() = parts.headers.clone();
+
+                let result = client.send(request).await;
+                let should_retry = match &result {
+                    Ok(response) => {
+                        response.status().is_server_error()
+                            || response.status() == StatusCode::TOO_MANY_REQUESTS
+                    }
+                    Err(error) => error.is_network() || error.is_timeout(),
+                };
+                if !should_retry || retry >= policy.max_retries {
+                    return result;
+                }
+
+                let backoff = policy.backoff(retry);
+                match &result {
+                    Ok(response) => log::warn!(
+                        "request to {} failed with status {}, retrying in {:?}",
+                        parts.uri,
+                        response.status(),
+                        backoff
+                    ),
+                    Err(error) => log::warn!(
+                        "request to {} failed: {}, retrying in {:?}",
+                        parts.uri,
+                        error,
+                        backoff
+                    ),
+                }
+                smol::Timer::after(backoff).await;
+                retry += 1;
+            }
+        })
+    }
+
+    fn proxy(&self) -> Option<&str> {
+        self.client.proxy()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use isahc::error::ErrorKind;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct FlakyHttpClient {
+        failures: usize,
+        requests: AtomicUsize,
+    }
+
+    impl HttpClient for FlakyHttpClient {
+        fn send(
+            &self,
+            mut req: Request<AsyncBody>,
+        ) -> BoxFuture<'static, Result<Response<AsyncBody>, Error>> {
+            let attempt = self.requests.fetch_add(1, Ordering::SeqCst);
+            let failures = self.failures;
+            Box::pin(async move {
+                let mut body = String::new();
+                req.body_mut().read_to_string(&mut body).await?;
+                assert_eq!(body, "payload");
+                assert_eq!(req.headers()["x-test"], "1");
+                if attempt < failures {
+                    Err(Error::from(ErrorKind::ConnectionFailed))
+                } else {
+                    Ok(Response::builder().status(200).body(AsyncBody::empty())?)
+                }
+            })
+        }
+
+        fn proxy(&self) -> Option<&str> {
+            None
+        }
+    }
+
+    fn request(method: Method) -> Request<AsyncBody> {
+        Request::builder()
+            .method(method)
+            .uri("http://test.example")
+            .header("x-test", "1")
+            .body(AsyncBody::from("payload"))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_retrying_transient_failures() {
+        let policy = RetryPolicy {
+            max_retries: 2,
+            ..RetryPolicy::NONE
+        };
+
+        let flaky = Arc::new(FlakyHttpClient {
+            failures: 2,
+            requests: AtomicUsize::new(0),
+        });
+        let client = RetryingHttpClient::new(flaky.clone(), policy);
+        let response = smol::block_on(client.send(request(Method::PUT))).unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(flaky.requests.load(Ordering::SeqCst), 3);
+
+        let flaky = Arc::new(FlakyHttpClient {
+            failures: 3,
+            requests: AtomicUsize::new(0),
+        });
+        let client = RetryingHttpClient::new(flaky.clone(), policy);
+        assert!(smol::block_on(client.send(request(Method::GET))).is_err());
+        assert_eq!(flaky.requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_retrying_non_idempotent_requests() {
+        let policy = RetryPolicy {
+            max_retries: 2,
+            ..RetryPolicy::NONE
+        };
+
+        let flaky = Arc::new(FlakyHttpClient {
+            failures: 2,
+            requests: AtomicUsize::new(0),
+        });
+        let client = RetryingHttpClient::new(flaky.clone(), policy);
+        assert!(smol::block_on(client.send(request(Method::POST))).is_err());
+        assert_eq!(flaky.requests.load(Ordering::SeqCst), 1);
+
+        let mut request = request(Method::POST);
+        request.extensions_mut().insert(RetryNonIdempotent);
+        let response = smol::block_on(client.send(request)).unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(flaky.requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.backoff(0), Duration::from_millis(500));
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(3), Duration::from_secs(4));
+        assert_eq!(policy.backoff(10), Duration::from_secs(8));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(8));
+    }
+}
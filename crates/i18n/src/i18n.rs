@@ -0,0 +1,344 @@
+//! Translations of the strings shown in Zed's UI.
+//!
+//! Strings are looked up by key, like `feedback.give_feedback`, in the locale
+//! bundles of `assets/locales`: one JSON file per locale, mapping each key to
+//! its translation. A translation is either a string or, for strings that
+//! depend on a number, an object of plural forms (`zero`, `one`, `two`, `few`,
+//! `many` and `other`). Translations can refer to arguments as `{name}`.
+//!
+//! `en.json` is the source of every key. Keys missing from a locale fall back
+//! to its base language (`pt` for `pt-BR`) and then to English.
+
+mod i18n_settings;
+mod plural;
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use gpui::{AppContext, Global, SharedString};
+use rust_embed::RustEmbed;
+use serde::Deserialize;
+use settings::{Settings, SettingsStore};
+use std::fmt::Display;
+use util::ResultExt;
+
+pub use i18n_settings::I18nSettings;
+pub use plural::PluralCategory;
+
+/// The locale whose bundle has every key, used when a translation is missing.
+pub const DEFAULT_LOCALE: &str = "en";
+
+#[derive(RustEmbed)]
+#[folder = "../../assets"]
+#[include = "locales/*.json"]
+#[exclude = "*.DS_Store"]
+struct LocaleAssets;
+
+lazy_static::lazy_static! {
+    /// The catalog used before [`init`], so that strings can be looked up in tests.
+    static ref EMBEDDED_CATALOG: Catalog = Catalog::embedded();
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+enum Message {
+    Text(String),
+    Plural(PluralForms),
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PluralForms {
+    zero: Option<String>,
+    one: Option<String>,
+    two: Option<String>,
+    few: Option<String>,
+    many: Option<String>,
+    other: String,
+}
+
+impl PluralForms {
+    fn form(&self, category: PluralCategory) -> &str {
+        let form = match category {
+            PluralCategory::Zero => &self.zero,
+            PluralCategory::One => &self.one,
+            PluralCategory::Two => &self.two,
+            PluralCategory::Few => &self.few,
+            PluralCategory::Many => &self.many,
+            PluralCategory::Other => return &self.other,
+        };
+        form.as_deref().unwrap_or(&self.other)
+    }
+}
+
+/// The translations of every locale, and the locale that strings are shown in.
+pub struct Catalog {
+    bundles: HashMap<String, HashMap<String, Message>>,
+    locale: String,
+}
+
+impl Global for Catalog {}
+
+impl Catalog {
+    /// Returns a catalog of the locale bundles embedded in Zed, in English.
+    pub fn embedded() -> Self {
+        let mut catalog = Self {
+            bundles: HashMap::default(),
+            locale: DEFAULT_LOCALE.to_string(),
+        };
+        for path in LocaleAssets::iter() {
+            let Some(locale) = path
+                .strip_prefix("locales/")
+                .and_then(|name| name.strip_suffix(".json"))
+            else {
+                continue;
+            };
+            let contents = util::asset_str::<LocaleAssets>(&path);
+            catalog
+                .add_bundle(locale, &contents)
+                .with_context(|| format!("failed to load the {locale} locale"))
+                .log_err();
+        }
+        catalog
+    }
+
+    /// Adds the translations of a locale, given the contents of its JSON bundle.
+    /// Translations that the locale already had are replaced.
+    pub fn add_bundle(&mut self, locale: &str, json: &str) -> Result<()> {
+        let messages: HashMap<String, Message> = serde_json::from_str(json)?;
+        self.bundles
+            .entry(normalize_locale(locale))
+            .or_default()
+            .extend(messages);
+        Ok(())
+    }
+
+    /// Returns the locales that have a bundle, sorted.
+    pub fn locales(&self) -> Vec<&str> {
+        let mut locales = self.bundles.keys().map(String::as_str).collect::<Vec<_>>();
+        locales.sort_unstable();
+        locales
+    }
+
+    /// Returns the locale that strings are shown in.
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    pub fn set_locale(&mut self, locale: &str) {
+        self.locale = normalize_locale(locale);
+    }
+
+    /// Returns the translation of the key, or the key itself if no locale has it.
+    pub fn translate(&self, key: &str) -> SharedString {
+        match self.message(key) {
+            Some(Message::Text(text)) => text.clone().into(),
+            Some(Message::Plural(forms)) => forms.other.clone().into(),
+            None => missing_translation(key),
+        }
+    }
+
+    /// Returns the translation of the key with each `{name}` replaced by the
+    /// argument of that name.
+    pub fn translate_with(&self, key: &str, args: &[(&str, &dyn Display)]) -> SharedString {
+        interpolate(&self.translate(key), args).into()
+    }
+
+    /// Returns the plural form of the key's translation for the count, with
+    /// `{count}` and each `{name}` replaced by their arguments.
+    pub fn translate_plural(
+        &self,
+        key: &str,
+        count: usize,
+        args: &[(&str, &dyn Display)],
+    ) -> SharedString {
+        let template = match self.message(key) {
+            Some(Message::Text(text)) => text.as_str(),
+            Some(Message::Plural(forms)) => {
+                forms.form(PluralCategory::for_count(&self.locale, count))
+            }
+            None => return missing_translation(key),
+        };
+        let mut all_args = vec![("count", &count as &dyn Display)];
+        all_args.extend_from_slice(args);
+        interpolate(template, &all_args).into()
+    }
+
+    fn message(&self, key: &str) -> Option<&Message> {
+        fallback_locales(&self.locale)
+            .find_map(|locale| self.bundles.get(locale.as_str())?.get(key))
+    }
+}
+
+/// Loads the locale bundles and shows strings in the locale of the settings,
+/// switching when it changes.
+pub fn init(cx: &mut AppContext) {
+    I18nSettings::register(cx);
+
+    let mut catalog = Catalog::embedded();
+    catalog.set_locale(&I18nSettings::get_global(cx).resolved_locale());
+    cx.set_global(catalog);
+
+    cx.observe_global::<SettingsStore>(|cx| {
+        let locale = normalize_locale(&I18nSettings::get_global(cx).resolved_locale());
+        if cx.global::<Catalog>().locale != locale {
+            cx.global_mut::<Catalog>().set_locale(&locale);
+            cx.refresh();
+        }
+    })
+    .detach();
+}
+
+/// Returns the translation of the key in the current locale.
+pub fn t(key: &str, cx: &AppContext) -> SharedString {
+    catalog(cx).translate(key)
+}
+
+/// Returns the translation of the key in the current locale, with each
+/// `{name}` replaced by the argument of that name.
+pub fn t_with(key: &str, args: &[(&str, &dyn Display)], cx: &AppContext) -> SharedString {
+    catalog(cx).translate_with(key, args)
+}
+
+/// Returns the plural form of the key's translation for the count in the
+/// current locale, with `{count}` replaced by the count.
+pub fn t_plural(key: &str, count: usize, cx: &AppContext) -> SharedString {
+    catalog(cx).translate_plural(key, count, &[])
+}
+
+fn catalog(cx: &AppContext) -> &Catalog {
+    cx.try_global::<Catalog>().unwrap_or(&EMBEDDED_CATALOG)
+}
+
+fn missing_translation(key: &str) -> SharedString {
+    log::warn!("missing translation for {key:?}");
+    key.to_string().into()
+}
+
+/// Normalizes locales like `pt_BR.UTF-8` and `PT-br` to `pt-BR`.
+fn normalize_locale(locale: &str) -> String {
+    let locale = locale.split(['.', '@']).next().unwrap_or(locale);
+    let mut parts = locale.split(['-', '_']);
+    let mut normalized = parts.next().unwrap_or_default().to_ascii_lowercase();
+    for part in parts {
+        normalized.push('-');
+        if part.len() == 2 {
+            normalized.push_str(&part.to_ascii_uppercase());
+        } else {
+            normalized.push_str(part);
+        }
+    }
+    normalized
+}
+
+/// Returns the locales to look translations up in: the locale, its base
+/// languages, and then the default locale.
+fn fallback_locales(locale: &str) -> impl Iterator<Item = String> + '_ {
+    let mut prefixes = locale
+        .match_indices('-')
+        .map(|(ix, _)| locale[..ix].to_string())
+        .collect::<Vec<_>>();
+    prefixes.reverse();
+    std::iter::once(locale.to_string())
+        .chain(prefixes)
+        .chain((locale != DEFAULT_LOCALE).then(|| DEFAULT_LOCALE.to_string()))
+}
+
+fn interpolate(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        let after_brace = &rest[start + 1..];
+        let arg = after_brace.find('}').and_then(|end| {
+            let name = &after_brace[..end];
+            let (_, value) = args.iter().find(|(arg_name, _)| *arg_name == name)?;
+            Some((value, end))
+        });
+        match arg {
+            Some((value, end)) => {
+                result.push_str(&value.to_string());
+                rest = &after_brace[end + 1..];
+            }
+            None => {
+                result.push('{');
+                rest = after_brace;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_bundles() {
+        let catalog = Catalog::embedded();
+        let english = &catalog.bundles[DEFAULT_LOCALE];
+        for locale in catalog.locales() {
+            for key in catalog.bundles[locale].keys() {
+                assert!(
+                    english.contains_key(key),
+                    "{locale} translates {key:?}, which isn't in {DEFAULT_LOCALE}.json"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_translate() {
+        let mut catalog = Catalog::embedded();
+        catalog
+            .add_bundle(
+                "pt",
+                r#"{
+                    "test.greeting": "Olá, {name}!",
+                    "test.files": { "one": "{count} arquivo", "other": "{count} arquivos" }
+                }"#,
+            )
+            .unwrap();
+        catalog
+            .add_bundle("pt_BR", r#"{ "test.greeting": "Oi, {name}!" }"#)
+            .unwrap();
+        catalog
+            .add_bundle(
+                "en",
+                r#"{
+                    "test.greeting": "Hello, {name}!",
+                    "test.files": { "one": "{count} file", "other": "{count} files" },
+                    "test.untranslated": "Untranslated"
+                }"#,
+            )
+            .unwrap();
+
+        catalog.set_locale("pt_BR.UTF-8");
+        assert_eq!(catalog.locale(), "pt-BR");
+        assert_eq!(
+            catalog.translate_with("test.greeting", &[("name", &"Zed")]),
+            "Oi, Zed!"
+        );
+        assert_eq!(catalog.translate_plural("test.files", 0, &[]), "0 arquivo");
+        assert_eq!(catalog.translate_plural("test.files", 2, &[]), "2 arquivos");
+        assert_eq!(catalog.translate("test.untranslated"), "Untranslated");
+        assert_eq!(catalog.translate("test.missing"), "test.missing");
+
+        catalog.set_locale("en");
+        assert_eq!(
+            catalog.translate_with("test.greeting", &[("name", &"Zed")]),
+            "Hello, Zed!"
+        );
+        assert_eq!(catalog.translate_plural("test.files", 0, &[]), "0 files");
+        assert_eq!(catalog.translate_plural("test.files", 1, &[]), "1 file");
+    }
+
+    #[test]
+    fn test_interpolate() {
+        assert_eq!(
+            interpolate("{a} and {b}, {a}", &[("a", &1), ("b", &"two")]),
+            "1 and two, 1"
+        );
+        assert_eq!(interpolate("{unknown} {", &[]), "{unknown} {");
+    }
+}
@@ -0,0 +1,52 @@
+use gpui::AppContext;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+use crate::DEFAULT_LOCALE;
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct I18nSettings {
+    pub locale: Option<String>,
+}
+
+impl I18nSettings {
+    /// Returns the locale to show strings in: the `locale` setting, or else
+    /// the locale of the system's environment.
+    pub fn resolved_locale(&self) -> String {
+        self.locale
+            .clone()
+            .or_else(system_locale)
+            .unwrap_or_else(|| DEFAULT_LOCALE.to_string())
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct I18nSettingsContent {
+    /// The locale to show the interface in, like `en` or `pt-BR`.
+    /// When null, the locale of the system is used.
+    ///
+    /// Default: null
+    pub locale: Option<String>,
+}
+
+impl Settings for I18nSettings {
+    const KEY: Option<&'static str> = None;
+
+    type FileContent = I18nSettingsContent;
+
+    fn load(
+        sources: SettingsSources<Self::FileContent>,
+        _: &mut AppContext,
+    ) -> anyhow::Result<Self> {
+        sources.json_merge()
+    }
+}
+
+/// Returns the locale that the environment selects for messages, if any.
+fn system_locale() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|locale| !locale.is_empty() && locale != "C" && locale != "POSIX")
+}
@@ -0,0 +1,100 @@
+/// The plural forms that a translation can have, following the categories of
+/// the [Unicode CLDR](https://cldr.unicode.org/index/cldr-spec/plural-rules).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralCategory {
+    /// Returns the plural form that the locale's language uses for the count.
+    pub fn for_count(locale: &str, count: usize) -> Self {
+        let language = locale.split('-').next().unwrap_or(locale);
+        let (n, n10, n100) = (count, count % 10, count % 100);
+        match language {
+            // Languages without plural forms.
+            "id" | "ja" | "ko" | "ms" | "th" | "vi" | "zh" => Self::Other,
+            // Languages that use the singular for zero.
+            "fr" | "hi" | "pt" => {
+                if n <= 1 {
+                    Self::One
+                } else {
+                    Self::Other
+                }
+            }
+            "be" | "bs" | "hr" | "ru" | "sr" | "uk" => {
+                if n10 == 1 && n100 != 11 {
+                    Self::One
+                } else if (2..=4).contains(&n10) && !(12..=14).contains(&n100) {
+                    Self::Few
+                } else {
+                    Self::Many
+                }
+            }
+            "pl" => {
+                if n == 1 {
+                    Self::One
+                } else if (2..=4).contains(&n10) && !(12..=14).contains(&n100) {
+                    Self::Few
+                } else {
+                    Self::Many
+                }
+            }
+            "cs" | "sk" => match n {
+                1 => Self::One,
+                2..=4 => Self::Few,
+                _ => Self::Other,
+            },
+            "ar" => match n100 {
+                _ if n == 0 => Self::Zero,
+                _ if n == 1 => Self::One,
+                _ if n == 2 => Self::Two,
+                3..=10 => Self::Few,
+                11..=99 => Self::Many,
+                _ => Self::Other,
+            },
+            // Like English, most languages only distinguish the singular.
+            _ => {
+                if n == 1 {
+                    Self::One
+                } else {
+                    Self::Other
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plural_categories() {
+        let categories = |locale| {
+            [0, 1, 2, 5, 11, 21, 22, 112].map(|count| PluralCategory::for_count(locale, count))
+        };
+        use PluralCategory::*;
+        assert_eq!(
+            categories("en"),
+            [Other, One, Other, Other, Other, Other, Other, Other]
+        );
+        assert_eq!(
+            categories("pt-BR"),
+            [One, One, Other, Other, Other, Other, Other, Other]
+        );
+        assert_eq!(
+            categories("ru"),
+            [Many, One, Few, Many, Many, One, Few, Many]
+        );
+        assert_eq!(
+            categories("pl"),
+            [Many, One, Few, Many, Many, Many, Few, Many]
+        );
+        assert_eq!(categories("ja"), [Other; 8]);
+    }
+}
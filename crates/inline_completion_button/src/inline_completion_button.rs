@@ -340,6 +340,10 @@ impl StatusItemView for InlineCompletionButton {
         }
         cx.notify();
     }
+
+    fn status_item_id() -> Option<&'static str> {
+        Some("inline_completion")
+    }
 }
 
 impl SupermavenButtonStatus {
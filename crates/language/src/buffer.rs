@@ -6,7 +6,9 @@ pub use crate::{
 };
 use crate::{
     diagnostic_set::{DiagnosticEntry, DiagnosticGroup},
-    language_settings::{language_settings, IndentGuideSettings, LanguageSettings},
+    language_settings::{
+        language_settings, IndentGuideSettings, LanguageSettings, TextMateHighlighting,
+    },
     markdown::parse_markdown,
     outline::OutlineItem,
     syntax_map::{
@@ -14,7 +16,8 @@ use crate::{
         SyntaxSnapshot, ToTreeSitterPoint,
     },
     task_context::RunnableRange,
-    LanguageScope, Outline, RunnableCapture, RunnableTag,
+    textmate_grammar::TextMateHighlights,
+    LanguageScope, Outline, RunnableCapture, RunnableTag, TextObject,
 };
 use anyhow::{anyhow, Context, Result};
 pub use clock::ReplicaId;
@@ -41,7 +44,10 @@ use std::{
     ops::{Deref, Range},
     path::{Path, PathBuf},
     str,
-    sync::Arc,
+    sync::{
+        atomic::{self, AtomicUsize},
+        Arc,
+    },
     time::{Duration, Instant, SystemTime},
     vec,
 };
@@ -70,6 +76,11 @@ lazy_static! {
     pub static ref BUFFER_DIFF_TASK: TaskLabel = TaskLabel::new();
 }
 
+/// Buffers longer than this (in bytes) are always reparsed in the background,
+/// as parsing them is unlikely to finish before the synchronous parse timeout.
+/// Their background parses are cancelled once the buffer is edited again.
+const MAX_SYNC_PARSE_LEN: usize = 1024 * 1024;
+
 /// Indicate whether a [Buffer] has permissions to edit.
 #[derive(PartialEq, Clone, Copy, Debug)]
 pub enum Capability {
@@ -101,9 +112,13 @@ pub struct Buffer {
     autoindent_requests: Vec<Arc<AutoindentRequest>>,
     pending_autoindent: Option<Task<()>>,
     sync_parse_timeout: Duration,
+    max_sync_parse_len: usize,
     syntax_map: Mutex<SyntaxMap>,
     parsing_in_background: bool,
+    parse_cancellation_flag: Arc<AtomicUsize>,
     parse_count: usize,
+    textmate_highlights: Option<Arc<TextMateHighlights>>,
+    pending_textmate_highlights: Option<Task<()>>,
     diagnostics: SmallVec<[(LanguageServerId, DiagnosticSet); 2]>,
     remote_selections: TreeMap<ReplicaId, SelectionSet>,
     selections_update_count: usize,
@@ -125,6 +140,7 @@ pub struct BufferSnapshot {
     text: text::BufferSnapshot,
     git_diff: git::diff::BufferDiff,
     pub(crate) syntax: SyntaxSnapshot,
+    textmate_highlights: Option<Arc<TextMateHighlights>>,
     file: Option<Arc<dyn File>>,
     diagnostics: SmallVec<[(LanguageServerId, DiagnosticSet); 2]>,
     diagnostics_update_count: usize,
@@ -451,6 +467,9 @@ pub struct BufferChunks<'a> {
     hint_depth: usize,
     unnecessary_depth: usize,
     highlights: Option<BufferChunkHighlights<'a>>,
+    /// Sorted, non-overlapping highlights, for buffers highlighted by a TextMate grammar.
+    textmate_highlights: Vec<(Range<usize>, HighlightId)>,
+    textmate_highlight_ix: usize,
 }
 
 /// A chunk of a buffer's text, along with its syntax highlight and
@@ -711,8 +730,12 @@ impl Buffer {
             capability,
             syntax_map: Mutex::new(SyntaxMap::new()),
             parsing_in_background: false,
+            parse_cancellation_flag: Default::default(),
             parse_count: 0,
+            textmate_highlights: None,
+            pending_textmate_highlights: None,
             sync_parse_timeout: Duration::from_millis(1),
+            max_sync_parse_len: MAX_SYNC_PARSE_LEN,
             autoindent_requests: Default::default(),
             pending_autoindent: Default::default(),
             language: None,
@@ -741,6 +764,7 @@ impl Buffer {
         BufferSnapshot {
             text,
             syntax,
+            textmate_highlights: self.textmate_highlights.clone(),
             git_diff: self.git_diff.clone(),
             file: self.file.clone(),
             remote_selections: self.remote_selections.clone(),
@@ -784,6 +808,7 @@ impl Buffer {
     pub fn set_language(&mut self, language: Option<Arc<Language>>, cx: &mut ModelContext<Self>) {
         self.parse_count += 1;
         self.syntax_map.lock().clear();
+        self.textmate_highlights = None;
         self.language = language;
         self.reparse(cx);
         cx.emit(Event::LanguageChanged);
@@ -1034,6 +1059,11 @@ impl Buffer {
         self.sync_parse_timeout = timeout;
     }
 
+    #[cfg(test)]
+    pub fn set_max_sync_parse_len(&mut self, len: usize) {
+        self.max_sync_parse_len = len;
+    }
+
     /// Called after an edit to synchronize the buffer's main parse tree with
     /// the buffer's new underlying state.
     ///
@@ -1046,7 +1076,11 @@ impl Buffer {
     ///
     /// Meanwhile, in the foreground, we block the main thread for up to 1ms
     /// waiting on the parse to complete. As soon as it completes, we proceed
-    /// synchronously, unless a 1ms timeout elapses.
+    /// synchronously, unless a 1ms timeout elapses. Large buffers don't block
+    /// the main thread at all, and their background parse is cancelled when
+    /// the buffer is edited again, as its result would be stale by the time
+    /// it completes. Until a parse completes, the interpolated tree keeps the
+    /// previous highlights where they were.
     ///
     /// If we time out waiting on the parse, we spawn a second task waiting
     /// until the parse does complete and return with the interpolated tree still
@@ -1058,7 +1092,12 @@ impl Buffer {
     /// for the same buffer, we only initiate a new parse if we are not already
     /// parsing in the background.
     pub fn reparse(&mut self, cx: &mut ModelContext<Self>) {
+        self.update_textmate_highlights(cx);
         if self.parsing_in_background {
+            if self.text.len() > self.max_sync_parse_len {
+                self.parse_cancellation_flag
+                    .store(1, atomic::Ordering::Relaxed);
+            }
             return;
         }
         let language = if let Some(language) = self.language.clone() {
@@ -1069,6 +1108,11 @@ impl Buffer {
 
         let text = self.text_snapshot();
         let parsed_version = self.version();
+        let sync_parse_timeout = if text.len() > self.max_sync_parse_len {
+            Duration::ZERO
+        } else {
+            self.sync_parse_timeout
+        };
 
         let mut syntax_map = self.syntax_map.lock();
         syntax_map.interpolate(&text);
@@ -1076,27 +1120,36 @@ impl Buffer {
         let mut syntax_snapshot = syntax_map.snapshot();
         drop(syntax_map);
 
+        let cancellation_flag = Arc::new(AtomicUsize::new(0));
+        self.parse_cancellation_flag = cancellation_flag.clone();
         let parse_task = cx.background_executor().spawn({
             let language = language.clone();
             let language_registry = language_registry.clone();
             async move {
-                syntax_snapshot.reparse(&text, language_registry, language);
-                syntax_snapshot
+                let completed = syntax_snapshot.reparse_cancellable(
+                    &text,
+                    language_registry,
+                    language,
+                    &cancellation_flag,
+                );
+                (syntax_snapshot, completed)
             }
         });
 
         match cx
             .background_executor()
-            .block_with_timeout(self.sync_parse_timeout, parse_task)
+            .block_with_timeout(sync_parse_timeout, parse_task)
         {
-            Ok(new_syntax_snapshot) => {
-                self.did_finish_parsing(new_syntax_snapshot, cx);
+            Ok((new_syntax_snapshot, completed)) => {
+                if completed {
+                    self.did_finish_parsing(new_syntax_snapshot, cx);
+                }
                 return;
             }
             Err(parse_task) => {
                 self.parsing_in_background = true;
                 cx.spawn(move |this, mut cx| async move {
-                    let new_syntax_map = parse_task.await;
+                    let (new_syntax_map, completed) = parse_task.await;
                     this.update(&mut cx, move |this, cx| {
                         let grammar_changed =
                             this.language.as_ref().map_or(true, |current_language| {
@@ -1107,10 +1160,13 @@ impl Buffer {
                             && language_registry.map_or(false, |registry| {
                                 registry.version() != new_syntax_map.language_registry_version()
                             });
-                        let parse_again = language_registry_changed
+                        let parse_again = !completed
+                            || language_registry_changed
                             || grammar_changed
                             || this.version.changed_since(&parsed_version);
-                        this.did_finish_parsing(new_syntax_map, cx);
+                        if completed {
+                            this.did_finish_parsing(new_syntax_map, cx);
+                        }
                         this.parsing_in_background = false;
                         if parse_again {
                             this.reparse(cx);
@@ -1123,6 +1179,46 @@ impl Buffer {
         }
     }
 
+    /// Highlights the buffer with the TextMate grammar of its language on a background thread,
+    /// if it should be highlighted with it. Until that completes, the previous highlights are
+    /// shown where they were.
+    fn update_textmate_highlights(&mut self, cx: &mut ModelContext<Self>) {
+        let grammar = self.language.as_ref().and_then(|language| {
+            let textmate_grammar = language.textmate_grammar()?;
+            let enabled = match language_settings(Some(language), self.file.as_ref(), cx)
+                .textmate_highlighting
+            {
+                TextMateHighlighting::Fallback => language
+                    .grammar()
+                    .map_or(true, |grammar| grammar.highlights_query.is_none()),
+                TextMateHighlighting::Always => true,
+                TextMateHighlighting::Never => false,
+            };
+            enabled.then(|| textmate_grammar.clone())
+        });
+        let Some(grammar) = grammar else {
+            self.textmate_highlights = None;
+            self.pending_textmate_highlights = None;
+            return;
+        };
+
+        let text = self.text.snapshot();
+        let highlights = cx
+            .background_executor()
+            .spawn(async move { grammar.highlight(&text) });
+        self.pending_textmate_highlights = Some(cx.spawn(|this, mut cx| async move {
+            let highlights = highlights.await;
+            this.update(&mut cx, |this, cx| {
+                this.textmate_highlights = Some(Arc::new(highlights));
+                this.pending_textmate_highlights = None;
+                this.parse_count += 1;
+                cx.emit(Event::Reparsed);
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
     fn did_finish_parsing(&mut self, syntax_snapshot: SyntaxSnapshot, cx: &mut ModelContext<Self>) {
         self.parse_count += 1;
         self.syntax_map.lock().did_parse(syntax_snapshot);
@@ -2014,6 +2110,18 @@ impl Buffer {
         cx.emit(Event::Operation(operation));
     }
 
+    /// Returns the replica ids of the remote peers that currently have an active selection in
+    /// this buffer, which happens whenever one of their editors for this buffer is focused.
+    pub fn remote_replicas_with_selections(&self) -> impl Iterator<Item = ReplicaId> + '_ {
+        let local_replica_id = self.text.replica_id();
+        self.remote_selections
+            .iter()
+            .filter(move |(replica_id, set)| {
+                **replica_id != local_replica_id && !set.selections.is_empty()
+            })
+            .map(|(replica_id, _)| *replica_id)
+    }
+
     /// Removes the selections for a given peer.
     pub fn remove_peer(&mut self, replica_id: ReplicaId, cx: &mut ModelContext<Self>) {
         self.remote_selections.remove(&replica_id);
@@ -2470,17 +2578,22 @@ impl BufferSnapshot {
         let range = range.start.to_offset(self)..range.end.to_offset(self);
 
         let mut syntax = None;
+        let mut textmate_highlights = Vec::new();
         let mut diagnostic_endpoints = Vec::new();
         if language_aware {
-            let captures = self.syntax.captures(range.clone(), &self.text, |grammar| {
-                grammar.highlights_query.as_ref()
-            });
-            let highlight_maps = captures
-                .grammars()
-                .into_iter()
-                .map(|grammar| grammar.highlight_map())
-                .collect();
-            syntax = Some((captures, highlight_maps));
+            if let Some(highlights) = &self.textmate_highlights {
+                textmate_highlights = highlights.in_range(range.clone(), &self.text);
+            } else {
+                let captures = self.syntax.captures(range.clone(), &self.text, |grammar| {
+                    grammar.highlights_query.as_ref()
+                });
+                let highlight_maps = captures
+                    .grammars()
+                    .into_iter()
+                    .map(|grammar| grammar.highlight_map())
+                    .collect();
+                syntax = Some((captures, highlight_maps));
+            }
             for entry in self.diagnostics_in_range::<_, usize>(range.clone(), false) {
                 diagnostic_endpoints.push(DiagnosticEndpoint {
                     offset: entry.range.start,
@@ -2500,6 +2613,7 @@ impl BufferSnapshot {
         }
 
         BufferChunks::new(self.text.as_rope(), range, syntax, diagnostic_endpoints)
+            .with_textmate_highlights(textmate_highlights)
     }
 
     /// Invokes the given callback for each line of text in the given range of the buffer.
@@ -3014,6 +3128,62 @@ impl BufferSnapshot {
         })
     }
 
+    /// Returns the [`TextObject`]s whose ranges intersect the given range, from the
+    /// innermost syntax layer outward.
+    pub fn text_object_ranges<T: ToOffset>(
+        &self,
+        range: Range<T>,
+    ) -> impl Iterator<Item = (Range<usize>, TextObject)> + '_ {
+        let offset_range = range.start.to_offset(self)..range.end.to_offset(self);
+        let mut syntax_matches = self.syntax.matches(offset_range, self, |grammar| {
+            grammar
+                .text_object_config
+                .as_ref()
+                .map(|config| &config.query)
+        });
+
+        let configs = syntax_matches
+            .grammars()
+            .iter()
+            .map(|grammar| grammar.text_object_config.as_ref())
+            .collect::<Vec<_>>();
+
+        let mut captures = Vec::<(Range<usize>, TextObject)>::new();
+        iter::from_fn(move || loop {
+            if let Some(capture) = captures.pop() {
+                return Some(capture);
+            }
+
+            let mat = syntax_matches.peek()?;
+            if let Some(config) = configs[mat.grammar_index] {
+                // A text object can span several nodes, such as consecutive line comments,
+                // in which case it covers all the nodes captured under the same name.
+                for capture in mat.captures {
+                    let Some(text_object) = config
+                        .text_objects_by_capture_ix
+                        .get(capture.index as usize)
+                        .copied()
+                        .flatten()
+                    else {
+                        continue;
+                    };
+                    let range = capture.node.byte_range();
+                    match captures
+                        .iter_mut()
+                        .find(|(_, existing)| *existing == text_object)
+                    {
+                        Some((existing_range, _)) => {
+                            existing_range.start = existing_range.start.min(range.start);
+                            existing_range.end = existing_range.end.max(range.end);
+                        }
+                        None => captures.push((range, text_object)),
+                    }
+                }
+            }
+            syntax_matches.advance();
+        })
+    }
+
     pub fn runnable_ranges(
         &self,
         range: Range<Anchor>,
@@ -3589,6 +3759,7 @@ impl Clone for BufferSnapshot {
             text: self.text.clone(),
             git_diff: self.git_diff.clone(),
             syntax: self.syntax.clone(),
+            textmate_highlights: self.textmate_highlights.clone(),
             file: self.file.clone(),
             remote_selections: self.remote_selections.clone(),
             diagnostics: self.diagnostics.clone(),
@@ -3642,9 +3813,20 @@ impl<'a> BufferChunks<'a> {
             hint_depth: 0,
             unnecessary_depth: 0,
             highlights,
+            textmate_highlights: Vec::new(),
+            textmate_highlight_ix: 0,
         }
     }
 
+    pub(crate) fn with_textmate_highlights(
+        mut self,
+        highlights: Vec<(Range<usize>, HighlightId)>,
+    ) -> Self {
+        self.textmate_highlights = highlights;
+        self.textmate_highlight_ix = 0;
+        self
+    }
+
     /// Seeks to the given byte offset in the buffer.
     pub fn seek(&mut self, offset: usize) {
         self.range.start = offset;
@@ -3667,6 +3849,9 @@ impl<'a> BufferChunks<'a> {
             }
             highlights.captures.set_byte_range(self.range.clone());
         }
+        self.textmate_highlight_ix = self
+            .textmate_highlights
+            .partition_point(|(range, _)| range.end <= offset);
     }
 
     /// The current byte offset in the buffer.
@@ -3761,6 +3946,14 @@ impl<'a> Iterator for BufferChunks<'a> {
             }
         }
 
+        while let Some((range, _)) = self.textmate_highlights.get(self.textmate_highlight_ix) {
+            if range.end <= self.range.start {
+                self.textmate_highlight_ix += 1;
+            } else {
+                break;
+            }
+        }
+
         if let Some(chunk) = self.chunks.peek() {
             let chunk_start = self.range.start;
             let mut chunk_end = (self.chunks.offset() + chunk.len())
@@ -3773,6 +3966,16 @@ impl<'a> Iterator for BufferChunks<'a> {
                     highlight_id = Some(*parent_highlight_id);
                 }
             }
+            if let Some((range, textmate_highlight_id)) =
+                self.textmate_highlights.get(self.textmate_highlight_ix)
+            {
+                if range.start <= chunk_start {
+                    chunk_end = chunk_end.min(range.end);
+                    highlight_id = Some(*textmate_highlight_id);
+                } else {
+                    chunk_end = chunk_end.min(range.start);
+                }
+            }
 
             let slice =
                 &chunk[chunk_start - self.chunks.offset()..chunk_end - self.chunks.offset()];
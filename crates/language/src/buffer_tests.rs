@@ -577,6 +577,46 @@ async fn test_reparse(cx: &mut gpui::TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_cancelling_background_parse_of_large_buffer(cx: &mut gpui::TestAppContext) {
+    let buffer = cx.new_model(|cx| {
+        let text = "fn a() {}\n".repeat(100);
+        let mut buffer = Buffer::local(text, cx).with_language(Arc::new(rust_lang()), cx);
+        // Treats the buffer as a large one.
+        buffer.set_max_sync_parse_len(0);
+        buffer
+    });
+    cx.executor().run_until_parked();
+    let parse_count = buffer.update(cx, |buffer, _| buffer.parse_count());
+
+    // The parse that the first edit starts is cancelled by the second one, so
+    // only the text after the second edit is parsed.
+    buffer.update(cx, |buf, cx| {
+        let offset = buf.text().find('}').unwrap();
+        buf.edit([(offset..offset, "b")], None, cx);
+        assert!(buf.is_parsing());
+    });
+    buffer.update(cx, |buf, cx| {
+        let offset = buf.text().find('}').unwrap();
+        buf.edit([(offset..offset, ".c();")], None, cx);
+        assert!(buf.text().starts_with("fn a() {b.c();}\n"));
+        assert!(buf.is_parsing());
+    });
+    cx.executor().run_until_parked();
+    assert!(!buffer.update(cx, |buffer, _| buffer.is_parsing()));
+    assert_eq!(
+        buffer.update(cx, |buffer, _| buffer.parse_count()),
+        parse_count + 1
+    );
+    assert!(get_tree_sexp(&buffer, cx).starts_with(concat!(
+        "(source_file (function_item name: (identifier) ",
+        "parameters: (parameters) ",
+        "body: (block (expression_statement (call_expression ",
+        "function: (field_expression value: (identifier) field: (field_identifier)) ",
+        "arguments: (arguments))))) ",
+    )));
+}
+
 #[gpui::test]
 async fn test_resetting_language(cx: &mut gpui::TestAppContext) {
     let buffer = cx.new_model(|cx| {
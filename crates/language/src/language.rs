@@ -15,6 +15,7 @@ mod outline;
 pub mod proto;
 mod syntax_map;
 mod task_context;
+mod textmate_grammar;
 
 #[cfg(test)]
 mod buffer_tests;
@@ -59,6 +60,7 @@ use std::{
 use syntax_map::{QueryCursorHandle, SyntaxSnapshot};
 use task::RunnableTag;
 pub use task_context::{ContextProvider, RunnableRange};
+pub use textmate_grammar::TextMateGrammar;
 use theme::SyntaxTheme;
 use tree_sitter::{self, wasmtime, Query, QueryCursor, WasmStore};
 
@@ -273,7 +275,16 @@ pub trait LspAdapterDelegate: Send + Sync {
 
     async fn which(&self, command: &OsStr) -> Option<PathBuf>;
     async fn shell_env(&self) -> HashMap<String, String>;
+    /// The environment variables configured for the project, which are set
+    /// for every language server on top of those of its binary.
+    async fn project_env(&self) -> HashMap<String, String>;
     async fn read_text_file(&self, path: PathBuf) -> Result<String>;
+
+    /// Returns the command that starts the given language server binary, which runs it
+    /// elsewhere than on the host when the project's servers are, such as in a dev container.
+    fn language_server_command(&self, binary: LanguageServerBinary) -> LanguageServerBinary {
+        binary
+    }
 }
 
 #[async_trait(?Send)]
@@ -821,6 +832,7 @@ pub struct Language {
     pub(crate) id: LanguageId,
     pub(crate) config: LanguageConfig,
     pub(crate) grammar: Option<Arc<Grammar>>,
+    pub(crate) textmate_grammar: Option<Arc<TextMateGrammar>>,
     pub(crate) context_provider: Option<Arc<dyn ContextProvider>>,
 }
 
@@ -841,6 +853,7 @@ pub struct Grammar {
     pub(crate) brackets_config: Option<BracketConfig>,
     pub(crate) redactions_config: Option<RedactionConfig>,
     pub(crate) runnable_config: Option<RunnableConfig>,
+    pub(crate) text_object_config: Option<TextObjectConfig>,
     pub(crate) indents_config: Option<IndentConfig>,
     pub outline_config: Option<OutlineConfig>,
     pub embedding_config: Option<EmbeddingConfig>,
@@ -899,6 +912,47 @@ struct RunnableConfig {
     pub extra_captures: Vec<RunnableCapture>,
 }
 
+/// A syntax-aware region of code that can be selected, such as the body of a function.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TextObject {
+    InsideFunction,
+    AroundFunction,
+    InsideClass,
+    AroundClass,
+    InsideComment,
+    AroundComment,
+}
+
+impl TextObject {
+    pub fn from_capture_name(name: &str) -> Option<TextObject> {
+        match name {
+            "function.inside" => Some(TextObject::InsideFunction),
+            "function.around" => Some(TextObject::AroundFunction),
+            "class.inside" => Some(TextObject::InsideClass),
+            "class.around" => Some(TextObject::AroundClass),
+            "comment.inside" => Some(TextObject::InsideComment),
+            "comment.around" => Some(TextObject::AroundComment),
+            _ => None,
+        }
+    }
+
+    /// Returns the text object surrounding this one, if this is an inner text object.
+    pub fn around(&self) -> Option<Self> {
+        match self {
+            TextObject::InsideFunction => Some(TextObject::AroundFunction),
+            TextObject::InsideClass => Some(TextObject::AroundClass),
+            TextObject::InsideComment => Some(TextObject::AroundComment),
+            _ => None,
+        }
+    }
+}
+
+struct TextObjectConfig {
+    pub query: Query,
+    /// The text object each capture corresponds to, indexed by capture index
+    pub text_objects_by_capture_ix: Vec<Option<TextObject>>,
+}
+
 struct OverrideConfig {
     query: Query,
     values: HashMap<u32, (String, LanguageConfigOverride)>,
@@ -941,11 +995,13 @@ impl Language {
                     override_config: None,
                     redactions_config: None,
                     runnable_config: None,
+                    text_object_config: None,
                     error_query: Query::new(&ts_language, "(ERROR) @error").unwrap(),
                     ts_language,
                     highlight_map: Default::default(),
                 })
             }),
+            textmate_grammar: None,
             context_provider: None,
         }
     }
@@ -955,6 +1011,11 @@ impl Language {
         self
     }
 
+    pub fn with_textmate_grammar(mut self, grammar: Option<Arc<TextMateGrammar>>) -> Self {
+        self.textmate_grammar = grammar;
+        self
+    }
+
     pub fn with_queries(mut self, queries: LanguageQueries) -> Result<Self> {
         if let Some(query) = queries.highlights {
             self = self
@@ -1001,6 +1062,11 @@ impl Language {
                 .with_runnable_query(query.as_ref())
                 .context("Error loading tests query")?;
         }
+        if let Some(query) = queries.text_objects {
+            self = self
+                .with_text_object_query(query.as_ref())
+                .context("Error loading textobjects query")?;
+        }
         Ok(self)
     }
 
@@ -1037,6 +1103,26 @@ impl Language {
         Ok(self)
     }
 
+    pub fn with_text_object_query(mut self, source: &str) -> Result<Self> {
+        let grammar = self
+            .grammar_mut()
+            .ok_or_else(|| anyhow!("cannot mutate grammar"))?;
+
+        let query = Query::new(&grammar.ts_language, source)?;
+        let text_objects_by_capture_ix = query
+            .capture_names()
+            .iter()
+            .map(|name| TextObject::from_capture_name(name))
+            .collect();
+
+        grammar.text_object_config = Some(TextObjectConfig {
+            query,
+            text_objects_by_capture_ix,
+        });
+
+        Ok(self)
+    }
+
     pub fn with_outline_query(mut self, source: &str) -> Result<Self> {
         let grammar = self
             .grammar_mut()
@@ -1358,12 +1444,20 @@ impl Language {
                     HighlightMap::new(highlights_query.capture_names(), theme);
             }
         }
+        if let Some(textmate_grammar) = self.textmate_grammar.as_ref() {
+            textmate_grammar.set_theme(theme);
+        }
     }
 
     pub fn grammar(&self) -> Option<&Arc<Grammar>> {
         self.grammar.as_ref()
     }
 
+    /// The TextMate grammar that highlights the language, instead of its Tree-sitter grammar.
+    pub fn textmate_grammar(&self) -> Option<&Arc<TextMateGrammar>> {
+        self.textmate_grammar.as_ref()
+    }
+
     pub fn default_scope(self: &Arc<Self>) -> LanguageScope {
         LanguageScope {
             language: self.clone(),
@@ -1460,6 +1554,15 @@ impl LanguageScope {
         }
     }
 
+    /// Returns the name of the `overrides` query capture that this scope is
+    /// in, like `string` or `comment`.
+    pub fn override_name(&self) -> Option<&str> {
+        let id = self.override_id?;
+        let grammar = self.language.grammar.as_ref()?;
+        let override_config = grammar.override_config.as_ref()?;
+        override_config.values.get(&id).map(|e| e.0.as_str())
+    }
+
     fn config_override(&self) -> Option<&LanguageConfigOverride> {
         let id = self.override_id?;
         let grammar = self.language.grammar.as_ref()?;
@@ -4,7 +4,7 @@ use crate::{
     },
     task_context::ContextProvider,
     with_parser, CachedLspAdapter, File, Language, LanguageConfig, LanguageId, LanguageMatcher,
-    LanguageServerName, LspAdapter, LspAdapterDelegate, PLAIN_TEXT,
+    LanguageServerName, LspAdapter, LspAdapterDelegate, TextMateGrammar, PLAIN_TEXT,
 };
 use anyhow::{anyhow, Context as _, Result};
 use collections::{hash_map, HashMap};
@@ -46,6 +46,7 @@ struct LanguageRegistryState {
     language_settings: AllLanguageSettingsContent,
     available_languages: Vec<AvailableLanguage>,
     grammars: HashMap<Arc<str>, AvailableGrammar>,
+    textmate_grammars: HashMap<Arc<str>, Arc<TextMateGrammar>>,
     lsp_adapters: HashMap<Arc<str>, Vec<Arc<CachedLspAdapter>>>,
     available_lsp_adapters:
         HashMap<LanguageServerName, Arc<dyn Fn() -> Arc<CachedLspAdapter> + 'static + Send + Sync>>,
@@ -126,6 +127,7 @@ pub const QUERY_FILENAME_PREFIXES: &[(
     ("overrides", |q| &mut q.overrides),
     ("redactions", |q| &mut q.redactions),
     ("runnables", |q| &mut q.runnables),
+    ("textobjects", |q| &mut q.text_objects),
 ];
 
 /// Tree-sitter language queries for a given language.
@@ -140,6 +142,7 @@ pub struct LanguageQueries {
     pub overrides: Option<Cow<'static, str>>,
     pub redactions: Option<Cow<'static, str>>,
     pub runnables: Option<Cow<'static, str>>,
+    pub text_objects: Option<Cow<'static, str>>,
 }
 
 #[derive(Clone, Default)]
@@ -155,6 +158,7 @@ impl LanguageRegistry {
                 languages: Vec::new(),
                 available_languages: Vec::new(),
                 grammars: Default::default(),
+                textmate_grammars: Default::default(),
                 language_settings: Default::default(),
                 loading_languages: Default::default(),
                 lsp_adapters: Default::default(),
@@ -366,6 +370,50 @@ impl LanguageRegistry {
         );
     }
 
+    /// Adds a TextMate grammar to the registry, for the language with the same name as the grammar.
+    /// If there's no such language, a language is registered for the file types of the grammar.
+    pub fn register_textmate_grammar(&self, grammar: Arc<TextMateGrammar>) {
+        let name: Arc<str> = grammar.grammar().name().into();
+        let mut state = self.state.write();
+        state
+            .textmate_grammars
+            .insert(name.clone(), grammar.clone());
+        if state
+            .available_languages
+            .iter()
+            .any(|language| language.name == name)
+        {
+            // Reload the language if it was loaded already, to use the grammar.
+            state.languages.retain(|language| language.name() != name);
+            for language in &mut state.available_languages {
+                if language.name == name {
+                    language.loaded = false;
+                }
+            }
+            state.version += 1;
+            state.reload_count += 1;
+            *state.subscription.0.borrow_mut() = ();
+            return;
+        }
+        drop(state);
+
+        let matcher = LanguageMatcher {
+            path_suffixes: grammar.grammar().file_types().to_vec(),
+            ..Default::default()
+        };
+        self.register_language(name.clone(), None, matcher.clone(), move || {
+            Ok((
+                LanguageConfig {
+                    name: name.clone(),
+                    matcher: matcher.clone(),
+                    ..Default::default()
+                },
+                LanguageQueries::default(),
+                None,
+            ))
+        });
+    }
+
     /// Adds paths to WASM grammar files, which can be loaded if needed.
     pub fn register_wasm_grammars(
         &self,
@@ -591,6 +639,8 @@ impl LanguageRegistry {
                     .spawn(async move {
                         let id = language.id;
                         let name = language.name.clone();
+                        let textmate_grammar =
+                            this.state.read().textmate_grammars.get(&name).cloned();
                         let language = async {
                             let (config, queries, provider) = (language.load)()?;
 
@@ -598,10 +648,12 @@ impl LanguageRegistry {
                                 let grammar = Some(this.get_or_load_grammar(grammar).await?);
                                 Language::new_with_id(id, config, grammar)
                                     .with_context_provider(provider)
+                                    .with_textmate_grammar(textmate_grammar)
                                     .with_queries(queries)
                             } else {
                                 Ok(Language::new_with_id(id, config, None)
-                                    .with_context_provider(provider))
+                                    .with_context_provider(provider)
+                                    .with_textmate_grammar(textmate_grammar))
                             }
                         }
                         .await;
@@ -771,7 +823,15 @@ impl LanguageRegistry {
 
                 delegate.update_status(adapter.name.clone(), LanguageServerBinaryStatus::None);
 
-                let binary = binary_result?;
+                let mut binary = binary_result?;
+                let project_env = delegate.project_env().await;
+                if !project_env.is_empty() {
+                    binary
+                        .env
+                        .get_or_insert_with(Default::default)
+                        .extend(project_env);
+                }
+                let binary = delegate.language_server_command(binary);
                 let options = adapter
                     .adapter
                     .clone()
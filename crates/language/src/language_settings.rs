@@ -4,7 +4,7 @@ use crate::{File, Language, LanguageServerName};
 use anyhow::Result;
 use collections::{HashMap, HashSet};
 use globset::{Glob, GlobMatcher, GlobSet, GlobSetBuilder};
-use gpui::AppContext;
+use gpui::{AppContext, FontFeatures};
 use itertools::{Either, Itertools};
 use schemars::{
     schema::{InstanceType, ObjectValidation, Schema, SchemaObject},
@@ -118,6 +118,13 @@ pub struct LanguageSettings {
     pub code_actions_on_format: HashMap<String, bool>,
     /// Whether to perform linked edits
     pub linked_edits: bool,
+    /// When to highlight files with a TextMate grammar.
+    pub textmate_highlighting: TextMateHighlighting,
+    /// Which Emmet abbreviations to expand when pressing tab.
+    pub emmet: EmmetMode,
+    /// The OpenType features to enable for rendering this language's buffers,
+    /// instead of the `buffer_font_features`.
+    pub buffer_font_features: Option<FontFeatures>,
 }
 
 impl LanguageSettings {
@@ -333,6 +340,21 @@ pub struct LanguageSettingsContent {
     ///
     /// Default: true
     pub linked_edits: Option<bool>,
+    /// When to highlight files with the TextMate grammar of their language,
+    /// from the `grammars` directory of the config directory.
+    ///
+    /// Default: fallback
+    pub textmate_highlighting: Option<TextMateHighlighting>,
+    /// Which Emmet abbreviations, like `ul>li*3` or `m10`, to expand when
+    /// pressing tab after them.
+    ///
+    /// Default: off
+    pub emmet: Option<EmmetMode>,
+    /// The OpenType features to enable for rendering this language's buffers,
+    /// like `{"calt": false}` to disable ligatures. Replaces the `buffer_font_features`.
+    ///
+    /// Default: null
+    pub buffer_font_features: Option<FontFeatures>,
 }
 
 /// The contents of the inline completion settings.
@@ -388,6 +410,33 @@ pub enum FormatOnSave {
     CodeActions(HashMap<String, bool>),
 }
 
+/// Controls when files are highlighted with a TextMate grammar.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TextMateHighlighting {
+    /// Use the TextMate grammar only for languages without a Tree-sitter grammar.
+    Fallback,
+    /// Use the TextMate grammar instead of the Tree-sitter grammar.
+    Always,
+    /// Never use the TextMate grammar.
+    Never,
+}
+
+/// Controls which Emmet abbreviations are expanded.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EmmetMode {
+    /// Don't expand abbreviations.
+    #[default]
+    Off,
+    /// Expand abbreviations into HTML elements.
+    Html,
+    /// Expand abbreviations into JSX elements.
+    Jsx,
+    /// Expand abbreviations into CSS declarations.
+    Css,
+}
+
 /// Controls how whitespace should be displayedin the editor.
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -786,6 +835,15 @@ fn merge_settings(settings: &mut LanguageSettings, src: &LanguageSettingsContent
         src.code_actions_on_format.clone(),
     );
     merge(&mut settings.linked_edits, src.linked_edits);
+    merge(
+        &mut settings.textmate_highlighting,
+        src.textmate_highlighting,
+    );
+    merge(&mut settings.emmet, src.emmet);
+    merge(
+        &mut settings.buffer_font_features,
+        src.buffer_font_features.clone().map(Some),
+    );
 
     merge(
         &mut settings.preferred_line_length,
@@ -12,7 +12,10 @@ use std::{
     collections::BinaryHeap,
     fmt, iter,
     ops::{Deref, DerefMut, Range},
-    sync::Arc,
+    sync::{
+        atomic::{self, AtomicUsize},
+        Arc,
+    },
 };
 use sum_tree::{Bias, SeekTarget, SumTree};
 use text::{Anchor, BufferSnapshot, OffsetRangeExt, Point, Rope, ToOffset, ToPoint};
@@ -377,11 +380,46 @@ impl SyntaxSnapshot {
         registry: Option<Arc<LanguageRegistry>>,
         root_language: Arc<Language>,
     ) {
+        self.reparse_with_cancellation_flag(text, registry, root_language, None);
+    }
+
+    /// Reparses the text like [`Self::reparse`], unless the flag is set to a
+    /// value other than zero before it completes. Returns whether it completed.
+    /// When it didn't, the trees are left as they were interpolated, so that
+    /// the highlights of the last parse are still shown where they were.
+    pub fn reparse_cancellable(
+        &mut self,
+        text: &BufferSnapshot,
+        registry: Option<Arc<LanguageRegistry>>,
+        root_language: Arc<Language>,
+        cancellation_flag: &AtomicUsize,
+    ) -> bool {
+        self.reparse_with_cancellation_flag(text, registry, root_language, Some(cancellation_flag))
+    }
+
+    fn reparse_with_cancellation_flag(
+        &mut self,
+        text: &BufferSnapshot,
+        registry: Option<Arc<LanguageRegistry>>,
+        root_language: Arc<Language>,
+        cancellation_flag: Option<&AtomicUsize>,
+    ) -> bool {
+        if is_cancelled(cancellation_flag) {
+            return false;
+        }
         let edit_ranges = text
             .edits_since::<usize>(&self.parsed_version)
             .map(|edit| edit.new)
             .collect::<Vec<_>>();
-        self.reparse_with_ranges(text, root_language.clone(), edit_ranges, registry.as_ref());
+        if !self.reparse_with_ranges(
+            text,
+            root_language.clone(),
+            edit_ranges,
+            registry.as_ref(),
+            cancellation_flag,
+        ) {
+            return false;
+        }
 
         if let Some(registry) = registry {
             if registry.version() != self.language_registry_version {
@@ -407,17 +445,21 @@ impl SyntaxSnapshot {
                 }
                 drop(cursor);
 
-                if !resolved_injection_ranges.is_empty() {
-                    self.reparse_with_ranges(
+                if !resolved_injection_ranges.is_empty()
+                    && !self.reparse_with_ranges(
                         text,
                         root_language,
                         resolved_injection_ranges,
                         Some(&registry),
-                    );
+                        cancellation_flag,
+                    )
+                {
+                    return false;
                 }
                 self.language_registry_version = registry.version();
             }
         }
+        true
     }
 
     fn reparse_with_ranges(
@@ -426,7 +468,8 @@ impl SyntaxSnapshot {
         root_language: Arc<Language>,
         invalidated_ranges: Vec<Range<usize>>,
         registry: Option<&Arc<LanguageRegistry>>,
-    ) {
+        cancellation_flag: Option<&AtomicUsize>,
+    ) -> bool {
         log::trace!("reparse. invalidated ranges:{:?}", invalidated_ranges);
 
         let max_depth = self.layers.summary().max_depth;
@@ -610,9 +653,11 @@ impl SyntaxSnapshot {
                             step_start_byte,
                             included_ranges,
                             Some(old_tree.clone()),
+                            cancellation_flag,
                         );
                         match result {
                             Ok(t) => tree = t,
+                            Err(_) if is_cancelled(cancellation_flag) => return false,
                             Err(e) => {
                                 log::error!("error parsing text: {:?}", e);
                                 continue;
@@ -663,9 +708,11 @@ impl SyntaxSnapshot {
                             step_start_byte,
                             included_ranges,
                             None,
+                            cancellation_flag,
                         );
                         match result {
                             Ok(t) => tree = t,
+                            Err(_) if is_cancelled(cancellation_flag) => return false,
                             Err(e) => {
                                 log::error!("error parsing text: {:?}", e);
                                 continue;
@@ -727,6 +774,7 @@ impl SyntaxSnapshot {
         self.parsed_version = text.version.clone();
         #[cfg(debug_assertions)]
         self.check_invariants(text);
+        true
     }
 
     #[cfg(debug_assertions)]
@@ -1181,23 +1229,35 @@ fn parse_text(
     start_byte: usize,
     ranges: Vec<tree_sitter::Range>,
     old_tree: Option<Tree>,
+    cancellation_flag: Option<&AtomicUsize>,
 ) -> anyhow::Result<Tree> {
     with_parser(|parser| {
         let mut chunks = text.chunks_in_range(start_byte..text.len());
         parser.set_included_ranges(&ranges)?;
         parser.set_language(&grammar.ts_language)?;
-        parser
-            .parse_with(
-                &mut move |offset, _| {
-                    chunks.seek(start_byte + offset);
-                    chunks.next().unwrap_or("").as_bytes()
-                },
-                old_tree.as_ref(),
-            )
-            .ok_or_else(|| anyhow::anyhow!("failed to parse"))
+        // The flag is unset before the parser is returned to the pool, so it
+        // doesn't outlive the flag.
+        unsafe { parser.set_cancellation_flag(cancellation_flag) };
+        let tree = parser.parse_with(
+            &mut move |offset, _| {
+                chunks.seek(start_byte + offset);
+                chunks.next().unwrap_or("").as_bytes()
+            },
+            old_tree.as_ref(),
+        );
+        unsafe { parser.set_cancellation_flag(None) };
+        if tree.is_none() {
+            // Otherwise, the next parse would resume the cancelled one.
+            parser.reset();
+        }
+        tree.ok_or_else(|| anyhow::anyhow!("failed to parse"))
     })
 }
 
+fn is_cancelled(cancellation_flag: Option<&AtomicUsize>) -> bool {
+    cancellation_flag.map_or(false, |flag| flag.load(atomic::Ordering::Relaxed) != 0)
+}
+
 #[allow(clippy::too_many_arguments)]
 fn get_injections(
     config: &InjectionConfig,
@@ -0,0 +1,218 @@
+use crate::{HighlightId, HighlightMap};
+use parking_lot::Mutex;
+use std::{ops::Range, sync::Arc};
+use text::{Anchor, ToOffset};
+use theme::SyntaxTheme;
+
+/// The highlight names, as used by the captures of highlight queries, that scopes
+/// starting with the given names are highlighted as. More specific scopes come first.
+const SCOPE_HIGHLIGHT_NAMES: &[(&str, &str)] = &[
+    ("comment.block.documentation", "comment.doc"),
+    ("comment", "comment"),
+    ("constant.character.escape", "string.escape"),
+    ("constant.numeric", "number"),
+    ("constant.language.boolean", "boolean"),
+    ("constant", "constant"),
+    ("entity.name.function", "function"),
+    ("entity.name.tag", "tag"),
+    ("entity.name.section", "title"),
+    ("entity.name", "type"),
+    ("entity.other.attribute-name", "attribute"),
+    ("entity.other.inherited-class", "type"),
+    ("keyword.operator", "operator"),
+    ("keyword", "keyword"),
+    ("markup.heading", "title"),
+    ("markup.bold", "emphasis.strong"),
+    ("markup.italic", "emphasis"),
+    ("markup.underline.link", "link_uri"),
+    ("markup.raw", "text.literal"),
+    ("punctuation.definition.comment", "comment"),
+    ("punctuation.definition.string", "string"),
+    ("punctuation.separator", "punctuation.delimiter"),
+    ("punctuation", "punctuation"),
+    ("storage.type.function", "keyword"),
+    ("storage.type", "type"),
+    ("storage", "keyword"),
+    ("string.regexp", "string.regex"),
+    ("string", "string"),
+    ("support.function", "function"),
+    ("support.constant", "constant"),
+    ("support.variable", "variable.special"),
+    ("support", "type"),
+    ("variable.language", "variable.special"),
+    ("variable.other.constant", "constant"),
+    ("variable", "variable"),
+];
+
+/// A [TextMate grammar](textmate::Grammar), which highlights languages that
+/// don't have a Tree-sitter grammar.
+pub struct TextMateGrammar {
+    grammar: textmate::Grammar,
+    highlight_names: Vec<&'static str>,
+    /// The index in `highlight_names` of each scope of the grammar.
+    scope_highlights: Vec<Option<u32>>,
+    highlight_map: Mutex<HighlightMap>,
+}
+
+/// The highlights of a buffer's text, computed by a [`TextMateGrammar`].
+pub(crate) struct TextMateHighlights {
+    grammar: Arc<TextMateGrammar>,
+    /// Sorted, non-overlapping ranges, with the index of their highlight name.
+    highlights: Vec<(Range<Anchor>, u32)>,
+}
+
+impl TextMateGrammar {
+    pub fn new(grammar: textmate::Grammar) -> Self {
+        let mut highlight_names = Vec::new();
+        let scope_highlights = grammar
+            .scope_names()
+            .iter()
+            .map(|scope| {
+                let (_, highlight_name) = SCOPE_HIGHLIGHT_NAMES.iter().find(|(prefix, _)| {
+                    scope
+                        .strip_prefix(prefix)
+                        .map_or(false, |suffix| suffix.is_empty() || suffix.starts_with('.'))
+                })?;
+                let ix = highlight_names
+                    .iter()
+                    .position(|name| name == highlight_name)
+                    .unwrap_or_else(|| {
+                        highlight_names.push(*highlight_name);
+                        highlight_names.len() - 1
+                    });
+                Some(ix as u32)
+            })
+            .collect();
+        Self {
+            grammar,
+            highlight_names,
+            scope_highlights,
+            highlight_map: Default::default(),
+        }
+    }
+
+    pub fn grammar(&self) -> &textmate::Grammar {
+        &self.grammar
+    }
+
+    pub(crate) fn set_theme(&self, theme: &SyntaxTheme) {
+        *self.highlight_map.lock() = HighlightMap::new(&self.highlight_names, theme);
+    }
+
+    pub(crate) fn highlight_map(&self) -> HighlightMap {
+        self.highlight_map.lock().clone()
+    }
+
+    /// Tokenizes the whole text, which is slow, and thus done on a background thread.
+    pub(crate) fn highlight(self: Arc<Self>, text: &text::BufferSnapshot) -> TextMateHighlights {
+        let mut state = self.grammar.initial_state();
+        let mut highlights = Vec::<(Range<usize>, u32)>::new();
+        let mut line_start = 0;
+        for line in text.text().split_inclusive('\n') {
+            self.grammar
+                .tokenize_line(line, &mut state, |range, scopes| {
+                    let Some(highlight) = scopes
+                        .iter()
+                        .rev()
+                        .find_map(|scope| self.scope_highlights[scope.0 as usize])
+                    else {
+                        return;
+                    };
+                    let range = line_start + range.start..line_start + range.end;
+                    match highlights.last_mut() {
+                        Some((last_range, last_highlight))
+                            if last_range.end == range.start && *last_highlight == highlight =>
+                        {
+                            last_range.end = range.end;
+                        }
+                        _ => highlights.push((range, highlight)),
+                    }
+                });
+            line_start += line.len();
+        }
+
+        // Text inserted at the boundary of two highlights extends the highlight before it,
+        // until the text is highlighted again.
+        let highlights = highlights
+            .into_iter()
+            .map(|(range, highlight)| {
+                (
+                    text.anchor_after(range.start)..text.anchor_after(range.end),
+                    highlight,
+                )
+            })
+            .collect();
+        TextMateHighlights {
+            grammar: self,
+            highlights,
+        }
+    }
+}
+
+impl TextMateHighlights {
+    pub(crate) fn in_range(
+        &self,
+        range: Range<usize>,
+        text: &text::BufferSnapshot,
+    ) -> Vec<(Range<usize>, HighlightId)> {
+        let highlight_map = self.grammar.highlight_map();
+        let start_ix = self.highlights.partition_point(|(highlight_range, _)| {
+            highlight_range.end.to_offset(text) <= range.start
+        });
+        self.highlights[start_ix..]
+            .iter()
+            .map(|(highlight_range, highlight)| {
+                (
+                    highlight_range.start.to_offset(text)..highlight_range.end.to_offset(text),
+                    highlight_map.get(*highlight),
+                )
+            })
+            .take_while(|(highlight_range, _)| highlight_range.start < range.end)
+            .filter(|(highlight_range, _)| !highlight_range.is_empty())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_highlight_names() {
+        let grammar = textmate::Grammar::from_json(
+            r#"{
+                "scopeName": "source.example",
+                "patterns": [
+                    { "match": "//.*", "name": "comment.line.double-slash.example" },
+                    { "match": "\\d+", "name": "constant.numeric.integer.example" },
+                    { "match": "\\+", "name": "keyword.operator.arithmetic.example" },
+                    { "match": "\\w+", "name": "meta.identifier.example" }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let grammar = TextMateGrammar::new(grammar);
+        let highlight_names = grammar
+            .grammar
+            .scope_names()
+            .iter()
+            .zip(&grammar.scope_highlights)
+            .map(|(scope, highlight)| {
+                (
+                    scope.as_str(),
+                    highlight.map(|ix| grammar.highlight_names[ix as usize]),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            highlight_names,
+            [
+                ("comment.line.double-slash.example", Some("comment")),
+                ("constant.numeric.integer.example", Some("number")),
+                ("keyword.operator.arithmetic.example", Some("operator")),
+                ("meta.identifier.example", None),
+                ("source.example", None),
+            ]
+        );
+    }
+}
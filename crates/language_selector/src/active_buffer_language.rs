@@ -76,4 +76,8 @@ impl StatusItemView for ActiveBufferLanguage {
 
         cx.notify();
     }
+
+    fn status_item_id() -> Option<&'static str> {
+        Some("language")
+    }
 }
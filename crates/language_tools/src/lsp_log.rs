@@ -4,8 +4,8 @@ use editor::{actions::MoveToEnd, Editor, EditorEvent};
 use futures::{channel::mpsc, StreamExt};
 use gpui::{
     actions, div, AnchorCorner, AnyElement, AppContext, Context, EventEmitter, FocusHandle,
-    FocusableView, IntoElement, Model, ModelContext, ParentElement, Render, Styled, Subscription,
-    View, ViewContext, VisualContext, WeakModel, WindowContext,
+    FocusableView, Global, IntoElement, Model, ModelContext, ParentElement, Render, Styled,
+    Subscription, View, ViewContext, VisualContext, WeakModel, WindowContext,
 };
 use language::{LanguageServerId, LanguageServerName};
 use lsp::{IoKind, LanguageServer};
@@ -30,6 +30,10 @@ pub struct LogStore {
     io_tx: mpsc::UnboundedSender<(LanguageServerId, IoKind, String)>,
 }
 
+struct GlobalLogStore(Model<LogStore>);
+
+impl Global for GlobalLogStore {}
+
 struct ProjectState {
     _subscriptions: [gpui::Subscription; 2],
 }
@@ -97,6 +101,7 @@ actions!(debug, [OpenLanguageServerLogs]);
 
 pub fn init(cx: &mut AppContext) {
     let log_store = cx.new_model(|cx| LogStore::new(cx));
+    cx.set_global(GlobalLogStore(log_store.clone()));
 
     cx.observe_new_views(move |workspace: &mut Workspace, cx| {
         let project = workspace.project();
@@ -124,6 +129,12 @@ pub fn init(cx: &mut AppContext) {
 }
 
 impl LogStore {
+    /// The store that collects the logs of the language servers of every workspace.
+    pub fn global(cx: &AppContext) -> Option<Model<Self>> {
+        cx.try_global::<GlobalLogStore>()
+            .map(|log_store| log_store.0.clone())
+    }
+
     pub fn new(cx: &mut ModelContext<Self>) -> Self {
         let (io_tx, mut io_rx) = mpsc::unbounded();
 
@@ -303,6 +314,45 @@ impl LogStore {
         cx.notify();
     }
 
+    /// The name and log of each language server whose logs are stored.
+    pub fn language_server_logs(
+        &self,
+        cx: &AppContext,
+    ) -> Vec<(LanguageServerId, LanguageServerName, String)> {
+        let mut names = HashMap::default();
+        for project in self.projects.keys().filter_map(|project| project.upgrade()) {
+            let project = project.read(cx);
+            names.extend(
+                project
+                    .language_servers()
+                    .map(|(server_id, name, _)| (server_id, name)),
+            );
+            names.extend(
+                project
+                    .supplementary_language_servers()
+                    .map(|(server_id, (name, _))| (*server_id, name.clone())),
+            );
+        }
+
+        let mut logs = self
+            .language_servers
+            .iter()
+            .map(|(server_id, state)| {
+                let name = match &state.kind {
+                    LanguageServerKind::Global { name } => Some(name.clone()),
+                    LanguageServerKind::Local { .. } => names.get(server_id).cloned(),
+                };
+                (
+                    *server_id,
+                    name.unwrap_or_else(|| LanguageServerName("unknown".into())),
+                    log_contents(&state.log_messages),
+                )
+            })
+            .collect::<Vec<_>>();
+        logs.sort_by_key(|(server_id, _, _)| *server_id);
+        logs
+    }
+
     fn server_logs(&self, server_id: LanguageServerId) -> Option<&VecDeque<String>> {
         Some(&self.language_servers.get(&server_id)?.log_messages)
     }
@@ -0,0 +1,19 @@
+(function_definition
+    body: (_
+        "{"
+        (_)* @function.inside
+        "}")) @function.around
+
+(struct_specifier
+    body: (_
+        "{"
+        (_)* @class.inside
+        "}")) @class.around
+
+(enum_specifier
+    body: (_
+        "{"
+        [(_) ","?]* @class.inside
+        "}")) @class.around
+
+(comment)+ @comment.around
@@ -0,0 +1,31 @@
+(function_definition
+    body: (_
+        "{"
+        (_)* @function.inside
+        "}")) @function.around
+
+(lambda_expression
+    body: (_
+        "{"
+        (_)* @function.inside
+        "}")) @function.around
+
+(class_specifier
+    body: (_
+        "{"
+        (_)* @class.inside
+        "}")) @class.around
+
+(struct_specifier
+    body: (_
+        "{"
+        (_)* @class.inside
+        "}")) @class.around
+
+(enum_specifier
+    body: (_
+        "{"
+        [(_) ","?]* @class.inside
+        "}")) @class.around
+
+(comment)+ @comment.around
@@ -0,0 +1,39 @@
+(function_declaration
+    body: (_
+        "{"
+        (_)* @function.inside
+        "}")) @function.around
+
+(function_expression
+    body: (_
+        "{"
+        (_)* @function.inside
+        "}")) @function.around
+
+(method_definition
+    body: (_
+        "{"
+        (_)* @function.inside
+        "}")) @function.around
+
+(arrow_function
+    body: (statement_block
+        "{"
+        (_)* @function.inside
+        "}")) @function.around
+
+(arrow_function) @function.around
+
+(class_declaration
+    body: (_
+        "{"
+        [(_) ";"?]* @class.inside
+        "}")) @class.around
+
+(interface_declaration
+    body: (_
+        "{"
+        [(_) ";"?]* @class.inside
+        "}")) @class.around
+
+(comment)+ @comment.around
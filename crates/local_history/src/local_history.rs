@@ -0,0 +1,191 @@
+mod local_history_settings;
+mod local_history_view;
+mod snapshot_store;
+
+use std::{
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::Result;
+use collections::HashMap;
+use editor::{Editor, EditorMode};
+use fs::Fs;
+use gpui::{
+    actions, AppContext, Context, EntityId, EventEmitter, Global, Model, ModelContext,
+    Subscription, Task,
+};
+use language::Buffer;
+use settings::Settings;
+use util::ResultExt;
+use workspace::Workspace;
+
+pub use local_history_settings::LocalHistorySettings;
+pub use local_history_view::LocalHistoryView;
+pub use snapshot_store::{Snapshot, SnapshotLimits, SnapshotStore};
+
+actions!(local_history, [ViewLocalHistory]);
+
+pub fn init(fs: Arc<dyn Fs>, cx: &mut AppContext) {
+    LocalHistorySettings::register(cx);
+
+    let store = Arc::new(SnapshotStore::new(fs, paths::local_history_dir().clone()));
+    let local_history = cx.new_model(|_| LocalHistory::new(store));
+    cx.set_global(GlobalLocalHistory(local_history.clone()));
+
+    cx.observe_new_views(move |editor: &mut Editor, cx| {
+        if editor.mode() != EditorMode::Full {
+            return;
+        }
+        if let Some(buffer) = editor.buffer().read(cx).as_singleton() {
+            local_history.update(cx, |local_history, cx| {
+                local_history.track_buffer(buffer, cx)
+            });
+        }
+    })
+    .detach();
+
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace.register_action(LocalHistoryView::deploy);
+    })
+    .detach();
+}
+
+struct GlobalLocalHistory(Model<LocalHistory>);
+
+impl Global for GlobalLocalHistory {}
+
+/// Emitted after a new snapshot of a file has been saved.
+pub struct SnapshotSaved {
+    pub abs_path: PathBuf,
+}
+
+/// Takes snapshots of the files that are open in editors, whenever they are
+/// saved and periodically while they have unsaved changes.
+pub struct LocalHistory {
+    store: Arc<SnapshotStore>,
+    buffers: HashMap<EntityId, TrackedBuffer>,
+}
+
+struct TrackedBuffer {
+    pending_snapshot: Option<Task<()>>,
+    _subscriptions: [Subscription; 2],
+}
+
+impl EventEmitter<SnapshotSaved> for LocalHistory {}
+
+impl LocalHistory {
+    fn new(store: Arc<SnapshotStore>) -> Self {
+        Self {
+            store,
+            buffers: HashMap::default(),
+        }
+    }
+
+    pub fn global(cx: &AppContext) -> Model<Self> {
+        cx.global::<GlobalLocalHistory>().0.clone()
+    }
+
+    pub fn store(&self) -> Arc<SnapshotStore> {
+        self.store.clone()
+    }
+
+    fn track_buffer(&mut self, buffer: Model<Buffer>, cx: &mut ModelContext<Self>) {
+        let buffer_id = buffer.entity_id();
+        if self.buffers.contains_key(&buffer_id) {
+            return;
+        }
+
+        let subscriptions = [
+            cx.subscribe(&buffer, Self::handle_buffer_event),
+            cx.observe_release(&buffer, move |this, _, _| {
+                this.buffers.remove(&buffer_id);
+            }),
+        ];
+        self.buffers.insert(
+            buffer_id,
+            TrackedBuffer {
+                pending_snapshot: None,
+                _subscriptions: subscriptions,
+            },
+        );
+    }
+
+    fn handle_buffer_event(
+        &mut self,
+        buffer: Model<Buffer>,
+        event: &language::Event,
+        cx: &mut ModelContext<Self>,
+    ) {
+        let settings = *LocalHistorySettings::get_global(cx);
+        if !settings.enabled {
+            return;
+        }
+        let buffer_id = buffer.entity_id();
+
+        match event {
+            language::Event::Saved => {
+                if let Some(tracked_buffer) = self.buffers.get_mut(&buffer_id) {
+                    tracked_buffer.pending_snapshot = None;
+                }
+                self.take_snapshot(&buffer, cx).detach_and_log_err(cx);
+            }
+            language::Event::Edited if settings.snapshot_interval > 0 => {
+                let Some(tracked_buffer) = self.buffers.get_mut(&buffer_id) else {
+                    return;
+                };
+                if tracked_buffer.pending_snapshot.is_some() {
+                    return;
+                }
+
+                let interval = Duration::from_secs(settings.snapshot_interval);
+                tracked_buffer.pending_snapshot = Some(cx.spawn(|this, mut cx| async move {
+                    cx.background_executor().timer(interval).await;
+                    this.update(&mut cx, |this, cx| {
+                        if let Some(tracked_buffer) = this.buffers.get_mut(&buffer_id) {
+                            tracked_buffer.pending_snapshot = None;
+                        }
+                        if buffer.read(cx).is_dirty() {
+                            this.take_snapshot(&buffer, cx).detach_and_log_err(cx);
+                        }
+                    })
+                    .log_err();
+                }));
+            }
+            _ => {}
+        }
+    }
+
+    fn take_snapshot(
+        &self,
+        buffer: &Model<Buffer>,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let buffer = buffer.read(cx);
+        let Some(abs_path) = buffer
+            .file()
+            .and_then(|file| file.as_local())
+            .map(|file| file.abs_path(cx))
+        else {
+            return Task::ready(Ok(()));
+        };
+        let text = buffer.text();
+        let limits = LocalHistorySettings::get_global(cx).snapshot_limits();
+        let store = self.store.clone();
+
+        cx.spawn(|this, mut cx| async move {
+            let saved = cx
+                .background_executor()
+                .spawn({
+                    let abs_path = abs_path.clone();
+                    async move { store.save(&abs_path, text, SystemTime::now(), limits).await }
+                })
+                .await?;
+            if saved {
+                this.update(&mut cx, |_, cx| cx.emit(SnapshotSaved { abs_path }))?;
+            }
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,64 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+use crate::SnapshotLimits;
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct LocalHistorySettings {
+    pub enabled: bool,
+    pub max_snapshots_per_file: usize,
+    pub max_snapshot_size: u64,
+    pub max_total_size: u64,
+    pub snapshot_interval: u64,
+}
+
+impl LocalHistorySettings {
+    pub fn snapshot_limits(&self) -> SnapshotLimits {
+        SnapshotLimits {
+            max_snapshots_per_file: self.max_snapshots_per_file,
+            max_snapshot_size: self.max_snapshot_size,
+            max_total_size: self.max_total_size,
+        }
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct LocalHistorySettingsContent {
+    /// Whether to keep snapshots of files as they are edited.
+    ///
+    /// Default: true
+    pub enabled: Option<bool>,
+    /// The number of snapshots to keep for each file, after which the oldest
+    /// ones are removed.
+    ///
+    /// Default: 50
+    pub max_snapshots_per_file: Option<usize>,
+    /// The size, in bytes, above which no snapshots of a file are taken.
+    ///
+    /// Default: 1048576 (1 MiB)
+    pub max_snapshot_size: Option<u64>,
+    /// The size, in bytes, that the snapshots of all files may take, after
+    /// which the oldest ones are removed.
+    ///
+    /// Default: 104857600 (100 MiB)
+    pub max_total_size: Option<u64>,
+    /// How often, in seconds, to take a snapshot of a file while it has
+    /// unsaved changes. Snapshots are always taken when a file is saved.
+    ///
+    /// Default: 300
+    pub snapshot_interval: Option<u64>,
+}
+
+impl Settings for LocalHistorySettings {
+    const KEY: Option<&'static str> = Some("local_history");
+
+    type FileContent = LocalHistorySettingsContent;
+
+    fn load(
+        sources: SettingsSources<Self::FileContent>,
+        _: &mut gpui::AppContext,
+    ) -> anyhow::Result<Self> {
+        sources.json_merge()
+    }
+}
@@ -0,0 +1,286 @@
+use std::{path::PathBuf, sync::Arc};
+
+use editor::{actions::ExpandAllHunkDiffs, Editor, EditorEvent};
+use gpui::{
+    AnyElement, AppContext, EventEmitter, FocusHandle, FocusableView, IntoElement, Model,
+    ParentElement, Render, Styled, Subscription, Task, View, ViewContext, VisualContext,
+    WindowContext,
+};
+use language::Buffer;
+use time::OffsetDateTime;
+use time_format::TimestampFormat;
+use ui::{prelude::*, Button, ListItem};
+use util::ResultExt;
+use workspace::{
+    item::{Item, TabContentParams},
+    notifications::NotificationId,
+    Toast, Workspace,
+};
+
+use crate::{LocalHistory, Snapshot, SnapshotSaved, SnapshotStore, ViewLocalHistory};
+
+/// Lists the snapshots of a file, showing how each one differs from the
+/// file's current contents and allowing it to be restored.
+pub struct LocalHistoryView {
+    abs_path: PathBuf,
+    buffer: Model<Buffer>,
+    store: Arc<SnapshotStore>,
+    snapshots: Vec<Snapshot>,
+    selected_snapshot: Option<(usize, String)>,
+    editor: View<Editor>,
+    focus_handle: FocusHandle,
+    pending_load: Option<Task<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl LocalHistoryView {
+    pub fn deploy(
+        workspace: &mut Workspace,
+        _: &ViewLocalHistory,
+        cx: &mut ViewContext<Workspace>,
+    ) {
+        let file = workspace
+            .active_item_as::<Editor>(cx)
+            .and_then(|editor| editor.read(cx).buffer().read(cx).as_singleton())
+            .and_then(|buffer| {
+                let abs_path = buffer.read(cx).file()?.as_local()?.abs_path(cx);
+                Some((buffer, abs_path))
+            });
+        let Some((buffer, abs_path)) = file else {
+            struct NoLocalHistory;
+            workspace.show_toast(
+                Toast::new(
+                    NotificationId::unique::<NoLocalHistory>(),
+                    "Local history is only available for local files",
+                ),
+                cx,
+            );
+            return;
+        };
+
+        let existing = workspace
+            .active_pane()
+            .read(cx)
+            .items()
+            .filter_map(|item| item.downcast::<LocalHistoryView>())
+            .find(|view| view.read(cx).abs_path == abs_path);
+        if let Some(existing) = existing {
+            workspace.activate_item(&existing, cx);
+        } else {
+            let view = cx.new_view(|cx| LocalHistoryView::new(buffer, abs_path, cx));
+            workspace.add_item_to_active_pane(Box::new(view), None, cx);
+        }
+    }
+
+    pub fn new(buffer: Model<Buffer>, abs_path: PathBuf, cx: &mut ViewContext<Self>) -> Self {
+        let local_history = LocalHistory::global(cx);
+        let store = local_history.read(cx).store();
+
+        let language = buffer.read(cx).language().cloned();
+        let preview_buffer = cx.new_model(|cx| {
+            let mut preview_buffer = Buffer::local("", cx);
+            preview_buffer.set_language(language, cx);
+            preview_buffer
+        });
+        let editor = cx.new_view(|cx| {
+            let mut editor = Editor::for_buffer(preview_buffer, None, cx);
+            editor.set_read_only(true);
+            editor.set_show_inline_completions(false);
+            editor
+        });
+
+        let subscriptions = vec![
+            cx.subscribe(&editor, |_, editor, event: &EditorEvent, cx| {
+                if let EditorEvent::DiffBaseChanged = event {
+                    editor.update(cx, |editor, cx| {
+                        editor.expand_all_hunk_diffs(&ExpandAllHunkDiffs, cx)
+                    });
+                }
+            }),
+            cx.subscribe(&local_history, |this, _, event: &SnapshotSaved, cx| {
+                if event.abs_path == this.abs_path {
+                    this.reload(cx);
+                }
+            }),
+        ];
+
+        let mut this = Self {
+            abs_path,
+            buffer,
+            store,
+            snapshots: Vec::new(),
+            selected_snapshot: None,
+            editor,
+            focus_handle: cx.focus_handle(),
+            pending_load: None,
+            _subscriptions: subscriptions,
+        };
+        this.reload(cx);
+        this
+    }
+
+    /// Reloads the list of snapshots, selecting the newest one.
+    fn reload(&mut self, cx: &mut ViewContext<Self>) {
+        let store = self.store.clone();
+        let abs_path = self.abs_path.clone();
+        self.pending_load = Some(cx.spawn(|this, mut cx| async move {
+            let snapshots = cx
+                .background_executor()
+                .spawn(async move { store.snapshots(&abs_path).await })
+                .await
+                .log_err()
+                .unwrap_or_default();
+            this.update(&mut cx, |this, cx| {
+                this.snapshots = snapshots;
+                this.pending_load = None;
+                this.select_snapshot(0, cx);
+                cx.notify();
+            })
+            .log_err();
+        }));
+    }
+
+    fn select_snapshot(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        let Some(snapshot) = self.snapshots.get(ix).cloned() else {
+            self.selected_snapshot = None;
+            return;
+        };
+
+        let store = self.store.clone();
+        self.pending_load = Some(cx.spawn(|this, mut cx| async move {
+            let text = cx
+                .background_executor()
+                .spawn(async move { store.load(&snapshot).await })
+                .await
+                .log_err();
+            this.update(&mut cx, |this, cx| {
+                this.pending_load = None;
+                if let Some(text) = text {
+                    this.show_snapshot(ix, text, cx);
+                }
+            })
+            .log_err();
+        }));
+    }
+
+    fn show_snapshot(&mut self, ix: usize, text: String, cx: &mut ViewContext<Self>) {
+        let current_text = self.buffer.read(cx).text();
+        if let Some(preview_buffer) = self.editor.read(cx).buffer().read(cx).as_singleton() {
+            preview_buffer.update(cx, |preview_buffer, cx| {
+                preview_buffer.set_text(text.as_str(), cx);
+                preview_buffer.set_diff_base(Some(current_text), cx);
+            });
+        }
+        self.selected_snapshot = Some((ix, text));
+        cx.notify();
+    }
+
+    /// Replaces the file's contents with the selected snapshot. The change can
+    /// be undone in the file's editor, and isn't written to disk until saved.
+    fn restore_selected_snapshot(&mut self, cx: &mut ViewContext<Self>) {
+        let Some((ix, text)) = self.selected_snapshot.clone() else {
+            return;
+        };
+        self.buffer.update(cx, |buffer, cx| {
+            buffer.set_text(text.as_str(), cx);
+        });
+        self.show_snapshot(ix, text, cx);
+    }
+
+    fn render_snapshot(&self, ix: usize, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let snapshot = &self.snapshots[ix];
+        let timestamp = time_format::format_localized_timestamp(
+            OffsetDateTime::from(snapshot.timestamp),
+            OffsetDateTime::now_utc(),
+            cx.local_timezone(),
+            TimestampFormat::EnhancedAbsolute,
+        );
+        let selected = self
+            .selected_snapshot
+            .as_ref()
+            .map_or(false, |(selected_ix, _)| *selected_ix == ix);
+
+        ListItem::new(ix)
+            .selected(selected)
+            .child(Label::new(timestamp))
+            .on_click(cx.listener(move |this, _, cx| this.select_snapshot(ix, cx)))
+    }
+}
+
+impl Render for LocalHistoryView {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let snapshot_list = v_flex()
+            .id("local-history-snapshots")
+            .w(px(240.))
+            .h_full()
+            .p_1()
+            .border_r_1()
+            .border_color(cx.theme().colors().border)
+            .overflow_y_scroll()
+            .when(self.snapshots.is_empty(), |list| {
+                list.child(
+                    Label::new("No snapshots of this file")
+                        .color(Color::Muted)
+                        .size(LabelSize::Small),
+                )
+            })
+            .children((0..self.snapshots.len()).map(|ix| self.render_snapshot(ix, cx)));
+
+        let header = h_flex()
+            .p_1()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(Label::new(self.abs_path.to_string_lossy().to_string()).color(Color::Muted))
+            .child(
+                Button::new("restore-snapshot", "Restore")
+                    .disabled(self.selected_snapshot.is_none())
+                    .on_click(cx.listener(|this, _, cx| this.restore_selected_snapshot(cx))),
+            );
+
+        h_flex()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(snapshot_list)
+            .child(
+                v_flex()
+                    .flex_1()
+                    .h_full()
+                    .child(header)
+                    .child(div().flex_1().child(self.editor.clone())),
+            )
+    }
+}
+
+impl FocusableView for LocalHistoryView {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<()> for LocalHistoryView {}
+
+impl Item for LocalHistoryView {
+    type Event = ();
+
+    fn tab_content(&self, params: TabContentParams, _: &WindowContext) -> AnyElement {
+        let file_name = self
+            .abs_path
+            .file_name()
+            .unwrap_or_else(|| self.abs_path.as_os_str())
+            .to_string_lossy();
+        Label::new(format!("History: {file_name}"))
+            .single_line()
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+}
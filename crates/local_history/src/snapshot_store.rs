@@ -0,0 +1,314 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Result;
+use fs::{Fs, RemoveOptions};
+use futures::StreamExt;
+use sha2::{Digest, Sha256};
+
+/// A copy of a file's contents taken at a point in time.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    pub path: PathBuf,
+    pub timestamp: SystemTime,
+}
+
+/// The limits on the snapshots that are kept.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SnapshotLimits {
+    /// The number of snapshots to keep for each file.
+    pub max_snapshots_per_file: usize,
+    /// The size, in bytes, above which no snapshots of a file are taken.
+    pub max_snapshot_size: u64,
+    /// The size, in bytes, of the snapshots of all files, above which the
+    /// oldest snapshots are removed.
+    pub max_total_size: u64,
+}
+
+/// Stores the snapshots of files on disk.
+///
+/// The snapshots of each file live in a directory named after a hash of the
+/// file's path, with one file per snapshot named after the number of
+/// milliseconds since the epoch at which it was taken.
+pub struct SnapshotStore {
+    fs: Arc<dyn Fs>,
+    root: PathBuf,
+}
+
+impl SnapshotStore {
+    pub fn new(fs: Arc<dyn Fs>, root: PathBuf) -> Self {
+        Self { fs, root }
+    }
+
+    fn snapshots_dir(&self, abs_path: &Path) -> PathBuf {
+        let digest = Sha256::digest(abs_path.to_string_lossy().as_bytes());
+        self.root.join(format!("{digest:x}"))
+    }
+
+    /// Returns the snapshots of the file at the given path, newest first.
+    pub async fn snapshots(&self, abs_path: &Path) -> Result<Vec<Snapshot>> {
+        let dir = self.snapshots_dir(abs_path);
+        if !self.fs.is_dir(&dir).await {
+            return Ok(Vec::new());
+        }
+
+        let mut entries = self.fs.read_dir(&dir).await?;
+        let mut snapshots = Vec::new();
+        while let Some(path) = entries.next().await {
+            let path = path?;
+            let Some(millis) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse::<u64>().ok())
+            else {
+                continue;
+            };
+            snapshots.push(Snapshot {
+                path,
+                timestamp: UNIX_EPOCH + Duration::from_millis(millis),
+            });
+        }
+        snapshots.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        Ok(snapshots)
+    }
+
+    pub async fn load(&self, snapshot: &Snapshot) -> Result<String> {
+        self.fs.load(&snapshot.path).await
+    }
+
+    /// Saves a snapshot of the file at the given path, unless its text is the
+    /// same as the latest snapshot's or is larger than the limits allow. The
+    /// oldest snapshots are removed so that at most `max_snapshots_per_file`
+    /// are kept for the file, and that all of them fit in `max_total_size`.
+    ///
+    /// Returns whether a snapshot was saved.
+    pub async fn save(
+        &self,
+        abs_path: &Path,
+        text: String,
+        timestamp: SystemTime,
+        limits: SnapshotLimits,
+    ) -> Result<bool> {
+        let max_snapshots = limits.max_snapshots_per_file;
+        if max_snapshots == 0 || text.len() as u64 > limits.max_snapshot_size {
+            return Ok(false);
+        }
+
+        let snapshots = self.snapshots(abs_path).await?;
+        if let Some(latest) = snapshots.first() {
+            if self.load(latest).await.ok().as_deref() == Some(text.as_str()) {
+                return Ok(false);
+            }
+        }
+
+        let dir = self.snapshots_dir(abs_path);
+        self.fs.create_dir(&dir).await?;
+        let millis = timestamp.duration_since(UNIX_EPOCH)?.as_millis();
+        self.fs
+            .atomic_write(dir.join(format!("{millis}.txt")), text)
+            .await?;
+
+        for snapshot in snapshots.iter().skip(max_snapshots - 1) {
+            self.fs
+                .remove_file(
+                    &snapshot.path,
+                    RemoveOptions {
+                        recursive: false,
+                        ignore_if_not_exists: true,
+                    },
+                )
+                .await?;
+        }
+        self.remove_oldest_snapshots(limits.max_total_size).await?;
+
+        Ok(true)
+    }
+
+    /// Removes the oldest snapshots of any file, until the snapshots of all
+    /// files take at most `max_total_size` bytes.
+    async fn remove_oldest_snapshots(&self, max_total_size: u64) -> Result<()> {
+        let mut snapshots = Vec::new();
+        let mut dirs = self.fs.read_dir(&self.root).await?;
+        while let Some(dir) = dirs.next().await {
+            let dir = dir?;
+            if !self.fs.is_dir(&dir).await {
+                continue;
+            }
+            let mut entries = self.fs.read_dir(&dir).await?;
+            while let Some(path) = entries.next().await {
+                let path = path?;
+                let Some(millis) = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .and_then(|stem| stem.parse::<u64>().ok())
+                else {
+                    continue;
+                };
+                let Some(metadata) = self.fs.metadata(&path).await? else {
+                    continue;
+                };
+                snapshots.push((millis, metadata.len, path));
+            }
+        }
+
+        let mut total_size = snapshots.iter().map(|(_, len, _)| len).sum::<u64>();
+        if total_size <= max_total_size {
+            return Ok(());
+        }
+        snapshots.sort_by_key(|(millis, _, _)| *millis);
+        for (_, len, path) in snapshots {
+            if total_size <= max_total_size {
+                break;
+            }
+            self.fs
+                .remove_file(
+                    &path,
+                    RemoveOptions {
+                        recursive: false,
+                        ignore_if_not_exists: true,
+                    },
+                )
+                .await?;
+            total_size -= len;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::FakeFs;
+    use gpui::TestAppContext;
+
+    #[gpui::test]
+    async fn test_snapshot_store(cx: &mut TestAppContext) {
+        let fs = FakeFs::new(cx.executor());
+        let store = SnapshotStore::new(fs, PathBuf::from("/history"));
+        let path = Path::new("/project/main.rs");
+        let time = |millis| UNIX_EPOCH + Duration::from_millis(millis);
+        let limits = SnapshotLimits {
+            max_snapshots_per_file: 2,
+            ..LIMITS
+        };
+
+        assert!(store.snapshots(path).await.unwrap().is_empty());
+
+        assert!(store
+            .save(path, "one".into(), time(1), limits)
+            .await
+            .unwrap());
+        // The text hasn't changed since the latest snapshot.
+        assert!(!store
+            .save(path, "one".into(), time(2), limits)
+            .await
+            .unwrap());
+        assert!(store
+            .save(path, "two".into(), time(3), limits)
+            .await
+            .unwrap());
+        assert!(store
+            .save(path, "three".into(), time(4), limits)
+            .await
+            .unwrap());
+
+        let snapshots = store.snapshots(path).await.unwrap();
+        assert_eq!(
+            snapshots
+                .iter()
+                .map(|snapshot| snapshot.timestamp)
+                .collect::<Vec<_>>(),
+            [time(4), time(3)]
+        );
+        assert_eq!(store.load(&snapshots[0]).await.unwrap(), "three");
+        assert_eq!(store.load(&snapshots[1]).await.unwrap(), "two");
+
+        // Snapshots of other files are stored separately.
+        let other_path = Path::new("/project/lib.rs");
+        assert!(store.snapshots(other_path).await.unwrap().is_empty());
+
+        let limits = SnapshotLimits {
+            max_snapshots_per_file: 0,
+            ..LIMITS
+        };
+        assert!(!store
+            .save(path, "four".into(), time(5), limits)
+            .await
+            .unwrap());
+        assert_eq!(store.snapshots(path).await.unwrap().len(), 2);
+    }
+
+    #[gpui::test]
+    async fn test_snapshot_size_limits(cx: &mut TestAppContext) {
+        let fs = FakeFs::new(cx.executor());
+        let store = SnapshotStore::new(fs, PathBuf::from("/history"));
+        let main_path = Path::new("/project/main.rs");
+        let lib_path = Path::new("/project/lib.rs");
+        let time = |millis| UNIX_EPOCH + Duration::from_millis(millis);
+        let limits = SnapshotLimits {
+            max_snapshot_size: 10,
+            max_total_size: 20,
+            ..LIMITS
+        };
+
+        // Files that are too large aren't snapshotted.
+        assert!(!store
+            .save(main_path, "a".repeat(11), time(1), limits)
+            .await
+            .unwrap());
+        assert!(timestamps(&store, main_path).await.is_empty());
+
+        assert!(store
+            .save(main_path, "a".repeat(10), time(2), limits)
+            .await
+            .unwrap());
+        assert!(store
+            .save(lib_path, "b".repeat(5), time(3), limits)
+            .await
+            .unwrap());
+        assert!(store
+            .save(lib_path, "c".repeat(5), time(4), limits)
+            .await
+            .unwrap());
+        assert_eq!(timestamps(&store, main_path).await, [time(2)]);
+        assert_eq!(timestamps(&store, lib_path).await, [time(4), time(3)]);
+
+        // Once all snapshots take more space than allowed, the oldest ones
+        // are removed, whichever file they belong to.
+        assert!(store
+            .save(lib_path, "d".repeat(5), time(5), limits)
+            .await
+            .unwrap());
+        assert!(timestamps(&store, main_path).await.is_empty());
+        assert_eq!(
+            timestamps(&store, lib_path).await,
+            [time(5), time(4), time(3)]
+        );
+
+        assert!(store
+            .save(main_path, "e".repeat(10), time(6), limits)
+            .await
+            .unwrap());
+        assert_eq!(timestamps(&store, main_path).await, [time(6)]);
+        assert_eq!(timestamps(&store, lib_path).await, [time(5), time(4)]);
+    }
+
+    async fn timestamps(store: &SnapshotStore, path: &Path) -> Vec<SystemTime> {
+        store
+            .snapshots(path)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|snapshot| snapshot.timestamp)
+            .collect()
+    }
+
+    const LIMITS: SnapshotLimits = SnapshotLimits {
+        max_snapshots_per_file: 50,
+        max_snapshot_size: 1024,
+        max_total_size: 1024 * 1024,
+    };
+}
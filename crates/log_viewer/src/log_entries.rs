@@ -0,0 +1,192 @@
+use std::collections::BTreeSet;
+
+use log::{Level, LevelFilter};
+
+/// An entry of Zed's log, with the lines of a multi-line message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogEntry {
+    /// The level of the entry, or `None` for lines that precede the first entry.
+    pub level: Option<Level>,
+    /// The module that logged the entry, when it is written to the log.
+    pub module: Option<String>,
+    pub text: String,
+}
+
+/// Which of the log's entries are shown.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogFilter {
+    pub level: LevelFilter,
+    /// A module path, whose entries, and the entries of its submodules, are shown.
+    pub module: Option<String>,
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        Self {
+            level: LevelFilter::Trace,
+            module: None,
+        }
+    }
+}
+
+impl LogFilter {
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        let Some(level) = entry.level else {
+            return self.module.is_none();
+        };
+        if level > self.level {
+            return false;
+        }
+        match (&self.module, &entry.module) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(filter), Some(module)) => {
+                module == filter
+                    || module
+                        .strip_prefix(filter.as_str())
+                        .map_or(false, |rest| rest.starts_with("::"))
+            }
+        }
+    }
+}
+
+/// Parses log text into entries, adding the ones that it contains to `entries`.
+///
+/// Lines are expected to look like `<time> [<LEVEL>] <module>: <message>`, and lines
+/// that don't are continuations of the previous entry.
+pub fn parse_log(text: &str, entries: &mut Vec<LogEntry>) {
+    for line in text.lines() {
+        match parse_header(line) {
+            Some((level, module)) => entries.push(LogEntry {
+                level: Some(level),
+                module: module.map(ToString::to_string),
+                text: line.to_string(),
+            }),
+            None => match entries.last_mut() {
+                Some(entry) => {
+                    entry.text.push('\n');
+                    entry.text.push_str(line);
+                }
+                None => entries.push(LogEntry {
+                    level: None,
+                    module: None,
+                    text: line.to_string(),
+                }),
+            },
+        }
+    }
+}
+
+/// Returns the modules, and the modules that contain them, that logged the given entries.
+pub fn modules<'a>(entries: impl IntoIterator<Item = &'a LogEntry>) -> BTreeSet<String> {
+    let mut modules = BTreeSet::new();
+    for module in entries
+        .into_iter()
+        .filter_map(|entry| entry.module.as_deref())
+    {
+        let mut end = 0;
+        for component in module.split("::") {
+            end += component.len();
+            if !modules.contains(&module[..end]) {
+                modules.insert(module[..end].to_string());
+            }
+            end += "::".len();
+        }
+    }
+    modules
+}
+
+fn parse_header(line: &str) -> Option<(Level, Option<&str>)> {
+    let (_time, rest) = line.split_once(' ')?;
+    let rest = rest.strip_prefix('[')?;
+    let (level, rest) = rest.split_once("] ")?;
+    let level = level.trim().parse::<Level>().ok()?;
+    let module = rest
+        .split_once(": ")
+        .map(|(module, _)| module)
+        .filter(|module| {
+            !module.is_empty()
+                && module
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '_' || c == ':')
+        });
+    Some((level, module))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str = "\
+thread started
+2024-06-25T10:00:00+02:00 [INFO] zed: ========== starting zed ==========
+2024-06-25T10:00:01+02:00 [WARN] project::lsp_store: slow response
+2024-06-25T10:00:02+02:00 [ERROR] project: failed to load
+caused by: missing file
+2024-06-25T10:00:03+02:00 [INFO] a message without a module
+";
+
+    #[test]
+    fn test_parse_log() {
+        let mut entries = Vec::new();
+        parse_log(LOG, &mut entries);
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| (entry.level, entry.module.as_deref()))
+                .collect::<Vec<_>>(),
+            [
+                (None, None),
+                (Some(Level::Info), Some("zed")),
+                (Some(Level::Warn), Some("project::lsp_store")),
+                (Some(Level::Error), Some("project")),
+                (Some(Level::Info), None),
+            ]
+        );
+        assert_eq!(
+            entries[3].text,
+            "2024-06-25T10:00:02+02:00 [ERROR] project: failed to load\ncaused by: missing file"
+        );
+        assert_eq!(
+            modules(&entries).into_iter().collect::<Vec<_>>(),
+            ["project", "project::lsp_store", "zed"]
+        );
+    }
+
+    #[test]
+    fn test_filter_log() {
+        let mut entries = Vec::new();
+        parse_log(LOG, &mut entries);
+        let filtered = |filter: LogFilter| {
+            entries
+                .iter()
+                .enumerate()
+                .filter(|(_, entry)| filter.matches(entry))
+                .map(|(ix, _)| ix)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(filtered(LogFilter::default()), [0, 1, 2, 3, 4]);
+        assert_eq!(
+            filtered(LogFilter {
+                level: LevelFilter::Warn,
+                module: None,
+            }),
+            [0, 2, 3]
+        );
+        assert_eq!(
+            filtered(LogFilter {
+                level: LevelFilter::Trace,
+                module: Some("project".into()),
+            }),
+            [2, 3]
+        );
+        assert_eq!(
+            filtered(LogFilter {
+                level: LevelFilter::Trace,
+                module: Some("proj".into()),
+            }),
+            Vec::<usize>::new()
+        );
+    }
+}
@@ -0,0 +1,493 @@
+mod log_entries;
+
+use std::{
+    collections::BTreeSet,
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    path::Path,
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::Result;
+use editor::{actions::MoveToEnd, scroll::Autoscroll, Editor, EditorEvent};
+use gpui::{
+    div, AnchorCorner, AnyElement, AppContext, EventEmitter, FocusHandle, FocusableView,
+    IntoElement, ParentElement, Render, Styled, Subscription, Task, View, ViewContext,
+    VisualContext, WindowContext,
+};
+use itertools::Itertools;
+use log::LevelFilter;
+use project::search::SearchQuery;
+use ui::{prelude::*, Button, ContextMenu, IconButton, PopoverMenu, Tooltip};
+use util::ResultExt;
+use workspace::{
+    item::{Item, ItemHandle, TabContentParams},
+    searchable::{SearchEvent, SearchableItem, SearchableItemHandle},
+    OpenLog, ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView, Workspace,
+};
+
+pub use log_entries::{parse_log, LogEntry, LogFilter};
+
+/// How often the log file is checked for new entries.
+const TAIL_INTERVAL: Duration = Duration::from_secs(1);
+
+const LEVELS: [LevelFilter; 5] = [
+    LevelFilter::Error,
+    LevelFilter::Warn,
+    LevelFilter::Info,
+    LevelFilter::Debug,
+    LevelFilter::Trace,
+];
+
+pub fn init(cx: &mut AppContext) {
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace.register_action(|workspace, _: &OpenLog, cx| {
+            workspace
+                .with_local_workspace(cx, |workspace, cx| {
+                    let existing = workspace
+                        .active_pane()
+                        .read(cx)
+                        .items()
+                        .find_map(|item| item.downcast::<LogView>());
+                    if let Some(existing) = existing {
+                        workspace.activate_item(&existing, cx);
+                    } else {
+                        let log_view = cx.new_view(LogView::new);
+                        workspace.add_item_to_active_pane(Box::new(log_view), None, cx);
+                    }
+                })
+                .detach();
+        });
+    })
+    .detach();
+}
+
+/// A view of Zed's log, which follows the entries as they are written.
+pub struct LogView {
+    editor: View<Editor>,
+    entries: Vec<LogEntry>,
+    modules: BTreeSet<String>,
+    filter: LogFilter,
+    follow: bool,
+    focus_handle: FocusHandle,
+    _subscriptions: Vec<Subscription>,
+    _tail_task: Task<()>,
+}
+
+impl LogView {
+    pub fn new(cx: &mut ViewContext<Self>) -> Self {
+        let editor = cx.new_view(|cx| {
+            let mut editor = Editor::multi_line(cx);
+            editor.set_read_only(true);
+            editor.set_show_inline_completions(false);
+            editor
+        });
+        let editor_subscription = cx.subscribe(&editor, |_, _, event: &EditorEvent, cx| {
+            cx.emit(event.clone())
+        });
+        let search_subscription = cx.subscribe(&editor, |_, _, event: &SearchEvent, cx| {
+            cx.emit(event.clone())
+        });
+
+        let focus_handle = cx.focus_handle();
+        let focus_subscription = cx.on_focus(&focus_handle, |log_view, cx| {
+            cx.focus_view(&log_view.editor);
+        });
+
+        let tail_task = cx.spawn(|this, mut cx| async move {
+            let old_log = cx
+                .background_executor()
+                .spawn(async { std::fs::read_to_string(paths::old_log_file()) })
+                .await
+                .unwrap_or_default();
+            if this
+                .update(&mut cx, |this, cx| this.push_log(&old_log, cx))
+                .is_err()
+            {
+                return;
+            }
+
+            let mut offset = 0;
+            loop {
+                let new_log = cx
+                    .background_executor()
+                    .spawn(async move { read_log_from(paths::log_file(), offset) })
+                    .await
+                    .log_err();
+                if let Some((new_log, new_offset)) = new_log {
+                    offset = new_offset;
+                    if this
+                        .update(&mut cx, |this, cx| this.push_log(&new_log, cx))
+                        .is_err()
+                    {
+                        return;
+                    }
+                }
+                cx.background_executor().timer(TAIL_INTERVAL).await;
+            }
+        });
+
+        Self {
+            editor,
+            entries: Vec::new(),
+            modules: BTreeSet::new(),
+            filter: LogFilter::default(),
+            follow: true,
+            focus_handle,
+            _subscriptions: vec![editor_subscription, search_subscription, focus_subscription],
+            _tail_task: tail_task,
+        }
+    }
+
+    pub fn filter(&self) -> &LogFilter {
+        &self.filter
+    }
+
+    pub fn set_filter(&mut self, filter: LogFilter, cx: &mut ViewContext<Self>) {
+        if filter == self.filter {
+            return;
+        }
+        self.filter = filter;
+        let text = self
+            .entries
+            .iter()
+            .filter(|entry| self.filter.matches(entry))
+            .map(|entry| entry.text.as_str())
+            .join("\n");
+        self.editor.update(cx, |editor, cx| {
+            editor.set_text(text, cx);
+            if self.follow {
+                editor.move_to_end(&MoveToEnd, cx);
+            }
+        });
+        cx.notify();
+    }
+
+    pub fn is_following(&self) -> bool {
+        self.follow
+    }
+
+    pub fn toggle_follow(&mut self, cx: &mut ViewContext<Self>) {
+        self.follow = !self.follow;
+        if self.follow {
+            self.editor
+                .update(cx, |editor, cx| editor.move_to_end(&MoveToEnd, cx));
+        }
+        cx.notify();
+    }
+
+    /// Adds the entries of the given log text, which is a whole number of lines.
+    fn push_log(&mut self, log: &str, cx: &mut ViewContext<Self>) {
+        if log.is_empty() {
+            return;
+        }
+
+        let mut new_entries = Vec::new();
+        parse_log(log, &mut new_entries);
+        let mut new_text = String::new();
+        // Lines that don't start an entry continue the last one.
+        let starts_with_continuation = new_entries
+            .first()
+            .map_or(false, |entry| entry.level.is_none());
+        if let Some(last_entry) = self.entries.last_mut().filter(|_| starts_with_continuation) {
+            let continuation = new_entries.remove(0);
+            last_entry.text.push('\n');
+            last_entry.text.push_str(&continuation.text);
+            if self.filter.matches(last_entry) {
+                new_text.push('\n');
+                new_text.push_str(&continuation.text);
+            }
+        }
+        for entry in new_entries
+            .iter()
+            .filter(|entry| self.filter.matches(entry))
+        {
+            new_text.push('\n');
+            new_text.push_str(&entry.text);
+        }
+        self.modules.extend(log_entries::modules(&new_entries));
+        self.entries.extend(new_entries);
+
+        if new_text.is_empty() {
+            cx.notify();
+            return;
+        }
+        let follow = self.follow;
+        self.editor.update(cx, |editor, cx| {
+            let end = editor.buffer().read(cx).len(cx);
+            // The first line of the log doesn't follow a newline.
+            let new_text = if end == 0 {
+                &new_text[1..]
+            } else {
+                new_text.as_str()
+            };
+            editor.set_read_only(false);
+            editor.edit([(end..end, new_text)], cx);
+            editor.set_read_only(true);
+            if follow {
+                let end = editor.buffer().read(cx).len(cx);
+                editor.change_selections(Some(Autoscroll::newest()), cx, |selections| {
+                    selections.select_ranges([end..end])
+                });
+            }
+        });
+        cx.notify();
+    }
+}
+
+/// Reads the complete lines of the log file after the given offset, returning them
+/// and the offset after them. When the file is shorter than the offset, because the
+/// log was rotated, it is read from the start.
+fn read_log_from(path: &Path, offset: u64) -> Result<(String, u64)> {
+    let mut file = File::open(path)?;
+    let offset = if file.metadata()?.len() < offset {
+        0
+    } else {
+        offset
+    };
+    file.seek(SeekFrom::Start(offset))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    let end = bytes
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .map_or(0, |ix| ix + 1);
+    bytes.truncate(end);
+    Ok((
+        String::from_utf8_lossy(&bytes).into_owned(),
+        offset + end as u64,
+    ))
+}
+
+impl Render for LogView {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        self.editor
+            .update(cx, |editor, cx| editor.render(cx).into_any_element())
+    }
+}
+
+impl FocusableView for LogView {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<EditorEvent> for LogView {}
+impl EventEmitter<SearchEvent> for LogView {}
+
+impl Item for LogView {
+    type Event = EditorEvent;
+
+    fn to_item_events(event: &Self::Event, f: impl FnMut(workspace::item::ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn tab_content(&self, params: TabContentParams, _: &WindowContext<'_>) -> AnyElement {
+        Label::new("Log")
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn as_searchable(&self, handle: &View<Self>) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(handle.clone()))
+    }
+}
+
+impl SearchableItem for LogView {
+    type Match = <Editor as SearchableItem>::Match;
+
+    fn clear_matches(&mut self, cx: &mut ViewContext<Self>) {
+        self.editor.update(cx, |e, cx| e.clear_matches(cx))
+    }
+
+    fn update_matches(&mut self, matches: &[Self::Match], cx: &mut ViewContext<Self>) {
+        self.editor
+            .update(cx, |e, cx| e.update_matches(matches, cx))
+    }
+
+    fn query_suggestion(&mut self, cx: &mut ViewContext<Self>) -> String {
+        self.editor.update(cx, |e, cx| e.query_suggestion(cx))
+    }
+
+    fn activate_match(
+        &mut self,
+        index: usize,
+        matches: &[Self::Match],
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.editor
+            .update(cx, |e, cx| e.activate_match(index, matches, cx))
+    }
+
+    fn select_matches(&mut self, matches: &[Self::Match], cx: &mut ViewContext<Self>) {
+        self.editor
+            .update(cx, |e, cx| e.select_matches(matches, cx))
+    }
+
+    fn find_matches(
+        &mut self,
+        query: Arc<SearchQuery>,
+        cx: &mut ViewContext<Self>,
+    ) -> gpui::Task<Vec<Self::Match>> {
+        self.editor.update(cx, |e, cx| e.find_matches(query, cx))
+    }
+
+    fn replace(&mut self, _: &Self::Match, _: &SearchQuery, _: &mut ViewContext<Self>) {
+        // The log is read-only.
+    }
+
+    fn supported_options() -> workspace::searchable::SearchOptions {
+        workspace::searchable::SearchOptions {
+            case: true,
+            word: true,
+            regex: true,
+            replacement: false,
+            selection: false,
+        }
+    }
+
+    fn active_match_index(
+        &mut self,
+        matches: &[Self::Match],
+        cx: &mut ViewContext<Self>,
+    ) -> Option<usize> {
+        self.editor
+            .update(cx, |e, cx| e.active_match_index(matches, cx))
+    }
+}
+
+pub struct LogViewToolbarItemView {
+    log_view: Option<View<LogView>>,
+    _log_view_subscription: Option<Subscription>,
+}
+
+impl LogViewToolbarItemView {
+    pub fn new() -> Self {
+        Self {
+            log_view: None,
+            _log_view_subscription: None,
+        }
+    }
+}
+
+impl EventEmitter<ToolbarItemEvent> for LogViewToolbarItemView {}
+
+impl ToolbarItemView for LogViewToolbarItemView {
+    fn set_active_pane_item(
+        &mut self,
+        active_pane_item: Option<&dyn ItemHandle>,
+        cx: &mut ViewContext<Self>,
+    ) -> ToolbarItemLocation {
+        if let Some(log_view) = active_pane_item.and_then(|item| item.downcast::<LogView>()) {
+            self._log_view_subscription = Some(cx.observe(&log_view, |_, _, cx| cx.notify()));
+            self.log_view = Some(log_view);
+            return ToolbarItemLocation::PrimaryLeft;
+        }
+        self.log_view = None;
+        self._log_view_subscription = None;
+        ToolbarItemLocation::Hidden
+    }
+}
+
+impl Render for LogViewToolbarItemView {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let Some(log_view) = self.log_view.clone() else {
+            return div();
+        };
+        let (filter, modules, is_following) = {
+            let log_view = log_view.read(cx);
+            (
+                log_view.filter().clone(),
+                log_view.modules.clone(),
+                log_view.is_following(),
+            )
+        };
+
+        let level_menu = PopoverMenu::new("log-level-menu")
+            .anchor(AnchorCorner::TopLeft)
+            .trigger(Button::new(
+                "log-level-menu-trigger",
+                format!("Level: {}", filter.level),
+            ))
+            .menu({
+                let log_view = log_view.clone();
+                let filter = filter.clone();
+                move |cx| {
+                    let log_view = log_view.clone();
+                    let filter = filter.clone();
+                    Some(ContextMenu::build(cx, move |mut menu, cx| {
+                        for level in LEVELS {
+                            let new_filter = LogFilter {
+                                level,
+                                ..filter.clone()
+                            };
+                            menu = menu.toggleable_entry(
+                                level.to_string(),
+                                level == filter.level,
+                                None,
+                                cx.handler_for(&log_view, move |log_view, cx| {
+                                    log_view.set_filter(new_filter.clone(), cx)
+                                }),
+                            );
+                        }
+                        menu
+                    }))
+                }
+            });
+
+        let module_menu = PopoverMenu::new("log-module-menu")
+            .anchor(AnchorCorner::TopLeft)
+            .trigger(Button::new(
+                "log-module-menu-trigger",
+                format!(
+                    "Module: {}",
+                    filter.module.as_deref().unwrap_or("All Modules")
+                ),
+            ))
+            .menu({
+                let log_view = log_view.clone();
+                move |cx| {
+                    let log_view = log_view.clone();
+                    let filter = filter.clone();
+                    let modules = modules.clone();
+                    Some(ContextMenu::build(cx, move |mut menu, cx| {
+                        for module in std::iter::once(None).chain(modules.into_iter().map(Some)) {
+                            let label = module.clone().unwrap_or_else(|| "All Modules".to_string());
+                            let new_filter = LogFilter {
+                                module,
+                                ..filter.clone()
+                            };
+                            menu = menu.toggleable_entry(
+                                label,
+                                new_filter.module == filter.module,
+                                None,
+                                cx.handler_for(&log_view, move |log_view, cx| {
+                                    log_view.set_filter(new_filter.clone(), cx)
+                                }),
+                            );
+                        }
+                        menu
+                    }))
+                }
+            });
+
+        h_flex().gap_2().child(level_menu).child(module_menu).child(
+            IconButton::new("log-follow", IconName::ArrowDownFromLine)
+                .icon_size(IconSize::Small)
+                .selected(is_following)
+                .tooltip(|cx| Tooltip::text("Follow New Entries", cx))
+                .on_click(move |_, cx| {
+                    log_view.update(cx, |log_view, cx| log_view.toggle_follow(cx))
+                }),
+        )
+    }
+}
@@ -1161,6 +1161,29 @@ impl MultiBuffer {
         context_line_count: u32,
         cx: &mut ModelContext<Self>,
     ) -> Vec<Range<Anchor>>
+    where
+        O: text::ToPoint + text::ToOffset,
+    {
+        self.insert_excerpts_with_context_lines_after(
+            ExcerptId::max(),
+            buffer,
+            ranges,
+            context_line_count,
+            cx,
+        )
+    }
+
+    /// Inserts excerpts around the given ranges after `prev_excerpt_id`, expanded by
+    /// `context_line_count` lines and merged when they overlap, returning the ranges
+    /// as multibuffer anchors.
+    pub fn insert_excerpts_with_context_lines_after<O>(
+        &mut self,
+        prev_excerpt_id: ExcerptId,
+        buffer: Model<Buffer>,
+        ranges: Vec<Range<O>>,
+        context_line_count: u32,
+        cx: &mut ModelContext<Self>,
+    ) -> Vec<Range<Anchor>>
     where
         O: text::ToPoint + text::ToOffset,
     {
@@ -1169,7 +1192,7 @@ impl MultiBuffer {
         let (excerpt_ranges, range_counts) =
             build_excerpt_ranges(&buffer_snapshot, &ranges, context_line_count);
 
-        let excerpt_ids = self.push_excerpts(buffer, excerpt_ranges, cx);
+        let excerpt_ids = self.insert_excerpts_after(prev_excerpt_id, buffer, excerpt_ranges, cx);
 
         let mut anchor_ranges = Vec::new();
         let mut ranges = ranges.into_iter();
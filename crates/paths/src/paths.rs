@@ -156,6 +156,30 @@ pub fn themes_dir() -> &'static PathBuf {
     THEMES_DIR.get_or_init(|| config_dir().join("themes"))
 }
 
+/// Returns the path to the snippets directory.
+///
+/// This is where the user's snippets, in the format used by VS Code, are stored.
+pub fn snippets_dir() -> &'static PathBuf {
+    static SNIPPETS_DIR: OnceLock<PathBuf> = OnceLock::new();
+    SNIPPETS_DIR.get_or_init(|| config_dir().join("snippets"))
+}
+
+/// Returns the path to the TextMate grammars directory.
+///
+/// This is where the user's `.tmLanguage.json` and `.tmLanguage` grammars are stored.
+pub fn textmate_grammars_dir() -> &'static PathBuf {
+    static TEXTMATE_GRAMMARS_DIR: OnceLock<PathBuf> = OnceLock::new();
+    TEXTMATE_GRAMMARS_DIR.get_or_init(|| config_dir().join("grammars"))
+}
+
+/// Returns the path to the scripts directory.
+///
+/// This is where the user's scripts, which can be bound to keystrokes, are stored.
+pub fn scripts_dir() -> &'static PathBuf {
+    static SCRIPTS_DIR: OnceLock<PathBuf> = OnceLock::new();
+    SCRIPTS_DIR.get_or_init(|| config_dir().join("scripts"))
+}
+
 /// Returns the path to the contexts directory.
 ///
 /// This is where the saved contexts from the Assistant are stored.
@@ -218,6 +242,30 @@ pub fn supermaven_dir() -> &'static PathBuf {
     SUPERMAVEN_DIR.get_or_init(|| support_dir().join("supermaven"))
 }
 
+/// Returns the path to the local history directory.
+///
+/// This is where snapshots of files are kept as they are edited.
+pub fn local_history_dir() -> &'static PathBuf {
+    static LOCAL_HISTORY_DIR: OnceLock<PathBuf> = OnceLock::new();
+    LOCAL_HISTORY_DIR.get_or_init(|| support_dir().join("local_history"))
+}
+
+/// Returns the path to the search index directory.
+///
+/// This is where the index used to skip files in project searches is kept between sessions.
+pub fn search_index_dir() -> &'static PathBuf {
+    static SEARCH_INDEX_DIR: OnceLock<PathBuf> = OnceLock::new();
+    SEARCH_INDEX_DIR.get_or_init(|| support_dir().join("search_index"))
+}
+
+/// Returns the path to the auto-update backup directory.
+///
+/// This is where the previous installation is kept after an update, so that it can be rolled back to.
+pub fn auto_update_backup_dir() -> &'static PathBuf {
+    static AUTO_UPDATE_BACKUP_DIR: OnceLock<PathBuf> = OnceLock::new();
+    AUTO_UPDATE_BACKUP_DIR.get_or_init(|| support_dir().join("update_backup"))
+}
+
 /// Returns the path to the default Prettier directory.
 pub fn default_prettier_dir() -> &'static PathBuf {
     static DEFAULT_PRETTIER_DIR: OnceLock<PathBuf> = OnceLock::new();
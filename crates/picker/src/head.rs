@@ -22,6 +22,8 @@ impl Head {
         let editor = cx.new_view(|cx| {
             let mut editor = Editor::single_line(cx);
             editor.set_placeholder_text(placeholder_text, cx);
+            // Pickers are mostly shown in modals, whose inputs are tabbed between.
+            editor.set_tab_moves_focus(true);
             editor
         });
         cx.subscribe(&editor, edit_handler).detach();
@@ -0,0 +1,338 @@
+use anyhow::{anyhow, Context as _, Result};
+use collections::HashMap;
+use lsp::LanguageServerBinary;
+use serde::Deserialize;
+use std::{
+    collections::hash_map::DefaultHasher,
+    ffi::OsString,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+/// The label Zed puts on the containers it starts, to find them again when reopening a project.
+const LOCAL_FOLDER_LABEL: &str = "dev.zed.devcontainer.local_folder";
+
+/// Variables that describe the host rather than the project, which the container sets itself.
+const HOST_VARIABLES: &[&str] = &[
+    "PATH", "HOME", "USER", "LOGNAME", "SHELL", "TMPDIR", "PWD", "OLDPWD", "TERM",
+];
+
+/// A running dev container that a local project's terminals, tasks and language servers are run in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DevContainer {
+    pub container_id: String,
+    /// The path of the project inside of the container.
+    pub workspace_folder: String,
+    /// The path of the project on the host, mounted at the `workspace_folder`.
+    pub local_folder: PathBuf,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+struct DevContainerConfig {
+    image: Option<String>,
+    build: Option<BuildConfig>,
+    workspace_folder: Option<String>,
+    remote_user: Option<String>,
+    #[serde(default)]
+    run_args: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+struct BuildConfig {
+    dockerfile: Option<String>,
+    context: Option<String>,
+}
+
+impl DevContainer {
+    /// Returns the path inside of the container that corresponds to the given path on the host.
+    pub fn container_path(&self, path: &Path) -> Option<String> {
+        let relative_path = path.strip_prefix(&self.local_folder).ok()?;
+        let mut container_path = self.workspace_folder.clone();
+        for component in relative_path.components() {
+            container_path.push('/');
+            container_path.push_str(&component.as_os_str().to_string_lossy());
+        }
+        Some(container_path)
+    }
+
+    /// Rewrites the paths into the project folder on the host that the text contains,
+    /// such as those of task variables, to where the folder is mounted in the container.
+    pub fn translate_paths(&self, text: &str) -> String {
+        let local_folder = self.local_folder.to_string_lossy();
+        let mut translated = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(ix) = rest.find(local_folder.as_ref()) {
+            let after = &rest[ix + local_folder.len()..];
+            // Only whole folder names match, so that `/project` doesn't match `/project-2`.
+            let is_whole_folder = after.chars().next().map_or(true, |c| {
+                !(c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+            });
+            translated.push_str(&rest[..ix]);
+            if is_whole_folder {
+                translated.push_str(&self.workspace_folder);
+            } else {
+                translated.push_str(&local_folder);
+            }
+            rest = after;
+        }
+        translated.push_str(rest);
+        translated
+    }
+
+    /// Returns the variables of the given environment to set in the container, sorted by name.
+    /// Variables describing the host are left out, and paths into the project are translated.
+    pub fn container_env(&self, env: &HashMap<String, String>) -> Vec<(String, String)> {
+        let mut container_env = env
+            .iter()
+            .filter(|(name, _)| {
+                !HOST_VARIABLES.contains(&name.as_str()) && !name.starts_with("DIRENV_")
+            })
+            .map(|(name, value)| (name.clone(), self.translate_paths(value)))
+            .collect::<Vec<_>>();
+        container_env.sort();
+        container_env
+    }
+
+    /// Returns the command that runs the given language server in the container.
+    ///
+    /// The project folder and Zed's support directory are also mounted at their paths on the
+    /// host, so the server can be given the same paths and URIs as a server on the host would.
+    pub fn language_server_binary(
+        &self,
+        binary: LanguageServerBinary,
+        root_path: &Path,
+    ) -> LanguageServerBinary {
+        let mut arguments: Vec<OsString> = vec!["exec".into(), "-i".into()];
+        for (name, value) in self.container_env(&binary.env.unwrap_or_default()) {
+            arguments.push("-e".into());
+            arguments.push(format!("{name}={value}").into());
+        }
+        arguments.push("-w".into());
+        arguments.push(root_path.into());
+        arguments.push(self.container_id.clone().into());
+        arguments.push(binary.path.into());
+        arguments.extend(binary.arguments);
+        LanguageServerBinary {
+            path: "docker".into(),
+            arguments,
+            env: None,
+        }
+    }
+}
+
+/// Returns the dev container configuration of the project at the given path, if it has one.
+pub fn dev_container_config_path(local_folder: &Path) -> Option<PathBuf> {
+    [
+        local_folder.join(".devcontainer").join("devcontainer.json"),
+        local_folder.join(".devcontainer.json"),
+    ]
+    .into_iter()
+    .find(|path| path.is_file())
+}
+
+/// Builds and starts the dev container described by the given configuration,
+/// reusing the container that was started for the same folder before, if there is one.
+pub async fn start_dev_container(
+    config_path: PathBuf,
+    local_folder: PathBuf,
+) -> Result<DevContainer> {
+    let content = smol::fs::read_to_string(&config_path)
+        .await
+        .with_context(|| format!("reading {config_path:?}"))?;
+    let config: DevContainerConfig = settings::parse_json_with_comments(&content)
+        .with_context(|| format!("parsing {config_path:?}"))?;
+    let config_dir = config_path
+        .parent()
+        .ok_or_else(|| anyhow!("invalid dev container configuration path {config_path:?}"))?;
+
+    let folder_name = local_folder
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "workspace".to_string());
+    let workspace_folder = config
+        .workspace_folder
+        .clone()
+        .unwrap_or_else(|| format!("/workspaces/{folder_name}"));
+    let label = format!("{LOCAL_FOLDER_LABEL}={}", local_folder.to_string_lossy());
+
+    let label_filter = format!("label={label}");
+    let existing_container = docker(&["ps", "-aq", "--filter", label_filter.as_str()]).await?;
+    let container_id = if let Some(container_id) = existing_container.lines().next() {
+        docker(&["start", container_id]).await?;
+        container_id.to_string()
+    } else {
+        let image = if let Some(image) = config.image.clone() {
+            image
+        } else if let Some(build) = &config.build {
+            let mut hasher = DefaultHasher::new();
+            local_folder.hash(&mut hasher);
+            let image =
+                format!("zed-devcontainer-{folder_name}-{:x}", hasher.finish()).to_lowercase();
+            let dockerfile = config_dir.join(build.dockerfile.as_deref().unwrap_or("Dockerfile"));
+            let context = config_dir.join(build.context.as_deref().unwrap_or("."));
+            docker(&[
+                "build",
+                "-f",
+                dockerfile.to_string_lossy().as_ref(),
+                "-t",
+                image.as_str(),
+                context.to_string_lossy().as_ref(),
+            ])
+            .await?;
+            image
+        } else {
+            return Err(anyhow!(
+                "{config_path:?} specifies neither an image nor a build"
+            ));
+        };
+
+        let local_folder_path = local_folder.to_string_lossy();
+        let mount = format!("type=bind,source={local_folder_path},target={workspace_folder}");
+        // Language servers are given the paths of the host, see `language_server_binary`.
+        let host_mount = format!("type=bind,source={local_folder_path},target={local_folder_path}");
+        let support_dir = paths::support_dir().to_string_lossy();
+        let support_dir_mount =
+            format!("type=bind,source={support_dir},target={support_dir},readonly");
+        let mut args = vec![
+            "run",
+            "-d",
+            "--label",
+            label.as_str(),
+            "--mount",
+            mount.as_str(),
+            "-w",
+            workspace_folder.as_str(),
+        ];
+        if local_folder_path != workspace_folder {
+            args.extend(["--mount", host_mount.as_str()]);
+        }
+        args.extend(["--mount", support_dir_mount.as_str()]);
+        if let Some(user) = &config.remote_user {
+            args.extend(["-u", user.as_str()]);
+        }
+        args.extend(config.run_args.iter().map(String::as_str));
+        // Keep the container running, as everything is run in it with `docker exec`.
+        args.extend([image.as_str(), "sleep", "infinity"]);
+        docker(&args).await?.trim().to_string()
+    };
+
+    Ok(DevContainer {
+        container_id,
+        workspace_folder,
+        local_folder,
+    })
+}
+
+async fn docker(args: &[&str]) -> Result<String> {
+    let output = smol::process::Command::new("docker")
+        .args(args)
+        .output()
+        .await
+        .context("running docker")?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "docker {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dev_container() -> DevContainer {
+        DevContainer {
+            container_id: "abc".to_string(),
+            workspace_folder: "/workspaces/project".to_string(),
+            local_folder: PathBuf::from("/home/me/project"),
+        }
+    }
+
+    #[test]
+    fn test_container_path() {
+        let container = dev_container();
+        assert_eq!(
+            container.container_path(Path::new("/home/me/project")),
+            Some("/workspaces/project".to_string())
+        );
+        assert_eq!(
+            container.container_path(Path::new("/home/me/project/src/lib")),
+            Some("/workspaces/project/src/lib".to_string())
+        );
+        assert_eq!(container.container_path(Path::new("/home/me/other")), None);
+    }
+
+    #[test]
+    fn test_container_env() {
+        let container = dev_container();
+        let env = HashMap::from_iter([
+            (
+                "PATH".to_string(),
+                "/home/me/.cargo/bin:/usr/bin".to_string(),
+            ),
+            ("HOME".to_string(), "/home/me".to_string()),
+            ("DIRENV_DIR".to_string(), "-/home/me/project".to_string()),
+            (
+                "ZED_FILE".to_string(),
+                "/home/me/project/src/main.rs".to_string(),
+            ),
+            (
+                "ZED_WORKTREE_ROOT".to_string(),
+                "/home/me/project".to_string(),
+            ),
+            (
+                "OTHER_PROJECTS".to_string(),
+                "/home/me/project-2:/home/me/project.old".to_string(),
+            ),
+            ("RUST_LOG".to_string(), "info".to_string()),
+        ]);
+        assert_eq!(
+            container.container_env(&env),
+            [
+                ("OTHER_PROJECTS", "/home/me/project-2:/home/me/project.old"),
+                ("RUST_LOG", "info"),
+                ("ZED_FILE", "/workspaces/project/src/main.rs"),
+                ("ZED_WORKTREE_ROOT", "/workspaces/project"),
+            ]
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+        );
+    }
+
+    #[test]
+    fn test_language_server_binary() {
+        let container = dev_container();
+        let binary = container.language_server_binary(
+            LanguageServerBinary {
+                path: PathBuf::from("/home/me/.local/share/zed/languages/server/bin"),
+                arguments: vec!["--stdio".into()],
+                env: Some(HashMap::from_iter([
+                    ("PATH".to_string(), "/usr/bin".to_string()),
+                    ("RUST_LOG".to_string(), "info".to_string()),
+                ])),
+            },
+            Path::new("/home/me/project"),
+        );
+        assert_eq!(binary.path, PathBuf::from("docker"));
+        assert_eq!(
+            binary.arguments,
+            [
+                "exec",
+                "-i",
+                "-e",
+                "RUST_LOG=info",
+                "-w",
+                "/home/me/project",
+                "abc",
+                "/home/me/.local/share/zed/languages/server/bin",
+                "--stdio",
+            ]
+            .map(OsString::from)
+        );
+        assert_eq!(binary.env, None);
+    }
+}
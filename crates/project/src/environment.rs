@@ -0,0 +1,214 @@
+use crate::{
+    project_settings::{EnvLoader, ProjectSettings},
+    Project,
+};
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use fs::Fs;
+use futures::FutureExt as _;
+use gpui::{AppContext, Model, ModelContext, Task};
+use settings::{Settings, SettingsLocation};
+use std::path::Path;
+use util::ResultExt;
+use worktree::{UpdatedEntriesSet, Worktree, WorktreeId};
+
+/// The files in the root of a worktree that its environment is loaded from,
+/// by the `dotenv` and `direnv` loaders.
+const ENVIRONMENT_FILES: [&str; 2] = [".env", ".envrc"];
+
+impl Project {
+    /// Returns the environment variables of the processes started in the given
+    /// worktree, such as terminals, tasks, formatters and language servers:
+    /// those loaded by the worktree's `env_loader`, overridden by its `env`
+    /// settings.
+    pub fn worktree_environment(
+        &self,
+        worktree_id: WorktreeId,
+        cx: &AppContext,
+    ) -> Task<HashMap<String, String>> {
+        let loaded_environment = self
+            .worktree_environments
+            .get(&worktree_id)
+            .map(|(_, environment)| environment.clone());
+        let configured_environment = configured_environment(worktree_id, cx);
+        cx.background_executor().spawn(async move {
+            let mut environment = match loaded_environment {
+                Some(loaded_environment) => loaded_environment.await,
+                None => HashMap::default(),
+            };
+            environment.extend(configured_environment);
+            environment
+        })
+    }
+
+    /// Loads the environment of the worktree again when one of the files that
+    /// the `env_loader`s load it from changed. Processes that were already
+    /// started keep their environment.
+    pub(crate) fn update_worktree_environment_files(
+        &mut self,
+        worktree: &Model<Worktree>,
+        changes: &UpdatedEntriesSet,
+        cx: &mut ModelContext<Self>,
+    ) {
+        let environment_file_changed = changes.iter().any(|(path, _, _)| {
+            ENVIRONMENT_FILES
+                .iter()
+                .any(|file_name| path.as_ref() == Path::new(file_name))
+        });
+        if environment_file_changed
+            && self
+                .worktree_environments
+                .remove(&worktree.read(cx).id())
+                .is_some()
+        {
+            self.update_worktree_environments(cx);
+        }
+    }
+
+    /// Loads the environment of each local worktree with its `env_loader`,
+    /// unless it was already loaded with the same one.
+    pub(crate) fn update_worktree_environments(&mut self, cx: &mut ModelContext<Self>) {
+        let worktrees = self.worktrees().collect::<Vec<_>>();
+        for worktree in worktrees {
+            let worktree = worktree.read(cx);
+            if !worktree.is_local() {
+                continue;
+            }
+            let worktree_id = worktree.id();
+            let loader = ProjectSettings::get(
+                Some(SettingsLocation {
+                    worktree_id: worktree_id.to_usize(),
+                    path: Path::new(""),
+                }),
+                cx,
+            )
+            .env_loader;
+            if self
+                .worktree_environments
+                .get(&worktree_id)
+                .map_or(false, |(current_loader, _)| *current_loader == loader)
+            {
+                continue;
+            }
+
+            let abs_path = worktree.abs_path().clone();
+            let fs = self.fs.clone();
+            let environment = cx
+                .background_executor()
+                .spawn(async move {
+                    load_environment(loader, &abs_path, fs.as_ref())
+                        .await
+                        .with_context(|| format!("failed to load the environment of {abs_path:?}"))
+                        .log_err()
+                        .unwrap_or_default()
+                })
+                .shared();
+            self.worktree_environments
+                .insert(worktree_id, (loader, environment));
+        }
+    }
+}
+
+fn configured_environment(worktree_id: WorktreeId, cx: &AppContext) -> HashMap<String, String> {
+    ProjectSettings::get(
+        Some(SettingsLocation {
+            worktree_id: worktree_id.to_usize(),
+            path: Path::new(""),
+        }),
+        cx,
+    )
+    .env
+    .clone()
+}
+
+async fn load_environment(
+    loader: EnvLoader,
+    dir: &Path,
+    fs: &dyn Fs,
+) -> Result<HashMap<String, String>> {
+    match loader {
+        EnvLoader::None => Ok(HashMap::default()),
+        EnvLoader::Dotenv => {
+            let path = dir.join(".env");
+            if !fs.is_file(&path).await {
+                return Ok(HashMap::default());
+            }
+            Ok(parse_dotenv(&fs.load(&path).await?))
+        }
+        EnvLoader::Direnv => load_direnv_environment(dir).await,
+    }
+}
+
+async fn load_direnv_environment(dir: &Path) -> Result<HashMap<String, String>> {
+    let output = smol::process::Command::new("direnv")
+        .args(["export", "json"])
+        .current_dir(dir)
+        .output()
+        .await
+        .context("failed to run direnv")?;
+    anyhow::ensure!(
+        output.status.success(),
+        "direnv exited with error {:?}: {}",
+        output.status,
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // direnv prints nothing when there is no `.envrc` to load.
+    if output.stdout.iter().all(u8::is_ascii_whitespace) {
+        return Ok(HashMap::default());
+    }
+    let environment: HashMap<String, Option<String>> =
+        serde_json::from_slice(&output.stdout).context("failed to parse direnv output")?;
+    // The variables that direnv would unset are null.
+    Ok(environment
+        .into_iter()
+        .filter_map(|(key, value)| Some((key, value?)))
+        .collect())
+}
+
+/// Parses the `KEY=value` assignments of a `.env` file, which may be preceded
+/// by `export` and have quoted values. Blank lines and `#` comments are skipped.
+fn parse_dotenv(contents: &str) -> HashMap<String, String> {
+    let mut environment = HashMap::default();
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        let value = ['"', '\'']
+            .into_iter()
+            .find_map(|quote| value.strip_prefix(quote)?.strip_suffix(quote))
+            .unwrap_or(value);
+        environment.insert(key.trim().to_string(), value.to_string());
+    }
+    environment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dotenv() {
+        let environment = parse_dotenv(
+            "
+            # Toolchain
+            RUSTUP_TOOLCHAIN=nightly
+            export DATABASE_URL = \"postgres://localhost/dev\"
+            GREETING='hello world'
+            EMPTY=
+            not an assignment
+            ",
+        );
+        assert_eq!(environment.len(), 4);
+        assert_eq!(environment["RUSTUP_TOOLCHAIN"], "nightly");
+        assert_eq!(environment["DATABASE_URL"], "postgres://localhost/dev");
+        assert_eq!(environment["GREETING"], "hello world");
+        assert_eq!(environment["EMPTY"], "");
+    }
+}
@@ -1,10 +1,13 @@
 pub mod connection_manager;
 pub mod debounced_delay;
+pub mod dev_container;
+mod environment;
 pub mod lsp_command;
 pub mod lsp_ext_command;
 mod prettier_support;
 pub mod project_settings;
 pub mod search;
+mod search_index;
 mod task_inventory;
 pub mod terminals;
 
@@ -21,6 +24,7 @@ use client::{
 use clock::ReplicaId;
 use collections::{btree_map, hash_map, BTreeMap, HashMap, HashSet, VecDeque};
 use debounced_delay::DebouncedDelay;
+use dev_container::DevContainer;
 use futures::{
     channel::{
         mpsc::{self, UnboundedReceiver},
@@ -37,7 +41,8 @@ use git::{blame::Blame, repository::GitRepository};
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use gpui::{
     AnyModel, AppContext, AsyncAppContext, BackgroundExecutor, BorrowAppContext, Context, Entity,
-    EventEmitter, Model, ModelContext, PromptLevel, SharedString, Task, WeakModel, WindowContext,
+    EventEmitter, Global, Model, ModelContext, PromptLevel, SharedString, Task, WeakModel,
+    WindowContext,
 };
 use http::{HttpClient, Url};
 use itertools::Itertools;
@@ -72,11 +77,12 @@ use paths::{
 };
 use postage::watch;
 use prettier_support::{DefaultPrettier, PrettierInstance};
-use project_settings::{LspSettings, ProjectSettings};
+use project_settings::{EnvLoader, LspSettings, ProjectSettings};
 use rand::prelude::*;
 use rpc::{ErrorCode, ErrorExt as _};
 use search::SearchQuery;
 use search_history::SearchHistory;
+use search_index::{IndexingReader, SearchIndex};
 use serde::Serialize;
 use settings::{watch_config_file, Settings, SettingsLocation, SettingsStore};
 use sha2::{Digest, Sha256};
@@ -133,8 +139,12 @@ const MAX_SERVER_REINSTALL_ATTEMPT_COUNT: u64 = 4;
 const SERVER_REINSTALL_DEBOUNCE_TIMEOUT: Duration = Duration::from_secs(1);
 const SERVER_LAUNCHING_BEFORE_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
 pub const SERVER_PROGRESS_THROTTLE_TIMEOUT: Duration = Duration::from_millis(100);
+/// How often a local search reports the file it's searching.
+const SEARCH_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
 
 const MAX_PROJECT_SEARCH_HISTORY_SIZE: usize = 500;
+/// How many files a search worker indexes before adding them to the search index.
+const SEARCH_INDEX_BATCH_SIZE: usize = 64;
 
 pub trait Item {
     fn try_open(
@@ -210,6 +220,9 @@ pub struct Project {
     loading_local_worktrees:
         HashMap<Arc<Path>, Shared<Task<Result<Model<Worktree>, Arc<anyhow::Error>>>>>,
     opened_buffers: HashMap<BufferId, OpenBuffer>,
+    /// Buffers whose diff base was set with [`Project::set_fixed_diff_base`], which isn't
+    /// replaced by the one from git.
+    buffers_with_fixed_diff_base: HashSet<BufferId>,
     local_buffer_ids_by_path: HashMap<ProjectPath, BufferId>,
     local_buffer_ids_by_entry_id: HashMap<ProjectEntryId, BufferId>,
     buffer_snapshots: HashMap<BufferId, HashMap<LanguageServerId, Vec<LspBufferSnapshot>>>, // buffer_id -> server_id -> vec of snapshots
@@ -228,7 +241,11 @@ pub struct Project {
     tasks: Model<Inventory>,
     hosted_project_id: Option<ProjectId>,
     dev_server_project_id: Option<client::DevServerProjectId>,
+    dev_container: Option<DevContainer>,
     search_history: SearchHistory,
+    search_index: Arc<RwLock<SearchIndex>>,
+    #[allow(clippy::type_complexity)]
+    worktree_environments: HashMap<WorktreeId, (EnvLoader, Shared<Task<HashMap<String, String>>>)>,
 }
 
 pub enum LanguageServerToQuery {
@@ -236,6 +253,13 @@ pub enum LanguageServerToQuery {
     Other(LanguageServerId),
 }
 
+/// The language servers that were to be started while starting them is
+/// deferred.
+#[derive(Default)]
+struct DeferredLanguageServers(Vec<(WeakModel<Project>, WeakModel<Worktree>, Arc<Language>)>);
+
+impl Global for DeferredLanguageServers {}
+
 struct LspBufferSnapshot {
     version: i32,
     snapshot: TextBufferSnapshot,
@@ -353,6 +377,8 @@ pub enum Event {
     },
     CollaboratorJoined(proto::PeerId),
     CollaboratorLeft(proto::PeerId),
+    /// A collaborator focused or blurred one of the project's buffers.
+    CollaboratorPresenceChanged,
     RefreshInlayHints,
     RevealInProjectPanel(ProjectEntryId),
     SnippetEdit(BufferId, Vec<(lsp::Range, Snippet)>),
@@ -631,6 +657,10 @@ pub enum SearchResult {
         buffer: Model<Buffer>,
         ranges: Vec<Range<Anchor>>,
     },
+    /// The file that's being searched, reported periodically while a local search is underway.
+    Progress {
+        path: ProjectPath,
+    },
     LimitReached,
 }
 
@@ -646,6 +676,27 @@ impl Project {
         ProjectSettings::register(cx);
     }
 
+    /// Defers starting language servers until [`Self::start_deferred_language_servers`]
+    /// is called, like while the first window is drawn at startup.
+    pub fn defer_language_servers(cx: &mut AppContext) {
+        cx.default_global::<DeferredLanguageServers>();
+    }
+
+    /// Starts the language servers that were deferred, and stops deferring them.
+    pub fn start_deferred_language_servers(cx: &mut AppContext) {
+        if !cx.has_global::<DeferredLanguageServers>() {
+            return;
+        }
+        let deferred = cx.remove_global::<DeferredLanguageServers>();
+        for (project, worktree, language) in deferred.0 {
+            if let Some((project, worktree)) = project.upgrade().zip(worktree.upgrade()) {
+                project.update(cx, |project, cx| {
+                    project.start_language_servers(&worktree, language, cx)
+                });
+            }
+        }
+    }
+
     pub fn init(client: &Arc<Client>, cx: &mut AppContext) {
         connection_manager::init(client.clone(), cx);
         Self::init_settings(cx);
@@ -726,6 +777,7 @@ impl Project {
                 buffer_ordered_messages_tx: tx,
                 collaborators: Default::default(),
                 opened_buffers: Default::default(),
+                buffers_with_fixed_diff_base: Default::default(),
                 shared_buffers: Default::default(),
                 loading_buffers_by_path: Default::default(),
                 loading_local_worktrees: Default::default(),
@@ -776,7 +828,10 @@ impl Project {
                 tasks,
                 hosted_project_id: None,
                 dev_server_project_id: None,
+                dev_container: None,
                 search_history: Self::new_search_history(),
+                search_index: Default::default(),
+                worktree_environments: Default::default(),
             }
         })
     }
@@ -919,6 +974,7 @@ impl Project {
                 language_server_watched_paths: HashMap::default(),
                 language_server_watcher_registrations: HashMap::default(),
                 opened_buffers: Default::default(),
+                buffers_with_fixed_diff_base: Default::default(),
                 buffers_being_formatted: Default::default(),
                 buffers_needing_diff: Default::default(),
                 git_diff_debouncer: DebouncedDelay::new(),
@@ -938,7 +994,10 @@ impl Project {
                     .payload
                     .dev_server_project_id
                     .map(|dev_server_project_id| DevServerProjectId(dev_server_project_id)),
+                dev_container: None,
                 search_history: Self::new_search_history(),
+                search_index: Default::default(),
+                worktree_environments: Default::default(),
             };
             this.set_role(role, cx);
             for worktree in worktrees {
@@ -1119,6 +1178,8 @@ impl Project {
     }
 
     fn on_settings_changed(&mut self, cx: &mut ModelContext<Self>) {
+        self.update_worktree_environments(cx);
+
         let mut language_servers_to_start = Vec::new();
         let mut language_formatters_to_check = Vec::new();
         for buffer in self.opened_buffers.values() {
@@ -1290,6 +1351,27 @@ impl Project {
         self.dev_server_project_id
     }
 
+    /// The dev container that terminals, tasks and language servers of this project are run in, if any.
+    pub fn dev_container(&self) -> Option<&DevContainer> {
+        self.dev_container.as_ref()
+    }
+
+    /// Moves the project into the given dev container, or back to the host, restarting
+    /// its language servers there. Terminals that are already open are left where they run.
+    pub fn set_dev_container(
+        &mut self,
+        dev_container: Option<DevContainer>,
+        cx: &mut ModelContext<Self>,
+    ) {
+        if self.dev_container == dev_container {
+            return;
+        }
+        self.dev_container = dev_container;
+        let buffers = self.opened_buffers();
+        self.restart_language_servers_for_buffers(buffers, cx);
+        cx.notify();
+    }
+
     pub fn supports_remote_terminal(&self, cx: &AppContext) -> bool {
         let Some(id) = self.dev_server_project_id else {
             return false;
@@ -1352,6 +1434,50 @@ impl Project {
         self.collaborators.values().find(|c| c.replica_id == 0)
     }
 
+    /// Returns the collaborators that currently have each open buffer focused, keyed by the
+    /// buffer's project path.
+    pub fn collaborators_by_path(
+        &self,
+        cx: &AppContext,
+    ) -> HashMap<ProjectPath, Vec<Collaborator>> {
+        let mut collaborators_by_path = HashMap::<ProjectPath, Vec<Collaborator>>::default();
+        if self.collaborators.is_empty() {
+            return collaborators_by_path;
+        }
+
+        for buffer in self
+            .opened_buffers
+            .values()
+            .filter_map(|buffer| buffer.upgrade())
+        {
+            let buffer = buffer.read(cx);
+            let Some(file) = File::from_dyn(buffer.file()) else {
+                continue;
+            };
+            let project_path = ProjectPath {
+                worktree_id: file.worktree_id(cx),
+                path: file.path.clone(),
+            };
+            for replica_id in buffer.remote_replicas_with_selections() {
+                if let Some(collaborator) = self
+                    .collaborators
+                    .values()
+                    .find(|collaborator| collaborator.replica_id == replica_id)
+                {
+                    collaborators_by_path
+                        .entry(project_path.clone())
+                        .or_default()
+                        .push(collaborator.clone());
+                }
+            }
+        }
+
+        for collaborators in collaborators_by_path.values_mut() {
+            collaborators.sort_by_key(|collaborator| collaborator.replica_id);
+        }
+        collaborators_by_path
+    }
+
     pub fn set_worktrees_reordered(&mut self, worktrees_reordered: bool) {
         self.worktrees_reordered = worktrees_reordered;
     }
@@ -3209,6 +3335,16 @@ impl Project {
         language: Arc<Language>,
         cx: &mut ModelContext<Self>,
     ) {
+        if cx.has_global::<DeferredLanguageServers>() {
+            let project = cx.weak_model();
+            cx.global_mut::<DeferredLanguageServers>().0.push((
+                project,
+                worktree.downgrade(),
+                language,
+            ));
+            return;
+        }
+
         let root_file = worktree.update(cx, |tree, cx| tree.root_file(cx));
         let settings = language_settings(Some(&language), root_file.map(|f| f as _).as_ref(), cx);
         if !settings.enable_language_server {
@@ -5189,6 +5325,7 @@ impl Project {
                 | (_, FormatOnSave::External { command, arguments }) => {
                     let buffer_abs_path = buffer_abs_path.as_ref().map(|path| path.as_path());
                     format_operation = Self::format_via_external_command(
+                        &project,
                         buffer,
                         buffer_abs_path,
                         command,
@@ -5338,25 +5475,30 @@ impl Project {
     }
 
     async fn format_via_external_command(
+        this: &WeakModel<Self>,
         buffer: &Model<Buffer>,
         buffer_abs_path: Option<&Path>,
         command: &str,
         arguments: &[String],
         cx: &mut AsyncAppContext,
     ) -> Result<Option<Diff>> {
-        let working_dir_path = buffer.update(cx, |buffer, cx| {
+        let worktree = buffer.update(cx, |buffer, cx| {
             let file = File::from_dyn(buffer.file())?;
             let worktree = file.worktree.read(cx);
             let mut worktree_path = worktree.abs_path().to_path_buf();
             if worktree.root_entry()?.is_file() {
                 worktree_path.pop();
             }
-            Some(worktree_path)
+            Some((worktree.id(), worktree_path))
         })?;
 
         let mut child = smol::process::Command::new(command);
 
-        if let Some(working_dir_path) = working_dir_path {
+        if let Some((worktree_id, working_dir_path)) = worktree {
+            let environment = this
+                .update(cx, |this, cx| this.worktree_environment(worktree_id, cx))?
+                .await;
+            child.envs(environment);
             child.current_dir(working_dir_path);
         }
 
@@ -7096,7 +7238,8 @@ impl Project {
         // Local search is split into several phases.
         // TL;DR is that we do 2 passes; initial pass to pick files which contain at least one match
         // and the second phase that finds positions of all the matches found in the candidate files.
-        // The Receiver obtained from this function returns matches sorted by buffer path. Files without a buffer path are reported first.
+        // The Receiver obtained from this function returns matches as soon as they're found, in batches sorted by buffer path.
+        // Files without a buffer path are reported first. Dropping the Receiver cancels the search.
         //
         // It gets a bit hairy though, because we must account for files that do not have a persistent representation
         // on FS. Namely, if you have an untitled buffer or unsaved changes in a buffer, we want to scan that too.
@@ -7105,8 +7248,7 @@ impl Project {
         //    Then, we go through a worktree and check for files that do match a predicate. If the file had an opened version, we skip the scan
         //    of FS version for that file altogether - after all, what we have in memory is more up-to-date than what's in FS.
         // 2. At this point, we have a list of all potentially matching buffers/files.
-        //    We sort that list by buffer path - this list is retained for later use.
-        //    We ensure that all buffers are now opened and available in project.
+        //    As matching files are found, we sort each batch of them by buffer path and ensure that their buffers are opened and available in project.
         // 3. We run a scan over all the candidate buffers on multiple background threads.
         //    We cannot assume that there will even be a match - while at least one match
         //    is guaranteed for files obtained from FS, the buffers we got from memory (unsaved files/unnamed buffers) might not have a match at all.
@@ -7147,6 +7289,7 @@ impl Project {
         }
         let workers = background.num_cpus().min(path_count);
         let (matching_paths_tx, matching_paths_rx) = smol::channel::bounded(1024);
+        let current_path = Arc::new(Mutex::new(None));
         let mut unnamed_files = vec![];
         let opened_buffers = self
             .opened_buffers
@@ -7157,7 +7300,7 @@ impl Project {
                     let is_ignored = buffer
                         .project_path(cx)
                         .and_then(|path| self.entry_for_path(&path, cx))
-                        .map_or(false, |entry| entry.is_ignored);
+                        .map_or(false, |entry| entry.is_ignored || entry.is_hidden);
                     (is_ignored, buffer.snapshot())
                 });
                 if is_ignored && !query.include_ignored() {
@@ -7186,12 +7329,14 @@ impl Project {
                 opened_buffers,
                 cx.background_executor().clone(),
                 self.fs.clone(),
+                self.search_index.clone(),
                 workers,
                 query.clone(),
                 include_root,
                 path_count,
                 snapshots,
                 matching_paths_tx,
+                current_path.clone(),
             ))
             .detach();
 
@@ -7203,28 +7348,52 @@ impl Project {
 
             let mut matching_paths = matching_paths_rx
                 .take(MAX_SEARCH_RESULT_FILES + 1)
-                .collect::<Vec<_>>()
-                .await;
-            let mut limit_reached = if matching_paths.len() > MAX_SEARCH_RESULT_FILES {
-                matching_paths.pop();
-                true
-            } else {
-                false
-            };
-            cx.update(|cx| {
-                sort_search_matches(&mut matching_paths, cx);
-            })?;
-
+                .ready_chunks(64)
+                .fuse();
+            let mut limit_reached = false;
+            let mut file_count = 0;
             let mut range_count = 0;
+            let mut reported_path = None;
             let query = Arc::new(query);
 
-            // Now that we know what paths match the query, we will load at most
-            // 64 buffers at a time to avoid overwhelming the main thread. For each
+            // As paths are found to match the query, we will load at most 64
+            // buffers at a time to avoid overwhelming the main thread. For each
             // opened buffer, we will spawn a background task that retrieves all the
-            // ranges in the buffer matched by the query.
-            'outer: for matching_paths_chunk in matching_paths.chunks(64) {
+            // ranges in the buffer matched by the query. While waiting for matches,
+            // we periodically report the file being searched, which also lets us notice
+            // that the search was dropped and stop the background scan.
+            'outer: loop {
+                let mut matching_paths_chunk = futures::select_biased! {
+                    chunk = matching_paths.next() => match chunk {
+                        Some(chunk) => chunk,
+                        None => break,
+                    },
+                    _ = cx.background_executor().timer(SEARCH_PROGRESS_INTERVAL).fuse() => {
+                        if result_tx.is_closed() {
+                            return Ok(());
+                        }
+                        let path = current_path.lock().clone();
+                        if path != reported_path {
+                            reported_path = path.clone();
+                            if let Some(path) = path {
+                                result_tx.send(SearchResult::Progress { path }).await?;
+                            }
+                        }
+                        continue;
+                    },
+                };
+
+                file_count += matching_paths_chunk.len();
+                if file_count > MAX_SEARCH_RESULT_FILES {
+                    matching_paths_chunk.pop();
+                    limit_reached = true;
+                }
+                cx.update(|cx| {
+                    sort_search_matches(&mut matching_paths_chunk, cx);
+                })?;
+
                 let mut chunk_results = Vec::new();
-                for matching_path in matching_paths_chunk {
+                for matching_path in &matching_paths_chunk {
                     let query = query.clone();
                     let buffer = match matching_path {
                         SearchMatchCandidate::OpenBuffer { buffer, .. } => {
@@ -7291,14 +7460,18 @@ impl Project {
         opened_buffers: HashMap<Arc<Path>, (Model<Buffer>, BufferSnapshot)>,
         executor: BackgroundExecutor,
         fs: Arc<dyn Fs>,
+        search_index: Arc<RwLock<SearchIndex>>,
         workers: usize,
         query: SearchQuery,
         include_root: bool,
         path_count: usize,
         snapshots: Vec<(Snapshot, WorktreeSettings)>,
         matching_paths_tx: Sender<SearchMatchCandidate>,
+        current_path: Arc<Mutex<Option<ProjectPath>>>,
     ) {
         let fs = &fs;
+        let search_index = &search_index;
+        let current_path = &current_path;
         let query = &query;
         let matching_paths_tx = &matching_paths_tx;
         let snapshots = &snapshots;
@@ -7344,6 +7517,8 @@ impl Project {
                                 &opened_buffers,
                                 include_root,
                                 fs,
+                                search_index,
+                                current_path,
                             )
                             .await;
                         }
@@ -7371,6 +7546,26 @@ impl Project {
                 }
             })
             .await;
+
+        // Saved in a separate task, so that the search finishes without waiting on it.
+        let changed_snapshots = snapshots
+            .iter()
+            .filter(|(snapshot, _)| search_index.write().take_changed(snapshot.id()))
+            .map(|(snapshot, _)| snapshot.clone())
+            .collect::<Vec<_>>();
+        if !changed_snapshots.is_empty() {
+            let fs = fs.clone();
+            let search_index = search_index.clone();
+            executor
+                .spawn(async move {
+                    for snapshot in changed_snapshots {
+                        search_index::save_worktree(fs.as_ref(), &search_index, &snapshot)
+                            .await
+                            .log_err();
+                    }
+                })
+                .detach();
+        }
     }
 
     pub fn request_lsp<R: LspCommand>(
@@ -7704,6 +7899,8 @@ impl Project {
     }
 
     pub fn remove_worktree(&mut self, id_to_remove: WorktreeId, cx: &mut ModelContext<Self>) {
+        self.search_index.write().remove_worktree(id_to_remove);
+        self.worktree_environments.remove(&id_to_remove);
         self.diagnostics.remove(&id_to_remove);
         self.diagnostic_summaries.remove(&id_to_remove);
 
@@ -7785,11 +7982,15 @@ impl Project {
             let is_local = worktree.read(cx).is_local();
             match event {
                 worktree::Event::UpdatedEntries(changes) => {
+                    this.search_index
+                        .write()
+                        .remove_changed_paths(worktree.read(cx).id(), changes);
                     if is_local {
                         this.update_local_worktree_buffers(&worktree, changes, cx);
                         this.update_local_worktree_language_servers(&worktree, changes, cx);
                         this.update_local_worktree_settings(&worktree, changes, cx);
                         this.update_prettier_settings(&worktree, changes, cx);
+                        this.update_worktree_environment_files(&worktree, changes, cx);
                     }
 
                     cx.emit(Event::WorktreeUpdatedEntries(
@@ -7849,6 +8050,21 @@ impl Project {
         })
         .detach();
 
+        if worktree.read(cx).is_local() {
+            let fs = self.fs.clone();
+            let search_index = self.search_index.clone();
+            let worktree_id = worktree.read(cx).id();
+            let abs_path = worktree.read(cx).abs_path();
+            cx.background_executor()
+                .spawn(async move {
+                    search_index::load_worktree(fs.as_ref(), &search_index, worktree_id, &abs_path)
+                        .await
+                        .log_err();
+                })
+                .detach();
+        }
+
+        self.update_worktree_environments(cx);
         cx.emit(Event::WorktreeAdded);
         self.metadata_changed(cx);
     }
@@ -7884,6 +8100,7 @@ impl Project {
                 buffer
             } else {
                 self.opened_buffers.remove(&buffer_id);
+                self.buffers_with_fixed_diff_base.remove(&buffer_id);
                 self.local_buffer_ids_by_path.remove(&project_path);
                 self.local_buffer_ids_by_entry_id.remove(entry_id);
                 continue;
@@ -8037,6 +8254,30 @@ impl Project {
         }
     }
 
+    /// Sets the text that the buffer's changes are shown against, instead of the file's contents in
+    /// the git index. Unlike [`Buffer::set_diff_base`], it's kept when the repository changes.
+    pub fn set_fixed_diff_base(
+        &mut self,
+        buffer: &Model<Buffer>,
+        diff_base: String,
+        cx: &mut ModelContext<Self>,
+    ) {
+        let buffer_id = buffer.read(cx).remote_id();
+        self.buffers_with_fixed_diff_base.insert(buffer_id);
+        buffer.update(cx, |buffer, cx| {
+            buffer.set_diff_base(Some(diff_base.clone()), cx)
+        });
+        if let Some(project_id) = self.remote_id() {
+            self.client
+                .send(proto::UpdateDiffBase {
+                    project_id,
+                    buffer_id: buffer_id.into(),
+                    diff_base: Some(diff_base),
+                })
+                .log_err();
+        }
+    }
+
     fn update_local_worktree_buffers_git_repos(
         &mut self,
         worktree_handle: Model<Worktree>,
@@ -8075,6 +8316,12 @@ impl Project {
             .values()
             .filter_map(|buffer| {
                 let buffer = buffer.upgrade()?;
+                if self
+                    .buffers_with_fixed_diff_base
+                    .contains(&buffer.read(cx).remote_id())
+                {
+                    return None;
+                }
                 let file = File::from_dyn(buffer.read(cx).file())?;
                 if file.worktree != worktree_handle {
                     return None;
@@ -8094,7 +8341,7 @@ impl Project {
         let remote_id = self.remote_id();
         let client = self.client.clone();
         let fs = self.fs.clone();
-        cx.spawn(move |_, mut cx| async move {
+        cx.spawn(move |this, mut cx| async move {
             // Wait for all of the buffers to load.
             let future_buffers = future_buffers.collect::<Vec<_>>().await;
 
@@ -8149,15 +8396,21 @@ impl Project {
 
             // Assign the new diff bases on all of the buffers.
             for (buffer, diff_base) in diff_bases_by_buffer {
-                let buffer_id = buffer.update(&mut cx, |buffer, cx| {
-                    buffer.set_diff_base(diff_base.clone(), cx);
-                    buffer.remote_id().into()
+                // The diff base may have been fixed while it was loaded from git.
+                let buffer_id = buffer.read_with(&cx, |buffer, _| buffer.remote_id())?;
+                if this.read_with(&cx, |this, _| {
+                    this.buffers_with_fixed_diff_base.contains(&buffer_id)
+                })? {
+                    continue;
+                }
+                buffer.update(&mut cx, |buffer, cx| {
+                    buffer.set_diff_base(diff_base.clone(), cx)
                 })?;
                 if let Some(project_id) = remote_id {
                     client
                         .send(proto::UpdateDiffBase {
                             project_id,
-                            buffer_id,
+                            buffer_id: buffer_id.into(),
                             diff_base,
                         })
                         .log_err();
@@ -8350,7 +8603,7 @@ impl Project {
                         include_ignored
                             || worktree
                                 .entry_for_path(path.as_ref())
-                                .map_or(false, |entry| !entry.is_ignored)
+                                .map_or(false, |entry| !entry.is_ignored && !entry.is_hidden)
                     })
                     .flat_map(move |(path, summaries)| {
                         summaries.iter().map(move |(server_id, summary)| {
@@ -9097,10 +9350,16 @@ impl Project {
                 .map(language::proto::deserialize_operation)
                 .collect::<Result<Vec<_>, _>>()?;
             let is_remote = this.is_remote();
+            let updates_selections = ops
+                .iter()
+                .any(|op| matches!(op, Operation::UpdateSelections { .. }));
             match this.opened_buffers.entry(buffer_id) {
                 hash_map::Entry::Occupied(mut e) => match e.get_mut() {
                     OpenBuffer::Strong(buffer) => {
                         buffer.update(cx, |buffer, cx| buffer.apply_ops(ops, cx))?;
+                        if updates_selections {
+                            cx.emit(Event::CollaboratorPresenceChanged);
+                        }
                     }
                     OpenBuffer::Operations(operations) => operations.extend_from_slice(&ops),
                     OpenBuffer::Weak(_) => {}
@@ -10036,6 +10295,7 @@ impl Project {
                             });
                         }
                     }
+                    SearchResult::Progress { .. } => {}
                     SearchResult::LimitReached => limit_reached = true,
                 }
             }
@@ -11199,9 +11459,18 @@ async fn search_snapshots(
     opened_buffers: &HashMap<Arc<Path>, (Model<Buffer>, BufferSnapshot)>,
     include_root: bool,
     fs: &Arc<dyn Fs>,
+    search_index: &RwLock<SearchIndex>,
+    current_path: &Mutex<Option<ProjectPath>>,
 ) {
     let mut snapshot_start_ix = 0;
     let mut abs_path = PathBuf::new();
+    // Regular expressions can match text without containing any of their trigrams,
+    // so only text queries can skip files using the index.
+    let query_trigrams =
+        (!query.is_regex()).then(|| search_index::trigrams(query.as_str().as_bytes()));
+    // The files this worker indexed, added to the index in batches to not contend
+    // with the other workers for it on every file.
+    let mut indexed_files = Vec::new();
 
     for (snapshot, _) in snapshots {
         let snapshot_end_ix = snapshot_start_ix
@@ -11239,11 +11508,38 @@ async fn search_snapshots(
                 };
 
                 let matches = if matched_path {
+                    *current_path.lock() = Some(ProjectPath {
+                        worktree_id: snapshot.id(),
+                        path: entry.path.clone(),
+                    });
                     abs_path.clear();
                     abs_path.push(&snapshot.abs_path());
                     abs_path.push(&entry.path);
-                    if let Some(file) = fs.open_sync(&abs_path).await.log_err() {
-                        query.detect(file).unwrap_or(false)
+                    let may_contain = query_trigrams.as_ref().and_then(|query_trigrams| {
+                        search_index.read().may_contain(
+                            snapshot.id(),
+                            &entry.path,
+                            entry.mtime,
+                            query_trigrams,
+                        )
+                    });
+                    if may_contain == Some(false) {
+                        false
+                    } else if let Some(file) = fs.open_sync(&abs_path).await.log_err() {
+                        if query_trigrams.is_some() && may_contain.is_none() {
+                            let mut reader = IndexingReader::new(file);
+                            let matches = query.detect(&mut reader).unwrap_or(false);
+                            if let Some(indexed_file) = reader.finish(entry.mtime) {
+                                indexed_files
+                                    .push(((snapshot.id(), entry.path.clone()), indexed_file));
+                                if indexed_files.len() >= SEARCH_INDEX_BATCH_SIZE {
+                                    search_index.write().extend(indexed_files.drain(..));
+                                }
+                            }
+                            matches
+                        } else {
+                            query.detect(file).unwrap_or(false)
+                        }
                     } else {
                         false
                     }
@@ -11259,6 +11555,7 @@ async fn search_snapshots(
                         is_file: entry.is_file(),
                     };
                     if results_tx.send(project_path).await.is_err() {
+                        search_index.write().extend(indexed_files);
                         return;
                     }
                 }
@@ -11267,6 +11564,10 @@ async fn search_snapshots(
             snapshot_start_ix = snapshot_end_ix;
         }
     }
+
+    if !indexed_files.is_empty() {
+        search_index.write().extend(indexed_files);
+    }
 }
 
 async fn search_ignored_entry(
@@ -11495,6 +11796,8 @@ pub struct ProjectLspAdapterDelegate {
     http_client: Arc<dyn HttpClient>,
     language_registry: Arc<LanguageRegistry>,
     shell_env: Mutex<Option<HashMap<String, String>>>,
+    project_env: Shared<Task<HashMap<String, String>>>,
+    dev_container: Option<DevContainer>,
 }
 
 impl ProjectLspAdapterDelegate {
@@ -11510,18 +11813,24 @@ impl ProjectLspAdapterDelegate {
             http_client: project.client.http_client(),
             language_registry: project.languages.clone(),
             shell_env: Default::default(),
+            project_env: project
+                .worktree_environment(worktree.read(cx).id(), cx)
+                .shared(),
+            dev_container: project.dev_container.clone(),
         })
     }
 
     async fn load_shell_env(&self) {
         let worktree_abs_path = self.worktree.abs_path();
-        let shell_env = load_shell_environment(&worktree_abs_path)
+        let mut shell_env = load_shell_environment(&worktree_abs_path)
             .await
             .with_context(|| {
                 format!("failed to determine load login shell environment in {worktree_abs_path:?}")
             })
             .log_err()
             .unwrap_or_default();
+        // The project's environment variables take precedence over the login shell's.
+        shell_env.extend(self.project_env.clone().await);
         *self.shell_env.lock() = Some(shell_env);
     }
 }
@@ -11551,6 +11860,19 @@ impl LspAdapterDelegate for ProjectLspAdapterDelegate {
         self.shell_env.lock().as_ref().cloned().unwrap_or_default()
     }
 
+    async fn project_env(&self) -> HashMap<String, String> {
+        self.project_env.clone().await
+    }
+
+    fn language_server_command(&self, binary: LanguageServerBinary) -> LanguageServerBinary {
+        match &self.dev_container {
+            Some(dev_container) => {
+                dev_container.language_server_binary(binary, self.worktree.abs_path())
+            }
+            None => binary,
+        }
+    }
+
     #[cfg(not(target_os = "windows"))]
     async fn which(&self, command: &OsStr) -> Option<PathBuf> {
         let worktree_abs_path = self.worktree.abs_path();
@@ -12077,7 +12399,8 @@ fn sort_search_matches(search_matches: &mut Vec<SearchMatchCandidate>, cx: &AppC
     });
 }
 
-fn compare_paths(
+/// Orders paths the way project search results are sorted in, with directories before files at each level.
+pub fn compare_paths(
     (path_a, a_is_file): (&Path, bool),
     (path_b, b_is_file): (&Path, bool),
 ) -> cmp::Ordering {
@@ -20,6 +20,32 @@ pub struct ProjectSettings {
     /// Configuration for Git-related features
     #[serde(default)]
     pub git: GitSettings,
+
+    /// Environment variables to set for the terminals, tasks, formatters and
+    /// language servers started in the project.
+    ///
+    /// Default: {}
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+
+    /// How to load more environment variables for the project from its root
+    /// directory. Variables set in `env` take precedence over loaded ones.
+    ///
+    /// Default: none
+    #[serde(default)]
+    pub env_loader: EnvLoader,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EnvLoader {
+    /// Don't load any environment variables.
+    #[default]
+    None,
+    /// Load the variables of the `.env` file in the project's root.
+    Dotenv,
+    /// Load the variables that `direnv` exports for the project's root.
+    Direnv,
 }
 
 #[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
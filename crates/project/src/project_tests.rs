@@ -294,6 +294,55 @@ async fn test_managing_project_specific_settings(cx: &mut gpui::TestAppContext)
     );
 }
 
+#[gpui::test]
+async fn test_worktree_environment(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/the-root",
+        json!({
+            ".zed": {
+                "settings.json": r#"{ "env_loader": "dotenv", "env": { "B": "configured" } }"#,
+            },
+            ".env": "A=loaded\nB=loaded\n",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), ["/the-root".as_ref()], cx).await;
+    cx.executor().run_until_parked();
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees().next().unwrap().read(cx).id()
+    });
+
+    let environment = project
+        .update(cx, |project, cx| {
+            project.worktree_environment(worktree_id, cx)
+        })
+        .await;
+    assert_eq!(environment.get("A").map(String::as_str), Some("loaded"));
+    assert_eq!(environment.get("B").map(String::as_str), Some("configured"));
+
+    // The environment is loaded again when the `.env` file changes.
+    fs.save(
+        "/the-root/.env".as_ref(),
+        &"A=changed\n".into(),
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    cx.executor().run_until_parked();
+
+    let environment = project
+        .update(cx, |project, cx| {
+            project.worktree_environment(worktree_id, cx)
+        })
+        .await;
+    assert_eq!(environment.get("A").map(String::as_str), Some("changed"));
+    assert_eq!(environment.get("B").map(String::as_str), Some("configured"));
+}
+
 #[gpui::test]
 async fn test_managing_language_servers(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -1379,6 +1428,42 @@ async fn test_restarting_server_with_diagnostics_published(cx: &mut gpui::TestAp
     });
 }
 
+#[gpui::test]
+async fn test_deferring_language_servers(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree("/dir", json!({ "a.rs": "" })).await;
+
+    let project = Project::test(fs, ["/dir".as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+
+    language_registry.add(rust_lang());
+    let mut fake_servers =
+        language_registry.register_fake_lsp_adapter("Rust", FakeLspAdapter::default());
+
+    cx.update(Project::defer_language_servers);
+    let _buffer = project
+        .update(cx, |project, cx| project.open_local_buffer("/dir/a.rs", cx))
+        .await
+        .unwrap();
+    cx.executor().run_until_parked();
+    assert!(fake_servers.try_next().is_err());
+
+    // The server is started once it stops being deferred, and the buffer is
+    // opened with it.
+    cx.update(Project::start_deferred_language_servers);
+    let mut fake_server = fake_servers.next().await.unwrap();
+    let notification = fake_server
+        .receive_notification::<lsp::notification::DidOpenTextDocument>()
+        .await
+        .text_document;
+    assert_eq!(
+        notification.uri,
+        lsp::Url::from_file_path("/dir/a.rs").unwrap()
+    );
+}
+
 #[gpui::test]
 async fn test_restarted_server_reporting_invalid_buffer_version(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -3983,6 +4068,143 @@ async fn test_search(cx: &mut gpui::TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_search_after_file_changes(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/dir",
+        json!({
+            "one.rs": "const ONE: usize = 1;",
+            "two.rs": "const TWO: usize = one::ONE + one::ONE;",
+        }),
+    )
+    .await;
+    let project = Project::test(fs.clone(), ["/dir".as_ref()], cx).await;
+    let query = SearchQuery::text(
+        "two",
+        false,
+        false,
+        false,
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        search(&project, query.clone(), cx).await.unwrap(),
+        HashMap::from_iter([("dir/two.rs".to_string(), vec![6..9])])
+    );
+
+    // Files that were skipped before are searched again once they change
+    fs.insert_file("/dir/one.rs", "const ONE: usize = two::TWO - 1;".into())
+        .await;
+    cx.executor().run_until_parked();
+    assert_eq!(
+        search(&project, query, cx).await.unwrap(),
+        HashMap::from_iter([
+            ("dir/one.rs".to_string(), vec![19..22, 24..27]),
+            ("dir/two.rs".to_string(), vec![6..9]),
+        ])
+    );
+}
+
+#[gpui::test]
+async fn test_search_index_is_saved_between_sessions(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/dir",
+        json!({
+            "one.rs": "const ONE: usize = 1;",
+            "two.rs": "const TWO: usize = one::ONE + one::ONE;",
+        }),
+    )
+    .await;
+    let project = Project::test(fs.clone(), ["/dir".as_ref()], cx).await;
+    let query = SearchQuery::text(
+        "two",
+        false,
+        false,
+        false,
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    search(&project, query, cx).await.unwrap();
+    cx.executor().run_until_parked();
+    drop(project);
+
+    let project = Project::test(fs.clone(), ["/dir".as_ref()], cx).await;
+    cx.executor().run_until_parked();
+    project.read_with(cx, |project, cx| {
+        let worktree_id = project.worktrees().next().unwrap().read(cx).id();
+        let path = ProjectPath {
+            worktree_id,
+            path: Path::new("one.rs").into(),
+        };
+        let entry = project.entry_for_path(&path, cx).unwrap();
+        let may_contain = |query: &str| {
+            project.search_index.read().may_contain(
+                worktree_id,
+                &path.path,
+                entry.mtime,
+                &search_index::trigrams(query.as_bytes()),
+            )
+        };
+        assert_eq!(may_contain("ONE"), Some(true));
+        assert_eq!(
+            may_contain("TWO"),
+            Some(false),
+            "Files indexed in the previous session should be skipped without being read"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_fixed_diff_base_is_kept_when_repository_changes(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/dir",
+        json!({
+            ".git": {},
+            "new.txt": "one\ntwo\nthree\n",
+        }),
+    )
+    .await;
+    fs.set_index_for_repo(
+        Path::new("/dir/.git"),
+        &[(Path::new("new.txt"), "one\nthree\n".to_string())],
+    );
+    let project = Project::test(fs.clone(), ["/dir".as_ref()], cx).await;
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer("/dir/new.txt", cx)
+        })
+        .await
+        .unwrap();
+    cx.executor().run_until_parked();
+
+    project.update(cx, |project, cx| {
+        project.set_fixed_diff_base(&buffer, "one\n".to_string(), cx)
+    });
+    fs.set_index_for_repo(
+        Path::new("/dir/.git"),
+        &[(Path::new("new.txt"), "two\n".to_string())],
+    );
+    cx.executor().run_until_parked();
+
+    buffer.read_with(cx, |buffer, _| {
+        assert_eq!(
+            buffer.diff_base().map(|rope| rope.to_string()).as_deref(),
+            Some("one\n")
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_search_with_inclusions(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -5183,7 +5405,7 @@ async fn search(
             SearchResult::Buffer { buffer, ranges } => {
                 results.entry(buffer).or_insert(ranges);
             }
-            SearchResult::LimitReached => {}
+            SearchResult::Progress { .. } | SearchResult::LimitReached => {}
         }
     }
     Ok(results
@@ -0,0 +1,383 @@
+use anyhow::{anyhow, Context as _, Result};
+use collections::{HashMap, HashSet};
+use fs::{Fs, RenameOptions};
+use parking_lot::RwLock;
+use sha2::{Digest, Sha256};
+use std::{
+    io::{self, Read},
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+use worktree::{PathChange, ProjectEntryId, Snapshot, WorktreeId};
+
+/// Files bigger than this are searched without being indexed, to keep the index small.
+const MAX_INDEXED_FILE_SIZE: usize = 4 * 1024 * 1024;
+
+/// Written at the start of index files, and bumped whenever their format changes so
+/// that older index files are discarded instead of misread.
+const INDEX_FILE_VERSION: u32 = 1;
+
+/// Remembers which trigrams the files of a project contain, so that text searches
+/// only need to read the files that can contain the query.
+///
+/// Trigrams are built from ASCII-lowercased bytes, so the same index serves both
+/// case sensitive and case insensitive searches. Files are indexed the first time
+/// they are searched, and re-indexed when their modification time changes.
+///
+/// The index of each local worktree is saved to [`paths::search_index_dir`] after
+/// searches that indexed new files, and loaded again when the worktree is opened.
+#[derive(Default)]
+pub struct SearchIndex {
+    files: HashMap<(WorktreeId, Arc<Path>), IndexedFile>,
+    changed_worktrees: HashSet<WorktreeId>,
+}
+
+pub struct IndexedFile {
+    mtime: Option<SystemTime>,
+    trigrams: Vec<u32>,
+}
+
+impl IndexedFile {
+    /// Indexes the content of a file, unless it's too large to be indexed or has no
+    /// modification time to tell when it changes.
+    pub fn new(mtime: Option<SystemTime>, content: &[u8]) -> Option<Self> {
+        if mtime.is_none() || content.len() > MAX_INDEXED_FILE_SIZE {
+            return None;
+        }
+        Some(Self {
+            mtime,
+            trigrams: trigrams(content),
+        })
+    }
+}
+
+/// Reads a file while keeping a copy of its content, so that the file is indexed
+/// while it's searched instead of being read twice. Files too large to be indexed
+/// are only streamed through.
+pub struct IndexingReader<R> {
+    reader: R,
+    content: Option<Vec<u8>>,
+}
+
+impl<R: Read> IndexingReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            content: Some(Vec::new()),
+        }
+    }
+
+    /// Reads what the search left of the file and indexes it, unless it's too large.
+    pub fn finish(mut self, mtime: Option<SystemTime>) -> Option<IndexedFile> {
+        let mut buffer = [0; 8192];
+        while self.content.is_some() {
+            if self.read(&mut buffer).ok()? == 0 {
+                break;
+            }
+        }
+        IndexedFile::new(mtime, &self.content?)
+    }
+}
+
+impl<R: Read> Read for IndexingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.reader.read(buf)?;
+        if let Some(content) = &mut self.content {
+            if content.len() + len > MAX_INDEXED_FILE_SIZE {
+                self.content = None;
+            } else {
+                content.extend_from_slice(&buf[..len]);
+            }
+        }
+        Ok(len)
+    }
+}
+
+impl SearchIndex {
+    /// Returns whether the file can contain all of the given trigrams,
+    /// or `None` if the file isn't indexed as of the given modification time.
+    pub fn may_contain(
+        &self,
+        worktree_id: WorktreeId,
+        path: &Arc<Path>,
+        mtime: Option<SystemTime>,
+        query_trigrams: &[u32],
+    ) -> Option<bool> {
+        let file = self.files.get(&(worktree_id, path.clone()))?;
+        if mtime.is_none() || file.mtime != mtime {
+            return None;
+        }
+        Some(
+            query_trigrams
+                .iter()
+                .all(|trigram| file.trigrams.binary_search(trigram).is_ok()),
+        )
+    }
+
+    /// Adds files that were indexed, all at once so that searches indexing files in
+    /// parallel don't wait on each other for every file.
+    pub fn extend(
+        &mut self,
+        files: impl IntoIterator<Item = ((WorktreeId, Arc<Path>), IndexedFile)>,
+    ) {
+        for ((worktree_id, path), file) in files {
+            self.changed_worktrees.insert(worktree_id);
+            self.files.insert((worktree_id, path), file);
+        }
+    }
+
+    /// Returns whether files of the worktree were indexed since it was last saved.
+    pub fn take_changed(&mut self, worktree_id: WorktreeId) -> bool {
+        self.changed_worktrees.remove(&worktree_id)
+    }
+
+    /// Encodes the indexed files that are still in the worktree, so that files
+    /// removed from it are dropped from the saved index.
+    fn encode_worktree(&self, snapshot: &Snapshot) -> Vec<u8> {
+        let mut bytes = INDEX_FILE_VERSION.to_le_bytes().to_vec();
+        for entry in snapshot.files(true, 0) {
+            let Some(file) = self.files.get(&(snapshot.id(), entry.path.clone())) else {
+                continue;
+            };
+            let (Some(path), Some(mtime)) = (entry.path.to_str(), file.mtime) else {
+                continue;
+            };
+            let Ok(mtime) = mtime.duration_since(SystemTime::UNIX_EPOCH) else {
+                continue;
+            };
+            bytes.extend((path.len() as u32).to_le_bytes());
+            bytes.extend(path.as_bytes());
+            bytes.extend(mtime.as_secs().to_le_bytes());
+            bytes.extend(mtime.subsec_nanos().to_le_bytes());
+            bytes.extend((file.trigrams.len() as u32).to_le_bytes());
+            for trigram in &file.trigrams {
+                bytes.extend(trigram.to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// Adds the files of a saved index, keeping the files that were indexed in the
+    /// meantime as they're at least as recent.
+    fn decode_worktree(&mut self, worktree_id: WorktreeId, bytes: &[u8]) -> Result<()> {
+        let mut decoder = Decoder(bytes);
+        let version = decoder.u32()?;
+        if version != INDEX_FILE_VERSION {
+            return Err(anyhow!("unsupported search index version {version}"));
+        }
+        let mut files = Vec::new();
+        while !decoder.0.is_empty() {
+            let path_len = decoder.u32()? as usize;
+            let path = std::str::from_utf8(decoder.bytes(path_len)?)?;
+            let secs = u64::from_le_bytes(decoder.bytes(8)?.try_into()?);
+            let nanos = decoder.u32()?;
+            let trigram_count = decoder.u32()? as usize;
+            let trigrams = (0..trigram_count)
+                .map(|_| decoder.u32())
+                .collect::<Result<Vec<_>>>()?;
+            files.push((
+                Arc::<Path>::from(Path::new(path)),
+                IndexedFile {
+                    mtime: Some(SystemTime::UNIX_EPOCH + Duration::new(secs, nanos)),
+                    trigrams,
+                },
+            ));
+        }
+        for (path, file) in files {
+            self.files.entry((worktree_id, path)).or_insert(file);
+        }
+        Ok(())
+    }
+
+    /// Forgets the files that were added, changed or removed, rather than keeping
+    /// them around until they're searched again.
+    pub fn remove_changed_paths(
+        &mut self,
+        worktree_id: WorktreeId,
+        changes: &[(Arc<Path>, ProjectEntryId, PathChange)],
+    ) {
+        if self.files.is_empty() {
+            return;
+        }
+        for (path, _, change) in changes {
+            if *change != PathChange::Loaded {
+                self.files.remove(&(worktree_id, path.clone()));
+            }
+        }
+    }
+
+    pub fn remove_worktree(&mut self, worktree_id: WorktreeId) {
+        self.files.retain(|(id, _), _| *id != worktree_id);
+    }
+}
+
+struct Decoder<'a>(&'a [u8]);
+
+impl<'a> Decoder<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.0.len() < len {
+            return Err(anyhow!("search index file is truncated"));
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.bytes(4)?.try_into()?))
+    }
+}
+
+/// Returns where the index of the worktree at the given path is saved.
+fn index_file_path(worktree_abs_path: &Path) -> PathBuf {
+    let mut hasher = Sha256::new();
+    hasher.update(worktree_abs_path.to_string_lossy().as_bytes());
+    paths::search_index_dir().join(format!("{:x}", hasher.finalize()))
+}
+
+/// Loads the saved index of a worktree, if it was saved before.
+pub async fn load_worktree(
+    fs: &dyn Fs,
+    index: &RwLock<SearchIndex>,
+    worktree_id: WorktreeId,
+    worktree_abs_path: &Path,
+) -> Result<()> {
+    let path = index_file_path(worktree_abs_path);
+    if !fs.is_file(&path).await {
+        return Ok(());
+    }
+    let mut bytes = Vec::new();
+    fs.open_sync(&path).await?.read_to_end(&mut bytes)?;
+    index
+        .write()
+        .decode_worktree(worktree_id, &bytes)
+        .with_context(|| format!("loading search index {path:?}"))
+}
+
+/// Saves the index of a worktree, replacing the index that was saved before.
+pub async fn save_worktree(
+    fs: &dyn Fs,
+    index: &RwLock<SearchIndex>,
+    snapshot: &Snapshot,
+) -> Result<()> {
+    let bytes = index.read().encode_worktree(snapshot);
+    let path = index_file_path(snapshot.abs_path());
+    // Written next to the index and moved over it, so that an interrupted save
+    // doesn't leave a truncated index behind.
+    let temp_path = path.with_extension("tmp");
+    fs.create_dir(paths::search_index_dir()).await?;
+    let mut content = futures::io::Cursor::new(bytes);
+    fs.create_file_with(&temp_path, Pin::new(&mut content))
+        .await?;
+    fs.rename(
+        &temp_path,
+        &path,
+        RenameOptions {
+            overwrite: true,
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+/// Returns the sorted, deduplicated trigrams of the ASCII-lowercased text.
+pub fn trigrams(text: &[u8]) -> Vec<u32> {
+    let mut trigrams = text
+        .windows(3)
+        .map(|window| {
+            u32::from_be_bytes([
+                0,
+                window[0].to_ascii_lowercase(),
+                window[1].to_ascii_lowercase(),
+                window[2].to_ascii_lowercase(),
+            ])
+        })
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    trigrams.sort_unstable();
+    trigrams
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_may_contain() {
+        let worktree_id = WorktreeId::from_usize(1);
+        let path: Arc<Path> = Path::new("src/main.rs").into();
+        let mtime = Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1));
+        let mut index = SearchIndex::default();
+        assert_eq!(
+            index.may_contain(worktree_id, &path, mtime, &trigrams(b"main")),
+            None
+        );
+
+        index.extend(
+            IndexedFile::new(mtime, b"fn main() {}")
+                .map(|file| ((worktree_id, path.clone()), file)),
+        );
+        assert_eq!(
+            index.may_contain(worktree_id, &path, mtime, &trigrams(b"MAIN")),
+            Some(true)
+        );
+        assert_eq!(
+            index.may_contain(worktree_id, &path, mtime, &trigrams(b"fn ma")),
+            Some(true)
+        );
+        assert_eq!(
+            index.may_contain(worktree_id, &path, mtime, &trigrams(b"mains")),
+            Some(false)
+        );
+
+        let new_mtime = Some(SystemTime::UNIX_EPOCH + Duration::from_secs(2));
+        assert_eq!(
+            index.may_contain(worktree_id, &path, new_mtime, &trigrams(b"main")),
+            None,
+            "Modified files should be re-indexed"
+        );
+
+        index.remove_worktree(worktree_id);
+        assert_eq!(
+            index.may_contain(worktree_id, &path, mtime, &trigrams(b"main")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_indexing_reader() {
+        let mtime = Some(SystemTime::UNIX_EPOCH);
+        let content = b"fn main() { println!(\"hello\"); }";
+
+        // What the search didn't read is read when the file is indexed.
+        let mut reader = IndexingReader::new(&content[..]);
+        let mut start = [0; 4];
+        reader.read_exact(&mut start).unwrap();
+        let file = reader.finish(mtime).unwrap();
+        assert_eq!(file.trigrams, trigrams(content));
+
+        let large_content = vec![b'a'; MAX_INDEXED_FILE_SIZE + 1];
+        let mut reader = IndexingReader::new(large_content.as_slice());
+        io::copy(&mut reader, &mut io::sink()).unwrap();
+        assert!(reader.finish(mtime).is_none());
+        assert!(IndexedFile::new(None, content).is_none());
+    }
+
+    #[test]
+    fn test_decoding_invalid_index_files() {
+        let worktree_id = WorktreeId::from_usize(1);
+        let mut index = SearchIndex::default();
+        assert!(index
+            .decode_worktree(worktree_id, &(INDEX_FILE_VERSION + 1).to_le_bytes())
+            .is_err());
+
+        let mut bytes = INDEX_FILE_VERSION.to_le_bytes().to_vec();
+        bytes.extend(11u32.to_le_bytes());
+        bytes.extend(b"src/main");
+        assert!(index.decode_worktree(worktree_id, &bytes).is_err());
+        assert!(index.files.is_empty());
+    }
+}
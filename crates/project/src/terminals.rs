@@ -1,8 +1,9 @@
-use crate::Project;
+use crate::{dev_container::DevContainer, Project};
 use anyhow::Context as _;
 use collections::HashMap;
 use gpui::{
-    AnyWindowHandle, AppContext, Context, Entity, Model, ModelContext, SharedString, WeakModel,
+    AnyWindowHandle, AppContext, Context, Entity, Model, ModelContext, SharedString, Task,
+    WeakModel,
 };
 use itertools::Itertools;
 use settings::{Settings, SettingsLocation};
@@ -19,6 +20,7 @@ use terminal::{
     TaskState, TaskStatus, Terminal, TerminalBuilder,
 };
 use util::ResultExt;
+use worktree::Worktree;
 
 // #[cfg(target_os = "macos")]
 // use std::os::unix::ffi::OsStrExt;
@@ -65,31 +67,73 @@ impl Project {
         })
     }
 
+    /// Creates a terminal, or a terminal running the task, once the environment
+    /// of the project has been loaded for it.
     pub fn create_terminal(
         &mut self,
         working_directory: Option<TerminalWorkDir>,
         spawn_task: Option<SpawnInTerminal>,
         window: AnyWindowHandle,
         cx: &mut ModelContext<Self>,
+    ) -> Task<anyhow::Result<Model<Terminal>>> {
+        let environment = match self.terminal_worktree(&working_directory, &spawn_task, cx) {
+            Some((worktree, _)) => self.worktree_environment(worktree.read(cx).id(), cx),
+            None => Task::ready(HashMap::default()),
+        };
+        cx.spawn(|project, mut cx| async move {
+            let environment = environment.await;
+            project.update(&mut cx, |project, cx| {
+                project.create_terminal_with_environment(
+                    environment,
+                    working_directory,
+                    spawn_task,
+                    window,
+                    cx,
+                )
+            })?
+        })
+    }
+
+    /// The worktree of the terminal's or task's working directory, and the
+    /// path of the directory within it.
+    fn terminal_worktree(
+        &self,
+        working_directory: &Option<TerminalWorkDir>,
+        spawn_task: &Option<SpawnInTerminal>,
+        cx: &AppContext,
+    ) -> Option<(Model<Worktree>, PathBuf)> {
+        let terminal_cwd = working_directory.as_ref().and_then(|cwd| cwd.local_path());
+        let task_cwd = spawn_task
+            .as_ref()
+            .and_then(|spawn_task| spawn_task.cwd.as_ref())
+            .and_then(|cwd| cwd.local_path());
+
+        terminal_cwd
+            .and_then(|terminal_cwd| self.find_local_worktree(&terminal_cwd, cx))
+            .or_else(|| task_cwd.and_then(|spawn_cwd| self.find_local_worktree(&spawn_cwd, cx)))
+    }
+
+    fn create_terminal_with_environment(
+        &mut self,
+        environment: HashMap<String, String>,
+        working_directory: Option<TerminalWorkDir>,
+        spawn_task: Option<SpawnInTerminal>,
+        window: AnyWindowHandle,
+        cx: &mut ModelContext<Self>,
     ) -> anyhow::Result<Model<Terminal>> {
         // used only for TerminalSettings::get
-        let worktree = {
-            let terminal_cwd = working_directory.as_ref().and_then(|cwd| cwd.local_path());
-            let task_cwd = spawn_task
-                .as_ref()
-                .and_then(|spawn_task| spawn_task.cwd.as_ref())
-                .and_then(|cwd| cwd.local_path());
-
-            terminal_cwd
-                .and_then(|terminal_cwd| self.find_local_worktree(&terminal_cwd, cx))
-                .or_else(|| task_cwd.and_then(|spawn_cwd| self.find_local_worktree(&spawn_cwd, cx)))
-        };
+        let worktree = self.terminal_worktree(&working_directory, &spawn_task, cx);
 
         let settings_location = worktree.as_ref().map(|(worktree, path)| SettingsLocation {
             worktree_id: worktree.read(cx).id().to_usize(),
             path,
         });
 
+        let working_directory = match &self.dev_container {
+            Some(dev_container) => container_work_dir(dev_container, working_directory),
+            None => working_directory,
+        };
+
         let is_terminal = spawn_task.is_none()
             && working_directory
                 .as_ref()
@@ -98,7 +142,9 @@ impl Project {
         let python_settings = settings.detect_venv.clone();
         let (completion_tx, completion_rx) = bounded(1);
 
-        let mut env = settings.env.clone();
+        // The terminal settings and the task add to the environment of the project.
+        let mut env = environment;
+        env.extend(settings.env.clone());
         // Alacritty uses parent project's working directory when no working directory is provided
         // https://github.com/alacritty/alacritty/blob/fd1a3cc79192d1d03839f0fd8c72e1f8d0fce42e/extra/man/alacritty.5.scd?plain=1#L47-L52
 
@@ -135,6 +181,32 @@ impl Project {
                     ssh_shell,
                 )
             }
+            Some(TerminalWorkDir::Container { container_id, path }) => {
+                log::debug!("Connecting to a dev container: {container_id:?}");
+                let dev_container = self
+                    .dev_container
+                    .as_ref()
+                    .filter(|dev_container| dev_container.container_id == *container_id)
+                    .context("the project is not in this dev container")?;
+                let container_shell = prepare_container_shell(
+                    dev_container,
+                    &env,
+                    spawn_task.as_ref(),
+                    path.as_deref(),
+                )?;
+
+                (
+                    spawn_task.map(|spawn_task| TaskState {
+                        id: spawn_task.id,
+                        full_label: spawn_task.full_label,
+                        label: spawn_task.label,
+                        command_label: spawn_task.command_label,
+                        status: TaskStatus::Running,
+                        completion_rx,
+                    }),
+                    container_shell,
+                )
+            }
             _ => {
                 if let Some(spawn_task) = spawn_task {
                     log::debug!("Spawning task: {spawn_task:?}");
@@ -366,6 +438,77 @@ fn prepare_ssh_shell(
     Ok(Shell::WithArguments { program, args })
 }
 
+/// Moves local working directories, and terminals without one, into the dev container.
+fn container_work_dir(
+    dev_container: &DevContainer,
+    working_directory: Option<TerminalWorkDir>,
+) -> Option<TerminalWorkDir> {
+    let path = match &working_directory {
+        Some(TerminalWorkDir::Local(path)) => dev_container.container_path(path),
+        None => None,
+        Some(_) => return working_directory,
+    };
+
+    Some(TerminalWorkDir::Container {
+        container_id: dev_container.container_id.clone(),
+        path: Some(path.unwrap_or_else(|| dev_container.workspace_folder.clone())),
+    })
+}
+
+/// Runs the terminal in the dev container with `docker exec`, with the environment of the project
+/// and the task, and the paths into the project on the host translated to the container's.
+fn prepare_container_shell(
+    dev_container: &DevContainer,
+    env: &HashMap<String, String>,
+    spawn_task: Option<&SpawnInTerminal>,
+    path: Option<&str>,
+) -> anyhow::Result<Shell> {
+    let to_run = if let Some(spawn_task) = spawn_task {
+        let command = dev_container.translate_paths(&spawn_task.command);
+        Some(shlex::try_quote(&command)?.into_owned())
+            .into_iter()
+            .chain(spawn_task.args.iter().filter_map(|arg| {
+                let arg = dev_container.translate_paths(arg);
+                shlex::try_quote(&arg).ok().map(|arg| arg.into_owned())
+            }))
+            .join(" ")
+    } else {
+        // Containers often don't set `$SHELL`, so fall back to the best shell available.
+        "exec ${SHELL:-$(command -v bash || echo sh)} -l".to_string()
+    };
+
+    // Alacritty's terminfo is unlikely to be installed in the container, see `prepare_ssh_shell`.
+    let mut args = vec![
+        "exec".to_string(),
+        "-it".to_string(),
+        "-e".to_string(),
+        "TERM=xterm-256color".to_string(),
+    ];
+    let mut container_env = env.clone();
+    if let Some(spawn_task) = spawn_task {
+        container_env.extend(spawn_task.env.clone());
+    }
+    for (name, value) in dev_container.container_env(&container_env) {
+        args.push("-e".to_string());
+        args.push(format!("{name}={value}"));
+    }
+    if let Some(path) = path {
+        args.push("-w".to_string());
+        args.push(path.to_string());
+    }
+    args.extend([
+        dev_container.container_id.clone(),
+        "sh".to_string(),
+        "-c".to_string(),
+        to_run,
+    ]);
+
+    Ok(Shell::WithArguments {
+        program: "docker".to_string(),
+        args,
+    })
+}
+
 fn add_environment_path(env: &mut HashMap<String, String>, new_path: &Path) -> anyhow::Result<()> {
     let mut env_paths = vec![new_path.to_path_buf()];
     if let Some(path) = env.get("PATH").or(env::var("PATH").ok().as_ref()) {
@@ -381,7 +524,10 @@ fn add_environment_path(env: &mut HashMap<String, String>, new_path: &Path) -> a
 
 #[cfg(test)]
 mod tests {
+    use crate::dev_container::DevContainer;
     use collections::HashMap;
+    use task::{RevealStrategy, SpawnInTerminal, TaskId};
+    use terminal::terminal_settings::Shell;
 
     #[test]
     fn test_add_environment_path_with_existing_path() {
@@ -418,4 +564,71 @@ mod tests {
         }
         assert_eq!(env.get("OTHER").unwrap(), "aaa");
     }
+
+    #[test]
+    fn test_prepare_container_shell() {
+        let dev_container = DevContainer {
+            container_id: "abc".to_string(),
+            workspace_folder: "/workspaces/project".to_string(),
+            local_folder: std::path::PathBuf::from("/home/me/project"),
+        };
+        let env = HashMap::from_iter([
+            (
+                "PATH".to_string(),
+                "/home/me/.cargo/bin:/usr/bin".to_string(),
+            ),
+            ("RUST_LOG".to_string(), "info".to_string()),
+        ]);
+        let spawn_task = SpawnInTerminal {
+            id: TaskId("test".to_string()),
+            command: "cargo".to_string(),
+            args: vec![
+                "test".to_string(),
+                "--manifest-path".to_string(),
+                "/home/me/project/Cargo.toml".to_string(),
+            ],
+            env: HashMap::from_iter([(
+                "ZED_FILE".to_string(),
+                "/home/me/project/src/main.rs".to_string(),
+            )]),
+            full_label: "cargo test".to_string(),
+            label: "cargo test".to_string(),
+            command_label: "cargo test".to_string(),
+            cwd: None,
+            use_new_terminal: false,
+            allow_concurrent_runs: false,
+            reveal: RevealStrategy::default(),
+        };
+
+        let shell = super::prepare_container_shell(
+            &dev_container,
+            &env,
+            Some(&spawn_task),
+            Some("/workspaces/project/src"),
+        )
+        .unwrap();
+        let Shell::WithArguments { program, args } = shell else {
+            panic!("expected a shell with arguments");
+        };
+        assert_eq!(program, "docker");
+        assert_eq!(
+            args,
+            [
+                "exec",
+                "-it",
+                "-e",
+                "TERM=xterm-256color",
+                "-e",
+                "RUST_LOG=info",
+                "-e",
+                "ZED_FILE=/workspaces/project/src/main.rs",
+                "-w",
+                "/workspaces/project/src",
+                "abc",
+                "sh",
+                "-c",
+                "cargo test --manifest-path /workspaces/project/Cargo.toml",
+            ]
+        );
+    }
 }
@@ -1,10 +1,12 @@
 mod project_panel_settings;
-use client::{ErrorCode, ErrorExt};
+use client::{Collaborator, ErrorCode, ErrorExt};
 use settings::{Settings, SettingsStore};
 
 use db::kvp::KEY_VALUE_STORE;
 use editor::{items::entry_git_aware_label_color, scroll::Autoscroll, Editor};
 use file_icons::FileIcons;
+use futures::StreamExt;
+use fuzzy::{CharBag, PathMatchCandidate};
 
 use anyhow::{anyhow, Result};
 use collections::{hash_map, BTreeSet, HashMap};
@@ -13,12 +15,15 @@ use gpui::{
     actions, anchored, deferred, div, impl_actions, px, uniform_list, Action, AnyElement,
     AppContext, AssetSource, AsyncWindowContext, ClipboardItem, DismissEvent, Div, DragMoveEvent,
     EventEmitter, ExternalPaths, FocusHandle, FocusableView, InteractiveElement, KeyContext,
-    ListSizingBehavior, Model, MouseButton, MouseDownEvent, ParentElement, Pixels, Point,
-    PromptLevel, Render, Stateful, Styled, Subscription, Task, UniformListScrollHandle, View,
-    ViewContext, VisualContext as _, WeakView, WindowContext,
+    KeyDownEvent, ListSizingBehavior, Model, MouseButton, MouseDownEvent, ParentElement, Pixels,
+    Point, PromptLevel, Render, Stateful, Styled, Subscription, Task, UniformListScrollHandle,
+    View, ViewContext, VisualContext as _, WeakView, WindowContext,
 };
 use menu::{Confirm, SelectFirst, SelectLast, SelectNext, SelectPrev};
-use project::{Entry, EntryKind, Fs, Project, ProjectEntryId, ProjectPath, Worktree, WorktreeId};
+use project::{
+    copy_recursive, CopyOptions, Entry, EntryKind, Fs, Project, ProjectEntryId, ProjectPath,
+    RemoveOptions, Worktree, WorktreeId,
+};
 use project_panel_settings::{ProjectPanelDockPosition, ProjectPanelSettings};
 use serde::{Deserialize, Serialize};
 use std::{
@@ -27,19 +32,30 @@ use std::{
     ffi::OsStr,
     ops::Range,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{
+        atomic::{self, AtomicUsize},
+        Arc,
+    },
 };
 use theme::ThemeSettings;
-use ui::{prelude::*, v_flex, ContextMenu, Icon, KeyBinding, Label, ListItem, Tooltip};
+use ui::{
+    prelude::*, v_flex, ContextMenu, HighlightedLabel, Icon, KeyBinding, Label, ListItem, Tooltip,
+};
 use util::{maybe, ResultExt, TryFutureExt};
 use workspace::{
     dock::{DockPosition, Panel, PanelEvent},
-    notifications::{DetachAndPromptErr, NotifyTaskExt},
-    DraggedSelection, OpenInTerminal, SelectedEntry, Workspace,
+    notifications::{DetachAndPromptErr, NotificationId, NotifyTaskExt},
+    pane,
+    presence::render_collaborator_presence,
+    DraggedSelection, OpenInTerminal, SelectedEntry, SplitDirection, Toast, Workspace,
 };
 use worktree::CreatedEntry;
 
 const PROJECT_PANEL_KEY: &str = "ProjectPanel";
+const DELETED_ENTRIES_DIR: &str = "deleted_entries";
+/// Permanent deletions of entries larger than this can't be undone, to avoid
+/// copying them aside before deleting them.
+const MAX_DELETED_ENTRIES_BACKUP_SIZE: u64 = 100 * 1024 * 1024;
 const NEW_ENTRY_ID: ProjectEntryId = ProjectEntryId::MAX;
 
 pub struct ProjectPanel {
@@ -59,10 +75,19 @@ pub struct ProjectPanel {
     edit_state: Option<EditState>,
     filename_editor: View<Editor>,
     clipboard: Option<ClipboardEntry>,
+    /// The last paste, which can be undone, along with the absolute paths of the
+    /// entries it replaced, which get restored from the trash.
+    last_file_operation: Option<(FileOperation, Vec<PathBuf>)>,
+    filter_query: String,
+    filter_match_positions: HashMap<ProjectEntryId, Vec<usize>>,
+    show_hidden_files: bool,
     _dragged_entry_destination: Option<Arc<Path>>,
     workspace: WeakView<Workspace>,
     width: Option<Pixels>,
     pending_serialization: Task<Option<()>>,
+    /// The backup of the last permanently deleted entries, which is removed once
+    /// another deletion replaces the notification offering to undo it.
+    last_deletion_backup_dir: Option<PathBuf>,
 }
 
 #[derive(Clone, Debug)]
@@ -80,6 +105,35 @@ enum ClipboardEntry {
     Cut(BTreeSet<SelectedEntry>),
 }
 
+/// A file operation that can be reverted with [`Undo`].
+#[derive(Clone, Debug)]
+enum FileOperation {
+    /// Entries that were created by copying, and get deleted on undo.
+    Copied(Vec<ProjectEntryId>),
+    /// Entries that were moved, along with the path they get moved back to on undo.
+    Moved(Vec<(ProjectEntryId, Arc<Path>)>),
+}
+
+/// Entries that were deleted, and how they're brought back when that's undone.
+#[derive(Debug)]
+enum DeletedEntries {
+    /// Entries that were moved to the trash, from these paths.
+    Trashed(Vec<PathBuf>),
+    /// Entries that were deleted permanently, after being copied into `backup_dir`,
+    /// along with the path they get copied back to.
+    BackedUp {
+        backup_dir: PathBuf,
+        backups: Vec<(PathBuf, PathBuf)>,
+    },
+}
+
+/// The destination of a pasted entry.
+struct PastePath {
+    path: PathBuf,
+    /// The entry already occupying `path` and a free path next to it, used to keep both entries.
+    conflict: Option<(ProjectEntryId, PathBuf)>,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct EntryDetails {
     filename: String,
@@ -94,10 +148,12 @@ pub struct EntryDetails {
     is_editing: bool,
     is_processing: bool,
     is_cut: bool,
+    filter_match_positions: Vec<usize>,
     git_status: Option<GitFileStatus>,
     is_private: bool,
     worktree_id: WorktreeId,
     canonical_path: Option<PathBuf>,
+    collaborators: Vec<Collaborator>,
 }
 
 #[derive(PartialEq, Clone, Default, Debug, Deserialize)]
@@ -129,6 +185,8 @@ actions!(
         RevealInFinder,
         Cut,
         Paste,
+        Undo,
+        RemoveFilterCharacter,
         Rename,
         Open,
         OpenPermanent,
@@ -137,6 +195,7 @@ actions!(
         UnfoldDirectory,
         FoldDirectory,
         SelectParent,
+        ToggleHiddenFiles,
     ]
 );
 
@@ -166,6 +225,7 @@ pub enum Event {
     },
     SplitEntry {
         entry_id: ProjectEntryId,
+        direction: SplitDirection,
     },
     Focus,
 }
@@ -213,6 +273,14 @@ impl ProjectPanel {
                     this.update_visible_entries(None, cx);
                     cx.notify();
                 }
+                project::Event::CollaboratorPresenceChanged
+                | project::Event::CollaboratorJoined(_)
+                | project::Event::CollaboratorLeft(_)
+                | project::Event::CollaboratorUpdated { .. } => {
+                    if ProjectPanelSettings::get_global(cx).collaborators {
+                        cx.notify();
+                    }
+                }
                 _ => {}
             })
             .detach();
@@ -244,9 +312,13 @@ impl ProjectPanel {
             .detach();
 
             let mut project_panel_settings = *ProjectPanelSettings::get_global(cx);
-            cx.observe_global::<SettingsStore>(move |_, cx| {
+            cx.observe_global::<SettingsStore>(move |this, cx| {
                 let new_settings = *ProjectPanelSettings::get_global(cx);
                 if project_panel_settings != new_settings {
+                    if project_panel_settings.show_hidden_files != new_settings.show_hidden_files {
+                        this.show_hidden_files = new_settings.show_hidden_files;
+                        this.update_visible_entries(None, cx);
+                    }
                     project_panel_settings = new_settings;
                     cx.notify();
                 }
@@ -269,10 +341,15 @@ impl ProjectPanel {
                 context_menu: None,
                 filename_editor,
                 clipboard: None,
+                last_file_operation: None,
+                filter_query: String::new(),
+                filter_match_positions: HashMap::default(),
+                show_hidden_files: project_panel_settings.show_hidden_files,
                 _dragged_entry_destination: None,
                 workspace: workspace.weak_handle(),
                 width: None,
                 pending_serialization: Task::ready(None),
+                last_deletion_backup_dir: None,
             };
             this.update_visible_entries(None, cx);
 
@@ -343,15 +420,20 @@ impl ProjectPanel {
                         }
                     }
                 }
-                &Event::SplitEntry { entry_id } => {
+                &Event::SplitEntry {
+                    entry_id,
+                    direction,
+                } => {
                     if let Some(worktree) = project.read(cx).worktree_for_entry(entry_id, cx) {
                         if let Some(entry) = worktree.read(cx).entry_for_id(entry_id) {
                             workspace
-                                .split_path(
+                                .split_path_in_direction(
                                     ProjectPath {
                                         worktree_id: worktree.read(cx).id(),
                                         path: entry.path.clone(),
                                     },
+                                    direction,
+                                    false,
                                     cx,
                                 )
                                 .detach_and_log_err(cx);
@@ -446,6 +528,7 @@ impl ProjectPanel {
             let is_local = project.is_local();
             let is_read_only = project.is_read_only();
             let is_remote = project.is_remote();
+            let show_hidden_files = self.show_hidden_files;
 
             let context_menu = ContextMenu::build(cx, |menu, cx| {
                 menu.context(self.focus_handle.clone()).when_else(
@@ -466,6 +549,11 @@ impl ProjectPanel {
                                 menu.separator()
                                     .action("Find in Folder…", Box::new(NewSearchInDirectory))
                             })
+                            .when(!is_dir, |menu| {
+                                menu.separator()
+                                    .action("Open in Split Right", Box::new(pane::SplitRight))
+                                    .action("Open in Split Down", Box::new(pane::SplitDown))
+                            })
                             .when(is_unfoldable, |menu| {
                                 menu.action("Unfold Directory", Box::new(UnfoldDirectory))
                             })
@@ -522,9 +610,19 @@ impl ProjectPanel {
                                         }),
                                     )
                             })
-                            .when(is_local & is_root, |menu| {
+                            .when(is_root, |menu| {
                                 menu.separator()
-                                    .action("Collapse All", Box::new(CollapseAllEntries))
+                                    .when(is_local, |menu| {
+                                        menu.action("Collapse All", Box::new(CollapseAllEntries))
+                                    })
+                                    .action(
+                                        if show_hidden_files {
+                                            "Hide Hidden Files"
+                                        } else {
+                                            "Show Hidden Files"
+                                        },
+                                        Box::new(ToggleHiddenFiles),
+                                    )
                             })
                     },
                 )
@@ -843,6 +941,10 @@ impl ProjectPanel {
     }
 
     fn cancel(&mut self, _: &menu::Cancel, cx: &mut ViewContext<Self>) {
+        if self.edit_state.is_none() && !self.filter_query.is_empty() {
+            self.set_filter_query(String::new(), cx);
+            return;
+        }
         self.edit_state = None;
         self.update_visible_entries(None, cx);
         self.marked_entries.clear();
@@ -866,8 +968,25 @@ impl ProjectPanel {
         });
     }
 
-    fn split_entry(&mut self, entry_id: ProjectEntryId, cx: &mut ViewContext<Self>) {
-        cx.emit(Event::SplitEntry { entry_id });
+    fn split_entry(
+        &mut self,
+        entry_id: ProjectEntryId,
+        direction: SplitDirection,
+        cx: &mut ViewContext<Self>,
+    ) {
+        cx.emit(Event::SplitEntry {
+            entry_id,
+            direction,
+        });
+    }
+
+    fn split_selected_entry(&mut self, direction: SplitDirection, cx: &mut ViewContext<Self>) {
+        if let Some((_, entry)) = self.selected_entry(cx) {
+            if entry.is_file() {
+                let entry_id = entry.id;
+                self.split_entry(entry_id, direction, cx);
+            }
+        }
     }
 
     fn new_file(&mut self, _: &NewFile, cx: &mut ViewContext<Self>) {
@@ -879,6 +998,11 @@ impl ProjectPanel {
     }
 
     fn add_entry(&mut self, is_dir: bool, cx: &mut ViewContext<Self>) {
+        // New entries are created in the unfiltered tree
+        if !self.filter_query.is_empty() {
+            self.filter_query.clear();
+            self.expand_to_selection(cx);
+        }
         if let Some(SelectedEntry {
             worktree_id,
             entry_id,
@@ -1005,6 +1129,18 @@ impl ProjectPanel {
             if file_paths.is_empty() {
                 return None;
             }
+            // Deletions in local projects can be undone, by restoring trashed
+            // entries from the trash, or by copying the entries aside before
+            // deleting them permanently.
+            let undo_state = project.is_local().then(|| {
+                let abs_paths = file_paths
+                    .iter()
+                    .filter_map(|(entry_id, _)| {
+                        project.absolute_path(&project.path_for_entry(*entry_id, cx)?, cx)
+                    })
+                    .collect::<Vec<_>>();
+                (project.fs().clone(), abs_paths)
+            });
             let answer = if !skip_prompt {
                 let operation = if trash { "Trash" } else { "Delete" };
 
@@ -1049,6 +1185,22 @@ impl ProjectPanel {
                         return Result::<(), anyhow::Error>::Ok(());
                     }
                 }
+                // Without a trash to restore them from, trashed entries get removed.
+                let has_trash = cfg!(any(target_os = "macos", target_os = "linux"));
+                let deleted_entries = match &undo_state {
+                    Some((fs, abs_paths)) if trash && (has_trash || fs.is_fake()) => {
+                        Some(DeletedEntries::Trashed(abs_paths.clone()))
+                    }
+                    Some((fs, abs_paths)) => back_up_entries(fs.as_ref(), abs_paths)
+                        .await
+                        .log_err()
+                        .flatten(),
+                    None => None,
+                };
+                let deleted_names = file_paths
+                    .iter()
+                    .map(|(_, name)| name.clone())
+                    .collect::<Vec<_>>();
                 for (entry_id, _) in file_paths {
                     this.update(&mut cx, |this, cx| {
                         this.project
@@ -1057,6 +1209,11 @@ impl ProjectPanel {
                     })??
                     .await?;
                 }
+                if let (Some((fs, _)), Some(deleted_entries)) = (undo_state, deleted_entries) {
+                    this.update(&mut cx, |this, cx| {
+                        this.show_undo_delete_toast(fs, deleted_entries, &deleted_names, cx)
+                    })?;
+                }
                 Result::<(), anyhow::Error>::Ok(())
             })
             .detach_and_log_err(cx);
@@ -1064,6 +1221,69 @@ impl ProjectPanel {
         });
     }
 
+    fn show_undo_delete_toast(
+        &mut self,
+        fs: Arc<dyn Fs>,
+        deleted_entries: DeletedEntries,
+        deleted_names: &[String],
+        cx: &mut ViewContext<Self>,
+    ) {
+        struct UndoDeleteToast;
+
+        // The notification replaces the previous one, so its deletion can no
+        // longer be undone.
+        let backup_dir = match &deleted_entries {
+            DeletedEntries::Trashed(_) => None,
+            DeletedEntries::BackedUp { backup_dir, .. } => Some(backup_dir.clone()),
+        };
+        if let Some(previous_backup_dir) =
+            std::mem::replace(&mut self.last_deletion_backup_dir, backup_dir)
+        {
+            let fs = fs.clone();
+            cx.background_executor()
+                .spawn(async move {
+                    fs.remove_dir(
+                        &previous_backup_dir,
+                        RemoveOptions {
+                            recursive: true,
+                            ignore_if_not_exists: true,
+                        },
+                    )
+                    .await
+                })
+                .detach_and_log_err(cx);
+        }
+
+        let operation = match deleted_entries {
+            DeletedEntries::Trashed(_) => "Trashed",
+            DeletedEntries::BackedUp { .. } => "Deleted",
+        };
+        let message = match deleted_names {
+            [name] => format!("{operation} {name}"),
+            names => format!("{operation} {} files", names.len()),
+        };
+        let deleted_entries = Arc::new(deleted_entries);
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.show_toast(
+                    Toast::new(NotificationId::unique::<UndoDeleteToast>(), message).on_click(
+                        "Undo",
+                        move |cx| {
+                            let fs = fs.clone();
+                            let deleted_entries = deleted_entries.clone();
+                            cx.background_executor()
+                                .spawn(async move {
+                                    restore_entries(fs.as_ref(), &deleted_entries).await
+                                })
+                                .detach_and_log_err(cx);
+                        },
+                    ),
+                    cx,
+                )
+            })
+            .ok();
+    }
+
     fn unfold_directory(&mut self, _: &UnfoldDirectory, cx: &mut ViewContext<Self>) {
         if let Some((worktree, entry)) = self.selected_entry(cx) {
             self.unfolded_dir_ids.insert(entry.id);
@@ -1091,6 +1311,32 @@ impl ProjectPanel {
         }
     }
 
+    /// Unfolds the directory chain the entry is displayed in, or folds it back if it was unfolded.
+    /// Directories that can't be folded are expanded or collapsed instead.
+    fn toggle_folded(&mut self, entry_id: ProjectEntryId, cx: &mut ViewContext<Self>) {
+        let (is_unfoldable, is_foldable) = ProjectPanelSettings::get_global(cx)
+            .auto_fold_dirs
+            .then(|| {
+                let worktree = self.project.read(cx).worktree_for_entry(entry_id, cx)?;
+                let worktree = worktree.read(cx);
+                let entry = worktree.entry_for_id(entry_id)?;
+                Some((
+                    self.is_unfoldable(entry, worktree),
+                    self.is_foldable(entry, worktree),
+                ))
+            })
+            .flatten()
+            .unwrap_or_default();
+
+        if is_unfoldable {
+            self.unfold_directory(&UnfoldDirectory, cx);
+        } else if is_foldable {
+            self.fold_directory(&FoldDirectory, cx);
+        } else {
+            self.toggle_expanded(entry_id, cx);
+        }
+    }
+
     fn fold_directory(&mut self, _: &FoldDirectory, cx: &mut ViewContext<Self>) {
         if let Some((worktree, entry)) = self.selected_entry(cx) {
             self.unfolded_dir_ids.remove(&entry.id);
@@ -1211,6 +1457,78 @@ impl ProjectPanel {
         }
     }
 
+    fn filter_key_down(&mut self, event: &KeyDownEvent, cx: &mut ViewContext<Self>) {
+        let keystroke = &event.keystroke;
+        if self.edit_state.is_some()
+            || keystroke.modifiers.control
+            || keystroke.modifiers.alt
+            || keystroke.modifiers.platform
+            || keystroke.modifiers.function
+        {
+            return;
+        }
+        let text = match &keystroke.ime_key {
+            Some(ime_key) => ime_key.clone(),
+            None if keystroke.key.chars().count() == 1 => {
+                if keystroke.modifiers.shift {
+                    keystroke.key.to_uppercase()
+                } else {
+                    keystroke.key.clone()
+                }
+            }
+            None => return,
+        };
+        if text.chars().any(|c| c.is_control()) {
+            return;
+        }
+
+        let mut query = self.filter_query.clone();
+        query.push_str(&text);
+        self.set_filter_query(query, cx);
+        cx.stop_propagation();
+    }
+
+    fn remove_filter_character(&mut self, _: &RemoveFilterCharacter, cx: &mut ViewContext<Self>) {
+        let mut query = self.filter_query.clone();
+        query.pop();
+        self.set_filter_query(query, cx);
+    }
+
+    fn toggle_hidden_files(&mut self, _: &ToggleHiddenFiles, cx: &mut ViewContext<Self>) {
+        self.show_hidden_files = !self.show_hidden_files;
+        self.update_visible_entries(None, cx);
+        cx.notify();
+    }
+
+    fn set_filter_query(&mut self, query: String, cx: &mut ViewContext<Self>) {
+        self.filter_query = query;
+        self.marked_entries.clear();
+        if self.filter_query.is_empty() {
+            // Keep the entry that was selected while filtering visible in the unfiltered tree
+            self.expand_to_selection(cx);
+            self.update_visible_entries(None, cx);
+        } else {
+            self.update_visible_entries(None, cx);
+            let first_match = self
+                .visible_entries
+                .iter()
+                .find_map(|(worktree_id, entries, _)| {
+                    entries
+                        .iter()
+                        .find(|entry| self.filter_match_positions.contains_key(&entry.id))
+                        .map(|entry| SelectedEntry {
+                            worktree_id: *worktree_id,
+                            entry_id: entry.id,
+                        })
+                });
+            if first_match.is_some() {
+                self.selection = first_match;
+            }
+        }
+        self.autoscroll(cx);
+        cx.notify();
+    }
+
     fn autoscroll(&mut self, cx: &mut ViewContext<Self>) {
         if let Some((_, _, index)) = self.selection.and_then(|s| self.index_for_selection(s)) {
             self.scroll_handle.scroll_to_item(index);
@@ -1239,41 +1557,61 @@ impl ProjectPanel {
         source: &SelectedEntry,
         (worktree, target_entry): (Model<Worktree>, &Entry),
         cx: &AppContext,
-    ) -> Option<PathBuf> {
+    ) -> Option<PastePath> {
         let mut new_path = target_entry.path.to_path_buf();
         // If we're pasting into a file, or a directory into itself, go up one level.
         if target_entry.is_file() || (target_entry.is_dir() && target_entry.id == source.entry_id) {
             new_path.pop();
         }
-        let clipboard_entry_file_name = self
+        let source_path = self
             .project
             .read(cx)
             .path_for_entry(source.entry_id, cx)?
-            .path
-            .file_name()?
-            .to_os_string();
-        new_path.push(&clipboard_entry_file_name);
+            .path;
+        // Pasting an entry next to itself duplicates it instead of asking to replace it.
+        let is_duplicate = source_path.parent() == Some(new_path.as_path());
+        new_path.push(source_path.file_name()?);
+
+        let worktree = worktree.read(cx);
+        match worktree.entry_for_path(&new_path) {
+            None => Some(PastePath {
+                path: new_path,
+                conflict: None,
+            }),
+            Some(_) if is_duplicate => Some(PastePath {
+                path: Self::free_copy_path(worktree, new_path.clone())?,
+                conflict: None,
+            }),
+            Some(existing_entry) => Some(PastePath {
+                conflict: Some((
+                    existing_entry.id,
+                    Self::free_copy_path(worktree, new_path.clone())?,
+                )),
+                path: new_path,
+            }),
+        }
+    }
+
+    /// Appends " copy" and a counter to the file name until the path is not taken in the worktree.
+    fn free_copy_path(worktree: &Worktree, mut new_path: PathBuf) -> Option<PathBuf> {
         let extension = new_path.extension().map(|e| e.to_os_string());
-        let file_name_without_extension = Path::new(&clipboard_entry_file_name).file_stem()?;
+        let file_name_without_extension = new_path.file_stem()?.to_os_string();
         let mut ix = 0;
-        {
-            let worktree = worktree.read(cx);
-            while worktree.entry_for_path(&new_path).is_some() {
-                new_path.pop();
-
-                let mut new_file_name = file_name_without_extension.to_os_string();
-                new_file_name.push(" copy");
-                if ix > 0 {
-                    new_file_name.push(format!(" {}", ix));
-                }
-                if let Some(extension) = extension.as_ref() {
-                    new_file_name.push(".");
-                    new_file_name.push(extension);
-                }
+        while worktree.entry_for_path(&new_path).is_some() {
+            new_path.pop();
 
-                new_path.push(new_file_name);
-                ix += 1;
+            let mut new_file_name = file_name_without_extension.clone();
+            new_file_name.push(" copy");
+            if ix > 0 {
+                new_file_name.push(format!(" {}", ix));
             }
+            if let Some(extension) = extension.as_ref() {
+                new_file_name.push(".");
+                new_file_name.push(extension);
+            }
+
+            new_path.push(new_file_name);
+            ix += 1;
         }
         Some(new_path)
     }
@@ -1281,36 +1619,176 @@ impl ProjectPanel {
     fn paste(&mut self, _: &Paste, cx: &mut ViewContext<Self>) {
         maybe!({
             let (worktree, entry) = self.selected_entry_handle(cx)?;
-            let entry = entry.clone();
             let worktree_id = worktree.read(cx).id();
             let clipboard_entries = self
                 .clipboard
                 .as_ref()
                 .filter(|clipboard| !clipboard.items().is_empty())?;
+            if clipboard_entries
+                .items()
+                .iter()
+                .any(|clipboard_entry| clipboard_entry.worktree_id != worktree_id)
+            {
+                return None;
+            }
+            let is_cut = clipboard_entries.is_cut();
+            let sources = clipboard_entries
+                .items()
+                .iter()
+                .copied()
+                .collect::<Vec<_>>();
+            let target_entry_id = entry.id;
+            self.paste_entries(sources, target_entry_id, is_cut, cx)
+        });
+    }
 
-            for clipboard_entry in clipboard_entries.items() {
-                if clipboard_entry.worktree_id != worktree_id {
-                    return None;
+    /// Copies or moves the given entries next to the target entry, or into it if it's a directory,
+    /// asking whether to replace the entries that already exist in the destination.
+    fn paste_entries(
+        &mut self,
+        sources: Vec<SelectedEntry>,
+        target_entry_id: ProjectEntryId,
+        is_cut: bool,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<()> {
+        let worktree = self
+            .project
+            .read(cx)
+            .worktree_for_entry(target_entry_id, cx)?;
+        let worktree_id = worktree.read(cx).id();
+        let entry = worktree.read(cx).entry_for_id(target_entry_id)?.clone();
+
+        let mut paste_paths = Vec::new();
+        for source in &sources {
+            let paste_path = self.create_paste_path(source, (worktree.clone(), &entry), cx)?;
+            if is_cut {
+                let source_path = self.project.read(cx).path_for_entry(source.entry_id, cx)?;
+                // Moving an entry into its current directory or into itself does nothing.
+                if source_path.path.parent() == paste_path.path.parent()
+                    || paste_path.path.starts_with(&source_path.path)
+                {
+                    continue;
                 }
-                let new_path =
-                    self.create_paste_path(clipboard_entry, self.selected_entry_handle(cx)?, cx)?;
-                if clipboard_entries.is_cut() {
-                    self.project
-                        .update(cx, |project, cx| {
-                            project.rename_entry(clipboard_entry.entry_id, new_path, cx)
-                        })
-                        .detach_and_log_err(cx)
+            }
+            paste_paths.push((source.entry_id, paste_path));
+        }
+        if paste_paths.is_empty() {
+            return None;
+        }
+
+        let conflicting_names = paste_paths
+            .iter()
+            .filter(|(_, paste_path)| paste_path.conflict.is_some())
+            .filter_map(|(_, paste_path)| {
+                Some(paste_path.path.file_name()?.to_string_lossy().into_owned())
+            })
+            .collect::<Vec<_>>();
+        let answer = if conflicting_names.is_empty() {
+            None
+        } else {
+            let prompt = if let [name] = conflicting_names.as_slice() {
+                format!(
+                    "{name} already exists in the destination folder. Do you want to replace it?"
+                )
+            } else {
+                format!(
+                    "{} entries already exist in the destination folder. Do you want to replace them?",
+                    conflicting_names.len()
+                )
+            };
+            Some(cx.prompt(
+                PromptLevel::Warning,
+                &prompt,
+                None,
+                &["Replace", "Keep Both", "Cancel"],
+            ))
+        };
+
+        cx.spawn(|project_panel, mut cx| async move {
+            let replace = match answer {
+                Some(answer) => match answer.await {
+                    Ok(0) => true,
+                    Ok(1) => false,
+                    _ => return Ok(()),
+                },
+                None => false,
+            };
+
+            let mut copied_entries = Vec::new();
+            let mut moved_entries = Vec::new();
+            let mut replaced_entries = Vec::new();
+            for (entry_id, paste_path) in paste_paths {
+                let new_path = match paste_path.conflict {
+                    Some((existing_entry_id, _)) if replace => {
+                        let (abs_path, delete_task) =
+                            project_panel.update(&mut cx, |project_panel, cx| {
+                                project_panel.project.update(cx, |project, cx| {
+                                    (
+                                        project
+                                            .path_for_entry(existing_entry_id, cx)
+                                            .and_then(|path| project.absolute_path(&path, cx)),
+                                        project.delete_entry(existing_entry_id, true, cx),
+                                    )
+                                })
+                            })?;
+                        replaced_entries.push(abs_path);
+                        if let Some(delete_task) = delete_task {
+                            delete_task.await?;
+                        }
+                        paste_path.path
+                    }
+                    Some((_, free_path)) => free_path,
+                    None => paste_path.path,
+                };
+
+                if is_cut {
+                    let (original_path, rename_task) =
+                        project_panel.update(&mut cx, |project_panel, cx| {
+                            project_panel.project.update(cx, |project, cx| {
+                                (
+                                    project.path_for_entry(entry_id, cx),
+                                    project.rename_entry(entry_id, new_path, cx),
+                                )
+                            })
+                        })?;
+                    rename_task.await?;
+                    if let Some(original_path) = original_path {
+                        moved_entries.push((entry_id, original_path.path));
+                    }
                 } else {
-                    self.project
-                        .update(cx, |project, cx| {
-                            project.copy_entry(clipboard_entry.entry_id, new_path, cx)
-                        })
-                        .detach_and_log_err(cx)
+                    let copy_task = project_panel.update(&mut cx, |project_panel, cx| {
+                        project_panel
+                            .project
+                            .update(cx, |project, cx| project.copy_entry(entry_id, new_path, cx))
+                    })?;
+                    if let Some(new_entry) = copy_task.await? {
+                        copied_entries.push(new_entry.id);
+                    }
                 }
             }
-            self.expand_entry(worktree_id, entry.id, cx);
-            Some(())
-        });
+
+            project_panel.update(&mut cx, |project_panel, cx| {
+                let operation = if is_cut {
+                    FileOperation::Moved(moved_entries)
+                } else {
+                    FileOperation::Copied(copied_entries)
+                };
+                // Undoing the paste would lose the entries it replaced, unless
+                // they can be restored from the trash.
+                let project = project_panel.project.read(cx);
+                let can_restore_trashed = project.is_local()
+                    && (cfg!(any(target_os = "macos", target_os = "linux"))
+                        || project_panel.fs.is_fake());
+                project_panel.last_file_operation = replaced_entries
+                    .into_iter()
+                    .collect::<Option<Vec<_>>>()
+                    .filter(|replaced| replaced.is_empty() || can_restore_trashed)
+                    .map(|replaced| (operation, replaced));
+            })
+        })
+        .detach_and_log_err(cx);
+        self.expand_entry(worktree_id, entry.id, cx);
+        Some(())
     }
 
     fn duplicate(&mut self, _: &Duplicate, cx: &mut ViewContext<Self>) {
@@ -1318,6 +1796,48 @@ impl ProjectPanel {
         self.paste(&Paste {}, cx);
     }
 
+    fn undo(&mut self, _: &Undo, cx: &mut ViewContext<Self>) {
+        let Some((operation, replaced_entries)) = self.last_file_operation.take() else {
+            return;
+        };
+        let fs = self.fs.clone();
+        cx.spawn(|project_panel, mut cx| async move {
+            match operation {
+                FileOperation::Copied(entry_ids) => {
+                    // Copies that replaced entries aren't trashed, so that it's
+                    // the replaced entries that get restored from the trash.
+                    let trash = replaced_entries.is_empty();
+                    for entry_id in entry_ids {
+                        let delete_task = project_panel.update(&mut cx, |project_panel, cx| {
+                            project_panel
+                                .project
+                                .update(cx, |project, cx| project.delete_entry(entry_id, trash, cx))
+                        })?;
+                        if let Some(delete_task) = delete_task {
+                            delete_task.await?;
+                        }
+                    }
+                }
+                FileOperation::Moved(entries) => {
+                    for (entry_id, original_path) in entries {
+                        project_panel
+                            .update(&mut cx, |project_panel, cx| {
+                                project_panel.project.update(cx, |project, cx| {
+                                    project.rename_entry(entry_id, original_path, cx)
+                                })
+                            })?
+                            .await?;
+                    }
+                }
+            }
+            for abs_path in replaced_entries {
+                fs.restore_from_trash(&abs_path).await?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn copy_path(&mut self, _: &CopyPath, cx: &mut ViewContext<Self>) {
         if let Some((worktree, entry)) = self.selected_entry(cx) {
             cx.write_to_clipboard(ClipboardItem::new(
@@ -1388,24 +1908,6 @@ impl ProjectPanel {
         }
     }
 
-    fn move_entry(
-        &mut self,
-        entry_to_move: ProjectEntryId,
-        destination: ProjectEntryId,
-        destination_is_file: bool,
-        cx: &mut ViewContext<Self>,
-    ) {
-        if self
-            .project
-            .read(cx)
-            .entry_is_worktree_root(entry_to_move, cx)
-        {
-            self.move_worktree_root(entry_to_move, destination, cx)
-        } else {
-            self.move_worktree_entry(entry_to_move, destination, destination_is_file, cx)
-        }
-    }
-
     fn move_worktree_root(
         &mut self,
         entry_to_move: ProjectEntryId,
@@ -1430,35 +1932,18 @@ impl ProjectPanel {
         return;
     }
 
-    fn move_worktree_entry(
-        &mut self,
-        entry_to_move: ProjectEntryId,
-        destination: ProjectEntryId,
-        destination_is_file: bool,
-        cx: &mut ViewContext<Self>,
-    ) {
-        let destination_worktree = self.project.update(cx, |project, cx| {
-            let entry_path = project.path_for_entry(entry_to_move, cx)?;
-            let destination_entry_path = project.path_for_entry(destination, cx)?.path.clone();
-
-            let mut destination_path = destination_entry_path.as_ref();
-            if destination_is_file {
-                destination_path = destination_path.parent()?;
-            }
-
-            let mut new_path = destination_path.to_path_buf();
-            new_path.push(entry_path.path.file_name()?);
-            if new_path != entry_path.path.as_ref() {
-                let task = project.rename_entry(entry_to_move, new_path, cx);
-                cx.foreground_executor().spawn(task).detach_and_log_err(cx);
+    fn entry_at_index(&self, index: usize) -> Option<SelectedEntry> {
+        let mut index = index;
+        for (worktree_id, worktree_entries, _) in &self.visible_entries {
+            if let Some(entry) = worktree_entries.get(index) {
+                return Some(SelectedEntry {
+                    worktree_id: *worktree_id,
+                    entry_id: entry.id,
+                });
             }
-
-            project.worktree_id_for_entry(destination, cx)
-        });
-
-        if let Some(destination_worktree) = destination_worktree {
-            self.expand_entry(destination_worktree, destination, cx);
+            index -= worktree_entries.len();
         }
+        None
     }
 
     fn index_for_selection(&self, selection: SelectedEntry) -> Option<(usize, usize, usize)> {
@@ -1539,6 +2024,10 @@ impl ProjectPanel {
         cx: &mut ViewContext<Self>,
     ) {
         let auto_collapse_dirs = ProjectPanelSettings::get_global(cx).auto_fold_dirs;
+        // Entries can be added or removed above the ones in view, e.g. when files are created
+        // by other programs. Remember the topmost entry in view so that it stays in place.
+        let scroll_top_index = self.scroll_handle.logical_scroll_top_index();
+        let scroll_anchor = self.entry_at_index(scroll_top_index);
         let project = self.project.read(cx);
         self.last_worktree_root_id = project
             .visible_worktrees(cx)
@@ -1548,6 +2037,7 @@ impl ProjectPanel {
             .map(|entry| entry.id);
 
         self.visible_entries.clear();
+        self.filter_match_positions.clear();
         for worktree in project.visible_worktrees(cx) {
             let snapshot = worktree.read(cx).snapshot();
             let worktree_id = snapshot.id();
@@ -1579,48 +2069,65 @@ impl ProjectPanel {
             }
 
             let mut visible_worktree_entries = Vec::new();
-            let mut entry_iter = snapshot.entries(true, 0);
-            while let Some(entry) = entry_iter.entry() {
-                if auto_collapse_dirs
-                    && entry.kind.is_dir()
-                    && !self.unfolded_dir_ids.contains(&entry.id)
-                {
-                    if let Some(root_path) = snapshot.root_entry() {
-                        let mut child_entries = snapshot.child_entries(&entry.path);
-                        if let Some(child) = child_entries.next() {
-                            if entry.path != root_path.path
-                                && child_entries.next().is_none()
-                                && child.kind.is_dir()
-                            {
-                                entry_iter.advance();
-                                continue;
+            if !self.filter_query.is_empty() {
+                visible_worktree_entries = filter_worktree_entries(
+                    &snapshot,
+                    &self.filter_query,
+                    self.show_hidden_files,
+                    &mut self.filter_match_positions,
+                );
+            } else {
+                let mut entry_iter = snapshot.entries(true, 0);
+                while let Some(entry) = entry_iter.entry() {
+                    if !self.show_hidden_files && is_hidden_entry(entry) {
+                        if !(entry.is_dir() && entry_iter.advance_to_sibling()) {
+                            entry_iter.advance();
+                        }
+                        continue;
+                    }
+
+                    if auto_collapse_dirs
+                        && entry.kind.is_dir()
+                        && !self.unfolded_dir_ids.contains(&entry.id)
+                    {
+                        if let Some(root_path) = snapshot.root_entry() {
+                            let mut child_entries = snapshot.child_entries(&entry.path);
+                            if let Some(child) = child_entries.next() {
+                                if entry.path != root_path.path
+                                    && child_entries.next().is_none()
+                                    && child.kind.is_dir()
+                                {
+                                    entry_iter.advance();
+                                    continue;
+                                }
                             }
                         }
                     }
-                }
 
-                visible_worktree_entries.push(entry.clone());
-                if Some(entry.id) == new_entry_parent_id {
-                    visible_worktree_entries.push(Entry {
-                        id: NEW_ENTRY_ID,
-                        kind: new_entry_kind,
-                        path: entry.path.join("\0").into(),
-                        inode: 0,
-                        mtime: entry.mtime,
-                        is_ignored: entry.is_ignored,
-                        is_external: false,
-                        is_private: false,
-                        git_status: entry.git_status,
-                        canonical_path: entry.canonical_path.clone(),
-                        is_symlink: entry.is_symlink,
-                    });
-                }
-                if expanded_dir_ids.binary_search(&entry.id).is_err()
-                    && entry_iter.advance_to_sibling()
-                {
-                    continue;
+                    visible_worktree_entries.push(entry.clone());
+                    if Some(entry.id) == new_entry_parent_id {
+                        visible_worktree_entries.push(Entry {
+                            id: NEW_ENTRY_ID,
+                            kind: new_entry_kind,
+                            path: entry.path.join("\0").into(),
+                            inode: 0,
+                            mtime: entry.mtime,
+                            is_ignored: entry.is_ignored,
+                            is_external: false,
+                            is_private: false,
+                            is_hidden: entry.is_hidden,
+                            git_status: entry.git_status,
+                            canonical_path: entry.canonical_path.clone(),
+                            is_symlink: entry.is_symlink,
+                        });
+                    }
+                    if expanded_dir_ids.binary_search(&entry.id).is_err()
+                        && entry_iter.advance_to_sibling()
+                    {
+                        continue;
+                    }
+                    entry_iter.advance();
                 }
-                entry_iter.advance();
             }
 
             snapshot.propagate_git_statuses(&mut visible_worktree_entries);
@@ -1629,6 +2136,15 @@ impl ProjectPanel {
                 .push((worktree_id, visible_worktree_entries, OnceCell::new()));
         }
 
+        if let Some((_, _, new_scroll_top_index)) =
+            scroll_anchor.and_then(|entry| self.index_for_selection(entry))
+        {
+            if new_scroll_top_index != scroll_top_index {
+                self.scroll_handle
+                    .scroll_by_items(new_scroll_top_index as isize - scroll_top_index as isize);
+            }
+        }
+
         if let Some((worktree_id, entry_id)) = new_selected_entry {
             self.selection = Some(SelectedEntry {
                 worktree_id,
@@ -1756,37 +2272,30 @@ impl ProjectPanel {
         &mut self,
         selections: &DraggedSelection,
         target_entry_id: ProjectEntryId,
-        is_file: bool,
         cx: &mut ViewContext<Self>,
     ) {
         let should_copy = cx.modifiers().alt;
-        if should_copy {
-            let _ = maybe!({
-                let project = self.project.read(cx);
-                let target_worktree = project.worktree_for_entry(target_entry_id, cx)?;
-                let target_entry = target_worktree
-                    .read(cx)
-                    .entry_for_id(target_entry_id)?
-                    .clone();
-                for selection in selections.items() {
-                    let new_path = self.create_paste_path(
-                        &selection,
-                        (target_worktree.clone(), &target_entry),
-                        cx,
-                    )?;
-                    self.project
-                        .update(cx, |project, cx| {
-                            project.copy_entry(selection.entry_id, new_path, cx)
-                        })
-                        .detach_and_log_err(cx)
-                }
+        let project = self.project.read(cx);
+        let Some(target_worktree_id) = project.worktree_id_for_entry(target_entry_id, cx) else {
+            return;
+        };
+        let (roots, entries): (Vec<_>, Vec<_>) = selections
+            .items()
+            .copied()
+            .partition(|selection| project.entry_is_worktree_root(selection.entry_id, cx));
+        if !should_copy {
+            for root in &roots {
+                self.move_worktree_root(root.entry_id, target_entry_id, cx);
+            }
+        }
 
-                Some(())
-            });
-        } else {
-            for selection in selections.items() {
-                self.move_entry(selection.entry_id, target_entry_id, is_file, cx);
-            }
+        // Entries can't be moved or copied across worktrees yet.
+        let entries = entries
+            .into_iter()
+            .filter(|selection| selection.worktree_id == target_worktree_id)
+            .collect::<Vec<_>>();
+        if !entries.is_empty() {
+            self.paste_entries(entries, target_entry_id, !should_copy, cx);
         }
     }
 
@@ -1797,6 +2306,11 @@ impl ProjectPanel {
         mut callback: impl FnMut(ProjectEntryId, EntryDetails, &mut ViewContext<ProjectPanel>),
     ) {
         let mut ix = 0;
+        let collaborators_by_path = if ProjectPanelSettings::get_global(cx).collaborators {
+            self.project.read(cx).collaborators_by_path(cx)
+        } else {
+            HashMap::default()
+        };
         for (worktree_id, visible_worktree_entries, entries_paths) in &self.visible_entries {
             if ix >= range.end {
                 return;
@@ -1834,7 +2348,9 @@ impl ProjectPanel {
                 });
                 for entry in visible_worktree_entries[entry_range].iter() {
                     let status = git_status_setting.then(|| entry.git_status).flatten();
-                    let is_expanded = expanded_entry_ids.binary_search(&entry.id).is_ok();
+                    // The filtered tree shows every ancestor of a match, so all its directories are expanded
+                    let is_expanded = !self.filter_query.is_empty()
+                        || expanded_entry_ids.binary_search(&entry.id).is_ok();
                     let icon = match entry.kind {
                         EntryKind::File(_) => {
                             if show_file_icons {
@@ -1890,10 +2406,26 @@ impl ProjectPanel {
                             .clipboard
                             .as_ref()
                             .map_or(false, |e| e.is_cut() && e.items().contains(&selection)),
+                        filter_match_positions: self
+                            .filter_match_positions
+                            .get(&entry.id)
+                            .cloned()
+                            .unwrap_or_default(),
                         git_status: status,
                         is_private: entry.is_private,
                         worktree_id: *worktree_id,
                         canonical_path: entry.canonical_path.clone(),
+                        collaborators: if collaborators_by_path.is_empty() || entry.is_dir() {
+                            Vec::new()
+                        } else {
+                            collaborators_by_path
+                                .get(&ProjectPath {
+                                    worktree_id: *worktree_id,
+                                    path: entry.path.clone(),
+                                })
+                                .cloned()
+                                .unwrap_or_default()
+                        },
                     };
 
                     if let Some(edit_state) = &self.edit_state {
@@ -1972,7 +2504,12 @@ impl ProjectPanel {
         let width = self.size(cx);
         let filename_text_color =
             entry_git_aware_label_color(details.git_status, details.is_ignored, is_marked);
+        let git_status_badge = details
+            .git_status
+            .filter(|_| kind.is_file() && !details.is_ignored)
+            .map(git_status_badge);
         let file_name = details.filename.clone();
+        let filter_match_positions = details.filter_match_positions.clone();
         let mut icon = details.icon.clone();
         if settings.file_icons && show_editor && details.kind.is_file() {
             let filename = self.filename_editor.read(cx).text(cx);
@@ -1990,6 +2527,16 @@ impl ProjectPanel {
         let depth = details.depth;
         let worktree_id = details.worktree_id;
         let selections = Arc::new(self.marked_entries.clone());
+        let collaborator_presence = render_collaborator_presence(
+            ElementId::NamedInteger(
+                "project-panel-presence".into(),
+                entry_id.to_proto() as usize,
+            ),
+            &details.collaborators,
+            &self.project,
+            self.workspace.clone(),
+            cx,
+        );
 
         let dragged_selection = DraggedSelection {
             active_selection: selection,
@@ -2060,28 +2607,50 @@ impl ProjectPanel {
                 style.bg(cx.theme().colors().drop_target_background)
             })
             .on_drop(cx.listener(move |this, selections: &DraggedSelection, cx| {
-                this.drag_onto(selections, entry_id, kind.is_file(), cx);
+                this.drag_onto(selections, entry_id, cx);
             }))
             .child(
                 ListItem::new(entry_id.to_proto() as usize)
                     .indent_level(depth)
                     .indent_step_size(px(settings.indent_size))
                     .selected(is_marked || is_active)
-                    .when_some(canonical_path, |this, path| {
-                        this.end_slot::<AnyElement>(
-                            div()
-                                .id("symlink_icon")
-                                .tooltip(move |cx| {
-                                    Tooltip::text(format!("{path} • Symbolic Link"), cx)
-                                })
-                                .child(
-                                    Icon::new(IconName::ArrowUpRight)
-                                        .size(IconSize::Indicator)
-                                        .color(filename_text_color),
-                                )
-                                .into_any_element(),
-                        )
-                    })
+                    .when(
+                        canonical_path.is_some()
+                            || collaborator_presence.is_some()
+                            || git_status_badge.is_some(),
+                        |this| {
+                            this.end_slot::<AnyElement>(
+                                h_flex()
+                                    .gap_1()
+                                    .children(collaborator_presence)
+                                    .when_some(git_status_badge, |this, badge| {
+                                        this.child(
+                                            Label::new(badge)
+                                                .size(LabelSize::Small)
+                                                .color(filename_text_color),
+                                        )
+                                    })
+                                    .when_some(canonical_path, |this, path| {
+                                        this.child(
+                                            div()
+                                                .id("symlink_icon")
+                                                .tooltip(move |cx| {
+                                                    Tooltip::text(
+                                                        format!("{path} • Symbolic Link"),
+                                                        cx,
+                                                    )
+                                                })
+                                                .child(
+                                                    Icon::new(IconName::ArrowUpRight)
+                                                        .size(IconSize::Indicator)
+                                                        .color(filename_text_color),
+                                                ),
+                                        )
+                                    })
+                                    .into_any_element(),
+                            )
+                        },
+                    )
                     .child(if let Some(icon) = &icon {
                         h_flex().child(Icon::from_path(icon.to_string()).color(filename_text_color))
                     } else {
@@ -2094,11 +2663,16 @@ impl ProjectPanel {
                         if let (Some(editor), true) = (Some(&self.filename_editor), show_editor) {
                             h_flex().h_6().w_full().child(editor.clone())
                         } else {
-                            h_flex().h_6().child(
+                            h_flex().h_6().child(if filter_match_positions.is_empty() {
                                 Label::new(file_name)
                                     .single_line()
-                                    .color(filename_text_color),
-                            )
+                                    .color(filename_text_color)
+                                    .into_any_element()
+                            } else {
+                                HighlightedLabel::new(file_name, filter_match_positions)
+                                    .color(filename_text_color)
+                                    .into_any_element()
+                            })
                         }
                         .ml_1(),
                     )
@@ -2152,12 +2726,15 @@ impl ProjectPanel {
                                 if !this.marked_entries.insert(selection) {
                                     this.marked_entries.remove(&selection);
                                 }
+                            } else if kind.is_dir() && event.down.modifiers.alt {
+                                this.selection = Some(selection);
+                                this.toggle_folded(entry_id, cx);
                             } else if kind.is_dir() {
                                 this.toggle_expanded(entry_id, cx);
                             } else {
                                 let click_count = event.up.click_count;
                                 if click_count > 1 && event.down.modifiers.secondary() {
-                                    this.split_entry(entry_id, cx);
+                                    this.split_entry(entry_id, SplitDirection::Right, cx);
                                 } else {
                                     this.open_entry(
                                         entry_id,
@@ -2213,6 +2790,9 @@ impl ProjectPanel {
         };
 
         dispatch_context.add(identifier);
+        if !self.filter_query.is_empty() {
+            dispatch_context.add("filtering");
+        }
         dispatch_context
     }
 
@@ -2225,12 +2805,21 @@ impl ProjectPanel {
     ) {
         if let Some(worktree) = project.read(cx).worktree_for_entry(entry_id, cx) {
             let worktree = worktree.read(cx);
-            if skip_ignored
-                && worktree
-                    .entry_for_id(entry_id)
-                    .map_or(true, |entry| entry.is_ignored)
-            {
+            let Some(entry) = worktree.entry_for_id(entry_id) else {
                 return;
+            };
+            let is_hidden = !self.show_hidden_files && is_hidden_entry(entry);
+            if skip_ignored {
+                // Keep the panel as the user left it when following the active item.
+                if entry.is_ignored || is_hidden || !self.filter_query.is_empty() {
+                    return;
+                }
+            } else {
+                // Entries revealed explicitly are always shown.
+                self.filter_query.clear();
+                if is_hidden {
+                    self.show_hidden_files = true;
+                }
             }
 
             let worktree_id = worktree.id();
@@ -2243,13 +2832,106 @@ impl ProjectPanel {
     }
 }
 
+/// Copies the given entries aside so that they can be restored with
+/// [`restore_entries`] after being deleted permanently, into a directory of
+/// their own. Returns `None` when they're too large to be copied.
+async fn back_up_entries(fs: &dyn Fs, abs_paths: &[PathBuf]) -> Result<Option<DeletedEntries>> {
+    static NEXT_BACKUP_ID: AtomicUsize = AtomicUsize::new(0);
+
+    if exceeds_size(fs, abs_paths, MAX_DELETED_ENTRIES_BACKUP_SIZE).await? {
+        return Ok(None);
+    }
+
+    // Other instances of Zed back up their deletions in the same directory.
+    let backup_dir = paths::temp_dir().join(DELETED_ENTRIES_DIR).join(format!(
+        "{}-{}",
+        std::process::id(),
+        NEXT_BACKUP_ID.fetch_add(1, atomic::Ordering::Relaxed)
+    ));
+    fs.remove_dir(
+        &backup_dir,
+        RemoveOptions {
+            recursive: true,
+            ignore_if_not_exists: true,
+        },
+    )
+    .await?;
+
+    let mut backups = Vec::new();
+    for (ix, abs_path) in abs_paths.iter().enumerate() {
+        let Some(file_name) = abs_path.file_name() else {
+            continue;
+        };
+        let backup_parent = backup_dir.join(ix.to_string());
+        fs.create_dir(&backup_parent).await?;
+        let backup_path = backup_parent.join(file_name);
+        copy_recursive(fs, abs_path, &backup_path, CopyOptions::default()).await?;
+        backups.push((backup_path, abs_path.clone()));
+    }
+    Ok((!backups.is_empty()).then_some(DeletedEntries::BackedUp {
+        backup_dir,
+        backups,
+    }))
+}
+
+/// Returns whether the files at the given paths, and in the directories at
+/// them, are larger than `limit` bytes altogether.
+async fn exceeds_size(fs: &dyn Fs, abs_paths: &[PathBuf], limit: u64) -> Result<bool> {
+    let mut size = 0;
+    let mut pending_paths = abs_paths.to_vec();
+    while let Some(abs_path) = pending_paths.pop() {
+        let Some(metadata) = fs.metadata(&abs_path).await? else {
+            continue;
+        };
+        if metadata.is_dir {
+            let mut children = fs.read_dir(&abs_path).await?;
+            while let Some(child_path) = children.next().await {
+                pending_paths.extend(child_path.log_err());
+            }
+        } else {
+            size += metadata.len;
+            if size > limit {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+async fn restore_entries(fs: &dyn Fs, deleted_entries: &DeletedEntries) -> Result<()> {
+    match deleted_entries {
+        DeletedEntries::Trashed(abs_paths) => {
+            for abs_path in abs_paths {
+                fs.restore_from_trash(abs_path).await?;
+            }
+            Ok(())
+        }
+        DeletedEntries::BackedUp {
+            backup_dir,
+            backups,
+        } => {
+            for (backup_path, abs_path) in backups {
+                copy_recursive(fs, backup_path, abs_path, CopyOptions::default()).await?;
+            }
+            fs.remove_dir(
+                backup_dir,
+                RemoveOptions {
+                    recursive: true,
+                    ignore_if_not_exists: true,
+                },
+            )
+            .await
+        }
+    }
+}
+
 impl Render for ProjectPanel {
     fn render(&mut self, cx: &mut gpui::ViewContext<Self>) -> impl IntoElement {
         let has_worktree = self.visible_entries.len() != 0;
         let project = self.project.read(cx);
 
         if has_worktree {
-            h_flex()
+            v_flex()
                 .id("project-panel")
                 .size_full()
                 .relative()
@@ -2271,6 +2953,21 @@ impl Render for ProjectPanel {
                 .on_action(cx.listener(Self::new_search_in_directory))
                 .on_action(cx.listener(Self::unfold_directory))
                 .on_action(cx.listener(Self::fold_directory))
+                .on_action(cx.listener(Self::remove_filter_character))
+                .on_action(cx.listener(Self::toggle_hidden_files))
+                .on_action(cx.listener(|this, _: &pane::SplitLeft, cx| {
+                    this.split_selected_entry(SplitDirection::Left, cx)
+                }))
+                .on_action(cx.listener(|this, _: &pane::SplitUp, cx| {
+                    this.split_selected_entry(SplitDirection::Up, cx)
+                }))
+                .on_action(cx.listener(|this, _: &pane::SplitRight, cx| {
+                    this.split_selected_entry(SplitDirection::Right, cx)
+                }))
+                .on_action(cx.listener(|this, _: &pane::SplitDown, cx| {
+                    this.split_selected_entry(SplitDirection::Down, cx)
+                }))
+                .on_key_down(cx.listener(Self::filter_key_down))
                 .when(!project.is_read_only(), |el| {
                     el.on_action(cx.listener(Self::new_file))
                         .on_action(cx.listener(Self::new_directory))
@@ -2281,6 +2978,7 @@ impl Render for ProjectPanel {
                         .on_action(cx.listener(Self::copy))
                         .on_action(cx.listener(Self::paste))
                         .on_action(cx.listener(Self::duplicate))
+                        .on_action(cx.listener(Self::undo))
                 })
                 .when(project.is_local(), |el| {
                     el.on_action(cx.listener(Self::reveal_in_finder))
@@ -2297,6 +2995,22 @@ impl Render for ProjectPanel {
                     }),
                 )
                 .track_focus(&self.focus_handle)
+                .when(!self.filter_query.is_empty(), |el| {
+                    el.child(
+                        h_flex()
+                            .px_2()
+                            .py_1()
+                            .gap_1()
+                            .border_b_1()
+                            .border_color(cx.theme().colors().border_variant)
+                            .child(
+                                Icon::new(IconName::MagnifyingGlass)
+                                    .size(IconSize::Small)
+                                    .color(Color::Muted),
+                            )
+                            .child(Label::new(self.filter_query.clone()).size(LabelSize::Small)),
+                    )
+                })
                 .child(
                     uniform_list(
                         cx.view().clone(),
@@ -2488,6 +3202,78 @@ impl ClipboardEntry {
     }
 }
 
+/// Whether the entry is gitignored, hidden by a `.zedignore` file, or is a dot file or lives in a
+/// dot directory.
+fn is_hidden_entry(entry: &Entry) -> bool {
+    entry.is_ignored
+        || entry.is_hidden
+        || entry.path.components().any(|component| {
+            component
+                .as_os_str()
+                .to_str()
+                .map_or(false, |name| name.starts_with('.'))
+        })
+}
+
+/// Returns the entries whose name fuzzy matches the query along with all of their ancestors,
+/// so that the matches keep their place in the tree.
+fn filter_worktree_entries(
+    snapshot: &worktree::Snapshot,
+    query: &str,
+    show_hidden_files: bool,
+    match_positions: &mut HashMap<ProjectEntryId, Vec<usize>>,
+) -> Vec<Entry> {
+    let entries = snapshot
+        .entries(true, 0)
+        .filter(|entry| show_hidden_files || !is_hidden_entry(entry))
+        .collect::<Vec<_>>();
+    let names = entries
+        .iter()
+        .filter_map(|entry| entry.path.file_name()?.to_str())
+        .collect::<HashSet<_>>();
+    let candidates = names
+        .iter()
+        .map(|name| PathMatchCandidate {
+            path: Path::new(name),
+            char_bag: CharBag::from(name.to_lowercase().as_str()),
+        })
+        .collect();
+    // Entries with the same name match the same way, so they only need to be matched once
+    let positions_by_name = fuzzy::match_fixed_path_set(candidates, 0, query, false, names.len())
+        .into_iter()
+        .map(|path_match| (path_match.path, path_match.positions))
+        .collect::<HashMap<_, _>>();
+
+    let mut visible_paths = HashSet::new();
+    for entry in &entries {
+        let Some(positions) = entry
+            .path
+            .file_name()
+            .and_then(|name| positions_by_name.get(Path::new(name)))
+        else {
+            continue;
+        };
+        match_positions.insert(entry.id, positions.clone());
+        visible_paths.extend(entry.path.ancestors());
+    }
+
+    entries
+        .iter()
+        .filter(|entry| visible_paths.contains(entry.path.as_ref()))
+        .map(|entry| (*entry).clone())
+        .collect()
+}
+
+/// The letter shown next to files with the given git status.
+fn git_status_badge(status: GitFileStatus) -> &'static str {
+    match status {
+        GitFileStatus::Added => "A",
+        GitFileStatus::Modified => "M",
+        GitFileStatus::Conflict => "C",
+        GitFileStatus::Untracked => "U",
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2594,6 +3380,84 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_scroll_anchored_to_entry_in_view(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor().clone());
+        let files = (0..30)
+            .map(|ix| (format!("f{ix:02}"), json!("")))
+            .collect::<serde_json::Map<_, _>>();
+        fs.insert_tree("/root", serde_json::Value::Object(files))
+            .await;
+
+        let project = Project::test(fs.clone(), ["/root".as_ref()], cx).await;
+        let workspace = cx.add_window(|cx| Workspace::test_new(project.clone(), cx));
+        let cx = &mut VisualTestContext::from_window(*workspace, cx);
+        let panel = workspace
+            .update(cx, |workspace, cx| ProjectPanel::new(workspace, cx))
+            .unwrap();
+        let draw = |cx: &mut VisualTestContext| {
+            let panel = panel.clone();
+            cx.draw(
+                gpui::point(px(0.), px(0.)),
+                gpui::size(px(300.), px(200.)),
+                |_| panel,
+            );
+        };
+        draw(cx);
+
+        panel.update(cx, |panel, _| panel.scroll_handle.scroll_by_items(10));
+        draw(cx);
+        let scroll_top_index = panel.update(cx, |panel, _| {
+            panel.scroll_handle.logical_scroll_top_index()
+        });
+        assert_eq!(scroll_top_index, 10);
+        assert_eq!(
+            visible_entries_as_strings(&panel, scroll_top_index..scroll_top_index + 1, cx),
+            &["      f09"]
+        );
+
+        // Entries added above the ones in view keep them in place.
+        fs.insert_file("/root/a", Vec::new()).await;
+        fs.insert_file("/root/b", Vec::new()).await;
+        cx.executor().run_until_parked();
+        draw(cx);
+        let scroll_top_index = panel.update(cx, |panel, _| {
+            panel.scroll_handle.logical_scroll_top_index()
+        });
+        assert_eq!(scroll_top_index, 12);
+        assert_eq!(
+            visible_entries_as_strings(&panel, scroll_top_index..scroll_top_index + 1, cx),
+            &["      f09"]
+        );
+
+        // So do entries removed above them.
+        fs.remove_file("/root/f00".as_ref(), Default::default())
+            .await
+            .unwrap();
+        cx.executor().run_until_parked();
+        draw(cx);
+        let scroll_top_index = panel.update(cx, |panel, _| {
+            panel.scroll_handle.logical_scroll_top_index()
+        });
+        assert_eq!(scroll_top_index, 11);
+        assert_eq!(
+            visible_entries_as_strings(&panel, scroll_top_index..scroll_top_index + 1, cx),
+            &["      f09"]
+        );
+
+        // Entries added below them don't move them.
+        fs.insert_file("/root/z", Vec::new()).await;
+        cx.executor().run_until_parked();
+        assert_eq!(
+            panel.update(cx, |panel, _| panel
+                .scroll_handle
+                .logical_scroll_top_index()),
+            11
+        );
+    }
+
     #[gpui::test]
     async fn test_exclusions_in_visible_list(cx: &mut gpui::TestAppContext) {
         init_test(cx);
@@ -2829,6 +3693,50 @@ mod tests {
                 "          file_1.java",
             ]
         );
+
+        let nested_dir_3 = find_project_entry(
+            &panel,
+            "root1/dir_1/nested_dir_1/nested_dir_2/nested_dir_3",
+            cx,
+        )
+        .unwrap();
+        select_path(
+            &panel,
+            "root1/dir_1/nested_dir_1/nested_dir_2/nested_dir_3",
+            cx,
+        );
+        panel.update(cx, |panel, cx| panel.toggle_folded(nested_dir_3, cx));
+        assert_eq!(
+            visible_entries_as_strings(&panel, 0..10, cx),
+            &[
+                "v root1",
+                "    > dir_1",
+                "v root2",
+                "    v dir_2",
+                "          file_1.java",
+            ],
+            "Toggling a folded directory should unfold its directory chain"
+        );
+
+        let dir_1 = find_project_entry(&panel, "root1/dir_1", cx).unwrap();
+        select_path(&panel, "root1/dir_1", cx);
+        panel.update(cx, |panel, cx| panel.toggle_folded(dir_1, cx));
+        assert_eq!(
+            visible_entries_as_strings(&panel, 0..10, cx),
+            &[
+                "v root1",
+                "    v dir_1/nested_dir_1/nested_dir_2/nested_dir_3",
+                "        v nested_dir_4/nested_dir_5",
+                "              file_d.java",
+                "          file_a.java",
+                "          file_b.java",
+                "          file_c.java",
+                "v root2",
+                "    v dir_2",
+                "          file_1.java",
+            ],
+            "Toggling an unfolded directory chain should fold it back"
+        );
     }
 
     #[gpui::test(iterations = 30)]
@@ -3566,6 +4474,313 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_paste_conflicts_and_undo(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor().clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "a": {
+                    "one.txt": "from a",
+                },
+                "b": {
+                    "one.txt": "from b",
+                }
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), ["/root".as_ref()], cx).await;
+        let workspace = cx.add_window(|cx| Workspace::test_new(project.clone(), cx));
+        let cx = &mut VisualTestContext::from_window(*workspace, cx);
+        let panel = workspace
+            .update(cx, |workspace, cx| ProjectPanel::new(workspace, cx))
+            .unwrap();
+
+        toggle_expand_dir(&panel, "root/a", cx);
+        toggle_expand_dir(&panel, "root/b", cx);
+        select_path(&panel, "root/a/one.txt", cx);
+        panel.update(cx, |panel, cx| panel.copy(&Default::default(), cx));
+        select_path(&panel, "root/b", cx);
+        panel.update(cx, |panel, cx| panel.paste(&Default::default(), cx));
+        assert!(
+            cx.has_pending_prompt(),
+            "Should ask before overwriting an existing file"
+        );
+        cx.simulate_prompt_answer(1);
+        cx.executor().run_until_parked();
+        assert_eq!(
+            visible_entries_as_strings(&panel, 0..50, cx),
+            &[
+                "v root",
+                "    v a",
+                "          one.txt",
+                "    v b  <== selected",
+                "          one copy.txt",
+                "          one.txt",
+            ]
+        );
+
+        panel.update(cx, |panel, cx| panel.undo(&Default::default(), cx));
+        cx.executor().run_until_parked();
+        assert_eq!(
+            find_project_entry(&panel, "root/b/one copy.txt", cx),
+            None,
+            "Undoing a copy should remove the copied file"
+        );
+
+        select_path(&panel, "root/a/one.txt", cx);
+        panel.update(cx, |panel, cx| panel.cut(&Default::default(), cx));
+        select_path(&panel, "root/b", cx);
+        panel.update(cx, |panel, cx| panel.paste(&Default::default(), cx));
+        cx.simulate_prompt_answer(0);
+        cx.executor().run_until_parked();
+        assert_eq!(
+            visible_entries_as_strings(&panel, 0..50, cx),
+            &[
+                "v root",
+                "    v a",
+                "    v b  <== selected",
+                "          one.txt",
+            ]
+        );
+        assert_eq!(fs.load("/root/b/one.txt".as_ref()).await.unwrap(), "from a");
+
+        // Undoing the paste restores the entry it replaced from the trash.
+        panel.update(cx, |panel, cx| panel.undo(&Default::default(), cx));
+        cx.executor().run_until_parked();
+        assert_eq!(
+            visible_entries_as_strings(&panel, 0..50, cx),
+            &[
+                "v root",
+                "    v a",
+                "          one.txt",
+                "    v b  <== selected",
+                "          one.txt",
+            ]
+        );
+        assert_eq!(fs.load("/root/a/one.txt".as_ref()).await.unwrap(), "from a");
+        assert_eq!(fs.load("/root/b/one.txt".as_ref()).await.unwrap(), "from b");
+
+        select_path(&panel, "root/a/one.txt", cx);
+        panel.update(cx, |panel, cx| panel.copy(&Default::default(), cx));
+        select_path(&panel, "root/b", cx);
+        panel.update(cx, |panel, cx| panel.paste(&Default::default(), cx));
+        cx.simulate_prompt_answer(0);
+        cx.executor().run_until_parked();
+        assert_eq!(fs.load("/root/b/one.txt".as_ref()).await.unwrap(), "from a");
+
+        panel.update(cx, |panel, cx| panel.undo(&Default::default(), cx));
+        cx.executor().run_until_parked();
+        assert_eq!(fs.load("/root/a/one.txt".as_ref()).await.unwrap(), "from a");
+        assert_eq!(fs.load("/root/b/one.txt".as_ref()).await.unwrap(), "from b");
+    }
+
+    #[gpui::test]
+    async fn test_drag_entries_with_conflicts(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor().clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "a": {
+                    "one.txt": "from a",
+                    "two.txt": "from a",
+                },
+                "b": {
+                    "one.txt": "from b",
+                }
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), ["/root".as_ref()], cx).await;
+        let workspace = cx.add_window(|cx| Workspace::test_new(project.clone(), cx));
+        let cx = &mut VisualTestContext::from_window(*workspace, cx);
+        let panel = workspace
+            .update(cx, |workspace, cx| ProjectPanel::new(workspace, cx))
+            .unwrap();
+        let worktree_id =
+            cx.update(|cx| project.read(cx).worktrees().next().unwrap().read(cx).id());
+
+        toggle_expand_dir(&panel, "root/a", cx);
+        toggle_expand_dir(&panel, "root/b", cx);
+        let selected_entry = |path: &str, cx: &mut VisualTestContext| SelectedEntry {
+            worktree_id,
+            entry_id: find_project_entry(&panel, path, cx).unwrap(),
+        };
+        let one = selected_entry("root/a/one.txt", cx);
+        let two = selected_entry("root/a/two.txt", cx);
+        let b = find_project_entry(&panel, "root/b", cx).unwrap();
+        panel.update(cx, |panel, cx| {
+            let drag = DraggedSelection {
+                active_selection: one,
+                marked_selections: Arc::new(BTreeSet::from_iter([one, two])),
+            };
+            panel.drag_onto(&drag, b, cx);
+        });
+        assert!(
+            cx.has_pending_prompt(),
+            "Should ask before overwriting an existing file"
+        );
+        cx.simulate_prompt_answer(1);
+        cx.executor().run_until_parked();
+        assert_eq!(
+            visible_entries_as_strings(&panel, 0..50, cx),
+            &[
+                "v root",
+                "    v a",
+                "    v b  <== selected",
+                "          one copy.txt",
+                "          one.txt",
+                "          two.txt",
+            ]
+        );
+        assert_eq!(
+            fs.load("/root/b/one copy.txt".as_ref()).await.unwrap(),
+            "from a"
+        );
+
+        // Dropping entries into the directory they are already in does nothing
+        let two = selected_entry("root/b/two.txt", cx);
+        panel.update(cx, |panel, cx| {
+            let drag = DraggedSelection {
+                active_selection: two,
+                marked_selections: Arc::new(BTreeSet::new()),
+            };
+            panel.drag_onto(&drag, b, cx);
+        });
+        assert!(!cx.has_pending_prompt());
+        cx.executor().run_until_parked();
+        assert_eq!(
+            visible_entries_as_strings(&panel, 0..50, cx),
+            &[
+                "v root",
+                "    v a",
+                "    v b  <== selected",
+                "          one copy.txt",
+                "          one.txt",
+                "          two.txt",
+            ]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_back_up_and_restore_entries(cx: &mut gpui::TestAppContext) {
+        let fs = FakeFs::new(cx.executor().clone());
+        fs.insert_tree(
+            "/src",
+            json!({
+                "dir": {
+                    "nested.rs": "// Nested file",
+                },
+                "file.rs": "// Rust file",
+            }),
+        )
+        .await;
+
+        let abs_paths = [PathBuf::from("/src/dir"), PathBuf::from("/src/file.rs")];
+        let deleted_entries = back_up_entries(fs.as_ref(), &abs_paths)
+            .await
+            .unwrap()
+            .unwrap();
+        let DeletedEntries::BackedUp { backups, .. } = &deleted_entries else {
+            panic!("the entries should be backed up");
+        };
+        assert_eq!(backups.len(), 2);
+        // Each deletion is backed up on its own, replacing no other backup.
+        let other_deleted_entries = back_up_entries(fs.as_ref(), &abs_paths[1..]).await.unwrap();
+        assert!(other_deleted_entries.is_some());
+
+        for abs_path in &abs_paths {
+            fs.remove_dir(
+                abs_path,
+                RemoveOptions {
+                    recursive: true,
+                    ignore_if_not_exists: true,
+                },
+            )
+            .await
+            .ok();
+            fs.remove_file(abs_path, Default::default()).await.ok();
+        }
+        assert!(!fs.is_dir(Path::new("/src/dir")).await);
+        assert!(!fs.is_file(Path::new("/src/file.rs")).await);
+
+        restore_entries(fs.as_ref(), &deleted_entries)
+            .await
+            .unwrap();
+        assert_eq!(
+            fs.load(Path::new("/src/dir/nested.rs")).await.unwrap(),
+            "// Nested file"
+        );
+        assert_eq!(
+            fs.load(Path::new("/src/file.rs")).await.unwrap(),
+            "// Rust file"
+        );
+        if let Some(DeletedEntries::BackedUp { backup_dir, .. }) = other_deleted_entries {
+            assert!(fs.is_dir(&backup_dir).await);
+        }
+
+        // Entries too large to be copied aside aren't backed up.
+        fs.insert_file(
+            "/src/large.bin",
+            vec![0; MAX_DELETED_ENTRIES_BACKUP_SIZE as usize + 1],
+        )
+        .await;
+        assert!(back_up_entries(fs.as_ref(), &[PathBuf::from("/src")])
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[gpui::test]
+    async fn test_restore_trashed_entries(cx: &mut gpui::TestAppContext) {
+        let fs = FakeFs::new(cx.executor().clone());
+        fs.insert_tree(
+            "/src",
+            json!({
+                "dir": {
+                    "nested.rs": "// Nested file",
+                },
+                "file.rs": "// Rust file",
+            }),
+        )
+        .await;
+
+        fs.trash_dir(Path::new("/src/dir"), Default::default())
+            .await
+            .unwrap();
+        fs.trash_file(Path::new("/src/file.rs"), Default::default())
+            .await
+            .unwrap();
+        assert!(!fs.is_dir(Path::new("/src/dir")).await);
+        assert!(!fs.is_file(Path::new("/src/file.rs")).await);
+
+        let deleted_entries = DeletedEntries::Trashed(vec![
+            PathBuf::from("/src/dir"),
+            PathBuf::from("/src/file.rs"),
+        ]);
+        restore_entries(fs.as_ref(), &deleted_entries)
+            .await
+            .unwrap();
+        assert_eq!(
+            fs.load(Path::new("/src/dir/nested.rs")).await.unwrap(),
+            "// Nested file"
+        );
+        assert_eq!(
+            fs.load(Path::new("/src/file.rs")).await.unwrap(),
+            "// Rust file"
+        );
+        // Entries are only restored from the trash once.
+        assert!(restore_entries(fs.as_ref(), &deleted_entries)
+            .await
+            .is_err());
+    }
+
     #[gpui::test]
     async fn test_remove_opened_file(cx: &mut gpui::TestAppContext) {
         init_test_with_editor(cx);
@@ -3930,6 +5145,207 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_filter_entries(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor().clone());
+        fs.insert_tree(
+            "/project_root",
+            json!({
+                "dir_a": {
+                    "one.rs": "",
+                    "two.rs": "",
+                },
+                "dir_b": {
+                    "three.rs": "",
+                },
+                "dir_c": {
+                    "bone.rs": "",
+                },
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), ["/project_root".as_ref()], cx).await;
+        let workspace = cx.add_window(|cx| Workspace::test_new(project.clone(), cx));
+        let cx = &mut VisualTestContext::from_window(*workspace, cx);
+        let panel = workspace
+            .update(cx, |workspace, cx| ProjectPanel::new(workspace, cx))
+            .unwrap();
+        assert_eq!(
+            visible_entries_as_strings(&panel, 0..10, cx),
+            &[
+                "v project_root",
+                "    > dir_a",
+                "    > dir_b",
+                "    > dir_c"
+            ]
+        );
+
+        panel.update(cx, |panel, cx| panel.set_filter_query("one".into(), cx));
+        cx.executor().run_until_parked();
+        assert_eq!(
+            visible_entries_as_strings(&panel, 0..10, cx),
+            &[
+                "v project_root",
+                "    v dir_a",
+                "          one.rs  <== selected",
+                "    v dir_c",
+                "          bone.rs",
+            ]
+        );
+
+        panel.update(cx, |panel, cx| panel.cancel(&menu::Cancel, cx));
+        cx.executor().run_until_parked();
+        assert_eq!(
+            visible_entries_as_strings(&panel, 0..10, cx),
+            &[
+                "v project_root",
+                "    v dir_a",
+                "          one.rs  <== selected",
+                "          two.rs",
+                "    > dir_b",
+                "    > dir_c",
+            ]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_toggle_hidden_files(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor().clone());
+        fs.insert_tree(
+            "/project_root",
+            json!({
+                ".config": {
+                    "settings.json": "",
+                },
+                "src": {
+                    ".env": "",
+                    "main.rs": "",
+                },
+                ".gitattributes": "",
+                "README.md": "",
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), ["/project_root".as_ref()], cx).await;
+        let workspace = cx.add_window(|cx| Workspace::test_new(project.clone(), cx));
+        let cx = &mut VisualTestContext::from_window(*workspace, cx);
+        let panel = workspace
+            .update(cx, |workspace, cx| ProjectPanel::new(workspace, cx))
+            .unwrap();
+        toggle_expand_dir(&panel, "project_root/src", cx);
+        assert_eq!(
+            visible_entries_as_strings(&panel, 0..10, cx),
+            &[
+                "v project_root",
+                "    > .config",
+                "    v src  <== selected",
+                "          .env",
+                "          main.rs",
+                "      .gitattributes",
+                "      README.md",
+            ]
+        );
+
+        panel.update(cx, |panel, cx| {
+            panel.toggle_hidden_files(&ToggleHiddenFiles, cx)
+        });
+        assert_eq!(
+            visible_entries_as_strings(&panel, 0..10, cx),
+            &[
+                "v project_root",
+                "    v src  <== selected",
+                "          main.rs",
+                "      README.md",
+            ]
+        );
+
+        panel.update(cx, |panel, cx| {
+            panel.toggle_hidden_files(&ToggleHiddenFiles, cx)
+        });
+        assert_eq!(
+            visible_entries_as_strings(&panel, 0..10, cx),
+            &[
+                "v project_root",
+                "    > .config",
+                "    v src  <== selected",
+                "          .env",
+                "          main.rs",
+                "      .gitattributes",
+                "      README.md",
+            ]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_reveal_hidden_entries(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor().clone());
+        fs.insert_tree(
+            "/project_root",
+            json!({
+                ".config": {
+                    "settings.json": "",
+                },
+                "src": {
+                    "main.rs": "",
+                },
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), ["/project_root".as_ref()], cx).await;
+        let workspace = cx.add_window(|cx| Workspace::test_new(project.clone(), cx));
+        let cx = &mut VisualTestContext::from_window(*workspace, cx);
+        let panel = workspace
+            .update(cx, |workspace, cx| ProjectPanel::new(workspace, cx))
+            .unwrap();
+        let hidden_file = find_project_entry(&panel, "project_root/.config/settings.json", cx)
+            .expect("hidden files are scanned");
+        panel.update(cx, |panel, cx| {
+            panel.toggle_hidden_files(&ToggleHiddenFiles, cx)
+        });
+        assert_eq!(
+            visible_entries_as_strings(&panel, 0..10, cx),
+            &["v project_root", "    > src"]
+        );
+
+        panel.update(cx, |panel, cx| {
+            panel.project.update(cx, |_, cx| {
+                cx.emit(project::Event::ActiveEntryChanged(Some(hidden_file)))
+            })
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            visible_entries_as_strings(&panel, 0..10, cx),
+            &["v project_root", "    > src"],
+            "Following the active entry should not show hidden files"
+        );
+
+        panel.update(cx, |panel, cx| {
+            panel.project.update(cx, |_, cx| {
+                cx.emit(project::Event::RevealInProjectPanel(hidden_file))
+            })
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            visible_entries_as_strings(&panel, 0..10, cx),
+            &[
+                "v project_root",
+                "    v .config",
+                "          settings.json  <== selected",
+                "    > src",
+            ],
+            "Explicitly revealing a hidden entry should show hidden files"
+        );
+    }
+
     #[gpui::test]
     async fn test_new_file_move(cx: &mut gpui::TestAppContext) {
         init_test(cx);
@@ -4090,7 +5506,7 @@ mod tests {
                     .read(cx)
                     .entry_for_path(&(worktree_id, "").into(), cx)
                     .unwrap();
-                this.drag_onto(&drag, target_entry.id, false, cx);
+                this.drag_onto(&drag, target_entry.id, cx);
             });
         });
         cx.run_until_parked();
@@ -22,6 +22,8 @@ pub struct ProjectPanelSettings {
     pub indent_size: f32,
     pub auto_reveal_entries: bool,
     pub auto_fold_dirs: bool,
+    pub collaborators: bool,
+    pub show_hidden_files: bool,
 }
 
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
@@ -46,7 +48,8 @@ pub struct ProjectPanelSettingsContent {
     ///
     /// Default: true
     pub folder_icons: Option<bool>,
-    /// Whether to show the git status in the project panel.
+    /// Whether to show the git status in the project panel,
+    /// by coloring entries and showing a status letter next to changed files.
     ///
     /// Default: true
     pub git_status: Option<bool>,
@@ -62,9 +65,19 @@ pub struct ProjectPanelSettingsContent {
     pub auto_reveal_entries: Option<bool>,
     /// Whether to fold directories automatically
     /// when directory has only one directory inside.
+    /// Folded directories can be unfolded with alt-click.
     ///
     /// Default: false
     pub auto_fold_dirs: Option<bool>,
+    /// Whether to show the avatars of collaborators that have a file focused.
+    ///
+    /// Default: true
+    pub collaborators: Option<bool>,
+    /// Whether to show hidden (dot) files and files ignored by `.gitignore` or `.zedignore` in the project panel
+    /// by default. This can be toggled per panel with the `project_panel::ToggleHiddenFiles` action.
+    ///
+    /// Default: true
+    pub show_hidden_files: Option<bool>,
 }
 
 impl Settings for ProjectPanelSettings {
@@ -334,6 +334,11 @@ messages!(
     (RegenerateDevServerToken, Foreground),
     (RegenerateDevServerTokenResponse, Foreground),
     (RenameDevServer, Foreground),
+    (CreatePersonalAccessToken, Foreground),
+    (CreatePersonalAccessTokenResponse, Foreground),
+    (GetPersonalAccessTokens, Foreground),
+    (GetPersonalAccessTokensResponse, Foreground),
+    (RevokePersonalAccessToken, Foreground),
     (OpenNewBuffer, Foreground),
     (RestartLanguageServers, Foreground),
     (LinkedEditingRange, Background),
@@ -449,6 +454,9 @@ request_messages!(
     (DeleteDevServerProject, Ack),
     (RegenerateDevServerToken, RegenerateDevServerTokenResponse),
     (RenameDevServer, Ack),
+    (CreatePersonalAccessToken, CreatePersonalAccessTokenResponse),
+    (GetPersonalAccessTokens, GetPersonalAccessTokensResponse),
+    (RevokePersonalAccessToken, Ack),
     (RestartLanguageServers, Ack)
 );
 
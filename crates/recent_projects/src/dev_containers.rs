@@ -0,0 +1,172 @@
+use db::kvp::KEY_VALUE_STORE;
+use gpui::{Model, SharedString, ViewContext, VisualContext as _};
+use project::{
+    dev_container::{dev_container_config_path, start_dev_container},
+    Project,
+};
+use std::path::{Path, PathBuf};
+use util::ResultExt as _;
+use workspace::{
+    notifications::{
+        simple_message_notification::MessageNotification, DetachAndPromptErr, NotificationId,
+    },
+    Workspace,
+};
+
+use crate::{ReopenInDevContainer, ReopenLocally};
+
+/// Offers to run the terminals, tasks and language servers of local projects with a
+/// `.devcontainer/devcontainer.json` inside of their dev container, while the rest of Zed keeps
+/// running on the host. Folders that were reopened in their container are reopened in it again
+/// the next time they're opened, until they're reopened locally.
+pub struct DevContainers;
+
+impl DevContainers {
+    pub fn register(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
+        workspace.register_action(|workspace, _: &ReopenInDevContainer, cx| {
+            let project = workspace.project().clone();
+            let folders = local_folders(&project, cx);
+            cx.spawn(|workspace, mut cx| async move {
+                let config = cx
+                    .background_executor()
+                    .spawn(async move { find_dev_container_config(folders) })
+                    .await;
+                let Some((config_path, local_folder)) = config else {
+                    anyhow::bail!("No .devcontainer/devcontainer.json found in this project");
+                };
+                workspace.update(&mut cx, |workspace, cx| {
+                    open_in_dev_container(workspace, config_path, local_folder, cx)
+                })
+            })
+            .detach_and_prompt_err("Failed to open dev container", cx, |_, _| None);
+        });
+
+        workspace.register_action(|workspace, _: &ReopenLocally, cx| {
+            let project = workspace.project().clone();
+            let Some(local_folder) = project
+                .read(cx)
+                .dev_container()
+                .map(|dev_container| dev_container.local_folder.clone())
+            else {
+                return;
+            };
+            project.update(cx, |project, cx| project.set_dev_container(None, cx));
+            cx.background_executor()
+                .spawn(KEY_VALUE_STORE.delete_kvp(reopen_in_container_key(&local_folder)))
+                .detach_and_log_err(cx);
+        });
+
+        cx.subscribe(workspace.project(), |_, project, event, cx| {
+            if !matches!(event, project::Event::WorktreeAdded)
+                || project.read(cx).dev_container().is_some()
+            {
+                return;
+            }
+            let folders = local_folders(&project, cx);
+            cx.spawn(|workspace, mut cx| async move {
+                let config = cx
+                    .background_executor()
+                    .spawn(async move {
+                        let (config_path, local_folder) = find_dev_container_config(folders)?;
+                        let reopen = KEY_VALUE_STORE
+                            .read_kvp(&reopen_in_container_key(&local_folder))
+                            .log_err()
+                            .flatten()
+                            .is_some();
+                        Some((config_path, local_folder, reopen))
+                    })
+                    .await;
+                if let Some((config_path, local_folder, reopen)) = config {
+                    workspace
+                        .update(&mut cx, |workspace, cx| {
+                            if reopen {
+                                open_in_dev_container(workspace, config_path, local_folder, cx)
+                            } else {
+                                suggest_dev_container(workspace, config_path, local_folder, cx)
+                            }
+                        })
+                        .ok();
+                }
+            })
+            .detach();
+        })
+        .detach();
+    }
+}
+
+/// The key under which it's remembered that a folder was reopened in its dev container.
+fn reopen_in_container_key(local_folder: &Path) -> String {
+    format!("reopen-in-dev-container-{}", local_folder.to_string_lossy())
+}
+
+fn local_folders(project: &Model<Project>, cx: &ViewContext<Workspace>) -> Vec<PathBuf> {
+    let project = project.read(cx);
+    if !project.is_local() {
+        return Vec::new();
+    }
+    project
+        .visible_worktrees(cx)
+        .map(|worktree| worktree.read(cx).abs_path().to_path_buf())
+        .collect()
+}
+
+fn find_dev_container_config(folders: Vec<PathBuf>) -> Option<(PathBuf, PathBuf)> {
+    folders.into_iter().find_map(|folder| {
+        let config_path = dev_container_config_path(&folder)?;
+        Some((config_path, folder))
+    })
+}
+
+fn suggest_dev_container(
+    workspace: &mut Workspace,
+    config_path: PathBuf,
+    local_folder: PathBuf,
+    cx: &mut ViewContext<Workspace>,
+) {
+    struct DevContainerSuggestion;
+
+    let notification_id = NotificationId::identified::<DevContainerSuggestion>(SharedString::from(
+        local_folder.to_string_lossy().to_string(),
+    ));
+    let handle = cx.view().downgrade();
+    workspace.show_notification_once(notification_id, cx, |cx| {
+        cx.new_view(move |_| {
+            MessageNotification::new(
+                "This project has a dev container configuration. \
+                Do you want to run its terminals, tasks and language servers inside of the container?",
+            )
+            .with_click_message("Reopen in Container")
+            .on_click(move |cx| {
+                let config_path = config_path.clone();
+                let local_folder = local_folder.clone();
+                handle
+                    .update(cx, |workspace, cx| {
+                        open_in_dev_container(workspace, config_path, local_folder, cx)
+                    })
+                    .ok();
+            })
+        })
+    });
+}
+
+fn open_in_dev_container(
+    workspace: &mut Workspace,
+    config_path: PathBuf,
+    local_folder: PathBuf,
+    cx: &mut ViewContext<Workspace>,
+) {
+    let project = workspace.project().clone();
+    cx.spawn(|_, mut cx| async move {
+        let key = reopen_in_container_key(&local_folder);
+        let dev_container = start_dev_container(config_path, local_folder).await?;
+        project.update(&mut cx, |project, cx| {
+            project.set_dev_container(Some(dev_container), cx)
+        })?;
+        KEY_VALUE_STORE
+            .write_kvp(key, "true".to_string())
+            .await
+            .log_err();
+        anyhow::Ok(())
+    })
+    .detach_and_prompt_err("Failed to start dev container", cx, |_, _| None);
+}
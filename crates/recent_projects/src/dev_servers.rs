@@ -16,6 +16,7 @@ use gpui::{
 };
 use markdown::Markdown;
 use markdown::MarkdownStyle;
+use release_channel::ReleaseChannel;
 use rpc::proto::RegenerateDevServerTokenResponse;
 use rpc::{
     proto::{CreateDevServerResponse, DevServerStatus},
@@ -1134,6 +1135,11 @@ pub fn reconnect_to_dev_server(
     })
 }
 
+/// Installs and starts the dev server on the host over ssh, in a terminal so that passwords can
+/// be typed in.
+///
+/// ssh is only used to set up the server: once it's running, it connects to collab like manually
+/// set up dev servers do, and projects are streamed through collab rather than over ssh.
 pub async fn spawn_ssh_task(
     workspace: View<Workspace>,
     dev_server_store: Model<dev_server_projects::Store>,
@@ -1148,13 +1154,32 @@ pub async fn spawn_ssh_task(
         .flatten()
         .with_context(|| anyhow!("No terminal panel"))?;
 
+    // Run the same release channel on the server as locally, so that both ends speak the
+    // same protocol. Dev builds aren't released, so they use nightly servers instead.
+    let release_channel = match cx.update(|cx| ReleaseChannel::try_global(cx))? {
+        Some(ReleaseChannel::Dev) | Some(ReleaseChannel::Nightly) => ReleaseChannel::Nightly,
+        Some(ReleaseChannel::Preview) => ReleaseChannel::Preview,
+        Some(ReleaseChannel::Stable) | None => ReleaseChannel::Stable,
+    };
+    let channel = release_channel.dev_name();
+    // The binary of the channel is run rather than `~/.local/bin/zed`, which links to the
+    // channel that was installed last. It's where install.sh puts it on each platform.
+    let linux_binary = if release_channel == ReleaseChannel::Stable {
+        "$HOME/.local/zed.app/bin/zed".to_string()
+    } else {
+        format!("$HOME/.local/zed-{channel}.app/bin/zed")
+    };
+    let macos_binary = format!(
+        "/Applications/{}.app/Contents/MacOS/cli",
+        release_channel.display_name()
+    );
+
     let command = "sh".to_string();
     let args = vec![
         "-x".to_string(),
         "-c".to_string(),
         format!(
-            r#"~/.local/bin/zed -v >/dev/stderr || (curl -sSL https://zed.dev/install.sh || wget -qO- https://zed.dev/install.sh) | bash && ~/.local/bin/zed --dev-server-token {}"#,
-            access_token
+            r#"if [ "$(uname)" = Darwin ]; then zed="{macos_binary}"; else zed="{linux_binary}"; fi; "$zed" -v >/dev/stderr || (curl -sSL https://zed.dev/install.sh || wget -qO- https://zed.dev/install.sh) | ZED_CHANNEL={channel} bash && "$zed" --dev-server-token {access_token}"#,
         ),
     ];
 
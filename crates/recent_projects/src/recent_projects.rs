@@ -1,7 +1,9 @@
+mod dev_containers;
 mod dev_servers;
 pub mod disconnected_overlay;
 
 use client::{DevServerProjectId, ProjectId};
+use dev_containers::DevContainers;
 use dev_servers::reconnect_to_dev_server_project;
 pub use dev_servers::DevServerProjects;
 use disconnected_overlay::DisconnectedOverlay;
@@ -42,12 +44,13 @@ fn default_create_new_window() -> bool {
 }
 
 gpui::impl_actions!(projects, [OpenRecent]);
-gpui::actions!(projects, [OpenRemote]);
+gpui::actions!(projects, [OpenRemote, ReopenInDevContainer, ReopenLocally]);
 
 pub fn init(cx: &mut AppContext) {
     cx.observe_new_views(RecentProjects::register).detach();
     cx.observe_new_views(DevServerProjects::register).detach();
     cx.observe_new_views(DisconnectedOverlay::register).detach();
+    cx.observe_new_views(DevContainers::register).detach();
 }
 
 pub struct RecentProjects {
@@ -0,0 +1,207 @@
+use std::{cell::RefCell, ops::Range, path::PathBuf, rc::Rc};
+
+use anyhow::{anyhow, Result};
+use rhai::{Array, Dynamic, Engine, EvalAltResult, ImmutableString, Map, INT};
+
+/// The number of operations after which a script is stopped, so that
+/// a script that never finishes doesn't keep a thread busy.
+const MAX_OPERATIONS: u64 = 50_000_000;
+
+/// The state of the workspace that a script can read, and the changes that it requests.
+///
+/// All of the offsets are byte offsets into the text of the buffer before
+/// the script's changes are applied.
+#[derive(Debug, Default, PartialEq)]
+pub struct ScriptState {
+    pub text: String,
+    pub selections: Vec<Range<usize>>,
+    pub path: Option<PathBuf>,
+    pub worktrees: Vec<PathBuf>,
+
+    pub edits: Vec<(Range<usize>, String)>,
+    pub new_selections: Option<Vec<Range<usize>>>,
+    pub actions: Vec<String>,
+    pub paths_to_open: Vec<PathBuf>,
+}
+
+/// Runs the script, returning the state with the changes that it requested.
+pub fn run_script(source: &str, state: ScriptState) -> Result<ScriptState> {
+    let state = Rc::new(RefCell::new(state));
+    let engine = engine(&state);
+    engine.run(source).map_err(|error| anyhow!("{error}"))?;
+    drop(engine);
+
+    let mut state = state.take();
+    state.edits.sort_by_key(|(range, _)| range.start);
+    for edits in state.edits.windows(2) {
+        if edits[0].0.end > edits[1].0.start {
+            return Err(anyhow!(
+                "the edits at {:?} and {:?} overlap",
+                edits[0].0,
+                edits[1].0
+            ));
+        }
+    }
+    Ok(state)
+}
+
+fn engine(state: &Rc<RefCell<ScriptState>>) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.on_print(|text| log::info!("script: {text}"));
+    engine.on_debug(|text, _, position| log::debug!("script at {position}: {text}"));
+
+    engine.register_fn("text", {
+        let state = state.clone();
+        move || state.borrow().text.clone()
+    });
+    engine.register_fn("path", {
+        let state = state.clone();
+        move || match &state.borrow().path {
+            Some(path) => Dynamic::from(path.to_string_lossy().to_string()),
+            None => Dynamic::UNIT,
+        }
+    });
+    engine.register_fn("worktrees", {
+        let state = state.clone();
+        move || {
+            state
+                .borrow()
+                .worktrees
+                .iter()
+                .map(|path| Dynamic::from(path.to_string_lossy().to_string()))
+                .collect::<Array>()
+        }
+    });
+    engine.register_fn("selections", {
+        let state = state.clone();
+        move || {
+            let state = state.borrow();
+            state
+                .selections
+                .iter()
+                .map(|range| {
+                    let mut selection = Map::new();
+                    selection.insert("start".into(), Dynamic::from(range.start as INT));
+                    selection.insert("end".into(), Dynamic::from(range.end as INT));
+                    selection.insert(
+                        "text".into(),
+                        Dynamic::from(state.text[range.clone()].to_string()),
+                    );
+                    Dynamic::from_map(selection)
+                })
+                .collect::<Array>()
+        }
+    });
+    engine.register_fn("replace", {
+        let state = state.clone();
+        move |start: INT, end: INT, text: ImmutableString| -> Result<(), Box<EvalAltResult>> {
+            let mut state = state.borrow_mut();
+            let range = checked_range(&state.text, start, end)?;
+            state.edits.push((range, text.to_string()));
+            Ok(())
+        }
+    });
+    engine.register_fn("insert", {
+        let state = state.clone();
+        move |offset: INT, text: ImmutableString| -> Result<(), Box<EvalAltResult>> {
+            let mut state = state.borrow_mut();
+            let range = checked_range(&state.text, offset, offset)?;
+            state.edits.push((range, text.to_string()));
+            Ok(())
+        }
+    });
+    engine.register_fn("select", {
+        let state = state.clone();
+        move |start: INT, end: INT| -> Result<(), Box<EvalAltResult>> {
+            let mut state = state.borrow_mut();
+            let range = checked_range(&state.text, start, end)?;
+            state
+                .new_selections
+                .get_or_insert_with(Vec::new)
+                .push(range);
+            Ok(())
+        }
+    });
+    engine.register_fn("action", {
+        let state = state.clone();
+        move |name: ImmutableString| state.borrow_mut().actions.push(name.to_string())
+    });
+    engine.register_fn("open", {
+        let state = state.clone();
+        move |path: ImmutableString| {
+            state
+                .borrow_mut()
+                .paths_to_open
+                .push(PathBuf::from(path.as_str()))
+        }
+    });
+    engine
+}
+
+fn checked_range(text: &str, start: INT, end: INT) -> Result<Range<usize>, Box<EvalAltResult>> {
+    let range = usize::try_from(start).ok().zip(usize::try_from(end).ok());
+    match range {
+        Some((start, end))
+            if start <= end && text.is_char_boundary(start) && text.is_char_boundary(end) =>
+        {
+            Ok(start..end)
+        }
+        _ => Err(format!("{start}..{end} is not a valid range of the buffer").into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_script() {
+        let state = ScriptState {
+            text: "one two three".into(),
+            selections: vec![0..3, 8..13],
+            path: Some("/project/numbers.txt".into()),
+            worktrees: vec!["/project".into()],
+            ..Default::default()
+        };
+        let state = run_script(
+            r#"
+            for selection in selections() {
+                replace(selection.start, selection.end, selection.text.to_upper());
+            }
+            insert(text().len(), "\n" + path());
+            select(0, 3);
+            select(4, 7);
+            action("editor::Save");
+            open(worktrees()[0] + "/README.md");
+            "#,
+            state,
+        )
+        .unwrap();
+        assert_eq!(
+            state.edits,
+            [
+                (0..3, "ONE".into()),
+                (8..13, "THREE".into()),
+                (13..13, "\n/project/numbers.txt".into()),
+            ]
+        );
+        assert_eq!(state.new_selections, Some(vec![0..3, 4..7]));
+        assert_eq!(state.actions, ["editor::Save"]);
+        assert_eq!(state.paths_to_open, [PathBuf::from("/project/README.md")]);
+    }
+
+    #[test]
+    fn test_run_invalid_script() {
+        let state = || ScriptState {
+            text: "héllo".into(),
+            ..Default::default()
+        };
+        assert!(run_script("replace(2, 3, \"e\");", state()).is_err());
+        assert!(run_script("replace(3, 1, \"\");", state()).is_err());
+        assert!(run_script("replace(0, 3, \"a\"); replace(1, 4, \"b\");", state()).is_err());
+        assert!(run_script("loop {}", state()).is_err());
+        assert!(run_script("let x = ;", state()).is_err());
+        assert_eq!(run_script("", state()).unwrap(), state());
+    }
+}
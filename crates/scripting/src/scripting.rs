@@ -0,0 +1,169 @@
+mod script;
+
+use std::path::{Component, Path};
+
+use anyhow::{anyhow, Context as _, Result};
+use editor::{scroll::Autoscroll, Editor};
+use gpui::{impl_actions, AppContext, View, ViewContext};
+use serde::Deserialize;
+use workspace::{notifications::DetachAndPromptErr, Workspace};
+
+use script::{run_script, ScriptState};
+
+/// Run a script from the scripts directory.
+#[derive(PartialEq, Clone, Deserialize, Default)]
+pub struct Run {
+    /// The name of the script, without its `.rhai` extension.
+    pub name: String,
+}
+
+impl_actions!(scripting, [Run]);
+
+pub fn init(cx: &mut AppContext) {
+    cx.observe_new_views(
+        |workspace: &mut Workspace, _: &mut ViewContext<Workspace>| {
+            workspace.register_action(|workspace, action: &Run, cx| {
+                run(workspace, action.name.clone(), cx)
+            });
+        },
+    )
+    .detach();
+}
+
+fn run(workspace: &mut Workspace, name: String, cx: &mut ViewContext<Workspace>) {
+    let fs = workspace.app_state().fs.clone();
+    cx.spawn(|workspace, mut cx| async move {
+        let mut components = Path::new(&name).components();
+        if !matches!(
+            (components.next(), components.next()),
+            (Some(Component::Normal(_)), None)
+        ) {
+            return Err(anyhow!("{name:?} is not a valid script name"));
+        }
+        let path = paths::scripts_dir().join(format!("{name}.rhai"));
+        let source = fs
+            .load(&path)
+            .await
+            .with_context(|| format!("failed to load {path:?}"))?;
+        let (editor, state) =
+            workspace.update(&mut cx, |workspace, cx| script_state(workspace, cx))?;
+        // Scripts run off the main thread, so that the UI stays responsive
+        // until they finish or run out of operations.
+        let state = cx
+            .background_executor()
+            .spawn(async move { run_script(&source, state) })
+            .await?;
+        workspace.update(&mut cx, |workspace, cx| {
+            apply_script_changes(workspace, editor, state, cx)
+        })?
+    })
+    .detach_and_prompt_err("Failed to run script", cx, |error, _| {
+        Some(format!("{error:#}"))
+    });
+}
+
+/// The editor that a script was run in, and how many times its buffer had
+/// been edited when the script started.
+struct ScriptEditor {
+    editor: View<Editor>,
+    edit_count: usize,
+}
+
+/// Returns the state of the workspace for a script to read, and the active
+/// editor that it reads.
+fn script_state(
+    workspace: &mut Workspace,
+    cx: &mut ViewContext<Workspace>,
+) -> (Option<ScriptEditor>, ScriptState) {
+    let editor = workspace
+        .active_item(cx)
+        .and_then(|item| item.act_as::<Editor>(cx));
+
+    let mut state = ScriptState {
+        worktrees: workspace
+            .visible_worktrees(cx)
+            .map(|worktree| worktree.read(cx).abs_path().to_path_buf())
+            .collect(),
+        ..Default::default()
+    };
+    let editor = editor.map(|editor| {
+        let edit_count = editor.read(cx).buffer().read(cx).snapshot(cx).edit_count();
+        ScriptEditor { editor, edit_count }
+    });
+    if let Some(ScriptEditor { editor, .. }) = &editor {
+        let editor = editor.read(cx);
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        state.text = snapshot.text();
+        state.selections = editor
+            .selections
+            .all::<usize>(cx)
+            .into_iter()
+            .map(|selection| selection.range())
+            .collect();
+        state.path = editor
+            .active_excerpt(cx)
+            .and_then(|(_, buffer, _)| Some(buffer.read(cx).file()?.as_local()?.abs_path(cx)));
+    }
+    (editor, state)
+}
+
+fn apply_script_changes(
+    workspace: &mut Workspace,
+    editor: Option<ScriptEditor>,
+    state: ScriptState,
+    cx: &mut ViewContext<Workspace>,
+) -> Result<()> {
+    let actions = state
+        .actions
+        .iter()
+        .map(|name| {
+            cx.build_action(name, None)
+                .with_context(|| format!("{name:?} is not an action"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if !state.edits.is_empty() || state.new_selections.is_some() {
+        let ScriptEditor { editor, edit_count } =
+            editor.context("there is no active editor to change")?;
+        // The script's offsets don't apply to text that changed while it ran.
+        if editor.read(cx).buffer().read(cx).snapshot(cx).edit_count() != edit_count {
+            return Err(anyhow!(
+                "the editor was edited while the script was running"
+            ));
+        }
+        editor.update(cx, |editor, cx| {
+            // Selections are given in terms of the text before the edits, so they
+            // are anchored before the edits are applied, and grow to contain any
+            // text that is inserted at their ends.
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let new_selections = state.new_selections.map(|ranges| {
+                ranges
+                    .into_iter()
+                    .map(|range| {
+                        snapshot.anchor_before(range.start)..snapshot.anchor_after(range.end)
+                    })
+                    .collect::<Vec<_>>()
+            });
+            editor.transact(cx, |editor, cx| {
+                editor.edit(state.edits, cx);
+                if let Some(new_selections) = new_selections {
+                    editor.change_selections(Some(Autoscroll::fit()), cx, |selections| {
+                        selections.select_anchor_ranges(new_selections)
+                    });
+                }
+            });
+        });
+    }
+
+    for path in state.paths_to_open {
+        workspace
+            .open_abs_path(path, true, cx)
+            .detach_and_log_err(cx);
+    }
+
+    for action in actions {
+        cx.dispatch_action(action);
+    }
+
+    Ok(())
+}
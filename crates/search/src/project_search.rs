@@ -8,7 +8,8 @@ use editor::{
     actions::SelectAll,
     items::active_match_index,
     scroll::{Autoscroll, Axis},
-    Anchor, Editor, EditorElement, EditorEvent, EditorStyle, MultiBuffer, MAX_TAB_TITLE_LEN,
+    Anchor, Editor, EditorElement, EditorEvent, EditorStyle, ExcerptId, MultiBuffer,
+    MAX_TAB_TITLE_LEN,
 };
 use gpui::{
     actions, div, Action, AnyElement, AnyView, AppContext, Context as _, Element, EntityId,
@@ -17,22 +18,27 @@ use gpui::{
     Subscription, Task, TextStyle, UpdateGlobal, View, ViewContext, VisualContext, WeakModel,
     WeakView, WhiteSpace, WindowContext,
 };
+use language::Buffer;
 use menu::Confirm;
-use project::{search::SearchQuery, search_history::SearchHistoryCursor, Project, ProjectPath};
+use project::{
+    compare_paths, search::SearchQuery, search_history::SearchHistoryCursor, Project, ProjectPath,
+};
 use settings::Settings;
 use smol::stream::StreamExt;
 use std::{
     any::{Any, TypeId},
+    cmp::Ordering,
     mem,
     ops::{Not, Range},
     path::Path,
+    sync::Arc,
 };
 use theme::ThemeSettings;
 use ui::{
     h_flex, prelude::*, v_flex, Icon, IconButton, IconName, Label, LabelCommon, LabelSize,
     Selectable, Tooltip,
 };
-use util::paths::PathMatcher;
+use util::{paths::PathMatcher, truncate_and_remove_front};
 use workspace::{
     item::{BreadcrumbText, Item, ItemEvent, ItemHandle, TabContentParams},
     searchable::{Direction, SearchableItem, SearchableItemHandle},
@@ -118,14 +124,24 @@ struct ProjectSearch {
     excerpts: Model<MultiBuffer>,
     pending_search: Option<Task<Option<()>>>,
     match_ranges: Vec<Range<Anchor>>,
+    result_buffers: Vec<ResultBuffer>,
     active_query: Option<SearchQuery>,
     last_search_query_text: Option<String>,
     search_id: usize,
     no_results: Option<bool>,
     limit_reached: bool,
+    search_progress: Option<ProjectPath>,
     search_history_cursor: SearchHistoryCursor,
 }
 
+/// A buffer whose matches were added to the search results.
+#[derive(Clone)]
+struct ResultBuffer {
+    path: Option<(usize, Arc<Path>)>,
+    last_excerpt_id: ExcerptId,
+    match_count: usize,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum InputPanel {
     Query,
@@ -172,11 +188,13 @@ impl ProjectSearch {
             excerpts: cx.new_model(|_| MultiBuffer::new(replica_id, capability)),
             pending_search: Default::default(),
             match_ranges: Default::default(),
+            result_buffers: Default::default(),
             active_query: None,
             last_search_query_text: None,
             search_id: 0,
             no_results: None,
             limit_reached: false,
+            search_progress: None,
             search_history_cursor: Default::default(),
         }
     }
@@ -189,11 +207,13 @@ impl ProjectSearch {
                 .update(cx, |excerpts, cx| cx.new_model(|cx| excerpts.clone(cx))),
             pending_search: Default::default(),
             match_ranges: self.match_ranges.clone(),
+            result_buffers: self.result_buffers.clone(),
             active_query: self.active_query.clone(),
             last_search_query_text: self.last_search_query_text.clone(),
             search_id: self.search_id,
             no_results: self.no_results,
             limit_reached: self.limit_reached,
+            search_progress: None,
             search_history_cursor: self.search_history_cursor.clone(),
         })
     }
@@ -214,9 +234,11 @@ impl ProjectSearch {
             let this = this.upgrade()?;
             this.update(&mut cx, |this, cx| {
                 this.match_ranges.clear();
+                this.result_buffers.clear();
                 this.excerpts.update(cx, |this, cx| this.clear(cx));
                 this.no_results = Some(true);
                 this.limit_reached = false;
+                this.search_progress = None;
             })
             .ok()?;
 
@@ -224,25 +246,17 @@ impl ProjectSearch {
             while let Some(result) = matches.next().await {
                 match result {
                     project::SearchResult::Buffer { buffer, ranges } => {
-                        let mut match_ranges = this
-                            .update(&mut cx, |this, cx| {
-                                this.no_results = Some(false);
-                                this.excerpts.update(cx, |excerpts, cx| {
-                                    excerpts.stream_excerpts_with_context_lines(
-                                        buffer,
-                                        ranges,
-                                        editor::DEFAULT_MULTIBUFFER_CONTEXT,
-                                        cx,
-                                    )
-                                })
-                            })
-                            .ok()?;
-
-                        while let Some(range) = match_ranges.next().await {
-                            this.update(&mut cx, |this, _| this.match_ranges.push(range))
-                                .ok()?;
-                        }
-                        this.update(&mut cx, |_, cx| cx.notify()).ok()?;
+                        this.update(&mut cx, |this, cx| {
+                            this.insert_result_buffer(buffer, ranges, cx)
+                        })
+                        .ok()?;
+                    }
+                    project::SearchResult::Progress { path } => {
+                        this.update(&mut cx, |this, cx| {
+                            this.search_progress = Some(path);
+                            cx.notify();
+                        })
+                        .ok()?;
                     }
                     project::SearchResult::LimitReached => {
                         limit_reached = true;
@@ -252,6 +266,7 @@ impl ProjectSearch {
 
             this.update(&mut cx, |this, cx| {
                 this.limit_reached = limit_reached;
+                this.search_progress = None;
                 this.pending_search.take();
                 cx.notify();
             })
@@ -261,6 +276,83 @@ impl ProjectSearch {
         }));
         cx.notify();
     }
+
+    /// Stops the search that's underway, keeping the results found so far.
+    fn cancel_search(&mut self, cx: &mut ModelContext<Self>) {
+        if self.pending_search.take().is_some() {
+            self.search_progress = None;
+            cx.notify();
+        }
+    }
+
+    /// Adds the matches of a buffer to the results, which are kept sorted by path
+    /// regardless of the order the buffers are found in.
+    fn insert_result_buffer(
+        &mut self,
+        buffer: Model<Buffer>,
+        ranges: Vec<Range<language::Anchor>>,
+        cx: &mut ModelContext<Self>,
+    ) {
+        if ranges.is_empty() {
+            return;
+        }
+        self.no_results = Some(false);
+
+        let path = buffer
+            .read(cx)
+            .file()
+            .map(|file| (file.worktree_id(), file.path().clone()));
+        let ix = self.result_buffers.partition_point(|result_buffer| {
+            compare_result_paths(&result_buffer.path, &path) != Ordering::Greater
+        });
+        let prev_excerpt_id = ix.checked_sub(1).map_or(ExcerptId::min(), |prev_ix| {
+            self.result_buffers[prev_ix].last_excerpt_id
+        });
+        let match_ranges = self.excerpts.update(cx, |excerpts, cx| {
+            excerpts.insert_excerpts_with_context_lines_after(
+                prev_excerpt_id,
+                buffer,
+                ranges,
+                editor::DEFAULT_MULTIBUFFER_CONTEXT,
+                cx,
+            )
+        });
+        let Some(last_excerpt_id) = match_ranges.last().map(|range| range.end.excerpt_id) else {
+            return;
+        };
+
+        let match_ix = self.result_buffers[..ix]
+            .iter()
+            .map(|result_buffer| result_buffer.match_count)
+            .sum::<usize>();
+        let match_count = match_ranges.len();
+        self.match_ranges.splice(match_ix..match_ix, match_ranges);
+        self.result_buffers.insert(
+            ix,
+            ResultBuffer {
+                path,
+                last_excerpt_id,
+                match_count,
+            },
+        );
+        cx.notify();
+    }
+}
+
+/// Orders search results the same way project search does: buffers without a path first,
+/// then by worktree and path.
+fn compare_result_paths(
+    a: &Option<(usize, Arc<Path>)>,
+    b: &Option<(usize, Arc<Path>)>,
+) -> Ordering {
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Less,
+        (Some(_), None) => Ordering::Greater,
+        (Some((worktree_a, path_a)), Some((worktree_b, path_b))) => worktree_a
+            .cmp(worktree_b)
+            .then_with(|| compare_paths((path_a.as_ref(), true), (path_b.as_ref(), true))),
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -639,7 +731,10 @@ impl ProjectSearchView {
         });
         // Subscribe to query_editor in order to reraise editor events for workspace item activation purposes
         subscriptions.push(
-            cx.subscribe(&query_editor, |_, _, event: &EditorEvent, cx| {
+            cx.subscribe(&query_editor, |this, _, event: &EditorEvent, cx| {
+                if let EditorEvent::BufferEdited = event {
+                    this.cancel_outdated_search(cx);
+                }
                 cx.emit(ViewEvent::EditorEvent(event.clone()))
             }),
         );
@@ -858,6 +953,20 @@ impl ProjectSearchView {
         }
     }
 
+    /// Stops the search that's underway once the query it was started with gets edited.
+    fn cancel_outdated_search(&mut self, cx: &mut ViewContext<Self>) {
+        let query_text = self.query_editor.read(cx).text(cx);
+        let model = self.model.read(cx);
+        let is_outdated = model.pending_search.is_some()
+            && model
+                .active_query
+                .as_ref()
+                .map_or(false, |query| query.as_str() != query_text);
+        if is_outdated {
+            self.model.update(cx, |model, cx| model.cancel_search(cx));
+        }
+    }
+
     fn build_search_query(&mut self, cx: &mut ViewContext<Self>) -> Option<SearchQuery> {
         // Do not bail early in this function, as we want to fill out `self.panels_with_errors`.
         let text = self.query_editor.read(cx).text(cx);
@@ -1016,12 +1125,19 @@ impl ProjectSearchView {
         if match_ranges.is_empty() {
             self.active_match_index = None;
         } else {
+            // Results can be inserted before the first match while a search is underway,
+            // in which case the new first match gets selected instead.
+            let was_at_first_match = self.active_match_index == Some(0);
             self.active_match_index = Some(0);
             self.update_match_index(cx);
             let prev_search_id = mem::replace(&mut self.search_id, self.model.read(cx).search_id);
             let is_new_search = self.search_id != prev_search_id;
+            let first_match_moved = was_at_first_match && self.active_match_index != Some(0);
+            if first_match_moved {
+                self.active_match_index = Some(0);
+            }
             self.results_editor.update(cx, |editor, cx| {
-                if is_new_search {
+                if is_new_search || first_match_moved {
                     let range_to_select = match_ranges
                         .first()
                         .map(|range| editor.range_for_match(range));
@@ -1419,6 +1535,7 @@ impl Render for ProjectSearchBar {
             .unwrap_or_else(|| "0/0".to_string());
 
         let limit_reached = search.model.read(cx).limit_reached;
+        let search_progress = search.model.read(cx).search_progress.clone();
 
         let matches_column = h_flex()
             .child(
@@ -1464,6 +1581,18 @@ impl Render for ProjectSearchBar {
                         .child(Label::new("Search limit reached").color(Color::Warning))
                         .ml_2(),
                 )
+            })
+            .when_some(search_progress, |this, progress| {
+                let path = truncate_and_remove_front(&progress.path.to_string_lossy(), 40);
+                this.child(
+                    div()
+                        .child(
+                            Label::new(format!("Searching {path}…"))
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                        .ml_2(),
+                )
             });
 
         let search_line = h_flex()
@@ -3223,6 +3352,74 @@ pub mod tests {
             .expect("unable to update search view");
     }
 
+    #[gpui::test]
+    async fn test_editing_query_cancels_search(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/dir",
+            json!({
+                "one.rs": "const ONE: usize = 1;",
+                "two.rs": "const TWO: usize = one::ONE + one::ONE;",
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), ["/dir".as_ref()], cx).await;
+        let search = cx.new_model(|cx| ProjectSearch::new(project, cx));
+        let search_view = cx.add_window(|cx| ProjectSearchView::new(search.clone(), cx, None));
+
+        search_view
+            .update(cx, |search_view, cx| {
+                search_view
+                    .query_editor
+                    .update(cx, |query_editor, cx| query_editor.set_text("ONE", cx));
+                search_view.search(cx);
+            })
+            .unwrap();
+        search_view
+            .update(cx, |search_view, cx| {
+                assert!(search_view.model.read(cx).pending_search.is_some());
+                search_view
+                    .query_editor
+                    .update(cx, |query_editor, cx| query_editor.set_text("TWO", cx));
+            })
+            .unwrap();
+        search_view
+            .update(cx, |search_view, cx| {
+                assert!(
+                    search_view.model.read(cx).pending_search.is_none(),
+                    "Editing the query should cancel the search that's underway"
+                );
+            })
+            .unwrap();
+
+        cx.background_executor.run_until_parked();
+        search_view
+            .update(cx, |search_view, cx| {
+                assert_eq!(
+                    search_view
+                        .results_editor
+                        .update(cx, |editor, cx| editor.display_text(cx)),
+                    ""
+                );
+                assert_eq!(search_view.active_match_index, None);
+            })
+            .unwrap();
+
+        perform_search(search_view, "TWO", cx);
+        search_view
+            .update(cx, |search_view, cx| {
+                assert_eq!(
+                    search_view
+                        .results_editor
+                        .update(cx, |editor, cx| editor.display_text(cx)),
+                    "\n\n\nconst TWO: usize = one::ONE + one::ONE;\n"
+                );
+            })
+            .unwrap();
+    }
+
     fn init_test(cx: &mut TestAppContext) {
         cx.update(|cx| {
             let settings = SettingsStore::test(cx);
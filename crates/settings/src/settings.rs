@@ -10,7 +10,8 @@ use util::asset_str;
 pub use keymap_file::KeymapFile;
 pub use settings_file::*;
 pub use settings_store::{
-    Settings, SettingsJsonSchemaParams, SettingsLocation, SettingsSources, SettingsStore,
+    parse_json_with_comments, Settings, SettingsJsonSchemaParams, SettingsLocation,
+    SettingsSources, SettingsStore,
 };
 
 #[derive(RustEmbed)]
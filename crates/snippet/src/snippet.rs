@@ -10,6 +10,9 @@ pub struct Snippet {
 
 type TabStop = SmallVec<[Range<isize>; 2]>;
 
+/// Unknown variables are turned into placeholders, which are visited after the numbered tabstops.
+const VARIABLE_TABSTOPS_START: usize = usize::MAX / 2;
+
 impl Snippet {
     pub fn parse(source: &str) -> Result<Self> {
         let mut text = String::with_capacity(source.len());
@@ -34,6 +37,62 @@ impl Snippet {
     }
 }
 
+/// Replaces the variables in the snippet source (like `$TM_FILENAME` or `${CLIPBOARD:default}`)
+/// that `resolve` knows the value of. Variables it doesn't know are left in place,
+/// to be turned into placeholders when the snippet is parsed.
+pub fn expand_variables(source: &str, resolve: impl Fn(&str) -> Option<String>) -> String {
+    let mut result = String::with_capacity(source.len());
+    let mut source = source;
+    while let Some(ix) = source.find(['$', '\\']) {
+        result.push_str(&source[..ix]);
+        source = &source[ix..];
+        if source.starts_with('\\') {
+            let len = 1 + source[1..].chars().next().map_or(0, char::len_utf8);
+            result.push_str(&source[..len]);
+            source = &source[len..];
+            continue;
+        }
+
+        let (braced, name_source) = match source[1..].strip_prefix('{') {
+            Some(rest) => (true, rest),
+            None => (false, &source[1..]),
+        };
+        let expanded = parse_variable_name(name_source).and_then(|(name, rest)| {
+            let (default, rest) = if !braced {
+                (None, rest)
+            } else if let Some(rest) = rest.strip_prefix('}') {
+                (None, rest)
+            } else if let Some(default) = rest.strip_prefix(':') {
+                let rest = skip_to_closing_brace(default)?;
+                (Some(&default[..default.len() - rest.len() - 1]), rest)
+            } else {
+                return None;
+            };
+            Some((resolve(name)?, default, rest))
+        });
+        if let Some((value, default, rest)) = expanded {
+            match default {
+                // Like in VS Code, empty variables (such as an empty selection) use their default.
+                Some(default) if value.is_empty() => result.push_str(default),
+                _ => {
+                    for c in value.chars() {
+                        if matches!(c, '$' | '}' | '\\') {
+                            result.push('\\');
+                        }
+                        result.push(c);
+                    }
+                }
+            }
+            source = rest;
+        } else {
+            result.push('$');
+            source = &source[1..];
+        }
+    }
+    result.push_str(source);
+    result
+}
+
 fn parse_snippet<'a>(
     mut source: &'a str,
     nested: bool,
@@ -79,12 +138,18 @@ fn parse_tabstop<'a>(
     let tabstop_start = text.len();
     let tabstop_index;
     if source.starts_with('{') {
+        if let Some((name, rest)) = parse_variable_name(&source[1..]) {
+            return parse_variable(name, rest, true, text, tabstops);
+        }
+
         let (index, rest) = parse_int(&source[1..])?;
         tabstop_index = index;
         source = rest;
 
         if source.starts_with(':') {
             source = parse_snippet(&source[1..], true, text, tabstops)?;
+        } else if source.starts_with('|') {
+            source = parse_choices(&source[1..], text)?;
         }
 
         if source.starts_with('}') {
@@ -92,10 +157,16 @@ fn parse_tabstop<'a>(
         } else {
             return Err(anyhow!("expected a closing brace"));
         }
-    } else {
+    } else if let Some((name, rest)) = parse_variable_name(source) {
+        return parse_variable(name, rest, false, text, tabstops);
+    } else if source.starts_with(|c: char| c.is_ascii_digit()) {
         let (index, rest) = parse_int(source)?;
         tabstop_index = index;
         source = rest;
+    } else {
+        // A `$` that doesn't start a tabstop or a variable is inserted literally.
+        text.push('$');
+        return Ok(source);
     }
 
     tabstops
@@ -105,6 +176,100 @@ fn parse_tabstop<'a>(
     Ok(source)
 }
 
+/// Inserts an unknown variable as a placeholder containing its default, or else its name.
+fn parse_variable<'a>(
+    name: &str,
+    mut source: &'a str,
+    braced: bool,
+    text: &mut String,
+    tabstops: &mut BTreeMap<usize, TabStop>,
+) -> Result<&'a str> {
+    let tabstop_start = text.len();
+    let tabstop_index = VARIABLE_TABSTOPS_START + tabstops.range(VARIABLE_TABSTOPS_START..).count();
+    tabstops.insert(tabstop_index, TabStop::new());
+
+    if braced {
+        if source.starts_with(':') {
+            source = parse_snippet(&source[1..], true, text, tabstops)?;
+        } else if source.starts_with('}') {
+            text.push_str(name);
+        } else if source.starts_with('/') {
+            return Err(anyhow!("variable transformations are not supported"));
+        }
+
+        if source.starts_with('}') {
+            source = &source[1..];
+        } else {
+            return Err(anyhow!("expected a closing brace"));
+        }
+    } else {
+        text.push_str(name);
+    }
+
+    tabstops
+        .entry(tabstop_index)
+        .or_default()
+        .push(tabstop_start as isize..text.len() as isize);
+    Ok(source)
+}
+
+/// Inserts the first of the choices, like `one` for `${1|one,two|}`.
+fn parse_choices<'a>(mut source: &'a str, text: &mut String) -> Result<&'a str> {
+    let mut is_first_choice = true;
+    loop {
+        match source.chars().next() {
+            None => return Err(anyhow!("expected a closing pipe")),
+            Some('|') => return Ok(&source[1..]),
+            Some(',') => {
+                is_first_choice = false;
+                source = &source[1..];
+            }
+            Some(c) => {
+                let mut c = c;
+                if c == '\\' {
+                    source = &source[1..];
+                    c = source
+                        .chars()
+                        .next()
+                        .ok_or_else(|| anyhow!("expected a closing pipe"))?;
+                }
+                if is_first_choice {
+                    text.push(c);
+                }
+                source = &source[c.len_utf8()..];
+            }
+        }
+    }
+}
+
+fn parse_variable_name(source: &str) -> Option<(&str, &str)> {
+    if !source.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        return None;
+    }
+    let len = source
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .unwrap_or(source.len());
+    Some(source.split_at(len))
+}
+
+/// Returns the source after the brace that closes the current one.
+fn skip_to_closing_brace(source: &str) -> Option<&str> {
+    let mut depth = 0;
+    let mut chars = source.char_indices();
+    while let Some((ix, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '{' => depth += 1,
+            '}' if depth == 0 => return Some(&source[ix + 1..]),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
 fn parse_int(source: &str) -> Result<(usize, &str)> {
     let len = source
         .find(|c: char| !c.is_ascii_digit())
@@ -199,6 +364,64 @@ mod tests {
         assert_eq!(tabstops(&snippet), &[vec![3..3]]);
     }
 
+    #[test]
+    fn test_snippet_with_choices() {
+        let snippet = Snippet::parse("let ${1|mut x,y\\,z|} = $0;").unwrap();
+        assert_eq!(snippet.text, "let mut x = ;");
+        assert_eq!(tabstops(&snippet), &[vec![4..9], vec![12..12]]);
+    }
+
+    #[test]
+    fn test_snippet_with_unknown_variables() {
+        let snippet = Snippet::parse("${1:a} $UNKNOWN ${OTHER:b$2} ${THIRD} $").unwrap();
+        assert_eq!(snippet.text, "a UNKNOWN b THIRD $");
+        assert_eq!(
+            tabstops(&snippet),
+            &[
+                vec![0..1],
+                vec![11..11],
+                vec![2..9],
+                vec![10..11],
+                vec![12..17],
+                vec![19..19]
+            ]
+        );
+
+        assert!(Snippet::parse("${TM_FILENAME/(.*)/${1:/upcase}/}").is_err());
+    }
+
+    #[test]
+    fn test_expand_variables() {
+        let resolve = |name: &str| match name {
+            "TM_FILENAME" => Some("main.rs".to_string()),
+            "CLIPBOARD" => Some("${1:}".to_string()),
+            "TM_SELECTED_TEXT" => Some(String::new()),
+            _ => None,
+        };
+        assert_eq!(
+            expand_variables("$TM_FILENAME ${TM_FILENAME} \\$TM_FILENAME", resolve),
+            "main.rs main.rs \\$TM_FILENAME"
+        );
+        assert_eq!(
+            expand_variables(
+                "${CLIPBOARD:${1:{nested}}}$1 $UNKNOWN ${UNKNOWN:x}",
+                resolve
+            ),
+            "\\${1:\\}$1 $UNKNOWN ${UNKNOWN:x}"
+        );
+        assert_eq!(
+            expand_variables(
+                "${TM_SELECTED_TEXT:${1:default}} $TM_SELECTED_TEXT",
+                resolve
+            ),
+            "${1:default} "
+        );
+
+        let snippet = Snippet::parse(&expand_variables("${CLIPBOARD}$1", resolve)).unwrap();
+        assert_eq!(snippet.text, "${1:}");
+        assert_eq!(tabstops(&snippet), &[vec![5..5]]);
+    }
+
     fn tabstops(snippet: &Snippet) -> Vec<Vec<Range<isize>>> {
         snippet.tabstops.iter().map(|t| t.to_vec()).collect()
     }
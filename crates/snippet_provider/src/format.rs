@@ -0,0 +1,42 @@
+use collections::HashMap;
+use serde::Deserialize;
+
+/// A file of snippets in the format used by VS Code, mapping the names of snippets to their definitions.
+#[derive(Deserialize, Debug, Default)]
+#[serde(transparent)]
+pub(crate) struct VsCodeSnippetsFile {
+    pub(crate) snippets: HashMap<String, VsCodeSnippet>,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct VsCodeSnippet {
+    /// The text, or texts, that the snippet is offered for when typed.
+    /// Snippets without a prefix are only available by their name.
+    pub(crate) prefix: Option<ListOrDirect>,
+    pub(crate) body: ListOrDirect,
+    pub(crate) description: Option<ListOrDirect>,
+    /// The comma-separated ids of the languages that the snippet applies to,
+    /// for snippets in `.code-snippets` files.
+    pub(crate) scope: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub(crate) enum ListOrDirect {
+    Single(String),
+    List(Vec<String>),
+}
+
+impl ListOrDirect {
+    pub(crate) fn into_vec(self) -> Vec<String> {
+        match self {
+            Self::Single(item) => vec![item],
+            Self::List(items) => items,
+        }
+    }
+
+    /// Joins multiple lines, as used for bodies and descriptions.
+    pub(crate) fn into_lines(self) -> String {
+        self.into_vec().join("\n")
+    }
+}
@@ -0,0 +1,320 @@
+mod format;
+
+use anyhow::{anyhow, Context as _, Result};
+use collections::HashMap;
+use fs::Fs;
+use futures::StreamExt;
+use gpui::{AppContext, Context, Global, Model, ModelContext, Task};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use util::ResultExt;
+
+use crate::format::VsCodeSnippetsFile;
+
+/// A snippet defined by the user, or imported from VS Code.
+#[derive(Debug, PartialEq)]
+pub struct UserSnippet {
+    pub name: String,
+    pub prefixes: Vec<String>,
+    /// The body of the snippet, in the snippet syntax shared by VS Code and LSP.
+    pub body: String,
+    pub description: Option<String>,
+    /// The VS Code ids of the languages that the snippet applies to, or `None` for all languages.
+    languages: Option<Vec<String>>,
+}
+
+impl UserSnippet {
+    fn applies_to(&self, language_id: Option<&str>) -> bool {
+        match (&self.languages, language_id) {
+            (None, _) => true,
+            (Some(languages), Some(language_id)) => {
+                languages.iter().any(|language| language == language_id)
+            }
+            (Some(_), None) => false,
+        }
+    }
+}
+
+/// Loads the user's snippets from the snippets directory, and keeps them up to date.
+///
+/// Snippets use the format of VS Code: `<language id>.json` files contain the snippets of
+/// a single language, while `.code-snippets` files may contain snippets for any
+/// language, restricted to some languages with their `scope`.
+pub struct SnippetProvider {
+    fs: Arc<dyn Fs>,
+    snippets_dir: PathBuf,
+    snippets: HashMap<PathBuf, Vec<Arc<UserSnippet>>>,
+    _watch_snippets: Task<()>,
+}
+
+struct GlobalSnippetProvider(Model<SnippetProvider>);
+
+impl Global for GlobalSnippetProvider {}
+
+pub fn init(fs: Arc<dyn Fs>, cx: &mut AppContext) {
+    let provider = cx.new_model(|cx| SnippetProvider::new(fs, paths::snippets_dir().clone(), cx));
+    cx.set_global(GlobalSnippetProvider(provider));
+}
+
+impl SnippetProvider {
+    pub fn global(cx: &AppContext) -> Model<Self> {
+        cx.global::<GlobalSnippetProvider>().0.clone()
+    }
+
+    pub fn try_global(cx: &AppContext) -> Option<Model<Self>> {
+        cx.try_global::<GlobalSnippetProvider>()
+            .map(|provider| provider.0.clone())
+    }
+
+    fn new(fs: Arc<dyn Fs>, snippets_dir: PathBuf, cx: &mut ModelContext<Self>) -> Self {
+        let _watch_snippets = cx.spawn({
+            let fs = fs.clone();
+            let snippets_dir = snippets_dir.clone();
+            |this, mut cx| async move {
+                if !fs.is_dir(&snippets_dir).await {
+                    fs.create_dir(&snippets_dir).await.log_err();
+                }
+                let (mut events, _) = fs.watch(&snippets_dir, Duration::from_millis(100)).await;
+
+                if let Some(mut entries) = fs.read_dir(&snippets_dir).await.log_err() {
+                    while let Some(path) = entries.next().await {
+                        if let Some(path) = path.log_err() {
+                            Self::reload_file(&this, &fs, path, &mut cx).await;
+                        }
+                    }
+                }
+
+                while let Some(paths) = events.next().await {
+                    for path in paths {
+                        Self::reload_file(&this, &fs, path, &mut cx).await;
+                    }
+                }
+            }
+        });
+
+        Self {
+            fs,
+            snippets_dir,
+            snippets: HashMap::default(),
+            _watch_snippets,
+        }
+    }
+
+    async fn reload_file(
+        this: &gpui::WeakModel<Self>,
+        fs: &Arc<dyn Fs>,
+        path: PathBuf,
+        cx: &mut gpui::AsyncAppContext,
+    ) {
+        if !is_snippet_file(&path) {
+            return;
+        }
+        let snippets = if fs.is_file(&path).await {
+            match load_snippets(fs.as_ref(), &path).await {
+                Ok(snippets) => snippets,
+                Err(error) => {
+                    log::error!("failed to load snippets from {path:?}: {error:#}");
+                    return;
+                }
+            }
+        } else {
+            Vec::new()
+        };
+        this.update(cx, |this, cx| {
+            if snippets.is_empty() {
+                this.snippets.remove(&path);
+            } else {
+                this.snippets.insert(path, snippets);
+            }
+            cx.notify();
+        })
+        .ok();
+    }
+
+    /// Returns the snippets that apply to the language of the given VS Code id, sorted by name.
+    pub fn snippets_for(&self, language_id: Option<&str>) -> Vec<Arc<UserSnippet>> {
+        let mut snippets = self
+            .snippets
+            .values()
+            .flatten()
+            .filter(|snippet| snippet.applies_to(language_id))
+            .cloned()
+            .collect::<Vec<_>>();
+        snippets.sort_by(|a, b| a.name.cmp(&b.name));
+        snippets
+    }
+
+    /// Copies the VS Code snippet files at the given paths, or in the given directories, into
+    /// the snippets directory. Snippets are merged into existing files of the same name.
+    ///
+    /// Returns the number of imported snippets.
+    pub fn import(&self, paths: Vec<PathBuf>, cx: &ModelContext<Self>) -> Task<Result<usize>> {
+        let fs = self.fs.clone();
+        let snippets_dir = self.snippets_dir.clone();
+        cx.background_executor().spawn(async move {
+            let mut files = Vec::new();
+            for path in paths {
+                if fs.is_dir(&path).await {
+                    let mut entries = fs.read_dir(&path).await?;
+                    while let Some(entry) = entries.next().await {
+                        let entry = entry?;
+                        if is_snippet_file(&entry) {
+                            files.push(entry);
+                        }
+                    }
+                } else {
+                    files.push(path);
+                }
+            }
+
+            fs.create_dir(&snippets_dir).await?;
+            let mut imported_snippets = 0;
+            for file in files {
+                let content = fs.load(&file).await?;
+                // Validate the snippets before copying them.
+                settings::parse_json_with_comments::<VsCodeSnippetsFile>(&content)
+                    .with_context(|| format!("invalid snippets file {file:?}"))?;
+                let snippets: serde_json::Map<String, serde_json::Value> =
+                    settings::parse_json_with_comments(&content)?;
+                imported_snippets += snippets.len();
+
+                let file_name = file
+                    .file_name()
+                    .ok_or_else(|| anyhow!("invalid snippets file {file:?}"))?;
+                let target = snippets_dir.join(file_name);
+                let mut merged_snippets = serde_json::Map::new();
+                if fs.is_file(&target).await {
+                    merged_snippets = settings::parse_json_with_comments(&fs.load(&target).await?)?;
+                }
+                merged_snippets.extend(snippets);
+                fs.atomic_write(target, serde_json::to_string_pretty(&merged_snippets)?)
+                    .await?;
+            }
+            Ok(imported_snippets)
+        })
+    }
+}
+
+fn is_snippet_file(path: &Path) -> bool {
+    path.extension().map_or(false, |extension| {
+        extension == "json" || extension == "code-snippets"
+    })
+}
+
+async fn load_snippets(fs: &dyn Fs, path: &Path) -> Result<Vec<Arc<UserSnippet>>> {
+    let content = fs.load(path).await?;
+    let file: VsCodeSnippetsFile = settings::parse_json_with_comments(&content)?;
+    // The snippets of `<language id>.json` files always apply to that language.
+    let file_language = path
+        .extension()
+        .filter(|extension| extension.to_str() == Some("json"))
+        .and_then(|_| path.file_stem())
+        .map(|stem| stem.to_string_lossy().to_lowercase());
+
+    Ok(file
+        .snippets
+        .into_iter()
+        .map(|(name, snippet)| {
+            let languages = match (&file_language, snippet.scope) {
+                (Some(language), _) => Some(vec![language.clone()]),
+                (None, Some(scope)) => Some(
+                    scope
+                        .split(',')
+                        .map(|language| language.trim().to_lowercase())
+                        .filter(|language| !language.is_empty())
+                        .collect(),
+                ),
+                (None, None) => None,
+            };
+            Arc::new(UserSnippet {
+                prefixes: snippet
+                    .prefix
+                    .map(|prefix| prefix.into_vec())
+                    .unwrap_or_default(),
+                body: snippet.body.into_lines(),
+                description: snippet
+                    .description
+                    .map(|description| description.into_lines()),
+                name,
+                languages,
+            })
+        })
+        .collect())
+}
+
+/// Returns the id that VS Code uses for the language with the given name,
+/// which is what snippet files are named after and scoped to.
+pub fn vs_code_language_id(language_name: &str) -> String {
+    match language_name.to_lowercase().as_str() {
+        "c++" => "cpp".to_string(),
+        "c#" => "csharp".to_string(),
+        "shell script" => "shellscript".to_string(),
+        "tsx" => "typescriptreact".to_string(),
+        "plain text" => "plaintext".to_string(),
+        name => name.replace(' ', ""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::FakeFs;
+    use gpui::TestAppContext;
+    use serde_json::json;
+
+    #[gpui::test]
+    async fn test_loading_snippets(cx: &mut TestAppContext) {
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/snippets",
+            json!({
+                "rust.json": r#"{
+                    // Comments are allowed, like in VS Code.
+                    "Print": {
+                        "prefix": ["print", "pr"],
+                        "body": ["println!(\"$1\");", "$0"],
+                        "description": "Print a line"
+                    }
+                }"#,
+                "global.code-snippets": r#"{
+                    "Todo": { "prefix": "todo", "body": "TODO: $0" },
+                    "Log": { "prefix": "log", "body": "console.log($1)", "scope": "javascript, typescript" }
+                }"#,
+                "notes.txt": "not snippets",
+            }),
+        )
+        .await;
+
+        let provider = cx.new_model(|cx| SnippetProvider::new(fs, PathBuf::from("/snippets"), cx));
+        cx.run_until_parked();
+
+        provider.read_with(cx, |provider, _| {
+            let names = |language_id| {
+                provider
+                    .snippets_for(language_id)
+                    .iter()
+                    .map(|snippet| snippet.name.clone())
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(names(Some("rust")), ["Print", "Todo"]);
+            assert_eq!(names(Some("typescript")), ["Log", "Todo"]);
+            assert_eq!(names(None), ["Todo"]);
+
+            let print = &provider.snippets_for(Some("rust"))[0];
+            assert_eq!(print.prefixes, ["print", "pr"]);
+            assert_eq!(print.body, "println!(\"$1\");\n$0");
+            assert_eq!(print.description.as_deref(), Some("Print a line"));
+        });
+    }
+
+    #[test]
+    fn test_vs_code_language_id() {
+        assert_eq!(vs_code_language_id("Rust"), "rust");
+        assert_eq!(vs_code_language_id("C++"), "cpp");
+        assert_eq!(vs_code_language_id("Shell Script"), "shellscript");
+        assert_eq!(vs_code_language_id("TSX"), "typescriptreact");
+    }
+}
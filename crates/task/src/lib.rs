@@ -32,6 +32,13 @@ pub enum TerminalWorkDir {
         /// The path on the remote server
         path: Option<String>,
     },
+    /// Container runs the terminal inside of a running dev container
+    Container {
+        /// The id of the container to run in
+        container_id: String,
+        /// The path inside of the container
+        path: Option<String>,
+    },
 }
 
 impl TerminalWorkDir {
@@ -39,7 +46,7 @@ impl TerminalWorkDir {
     pub fn is_local(&self) -> bool {
         match self {
             Self::Local(_) => true,
-            Self::Ssh { .. } => false,
+            Self::Ssh { .. } | Self::Container { .. } => false,
         }
     }
 
@@ -47,7 +54,7 @@ impl TerminalWorkDir {
     pub fn local_path(&self) -> Option<&Path> {
         match self {
             Self::Local(path) => Some(path),
-            Self::Ssh { .. } => None,
+            Self::Ssh { .. } | Self::Container { .. } => None,
         }
     }
 }
@@ -396,7 +396,8 @@ impl TerminalPanel {
                 !use_new_terminal,
                 "Should have handled 'allow_concurrent_runs && use_new_terminal' case above"
             );
-            self.replace_terminal(spawn_task, existing_item_index, existing_terminal, cx);
+            self.replace_terminal(spawn_task, existing_item_index, existing_terminal, cx)
+                .detach_and_log_err(cx);
         } else {
             self.deferred_tasks.insert(
                 spawn_in_terminal.id.clone(),
@@ -409,12 +410,14 @@ impl TerminalPanel {
                                     .spawn_in_new_terminal(spawn_task, cx)
                                     .detach_and_log_err(cx);
                             } else {
-                                terminal_panel.replace_terminal(
-                                    spawn_task,
-                                    existing_item_index,
-                                    existing_terminal,
-                                    cx,
-                                );
+                                terminal_panel
+                                    .replace_terminal(
+                                        spawn_task,
+                                        existing_item_index,
+                                        existing_terminal,
+                                        cx,
+                                    )
+                                    .detach_and_log_err(cx);
                             }
                         })
                         .ok();
@@ -515,7 +518,7 @@ impl TerminalPanel {
 
         cx.spawn(|terminal_panel, mut cx| async move {
             let pane = terminal_panel.update(&mut cx, |this, _| this.pane.clone())?;
-            let result = workspace.update(&mut cx, |workspace, cx| {
+            let terminal = workspace.update(&mut cx, |workspace, cx| {
                 let working_directory = if let Some(working_directory) = working_directory {
                     Some(working_directory)
                 } else {
@@ -525,27 +528,32 @@ impl TerminalPanel {
                 };
 
                 let window = cx.window_handle();
-                let terminal = workspace.project().update(cx, |project, cx| {
+                workspace.project().update(cx, |project, cx| {
                     project.create_terminal(working_directory, spawn_task, window, cx)
-                })?;
-                let terminal_view = Box::new(cx.new_view(|cx| {
-                    TerminalView::new(
-                        terminal.clone(),
-                        workspace.weak_handle(),
-                        workspace.database_id(),
-                        cx,
-                    )
-                }));
-                pane.update(cx, |pane, cx| {
-                    let focus = pane.has_focus(cx);
-                    pane.add_item(terminal_view, true, focus, None, cx);
-                });
-
-                if reveal_strategy == RevealStrategy::Always {
-                    workspace.focus_panel::<Self>(cx);
-                }
-                Ok(terminal)
+                })
             })?;
+            let result = match terminal.await {
+                Ok(terminal) => workspace.update(&mut cx, |workspace, cx| {
+                    let terminal_view = Box::new(cx.new_view(|cx| {
+                        TerminalView::new(
+                            terminal.clone(),
+                            workspace.weak_handle(),
+                            workspace.database_id(),
+                            cx,
+                        )
+                    }));
+                    pane.update(cx, |pane, cx| {
+                        let focus = pane.has_focus(cx);
+                        pane.add_item(terminal_view, true, focus, None, cx);
+                    });
+
+                    if reveal_strategy == RevealStrategy::Always {
+                        workspace.focus_panel::<Self>(cx);
+                    }
+                    terminal
+                }),
+                Err(error) => Err(error),
+            };
             terminal_panel.update(&mut cx, |this, cx| {
                 this.pending_terminals_to_add = this.pending_terminals_to_add.saturating_sub(1);
                 this.serialize(cx)
@@ -604,38 +612,36 @@ impl TerminalPanel {
         terminal_item_index: usize,
         terminal_to_replace: View<TerminalView>,
         cx: &mut ViewContext<'_, Self>,
-    ) -> Option<()> {
-        let project = self
-            .workspace
-            .update(cx, |workspace, _| workspace.project().clone())
-            .ok()?;
+    ) -> Task<Result<()>> {
+        let workspace = self.workspace.clone();
+        let project = match workspace.update(cx, |workspace, _| workspace.project().clone()) {
+            Ok(project) => project,
+            Err(error) => return Task::ready(Err(error)),
+        };
 
         let reveal = spawn_task.reveal;
         let window = cx.window_handle();
         let new_terminal = project.update(cx, |project, cx| {
-            project
-                .create_terminal(spawn_task.cwd.clone(), Some(spawn_task), window, cx)
-                .log_err()
-        })?;
-        terminal_to_replace.update(cx, |terminal_to_replace, cx| {
-            terminal_to_replace.set_terminal(new_terminal, cx);
+            project.create_terminal(spawn_task.cwd.clone(), Some(spawn_task), window, cx)
         });
+        cx.spawn(|terminal_panel, mut cx| async move {
+            let new_terminal = new_terminal.await?;
+            terminal_to_replace.update(&mut cx, |terminal_to_replace, cx| {
+                terminal_to_replace.set_terminal(new_terminal, cx);
+            })?;
 
-        match reveal {
-            RevealStrategy::Always => {
-                self.activate_terminal_view(terminal_item_index, cx);
-                let task_workspace = self.workspace.clone();
-                cx.spawn(|_, mut cx| async move {
-                    task_workspace
-                        .update(&mut cx, |workspace, cx| workspace.focus_panel::<Self>(cx))
-                        .ok()
-                })
-                .detach();
+            match reveal {
+                RevealStrategy::Always => {
+                    terminal_panel.update(&mut cx, |terminal_panel, cx| {
+                        terminal_panel.activate_terminal_view(terminal_item_index, cx);
+                    })?;
+                    workspace.update(&mut cx, |workspace, cx| workspace.focus_panel::<Self>(cx))?;
+                }
+                RevealStrategy::Never => {}
             }
-            RevealStrategy::Never => {}
-        }
 
-        Some(())
+            Ok(())
+        })
     }
 
     pub fn pane(&self) -> &View<Pane> {
@@ -0,0 +1,59 @@
+use collections::HashMap;
+use serde::Deserialize;
+
+/// A grammar, as found in `.tmLanguage.json` files and, in the property list format, `.tmLanguage` files.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RawGrammar {
+    pub(crate) name: Option<String>,
+    pub(crate) scope_name: String,
+    #[serde(default)]
+    pub(crate) file_types: Vec<String>,
+    #[serde(default)]
+    pub(crate) patterns: Vec<RawRule>,
+    #[serde(default)]
+    pub(crate) repository: HashMap<String, RawRule>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RawRule {
+    pub(crate) include: Option<String>,
+    #[serde(rename = "match")]
+    pub(crate) match_: Option<String>,
+    pub(crate) begin: Option<String>,
+    pub(crate) end: Option<String>,
+    pub(crate) name: Option<String>,
+    pub(crate) content_name: Option<String>,
+    pub(crate) captures: Option<HashMap<String, RawCapture>>,
+    pub(crate) begin_captures: Option<HashMap<String, RawCapture>>,
+    pub(crate) end_captures: Option<HashMap<String, RawCapture>>,
+    #[serde(default)]
+    pub(crate) patterns: Vec<RawRule>,
+    pub(crate) repository: Option<HashMap<String, RawRule>>,
+    pub(crate) apply_end_pattern_last: Option<Flag>,
+    pub(crate) disabled: Option<Flag>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub(crate) struct RawCapture {
+    pub(crate) name: Option<String>,
+}
+
+/// A boolean, which property list grammars usually write as an integer.
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(untagged)]
+pub(crate) enum Flag {
+    Bool(bool),
+    Integer(i64),
+}
+
+impl Flag {
+    pub(crate) fn is_set(flag: Option<Self>) -> bool {
+        match flag {
+            Some(Self::Bool(value)) => value,
+            Some(Self::Integer(value)) => value != 0,
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,811 @@
+//! An interpreter for [TextMate grammars](https://macromates.com/manual/en/language_grammars),
+//! as used by TextMate, Sublime Text and VS Code.
+//!
+//! Grammars assign a stack of scope names, like `source.zig string.quoted.double.zig`, to the text
+//! of every line. Zed uses them to highlight languages that no Tree-sitter grammar is available for.
+//!
+//! The following parts of the format are not supported:
+//! - including other grammars, like `source.js`,
+//! - `while` rules, which match their `begin` pattern once instead,
+//! - the patterns of captures, and back references in scope names,
+//! - the `\G` anchor, which is ignored.
+
+mod raw;
+
+use anyhow::{Context as _, Result};
+use collections::{HashMap, HashSet};
+use fancy_regex::Regex;
+use std::{cmp::Reverse, ops::Range, sync::Arc};
+
+use crate::raw::{Flag, RawCapture, RawGrammar, RawRule};
+
+/// How many times the tokenizer may push or pop a rule without consuming any text,
+/// before skipping a character to guarantee progress.
+const MAX_STALLED_MATCHES: usize = 16;
+
+/// A scope name, like `comment.line.double-slash`, interned by its [`Grammar`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ScopeId(pub u32);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct RuleId(usize);
+
+/// A compiled TextMate grammar.
+pub struct Grammar {
+    name: String,
+    scope_name: String,
+    file_types: Vec<String>,
+    scopes: Vec<String>,
+    rules: Vec<Rule>,
+    /// The rules that can match inside of each rule, with includes resolved.
+    candidates: Vec<Arc<[RuleId]>>,
+    root: RuleId,
+    root_scope: ScopeId,
+}
+
+enum Rule {
+    Match {
+        regex: Option<Arc<Regex>>,
+        scopes: Vec<ScopeId>,
+        captures: Captures,
+    },
+    BeginEnd {
+        begin: Option<Arc<Regex>>,
+        end: EndPattern,
+        scopes: Vec<ScopeId>,
+        content_scopes: Vec<ScopeId>,
+        begin_captures: Captures,
+        end_captures: Captures,
+        patterns: Vec<RuleId>,
+        apply_end_pattern_last: bool,
+    },
+    /// A list of rules, like the top level of a grammar, or an entry of a repository
+    /// that only includes other rules.
+    Patterns(Vec<RuleId>),
+}
+
+type Captures = Vec<(usize, Vec<ScopeId>)>;
+
+enum EndPattern {
+    Static(Option<Arc<Regex>>),
+    /// An end pattern that refers to the groups of the begin pattern, like `\1`,
+    /// which is compiled when the begin pattern matches.
+    BackReferences(String),
+}
+
+/// The rules that are open at the end of a line, which the next line is tokenized in.
+#[derive(Clone)]
+pub struct LineState {
+    stack: Vec<Frame>,
+}
+
+#[derive(Clone)]
+struct Frame {
+    rule: RuleId,
+    end: Option<Arc<Regex>>,
+    /// The scopes of the text inside of the rule.
+    scopes: Vec<ScopeId>,
+    /// How many of the scopes apply to the begin and end matches too, excluding the content name.
+    name_scopes_len: usize,
+}
+
+/// The groups of a match, by their index.
+type Groups = Vec<Option<Range<usize>>>;
+
+enum MatchKind {
+    End,
+    Rule(RuleId),
+}
+
+impl Grammar {
+    /// Parses a grammar in the JSON format, as used by `.tmLanguage.json` files.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let raw = serde_json::from_str::<RawGrammar>(json).context("invalid TextMate grammar")?;
+        Ok(Self::compile(raw))
+    }
+
+    /// Parses a grammar in the property list format, as used by `.tmLanguage` files.
+    pub fn from_plist(plist: &[u8]) -> Result<Self> {
+        let raw = plist::from_bytes::<RawGrammar>(plist).context("invalid TextMate grammar")?;
+        Ok(Self::compile(raw))
+    }
+
+    /// The name of the language, falling back to the scope name for grammars without a name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The scope name of the whole grammar, like `source.zig`.
+    pub fn scope_name(&self) -> &str {
+        &self.scope_name
+    }
+
+    /// The file extensions and names of the files that the grammar applies to.
+    pub fn file_types(&self) -> &[String] {
+        &self.file_types
+    }
+
+    /// All of the scope names used by the grammar, indexed by their [`ScopeId`].
+    pub fn scope_names(&self) -> &[String] {
+        &self.scopes
+    }
+
+    /// The state of the first line of a file.
+    pub fn initial_state(&self) -> LineState {
+        LineState {
+            stack: vec![Frame {
+                rule: self.root,
+                end: None,
+                scopes: vec![self.root_scope],
+                name_scopes_len: 1,
+            }],
+        }
+    }
+
+    /// Tokenizes a line of text, including its trailing newline, and updates the given
+    /// state to be the state of the next line.
+    ///
+    /// The callback is invoked with consecutive ranges of the line, along with their scopes,
+    /// from the outermost to the innermost one.
+    pub fn tokenize_line(
+        &self,
+        line: &str,
+        state: &mut LineState,
+        mut emit: impl FnMut(Range<usize>, &[ScopeId]),
+    ) {
+        let mut search_cache = HashMap::default();
+        let mut position = 0;
+        let mut stalled_matches = 0;
+        while position < line.len() {
+            let frame = state.stack.last().expect("the root frame is never popped");
+            let Some((kind, groups)) = self.next_match(line, position, frame, &mut search_cache)
+            else {
+                emit(position..line.len(), &frame.scopes);
+                break;
+            };
+            let match_range = groups[0].clone().unwrap_or(position..position);
+            if match_range.start > position {
+                emit(position..match_range.start, &frame.scopes);
+            }
+
+            let mut pushed_or_popped = false;
+            match kind {
+                MatchKind::End => {
+                    if let Rule::BeginEnd { end_captures, .. } = &self.rules[frame.rule.0] {
+                        let mut scopes = frame.scopes[..frame.name_scopes_len].to_vec();
+                        emit_captures(&match_range, &groups, end_captures, &mut scopes, &mut emit);
+                    }
+                    if state.stack.len() > 1 {
+                        state.stack.pop();
+                        pushed_or_popped = true;
+                    }
+                }
+                MatchKind::Rule(rule_id) => match &self.rules[rule_id.0] {
+                    Rule::Match {
+                        scopes, captures, ..
+                    } => {
+                        let mut match_scopes = frame.scopes.clone();
+                        match_scopes.extend(scopes);
+                        emit_captures(
+                            &match_range,
+                            &groups,
+                            captures,
+                            &mut match_scopes,
+                            &mut emit,
+                        );
+                    }
+                    Rule::BeginEnd {
+                        end,
+                        scopes,
+                        content_scopes,
+                        begin_captures,
+                        ..
+                    } => {
+                        let mut name_scopes = frame.scopes.clone();
+                        name_scopes.extend(scopes);
+                        emit_captures(
+                            &match_range,
+                            &groups,
+                            begin_captures,
+                            &mut name_scopes,
+                            &mut emit,
+                        );
+                        let end = match end {
+                            EndPattern::Static(regex) => regex.clone(),
+                            EndPattern::BackReferences(source) => {
+                                compile_regex(&substitute_back_references(source, line, &groups))
+                            }
+                        };
+                        let name_scopes_len = name_scopes.len();
+                        let mut frame_scopes = name_scopes;
+                        frame_scopes.extend(content_scopes);
+                        state.stack.push(Frame {
+                            rule: rule_id,
+                            end,
+                            scopes: frame_scopes,
+                            name_scopes_len,
+                        });
+                        pushed_or_popped = true;
+                    }
+                    Rule::Patterns(_) => {}
+                },
+            }
+
+            if match_range.end > position {
+                position = match_range.end;
+                stalled_matches = 0;
+            } else if !pushed_or_popped || stalled_matches >= MAX_STALLED_MATCHES {
+                // Skip a character, as empty matches would otherwise match forever.
+                let frame = state.stack.last().expect("the root frame is never popped");
+                let next_position = position
+                    + line[position..]
+                        .chars()
+                        .next()
+                        .map_or(1, |character| character.len_utf8());
+                emit(position..next_position, &frame.scopes);
+                position = next_position;
+                stalled_matches = 0;
+            } else {
+                stalled_matches += 1;
+            }
+        }
+    }
+
+    /// Finds the earliest match of the rules that can match inside of the given frame.
+    /// Of matches at the same position, the end pattern wins, and then the first rule.
+    fn next_match(
+        &self,
+        line: &str,
+        position: usize,
+        frame: &Frame,
+        search_cache: &mut HashMap<RuleId, Option<Groups>>,
+    ) -> Option<(MatchKind, Groups)> {
+        let apply_end_pattern_last = matches!(
+            self.rules[frame.rule.0],
+            Rule::BeginEnd {
+                apply_end_pattern_last: true,
+                ..
+            }
+        );
+
+        let mut best: Option<(MatchKind, Groups)> = None;
+        let mut consider = |kind: MatchKind, groups: Groups| {
+            let start = groups[0].as_ref().map_or(usize::MAX, |range| range.start);
+            let best_start = best
+                .as_ref()
+                .and_then(|(_, groups)| groups[0].as_ref())
+                .map_or(usize::MAX, |range| range.start);
+            if start < best_start {
+                best = Some((kind, groups));
+            }
+        };
+
+        let end_groups = frame
+            .end
+            .as_ref()
+            .and_then(|end| search(end, line, position));
+        let mut end_groups = end_groups.map(|groups| (MatchKind::End, groups));
+        if !apply_end_pattern_last {
+            if let Some((kind, groups)) = end_groups.take() {
+                consider(kind, groups);
+            }
+        }
+
+        for &rule_id in self.candidates[frame.rule.0].iter() {
+            let regex = match &self.rules[rule_id.0] {
+                Rule::Match { regex, .. } => regex,
+                Rule::BeginEnd { begin, .. } => begin,
+                Rule::Patterns(_) => continue,
+            };
+            let Some(regex) = regex else {
+                continue;
+            };
+
+            // A match after the current position stays valid, and a pattern that didn't
+            // match further to the left of the line won't match here either.
+            let cached = match search_cache.get(&rule_id) {
+                Some(Some(groups))
+                    if groups[0]
+                        .as_ref()
+                        .map_or(true, |range| range.start < position) =>
+                {
+                    None
+                }
+                cached => cached.cloned(),
+            };
+            let groups = match cached {
+                Some(groups) => groups,
+                None => {
+                    let groups = search(regex, line, position);
+                    search_cache.insert(rule_id, groups.clone());
+                    groups
+                }
+            };
+            if let Some(groups) = groups {
+                consider(MatchKind::Rule(rule_id), groups);
+            }
+        }
+
+        if let Some((kind, groups)) = end_groups {
+            consider(kind, groups);
+        }
+        best
+    }
+
+    fn compile(raw: RawGrammar) -> Self {
+        let mut compiler = Compiler {
+            rules: vec![Rule::Patterns(Vec::new())],
+            scopes: Vec::new(),
+            scope_ids: HashMap::default(),
+            compiled_rules: HashMap::default(),
+        };
+        let root = RuleId(0);
+        let root_patterns = compiler.compile_patterns(&raw.patterns, &[&raw.repository]);
+        compiler.rules[root.0] = Rule::Patterns(root_patterns);
+        let root_scope = compiler.scope(&raw.scope_name);
+
+        let candidates = (0..compiler.rules.len())
+            .map(|rule_ix| {
+                let patterns = match &compiler.rules[rule_ix] {
+                    Rule::Patterns(patterns) | Rule::BeginEnd { patterns, .. } => patterns,
+                    Rule::Match { .. } => return Vec::new().into(),
+                };
+                let mut candidates = Vec::new();
+                let mut visited = HashSet::default();
+                collect_candidates(&compiler.rules, patterns, &mut candidates, &mut visited);
+                candidates.into()
+            })
+            .collect();
+
+        Self {
+            name: raw.name.unwrap_or_else(|| raw.scope_name.clone()),
+            scope_name: raw.scope_name,
+            file_types: raw.file_types,
+            scopes: compiler.scopes,
+            rules: compiler.rules,
+            candidates,
+            root,
+            root_scope,
+        }
+    }
+}
+
+struct Compiler {
+    rules: Vec<Rule>,
+    scopes: Vec<String>,
+    scope_ids: HashMap<String, ScopeId>,
+    /// The rules that have been compiled, or are being compiled, by their raw rule.
+    /// As rules can include themselves, they are reserved before being compiled.
+    compiled_rules: HashMap<*const RawRule, RuleId>,
+}
+
+impl Compiler {
+    fn compile_patterns<'a>(
+        &mut self,
+        patterns: &'a [RawRule],
+        repositories: &[&'a HashMap<String, RawRule>],
+    ) -> Vec<RuleId> {
+        patterns
+            .iter()
+            .filter(|pattern| !Flag::is_set(pattern.disabled))
+            .filter_map(|pattern| self.compile_rule(pattern, repositories))
+            .collect()
+    }
+
+    fn compile_rule<'a>(
+        &mut self,
+        raw: &'a RawRule,
+        repositories: &[&'a HashMap<String, RawRule>],
+    ) -> Option<RuleId> {
+        if let Some(include) = &raw.include {
+            return self.resolve_include(include, repositories);
+        }
+        if let Some(rule_id) = self.compiled_rules.get(&(raw as *const RawRule)) {
+            return Some(*rule_id);
+        }
+        let rule_id = RuleId(self.rules.len());
+        self.rules.push(Rule::Patterns(Vec::new()));
+        self.compiled_rules.insert(raw as *const RawRule, rule_id);
+
+        let mut repositories = repositories.to_vec();
+        if let Some(repository) = &raw.repository {
+            repositories.push(repository);
+        }
+
+        let scopes = self.scopes(raw.name.as_deref());
+        let rule = match (&raw.match_, &raw.begin, &raw.end) {
+            (_, Some(begin), Some(end)) => Rule::BeginEnd {
+                begin: compile_regex(begin),
+                end: if has_back_references(end) {
+                    EndPattern::BackReferences(end.clone())
+                } else {
+                    EndPattern::Static(compile_regex(end))
+                },
+                scopes,
+                content_scopes: self.scopes(raw.content_name.as_deref()),
+                begin_captures: self
+                    .captures(raw.begin_captures.as_ref().or(raw.captures.as_ref())),
+                end_captures: self.captures(raw.end_captures.as_ref().or(raw.captures.as_ref())),
+                patterns: self.compile_patterns(&raw.patterns, &repositories),
+                apply_end_pattern_last: Flag::is_set(raw.apply_end_pattern_last),
+            },
+            (_, Some(begin), None) => Rule::Match {
+                regex: compile_regex(begin),
+                scopes,
+                captures: self.captures(raw.begin_captures.as_ref().or(raw.captures.as_ref())),
+            },
+            (Some(pattern), None, _) => Rule::Match {
+                regex: compile_regex(pattern),
+                scopes,
+                captures: self.captures(raw.captures.as_ref()),
+            },
+            (None, None, _) => Rule::Patterns(self.compile_patterns(&raw.patterns, &repositories)),
+        };
+        self.rules[rule_id.0] = rule;
+        Some(rule_id)
+    }
+
+    fn resolve_include(
+        &mut self,
+        include: &str,
+        repositories: &[&HashMap<String, RawRule>],
+    ) -> Option<RuleId> {
+        if include == "$self" || include == "$base" {
+            return Some(RuleId(0));
+        }
+        let Some(name) = include.strip_prefix('#') else {
+            log::debug!("including other TextMate grammars is not supported: {include}");
+            return None;
+        };
+        // Rules are resolved in the repositories surrounding them, from the innermost one.
+        let (depth, raw) = repositories
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(depth, repository)| Some((depth, repository.get(name)?)))?;
+        self.compile_rule(raw, &repositories[..=depth])
+    }
+
+    fn scopes(&mut self, names: Option<&str>) -> Vec<ScopeId> {
+        names
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(|name| self.scope(name))
+            .collect()
+    }
+
+    fn scope(&mut self, name: &str) -> ScopeId {
+        if let Some(scope_id) = self.scope_ids.get(name) {
+            return *scope_id;
+        }
+        let scope_id = ScopeId(self.scopes.len() as u32);
+        self.scopes.push(name.to_string());
+        self.scope_ids.insert(name.to_string(), scope_id);
+        scope_id
+    }
+
+    fn captures(&mut self, captures: Option<&HashMap<String, RawCapture>>) -> Captures {
+        let mut captures = captures
+            .into_iter()
+            .flatten()
+            .filter_map(|(index, capture)| {
+                let index = index.parse().ok()?;
+                let scopes = self.scopes(capture.name.as_deref());
+                (!scopes.is_empty()).then_some((index, scopes))
+            })
+            .collect::<Captures>();
+        captures.sort_by_key(|(index, _)| *index);
+        captures
+    }
+}
+
+fn collect_candidates(
+    rules: &[Rule],
+    patterns: &[RuleId],
+    candidates: &mut Vec<RuleId>,
+    visited: &mut HashSet<RuleId>,
+) {
+    for rule_id in patterns {
+        if !visited.insert(*rule_id) {
+            continue;
+        }
+        match &rules[rule_id.0] {
+            Rule::Patterns(patterns) => collect_candidates(rules, patterns, candidates, visited),
+            Rule::Match { .. } | Rule::BeginEnd { .. } => candidates.push(*rule_id),
+        }
+    }
+}
+
+/// Emits the text of a match, with the scopes of its groups. Groups can be nested inside of
+/// other groups, in which case their scopes are added to the scopes of the outer groups.
+fn emit_captures(
+    range: &Range<usize>,
+    groups: &Groups,
+    captures: &Captures,
+    scopes: &mut Vec<ScopeId>,
+    emit: &mut dyn FnMut(Range<usize>, &[ScopeId]),
+) {
+    if range.is_empty() {
+        return;
+    }
+    let scopes_len = scopes.len();
+    let mut nested_captures = Vec::new();
+    for (index, capture_scopes) in captures {
+        if *index == 0 {
+            scopes.extend(capture_scopes);
+        } else if let Some(Some(group)) = groups.get(*index) {
+            if !group.is_empty() && range.start <= group.start && group.end <= range.end {
+                nested_captures.push((group.clone(), capture_scopes.as_slice()));
+            }
+        }
+    }
+    nested_captures.sort_by_key(|(group, _)| (group.start, Reverse(group.end)));
+    emit_nested_captures(range.clone(), &nested_captures, scopes, emit);
+    scopes.truncate(scopes_len);
+}
+
+fn emit_nested_captures(
+    range: Range<usize>,
+    captures: &[(Range<usize>, &[ScopeId])],
+    scopes: &mut Vec<ScopeId>,
+    emit: &mut dyn FnMut(Range<usize>, &[ScopeId]),
+) {
+    let mut position = range.start;
+    let mut ix = 0;
+    while ix < captures.len() {
+        let (group, capture_scopes) = &captures[ix];
+        // Groups that overlap the previous group without being nested in it are ignored.
+        if group.start < position || group.end > range.end {
+            ix += 1;
+            continue;
+        }
+        if group.start > position {
+            emit(position..group.start, scopes);
+        }
+        let nested_end = captures[ix + 1..]
+            .iter()
+            .position(|(nested_group, _)| nested_group.start >= group.end)
+            .map_or(captures.len(), |nested_ix| ix + 1 + nested_ix);
+        let scopes_len = scopes.len();
+        scopes.extend(capture_scopes.iter());
+        emit_nested_captures(group.clone(), &captures[ix + 1..nested_end], scopes, emit);
+        scopes.truncate(scopes_len);
+        position = group.end;
+        ix = nested_end;
+    }
+    if position < range.end {
+        emit(position..range.end, scopes);
+    }
+}
+
+fn search(regex: &Regex, line: &str, position: usize) -> Option<Groups> {
+    let captures = regex.captures_from_pos(line, position).ok()??;
+    Some(
+        (0..captures.len())
+            .map(|ix| captures.get(ix).map(|group| group.start()..group.end()))
+            .collect(),
+    )
+}
+
+fn compile_regex(source: &str) -> Option<Arc<Regex>> {
+    // Like in Oniguruma, `^` and `$` match at the start and end of the line.
+    match Regex::new(&format!("(?m){}", translate_regex(source))) {
+        Ok(regex) => Some(Arc::new(regex)),
+        Err(error) => {
+            log::warn!("unsupported pattern in TextMate grammar {source:?}: {error}");
+            None
+        }
+    }
+}
+
+/// Translates the parts of the Oniguruma syntax used by TextMate grammars
+/// that `fancy_regex` doesn't support.
+fn translate_regex(source: &str) -> String {
+    let mut translated = String::with_capacity(source.len());
+    let mut in_class = false;
+    let mut chars = source.chars().peekable();
+    while let Some(character) = chars.next() {
+        match character {
+            '\\' => match chars.next() {
+                Some('h') if in_class => translated.push_str("0-9a-fA-F"),
+                Some('h') => translated.push_str("[0-9a-fA-F]"),
+                Some('H') if !in_class => translated.push_str("[^0-9a-fA-F]"),
+                Some('G') => {}
+                Some('Z') => translated.push_str(r"(?=\n?\z)"),
+                Some(escaped) => {
+                    translated.push('\\');
+                    translated.push(escaped);
+                }
+                None => translated.push('\\'),
+            },
+            '[' if !in_class => {
+                in_class = true;
+                translated.push('[');
+                // A closing bracket right after the opening one is part of the class.
+                if chars.peek() == Some(&'^') {
+                    translated.push(chars.next().unwrap());
+                }
+                if chars.peek() == Some(&']') {
+                    chars.next();
+                    translated.push_str(r"\]");
+                }
+            }
+            ']' if in_class => {
+                in_class = false;
+                translated.push(']');
+            }
+            _ => translated.push(character),
+        }
+    }
+    translated
+}
+
+fn has_back_references(source: &str) -> bool {
+    let mut chars = source.chars();
+    while let Some(character) = chars.next() {
+        if character == '\\' && chars.next().map_or(false, |next| next.is_ascii_digit()) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Replaces the back references of an end pattern with the text of the begin pattern's groups.
+fn substitute_back_references(source: &str, line: &str, groups: &Groups) -> String {
+    let mut substituted = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    while let Some(character) = chars.next() {
+        if character != '\\' {
+            substituted.push(character);
+            continue;
+        }
+        match chars.peek().and_then(|next| next.to_digit(10)) {
+            Some(index) => {
+                chars.next();
+                if let Some(Some(group)) = groups.get(index as usize) {
+                    substituted.push_str(&fancy_regex::escape(&line[group.clone()]));
+                }
+            }
+            None => {
+                substituted.push('\\');
+                if let Some(escaped) = chars.next() {
+                    substituted.push(escaped);
+                }
+            }
+        }
+    }
+    substituted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRAMMAR: &str = r##"{
+        "name": "Example",
+        "scopeName": "source.example",
+        "fileTypes": ["example"],
+        "patterns": [
+            { "include": "#comments" },
+            { "include": "#strings" },
+            {
+                "match": "\\b(fn)\\s+(\\w+)",
+                "captures": {
+                    "1": { "name": "keyword.other.fn" },
+                    "2": { "name": "entity.name.function" }
+                }
+            },
+            {
+                "begin": "<<(\\w+)",
+                "end": "^\\1$",
+                "name": "string.unquoted.heredoc"
+            }
+        ],
+        "repository": {
+            "comments": {
+                "patterns": [
+                    { "match": "//.*$", "name": "comment.line.double-slash" }
+                ]
+            },
+            "strings": {
+                "begin": "\"",
+                "end": "\"",
+                "name": "string.quoted.double",
+                "patterns": [{ "match": "\\\\.", "name": "constant.character.escape" }]
+            }
+        }
+    }"##;
+
+    #[test]
+    fn test_tokenize() {
+        let grammar = Grammar::from_json(GRAMMAR).unwrap();
+        assert_eq!(grammar.name(), "Example");
+        assert_eq!(grammar.file_types(), ["example"]);
+
+        assert_eq!(
+            tokenize(&grammar, "fn main \"a\\nb\" // done\n"),
+            [
+                ("fn", "keyword.other.fn"),
+                (" ", "source.example"),
+                ("main", "entity.name.function"),
+                (" ", "source.example"),
+                ("\"", "string.quoted.double"),
+                ("a", "string.quoted.double"),
+                ("\\n", "constant.character.escape"),
+                ("b", "string.quoted.double"),
+                ("\"", "string.quoted.double"),
+                (" ", "source.example"),
+                ("// done", "comment.line.double-slash"),
+                ("\n", "source.example"),
+            ]
+        );
+
+        // Open rules continue on the next lines, until their end pattern matches,
+        // which can refer to the text that the begin pattern matched.
+        assert_eq!(
+            tokenize(&grammar, "<<EOF\nfn x\nEOF\nfn"),
+            [
+                ("<<EOF", "string.unquoted.heredoc"),
+                ("\n", "string.unquoted.heredoc"),
+                ("fn x\n", "string.unquoted.heredoc"),
+                ("EOF", "string.unquoted.heredoc"),
+                ("\n", "source.example"),
+                ("fn", "source.example"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plist_grammar() {
+        let plist = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <plist version="1.0">
+            <dict>
+                <key>scopeName</key>
+                <string>source.example</string>
+                <key>patterns</key>
+                <array>
+                    <dict>
+                        <key>match</key>
+                        <string>\h+</string>
+                        <key>name</key>
+                        <string>constant.numeric</string>
+                    </dict>
+                </array>
+            </dict>
+            </plist>"#;
+        let grammar = Grammar::from_plist(plist.as_bytes()).unwrap();
+        assert_eq!(grammar.name(), "source.example");
+        assert_eq!(
+            tokenize(&grammar, "x ff"),
+            [("x ", "source.example"), ("ff", "constant.numeric")]
+        );
+    }
+
+    #[test]
+    fn test_translate_regex() {
+        assert_eq!(translate_regex(r"\h+"), "[0-9a-fA-F]+");
+        assert_eq!(translate_regex(r"[\h_]"), "[0-9a-fA-F_]");
+        assert_eq!(translate_regex(r"\G\s*[]x]"), r"\s*[\]x]");
+        assert_eq!(
+            substitute_back_references(r"^\1\.", "a+b", &vec![Some(0..3), Some(0..3)]),
+            r"^a\+b\."
+        );
+    }
+
+    /// Returns the text of each token, along with its innermost scope.
+    fn tokenize<'a>(grammar: &'a Grammar, text: &'a str) -> Vec<(&'a str, &'a str)> {
+        let mut state = grammar.initial_state();
+        let mut tokens = Vec::new();
+        for line in text.split_inclusive('\n') {
+            let line_start = line.as_ptr() as usize - text.as_ptr() as usize;
+            grammar.tokenize_line(line, &mut state, |range, scopes| {
+                let scope = scopes.last().unwrap();
+                tokens.push((
+                    &text[line_start + range.start..line_start + range.end],
+                    grammar.scope_names()[scope.0 as usize].as_str(),
+                ));
+            });
+        }
+        tokens
+    }
+}
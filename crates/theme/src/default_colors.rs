@@ -64,6 +64,10 @@ impl ThemeColors {
             scrollbar_thumb_border: gpui::transparent_black(),
             scrollbar_track_background: gpui::transparent_black(),
             scrollbar_track_border: neutral().light().step_5(),
+            feedback_counter_text: neutral().light().step_11(),
+            feedback_error_banner_background: red().light().step_3(),
+            feedback_submit_disabled_background: neutral().light().step_3(),
+            feedback_category_picker_background: neutral().light().step_3(),
             editor_foreground: neutral().light().step_12(),
             editor_background: neutral().light().step_1(),
             editor_gutter_background: neutral().light().step_1(),
@@ -161,6 +165,10 @@ impl ThemeColors {
             scrollbar_thumb_border: gpui::transparent_black(),
             scrollbar_track_background: gpui::transparent_black(),
             scrollbar_track_border: neutral().dark().step_5(),
+            feedback_counter_text: neutral().dark().step_11(),
+            feedback_error_banner_background: red().dark().step_3(),
+            feedback_submit_disabled_background: neutral().dark().step_3(),
+            feedback_category_picker_background: neutral().dark().step_3(),
             editor_foreground: neutral().dark().step_12(),
             editor_background: neutral().dark().step_1(),
             editor_gutter_background: neutral().dark().step_1(),
@@ -140,6 +140,10 @@ pub(crate) fn one_dark() -> Theme {
                 scrollbar_thumb_border: hsla(228. / 360., 8. / 100., 25. / 100., 1.),
                 scrollbar_track_background: gpui::transparent_black(),
                 scrollbar_track_border: hsla(228. / 360., 8. / 100., 25. / 100., 1.),
+                feedback_counter_text: hsla(218. / 360., 14. / 100., 71. / 100., 1.),
+                feedback_error_banner_background: hsla(1. / 360., 67. / 100., 22. / 100., 1.),
+                feedback_submit_disabled_background: hsla(222. / 360., 12. / 100., 34. / 100., 1.),
+                feedback_category_picker_background: hsla(223. / 360., 13. / 100., 21. / 100., 1.),
                 editor_foreground: hsla(218. / 360., 14. / 100., 71. / 100., 1.),
                 link_text_hover: blue,
             },
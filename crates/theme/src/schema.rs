@@ -354,6 +354,22 @@ pub struct ThemeColorsContent {
     #[serde(rename = "scrollbar.track.border")]
     pub scrollbar_track_border: Option<String>,
 
+    /// Text Color. Used for the character count of the feedback being written.
+    #[serde(rename = "feedback.counter.text")]
+    pub feedback_counter_text: Option<String>,
+
+    /// The background color of the banner shown when feedback fails to submit.
+    #[serde(rename = "feedback.error_banner.background")]
+    pub feedback_error_banner_background: Option<String>,
+
+    /// The background color of the button that submits feedback, while the feedback can't be submitted.
+    #[serde(rename = "feedback.submit.disabled_background")]
+    pub feedback_submit_disabled_background: Option<String>,
+
+    /// The background color of the pickers for the category and severity of feedback.
+    #[serde(rename = "feedback.category_picker.background")]
+    pub feedback_category_picker_background: Option<String>,
+
     #[serde(rename = "editor.foreground")]
     pub editor_foreground: Option<String>,
 
@@ -725,6 +741,22 @@ impl ThemeColorsContent {
                 .scrollbar_track_border
                 .as_ref()
                 .and_then(|color| try_parse_color(color).ok()),
+            feedback_counter_text: self
+                .feedback_counter_text
+                .as_ref()
+                .and_then(|color| try_parse_color(color).ok()),
+            feedback_error_banner_background: self
+                .feedback_error_banner_background
+                .as_ref()
+                .and_then(|color| try_parse_color(color).ok()),
+            feedback_submit_disabled_background: self
+                .feedback_submit_disabled_background
+                .as_ref()
+                .and_then(|color| try_parse_color(color).ok()),
+            feedback_category_picker_background: self
+                .feedback_category_picker_background
+                .as_ref()
+                .and_then(|color| try_parse_color(color).ok()),
             editor_foreground: self
                 .editor_foreground
                 .as_ref()
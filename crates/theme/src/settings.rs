@@ -20,6 +20,8 @@ use util::ResultExt as _;
 
 const MIN_FONT_SIZE: Pixels = px(6.0);
 const MIN_LINE_HEIGHT: f32 = 1.0;
+const MIN_UI_SCALE: f32 = 0.5;
+const MAX_UI_SCALE: f32 = 3.0;
 
 #[derive(
     Debug,
@@ -80,17 +82,44 @@ impl Into<String> for UiDensity {
     }
 }
 
+/// Whether to skip animations throughout the UI.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReduceMotion {
+    /// Follow the operating system's "reduce motion" preference.
+    #[default]
+    System,
+    /// Always skip animations.
+    On,
+    /// Always play animations.
+    Off,
+}
+
+impl ReduceMotion {
+    /// Returns the override to apply to the platform's preference, if any.
+    pub fn override_value(self) -> Option<bool> {
+        match self {
+            ReduceMotion::System => None,
+            ReduceMotion::On => Some(true),
+            ReduceMotion::Off => Some(false),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct ThemeSettings {
     pub ui_font_size: Pixels,
+    pub ui_scale: f32,
     pub ui_font: Font,
     pub buffer_font: Font,
     pub buffer_font_size: Pixels,
     pub buffer_line_height: BufferLineHeight,
+    pub buffer_letter_spacing: f32,
     pub theme_selection: Option<ThemeSelection>,
     pub active_theme: Arc<Theme>,
     pub theme_overrides: Option<ThemeStyleContent>,
     pub ui_density: UiDensity,
+    pub reduce_motion: ReduceMotion,
 }
 
 impl ThemeSettings {
@@ -172,6 +201,11 @@ pub(crate) struct AdjustedUiFontSize(Pixels);
 
 impl Global for AdjustedUiFontSize {}
 
+#[derive(Default)]
+pub(crate) struct AdjustedUiScale(f32);
+
+impl Global for AdjustedUiScale {}
+
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum ThemeSelection {
@@ -226,6 +260,9 @@ pub struct ThemeSettingsContent {
     /// The default font size for text in the UI.
     #[serde(default)]
     pub ui_font_size: Option<f32>,
+    /// How much to scale the whole UI, including text buffers, from 0.5 to 3.
+    #[serde(default)]
+    pub ui_scale: Option<f32>,
     /// The name of a font to use for rendering in the UI.
     #[serde(default)]
     pub ui_font_family: Option<String>,
@@ -247,6 +284,9 @@ pub struct ThemeSettingsContent {
     /// The buffer's line height.
     #[serde(default)]
     pub buffer_line_height: Option<BufferLineHeight>,
+    /// The space to add after each character in text buffers, as a fraction of the buffer font size.
+    #[serde(default)]
+    pub buffer_letter_spacing: Option<f32>,
     /// The OpenType features to enable for rendering in text buffers.
     #[serde(default)]
     pub buffer_font_features: Option<FontFeatures>,
@@ -260,6 +300,10 @@ pub struct ThemeSettingsContent {
     #[serde(rename = "unstable.ui_density", default)]
     pub ui_density: Option<UiDensity>,
 
+    /// Whether to skip animations, like spinners and the blinking of the cursor.
+    #[serde(default)]
+    pub reduce_motion: Option<ReduceMotion>,
+
     /// EXPERIMENTAL: Overrides for the current theme.
     ///
     /// These values will override the ones on the current theme specified in `theme`.
@@ -297,6 +341,10 @@ impl ThemeSettings {
         f32::max(self.buffer_line_height.value(), MIN_LINE_HEIGHT)
     }
 
+    pub fn buffer_letter_spacing(&self, cx: &AppContext) -> Pixels {
+        self.buffer_font_size(cx) * self.buffer_letter_spacing.max(0.)
+    }
+
     /// Switches to the theme with the given name, if it exists.
     ///
     /// Returns a `Some` containing the new theme if it was successful.
@@ -396,9 +444,20 @@ pub fn setup_ui_font(cx: &mut WindowContext) -> gpui::Font {
     };
 
     cx.set_rem_size(ui_font_size);
+    cx.set_ui_scale(get_ui_scale(cx));
+    cx.set_reduce_motion(ThemeSettings::get_global(cx).reduce_motion.override_value());
     ui_font
 }
 
+/// Returns whether animations should be skipped, taking both the `reduce_motion`
+/// setting and the operating system's preference into account.
+pub fn should_reduce_motion(cx: &AppContext) -> bool {
+    ThemeSettings::get_global(cx)
+        .reduce_motion
+        .override_value()
+        .unwrap_or_else(|| cx.should_reduce_motion())
+}
+
 pub fn get_ui_font_size(cx: &WindowContext) -> Pixels {
     let ui_font_size = ThemeSettings::get_global(cx).ui_font_size;
     cx.try_global::<AdjustedUiFontSize>()
@@ -424,6 +483,29 @@ pub fn reset_ui_font_size(cx: &mut WindowContext) {
     }
 }
 
+pub fn get_ui_scale(cx: &AppContext) -> f32 {
+    let ui_scale = ThemeSettings::get_global(cx).ui_scale;
+    cx.try_global::<AdjustedUiScale>()
+        .map_or(ui_scale, |adjusted_scale| adjusted_scale.0)
+        .clamp(MIN_UI_SCALE, MAX_UI_SCALE)
+}
+
+pub fn adjust_ui_scale(cx: &mut AppContext, f: fn(&mut f32)) {
+    let mut adjusted_scale = get_ui_scale(cx);
+    f(&mut adjusted_scale);
+    // Round away the error accumulated by repeated adjustments, like 1.1 + 0.1.
+    adjusted_scale = ((adjusted_scale * 100.).round() / 100.).clamp(MIN_UI_SCALE, MAX_UI_SCALE);
+    cx.set_global(AdjustedUiScale(adjusted_scale));
+    cx.refresh();
+}
+
+pub fn reset_ui_scale(cx: &mut AppContext) {
+    if cx.has_global::<AdjustedUiScale>() {
+        cx.remove_global::<AdjustedUiScale>();
+        cx.refresh();
+    }
+}
+
 impl settings::Settings for ThemeSettings {
     const KEY: Option<&'static str> = None;
 
@@ -436,6 +518,7 @@ impl settings::Settings for ThemeSettings {
         let defaults = sources.default;
         let mut this = Self {
             ui_font_size: defaults.ui_font_size.unwrap().into(),
+            ui_scale: defaults.ui_scale.unwrap(),
             ui_font: Font {
                 family: defaults.ui_font_family.clone().unwrap().into(),
                 features: defaults.ui_font_features.clone().unwrap(),
@@ -450,6 +533,7 @@ impl settings::Settings for ThemeSettings {
             },
             buffer_font_size: defaults.buffer_font_size.unwrap().into(),
             buffer_line_height: defaults.buffer_line_height.unwrap(),
+            buffer_letter_spacing: defaults.buffer_letter_spacing.unwrap(),
             theme_selection: defaults.theme.clone(),
             active_theme: themes
                 .get(defaults.theme.as_ref().unwrap().theme(*system_appearance))
@@ -457,6 +541,7 @@ impl settings::Settings for ThemeSettings {
                 .unwrap(),
             theme_overrides: None,
             ui_density: defaults.ui_density.unwrap_or(UiDensity::Default),
+            reduce_motion: defaults.reduce_motion.unwrap_or_default(),
         };
 
         for value in sources.user.into_iter().chain(sources.release_channel) {
@@ -499,11 +584,14 @@ impl settings::Settings for ThemeSettings {
             this.apply_theme_overrides();
 
             merge(&mut this.ui_font_size, value.ui_font_size.map(Into::into));
+            merge(&mut this.ui_scale, value.ui_scale);
             merge(
                 &mut this.buffer_font_size,
                 value.buffer_font_size.map(Into::into),
             );
             merge(&mut this.buffer_line_height, value.buffer_line_height);
+            merge(&mut this.buffer_letter_spacing, value.buffer_letter_spacing);
+            merge(&mut this.reduce_motion, value.reduce_motion);
         }
 
         Ok(this)
@@ -132,6 +132,14 @@ pub struct ThemeColors {
     pub scrollbar_track_background: Hsla,
     /// The border color of the scrollbar track.
     pub scrollbar_track_border: Hsla,
+    /// Text Color. Used for the character count of the feedback being written.
+    pub feedback_counter_text: Hsla,
+    /// The background color of the banner shown when feedback fails to submit.
+    pub feedback_error_banner_background: Hsla,
+    /// The background color of the button that submits feedback, while the feedback can't be submitted.
+    pub feedback_submit_disabled_background: Hsla,
+    /// The background color of the pickers for the category and severity of feedback.
+    pub feedback_category_picker_background: Hsla,
     // /// The opacity of the scrollbar status marks, like diagnostic states and git status.
     // todo()
     // pub scrollbar_status_opacity: Hsla,
@@ -0,0 +1,858 @@
+mod todo_panel_settings;
+
+use std::{ops::Range, sync::Arc};
+
+use anyhow::Context;
+use collections::{HashMap, HashSet};
+use db::kvp::KEY_VALUE_STORE;
+use editor::{
+    scroll::Autoscroll,
+    todo_comments::{edited_lines, keywords_pattern, todo_comments},
+    Editor, EditorSettings,
+};
+use futures::StreamExt;
+use gpui::{
+    actions, uniform_list, Action, AnyElement, AppContext, AsyncWindowContext, EventEmitter,
+    FocusHandle, FocusableView, Model, MouseButton, Render, Subscription, Task,
+    UniformListScrollHandle, View, WeakModel, WeakView,
+};
+use language::{Anchor, Buffer, BufferId, BufferSnapshot, OffsetRangeExt, Point, ToPoint};
+use project::{search::SearchQuery, Fs, Item as _, Project, ProjectPath, SearchResult};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsStore};
+use theme::SyntaxTheme;
+use todo_panel_settings::{TodoPanelDockPosition, TodoPanelSettings};
+use ui::{prelude::*, ListItem, Tooltip};
+use util::{paths::PathMatcher, ResultExt};
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    Workspace,
+};
+
+actions!(todo_panel, [ToggleFocus, Refresh]);
+
+const TODO_PANEL_KEY: &str = "TodoPanel";
+/// The longest text shown after a keyword, in characters.
+const MAX_TODO_TEXT_LEN: usize = 120;
+
+/// A panel listing the TODO-style comments of the project, grouped by file.
+pub struct TodoPanel {
+    fs: Arc<dyn Fs>,
+    workspace: WeakView<Workspace>,
+    project: Model<Project>,
+    width: Option<Pixels>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    pending_serialization: Task<Option<()>>,
+    keywords: Vec<String>,
+    regex: Option<Regex>,
+    hidden_keywords: HashSet<String>,
+    files: Vec<TodoFile>,
+    buffer_subscriptions: HashMap<BufferId, Subscription>,
+    update_tasks: HashMap<BufferId, Task<()>>,
+    /// The search for TODOs, `None` until the panel is first opened.
+    scan_task: Option<Task<()>>,
+    scanning: bool,
+    _subscriptions: Vec<Subscription>,
+}
+
+/// The TODOs of a file. Its buffer isn't kept open by the panel, so once it's released the
+/// TODOs are left as they were last seen, until the file is opened again.
+struct TodoFile {
+    buffer_id: BufferId,
+    buffer: WeakModel<Buffer>,
+    project_path: Option<ProjectPath>,
+    path: SharedString,
+    /// The version of the buffer that was scanned, so that only the lines edited since are
+    /// scanned again.
+    version: clock::Global,
+    todos: Vec<Todo>,
+}
+
+#[derive(Clone)]
+struct Todo {
+    keyword: String,
+    range: Range<Anchor>,
+    position: Point,
+    text: SharedString,
+}
+
+#[derive(Clone, Copy)]
+enum TodoEntry {
+    File(usize),
+    Todo(usize, usize),
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedTodoPanel {
+    width: Option<Pixels>,
+}
+
+pub fn init_settings(cx: &mut AppContext) {
+    TodoPanelSettings::register(cx);
+}
+
+pub fn init(cx: &mut AppContext) {
+    init_settings(cx);
+
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, cx| {
+            workspace.toggle_panel_focus::<TodoPanel>(cx);
+        });
+    })
+    .detach();
+}
+
+impl TodoPanel {
+    pub async fn load(
+        workspace: WeakView<Workspace>,
+        mut cx: AsyncWindowContext,
+    ) -> anyhow::Result<View<Self>> {
+        let serialized_panel = cx
+            .background_executor()
+            .spawn(async move { KEY_VALUE_STORE.read_kvp(TODO_PANEL_KEY) })
+            .await
+            .context("loading todo panel")
+            .log_err()
+            .flatten()
+            .map(|panel| serde_json::from_str::<SerializedTodoPanel>(&panel))
+            .transpose()
+            .log_err()
+            .flatten();
+
+        workspace.update(&mut cx, |workspace, cx| {
+            let panel = Self::new(workspace, cx);
+            if let Some(serialized_panel) = serialized_panel {
+                panel.update(cx, |panel, cx| {
+                    panel.width = serialized_panel.width.map(|px| px.round());
+                    cx.notify();
+                });
+            }
+            panel
+        })
+    }
+
+    fn new(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) -> View<Self> {
+        let project = workspace.project().clone();
+        let fs = workspace.app_state().fs.clone();
+        let workspace = workspace.weak_handle();
+        let workspace_view = cx.view().clone();
+        cx.new_view(|cx| {
+            let settings_subscription = cx.observe_global::<SettingsStore>(|todo_panel, cx| {
+                if todo_panel.keywords != configured_keywords(cx) && todo_panel.scan_task.is_some()
+                {
+                    todo_panel.scan(cx);
+                }
+                cx.notify();
+            });
+            // Buffers that were released are tracked again once they are opened.
+            let workspace_subscription =
+                cx.subscribe(&workspace_view, |todo_panel, _, event, cx| {
+                    if let workspace::Event::ItemAdded = event {
+                        todo_panel.track_reopened_buffers(cx);
+                    }
+                });
+
+            Self {
+                fs,
+                workspace,
+                project,
+                width: None,
+                focus_handle: cx.focus_handle(),
+                scroll_handle: UniformListScrollHandle::new(),
+                pending_serialization: Task::ready(None),
+                keywords: configured_keywords(cx),
+                regex: None,
+                hidden_keywords: HashSet::default(),
+                files: Vec::new(),
+                buffer_subscriptions: HashMap::default(),
+                update_tasks: HashMap::default(),
+                scan_task: None,
+                scanning: false,
+                _subscriptions: vec![settings_subscription, workspace_subscription],
+            }
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut ViewContext<Self>) {
+        let width = self.width;
+        self.pending_serialization = cx.background_executor().spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        TODO_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedTodoPanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn refresh(&mut self, _: &Refresh, cx: &mut ViewContext<Self>) {
+        self.scan(cx);
+    }
+
+    /// Searches the project for the configured keywords, keeping those inside of comments.
+    fn scan(&mut self, cx: &mut ViewContext<Self>) {
+        self.keywords = configured_keywords(cx);
+        self.hidden_keywords
+            .retain(|keyword| self.keywords.contains(keyword));
+        self.files.clear();
+        self.buffer_subscriptions.clear();
+        self.update_tasks.clear();
+        self.scanning = false;
+        cx.notify();
+
+        let pattern = keywords_pattern(self.keywords.iter().map(String::as_str));
+        self.regex = pattern
+            .as_ref()
+            .and_then(|pattern| Regex::new(pattern).log_err());
+        let query = pattern.and_then(|pattern| {
+            SearchQuery::regex(
+                pattern,
+                false,
+                true,
+                false,
+                PathMatcher::default(),
+                PathMatcher::default(),
+            )
+            .log_err()
+        });
+        let Some(query) = query else {
+            self.scan_task = Some(Task::ready(()));
+            return;
+        };
+
+        let mut results = self
+            .project
+            .update(cx, |project, cx| project.search(query, cx));
+        self.scanning = true;
+        self.scan_task = Some(cx.spawn(|todo_panel, mut cx| async move {
+            while let Some(result) = results.next().await {
+                if let SearchResult::Buffer { buffer, .. } = result {
+                    todo_panel
+                        .update(&mut cx, |todo_panel, cx| {
+                            todo_panel.insert_buffer(buffer, cx)
+                        })
+                        .ok();
+                }
+            }
+            todo_panel
+                .update(&mut cx, |todo_panel, cx| {
+                    todo_panel.scanning = false;
+                    cx.notify();
+                })
+                .ok();
+        }));
+    }
+
+    fn insert_buffer(&mut self, buffer: Model<Buffer>, cx: &mut ViewContext<Self>) {
+        let buffer_id = buffer.read(cx).remote_id();
+        if self.buffer_subscriptions.contains_key(&buffer_id) {
+            return;
+        }
+        // Comments are only known once the buffer is parsed, and may change as it is edited.
+        let subscription = cx.subscribe(&buffer, |todo_panel, buffer, event, cx| {
+            if let language::Event::Reparsed | language::Event::LanguageChanged = event {
+                todo_panel.update_buffer(buffer, cx);
+            }
+        });
+        self.buffer_subscriptions.insert(buffer_id, subscription);
+        cx.observe_release(&buffer, move |todo_panel, _, _| {
+            todo_panel.buffer_subscriptions.remove(&buffer_id);
+            todo_panel.update_tasks.remove(&buffer_id);
+        })
+        .detach();
+        self.update_buffer(buffer, cx);
+    }
+
+    /// Tracks the buffers of the files whose buffers were released, if they were opened again.
+    fn track_reopened_buffers(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let buffers = workspace
+            .read(cx)
+            .items_of_type::<Editor>(cx)
+            .filter_map(|editor| editor.read(cx).buffer().read(cx).as_singleton())
+            .collect::<Vec<_>>();
+        for buffer in buffers {
+            if self
+                .buffer_subscriptions
+                .contains_key(&buffer.read(cx).remote_id())
+            {
+                continue;
+            }
+            let project_path = buffer.read(cx).project_path(cx);
+            let released = self.files.iter().any(|file| {
+                file.project_path.is_some()
+                    && file.project_path == project_path
+                    && file.buffer.upgrade().is_none()
+            });
+            if released {
+                self.insert_buffer(buffer, cx);
+            }
+        }
+    }
+
+    fn update_buffer(&mut self, buffer: Model<Buffer>, cx: &mut ViewContext<Self>) {
+        let Some(regex) = self.regex.clone() else {
+            return;
+        };
+        let buffer_id = buffer.read(cx).remote_id();
+        let snapshot = buffer.read(cx).snapshot();
+        let project_path = buffer.read(cx).project_path(cx);
+        let path = buffer
+            .read(cx)
+            .file()
+            .map(|file| file.full_path(cx).to_string_lossy().to_string())
+            .unwrap_or_default();
+        let scanned = self
+            .files
+            .iter()
+            .find(|file| file.buffer_id == buffer_id)
+            .map(|file| (file.version.clone(), file.todos.clone()));
+        let theme = cx.theme().syntax().clone();
+        let task = cx.spawn(|todo_panel, mut cx| async move {
+            let (version, todos) = cx
+                .background_executor()
+                .spawn(async move {
+                    let todos = match scanned {
+                        Some((version, todos)) => {
+                            update_todos(&snapshot, &version, todos, &regex, &theme)
+                        }
+                        None => find_todos(&snapshot, 0..snapshot.len(), &regex, &theme),
+                    };
+                    (snapshot.version().clone(), todos)
+                })
+                .await;
+            todo_panel
+                .update(&mut cx, |todo_panel, cx| {
+                    todo_panel.update_tasks.remove(&buffer_id);
+                    // A file whose buffer was released is replaced once it's opened again.
+                    let ix = todo_panel.files.iter().position(|file| {
+                        file.buffer_id == buffer_id
+                            || (file.project_path.is_some()
+                                && file.project_path == project_path
+                                && file.buffer.upgrade().is_none())
+                    });
+                    let file = TodoFile {
+                        buffer_id,
+                        buffer: buffer.downgrade(),
+                        project_path,
+                        path: SharedString::from(path),
+                        version,
+                        todos,
+                    };
+                    // Files without TODOs are kept, so they aren't scanned whole again, but
+                    // aren't listed.
+                    if let Some(ix) = ix {
+                        todo_panel.files[ix] = file;
+                    } else {
+                        let ix = todo_panel
+                            .files
+                            .partition_point(|other| other.path < file.path);
+                        todo_panel.files.insert(ix, file);
+                    }
+                    cx.notify();
+                })
+                .ok();
+        });
+        self.update_tasks.insert(buffer_id, task);
+    }
+
+    fn toggle_keyword(&mut self, keyword: &str, cx: &mut ViewContext<Self>) {
+        if !self.hidden_keywords.remove(keyword) {
+            self.hidden_keywords.insert(keyword.to_string());
+        }
+        cx.notify();
+    }
+
+    fn visible_entries(&self) -> Vec<TodoEntry> {
+        let mut entries = Vec::new();
+        for (file_ix, file) in self.files.iter().enumerate() {
+            let mut todos = file
+                .todos
+                .iter()
+                .enumerate()
+                .filter(|(_, todo)| !self.hidden_keywords.contains(&todo.keyword))
+                .map(|(todo_ix, _)| TodoEntry::Todo(file_ix, todo_ix))
+                .peekable();
+            if todos.peek().is_some() {
+                entries.push(TodoEntry::File(file_ix));
+                entries.extend(todos);
+            }
+        }
+        entries
+    }
+
+    fn open_todo(&mut self, file_ix: usize, todo_ix: usize, cx: &mut ViewContext<Self>) {
+        let Some(file) = self.files.get(file_ix) else {
+            return;
+        };
+        let Some(todo) = file.todos.get(todo_ix) else {
+            return;
+        };
+        if let Some(buffer) = file.buffer.upgrade() {
+            let position = todo.range.start.to_point(buffer.read(cx));
+            self.workspace
+                .update(cx, |workspace, cx| {
+                    let pane = workspace.active_pane().clone();
+                    let editor = workspace.open_project_item::<Editor>(pane, buffer, cx);
+                    select_position(&editor, position, cx);
+                    cx.focus_view(&editor);
+                })
+                .ok();
+        } else if let Some(project_path) = file.project_path.clone() {
+            // The buffer was released, so the file is opened again at the last known position.
+            let position = todo.position;
+            let workspace = self.workspace.clone();
+            cx.spawn(|_, mut cx| async move {
+                let item = workspace
+                    .update(&mut cx, |workspace, cx| {
+                        workspace.open_path(project_path, None, true, cx)
+                    })?
+                    .await?;
+                if let Some(editor) = item.downcast::<Editor>() {
+                    cx.update(|cx| select_position(&editor, position, cx))?;
+                }
+                anyhow::Ok(())
+            })
+            .detach_and_log_err(cx);
+        }
+    }
+
+    fn render_header(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        h_flex()
+            .flex_none()
+            .flex_wrap()
+            .gap_1()
+            .p_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .children(self.keywords.iter().map(|keyword| {
+                let keyword = SharedString::from(keyword.clone());
+                Button::new(keyword.clone(), keyword.clone())
+                    .style(ButtonStyle::Subtle)
+                    .size(ButtonSize::Compact)
+                    .selected(!self.hidden_keywords.contains(keyword.as_ref()))
+                    .on_click(
+                        cx.listener(move |todo_panel, _, cx| {
+                            todo_panel.toggle_keyword(&keyword, cx)
+                        }),
+                    )
+            }))
+            .child(div().flex_1())
+            .child(
+                IconButton::new("refresh-todos", IconName::RotateCw)
+                    .icon_size(IconSize::Small)
+                    .disabled(self.scanning)
+                    .tooltip(|cx| Tooltip::for_action("Refresh", &Refresh, cx))
+                    .on_click(cx.listener(|todo_panel, _, cx| todo_panel.scan(cx))),
+            )
+    }
+
+    fn render_entry(&self, ix: usize, entry: TodoEntry, cx: &mut ViewContext<Self>) -> AnyElement {
+        match entry {
+            TodoEntry::File(file_ix) => {
+                let file = &self.files[file_ix];
+                ListItem::new(("todo-entry", ix))
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(Label::new(file.path.clone()).size(LabelSize::Small))
+                            .child(
+                                Label::new(file.todos.len().to_string())
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            ),
+                    )
+                    .into_any_element()
+            }
+            TodoEntry::Todo(file_ix, todo_ix) => {
+                let todo = &self.files[file_ix].todos[todo_ix];
+                ListItem::new(("todo-entry", ix))
+                    .indent_level(1)
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(
+                                Label::new(format!("{}", todo.position.row + 1))
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                            .child(Label::new(todo.text.clone()).single_line()),
+                    )
+                    .on_click(
+                        cx.listener(move |todo_panel, event: &gpui::ClickEvent, cx| {
+                            if event.down.button == MouseButton::Left {
+                                todo_panel.open_todo(file_ix, todo_ix, cx);
+                            }
+                        }),
+                    )
+                    .into_any_element()
+            }
+        }
+    }
+}
+
+/// Returns the keywords highlighted in comments, in the order they are listed by the panel.
+fn configured_keywords(cx: &AppContext) -> Vec<String> {
+    EditorSettings::get_global(cx)
+        .todo_highlights
+        .keywords
+        .keys()
+        .cloned()
+        .collect()
+}
+
+fn select_position(editor: &View<Editor>, position: Point, cx: &mut WindowContext) {
+    editor.update(cx, |editor, cx| {
+        editor.change_selections(Some(Autoscroll::center()), cx, |s| {
+            s.select_ranges([position..position])
+        });
+    });
+}
+
+/// Finds the TODOs within the range of the buffer.
+fn find_todos(
+    buffer: &BufferSnapshot,
+    range: Range<usize>,
+    regex: &Regex,
+    theme: &SyntaxTheme,
+) -> Vec<Todo> {
+    let offset = range.start;
+    let text = buffer.text_for_range(range).collect::<String>();
+    regex
+        .find_iter(&text)
+        .flat_map(|keyword| {
+            let range = offset + keyword.start()..offset + keyword.end();
+            todo_comments(buffer, range, regex, theme)
+        })
+        .map(|todo| {
+            let start = todo.range.start.to_point(buffer);
+            let line_end =
+                buffer.point_to_offset(Point::new(start.row, buffer.line_len(start.row)));
+            let text = buffer
+                .text_for_range(todo.range.start..line_end)
+                .collect::<String>();
+            let text = text.trim_end();
+            let text = match text.char_indices().nth(MAX_TODO_TEXT_LEN) {
+                Some((ix, _)) => format!("{}…", &text[..ix]),
+                None => text.to_string(),
+            };
+            Todo {
+                keyword: todo.keyword,
+                range: buffer.anchor_after(todo.range.start)..buffer.anchor_before(todo.range.end),
+                position: start,
+                text: text.into(),
+            }
+        })
+        .collect()
+}
+
+/// Scans the lines of the buffer that were edited since the version the TODOs were found in,
+/// keeping the TODOs on the other lines.
+fn update_todos(
+    buffer: &BufferSnapshot,
+    version: &clock::Global,
+    todos: Vec<Todo>,
+    regex: &Regex,
+    theme: &SyntaxTheme,
+) -> Vec<Todo> {
+    let edited_lines = edited_lines(buffer, version, Anchor::MIN..Anchor::MAX);
+    if edited_lines.is_empty() {
+        return todos;
+    }
+    let mut todos = todos
+        .into_iter()
+        .filter(|todo| {
+            let range = todo.range.to_offset(buffer);
+            !edited_lines
+                .iter()
+                .any(|edited| range.start <= edited.end && range.end >= edited.start)
+        })
+        .map(|todo| Todo {
+            position: todo.range.start.to_point(buffer),
+            ..todo
+        })
+        .collect::<Vec<_>>();
+    for range in edited_lines {
+        todos.extend(find_todos(buffer, range, regex, theme));
+    }
+    todos.sort_by_key(|todo| todo.position);
+    todos
+}
+
+impl Panel for TodoPanel {
+    fn persistent_name() -> &'static str {
+        "Todo Panel"
+    }
+
+    fn position(&self, cx: &WindowContext) -> DockPosition {
+        match TodoPanelSettings::get_global(cx).dock {
+            TodoPanelDockPosition::Left => DockPosition::Left,
+            TodoPanelDockPosition::Right => DockPosition::Right,
+        }
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, cx: &mut ViewContext<Self>) {
+        settings::update_settings_file::<TodoPanelSettings>(self.fs.clone(), cx, move |settings| {
+            let dock = match position {
+                DockPosition::Left | DockPosition::Bottom => TodoPanelDockPosition::Left,
+                DockPosition::Right => TodoPanelDockPosition::Right,
+            };
+            settings.dock = Some(dock);
+        });
+    }
+
+    fn size(&self, cx: &WindowContext) -> Pixels {
+        self.width
+            .unwrap_or_else(|| TodoPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, cx: &mut ViewContext<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, cx: &WindowContext) -> Option<IconName> {
+        TodoPanelSettings::get_global(cx)
+            .button
+            .then(|| IconName::Check)
+    }
+
+    fn icon_tooltip(&self, _: &WindowContext) -> Option<&'static str> {
+        Some("Todo Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn set_active(&mut self, active: bool, cx: &mut ViewContext<Self>) {
+        if active && self.scan_task.is_none() {
+            self.scan(cx);
+        }
+    }
+}
+
+impl FocusableView for TodoPanel {
+    fn focus_handle(&self, _cx: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for TodoPanel {}
+
+impl Render for TodoPanel {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let entries = self.visible_entries();
+        v_flex()
+            .id("todo-panel")
+            .size_full()
+            .key_context("TodoPanel")
+            .on_action(cx.listener(Self::refresh))
+            .track_focus(&self.focus_handle)
+            .child(self.render_header(cx))
+            .child(if entries.is_empty() {
+                let message = if self.scanning {
+                    "Searching for comments…"
+                } else {
+                    "No comments found"
+                };
+                v_flex()
+                    .p_4()
+                    .child(Label::new(message).color(Color::Muted))
+                    .into_any_element()
+            } else {
+                uniform_list(
+                    cx.view().clone(),
+                    "todo-entries",
+                    entries.len(),
+                    move |todo_panel, range, cx| {
+                        entries[range.clone()]
+                            .iter()
+                            .zip(range)
+                            .map(|(entry, ix)| todo_panel.render_entry(ix, *entry, cx))
+                            .collect()
+                    },
+                )
+                .size_full()
+                .track_scroll(self.scroll_handle.clone())
+                .into_any_element()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext};
+    use language::{Language, LanguageConfig, LanguageMatcher};
+    use project::FakeFs;
+    use serde_json::json;
+    use workspace::AppState;
+
+    #[gpui::test]
+    async fn test_todo_panel(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "a.rs": "// TODO: first\nfn a() {}\n",
+                "b.rs": "fn b() {} // FIXME: second\nconst TODO: &str = \"TODO\";\n",
+                "c.rs": "fn c() {}\n",
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), ["/root".as_ref()], cx).await;
+        project.update(cx, |project, cx| {
+            project.languages().add(rust_lang());
+            project.languages().set_theme(cx.theme().clone());
+        });
+        let workspace = cx.add_window(|cx| Workspace::test_new(project.clone(), cx));
+        let cx = &mut VisualTestContext::from_window(*workspace, cx);
+        let panel = workspace
+            .update(cx, |workspace, cx| TodoPanel::new(workspace, cx))
+            .unwrap();
+
+        panel.update(cx, |panel, cx| panel.scan(cx));
+        cx.run_until_parked();
+        assert_eq!(
+            visible_entries(&panel, cx),
+            [
+                "root/a.rs",
+                "  1: TODO: first",
+                "root/b.rs",
+                "  1: FIXME: second"
+            ]
+        );
+
+        panel.update(cx, |panel, cx| panel.toggle_keyword("TODO", cx));
+        assert_eq!(
+            visible_entries(&panel, cx),
+            ["root/b.rs", "  1: FIXME: second"]
+        );
+        panel.update(cx, |panel, cx| panel.toggle_keyword("TODO", cx));
+
+        // Editing a file scans it again, whether or not its buffer was released after the search.
+        let worktree_id = project.update(cx, |project, cx| {
+            project.worktrees().next().unwrap().read(cx).id()
+        });
+        let editor = workspace
+            .update(cx, |workspace, cx| {
+                workspace.open_path((worktree_id, "b.rs"), None, true, cx)
+            })
+            .unwrap()
+            .await
+            .unwrap()
+            .downcast::<Editor>()
+            .unwrap();
+        editor.update(cx, |editor, cx| {
+            editor.edit(
+                [(Point::new(1, 0)..Point::new(1, 0), "// HACK: third\n")],
+                cx,
+            );
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            visible_entries(&panel, cx),
+            [
+                "root/a.rs",
+                "  1: TODO: first",
+                "root/b.rs",
+                "  1: FIXME: second",
+                "  2: HACK: third"
+            ]
+        );
+
+        editor.update(cx, |editor, cx| {
+            editor.edit([(Point::new(0, 0)..Point::new(1, 0), "")], cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            visible_entries(&panel, cx),
+            [
+                "root/a.rs",
+                "  1: TODO: first",
+                "root/b.rs",
+                "  1: HACK: third"
+            ]
+        );
+
+        panel.update(cx, |panel, cx| panel.open_todo(0, 0, cx));
+        cx.run_until_parked();
+        let editor = workspace
+            .update(cx, |workspace, cx| {
+                workspace.active_item_as::<Editor>(cx).unwrap()
+            })
+            .unwrap();
+        editor.update(cx, |editor, cx| {
+            let buffer = editor.buffer().read(cx).as_singleton().unwrap();
+            assert_eq!(
+                buffer.read(cx).file().unwrap().path().as_ref(),
+                std::path::Path::new("a.rs")
+            );
+            assert_eq!(
+                editor.selections.newest::<Point>(cx).head(),
+                Point::new(0, 3)
+            );
+        });
+    }
+
+    fn visible_entries(panel: &View<TodoPanel>, cx: &mut VisualTestContext) -> Vec<String> {
+        panel.update(cx, |panel, _| {
+            panel
+                .visible_entries()
+                .into_iter()
+                .map(|entry| match entry {
+                    TodoEntry::File(file_ix) => panel.files[file_ix].path.to_string(),
+                    TodoEntry::Todo(file_ix, todo_ix) => {
+                        let todo = &panel.files[file_ix].todos[todo_ix];
+                        format!("  {}: {}", todo.position.row + 1, todo.text)
+                    }
+                })
+                .collect()
+        })
+    }
+
+    fn rust_lang() -> Arc<Language> {
+        Arc::new(
+            Language::new(
+                LanguageConfig {
+                    name: "Rust".into(),
+                    matcher: LanguageMatcher {
+                        path_suffixes: vec!["rs".to_string()],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                Some(tree_sitter_rust::language()),
+            )
+            .with_highlights_query("(line_comment) @comment")
+            .unwrap(),
+        )
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let app_state = AppState::test(cx);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            editor::init(cx);
+            workspace::init(app_state, cx);
+            Project::init_settings(cx);
+            crate::init(cx);
+        });
+    }
+}
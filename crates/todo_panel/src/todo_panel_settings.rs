@@ -0,0 +1,47 @@
+use gpui::Pixels;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoPanelDockPosition {
+    Left,
+    Right,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct TodoPanelSettings {
+    pub button: bool,
+    pub default_width: Pixels,
+    pub dock: TodoPanelDockPosition,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct TodoPanelSettingsContent {
+    /// Whether to show the todo panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Customise default width (in pixels) taken by todo panel
+    ///
+    /// Default: 300
+    pub default_width: Option<f32>,
+    /// The position of todo panel
+    ///
+    /// Default: right
+    pub dock: Option<TodoPanelDockPosition>,
+}
+
+impl Settings for TodoPanelSettings {
+    const KEY: Option<&'static str> = Some("todo_panel");
+
+    type FileContent = TodoPanelSettingsContent;
+
+    fn load(
+        sources: SettingsSources<Self::FileContent>,
+        _: &mut gpui::AppContext,
+    ) -> anyhow::Result<Self> {
+        sources.json_merge()
+    }
+}
@@ -1,4 +1,4 @@
-use gpui::{AnyView, DefiniteLength};
+use gpui::{AnyView, DefiniteLength, FocusHandle};
 
 use crate::{prelude::*, ElevationIndex, IconPosition, KeyBinding, Spacing};
 use crate::{
@@ -345,6 +345,11 @@ impl ButtonCommon for Button {
         self.base = self.base.layer(elevation);
         self
     }
+
+    fn track_focus(mut self, focus_handle: &FocusHandle) -> Self {
+        self.base = self.base.track_focus(focus_handle);
+        self
+    }
 }
 
 impl RenderOnce for Button {
@@ -366,7 +371,13 @@ impl RenderOnce for Button {
             self.label_color.unwrap_or_default()
         };
 
-        self.base.child(
+        let accessibility_label = self
+            .base
+            .accessibility_label
+            .clone()
+            .unwrap_or_else(|| label.clone());
+
+        self.base.accessibility_label(accessibility_label).child(
             h_flex()
                 .gap(Spacing::Small.rems(cx))
                 .when(self.icon_position == Some(IconPosition::Start), |this| {
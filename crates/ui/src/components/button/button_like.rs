@@ -1,4 +1,6 @@
-use gpui::{relative, DefiniteLength, MouseButton};
+use std::rc::Rc;
+
+use gpui::{relative, AccessibilityRole, DefiniteLength, FocusHandle, MouseButton};
 use gpui::{transparent_black, AnyElement, AnyView, ClickEvent, Hsla, Rems};
 use smallvec::SmallVec;
 
@@ -35,6 +37,10 @@ pub trait ButtonCommon: Clickable + Disableable {
     fn tooltip(self, tooltip: impl Fn(&mut WindowContext) -> AnyView + 'static) -> Self;
 
     fn layer(self, elevation: ElevationIndex) -> Self;
+
+    /// Makes the button focusable with the given focus handle, so that it can be reached
+    /// with tab and shift-tab, and clicked with `menu::Confirm` while it's focused.
+    fn track_focus(self, focus_handle: &FocusHandle) -> Self;
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
@@ -245,7 +251,6 @@ impl ButtonStyle {
         }
     }
 
-    #[allow(unused)]
     pub(crate) fn focused(self, cx: &mut WindowContext) -> ButtonLikeStyles {
         match self {
             ButtonStyle::Filled => ButtonLikeStyles {
@@ -341,10 +346,12 @@ pub struct ButtonLike {
     pub(super) width: Option<DefiniteLength>,
     pub(super) height: Option<DefiniteLength>,
     pub(super) layer: Option<Elevation>,
+    pub(super) accessibility_label: Option<SharedString>,
     size: ButtonSize,
     rounding: Option<ButtonLikeRounding>,
     tooltip: Option<Box<dyn Fn(&mut WindowContext) -> AnyView>>,
     on_click: Option<Box<dyn Fn(&ClickEvent, &mut WindowContext) + 'static>>,
+    focus_handle: Option<FocusHandle>,
     children: SmallVec<[AnyElement; 2]>,
 }
 
@@ -364,10 +371,18 @@ impl ButtonLike {
             tooltip: None,
             children: SmallVec::new(),
             on_click: None,
+            focus_handle: None,
             layer: None,
+            accessibility_label: None,
         }
     }
 
+    /// Sets the label that screen readers read for the button, for buttons without text.
+    pub fn accessibility_label(mut self, label: impl Into<SharedString>) -> Self {
+        self.accessibility_label = Some(label.into());
+        self
+    }
+
     pub(crate) fn height(mut self, height: DefiniteLength) -> Self {
         self.height = Some(height);
         self
@@ -443,6 +458,11 @@ impl ButtonCommon for ButtonLike {
         self.layer = Some(elevation.into());
         self
     }
+
+    fn track_focus(mut self, focus_handle: &FocusHandle) -> Self {
+        self.focus_handle = Some(focus_handle.clone());
+        self
+    }
 }
 
 impl VisibleOnHover for ButtonLike {
@@ -464,10 +484,19 @@ impl RenderOnce for ButtonLike {
             .selected_style
             .filter(|_| self.selected)
             .unwrap_or(self.style);
+        let on_click: Option<Rc<dyn Fn(&ClickEvent, &mut WindowContext)>> = self
+            .on_click
+            .filter(|_| !self.disabled)
+            .map(|on_click| on_click.into());
 
-        self.base
+        let button = self
+            .base
             .h_flex()
             .id(self.id.clone())
+            .accessibility_role(AccessibilityRole::Button)
+            .when_some(self.accessibility_label, |this, label| {
+                this.accessibility_label(label)
+            })
             .group("")
             .flex_none()
             .h(self.height.unwrap_or(self.size.rems().into()))
@@ -490,19 +519,35 @@ impl RenderOnce for ButtonLike {
                     .hover(|hover| hover.bg(style.hovered(self.layer, cx).background))
                     .active(|active| active.bg(style.active(cx).background))
             })
-            .when_some(
-                self.on_click.filter(|_| !self.disabled),
-                |this, on_click| {
-                    this.on_mouse_down(MouseButton::Left, |_, cx| cx.prevent_default())
-                        .on_click(move |event, cx| {
-                            cx.stop_propagation();
-                            (on_click)(event, cx)
-                        })
-                },
-            )
+            .when_some(on_click.clone(), |this, on_click| {
+                this.on_mouse_down(MouseButton::Left, |_, cx| cx.prevent_default())
+                    .on_click(move |event, cx| {
+                        cx.stop_propagation();
+                        (on_click)(event, cx)
+                    })
+            })
             .when_some(self.tooltip, |this, tooltip| {
                 this.tooltip(move |cx| tooltip(cx))
             })
-            .children(self.children)
+            .children(self.children);
+
+        match self.focus_handle {
+            Some(focus_handle) => {
+                let focused_border_color = style.focused(cx).border_color;
+                button
+                    .border_1()
+                    .border_color(transparent_black())
+                    .track_focus(&focus_handle)
+                    .tab_stop()
+                    .focus(move |focused| focused.border_color(focused_border_color))
+                    .when_some(on_click, |this, on_click| {
+                        this.on_action(move |_: &menu::Confirm, cx| {
+                            (on_click)(&ClickEvent::default(), cx)
+                        })
+                    })
+                    .into_any_element()
+            }
+            None => button.into_any_element(),
+        }
     }
 }
@@ -1,4 +1,4 @@
-use gpui::{AnyView, DefiniteLength};
+use gpui::{AnyView, DefiniteLength, FocusHandle};
 
 use crate::{prelude::*, ElevationIndex, SelectableButton, Spacing};
 use crate::{ButtonCommon, ButtonLike, ButtonSize, ButtonStyle, IconName, IconSize};
@@ -55,6 +55,13 @@ impl IconButton {
         self.selected_icon = icon.into();
         self
     }
+
+    /// Sets the label that screen readers read for the button. Without one, they
+    /// read the name of the icon.
+    pub fn accessibility_label(mut self, label: impl Into<SharedString>) -> Self {
+        self.base = self.base.accessibility_label(label);
+        self
+    }
 }
 
 impl Disableable for IconButton {
@@ -124,6 +131,11 @@ impl ButtonCommon for IconButton {
         self.base = self.base.layer(elevation);
         self
     }
+
+    fn track_focus(mut self, focus_handle: &FocusHandle) -> Self {
+        self.base = self.base.track_focus(focus_handle);
+        self
+    }
 }
 
 impl VisibleOnHover for IconButton {
@@ -138,8 +150,16 @@ impl RenderOnce for IconButton {
         let is_disabled = self.base.disabled;
         let is_selected = self.base.selected;
         let selected_style = self.base.selected_style;
+        let accessibility_label = self.base.accessibility_label.clone().unwrap_or_else(|| {
+            let icon_name = self.icon.path();
+            let icon_name = icon_name
+                .trim_start_matches("icons/")
+                .trim_end_matches(".svg");
+            icon_name.replace('_', " ").into()
+        });
 
         self.base
+            .accessibility_label(accessibility_label)
             .map(|this| match self.shape {
                 IconButtonShape::Square => {
                     let icon_size = self.icon_size.rems() * cx.rem_size();
@@ -1,4 +1,4 @@
-use gpui::{div, prelude::*, ElementId, IntoElement, Styled, WindowContext};
+use gpui::{div, prelude::*, AccessibilityRole, ElementId, IntoElement, Styled, WindowContext};
 
 use crate::prelude::*;
 use crate::{Color, Icon, IconName, Selection};
@@ -81,6 +81,7 @@ impl RenderOnce for Checkbox {
 
         h_flex()
             .id(self.id)
+            .accessibility_role(AccessibilityRole::CheckBox)
             .justify_center()
             .items_center()
             .size(crate::styles::custom_spacing(cx, 20.))
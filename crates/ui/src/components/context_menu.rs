@@ -3,8 +3,8 @@ use crate::{
     ListSubHeader, WithRemSize,
 };
 use gpui::{
-    px, Action, AnyElement, AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView,
-    IntoElement, Render, Subscription, View, VisualContext,
+    px, AccessibilityRole, Action, AnyElement, AppContext, DismissEvent, EventEmitter, FocusHandle,
+    FocusableView, IntoElement, Render, Subscription, View, VisualContext,
 };
 use menu::{SelectFirst, SelectLast, SelectNext, SelectPrev};
 use settings::Settings;
@@ -308,6 +308,8 @@ impl Render for ContextMenu {
                 v_flex()
                     .min_w(px(200.))
                     .track_focus(&self.focus_handle)
+                    .focus_trap()
+                    .accessibility_role(AccessibilityRole::Menu)
                     .on_mouse_down_out(cx.listener(|this, _, cx| this.cancel(&menu::Cancel, cx)))
                     .key_context("menu")
                     .on_action(cx.listener(ContextMenu::select_first))
@@ -342,6 +344,8 @@ impl Render for ContextMenu {
                                         .into_any_element()
                                 }
                                 ContextMenuItem::Label(label) => ListItem::new(ix)
+                                    .accessibility_role(AccessibilityRole::StaticText)
+                                    .accessibility_label(label.clone())
                                     .inset(true)
                                     .disabled(true)
                                     .child(Label::new(label.clone()))
@@ -367,6 +371,8 @@ impl Render for ContextMenu {
                                     };
 
                                     ListItem::new(ix)
+                                        .accessibility_role(AccessibilityRole::MenuItem)
+                                        .accessibility_label(label.clone())
                                         .inset(true)
                                         .selected(Some(ix) == self.selected_index)
                                         .when_some(*toggled, |list_item, toggled| {
@@ -421,6 +427,7 @@ impl Render for ContextMenu {
                                     let handler = handler.clone();
                                     let menu = cx.view().downgrade();
                                     ListItem::new(ix)
+                                        .accessibility_role(AccessibilityRole::MenuItem)
                                         .inset(true)
                                         .selected(if *selectable {
                                             Some(ix) == self.selected_index
@@ -1,6 +1,8 @@
 use std::sync::Arc;
 
-use gpui::{px, AnyElement, AnyView, ClickEvent, MouseButton, MouseDownEvent, Pixels};
+use gpui::{
+    px, AccessibilityRole, AnyElement, AnyView, ClickEvent, MouseButton, MouseDownEvent, Pixels,
+};
 use smallvec::SmallVec;
 
 use crate::{prelude::*, Disclosure};
@@ -36,6 +38,8 @@ pub struct ListItem {
     on_secondary_mouse_down: Option<Box<dyn Fn(&MouseDownEvent, &mut WindowContext) + 'static>>,
     children: SmallVec<[AnyElement; 2]>,
     selectable: bool,
+    accessibility_role: AccessibilityRole,
+    accessibility_label: Option<SharedString>,
 }
 
 impl ListItem {
@@ -58,9 +62,23 @@ impl ListItem {
             tooltip: None,
             children: SmallVec::new(),
             selectable: true,
+            accessibility_role: AccessibilityRole::ListItem,
+            accessibility_label: None,
         }
     }
 
+    /// Sets what the item is for screen readers, like a menu item, instead of a list item.
+    pub fn accessibility_role(mut self, role: AccessibilityRole) -> Self {
+        self.accessibility_role = role;
+        self
+    }
+
+    /// Sets the label that screen readers read for the item.
+    pub fn accessibility_label(mut self, label: impl Into<SharedString>) -> Self {
+        self.accessibility_label = Some(label.into());
+        self
+    }
+
     pub fn spacing(mut self, spacing: ListItemSpacing) -> Self {
         self.spacing = spacing;
         self
@@ -157,6 +175,10 @@ impl RenderOnce for ListItem {
     fn render(self, cx: &mut WindowContext) -> impl IntoElement {
         h_flex()
             .id(self.id)
+            .accessibility_role(self.accessibility_role)
+            .when_some(self.accessibility_label, |this, label| {
+                this.accessibility_label(label)
+            })
             .w_full()
             .relative()
             // When an item is inset draw the indent spacing outside of the item
@@ -1,7 +1,8 @@
 use crate::prelude::*;
 use crate::v_flex;
 use gpui::{
-    div, AnyElement, Element, IntoElement, ParentElement, RenderOnce, Styled, WindowContext,
+    div, AccessibilityRole, AnyElement, Element, IntoElement, ParentElement, RenderOnce, Styled,
+    WindowContext,
 };
 use smallvec::SmallVec;
 
@@ -41,6 +42,7 @@ pub struct Popover {
 impl RenderOnce for Popover {
     fn render(self, cx: &mut WindowContext) -> impl IntoElement {
         div()
+            .accessibility_role(AccessibilityRole::Popover)
             .flex()
             .gap_1()
             .child(v_flex().elevation_2(cx).px_1().children(self.children))
@@ -1,6 +1,6 @@
 use std::cmp::Ordering;
 
-use gpui::{AnyElement, IntoElement, Stateful};
+use gpui::{AccessibilityRole, AnyElement, IntoElement, Stateful};
 use smallvec::SmallVec;
 
 use crate::{prelude::*, BASE_REM_SIZE_IN_PX};
@@ -43,6 +43,7 @@ impl Tab {
         Self {
             div: div()
                 .id(id.clone())
+                .accessibility_role(AccessibilityRole::Tab)
                 .debug_selector(|| format!("TAB-{}", id)),
             selected: false,
             position: TabPosition::First,
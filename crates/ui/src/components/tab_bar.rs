@@ -1,4 +1,4 @@
-use gpui::{AnyElement, ScrollHandle};
+use gpui::{AccessibilityRole, AnyElement, ScrollHandle};
 use smallvec::SmallVec;
 
 use crate::prelude::*;
@@ -92,6 +92,7 @@ impl RenderOnce for TabBar {
     fn render(self, cx: &mut WindowContext) -> impl IntoElement {
         div()
             .id(self.id)
+            .accessibility_role(AccessibilityRole::TabList)
             .group("tab_bar")
             .flex()
             .flex_none()
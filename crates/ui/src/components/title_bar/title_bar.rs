@@ -11,6 +11,7 @@ pub struct TitleBar {
     content: Stateful<Div>,
     children: SmallVec<[AnyElement; 2]>,
     close_window_action: Box<dyn Action>,
+    background_color: Option<Hsla>,
 }
 
 impl TitleBar {
@@ -53,6 +54,7 @@ impl TitleBar {
             content: div().id(id.into()),
             children: SmallVec::new(),
             close_window_action,
+            background_color: None,
         }
     }
 
@@ -61,6 +63,12 @@ impl TitleBar {
         self.platform_style = style;
         self
     }
+
+    /// Sets the background color, in place of the theme's title bar background.
+    pub fn background_color(mut self, color: Hsla) -> Self {
+        self.background_color = Some(color);
+        self
+    }
 }
 
 impl InteractiveElement for TitleBar {
@@ -99,7 +107,9 @@ impl RenderOnce for TitleBar {
                     this.pl_2()
                 }
             })
-            .bg(cx.theme().colors().title_bar_background)
+            .bg(self
+                .background_color
+                .unwrap_or(cx.theme().colors().title_bar_background))
             .content_stretch()
             .child(
                 self.content
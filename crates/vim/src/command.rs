@@ -1,18 +1,25 @@
+use std::{ops::RangeInclusive, path::PathBuf};
+
+use anyhow::anyhow;
 use command_palette_hooks::CommandInterceptResult;
-use editor::actions::{SortLinesCaseInsensitive, SortLinesCaseSensitive};
+use editor::{
+    actions::{SortLinesCaseInsensitive, SortLinesCaseSensitive},
+    Editor, ToPoint,
+};
 use gpui::{impl_actions, Action, AppContext, ViewContext};
 use serde_derive::Deserialize;
+use util::paths::home_dir;
 use workspace::{SaveIntent, Workspace};
 
 use crate::{
     motion::{EndOfDocument, Motion, StartOfDocument},
     normal::{
         move_cursor,
-        search::{range_regex, FindCommand, ReplaceCommand},
+        search::{FindCommand, GlobalCommand, ReplaceCommand},
         JoinLines,
     },
     state::Mode,
-    Vim,
+    ShowRegisters, Vim,
 };
 
 #[derive(Debug, Clone, PartialEq, Deserialize)]
@@ -20,7 +27,16 @@ pub struct GoToLine {
     pub line: u32,
 }
 
-impl_actions!(vim, [GoToLine]);
+/// Opens the given file, or reloads the current one from disk when no path is given.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct EditFile {
+    #[serde(default)]
+    pub path: String,
+    #[serde(default)]
+    pub force: bool,
+}
+
+impl_actions!(vim, [GoToLine, EditFile]);
 
 pub fn register(workspace: &mut Workspace, _: &mut ViewContext<Workspace>) {
     workspace.register_action(|_: &mut Workspace, action: &GoToLine, cx| {
@@ -29,18 +45,207 @@ pub fn register(workspace: &mut Workspace, _: &mut ViewContext<Workspace>) {
             move_cursor(vim, Motion::StartOfDocument, Some(action.line as usize), cx);
         });
     });
+    workspace.register_action(edit_file);
+}
+
+fn edit_file(workspace: &mut Workspace, action: &EditFile, cx: &mut ViewContext<Workspace>) {
+    if action.path.is_empty() {
+        let Some(item) = workspace.active_item(cx) else {
+            return;
+        };
+        if item.is_dirty(cx) && !action.force {
+            workspace.show_error(
+                &anyhow!("No write since last change (add ! to override)"),
+                cx,
+            );
+            return;
+        }
+        let project = workspace.project().clone();
+        item.reload(project, cx).detach_and_log_err(cx);
+        return;
+    }
+
+    let path = match action.path.strip_prefix("~/") {
+        Some(path) => home_dir().join(path),
+        None => PathBuf::from(&action.path),
+    };
+    let path = if path.is_absolute() {
+        path
+    } else {
+        let Some(worktree) = workspace.visible_worktrees(cx).next() else {
+            return;
+        };
+        worktree.read(cx).abs_path().join(path)
+    };
+    workspace
+        .open_abs_path(path, true, cx)
+        .detach_and_log_err(cx);
+}
+
+/// A line address in an ex command range, such as `3`, `.`, `$` or `'a`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Address {
+    Line(u32),
+    CurrentLine,
+    LastLine,
+    Mark(char),
+}
+
+/// An [`Address`] shifted by a number of lines, such as `.+3` or `$-1`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Position {
+    address: Address,
+    offset: i64,
+}
+
+impl Position {
+    fn parse(query: &str) -> Option<(Self, &str)> {
+        let mut rest = query;
+        let mut address = None;
+        if let Some(remaining) = rest.strip_prefix('.') {
+            address = Some(Address::CurrentLine);
+            rest = remaining;
+        } else if let Some(remaining) = rest.strip_prefix('$') {
+            address = Some(Address::LastLine);
+            rest = remaining;
+        } else if let Some(remaining) = rest.strip_prefix('\'') {
+            let mark = remaining.chars().next()?;
+            address = Some(Address::Mark(mark));
+            rest = &remaining[mark.len_utf8()..];
+        } else {
+            let digits = leading_digits(rest);
+            if digits > 0 {
+                address = Some(Address::Line(rest[..digits].parse().ok()?));
+                rest = &rest[digits..];
+            }
+        }
+
+        let mut offset = 0;
+        let mut has_offset = false;
+        loop {
+            let sign = match rest.chars().next() {
+                Some('+') => 1,
+                Some('-') => -1,
+                _ => break,
+            };
+            rest = &rest[1..];
+            let digits = leading_digits(rest);
+            let amount = if digits > 0 {
+                rest[..digits].parse::<i64>().ok()?
+            } else {
+                1
+            };
+            offset += sign * amount;
+            rest = &rest[digits..];
+            has_offset = true;
+        }
+
+        if address.is_none() && !has_offset {
+            return None;
+        }
+        let position = Position {
+            address: address.unwrap_or(Address::CurrentLine),
+            offset,
+        };
+        Some((position, rest))
+    }
+
+    fn buffer_row(&self, vim: &Vim, editor: &Editor, cx: &AppContext) -> Option<u32> {
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        let max_row = snapshot.max_point().row;
+        let row = match self.address {
+            Address::Line(line) => line.saturating_sub(1),
+            Address::CurrentLine => editor.selections.newest::<language::Point>(cx).head().row,
+            Address::LastLine => max_row,
+            Address::Mark(mark) => {
+                let anchors = vim.state().marks.get(&mark.to_string())?;
+                anchors.last()?.to_point(&snapshot).row
+            }
+        };
+        let row = u32::try_from(row as i64 + self.offset).ok()?;
+        (row <= max_row).then_some(row)
+    }
+}
+
+fn leading_digits(text: &str) -> usize {
+    text.find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len())
+}
+
+/// The lines an ex command applies to, such as `%`, `3,5` or `.,$-1`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum CommandRange {
+    Buffer,
+    Lines(Position, Option<Position>),
+}
+
+impl CommandRange {
+    pub(crate) const CURRENT_LINE: Self = Self::Lines(
+        Position {
+            address: Address::CurrentLine,
+            offset: 0,
+        },
+        None,
+    );
+
+    /// Parses the range at the start of an ex command, returning it along with the
+    /// rest of the command.
+    pub(crate) fn parse(query: &str) -> (Option<Self>, &str) {
+        if let Some(rest) = query.strip_prefix('%') {
+            return (Some(Self::Buffer), rest);
+        }
+        let (start, rest) = match Position::parse(query) {
+            Some((start, rest)) => (start, rest),
+            None if query.starts_with([',', ';']) => (
+                Position {
+                    address: Address::CurrentLine,
+                    offset: 0,
+                },
+                query,
+            ),
+            None => return (None, query),
+        };
+        let Some(rest) = rest.strip_prefix([',', ';']) else {
+            return (Some(Self::Lines(start, None)), rest);
+        };
+        match Position::parse(rest) {
+            Some((end, rest)) => (Some(Self::Lines(start, Some(end))), rest),
+            None => (Some(Self::Lines(start, Some(start))), rest),
+        }
+    }
+
+    /// Resolves the range to buffer rows in the given editor, returning `None` if
+    /// it points outside of the buffer or to a mark that isn't set.
+    pub(crate) fn buffer_rows(
+        &self,
+        vim: &Vim,
+        editor: &Editor,
+        cx: &AppContext,
+    ) -> Option<RangeInclusive<u32>> {
+        match self {
+            Self::Buffer => {
+                let max_row = editor.buffer().read(cx).snapshot(cx).max_point().row;
+                Some(0..=max_row)
+            }
+            Self::Lines(start, end) => {
+                let start = start.buffer_row(vim, editor, cx)?;
+                let end = match end {
+                    Some(end) => end.buffer_row(vim, editor, cx)?,
+                    None => start,
+                };
+                Some(start.min(end)..=start.max(end))
+            }
+        }
+    }
 }
 
 pub fn command_interceptor(mut query: &str, cx: &AppContext) -> Option<CommandInterceptResult> {
     // Note: this is a very poor simulation of vim's command palette.
-    // In the future we should adjust it to handle parsing range syntax,
-    // and then calling the appropriate commands with/without ranges.
+    // Ranges are only understood by the commands that substitute (:s)
+    // or run on matching lines (:g), and a plain line number goes to that line.
     //
     // We also need to support passing arguments to commands like :w
     // (ideally with filename autocompletion).
-    //
-    // For now, you can only do a replace on the % range, and you can
-    // only use a specific line number range to "go to line"
     while query.starts_with(':') {
         query = &query[1..];
     }
@@ -264,6 +469,16 @@ pub fn command_interceptor(mut query: &str, cx: &AppContext) -> Option<CommandIn
         "sor" | "sor " | "sort" | "sort " => ("sort", SortLinesCaseSensitive.boxed_clone()),
         "sor i" | "sort i" => ("sort i", SortLinesCaseInsensitive.boxed_clone()),
 
+        // search highlights
+        "noh" | "nohl" | "nohls" | "nohlse" | "nohlsea" | "nohlsear" | "nohlsearc"
+        | "nohlsearch" => ("nohlsearch", editor::actions::Cancel.boxed_clone()),
+
+        // registers
+        "reg" | "regi" | "regis" | "regist" | "registe" | "register" | "registers" | "di"
+        | "dis" | "disp" | "displ" | "displa" | "display" => {
+            ("registers", ShowRegisters.boxed_clone())
+        }
+
         // Explore, etc.
         "E" | "Ex" | "Exp" | "Expl" | "Explo" | "Explor" | "Explore" => (
             "Explore",
@@ -330,26 +545,38 @@ pub fn command_interceptor(mut query: &str, cx: &AppContext) -> Option<CommandIn
                     }
                     .boxed_clone(),
                 )
-            } else if query.starts_with('%') {
-                (
-                    query,
-                    ReplaceCommand {
-                        query: query.to_string(),
-                    }
-                    .boxed_clone(),
-                )
             } else if let Ok(line) = query.parse::<u32>() {
                 (query, GoToLine { line }.boxed_clone())
-            } else if range_regex().is_match(query) {
+            } else if let Some((path, force)) = parse_edit(query) {
                 (
                     query,
-                    ReplaceCommand {
-                        query: query.to_string(),
+                    EditFile {
+                        path: path.to_string(),
+                        force,
                     }
                     .boxed_clone(),
                 )
             } else {
-                return None;
+                let (_, command) = CommandRange::parse(query);
+                if is_substitute(command) {
+                    (
+                        query,
+                        ReplaceCommand {
+                            query: query.to_string(),
+                        }
+                        .boxed_clone(),
+                    )
+                } else if is_global(command) {
+                    (
+                        query,
+                        GlobalCommand {
+                            query: query.to_string(),
+                        }
+                        .boxed_clone(),
+                    )
+                } else {
+                    return None;
+                }
             }
         }
     };
@@ -364,6 +591,35 @@ pub fn command_interceptor(mut query: &str, cx: &AppContext) -> Option<CommandIn
     })
 }
 
+fn parse_edit(query: &str) -> Option<(&str, bool)> {
+    let (command, path) = query.split_once(' ').unwrap_or((query, ""));
+    let (command, force) = match command.strip_suffix('!') {
+        Some(command) => (command, true),
+        None => (command, false),
+    };
+    matches!(command, "e" | "ed" | "edi" | "edit").then(|| (path.trim(), force))
+}
+
+fn is_pattern_delimiter(c: char) -> bool {
+    !c.is_alphanumeric() && !c.is_whitespace() && !matches!(c, '\\' | '"' | '|')
+}
+
+fn is_substitute(command: &str) -> bool {
+    command
+        .strip_prefix('s')
+        .and_then(|rest| rest.chars().next())
+        .map_or(false, is_pattern_delimiter)
+}
+
+fn is_global(command: &str) -> bool {
+    let rest = command
+        .strip_prefix("g!")
+        .or_else(|| command.strip_prefix('g'))
+        .or_else(|| command.strip_prefix('v'));
+    rest.and_then(|rest| rest.chars().next())
+        .map_or(false, is_pattern_delimiter)
+}
+
 fn generate_positions(string: &str, query: &str) -> Vec<usize> {
     let mut positions = Vec::new();
     let mut chars = query.chars();
@@ -388,9 +644,13 @@ fn generate_positions(string: &str, query: &str) -> Vec<usize> {
 
 #[cfg(test)]
 mod test {
-    use std::path::Path;
+    use std::{path::Path, time::Duration};
 
-    use crate::test::{NeovimBackedTestContext, VimTestContext};
+    use crate::{
+        state::Mode,
+        test::{NeovimBackedTestContext, VimTestContext},
+    };
+    use editor::Editor;
     use gpui::TestAppContext;
     use indoc::indoc;
 
@@ -516,4 +776,91 @@ mod test {
         cx.simulate_keystrokes(": q a enter");
         cx.workspace(|workspace, cx| assert_eq!(workspace.items(cx).count(), 0));
     }
+
+    #[gpui::test]
+    async fn test_command_ranges(cx: &mut TestAppContext) {
+        let mut cx = NeovimBackedTestContext::new(cx).await;
+
+        cx.set_shared_state(indoc! {"
+            a
+            ˇa
+            a
+            a"})
+            .await;
+        cx.simulate_shared_keystrokes(": s / a / b enter").await;
+        cx.shared_state().await.assert_eq(indoc! {"
+            a
+            ˇb
+            a
+            a"});
+        cx.executor().advance_clock(Duration::from_millis(250));
+        cx.run_until_parked();
+
+        cx.simulate_shared_keystrokes(": . + 1 , $ s / a / c enter")
+            .await;
+        cx.shared_state().await.assert_eq(indoc! {"
+            a
+            b
+            c
+            ˇc"});
+    }
+
+    #[gpui::test]
+    async fn test_command_global(cx: &mut TestAppContext) {
+        let mut cx = NeovimBackedTestContext::new(cx).await;
+
+        cx.set_shared_state(indoc! {"
+            ˇa
+            b
+            a
+            c
+            ab"})
+            .await;
+        cx.simulate_shared_keystrokes(": g / a / s / a / x enter")
+            .await;
+        cx.shared_state().await.assert_eq(indoc! {"
+            x
+            b
+            x
+            c
+            ˇxb"});
+        cx.simulate_shared_keystrokes(": v / x / d enter").await;
+        cx.shared_state().await.assert_eq(indoc! {"
+            x
+            x
+            ˇxb"});
+    }
+
+    #[gpui::test]
+    async fn test_command_global_with_flags(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        // when counting matches, nothing is substituted
+        cx.set_state(
+            indoc! {"
+            ˇa
+            b
+            ab"},
+            Mode::Normal,
+        );
+        cx.simulate_keystrokes(": g / a / s / / x / n enter");
+        cx.run_until_parked();
+        assert_eq!(cx.buffer_text(), "a\nb\nab");
+    }
+
+    #[gpui::test]
+    async fn test_command_edit(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        let fs = cx.workspace(|workspace, cx| workspace.project().read(cx).fs().clone());
+        fs.as_fake()
+            .insert_file("/root/dir/other.rs", b"other\n".to_vec())
+            .await;
+
+        cx.simulate_keystrokes(": e space d i r / o t h e r . r s enter");
+        cx.run_until_parked();
+        cx.workspace(|workspace, cx| {
+            let editor = workspace.active_item_as::<Editor>(cx).unwrap();
+            assert_eq!(editor.read(cx).text(cx), "other\n");
+        });
+    }
 }
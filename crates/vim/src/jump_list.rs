@@ -0,0 +1,102 @@
+use editor::{Anchor, Direction};
+use gpui::{actions, ViewContext, WindowContext};
+use workspace::Workspace;
+
+use crate::Vim;
+
+actions!(vim, [JumpListOlder, JumpListNewer]);
+
+pub(crate) fn register(workspace: &mut Workspace, _: &mut ViewContext<Workspace>) {
+    workspace.register_action(|workspace, _: &JumpListOlder, cx| {
+        move_in_jump_list(workspace, Direction::Prev, cx);
+    });
+    workspace.register_action(|workspace, _: &JumpListNewer, cx| {
+        move_in_jump_list(workspace, Direction::Next, cx);
+    });
+}
+
+// The jump list is the pane's navigation history, so that jumps made with vim motions and
+// with the rest of Zed (such as go to definition or opening a file) are navigated together.
+fn move_in_jump_list(
+    workspace: &mut Workspace,
+    direction: Direction,
+    cx: &mut ViewContext<Workspace>,
+) {
+    let count = Vim::update(cx, |vim, cx| vim.take_count(cx)).unwrap_or(1);
+    let pane = workspace.active_pane().downgrade();
+    cx.spawn(|workspace, mut cx| async move {
+        for _ in 0..count {
+            workspace
+                .update(&mut cx, |workspace, cx| match direction {
+                    Direction::Prev => workspace.go_back(pane.clone(), cx),
+                    Direction::Next => workspace.go_forward(pane.clone(), cx),
+                })?
+                .await?;
+        }
+        anyhow::Ok(())
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Adds a jump made by a vim motion from `origin` to the jump list.
+pub(crate) fn push_to_jump_list(vim: &mut Vim, origin: Anchor, cx: &mut WindowContext) {
+    vim.update_active_editor(cx, |_, editor, cx| {
+        editor.push_jump_to_nav_history(origin, cx)
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use indoc::indoc;
+
+    use crate::{state::Mode, test::VimTestContext};
+
+    #[gpui::test]
+    async fn test_jump_list(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state(
+            indoc! {"
+                ˇa
+                b
+                c
+                d"},
+            Mode::Normal,
+        );
+        // jumps are remembered however short they are, other motions are not
+        cx.simulate_keystrokes("shift-g k k");
+        cx.simulate_keystrokes("ctrl-o");
+        cx.run_until_parked();
+        cx.assert_state(
+            indoc! {"
+                ˇa
+                b
+                c
+                d"},
+            Mode::Normal,
+        );
+
+        cx.simulate_keystrokes("ctrl-i");
+        cx.run_until_parked();
+        cx.assert_state(
+            indoc! {"
+                a
+                ˇb
+                c
+                d"},
+            Mode::Normal,
+        );
+
+        cx.simulate_keystrokes("g g shift-g");
+        cx.simulate_keystrokes("2 ctrl-o");
+        cx.run_until_parked();
+        cx.assert_state(
+            indoc! {"
+                a
+                ˇb
+                c
+                d"},
+            Mode::Normal,
+        );
+    }
+}
@@ -14,6 +14,7 @@ use std::ops::Range;
 use workspace::Workspace;
 
 use crate::{
+    jump_list::push_to_jump_list,
     normal::{mark, normal_motion},
     state::{Mode, Operator},
     surrounds::SurroundsType,
@@ -411,6 +412,19 @@ pub(crate) fn search_motion(m: Motion, cx: &mut WindowContext) {
             }
             Mode::Normal | Mode::Replace | Mode::Insert => {
                 if Vim::read(cx).active_operator().is_none() {
+                    // the search bar already moved the cursor, remember where it came from.
+                    let prior_heads = prior_selections
+                        .iter()
+                        .map(|selection| selection.end)
+                        .collect::<Vec<_>>();
+                    if let Some(origin) = prior_heads.last().copied() {
+                        Vim::update(cx, |vim, cx| {
+                            vim.update_state(|state| {
+                                state.marks.insert("'".to_string(), prior_heads)
+                            });
+                            push_to_jump_list(vim, origin, cx);
+                        });
+                    }
                     return;
                 }
             }
@@ -429,6 +443,11 @@ pub(crate) fn motion(motion: Motion, cx: &mut WindowContext) {
 
     let count = Vim::update(cx, |vim, cx| vim.take_count(cx));
     let active_operator = Vim::read(cx).active_operator();
+    let jump_origin = if active_operator.is_none() && motion.is_jump() {
+        Vim::update(cx, |vim, cx| mark::save_jump_position(vim, cx))
+    } else {
+        None
+    };
     let mut waiting_operator: Option<Operator> = None;
     match Vim::read(cx).state().mode {
         Mode::Normal | Mode::Replace => {
@@ -453,6 +472,9 @@ pub(crate) fn motion(motion: Motion, cx: &mut WindowContext) {
             vim.push_operator(operator, cx);
             vim.update_state(|state| state.pre_count = count)
         }
+        if let Some(origin) = jump_origin {
+            push_to_jump_list(vim, origin, cx);
+        }
     });
 }
 
@@ -502,6 +524,23 @@ impl Motion {
         }
     }
 
+    /// Whether the motion is a jump, which updates the `'` mark so that it
+    /// can be undone with `''`.
+    fn is_jump(&self) -> bool {
+        use Motion::*;
+        matches!(
+            self,
+            StartOfDocument
+                | EndOfDocument
+                | Matching
+                | StartOfParagraph
+                | EndOfParagraph
+                | WindowTop
+                | WindowMiddle
+                | WindowBottom
+        )
+    }
+
     pub fn infallible(&self) -> bool {
         use Motion::*;
         match self {
@@ -4,18 +4,27 @@ use editor::{
     display_map::{DisplaySnapshot, ToDisplayPoint},
     movement,
     scroll::Autoscroll,
-    Anchor, Bias, DisplayPoint,
+    Anchor, Bias, DisplayPoint, Editor,
 };
 use gpui::WindowContext;
-use language::SelectionGoal;
+use language::{Point, SelectionGoal, ToPoint};
+use util::ResultExt;
 
 use crate::{
+    jump_list::push_to_jump_list,
     motion::{self, Motion},
-    state::Mode,
+    persistence::DB,
+    state::{GlobalMark, Mode},
     Vim,
 };
 
 pub fn create_mark(vim: &mut Vim, text: Arc<str>, tail: bool, cx: &mut WindowContext) {
+    if is_global_mark(&text) {
+        create_global_mark(vim, &text, cx);
+        vim.clear_operator(cx);
+        return;
+    }
+
     let Some(anchors) = vim.update_active_editor(cx, |_, editor, _| {
         editor
             .selections
@@ -59,7 +68,184 @@ pub fn create_visual_marks(vim: &mut Vim, mode: Mode, cx: &mut WindowContext) {
     vim.clear_operator(cx);
 }
 
+/// Uppercase marks are global: they remember the file they were set in and can
+/// be jumped to from any other file in the workspace.
+fn is_global_mark(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars.next().is_some_and(|c| c.is_ascii_uppercase()) && chars.next().is_none()
+}
+
+fn create_global_mark(vim: &mut Vim, text: &str, cx: &mut WindowContext) {
+    let Some((workspace, mark)) = vim
+        .update_active_editor(cx, |_, editor, cx| {
+            let workspace = editor.workspace()?;
+            let head = editor.selections.newest_anchor().head();
+            let (buffer, anchor) = editor
+                .buffer()
+                .read(cx)
+                .text_anchor_for_position(head, cx)?;
+            let path = buffer.read(cx).file()?.as_local()?.abs_path(cx);
+            let point = anchor.to_point(&buffer.read(cx));
+            Some((
+                workspace,
+                GlobalMark {
+                    path,
+                    point,
+                    anchor: Some((buffer.downgrade(), anchor)),
+                },
+            ))
+        })
+        .flatten()
+    else {
+        return;
+    };
+
+    if let Some(workspace_id) = workspace.read(cx).database_id() {
+        let mark_name = text.to_string();
+        let path = mark.path.clone();
+        let point = mark.point;
+        cx.background_executor()
+            .spawn(async move {
+                DB.save_global_mark(workspace_id, mark_name, path, point.row, point.column)
+                    .await
+                    .log_err()
+            })
+            .detach();
+    }
+    vim.workspace_state
+        .global_marks
+        .entry(workspace.entity_id())
+        .or_default()
+        .insert(text.to_string(), mark);
+}
+
+/// Finds the position of an uppercase mark. If it is in the active editor, it
+/// is returned so that it can be used like any other mark, otherwise the file
+/// containing it is opened (unless an operator is pending).
+fn jump_to_global_mark(text: &str, line: bool, cx: &mut WindowContext) -> Option<Vec<Anchor>> {
+    let (workspace, mark) = Vim::update(cx, |vim, cx| {
+        let workspace = vim
+            .update_active_editor(cx, |_, editor, _| editor.workspace())
+            .flatten()?;
+        let mark = vim
+            .workspace_state
+            .global_marks
+            .get(&workspace.entity_id())
+            .and_then(|marks| marks.get(text))
+            .cloned()
+            .or_else(|| {
+                let workspace_id = workspace.read(cx).database_id()?;
+                let (path, row, column) = DB
+                    .get_global_mark(workspace_id, text.to_string())
+                    .log_err()
+                    .flatten()?;
+                Some(GlobalMark {
+                    path,
+                    point: Point::new(row, column),
+                    anchor: None,
+                })
+            })?;
+        Some((workspace, mark))
+    })?;
+
+    let buffer = mark
+        .anchor
+        .as_ref()
+        .and_then(|(buffer, anchor)| Some((buffer.upgrade()?, *anchor)));
+    let point = match &buffer {
+        Some((buffer, anchor)) => anchor.to_point(&buffer.read(cx)),
+        None => mark.point,
+    };
+
+    let anchor = Vim::update(cx, |vim, cx| {
+        vim.update_active_editor(cx, |_, editor, cx| {
+            let multi_buffer = editor.buffer().read(cx);
+            let active_buffer = multi_buffer.as_singleton()?;
+            let is_mark_buffer = match &buffer {
+                Some((buffer, _)) => buffer == &active_buffer,
+                None => active_buffer
+                    .read(cx)
+                    .file()
+                    .and_then(|file| file.as_local())
+                    .is_some_and(|file| file.abs_path(cx) == mark.path),
+            };
+            if !is_mark_buffer {
+                return None;
+            }
+            let snapshot = multi_buffer.snapshot(cx);
+            Some(snapshot.anchor_before(snapshot.clip_point(point, Bias::Left)))
+        })
+        .flatten()
+    });
+    if let Some(anchor) = anchor {
+        return Some(vec![anchor]);
+    }
+
+    let is_active_operator = Vim::update(cx, |vim, cx| {
+        let is_active_operator = vim.state().active_operator().is_some();
+        if is_active_operator {
+            vim.clear_operator(cx);
+        } else {
+            save_jump_position(vim, cx);
+        }
+        is_active_operator
+    });
+    if is_active_operator {
+        return None;
+    }
+
+    let open = workspace.update(cx, |workspace, cx| {
+        workspace.open_abs_path(mark.path.clone(), true, cx)
+    });
+    cx.spawn(|mut cx| async move {
+        let item = open.await?;
+        let Some(editor) = item.downcast::<Editor>() else {
+            return Ok(());
+        };
+        editor.update(&mut cx, |editor, cx| {
+            let map = editor.snapshot(cx).display_snapshot;
+            let point = map.buffer_snapshot.clip_point(point, Bias::Left);
+            let mut point = point.to_display_point(&map);
+            if line {
+                point = motion::first_non_whitespace(&map, false, point);
+            }
+            editor.change_selections(Some(Autoscroll::center()), cx, |s| {
+                s.select_display_ranges([point..point])
+            })
+        })
+    })
+    .detach_and_log_err(cx);
+    None
+}
+
+/// Remembers the cursor positions as the `'` mark before jumping somewhere
+/// else, so that `''` can return to them.
+pub fn save_jump_position(vim: &mut Vim, cx: &mut WindowContext) -> Option<Anchor> {
+    let (anchors, newest) = vim.update_active_editor(cx, |_, editor, _| {
+        let anchors = editor
+            .selections
+            .disjoint_anchors()
+            .iter()
+            .map(|selection| selection.head())
+            .collect::<Vec<_>>();
+        (anchors, editor.selections.newest_anchor().head())
+    })?;
+    vim.update_state(|state| state.marks.insert("'".to_string(), anchors));
+    Some(newest)
+}
+
 pub fn jump(text: Arc<str>, line: bool, cx: &mut WindowContext) {
+    // `` ` `` and `'` refer to the same mark, the position before the latest jump.
+    let text: Arc<str> = if &*text == "`" { "'".into() } else { text };
+
+    if is_global_mark(&text) {
+        Vim::update(cx, |vim, cx| vim.pop_operator(cx));
+        if let Some(anchors) = jump_to_global_mark(&text, line, cx) {
+            jump_to_anchors(anchors, line, cx);
+        }
+        return;
+    }
+
     let anchors = Vim::update(cx, |vim, cx| {
         vim.pop_operator(cx);
 
@@ -85,7 +271,10 @@ pub fn jump(text: Arc<str>, line: bool, cx: &mut WindowContext) {
     });
 
     let Some(anchors) = anchors else { return };
+    jump_to_anchors(anchors, line, cx)
+}
 
+fn jump_to_anchors(anchors: Vec<Anchor>, line: bool, cx: &mut WindowContext) {
     let is_active_operator = Vim::read(cx).state().active_operator().is_some();
     if is_active_operator {
         if let Some(anchor) = anchors.last() {
@@ -100,6 +289,7 @@ pub fn jump(text: Arc<str>, line: bool, cx: &mut WindowContext) {
         return;
     } else {
         Vim::update(cx, |vim, cx| {
+            let origin = save_jump_position(vim, cx);
             vim.update_active_editor(cx, |_, editor, cx| {
                 let map = editor.snapshot(cx);
                 let mut ranges: Vec<Range<Anchor>> = Vec::new();
@@ -120,6 +310,9 @@ pub fn jump(text: Arc<str>, line: bool, cx: &mut WindowContext) {
                     s.select_anchor_ranges(ranges)
                 })
             });
+            if let Some(origin) = origin {
+                push_to_jump_list(vim, origin, cx);
+            }
         })
     }
 }
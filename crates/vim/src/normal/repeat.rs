@@ -1,14 +1,18 @@
+use std::{cell::RefCell, rc::Rc};
+
 use crate::{
     insert::NormalBefore,
     motion::Motion,
-    state::{Mode, RecordedSelection, ReplayableAction},
+    observe_action,
+    state::{Mode, Operator, RecordedSelection, ReplayableAction},
     visual::visual_motion,
     Vim,
 };
 use gpui::{actions, Action, ViewContext, WindowContext};
+use util::ResultExt;
 use workspace::Workspace;
 
-actions!(vim, [Repeat, EndRepeat]);
+actions!(vim, [Repeat, EndRepeat, ToggleRecord, ReplayLastRecording]);
 
 fn should_replay(action: &Box<dyn Action>) -> bool {
     // skip so that we don't leave the character palette open
@@ -43,11 +47,32 @@ pub(crate) fn register(workspace: &mut Workspace, _: &mut ViewContext<Workspace>
     workspace.register_action(|_: &mut Workspace, _: &EndRepeat, cx| {
         Vim::update(cx, |vim, cx| {
             vim.workspace_state.replaying = false;
+            vim.update_active_editor(cx, |_, editor, _| {
+                editor.show_local_selections = true;
+            });
             vim.switch_mode(Mode::Normal, false, cx)
         });
     });
 
     workspace.register_action(|_: &mut Workspace, _: &Repeat, cx| repeat(cx, false));
+
+    workspace.register_action(|_: &mut Workspace, _: &ToggleRecord, cx| {
+        Vim::update(cx, |vim, cx| {
+            if let Some(register) = vim.workspace_state.recording_register.take() {
+                vim.workspace_state.last_recorded_register = Some(register);
+                vim.sync_vim_settings(cx);
+            } else {
+                vim.push_operator(Operator::RecordRegister, cx);
+            }
+        })
+    });
+
+    workspace.register_action(|_: &mut Workspace, _: &ReplayLastRecording, cx| {
+        let Some(register) = Vim::read(cx).workspace_state.last_recorded_register else {
+            return;
+        };
+        replay_register(register, cx)
+    });
 }
 
 pub(crate) fn repeat(cx: &mut WindowContext, from_insert_mode: bool) {
@@ -165,42 +190,141 @@ pub(crate) fn repeat(cx: &mut WindowContext, from_insert_mode: bool) {
         actions = new_actions;
     }
 
-    Vim::update(cx, |vim, _| vim.workspace_state.replaying = true);
-    let window = cx.window_handle();
-    cx.spawn(move |mut cx| async move {
-        editor.update(&mut cx, |editor, _| {
-            editor.show_local_selections = false;
-        })?;
-        for action in actions {
-            if !matches!(
-                cx.update(|cx| Vim::read(cx).workspace_state.replaying),
-                Ok(true)
-            ) {
-                break;
-            }
+    actions.push(ReplayableAction::Action(EndRepeat.boxed_clone()));
+    editor
+        .update(cx, |editor, _| editor.show_local_selections = false)
+        .ok();
+    Vim::update(cx, |vim, cx| {
+        vim.workspace_state.replaying = true;
+        vim.workspace_state
+            .replayer
+            .get_or_insert_with(Replayer::new)
+            .replay(actions, cx);
+    });
+}
+
+pub(crate) fn replay_register(mut register: char, cx: &mut WindowContext) {
+    Vim::update(cx, |vim, cx| {
+        let count = vim.take_count(cx).unwrap_or(1);
+        vim.clear_operator(cx);
 
-            match action {
-                ReplayableAction::Action(action) => {
-                    if should_replay(&action) {
-                        window.update(&mut cx, |_, cx| cx.dispatch_action(action))
-                    } else {
-                        Ok(())
-                    }
+        if register == '@' {
+            let Some(last) = vim.workspace_state.last_replayed_register else {
+                return;
+            };
+            register = last;
+        }
+        let register = register.to_lowercase().next().unwrap_or(register);
+        let Some(actions) = vim.workspace_state.recordings.get(&register) else {
+            return;
+        };
+
+        let mut repeated_actions = vec![];
+        for _ in 0..count {
+            repeated_actions.extend(actions.iter().cloned());
+        }
+
+        vim.workspace_state.last_replayed_register = Some(register);
+
+        vim.workspace_state
+            .replayer
+            .get_or_insert_with(Replayer::new)
+            .replay(repeated_actions, cx);
+    });
+}
+
+/// The maximum number of actions replayed in one go, so that recursive
+/// macros don't loop forever.
+const MAX_REPLAYED_ACTIONS: usize = 10_000;
+
+#[derive(Default)]
+struct ReplayerState {
+    actions: Vec<ReplayableAction>,
+    running: bool,
+    ix: usize,
+}
+
+/// Replays recorded actions one at a time, both for `.` and for macros.
+///
+/// Actions replayed while another replay is in progress (e.g. a `.` inside of
+/// a macro) are spliced in right after the current action, so that they run
+/// to completion before the outer replay continues.
+#[derive(Clone, Default)]
+pub struct Replayer(Rc<RefCell<ReplayerState>>);
+
+impl Replayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn replay(&mut self, actions: Vec<ReplayableAction>, cx: &mut WindowContext) {
+        let mut state = self.0.borrow_mut();
+        let ix = state.ix;
+        state.actions.splice(ix..ix, actions);
+        if state.running {
+            return;
+        }
+        state.running = true;
+        let this = self.clone();
+        cx.defer(move |cx| this.next(cx))
+    }
+
+    pub fn stop(self) {
+        self.0.borrow_mut().actions.clear()
+    }
+
+    fn next(self, cx: &mut WindowContext) {
+        let mut state = self.0.borrow_mut();
+        let action = if state.ix < MAX_REPLAYED_ACTIONS {
+            state.actions.get(state.ix).cloned()
+        } else {
+            log::error!("aborting replay after {} actions", MAX_REPLAYED_ACTIONS);
+            None
+        };
+        state.ix += 1;
+        drop(state);
+
+        let Some(action) = action else {
+            Vim::update(cx, |vim, _| {
+                if vim
+                    .workspace_state
+                    .replayer
+                    .as_ref()
+                    .is_some_and(|replayer| Rc::ptr_eq(&replayer.0, &self.0))
+                {
+                    vim.workspace_state.replayer.take();
                 }
-                ReplayableAction::Insertion {
-                    text,
-                    utf16_range_to_replace,
-                } => editor.update(&mut cx, |editor, cx| {
-                    editor.replay_insert_event(&text, utf16_range_to_replace.clone(), cx)
-                }),
-            }?
+            });
+            return;
+        };
+        match action {
+            ReplayableAction::Action(action) => {
+                if should_replay(&action) {
+                    cx.dispatch_action(action.boxed_clone());
+                    cx.defer(move |cx| observe_action(action, cx));
+                }
+            }
+            ReplayableAction::Insertion {
+                text,
+                utf16_range_to_replace,
+            } => {
+                if let Some(editor) = Vim::read(cx).active_editor.clone() {
+                    Vim::update(cx, |vim, _| {
+                        vim.workspace_state.ignore_current_insertion = true
+                    });
+                    editor
+                        .update(cx, |editor, cx| {
+                            editor.replay_insert_event(&text, utf16_range_to_replace.clone(), cx)
+                        })
+                        .log_err();
+                    Vim::update(cx, |vim, _| {
+                        vim.workspace_state.ignore_current_insertion = false
+                    });
+                }
+            }
         }
-        editor.update(&mut cx, |editor, _| {
-            editor.show_local_selections = true;
-        })?;
-        window.update(&mut cx, |_, cx| cx.dispatch_action(EndRepeat.boxed_clone()))
-    })
-    .detach_and_log_err(cx);
+        cx.defer(move |cx| self.next(cx));
+    }
 }
 
 #[cfg(test)]
@@ -497,4 +621,40 @@ mod test {
         cx.simulate_shared_keystrokes(".").await;
         cx.shared_state().await.assert_eq("ˇx hello\n");
     }
+
+    #[gpui::test]
+    async fn test_record_replay(cx: &mut gpui::TestAppContext) {
+        let mut cx = NeovimBackedTestContext::new(cx).await;
+
+        cx.set_shared_state("ˇhello world").await;
+        cx.simulate_shared_keystrokes("q w c w j escape q").await;
+        cx.shared_state().await.assert_eq("ˇj world");
+        cx.simulate_shared_keystrokes("2 l @ w").await;
+        cx.shared_state().await.assert_eq("j ˇj");
+    }
+
+    #[gpui::test]
+    async fn test_record_replay_count(cx: &mut gpui::TestAppContext) {
+        let mut cx = NeovimBackedTestContext::new(cx).await;
+
+        cx.set_shared_state("ˇa\nb\nc\nd\ne\nf").await;
+        cx.simulate_shared_keystrokes("q a shift-a ! escape j q")
+            .await;
+        cx.shared_state().await.assert_eq("a!\nˇb\nc\nd\ne\nf");
+        cx.simulate_shared_keystrokes("2 @ a").await;
+        cx.shared_state().await.assert_eq("a!\nb!\nc!\nˇd\ne\nf");
+        cx.simulate_shared_keystrokes("@ @").await;
+        cx.shared_state().await.assert_eq("a!\nb!\nc!\nd!\nˇe\nf");
+    }
+
+    #[gpui::test]
+    async fn test_record_replay_append(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("ˇone two three", Mode::Normal);
+        cx.simulate_keystrokes("q a x q q shift-a w q");
+        cx.assert_state("ne ˇtwo three", Mode::Normal);
+        cx.simulate_keystrokes("@ a");
+        cx.assert_state("ne wo ˇthree", Mode::Normal);
+    }
 }
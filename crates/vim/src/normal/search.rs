@@ -1,13 +1,16 @@
-use std::{ops::Range, sync::OnceLock, time::Duration};
+use std::{ops::RangeInclusive, time::Duration};
 
-use gpui::{actions, impl_actions, ViewContext};
+use editor::{Editor, MultiBufferSnapshot, ToOffset};
+use gpui::{actions, impl_actions, View, ViewContext, WindowContext};
 use language::Point;
-use regex::Regex;
+use multi_buffer::MultiBufferRow;
+use regex::{Regex, RegexBuilder};
 use search::{buffer_search, BufferSearchBar, SearchOptions};
 use serde_derive::Deserialize;
-use workspace::{searchable::Direction, Workspace};
+use workspace::{searchable::Direction, Pane, Workspace};
 
 use crate::{
+    command::CommandRange,
     motion::{search_motion, Motion},
     normal::move_cursor,
     state::{Mode, SearchState},
@@ -45,26 +48,37 @@ pub struct ReplaceCommand {
     pub query: String,
 }
 
+/// Runs a command on every line matching (or, for `:g!` and `:v`, not matching) a pattern.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GlobalCommand {
+    pub query: String,
+}
+
 #[derive(Debug, Default)]
 struct Replacement {
     search: String,
     replacement: String,
     should_replace_all: bool,
     is_case_sensitive: bool,
-    range: Option<Range<usize>>,
+    /// The lines to replace in, `None` meaning the line containing the cursor.
+    range: Option<CommandRange>,
 }
 
+enum SubstitutionPreview {}
+
 actions!(vim, [SearchSubmit, MoveToNextMatch, MoveToPrevMatch]);
 impl_actions!(
     vim,
-    [FindCommand, ReplaceCommand, Search, MoveToPrev, MoveToNext]
+    [
+        FindCommand,
+        ReplaceCommand,
+        GlobalCommand,
+        Search,
+        MoveToPrev,
+        MoveToNext
+    ]
 );
 
-static RANGE_REGEX: OnceLock<Regex> = OnceLock::new();
-pub(crate) fn range_regex() -> &'static Regex {
-    RANGE_REGEX.get_or_init(|| Regex::new(r"^(\d+),(\d+)s(.*)").unwrap())
-}
-
 pub(crate) fn register(workspace: &mut Workspace, _: &mut ViewContext<Workspace>) {
     workspace.register_action(move_to_next);
     workspace.register_action(move_to_prev);
@@ -76,6 +90,7 @@ pub(crate) fn register(workspace: &mut Workspace, _: &mut ViewContext<Workspace>
 
     workspace.register_action(find_command);
     workspace.register_action(replace_command);
+    workspace.register_action(global_command);
 }
 
 fn move_to_next(workspace: &mut Workspace, action: &MoveToNext, cx: &mut ViewContext<Workspace>) {
@@ -343,23 +358,41 @@ fn replace_command(
     action: &ReplaceCommand,
     cx: &mut ViewContext<Workspace>,
 ) {
-    let replacement = parse_replace_all(&action.query);
+    let Some(replacement) = parse_replace_all(&action.query) else {
+        return;
+    };
     let pane = workspace.active_pane().clone();
-    let mut editor = Vim::read(cx)
+    let editor = Vim::read(cx)
         .active_editor
         .as_ref()
         .and_then(|editor| editor.upgrade());
-    if let Some(range) = &replacement.range {
-        if let Some(editor) = editor.as_mut() {
+    let range = replacement.range.unwrap_or(CommandRange::CURRENT_LINE);
+    if range != CommandRange::Buffer {
+        let rows = Vim::update(cx, |vim, cx| {
+            vim.update_active_editor(cx, |vim, editor, cx| range.buffer_rows(vim, editor, cx))
+        });
+        let Some(rows) = rows.flatten() else {
+            return;
+        };
+        if let Some(editor) = editor.as_ref() {
             editor.update(cx, |editor, cx| {
                 let snapshot = &editor.snapshot(cx).buffer_snapshot;
-                let range = snapshot
-                    .anchor_before(Point::new(range.start.saturating_sub(1) as u32, 0))
-                    ..snapshot.anchor_before(Point::new(range.end as u32, 0));
+                let range = rows_to_anchor_range(&rows, snapshot);
                 editor.set_search_within_ranges(&[range], cx)
             })
         }
     }
+    search_for_replacement(&pane, replacement, editor, cx);
+}
+
+/// Searches for the matches of the substitution in the search bar, replacing them all unless
+/// it should only count them or confirm each replacement.
+fn search_for_replacement(
+    pane: &View<Pane>,
+    replacement: Replacement,
+    editor: Option<View<Editor>>,
+    cx: &mut WindowContext,
+) {
     pane.update(cx, |pane, cx| {
         let Some(search_bar) = pane.toolbar().read(cx).item_of_type::<BufferSearchBar>() else {
             return;
@@ -423,28 +456,10 @@ fn replace_command(
 // we don't attempt to fully convert between the two regex syntaxes,
 // but we do flip \( and \) to ( and ) (and vice-versa) in the pattern,
 // and convert \0..\9 to $0..$9 in the replacement so that common idioms work.
-fn parse_replace_all(query: &str) -> Replacement {
-    let mut chars = query.chars();
-    let mut range = None;
-    let maybe_line_range_and_rest: Option<(Range<usize>, &str)> =
-        range_regex().captures(query).map(|captures| {
-            (
-                captures.get(1).unwrap().as_str().parse().unwrap()
-                    ..captures.get(2).unwrap().as_str().parse().unwrap(),
-                captures.get(3).unwrap().as_str(),
-            )
-        });
-    if maybe_line_range_and_rest.is_some() {
-        let (line_range, rest) = maybe_line_range_and_rest.unwrap();
-        range = Some(line_range);
-        chars = rest.chars();
-    } else if Some('%') != chars.next() || Some('s') != chars.next() {
-        return Replacement::default();
-    }
-
-    let Some(delimiter) = chars.next() else {
-        return Replacement::default();
-    };
+fn parse_replace_all(query: &str) -> Option<Replacement> {
+    let (range, command) = CommandRange::parse(query);
+    let mut chars = command.strip_prefix('s')?.chars();
+    let delimiter = chars.next().filter(|c| !c.is_alphanumeric())?;
 
     let mut search = String::new();
     let mut replacement = String::new();
@@ -507,7 +522,287 @@ fn parse_replace_all(query: &str) -> Replacement {
         }
     }
 
-    replacement
+    Some(replacement)
+}
+
+fn rows_to_anchor_range(
+    rows: &RangeInclusive<u32>,
+    snapshot: &MultiBufferSnapshot,
+) -> std::ops::Range<editor::Anchor> {
+    let end = Point::new(*rows.end() + 1, 0).min(snapshot.max_point());
+    snapshot.anchor_before(Point::new(*rows.start(), 0))..snapshot.anchor_before(end)
+}
+
+/// Highlights the text that the substitution being typed into the command palette would replace.
+pub(crate) fn preview_replace(query: &str, cx: &mut WindowContext) {
+    let query = query.trim_start_matches(':');
+    Vim::update(cx, |vim, cx| {
+        vim.update_active_editor(cx, |vim, editor, cx| {
+            editor.clear_background_highlights::<SubstitutionPreview>(cx);
+            let Some(replacement) = parse_replace_all(query) else {
+                return;
+            };
+            let range = replacement.range.unwrap_or(CommandRange::CURRENT_LINE);
+            let Some(rows) = range.buffer_rows(vim, editor, cx) else {
+                return;
+            };
+            if replacement.search.is_empty() {
+                return;
+            }
+            let Ok(regex) = RegexBuilder::new(&replacement.search)
+                .case_insensitive(!replacement.is_case_sensitive)
+                .build()
+            else {
+                return;
+            };
+
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let range = rows_to_anchor_range(&rows, &snapshot);
+            let start_offset = range.start.to_offset(&snapshot);
+            let text = snapshot.text_for_range(range.clone()).collect::<String>();
+            let ranges = regex
+                .find_iter(&text)
+                .filter(|found| !found.is_empty())
+                .map(|found| {
+                    snapshot.anchor_after(start_offset + found.start())
+                        ..snapshot.anchor_before(start_offset + found.end())
+                })
+                .collect::<Vec<_>>();
+            editor.highlight_background::<SubstitutionPreview>(
+                &ranges,
+                |colors| colors.search_match_background,
+                cx,
+            );
+        });
+    });
+}
+
+fn global_command(
+    workspace: &mut Workspace,
+    action: &GlobalCommand,
+    cx: &mut ViewContext<Workspace>,
+) {
+    let Some(global) = parse_global(&action.query) else {
+        return;
+    };
+    let Ok(regex) = Regex::new(&global.pattern) else {
+        return;
+    };
+    let Some(editor) = Vim::read(cx)
+        .active_editor
+        .as_ref()
+        .and_then(|editor| editor.upgrade())
+    else {
+        return;
+    };
+
+    let rows = Vim::update(cx, |vim, cx| {
+        vim.update_active_editor(cx, |vim, editor, cx| {
+            global
+                .range
+                .unwrap_or(CommandRange::Buffer)
+                .buffer_rows(vim, editor, cx)
+        })
+    });
+    let Some(rows) = rows.flatten() else {
+        return;
+    };
+
+    let command = global.command.trim();
+    let replacement = match command {
+        "d" | "de" | "del" | "dele" | "delet" | "delete" => None,
+        _ => match parse_replace_all(command) {
+            Some(replacement) => Some(replacement),
+            None => return,
+        },
+    };
+    let matching_rows = editor.update(cx, |editor, cx| {
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        rows.filter(|row| {
+            let line = line_text(*row, &snapshot);
+            regex.is_match(&line) != global.invert
+        })
+        .collect::<Vec<_>>()
+    });
+    if matching_rows.is_empty() {
+        return;
+    }
+
+    // like `:s`, with the `c` or `n` flags the matches are only searched for in the matching
+    // lines, to count them or to replace them one at a time from the search bar
+    let replacement = match replacement {
+        Some(mut replacement) if !replacement.should_replace_all => {
+            editor.update(cx, |editor, cx| {
+                let snapshot = editor.buffer().read(cx).snapshot(cx);
+                let ranges = matching_rows
+                    .iter()
+                    .map(|row| rows_to_anchor_range(&(*row..=*row), &snapshot))
+                    .collect::<Vec<_>>();
+                editor.set_search_within_ranges(&ranges, cx);
+            });
+            // an empty search reuses the pattern lines were matched against
+            if replacement.search.is_empty() {
+                replacement.search = global.pattern;
+            }
+            let pane = workspace.active_pane().clone();
+            search_for_replacement(&pane, replacement, Some(editor), cx);
+            return;
+        }
+        replacement => replacement,
+    };
+
+    let changed = editor.update(cx, |editor, cx| {
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        let last_row = *matching_rows.last()?;
+
+        // like vim, leave the cursor after the last deleted line, or on the last substituted one
+        let (edits, cursor_row) = match &replacement {
+            None => (
+                delete_rows(&matching_rows, &snapshot),
+                last_row + 1 - matching_rows.len() as u32,
+            ),
+            Some(replacement) => {
+                let edits = substitute_rows(&matching_rows, replacement, &regex, &snapshot);
+                let cursor_row = edits.last()?.0.start.row;
+                (edits, cursor_row)
+            }
+        };
+        editor.transact(cx, |editor, cx| {
+            editor.edit(edits, cx);
+            let max_row = editor.buffer().read(cx).snapshot(cx).max_point().row;
+            let cursor = Point::new(cursor_row.min(max_row), 0);
+            editor.change_selections(None, cx, |s| s.select_ranges([cursor..cursor]));
+        });
+        Some(())
+    });
+    if changed.is_none() {
+        return;
+    }
+    Vim::update(cx, |vim, cx| {
+        vim.switch_mode(Mode::Normal, false, cx);
+        move_cursor(
+            vim,
+            Motion::FirstNonWhitespace {
+                display_lines: false,
+            },
+            None,
+            cx,
+        )
+    });
+}
+
+struct Global {
+    pattern: String,
+    command: String,
+    invert: bool,
+    range: Option<CommandRange>,
+}
+
+fn parse_global(query: &str) -> Option<Global> {
+    let (range, command) = CommandRange::parse(query);
+    let (rest, invert) = if let Some(rest) = command.strip_prefix("g!") {
+        (rest, true)
+    } else if let Some(rest) = command.strip_prefix('g') {
+        (rest, false)
+    } else {
+        (command.strip_prefix('v')?, true)
+    };
+    let mut chars = rest.chars();
+    let delimiter = chars.next().filter(|c| !c.is_alphanumeric())?;
+
+    let mut pattern = String::new();
+    let mut escaped = false;
+    for c in chars.by_ref() {
+        if escaped {
+            escaped = false;
+            // unescape escaped parens
+            if c != '(' && c != ')' && c != delimiter {
+                pattern.push('\\')
+            }
+            pattern.push(c)
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == delimiter {
+            break;
+        } else {
+            // escape unescaped parens
+            if c == '(' || c == ')' {
+                pattern.push('\\')
+            }
+            pattern.push(c)
+        }
+    }
+
+    Some(Global {
+        pattern,
+        command: chars.collect(),
+        invert,
+        range,
+    })
+}
+
+fn line_text(row: u32, snapshot: &MultiBufferSnapshot) -> String {
+    let line_len = snapshot.line_len(MultiBufferRow(row));
+    snapshot
+        .text_for_range(Point::new(row, 0)..Point::new(row, line_len))
+        .collect()
+}
+
+fn delete_rows(
+    rows: &[u32],
+    snapshot: &MultiBufferSnapshot,
+) -> Vec<(std::ops::Range<Point>, String)> {
+    let max_point = snapshot.max_point();
+    let mut edits = Vec::new();
+    let mut rows = rows.iter().copied().peekable();
+    while let Some(start) = rows.next() {
+        let mut end = start;
+        while rows.peek() == Some(&(end + 1)) {
+            end = rows.next().unwrap();
+        }
+        // delete the newline preceding the last line, as it has none of its own
+        let range = if end < max_point.row {
+            Point::new(start, 0)..Point::new(end + 1, 0)
+        } else if start > 0 {
+            let previous_row = MultiBufferRow(start - 1);
+            Point::new(start - 1, snapshot.line_len(previous_row))..max_point
+        } else {
+            Point::zero()..max_point
+        };
+        edits.push((range, String::new()));
+    }
+    edits
+}
+
+fn substitute_rows(
+    rows: &[u32],
+    replacement: &Replacement,
+    pattern: &Regex,
+    snapshot: &MultiBufferSnapshot,
+) -> Vec<(std::ops::Range<Point>, String)> {
+    // an empty search reuses the pattern lines were matched against
+    let regex = if replacement.search.is_empty() {
+        Some(pattern.clone())
+    } else {
+        RegexBuilder::new(&replacement.search)
+            .case_insensitive(!replacement.is_case_sensitive)
+            .build()
+            .ok()
+    };
+    let Some(regex) = regex else {
+        return Vec::new();
+    };
+
+    rows.iter()
+        .filter_map(|row| {
+            let line = line_text(*row, snapshot);
+            let new_line = regex.replace_all(&line, replacement.replacement.as_str());
+            (new_line != line).then(|| {
+                let range = Point::new(*row, 0)..Point::new(*row, line.len() as u32);
+                (range, new_line.into_owned())
+            })
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -16,7 +16,9 @@ use editor::{
 use itertools::Itertools;
 
 use gpui::{actions, impl_actions, ViewContext, WindowContext};
-use language::{char_kind, BufferSnapshot, CharKind, Point, Selection};
+use language::{
+    char_kind, BufferSnapshot, CharKind, Point, Selection, TextObject, ToOffset, ToPoint,
+};
 use multi_buffer::MultiBufferRow;
 use serde::Deserialize;
 use workspace::Workspace;
@@ -36,6 +38,9 @@ pub enum Object {
     AngleBrackets,
     Argument,
     Tag,
+    Method,
+    Class,
+    Comment,
 }
 
 #[derive(Clone, Deserialize, PartialEq)]
@@ -61,7 +66,10 @@ actions!(
         CurlyBrackets,
         AngleBrackets,
         Argument,
-        Tag
+        Tag,
+        Method,
+        Class,
+        Comment
     ]
 );
 
@@ -99,6 +107,9 @@ pub fn register(workspace: &mut Workspace, _: &mut ViewContext<Workspace>) {
     });
     workspace
         .register_action(|_: &mut Workspace, _: &Argument, cx: _| object(Object::Argument, cx));
+    workspace.register_action(|_: &mut Workspace, _: &Method, cx: _| object(Object::Method, cx));
+    workspace.register_action(|_: &mut Workspace, _: &Class, cx: _| object(Object::Class, cx));
+    workspace.register_action(|_: &mut Workspace, _: &Comment, cx: _| object(Object::Comment, cx));
 }
 
 fn object(object: Object, cx: &mut WindowContext) {
@@ -126,7 +137,10 @@ impl Object {
             | Object::AngleBrackets
             | Object::CurlyBrackets
             | Object::SquareBrackets
-            | Object::Argument => true,
+            | Object::Argument
+            | Object::Method
+            | Object::Class
+            | Object::Comment => true,
         }
     }
 
@@ -141,7 +155,10 @@ impl Object {
             | Object::SquareBrackets
             | Object::Tag
             | Object::CurlyBrackets
-            | Object::AngleBrackets => true,
+            | Object::AngleBrackets
+            | Object::Method
+            | Object::Class
+            | Object::Comment => true,
         }
     }
 
@@ -164,7 +181,10 @@ impl Object {
             | Object::AngleBrackets
             | Object::VerticalBars
             | Object::Tag
-            | Object::Argument => Mode::Visual,
+            | Object::Argument
+            | Object::Method
+            | Object::Class
+            | Object::Comment => Mode::Visual,
             Object::Paragraph => Mode::VisualLine,
         }
     }
@@ -212,6 +232,31 @@ impl Object {
                 surrounding_markers(map, relative_to, around, self.is_multiline(), '<', '>')
             }
             Object::Argument => argument(map, relative_to, around),
+            Object::Method => {
+                let target = if around {
+                    TextObject::AroundFunction
+                } else {
+                    TextObject::InsideFunction
+                };
+                text_object(map, relative_to, target)
+            }
+            Object::Class => {
+                let target = if around {
+                    TextObject::AroundClass
+                } else {
+                    TextObject::InsideClass
+                };
+                text_object(map, relative_to, target)
+            }
+            Object::Comment => {
+                // most languages don't distinguish the inside of a comment, so fall back to the whole comment
+                let range = if around {
+                    None
+                } else {
+                    text_object(map, relative_to, TextObject::InsideComment)
+                };
+                range.or_else(|| text_object(map, relative_to, TextObject::AroundComment))
+            }
         }
     }
 
@@ -565,6 +610,56 @@ fn argument(
     }
 }
 
+/// Returns the smallest range of the given tree-sitter [`TextObject`] containing `relative_to`.
+///
+/// Outer text objects that span whole lines are extended to include those lines entirely, so
+/// that deleting a function does not leave an empty line behind.
+fn text_object(
+    map: &DisplaySnapshot,
+    relative_to: DisplayPoint,
+    target: TextObject,
+) -> Option<Range<DisplayPoint>> {
+    let snapshot = &map.buffer_snapshot;
+    let offset = relative_to.to_offset(map, Bias::Left);
+
+    let excerpt = snapshot.excerpt_containing(offset..offset)?;
+    let buffer = excerpt.buffer();
+    let buffer_offset = excerpt.map_offset_to_buffer(offset);
+
+    let mut range = buffer
+        .text_object_ranges(buffer_offset..buffer_offset)
+        .filter(|(range, text_object)| {
+            *text_object == target && range.start <= buffer_offset && buffer_offset < range.end
+        })
+        .map(|(range, _)| range)
+        .min_by_key(|range| range.len())?;
+
+    if target.around().is_none() {
+        let start = range.start.to_point(buffer);
+        let end = range.end.to_point(buffer);
+        let is_blank = |range: Range<Point>| {
+            buffer
+                .text_for_range(range)
+                .all(|text| text.trim().is_empty())
+        };
+        let line_end = Point::new(end.row, buffer.line_len(end.row));
+        if is_blank(Point::new(start.row, 0)..start)
+            && is_blank(end..line_end)
+            && end.row < buffer.max_point().row
+        {
+            range = Point::new(start.row, 0).to_offset(buffer)
+                ..Point::new(end.row + 1, 0).to_offset(buffer);
+        }
+    }
+
+    if excerpt.contains_buffer_range(range.clone()) {
+        let range = excerpt.map_range_from_buffer(range);
+        Some(range.start.to_display_point(map)..range.end.to_display_point(map))
+    } else {
+        None
+    }
+}
+
 fn sentence(
     map: &DisplaySnapshot,
     relative_to: DisplayPoint,
@@ -1437,6 +1532,63 @@ mod test {
         cx.assert_state("let a = [«test::call(first_arg)ˇ»]", Mode::Visual);
     }
 
+    #[gpui::test]
+    async fn test_syntax_tree_objects(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state(
+            indoc! {"
+                // a comment
+                // on two lines
+                impl Foo {
+                    fn foo() {
+                        let a = 1;
+                        ˇlet b = 2;
+                    }
+                }
+            "},
+            Mode::Normal,
+        );
+        cx.simulate_keystrokes("v i f");
+        cx.assert_state(
+            indoc! {"
+                // a comment
+                // on two lines
+                impl Foo {
+                    fn foo() {
+                        «let a = 1;
+                        let b = 2;ˇ»
+                    }
+                }
+            "},
+            Mode::Visual,
+        );
+
+        cx.simulate_keystrokes("escape");
+        cx.simulate_keystrokes("d a f");
+        cx.assert_state(
+            indoc! {"
+                // a comment
+                // on two lines
+                impl Foo {
+                ˇ}
+            "},
+            Mode::Normal,
+        );
+
+        cx.simulate_keystrokes("d a c");
+        cx.assert_state(
+            indoc! {"
+                // a comment
+                // on two lines
+                ˇ"},
+            Mode::Normal,
+        );
+
+        cx.simulate_keystrokes("g g d a g c");
+        cx.assert_state("ˇ", Mode::Normal);
+    }
+
     #[gpui::test]
     async fn test_delete_surrounding_character_objects(cx: &mut gpui::TestAppContext) {
         let mut cx = NeovimBackedTestContext::new(cx).await;
@@ -1,11 +1,12 @@
-use std::{fmt::Display, ops::Range, sync::Arc};
+use std::{fmt::Display, ops::Range, path::PathBuf, sync::Arc};
 
+use crate::normal::repeat::Replayer;
 use crate::surrounds::SurroundsType;
 use crate::{motion::Motion, object::Object};
 use collections::HashMap;
 use editor::{Anchor, ClipboardSelection};
-use gpui::{Action, ClipboardItem, KeyContext};
-use language::{CursorShape, Selection, TransactionId};
+use gpui::{Action, ClipboardItem, EntityId, KeyContext, WeakModel};
+use language::{Buffer, CursorShape, Point, Selection, TransactionId};
 use serde::{Deserialize, Serialize};
 use ui::SharedString;
 use workspace::searchable::Direction;
@@ -68,6 +69,8 @@ pub enum Operator {
     Uppercase,
     OppositeCase,
     Register,
+    RecordRegister,
+    ReplayRegister,
 }
 
 #[derive(Default, Clone)]
@@ -164,6 +167,30 @@ pub struct WorkspaceState {
 
     pub last_yank: Option<SharedString>,
     pub registers: HashMap<char, Register>,
+
+    /// The register a macro is currently being recorded into (set by `q{register}`).
+    pub recording_register: Option<char>,
+    pub last_recorded_register: Option<char>,
+    pub last_replayed_register: Option<char>,
+    /// Skips recording the next insertion into the macro, used for the
+    /// register name typed after `q` and for text inserted while replaying.
+    pub ignore_current_insertion: bool,
+    pub recordings: HashMap<char, Vec<ReplayableAction>>,
+    pub replayer: Option<Replayer>,
+
+    /// Uppercase marks, keyed by the workspace they were set in.
+    pub global_marks: HashMap<EntityId, HashMap<String, GlobalMark>>,
+}
+
+/// A mark set with an uppercase letter, which can be jumped to from any file
+/// in the workspace.
+#[derive(Clone, Debug)]
+pub struct GlobalMark {
+    pub path: PathBuf,
+    /// The position of the mark when it was set, used once the buffer is closed.
+    pub point: Point,
+    /// Tracks the position of the mark through edits while the buffer is open.
+    pub anchor: Option<(WeakModel<Buffer>, language::Anchor)>,
 }
 
 #[derive(Debug)]
@@ -228,6 +255,8 @@ impl EditorState {
                 | Some(Operator::FindBackward { .. })
                 | Some(Operator::Mark)
                 | Some(Operator::Register)
+                | Some(Operator::RecordRegister)
+                | Some(Operator::ReplayRegister)
                 | Some(Operator::Jump { .. })
         )
     }
@@ -319,6 +348,8 @@ impl Operator {
             Operator::Lowercase => "gu",
             Operator::OppositeCase => "g~",
             Operator::Register => "\"",
+            Operator::RecordRegister => "q",
+            Operator::ReplayRegister => "@",
         }
     }
 
@@ -330,6 +361,8 @@ impl Operator {
             | Operator::Jump { .. }
             | Operator::FindBackward { .. }
             | Operator::Register
+            | Operator::RecordRegister
+            | Operator::ReplayRegister
             | Operator::Replace
             | Operator::AddSurrounds { target: Some(_) }
             | Operator::ChangeSurrounds { .. }
@@ -4,15 +4,20 @@ use crate::{
     state::Mode,
     Vim,
 };
-use editor::{movement, scroll::Autoscroll, Bias};
+use editor::{
+    display_map::{DisplaySnapshot, ToDisplayPoint},
+    movement,
+    scroll::Autoscroll,
+    Bias, DisplayPoint,
+};
 use gpui::WindowContext;
-use language::BracketPair;
+use language::{BracketPair, Selection};
 use serde::Deserialize;
-use std::sync::Arc;
+use std::{ops::Range, sync::Arc};
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum SurroundsType {
     Motion(Motion),
-    Object(Object),
+    Object(Object, bool),
 }
 
 // This exists so that we can have Deserialize on Operators, but not on Motions.
@@ -50,9 +55,30 @@ pub fn add_surrounds(text: Arc<str>, target: SurroundsType, cx: &mut WindowConte
 
                 for selection in &display_selections {
                     let range = match &target {
-                        SurroundsType::Object(object) => {
-                            object.range(&display_map, selection.clone(), false)
-                        }
+                        SurroundsType::Object(object, around) => object
+                            .range(&display_map, selection.clone(), *around)
+                            .map(|mut range| {
+                                // Like in vim-surround, the whitespace included by an around object stays outside of the surrounds
+                                if *around {
+                                    let mut start = range.start.to_offset(&display_map, Bias::Left);
+                                    let mut end = range.end.to_offset(&display_map, Bias::Left);
+                                    for (ch, _) in display_map.buffer_chars_at(start) {
+                                        if start >= end || !ch.is_whitespace() {
+                                            break;
+                                        }
+                                        start += ch.len_utf8();
+                                    }
+                                    for (ch, _) in display_map.reverse_buffer_chars_at(end) {
+                                        if end <= start || !ch.is_whitespace() {
+                                            break;
+                                        }
+                                        end -= ch.len_utf8();
+                                    }
+                                    range = start.to_display_point(&display_map)
+                                        ..end.to_display_point(&display_map);
+                                }
+                                range
+                            }),
                         SurroundsType::Motion(motion) => {
                             let range = motion
                                 .range(
@@ -122,6 +148,11 @@ pub fn delete_surrounds(text: Arc<str>, cx: &mut WindowContext) {
     Vim::update(cx, |vim, cx| {
         vim.stop_recording();
 
+        if &*text == "t" {
+            replace_surrounding_tags(vim, "", "", cx);
+            return;
+        }
+
         // only legitimate surrounds can be removed
         let pair = match find_surround_pair(&all_support_surround_pair(), &text) {
             Some(pair) => pair.clone(),
@@ -214,7 +245,19 @@ pub fn delete_surrounds(text: Arc<str>, cx: &mut WindowContext) {
 }
 
 pub fn change_surrounds(text: Arc<str>, target: Object, cx: &mut WindowContext) {
-    if let Some(will_replace_pair) = object_to_bracket_pair(target) {
+    if target == Object::Tag {
+        Vim::update(cx, |vim, cx| {
+            vim.stop_recording();
+            let (open, close) = match find_surround_pair(&all_support_surround_pair(), &text) {
+                Some(pair) if pair.end != *text => {
+                    (format!("{} ", pair.start), format!(" {}", pair.end))
+                }
+                Some(pair) => (pair.start.clone(), pair.end.clone()),
+                None => (text.to_string(), text.to_string()),
+            };
+            replace_surrounding_tags(vim, &open, &close, cx);
+        });
+    } else if let Some(will_replace_pair) = object_to_bracket_pair(target) {
         Vim::update(cx, |vim, cx| {
             vim.stop_recording();
             vim.update_active_editor(cx, |_, editor, cx| {
@@ -334,7 +377,27 @@ pub fn check_and_move_to_valid_bracket_pair(
     cx: &mut WindowContext,
 ) -> bool {
     let mut valid = false;
-    if let Some(pair) = object_to_bracket_pair(object) {
+    if object == Object::Tag {
+        vim.update_active_editor(cx, |_, editor, cx| {
+            let (display_map, selections) = editor.selections.all_adjusted_display(cx);
+            let anchors = selections
+                .into_iter()
+                .map(|selection| {
+                    let start = selection.start.to_offset(&display_map, Bias::Left);
+                    match surrounding_tag_ranges(&display_map, selection) {
+                        Some((open_tag, _)) => {
+                            valid = true;
+                            open_tag.start..open_tag.start
+                        }
+                        None => start..start,
+                    }
+                })
+                .collect::<Vec<_>>();
+            editor.change_selections(Some(Autoscroll::fit()), cx, |s| {
+                s.select_ranges(anchors);
+            });
+        });
+    } else if let Some(pair) = object_to_bracket_pair(object) {
         vim.update_active_editor(cx, |_, editor, cx| {
             editor.transact(cx, |editor, cx| {
                 editor.set_clip_at_line_ends(false, cx);
@@ -378,6 +441,60 @@ pub fn check_and_move_to_valid_bracket_pair(
     return valid;
 }
 
+/// Replaces the opening and closing tags of the element surrounding each cursor with the given strings.
+fn replace_surrounding_tags(vim: &mut Vim, open: &str, close: &str, cx: &mut WindowContext) {
+    vim.update_active_editor(cx, |_, editor, cx| {
+        editor.transact(cx, |editor, cx| {
+            editor.set_clip_at_line_ends(false, cx);
+
+            let (display_map, selections) = editor.selections.all_adjusted_display(cx);
+            let mut edits = Vec::new();
+            let mut anchors = Vec::new();
+
+            for selection in selections {
+                let head = selection.head().to_offset(&display_map, Bias::Left);
+                let start = match surrounding_tag_ranges(&display_map, selection) {
+                    Some((open_tag, close_tag)) => {
+                        let start = open_tag.start;
+                        edits.push((open_tag, open.to_string()));
+                        edits.push((close_tag, close.to_string()));
+                        start
+                    }
+                    None => head,
+                };
+                let anchor = display_map.buffer_snapshot.anchor_before(start);
+                anchors.push(anchor..anchor);
+            }
+
+            // Cursors within the same element would otherwise edit its tags twice
+            edits.sort_by_key(|(range, _)| range.start);
+            edits.dedup_by_key(|(range, _)| range.start);
+            editor.buffer().update(cx, |buffer, cx| {
+                buffer.edit(edits, None, cx);
+            });
+            editor.set_clip_at_line_ends(true, cx);
+            editor.change_selections(Some(Autoscroll::fit()), cx, |s| {
+                s.select_anchor_ranges(anchors);
+            });
+        });
+    });
+}
+
+/// Returns the offset ranges of the opening and closing tags of the element surrounding the selection.
+fn surrounding_tag_ranges(
+    display_map: &DisplaySnapshot,
+    selection: Selection<DisplayPoint>,
+) -> Option<(Range<usize>, Range<usize>)> {
+    let around = Object::Tag.range(display_map, selection.clone(), true)?;
+    let inside = Object::Tag.range(display_map, selection, false)?;
+    Some((
+        around.start.to_offset(display_map, Bias::Left)
+            ..inside.start.to_offset(display_map, Bias::Left),
+        inside.end.to_offset(display_map, Bias::Left)
+            ..around.end.to_offset(display_map, Bias::Left),
+    ))
+}
+
 fn find_surround_pair<'a>(pairs: &'a [BracketPair], ch: &str) -> Option<&'a BracketPair> {
     pairs.iter().find(|pair| pair.start == ch || pair.end == ch)
 }
@@ -553,6 +670,23 @@ mod test {
             Mode::Normal,
         );
 
+        // test add surrounds with around object
+        cx.set_state(
+            indoc! {"
+            The quˇick brown
+            fox jumps over
+            the lazy dog."},
+            Mode::Normal,
+        );
+        cx.simulate_keystrokes("y s a w )");
+        cx.assert_state(
+            indoc! {"
+            The ˇ(quick) brown
+            fox jumps over
+            the lazy dog."},
+            Mode::Normal,
+        );
+
         // test add surrounds with motion
         cx.set_state(
             indoc! {"
@@ -984,4 +1118,21 @@ mod test {
             Mode::Normal,
         );
     }
+
+    #[gpui::test]
+    async fn test_tag_surrounds(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new_html(cx).await;
+
+        cx.set_state("<div><b>hˇi!</b></div>", Mode::Normal);
+        cx.simulate_keystrokes("d s t");
+        cx.assert_state("<div>ˇhi!</div>", Mode::Normal);
+
+        cx.set_state("<div><b>hˇi!</b></div>", Mode::Normal);
+        cx.simulate_keystrokes("c s t [");
+        cx.assert_state("<div>ˇ[ hi! ]</div>", Mode::Normal);
+
+        cx.set_state("<div><b>hˇi!</b></div>", Mode::Normal);
+        cx.simulate_keystrokes("c s t '");
+        cx.assert_state("<div>ˇ'hi!'</div>", Mode::Normal);
+    }
 }
@@ -14,7 +14,7 @@ pub use vim_test_context::*;
 use indoc::indoc;
 use search::BufferSearchBar;
 
-use crate::{insert::NormalBefore, motion, state::Mode, ModeIndicator};
+use crate::{insert::NormalBefore, motion, state::Mode, ModeIndicator, Vim};
 
 #[gpui::test]
 async fn test_initially_disabled(cx: &mut gpui::TestAppContext) {
@@ -1073,6 +1073,45 @@ async fn test_lowercase_marks(cx: &mut TestAppContext) {
         .assert_eq("line one\nˇtwo\nline three");
 }
 
+#[gpui::test]
+async fn test_registers_description(cx: &mut TestAppContext) {
+    let mut cx = VimTestContext::new(cx, true).await;
+
+    cx.set_state("ˇone two\nthree", Mode::Normal);
+    cx.simulate_keystrokes("\" a y w j \" b y y");
+    let description =
+        cx.update_workspace(|_, cx| Vim::update(cx, |vim, cx| vim.registers_description(cx)));
+    assert!(description.starts_with("Type Name Content\n"));
+    assert!(description.contains("  c  \"a   one \n"));
+    assert!(description.contains("  l  \"b   three^J\n"));
+}
+
+#[gpui::test]
+async fn test_uppercase_marks(cx: &mut TestAppContext) {
+    let mut cx = VimTestContext::new(cx, true).await;
+
+    cx.set_state("line one\nline ˇtwo\nline three", Mode::Normal);
+    cx.simulate_keystrokes("m shift-a j ' shift-a");
+    cx.assert_state("line one\nˇline two\nline three", Mode::Normal);
+    cx.simulate_keystrokes("` shift-a");
+    cx.assert_state("line one\nline ˇtwo\nline three", Mode::Normal);
+}
+
+#[gpui::test]
+async fn test_jump_marks(cx: &mut TestAppContext) {
+    let mut cx = NeovimBackedTestContext::new(cx).await;
+
+    cx.set_shared_state("line ˇone\nline two\nline three").await;
+    cx.simulate_shared_keystrokes("shift-g ' '").await;
+    cx.shared_state()
+        .await
+        .assert_eq("ˇline one\nline two\nline three");
+    cx.simulate_shared_keystrokes("` `").await;
+    cx.shared_state()
+        .await
+        .assert_eq("line one\nline two\nˇline three");
+}
+
 #[gpui::test]
 async fn test_lt_gt_marks(cx: &mut TestAppContext) {
     let mut cx = NeovimBackedTestContext::new(cx).await;
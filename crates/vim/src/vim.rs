@@ -7,10 +7,12 @@ mod change_list;
 mod command;
 mod editor_events;
 mod insert;
+mod jump_list;
 mod mode_indicator;
 mod motion;
 mod normal;
 mod object;
+mod persistence;
 mod replace;
 mod state;
 mod surrounds;
@@ -19,7 +21,9 @@ mod visual;
 use anyhow::Result;
 use change_list::push_to_change_list;
 use collections::HashMap;
-use command_palette_hooks::{CommandPaletteFilter, CommandPaletteInterceptor};
+use command_palette_hooks::{
+    CommandPaletteFilter, CommandPaletteInterceptor, CommandPalettePreview,
+};
 use editor::{
     movement::{self, FindRange},
     Anchor, Bias, Editor, EditorEvent, EditorMode, ToPoint,
@@ -38,7 +42,7 @@ use serde::Deserialize;
 use serde_derive::Serialize;
 use settings::{update_settings_file, Settings, SettingsSources, SettingsStore};
 use state::{EditorState, Mode, Operator, RecordedSelection, Register, WorkspaceState};
-use std::{ops::Range, sync::Arc};
+use std::{ops::Range, rc::Rc, sync::Arc};
 use surrounds::{add_surrounds, change_surrounds, delete_surrounds};
 use ui::BorrowAppContext;
 use visual::{visual_block_motion, visual_replace};
@@ -78,7 +82,8 @@ actions!(
         InnerObject,
         FindForward,
         FindBackward,
-        OpenDefaultKeymap
+        OpenDefaultKeymap,
+        ShowRegisters
     ]
 );
 
@@ -152,6 +157,15 @@ fn register(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
         });
     });
 
+    workspace.register_action(|_: &mut Workspace, _: &ShowRegisters, cx| {
+        let text = Vim::update(cx, |vim, cx| vim.registers_description(cx));
+        cx.emit(workspace::Event::OpenBundledFile {
+            text: text.into(),
+            title: "Registers",
+            language: "Plain Text",
+        });
+    });
+
     normal::register(workspace, cx);
     insert::register(workspace, cx);
     motion::register(workspace, cx);
@@ -160,6 +174,7 @@ fn register(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
     object::register(workspace, cx);
     visual::register(workspace, cx);
     change_list::register(workspace, cx);
+    jump_list::register(workspace, cx);
 }
 
 /// Called whenever an keystroke is typed so vim can observe all actions
@@ -171,18 +186,17 @@ fn observe_keystrokes(keystroke_event: &KeystrokeEvent, cx: &mut WindowContext)
         .map(|action| action.boxed_clone())
     {
         Vim::update(cx, |vim, _| {
-            if vim.workspace_state.recording {
+            if let Some(register) = vim.workspace_state.recording_register {
                 vim.workspace_state
-                    .recorded_actions
+                    .recordings
+                    .entry(register)
+                    .or_default()
                     .push(ReplayableAction::Action(action.boxed_clone()));
-
-                if vim.workspace_state.stop_recording_after_next_action {
-                    vim.workspace_state.recording = false;
-                    vim.workspace_state.stop_recording_after_next_action = false;
-                }
             }
         });
 
+        observe_action(action.boxed_clone(), cx);
+
         // Keystroke is handled by the vim system, so continue forward
         if action.name().starts_with("vim::") {
             return;
@@ -201,7 +215,9 @@ fn observe_keystrokes(keystroke_event: &KeystrokeEvent, cx: &mut WindowContext)
             | Operator::DeleteSurrounds
             | Operator::Mark
             | Operator::Jump { .. }
-            | Operator::Register,
+            | Operator::Register
+            | Operator::RecordRegister
+            | Operator::ReplayRegister,
         ) => {}
         Some(_) => {
             vim.clear_operator(cx);
@@ -210,6 +226,23 @@ fn observe_keystrokes(keystroke_event: &KeystrokeEvent, cx: &mut WindowContext)
     });
 }
 
+/// Records an action so that it can be repeated with `.`, this is called for
+/// both typed and replayed actions.
+fn observe_action(action: Box<dyn Action>, cx: &mut WindowContext) {
+    Vim::update(cx, |vim, _| {
+        if vim.workspace_state.recording {
+            vim.workspace_state
+                .recorded_actions
+                .push(ReplayableAction::Action(action));
+
+            if vim.workspace_state.stop_recording_after_next_action {
+                vim.workspace_state.recording = false;
+                vim.workspace_state.stop_recording_after_next_action = false;
+            }
+        }
+    });
+}
+
 /// The state pertaining to Vim mode.
 #[derive(Default)]
 struct Vim {
@@ -299,13 +332,28 @@ impl Vim {
                     .recorded_actions
                     .push(ReplayableAction::Insertion {
                         text: text.clone(),
-                        utf16_range_to_replace: range_to_replace,
+                        utf16_range_to_replace: range_to_replace.clone(),
                     });
                 if vim.workspace_state.stop_recording_after_next_action {
                     vim.workspace_state.recording = false;
                     vim.workspace_state.stop_recording_after_next_action = false;
                 }
             }
+
+            if vim.workspace_state.ignore_current_insertion {
+                vim.workspace_state.ignore_current_insertion = false;
+                return;
+            }
+            if let Some(register) = vim.workspace_state.recording_register {
+                vim.workspace_state
+                    .recordings
+                    .entry(register)
+                    .or_default()
+                    .push(ReplayableAction::Insertion {
+                        text: text.clone(),
+                        utf16_range_to_replace: range_to_replace,
+                    });
+            }
         });
     }
 
@@ -376,8 +424,14 @@ impl Vim {
         }
     }
 
-    pub fn stop_replaying(&mut self) {
+    pub fn stop_replaying(&mut self, cx: &mut WindowContext) {
         self.workspace_state.replaying = false;
+        if let Some(replayer) = self.workspace_state.replayer.take() {
+            replayer.stop();
+        }
+        self.update_active_editor(cx, |_, editor, _| {
+            editor.show_local_selections = true;
+        });
     }
 
     /// When finishing an action that modifies the buffer, stop recording.
@@ -667,6 +721,37 @@ impl Vim {
         }
     }
 
+    /// Lists the contents of all non-empty registers, like vim's `:registers`.
+    fn registers_description(&mut self, cx: &mut WindowContext) -> String {
+        let mut description = String::from("Type Name Content\n");
+        let names = ['"']
+            .into_iter()
+            .chain('0'..='9')
+            .chain('a'..='z')
+            .chain(['-', '*', '+', '/', '%']);
+        for name in names {
+            let register = if name == '%' {
+                self.update_active_editor(cx, |vim, editor, cx| {
+                    vim.read_register(Some(name), Some(editor), cx)
+                })
+                .flatten()
+            } else {
+                self.read_register(Some(name), None, cx)
+            };
+            let Some(register) = register.filter(|register| !register.text.is_empty()) else {
+                continue;
+            };
+            let kind = if register.text.ends_with('\n') {
+                'l'
+            } else {
+                'c'
+            };
+            let content = register.text.replace('\n', "^J");
+            description.push_str(&format!("  {kind}  \"{name}   {content}\n"));
+        }
+        description
+    }
+
     fn system_clipboard_is_newer(&self, cx: &mut AppContext) -> bool {
         cx.read_from_clipboard().is_some_and(|item| {
             if let Some(last_state) = &self.workspace_state.last_yank {
@@ -903,6 +988,25 @@ impl Vim {
                 }
             }),
             Some(Operator::Jump { line }) => normal::mark::jump(text, line, cx),
+            Some(Operator::RecordRegister) => Vim::update(cx, |vim, cx| {
+                if let Some(register) = text.chars().next() {
+                    // recording into an uppercase register appends to the existing macro
+                    let lower = register.to_lowercase().next().unwrap_or(register);
+                    if lower == register {
+                        vim.workspace_state.recordings.remove(&lower);
+                    }
+                    vim.workspace_state.recording_register = Some(lower);
+                    vim.workspace_state.ignore_current_insertion = true;
+                }
+                vim.clear_operator(cx);
+            }),
+            Some(Operator::ReplayRegister) => {
+                if let Some(register) = text.chars().next() {
+                    normal::repeat::replay_register(register, cx);
+                } else {
+                    Vim::update(cx, |vim, cx| vim.clear_operator(cx));
+                }
+            }
             _ => match Vim::read(cx).state().mode {
                 Mode::Replace => multi_replace(text, cx),
                 _ => {}
@@ -918,6 +1022,9 @@ impl Vim {
             CommandPaletteInterceptor::update_global(cx, |interceptor, _| {
                 interceptor.clear();
             });
+            CommandPalettePreview::update_global(cx, |preview, _| {
+                preview.clear();
+            });
             CommandPaletteFilter::update_global(cx, |filter, _| {
                 filter.hide_namespace(Self::NAMESPACE);
             });
@@ -932,6 +1039,9 @@ impl Vim {
         CommandPaletteInterceptor::update_global(cx, |interceptor, _| {
             interceptor.set(Box::new(command::command_interceptor));
         });
+        CommandPalettePreview::update_global(cx, |preview, _| {
+            preview.set(Rc::new(normal::search::preview_replace));
+        });
 
         if let Some(active_window) = cx
             .active_window()
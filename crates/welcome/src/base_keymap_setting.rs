@@ -15,6 +15,7 @@ pub enum BaseKeymap {
     SublimeText,
     Atom,
     TextMate,
+    Emacs,
     None,
 }
 
@@ -26,15 +27,17 @@ impl Display for BaseKeymap {
             BaseKeymap::SublimeText => write!(f, "Sublime Text"),
             BaseKeymap::Atom => write!(f, "Atom"),
             BaseKeymap::TextMate => write!(f, "TextMate"),
+            BaseKeymap::Emacs => write!(f, "Emacs"),
             BaseKeymap::None => write!(f, "None"),
         }
     }
 }
 
 impl BaseKeymap {
-    pub const OPTIONS: [(&'static str, Self); 5] = [
+    pub const OPTIONS: [(&'static str, Self); 6] = [
         ("VSCode (Default)", Self::VSCode),
         ("Atom", Self::Atom),
+        ("Emacs", Self::Emacs),
         ("JetBrains", Self::JetBrains),
         ("Sublime Text", Self::SublimeText),
         ("TextMate", Self::TextMate),
@@ -46,6 +49,7 @@ impl BaseKeymap {
             BaseKeymap::SublimeText => Some("keymaps/sublime_text.json"),
             BaseKeymap::Atom => Some("keymaps/atom.json"),
             BaseKeymap::TextMate => Some("keymaps/textmate.json"),
+            BaseKeymap::Emacs => Some("keymaps/emacs.json"),
             BaseKeymap::VSCode => None,
             BaseKeymap::None => None,
         }
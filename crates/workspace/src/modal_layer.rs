@@ -173,6 +173,8 @@ impl Render for ModalLayer {
                     .flex_col()
                     .items_center()
                     .track_focus(&active_modal.focus_handle)
+                    .focus_trap()
+                    .key_context("ModalLayer")
                     .child(h_flex().occlude().child(active_modal.modal.view())),
             )
     }
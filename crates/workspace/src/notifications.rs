@@ -174,6 +174,10 @@ impl Workspace {
 
     pub fn show_toast(&mut self, toast: Toast, cx: &mut ViewContext<Self>) {
         self.dismiss_notification(&toast.id, cx);
+        // Screen readers don't read toasts as they appear, since they don't take the focus.
+        if cx.accessibility_enabled() {
+            cx.announce(&toast.msg);
+        }
         self.show_notification(toast.id, cx, |cx| {
             cx.new_view(|_cx| match toast.on_click.as_ref() {
                 Some((click_msg, on_click)) => {
@@ -3,12 +3,15 @@ use crate::{
         ClosePosition, Item, ItemHandle, ItemSettings, PreviewTabsSettings, TabContentParams,
         WeakItemHandle,
     },
+    presence::render_collaborator_presence,
     toolbar::Toolbar,
+    unloaded_item::UnloadedItem,
     workspace_settings::{AutosaveSetting, TabBarSettings, WorkspaceSettings},
-    CloseWindow, NewFile, NewTerminal, OpenInTerminal, OpenTerminal, OpenVisible, SplitDirection,
-    ToggleFileFinder, ToggleProjectSymbols, ToggleZoom, Workspace,
+    CloseWindow, ItemDeserializers, NewFile, NewTerminal, OpenInTerminal, OpenTerminal,
+    OpenVisible, SplitDirection, ToggleFileFinder, ToggleProjectSymbols, ToggleZoom, Workspace,
 };
 use anyhow::Result;
+use client::Collaborator;
 use collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use futures::{stream::FuturesUnordered, StreamExt};
 use gpui::{
@@ -34,6 +37,7 @@ use std::{
         atomic::{AtomicUsize, Ordering},
         Arc,
     },
+    time::{Duration, Instant},
 };
 use theme::ThemeSettings;
 
@@ -218,6 +222,8 @@ pub struct Pane {
     items: Vec<Box<dyn ItemHandle>>,
     activation_history: Vec<ActivationHistoryEntry>,
     next_activation_timestamp: Arc<AtomicUsize>,
+    /// When each inactive item was last viewed, used to unload items that weren't viewed for a while.
+    last_viewed_at: HashMap<EntityId, Instant>,
     zoomed: bool,
     was_focused: bool,
     active_item_index: usize,
@@ -320,6 +326,13 @@ impl Pane {
             cx.on_focus_in(&focus_handle, Pane::focus_in),
             cx.on_focus_out(&focus_handle, Pane::focus_out),
             cx.observe_global::<SettingsStore>(Self::settings_changed),
+            cx.subscribe(&project, |_, _, event, cx| match event {
+                project::Event::CollaboratorPresenceChanged
+                | project::Event::CollaboratorJoined(_)
+                | project::Event::CollaboratorLeft(_)
+                | project::Event::CollaboratorUpdated { .. } => cx.notify(),
+                _ => {}
+            }),
         ];
 
         let handle = cx.view().downgrade();
@@ -329,6 +342,7 @@ impl Pane {
             items: Vec::new(),
             activation_history: Vec::new(),
             next_activation_timestamp: next_timestamp.clone(),
+            last_viewed_at: HashMap::default(),
             was_focused: false,
             zoomed: false,
             active_item_index: 0,
@@ -798,6 +812,15 @@ impl Pane {
             None
         };
 
+        // If the item's tab was unloaded, put the item back in its place.
+        if let Some(unloaded_item_index) =
+            project_entry_id.and_then(|entry_id| self.unloaded_item_index(entry_id, cx))
+        {
+            self.replace_item(unloaded_item_index, item, cx);
+            self.activate_item(unloaded_item_index, activate_pane, focus_item, cx);
+            return;
+        }
+
         let existing_item_index = self.items.iter().position(|existing_item| {
             if existing_item.item_id() == item.item_id() {
                 true
@@ -934,6 +957,8 @@ impl Pane {
             {
                 if let Some(prev_item) = self.items.get(prev_active_item_ix) {
                     prev_item.deactivated(cx);
+                    self.last_viewed_at
+                        .insert(prev_item.item_id(), cx.background_executor().now());
                 }
             }
             cx.emit(Event::ActivateItem {
@@ -960,7 +985,161 @@ impl Pane {
 
             self.tab_bar_scroll_handle.scroll_to_item(index);
             cx.notify();
+
+            if let Ok(unloaded_item) = self.items[index].to_any().downcast::<UnloadedItem>() {
+                self.load_unloaded_item(unloaded_item, cx);
+            }
+        }
+    }
+
+    /// Replaces the clean items that weren't viewed for the given duration with placeholders,
+    /// dropping their contents until the tabs are activated again.
+    pub fn unload_inactive_items(
+        &mut self,
+        max_inactive_duration: Duration,
+        items_to_keep: &HashSet<EntityId>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let now = cx.background_executor().now();
+        for index in 0..self.items.len() {
+            if index == self.active_item_index {
+                continue;
+            }
+            let item = &self.items[index];
+            if items_to_keep.contains(&item.item_id()) {
+                continue;
+            }
+            let Some(last_viewed_at) = self.last_viewed_at.get(&item.item_id()) else {
+                continue;
+            };
+            if now.saturating_duration_since(*last_viewed_at) < max_inactive_duration {
+                continue;
+            }
+            if let Some(unloaded_item) = UnloadedItem::for_item(item.as_ref(), cx) {
+                self.replace_item(index, Box::new(unloaded_item), cx);
+            }
+        }
+    }
+
+    fn load_unloaded_item(
+        &mut self,
+        unloaded_item: View<UnloadedItem>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if unloaded_item.read(cx).is_loading {
+            return;
+        }
+        unloaded_item.update(cx, |item, _| item.is_loading = true);
+
+        // Prefer deserializing the original item, as that restores things like the scroll
+        // position, and fall back to opening its path otherwise.
+        let (kind, item_id) = unloaded_item.read(cx).serialized_item();
+        let project_path = unloaded_item.read(cx).project_path().clone();
+        let workspace = self.workspace.clone();
+        let deserialize_task = workspace
+            .upgrade()
+            .and_then(|workspace| workspace.read(cx).database_id())
+            .and_then(|workspace_id| {
+                let deserializer = *cx.global::<ItemDeserializers>().get(kind)?;
+                Some(deserializer(
+                    self.project.clone(),
+                    workspace.clone(),
+                    workspace_id,
+                    item_id,
+                    cx,
+                ))
+            });
+
+        cx.spawn(|pane, mut cx| async move {
+            let loaded_item = async {
+                let loaded_item = match deserialize_task {
+                    Some(task) => task.await.log_err(),
+                    None => None,
+                };
+                anyhow::Ok(match loaded_item {
+                    Some(item) => item,
+                    None => {
+                        let (_, build_item) = workspace
+                            .update(&mut cx, |workspace, cx| {
+                                workspace.load_path(project_path, cx)
+                            })?
+                            .await?;
+                        pane.update(&mut cx, |_, cx| build_item(cx))?
+                    }
+                })
+            }
+            .await;
+            // Cleared even when loading fails, so that it's tried again the next time the item
+            // is activated.
+            unloaded_item.update(&mut cx, |item, _| item.is_loading = false)?;
+            let loaded_item = loaded_item?;
+            pane.update(&mut cx, |pane, cx| {
+                let Some(index) = pane
+                    .items
+                    .iter()
+                    .position(|item| item.item_id() == unloaded_item.item_id())
+                else {
+                    return;
+                };
+                let focus_item = index == pane.active_item_index && pane.has_focus(cx);
+                pane.replace_item(index, loaded_item, cx);
+                if index == pane.active_item_index {
+                    pane.activate_item(index, false, focus_item, cx);
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Swaps the item at the given index for another one, without changing which item is active.
+    fn replace_item(
+        &mut self,
+        index: usize,
+        item: Box<dyn ItemHandle>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if let Some(project_path) = item.project_path(cx) {
+            let abs_path = self.project.read(cx).absolute_path(&project_path, cx);
+            self.nav_history
+                .0
+                .lock()
+                .paths_by_item
+                .insert(item.item_id(), (project_path, abs_path));
+        }
+
+        let old_item = mem::replace(&mut self.items[index], item.clone());
+        let old_item_id = old_item.item_id();
+        for entry in &mut self.activation_history {
+            if entry.entity_id == old_item_id {
+                entry.entity_id = item.item_id();
+            }
+        }
+        if let Some(last_viewed_at) = self.last_viewed_at.remove(&old_item_id) {
+            self.last_viewed_at.insert(item.item_id(), last_viewed_at);
+        }
+        if self.is_active_preview_item(old_item_id) {
+            self.preview_item_id = Some(item.item_id());
         }
+
+        cx.emit(Event::RemoveItem {
+            item_id: old_item_id,
+        });
+        cx.emit(Event::AddItem { item });
+        cx.notify();
+    }
+
+    fn unloaded_item_index(
+        &self,
+        project_entry_id: ProjectEntryId,
+        cx: &AppContext,
+    ) -> Option<usize> {
+        self.items.iter().position(|item| {
+            item.to_any()
+                .downcast::<UnloadedItem>()
+                .map_or(false, |item| {
+                    item.read(cx).project_entry_id() == project_entry_id
+                })
+        })
     }
 
     pub fn activate_prev_item(&mut self, activate_pane: bool, cx: &mut ViewContext<Self>) {
@@ -1281,6 +1460,8 @@ impl Pane {
     ) {
         self.activation_history
             .retain(|entry| entry.entity_id != self.items[item_index].item_id());
+        self.last_viewed_at
+            .remove(&self.items[item_index].item_id());
 
         if item_index == self.active_item_index {
             let index_to_activate = self
@@ -1573,6 +1754,7 @@ impl Pane {
         ix: usize,
         item: &Box<dyn ItemHandle>,
         detail: usize,
+        collaborators: &[Collaborator],
         cx: &mut ViewContext<'_, Pane>,
     ) -> impl IntoElement {
         let is_active = ix == self.active_item_index;
@@ -1592,6 +1774,13 @@ impl Pane {
         let close_side = &ItemSettings::get_global(cx).close_position;
         let indicator = render_item_indicator(item.boxed_clone(), cx);
         let item_id = item.item_id();
+        let collaborator_presence = render_collaborator_presence(
+            ElementId::NamedInteger("tab-presence".into(), ix),
+            collaborators,
+            &self.project,
+            self.workspace.clone(),
+            cx,
+        );
         let is_first_item = ix == 0;
         let is_last_item = ix == self.items.len() - 1;
         let position_relative_to_active_item = ix.cmp(&self.active_item_index);
@@ -1676,7 +1865,12 @@ impl Pane {
                             .detach_and_log_err(cx);
                     })),
             )
-            .child(label);
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(label)
+                    .children(collaborator_presence),
+            );
 
         let single_entry_to_resolve = {
             let item_entries = self.items[ix].project_entry_ids(cx);
@@ -1830,6 +2024,12 @@ impl Pane {
             .disabled(!self.can_navigate_forward())
             .tooltip(|cx| Tooltip::for_action("Go Forward", &GoForward, cx));
 
+        let mut collaborators_by_path = if ItemSettings::get_global(cx).collaborators {
+            self.project.read(cx).collaborators_by_path(cx)
+        } else {
+            HashMap::default()
+        };
+
         TabBar::new("tab_bar")
             .track_scroll(self.tab_bar_scroll_handle.clone())
             .when(
@@ -1851,7 +2051,13 @@ impl Pane {
                     .iter()
                     .enumerate()
                     .zip(tab_details(&self.items, cx))
-                    .map(|((ix, item), detail)| self.render_tab(ix, item, detail, cx)),
+                    .map(|((ix, item), detail)| {
+                        let collaborators = item
+                            .project_path(cx)
+                            .and_then(|project_path| collaborators_by_path.remove(&project_path))
+                            .unwrap_or_default();
+                        self.render_tab(ix, item, detail, &collaborators, cx)
+                    }),
             )
             .child(
                 div()
@@ -2991,6 +3197,86 @@ mod tests {
         assert_item_labels(&pane, [], cx);
     }
 
+    #[gpui::test]
+    async fn test_unload_inactive_items(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+
+        let [clean_item, _dirty_item, _active_item] =
+            [(1, "1.txt", false), (2, "2.txt", true), (3, "3.txt", false)].map(
+                |(id, path, is_dirty)| {
+                    pane.update(cx, |pane, cx| {
+                        let item = Box::new(cx.new_view(|cx| {
+                            TestItem::new(cx)
+                                .with_label(&id.to_string())
+                                .with_dirty(is_dirty)
+                                .with_project_items(&[TestProjectItem::new(id, path, cx)])
+                        }));
+                        pane.add_item(item.clone(), false, false, None, cx);
+                        item
+                    })
+                },
+            );
+
+        cx.executor().advance_clock(Duration::from_secs(30));
+        pane.update(cx, |pane, cx| {
+            pane.unload_inactive_items(Duration::from_secs(60), &HashSet::default(), cx);
+            assert_eq!(pane.items_of_type::<UnloadedItem>().count(), 0);
+        });
+
+        // Only the clean item that isn't active gets unloaded, keeping its place in the tab bar.
+        cx.executor().advance_clock(Duration::from_secs(60));
+        pane.update(cx, |pane, cx| {
+            pane.unload_inactive_items(Duration::from_secs(60), &HashSet::default(), cx);
+            assert_eq!(pane.items_len(), 3);
+            assert_eq!(pane.index_for_item(&*clean_item), None);
+            let unloaded_item = pane
+                .items_of_type::<UnloadedItem>()
+                .exactly_one()
+                .ok()
+                .unwrap();
+            assert_eq!(pane.index_for_item(&unloaded_item), Some(0));
+            assert_eq!(
+                unloaded_item.read(cx).project_entry_id(),
+                ProjectEntryId::from_proto(1)
+            );
+        });
+
+        // Loading the unloaded item fails without a worktree for its path, after which it can
+        // be loaded again.
+        pane.update(cx, |pane, cx| pane.activate_item(0, false, false, cx));
+        cx.run_until_parked();
+        pane.update(cx, |pane, cx| {
+            let unloaded_item = pane
+                .items_of_type::<UnloadedItem>()
+                .exactly_one()
+                .ok()
+                .unwrap();
+            assert!(!unloaded_item.read(cx).is_loading);
+            pane.activate_item(2, false, false, cx);
+        });
+
+        // Opening the unloaded item again puts it back in its place.
+        pane.update(cx, |pane, cx| {
+            pane.add_item(
+                Box::new(cx.new_view(|cx| {
+                    TestItem::new(cx)
+                        .with_label("1")
+                        .with_project_items(&[TestProjectItem::new(1, "1.txt", cx)])
+                })),
+                false,
+                false,
+                None,
+                cx,
+            );
+        });
+        assert_item_labels(&pane, ["1*", "2^", "3"], cx);
+    }
+
     fn init_test(cx: &mut TestAppContext) {
         cx.update(|cx| {
             let settings_store = SettingsStore::test(cx);
@@ -0,0 +1,118 @@
+use std::time::{Duration, Instant};
+
+use gpui::{FrameTiming, IntoElement, ParentElement, Render, Styled, Task, ViewContext};
+use settings::Settings;
+use sysinfo::{Pid, ProcessRefreshKind, System};
+use theme::ThemeSettings;
+use ui::prelude::*;
+
+/// How often the overlay's numbers are updated. The overlay is only redrawn
+/// this often, so that it doesn't cause the frames that it measures.
+const REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// An overlay showing how quickly the window is drawing its frames, and how
+/// much memory Zed is using.
+pub struct PerformanceHud {
+    /// The memory used by Zed's process, in bytes.
+    memory: Option<u64>,
+    _refresh_task: Task<()>,
+}
+
+impl PerformanceHud {
+    pub fn new(cx: &mut ViewContext<Self>) -> Self {
+        let refresh_task = cx.spawn(|this, mut cx| async move {
+            let mut system = System::new();
+            let pid = Pid::from_u32(std::process::id());
+            loop {
+                let (memory, returned_system) = cx
+                    .background_executor()
+                    .spawn(async move {
+                        system.refresh_process_specifics(
+                            pid,
+                            ProcessRefreshKind::new().with_memory(),
+                        );
+                        let memory = system.process(pid).map(|process| process.memory());
+                        (memory, system)
+                    })
+                    .await;
+                system = returned_system;
+                if this
+                    .update(&mut cx, |this, cx| {
+                        this.memory = memory;
+                        cx.notify();
+                    })
+                    .is_err()
+                {
+                    break;
+                }
+                cx.background_executor().timer(REFRESH_INTERVAL).await;
+            }
+        });
+
+        Self {
+            memory: None,
+            _refresh_task: refresh_task,
+        }
+    }
+}
+
+impl Render for PerformanceHud {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let timings = cx.frame_timings();
+        let fps = timings.frames_per_second(Instant::now());
+        let percentiles = |duration: fn(&FrameTiming) -> Duration| {
+            [0.5, 0.95, 0.99]
+                .into_iter()
+                .map(|percentile| {
+                    timings
+                        .percentile(percentile, duration)
+                        .map_or("-".to_string(), |duration| {
+                            format!("{:.1}", duration.as_secs_f64() * 1000.)
+                        })
+                })
+                .collect::<Vec<_>>()
+                .join(" / ")
+        };
+        let rows = [
+            ("FPS".to_string(), fps.to_string()),
+            (
+                "Frame (p50 / p95 / p99 ms)".to_string(),
+                percentiles(|timing| timing.total),
+            ),
+            (
+                "Layout (p50 / p95 / p99 ms)".to_string(),
+                percentiles(|timing| timing.layout),
+            ),
+            (
+                "Paint (p50 / p95 / p99 ms)".to_string(),
+                percentiles(|timing| timing.paint),
+            ),
+            (
+                "Memory".to_string(),
+                self.memory.map_or("-".to_string(), |memory| {
+                    format!("{:.1} MB", memory as f64 / 1024. / 1024.)
+                }),
+            ),
+        ];
+
+        v_flex()
+            .absolute()
+            .top_2()
+            .right_2()
+            .p_2()
+            .gap_0p5()
+            .elevation_3(cx)
+            .font_family(ThemeSettings::get_global(cx).buffer_font.family.clone())
+            .children(rows.into_iter().map(|(label, value)| {
+                h_flex()
+                    .gap_4()
+                    .justify_between()
+                    .child(
+                        Label::new(label)
+                            .size(LabelSize::XSmall)
+                            .color(Color::Muted),
+                    )
+                    .child(Label::new(value).size(LabelSize::XSmall))
+            }))
+    }
+}
@@ -0,0 +1,85 @@
+use crate::Workspace;
+use client::Collaborator;
+use gpui::{AnyElement, Model, WeakView};
+use project::Project;
+use ui::{prelude::*, Avatar, Tooltip};
+
+/// The maximum amount of avatars shown next to a single file before collapsing the rest into a counter.
+const MAX_PRESENCE_AVATARS: usize = 3;
+
+/// Renders a small pile of avatars for the collaborators that currently have a file open.
+///
+/// Clicking on an avatar starts following the corresponding collaborator.
+pub fn render_collaborator_presence(
+    id: impl Into<ElementId>,
+    collaborators: &[Collaborator],
+    project: &Model<Project>,
+    workspace: WeakView<Workspace>,
+    cx: &WindowContext,
+) -> Option<AnyElement> {
+    if collaborators.is_empty() {
+        return None;
+    }
+
+    let user_store = project.read(cx).user_store().read(cx);
+    let participant_indices = user_store.participant_indices();
+    let player_colors = cx.theme().players();
+    let extra_count = collaborators.len().saturating_sub(MAX_PRESENCE_AVATARS);
+    let id = id.into();
+
+    let avatars = collaborators
+        .iter()
+        .take(MAX_PRESENCE_AVATARS)
+        .enumerate()
+        .filter_map(|(ix, collaborator)| {
+            let user = user_store.get_cached_user(collaborator.user_id)?;
+            let border_color = participant_indices.get(&collaborator.user_id).map_or(
+                gpui::transparent_black(),
+                |participant_index| {
+                    player_colors
+                        .color_for_participant(participant_index.0)
+                        .cursor
+                },
+            );
+            let peer_id = collaborator.peer_id;
+            let github_login = user.github_login.clone();
+            let workspace = workspace.clone();
+            Some(
+                div()
+                    .id(ElementId::NamedInteger(
+                        SharedString::from(format!("{id}-presence")),
+                        ix,
+                    ))
+                    .when(ix > 0, |this| this.ml_neg_1())
+                    .child(
+                        Avatar::new(user.avatar_uri.clone())
+                            .size(rems(0.875))
+                            .border_color(border_color),
+                    )
+                    .tooltip(move |cx| Tooltip::text(format!("Follow {github_login}"), cx))
+                    .on_click(move |_, cx| {
+                        cx.stop_propagation();
+                        workspace
+                            .update(cx, |workspace, cx| workspace.follow(peer_id, cx))
+                            .ok();
+                    }),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    Some(
+        h_flex()
+            .flex_none()
+            .children(avatars)
+            .when(extra_count > 0, |this| {
+                this.child(
+                    div().ml_0p5().child(
+                        Label::new(format!("+{extra_count}"))
+                            .size(LabelSize::XSmall)
+                            .color(Color::Muted),
+                    ),
+                )
+            })
+            .into_any_element(),
+    )
+}
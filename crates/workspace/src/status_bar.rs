@@ -1,8 +1,9 @@
-use crate::{ItemHandle, Pane};
+use crate::{ItemHandle, Pane, StatusBarSettings, StatusItemGroup};
 use gpui::{
-    AnyView, IntoElement, ParentElement, Render, Styled, Subscription, View, ViewContext,
-    WindowContext,
+    AnyView, AppContext, Hsla, IntoElement, ParentElement, Render, Styled, Subscription, View,
+    ViewContext, WindowContext,
 };
+use settings::{Settings, SettingsStore};
 use std::any::TypeId;
 use ui::{h_flex, prelude::*};
 use util::ResultExt;
@@ -13,6 +14,15 @@ pub trait StatusItemView: Render {
         active_pane_item: Option<&dyn crate::ItemHandle>,
         cx: &mut ViewContext<Self>,
     );
+
+    /// The identifier with which the `status_bar` settings refer to the item,
+    /// to hide it or move it. Items without one are always shown where they're added.
+    fn status_item_id() -> Option<&'static str>
+    where
+        Self: Sized,
+    {
+        None
+    }
 }
 
 trait StatusItemViewHandle: Send {
@@ -23,17 +33,21 @@ trait StatusItemViewHandle: Send {
         cx: &mut WindowContext,
     );
     fn item_type(&self) -> TypeId;
+    fn status_item_id(&self) -> Option<&'static str>;
 }
 
 pub struct StatusBar {
     left_items: Vec<Box<dyn StatusItemViewHandle>>,
     right_items: Vec<Box<dyn StatusItemViewHandle>>,
     active_pane: View<Pane>,
+    accent_color: Option<Hsla>,
     _observe_active_pane: Subscription,
+    _observe_settings: Subscription,
 }
 
 impl Render for StatusBar {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let [left_items, right_items] = self.arranged_items(cx);
         h_flex()
             .w_full()
             .justify_between()
@@ -41,40 +55,124 @@ impl Render for StatusBar {
             .py(Spacing::Small.rems(cx))
             .px(Spacing::Large.rems(cx))
             // .h_8()
-            .bg(cx.theme().colors().status_bar_background)
-            .child(self.render_left_tools(cx))
-            .child(self.render_right_tools(cx))
+            .bg(self.accent_color.map_or(
+                cx.theme().colors().status_bar_background,
+                |accent_color| {
+                    cx.theme()
+                        .colors()
+                        .status_bar_background
+                        .blend(accent_color)
+                },
+            ))
+            .child(self.render_left_tools(left_items, cx))
+            .child(self.render_right_tools(right_items, cx))
     }
 }
 
 impl StatusBar {
-    fn render_left_tools(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+    fn render_left_tools(
+        &self,
+        items: Vec<AnyView>,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
         h_flex()
             .gap(Spacing::Large.rems(cx))
             .overflow_x_hidden()
-            .children(self.left_items.iter().map(|item| item.to_any()))
+            .children(items)
     }
 
-    fn render_right_tools(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
-        h_flex()
-            .gap(Spacing::Large.rems(cx))
-            .children(self.right_items.iter().rev().map(|item| item.to_any()))
+    fn render_right_tools(
+        &self,
+        items: Vec<AnyView>,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        h_flex().gap(Spacing::Large.rems(cx)).children(items)
+    }
+
+    /// Returns the items of the left and right groups, from left to right, as
+    /// arranged by the `status_bar` settings.
+    fn arranged_items(&self, cx: &AppContext) -> [Vec<AnyView>; 2] {
+        let items = self
+            .items_with_groups()
+            .map(|(item, group)| (item.to_any(), item.status_item_id(), group));
+        arrange_items(items, StatusBarSettings::get_global(cx))
+    }
+
+    /// Returns the identifiers of the items that are shown in the left and
+    /// right groups, from left to right.
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn visible_item_ids(&self, cx: &AppContext) -> [Vec<Option<&'static str>>; 2] {
+        let items = self
+            .items_with_groups()
+            .map(|(item, group)| (item.status_item_id(), item.status_item_id(), group));
+        arrange_items(items, StatusBarSettings::get_global(cx))
+    }
+
+    fn items_with_groups(
+        &self,
+    ) -> impl Iterator<Item = (&dyn StatusItemViewHandle, StatusItemGroup)> {
+        self.left_items
+            .iter()
+            .map(|item| (item.as_ref(), StatusItemGroup::Left))
+            .chain(
+                self.right_items
+                    .iter()
+                    .rev()
+                    .map(|item| (item.as_ref(), StatusItemGroup::Right)),
+            )
     }
 }
 
+/// Arranges items, given from left to right with the group they're added to,
+/// into the left and right groups. Items moved to another group by the settings
+/// are placed at its end that's closest to the center, unless their order says otherwise.
+fn arrange_items<T>(
+    items: impl IntoIterator<Item = (T, Option<&'static str>, StatusItemGroup)>,
+    settings: &StatusBarSettings,
+) -> [Vec<T>; 2] {
+    let mut groups = [Vec::new(), Vec::new()];
+    for (item, id, group) in items {
+        let item_settings = id.and_then(|id| settings.items.get(id));
+        if item_settings.and_then(|settings| settings.visible) == Some(false) {
+            continue;
+        }
+        let group = item_settings
+            .and_then(|settings| settings.group)
+            .unwrap_or(group);
+        let order = item_settings
+            .and_then(|settings| settings.order)
+            .unwrap_or(0);
+        groups[group as usize].push((order, item));
+    }
+    groups.map(|mut group| {
+        group.sort_by_key(|(order, _)| *order);
+        group.into_iter().map(|(_, item)| item).collect()
+    })
+}
+
 impl StatusBar {
     pub fn new(active_pane: &View<Pane>, cx: &mut ViewContext<Self>) -> Self {
         let mut this = Self {
             left_items: Default::default(),
             right_items: Default::default(),
             active_pane: active_pane.clone(),
+            accent_color: None,
             _observe_active_pane: cx
                 .observe(active_pane, |this, _, cx| this.update_active_pane_item(cx)),
+            _observe_settings: cx.observe_global::<SettingsStore>(|_, cx| cx.notify()),
         };
         this.update_active_pane_item(cx);
         this
     }
 
+    /// Sets the color with which the status bar is tinted.
+    pub fn set_accent_color(&mut self, accent_color: Option<Hsla>, cx: &mut ViewContext<Self>) {
+        if self.accent_color != accent_color {
+            self.accent_color = accent_color;
+            cx.notify();
+        }
+    }
+
     pub fn add_left_item<T>(&mut self, item: View<T>, cx: &mut ViewContext<Self>)
     where
         T: 'static + StatusItemView,
@@ -183,6 +281,10 @@ impl<T: StatusItemView> StatusItemViewHandle for View<T> {
     fn item_type(&self) -> TypeId {
         TypeId::of::<T>()
     }
+
+    fn status_item_id(&self) -> Option<&'static str> {
+        T::status_item_id()
+    }
 }
 
 impl From<&dyn StatusItemViewHandle> for AnyView {
@@ -190,3 +292,77 @@ impl From<&dyn StatusItemViewHandle> for AnyView {
         val.to_any().clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StatusItemSettings;
+
+    #[test]
+    fn test_arrange_items() {
+        use StatusItemGroup::*;
+        let items = [
+            ("diagnostics", None, Left),
+            ("feedback", Some("feedback"), Left),
+            ("activity", Some("activity"), Left),
+            ("language", Some("language"), Right),
+            ("cursor", Some("cursor"), Right),
+        ];
+        let arrange = |settings: &[(&str, StatusItemSettings)]| {
+            let settings = StatusBarSettings {
+                items: settings
+                    .iter()
+                    .map(|(id, settings)| (id.to_string(), settings.clone()))
+                    .collect(),
+            };
+            arrange_items(items, &settings)
+        };
+
+        assert_eq!(
+            arrange(&[]),
+            [
+                vec!["diagnostics", "feedback", "activity"],
+                vec!["language", "cursor"]
+            ]
+        );
+        assert_eq!(
+            arrange(&[(
+                "feedback",
+                StatusItemSettings {
+                    visible: Some(false),
+                    ..Default::default()
+                }
+            )]),
+            [vec!["diagnostics", "activity"], vec!["language", "cursor"]]
+        );
+        assert_eq!(
+            arrange(&[
+                (
+                    "feedback",
+                    StatusItemSettings {
+                        group: Some(Right),
+                        ..Default::default()
+                    }
+                ),
+                (
+                    "activity",
+                    StatusItemSettings {
+                        order: Some(-1),
+                        ..Default::default()
+                    }
+                ),
+                (
+                    "language",
+                    StatusItemSettings {
+                        order: Some(1),
+                        ..Default::default()
+                    }
+                ),
+            ]),
+            [
+                vec!["activity", "diagnostics"],
+                vec!["feedback", "cursor", "language"]
+            ]
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{
+    item::{Item, ItemHandle, TabContentParams},
+    ItemId,
+};
+use gpui::{
+    div, AppContext, EventEmitter, FocusHandle, FocusableView, InteractiveElement, Render,
+    SharedString, Styled, View, ViewContext, VisualContext, WindowContext,
+};
+use project::{ProjectEntryId, ProjectPath};
+use ui::{prelude::*, Label};
+
+/// Stands in for a tab whose item was dropped after not being viewed for a while,
+/// keeping only what's needed to open the item again once the tab is activated.
+pub struct UnloadedItem {
+    project_path: ProjectPath,
+    project_entry_id: ProjectEntryId,
+    tooltip: Option<SharedString>,
+    serialized_item: (&'static str, ItemId),
+    pub(crate) is_loading: bool,
+    focus_handle: FocusHandle,
+}
+
+impl UnloadedItem {
+    /// Returns a placeholder for the given item, if it can be dropped without losing any state
+    /// that can't be restored from its file and the workspace database.
+    pub(crate) fn for_item(item: &dyn ItemHandle, cx: &mut WindowContext) -> Option<View<Self>> {
+        if !item.is_singleton(cx) || item.is_dirty(cx) || item.has_conflict(cx) {
+            return None;
+        }
+        if item.to_any().downcast::<Self>().is_ok() {
+            return None;
+        }
+        let project_path = item.project_path(cx)?;
+        let project_entry_id = *item.project_entry_ids(cx).first()?;
+        let serialized_item = (item.serialized_item_kind()?, item.item_id().as_u64());
+        let tooltip = item.tab_tooltip_text(cx);
+        Some(cx.new_view(|cx| Self {
+            project_path,
+            project_entry_id,
+            tooltip,
+            serialized_item,
+            is_loading: false,
+            focus_handle: cx.focus_handle(),
+        }))
+    }
+
+    pub fn project_path(&self) -> &ProjectPath {
+        &self.project_path
+    }
+
+    pub fn project_entry_id(&self) -> ProjectEntryId {
+        self.project_entry_id
+    }
+
+    /// The kind and id the original item was serialized with, which are used both to
+    /// persist the tab and to restore the item with its previous state.
+    pub fn serialized_item(&self) -> (&'static str, ItemId) {
+        self.serialized_item
+    }
+}
+
+impl EventEmitter<()> for UnloadedItem {}
+
+impl FocusableView for UnloadedItem {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for UnloadedItem {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        div()
+            .bg(cx.theme().colors().editor_background)
+            .track_focus(&self.focus_handle)
+            .size_full()
+    }
+}
+
+impl Item for UnloadedItem {
+    type Event = ();
+
+    fn tab_tooltip_text(&self, _: &AppContext) -> Option<SharedString> {
+        self.tooltip.clone()
+    }
+
+    fn tab_content(&self, params: TabContentParams, _: &WindowContext) -> gpui::AnyElement {
+        let file_name = self
+            .project_path
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Label::new(file_name)
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+}
@@ -4,12 +4,15 @@ mod modal_layer;
 pub mod notifications;
 pub mod pane;
 pub mod pane_group;
+mod performance_hud;
 mod persistence;
+pub mod presence;
 pub mod searchable;
 pub mod shared_screen;
 mod status_bar;
 pub mod tasks;
 mod toolbar;
+pub mod unloaded_item;
 mod workspace_settings;
 
 use anyhow::{anyhow, Context as _, Result};
@@ -29,9 +32,10 @@ use futures::{
 use gpui::{
     action_as, actions, canvas, impl_action_as, impl_actions, point, relative, size, Action,
     AnyElement, AnyView, AnyWeakView, AppContext, AsyncAppContext, AsyncWindowContext, Bounds,
-    DragMoveEvent, Entity as _, EntityId, EventEmitter, FocusHandle, FocusableView, Global,
+    DragMoveEvent, Entity as _, EntityId, EventEmitter, FocusHandle, FocusableView, Global, Hsla,
     KeyContext, Keystroke, ManagedView, Model, ModelContext, PathPromptOptions, Point, PromptLevel,
-    Render, Size, Subscription, Task, View, WeakView, WindowBounds, WindowHandle, WindowOptions,
+    Render, Rgba, Size, Subscription, Task, View, WeakView, WindowBounds, WindowHandle,
+    WindowOptions,
 };
 use item::{
     FollowableItem, FollowableItemHandle, Item, ItemHandle, ItemSettings, PreviewTabsSettings,
@@ -45,6 +49,7 @@ use node_runtime::NodeRuntime;
 use notifications::{simple_message_notification::MessageNotification, NotificationHandle};
 pub use pane::*;
 pub use pane_group::*;
+use performance_hud::PerformanceHud;
 use persistence::{model::SerializedWorkspace, SerializedWindowBounds, DB};
 pub use persistence::{
     model::{ItemId, LocalPaths, SerializedDevServerProject, SerializedWorkspaceLocation},
@@ -53,7 +58,7 @@ pub use persistence::{
 use postage::stream::Stream;
 use project::{Project, ProjectEntryId, ProjectPath, Worktree, WorktreeId};
 use serde::Deserialize;
-use settings::Settings;
+use settings::{Settings, SettingsLocation, SettingsStore};
 use shared_screen::SharedScreen;
 use sqlez::{
     bindable::{Bind, Column, StaticColumnCount},
@@ -72,7 +77,7 @@ use std::{
     path::{Path, PathBuf},
     rc::Rc,
     sync::{atomic::AtomicUsize, Arc, Weak},
-    time::Duration,
+    time::{Duration, Instant},
 };
 use task::SpawnInTerminal;
 use theme::{ActiveTheme, SystemAppearance, ThemeSettings};
@@ -86,14 +91,20 @@ use ui::{
 use util::{maybe, ResultExt};
 use uuid::Uuid;
 pub use workspace_settings::{
-    AutosaveSetting, RestoreOnStartupBehaviour, TabBarSettings, WorkspaceSettings,
+    AutosaveSetting, RestoreOnStartupBehaviour, StatusBarSettings, StatusItemGroup,
+    StatusItemSettings, TabBarSettings, UnloadInactiveTabsSetting, WorkspaceSettings,
 };
 
 use crate::persistence::{
     model::{DockData, DockStructure, SerializedItem, SerializedPane, SerializedPaneGroup},
     SerializedAxis,
 };
-use crate::{notifications::NotificationId, persistence::model::LocalPathsOrder};
+use crate::{
+    notifications::NotificationId, persistence::model::LocalPathsOrder, unloaded_item::UnloadedItem,
+};
+
+/// How often to check for tabs that weren't viewed for long enough to be unloaded.
+const UNLOAD_INACTIVE_ITEMS_INTERVAL: Duration = Duration::from_secs(60);
 
 lazy_static! {
     static ref ZED_WINDOW_SIZE: Option<Size<Pixels>> = env::var("ZED_WINDOW_SIZE")
@@ -118,6 +129,8 @@ actions!(
         CloseAllDocks,
         CloseWindow,
         Feedback,
+        FocusNext,
+        FocusPrevious,
         FollowNextCollaborator,
         NewCenterTerminal,
         NewFile,
@@ -132,6 +145,7 @@ actions!(
         ToggleBottomDock,
         ToggleCenteredLayout,
         ToggleLeftDock,
+        TogglePerformanceHud,
         ToggleRightDock,
         ToggleZoom,
         Unfollow,
@@ -284,6 +298,7 @@ pub fn init_settings(cx: &mut AppContext) {
     ItemSettings::register(cx);
     PreviewTabsSettings::register(cx);
     TabBarSettings::register(cx);
+    StatusBarSettings::register(cx);
 }
 
 pub fn init(app_state: Arc<AppState>, cx: &mut AppContext) {
@@ -595,6 +610,8 @@ pub struct Workspace {
     follower_states: HashMap<View<Pane>, FollowerState>,
     last_leaders_by_pane: HashMap<WeakView<Pane>, PeerId>,
     window_edited: bool,
+    window_accent_color: Option<String>,
+    accent_color: Option<Hsla>,
     active_call: Option<(Model<ActiveCall>, Vec<Subscription>)>,
     leader_updates_tx: mpsc::UnboundedSender<(PeerId, proto::UpdateFollowers)>,
     database_id: Option<WorkspaceId>,
@@ -604,6 +621,9 @@ pub struct Workspace {
     _apply_leader_updates: Task<Result<()>>,
     _observe_current_user: Task<Result<()>>,
     _schedule_serialize: Option<Task<()>>,
+    _unload_inactive_items: Task<Result<()>>,
+    items_kept_loaded: HashSet<EntityId>,
+    last_activated_at: Option<Instant>,
     pane_history_timestamp: Arc<AtomicUsize>,
     bounds: Bounds<Pixels>,
     centered_layout: bool,
@@ -611,6 +631,7 @@ pub struct Workspace {
     on_prompt_for_new_path: Option<PromptForNewPath>,
     render_disconnected_overlay:
         Option<Box<dyn Fn(&mut Self, &mut ViewContext<Self>) -> AnyElement>>,
+    performance_hud: Option<View<PerformanceHud>>,
 }
 
 impl EventEmitter<Event> for Workspace {}
@@ -649,11 +670,18 @@ impl Workspace {
                     this.collaborator_left(*peer_id, cx);
                 }
 
-                project::Event::WorktreeRemoved(_) | project::Event::WorktreeAdded => {
+                project::Event::WorktreeRemoved(_)
+                | project::Event::WorktreeAdded
+                | project::Event::WorktreeOrderChanged => {
                     this.update_window_title(cx);
+                    this.update_accent_color(cx);
                     this.serialize_workspace(cx);
                 }
 
+                project::Event::WorktreeUpdatedGitRepositories => {
+                    this.update_window_title(cx);
+                }
+
                 project::Event::DisconnectedFromHost => {
                     this.update_window_edited(cx);
                     let panes_to_unfollow: Vec<View<Pane>> =
@@ -766,6 +794,15 @@ impl Workspace {
             Ok(())
         });
 
+        let _unload_inactive_items: Task<Result<()>> = cx.spawn(|this, mut cx| async move {
+            loop {
+                cx.background_executor()
+                    .timer(UNLOAD_INACTIVE_ITEMS_INTERVAL)
+                    .await;
+                this.update(&mut cx, |this, cx| this.unload_inactive_items(cx))?;
+            }
+        });
+
         cx.emit(Event::WorkspaceCreated(weak_handle.clone()));
 
         let left_dock = Dock::new(DockPosition::Left, cx);
@@ -822,6 +859,10 @@ impl Workspace {
                 }));
                 cx.notify();
             }),
+            cx.observe_global::<SettingsStore>(|this, cx| {
+                this.update_window_title(cx);
+                this.update_accent_color(cx);
+            }),
             cx.observe_window_appearance(|_, cx| {
                 let window_appearance = cx.appearance();
 
@@ -851,6 +892,7 @@ impl Workspace {
 
         cx.defer(|this, cx| {
             this.update_window_title(cx);
+            this.update_accent_color(cx);
         });
         Workspace {
             weak_self: weak_handle.clone(),
@@ -874,12 +916,17 @@ impl Workspace {
             last_leaders_by_pane: Default::default(),
             dispatching_keystrokes: Default::default(),
             window_edited: false,
+            window_accent_color: None,
+            accent_color: None,
             active_call,
             database_id: workspace_id,
             app_state,
             _observe_current_user,
             _apply_leader_updates,
             _schedule_serialize: None,
+            _unload_inactive_items,
+            items_kept_loaded: HashSet::default(),
+            last_activated_at: None,
             leader_updates_tx,
             _subscriptions: subscriptions,
             pane_history_timestamp,
@@ -890,6 +937,7 @@ impl Workspace {
             bounds_save_task_queued: None,
             on_prompt_for_new_path: None,
             render_disconnected_overlay: None,
+            performance_hud: None,
         }
     }
 
@@ -928,6 +976,8 @@ impl Workspace {
                 });
 
             if let Some(paths_order) = paths_order {
+                // The order indexes the paths sorted, like they're stored.
+                paths_to_open.sort();
                 paths_to_open = paths_order
                     .iter()
                     .filter_map(|i| paths_to_open.get(*i).cloned())
@@ -2255,6 +2305,17 @@ impl Workspace {
         path: impl Into<ProjectPath>,
         allow_preview: bool,
         cx: &mut ViewContext<Self>,
+    ) -> Task<Result<Box<dyn ItemHandle>, anyhow::Error>> {
+        self.split_path_in_direction(path, SplitDirection::Right, allow_preview, cx)
+    }
+
+    /// Opens the path in a new pane, split from the last active center pane in the given direction.
+    pub fn split_path_in_direction(
+        &mut self,
+        path: impl Into<ProjectPath>,
+        direction: SplitDirection,
+        allow_preview: bool,
+        cx: &mut ViewContext<Self>,
     ) -> Task<Result<Box<dyn ItemHandle>, anyhow::Error>> {
         let pane = self.last_active_center_pane.clone().unwrap_or_else(|| {
             self.panes
@@ -2274,7 +2335,7 @@ impl Workspace {
             let (project_entry_id, build_item) = task.await?;
             this.update(&mut cx, move |this, cx| -> Option<_> {
                 let pane = pane.upgrade()?;
-                let new_pane = this.split_pane(pane, SplitDirection::Right, cx);
+                let new_pane = this.split_pane(pane, direction, cx);
                 new_pane.update(cx, |new_pane, cx| {
                     Some(new_pane.open_item(project_entry_id, true, allow_preview, cx, build_item))
                 })
@@ -2971,7 +3032,56 @@ impl Workspace {
         self.update_window_title(cx);
     }
 
+    /// Returns the workspace settings for this workspace's project, including
+    /// those from the settings file of its first worktree.
+    pub fn project_settings<'a>(&self, cx: &'a AppContext) -> &'a WorkspaceSettings {
+        let worktree_id = self
+            .project
+            .read(cx)
+            .visible_worktrees(cx)
+            .next()
+            .map(|worktree| worktree.read(cx).id().to_usize());
+        WorkspaceSettings::get(
+            worktree_id.map(|worktree_id| SettingsLocation {
+                worktree_id,
+                path: Path::new(""),
+            }),
+            cx,
+        )
+    }
+
+    /// Returns the color with which this workspace's window is tinted, if any.
+    pub fn accent_color(&self) -> Option<Hsla> {
+        self.accent_color
+    }
+
+    /// Parses the `window_accent_color` setting again when it changed.
+    fn update_accent_color(&mut self, cx: &mut WindowContext) {
+        let window_accent_color = self.project_settings(cx).window_accent_color.clone();
+        if window_accent_color == self.window_accent_color {
+            return;
+        }
+        self.accent_color = window_accent_color.as_deref().and_then(|accent_color| {
+            Rgba::try_from(accent_color)
+                .context("invalid window_accent_color")
+                .log_err()
+                .map(Hsla::from)
+        });
+        self.window_accent_color = window_accent_color;
+
+        let accent_color = self.accent_color;
+        self.status_bar.update(cx, |status_bar, cx| {
+            status_bar.set_accent_color(accent_color, cx)
+        });
+    }
+
     fn update_window_title(&mut self, cx: &mut WindowContext) {
+        if let Some(template) = self.project_settings(cx).window_title.clone() {
+            let title = self.format_window_title(&template, cx);
+            cx.set_window_title(&title);
+            return;
+        }
+
         let project = self.project().read(cx);
         let mut title = String::new();
 
@@ -3015,6 +3125,42 @@ impl Workspace {
         cx.set_window_title(&title);
     }
 
+    fn format_window_title(&self, template: &str, cx: &WindowContext) -> String {
+        let project = self.project().read(cx);
+        let project_name = project
+            .worktree_root_names(cx)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let branch = project
+            .visible_worktrees(cx)
+            .next()
+            .and_then(|worktree| worktree.read(cx).root_git_entry())
+            .and_then(|entry| entry.branch())
+            .map(|branch| branch.to_string());
+        let path = self.active_item(cx).and_then(|item| item.project_path(cx));
+        let file = path
+            .as_ref()
+            .map(|path| path.path.to_string_lossy().into_owned());
+        let filename = path
+            .as_ref()
+            .and_then(|path| path.path.file_name())
+            .map(|name| name.to_string_lossy().into_owned());
+
+        let mut title = format_window_title(
+            template,
+            &project_name,
+            branch.as_deref(),
+            file.as_deref(),
+            filename.as_deref(),
+        );
+        if project.is_remote() {
+            title.push_str(" ↙");
+        } else if project.is_shared() {
+            title.push_str(" ↗");
+        }
+        title
+    }
+
     fn update_window_edited(&mut self, cx: &mut WindowContext) {
         let is_edited = !self.project.read(cx).is_disconnected()
             && self
@@ -3488,6 +3634,7 @@ impl Workspace {
 
     pub fn on_window_activation_changed(&mut self, cx: &mut ViewContext<Self>) {
         if cx.is_window_active() {
+            self.last_activated_at = Some(cx.background_executor().now());
             self.update_active_view_for_followers(cx);
 
             if let Some(database_id) = self.database_id {
@@ -3538,6 +3685,11 @@ impl Workspace {
         self.database_id
     }
 
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn set_database_id(&mut self, database_id: WorkspaceId) {
+        self.database_id = Some(database_id);
+    }
+
     fn local_paths(&self, cx: &AppContext) -> Option<Vec<Arc<Path>>> {
         let project = self.project().read(cx);
 
@@ -3578,6 +3730,29 @@ impl Workspace {
         cx.notify();
     }
 
+    fn unload_inactive_items(&mut self, cx: &mut ViewContext<Self>) {
+        let UnloadInactiveTabsSetting::AfterDelay { minutes } =
+            WorkspaceSettings::get_global(cx).unload_inactive_tabs
+        else {
+            return;
+        };
+        let max_inactive_duration = Duration::from_secs(minutes * 60);
+        let panes_by_item = &self.panes_by_item;
+        self.items_kept_loaded
+            .retain(|item_id| panes_by_item.contains_key(item_id));
+        for pane in &self.panes {
+            pane.update(cx, |pane, cx| {
+                pane.unload_inactive_items(max_inactive_duration, &self.items_kept_loaded, cx)
+            });
+        }
+    }
+
+    /// Prevents the item from being unloaded when it isn't viewed for a while, for as long as
+    /// it's open. Used when something waits for the item to be closed, like `zed --wait`.
+    pub fn keep_item_loaded(&mut self, item_id: EntityId) {
+        self.items_kept_loaded.insert(item_id);
+    }
+
     fn serialize_workspace(&mut self, cx: &mut ViewContext<Self>) {
         if self._schedule_serialize.is_none() {
             self._schedule_serialize = Some(cx.spawn(|this, mut cx| async move {
@@ -3605,9 +3780,18 @@ impl Workspace {
                 (
                     pane.items()
                         .filter_map(|item_handle| {
+                            // Unloaded items are persisted as the items they stand in for.
+                            let (kind, item_id) =
+                                match item_handle.to_any().downcast::<UnloadedItem>() {
+                                    Ok(unloaded_item) => unloaded_item.read(cx).serialized_item(),
+                                    Err(_) => (
+                                        item_handle.serialized_item_kind()?,
+                                        item_handle.item_id().as_u64(),
+                                    ),
+                                };
                             Some(SerializedItem {
-                                kind: Arc::from(item_handle.serialized_item_kind()?),
-                                item_id: item_handle.item_id().as_u64(),
+                                kind: Arc::from(kind),
+                                item_id,
                                 active: Some(item_handle.item_id()) == active_item_id,
                                 preview: pane.is_active_preview_item(item_handle.item_id()),
                             })
@@ -3696,11 +3880,13 @@ impl Workspace {
 
         let location = if let Some(local_paths) = self.local_paths(cx) {
             if !local_paths.is_empty() {
-                let (order, paths): (Vec<_>, Vec<_>) = local_paths
+                // The paths are stored sorted, with the order of the roots as the index
+                // of each one among them.
+                let paths = local_paths.iter().sorted().collect::<Vec<_>>();
+                let order = local_paths
                     .iter()
-                    .enumerate()
-                    .sorted_by(|a, b| a.1.cmp(b.1))
-                    .unzip();
+                    .filter_map(|root| paths.iter().position(|path| *path == root))
+                    .collect::<Vec<_>>();
 
                 Some(SerializedWorkspaceLocation::Local(
                     LocalPaths::new(paths),
@@ -3845,6 +4031,8 @@ impl Workspace {
             .on_action(cx.listener(Self::send_keystrokes))
             .on_action(cx.listener(Self::add_folder_to_project))
             .on_action(cx.listener(Self::follow_next_collaborator))
+            .on_action(cx.listener(|_, _: &FocusNext, cx| cx.focus_next()))
+            .on_action(cx.listener(|_, _: &FocusPrevious, cx| cx.focus_previous()))
             .on_action(cx.listener(|workspace, _: &Unfollow, cx| {
                 let pane = workspace.active_pane().clone();
                 workspace.unfollow(&pane, cx);
@@ -3905,6 +4093,7 @@ impl Workspace {
                 }),
             )
             .on_action(cx.listener(Workspace::toggle_centered_layout))
+            .on_action(cx.listener(Workspace::toggle_performance_hud))
     }
 
     #[cfg(any(test, feature = "test-support"))]
@@ -3984,6 +4173,13 @@ impl Workspace {
         cx.notify();
     }
 
+    pub fn toggle_performance_hud(&mut self, _: &TogglePerformanceHud, cx: &mut ViewContext<Self>) {
+        if self.performance_hud.take().is_none() {
+            self.performance_hud = Some(cx.new_view(PerformanceHud::new));
+        }
+        cx.notify();
+    }
+
     fn adjust_padding(padding: Option<f32>) -> f32 {
         padding
             .unwrap_or(Self::DEFAULT_PADDING)
@@ -4295,7 +4491,8 @@ impl Render for Workspace {
                         })
                     }))
                     .child(self.modal_layer.clone())
-                    .children(self.render_notifications(cx)),
+                    .children(self.render_notifications(cx))
+                    .children(self.performance_hud.clone()),
             )
             .child(self.status_bar.clone())
             .children(if self.project.read(cx).is_disconnected() {
@@ -4754,16 +4951,23 @@ fn activate_any_workspace_window(cx: &mut AsyncAppContext) -> Option<WindowHandl
     .flatten()
 }
 
+/// Returns the windows of local workspaces, most recently activated first.
 fn local_workspace_windows(cx: &AppContext) -> Vec<WindowHandle<Workspace>> {
-    cx.windows()
+    let mut windows = cx
+        .windows()
         .into_iter()
         .filter_map(|window| window.downcast::<Workspace>())
-        .filter(|workspace| {
+        .filter_map(|window| {
+            let workspace = window.read(cx).ok()?;
             workspace
+                .project
                 .read(cx)
-                .is_ok_and(|workspace| workspace.project.read(cx).is_local())
+                .is_local()
+                .then_some((window, workspace.last_activated_at))
         })
-        .collect()
+        .collect::<Vec<_>>();
+    windows.sort_by_key(|(_, last_activated_at)| cmp::Reverse(*last_activated_at));
+    windows.into_iter().map(|(window, _)| window).collect()
 }
 
 #[derive(Default)]
@@ -4799,7 +5003,7 @@ pub fn open_paths(
                 if m > best_match {
                     existing = Some(window);
                     best_match = m;
-                } else if best_match.is_none() && open_options.open_new_workspace == Some(false) {
+                } else if existing.is_none() && open_options.open_new_workspace == Some(false) {
                     existing = Some(window)
                 }
             }
@@ -5164,6 +5368,69 @@ pub fn reload(reload: &Reload, cx: &mut AppContext) {
     .detach_and_log_err(cx);
 }
 
+/// Fills in the placeholders of a `window_title` template. The segments of the
+/// template between separators that are left empty by placeholders without a
+/// value are dropped, along with the separator before them.
+fn format_window_title(
+    template: &str,
+    project: &str,
+    branch: Option<&str>,
+    file: Option<&str>,
+    filename: Option<&str>,
+) -> String {
+    const SEPARATORS: &str = "—–-|:·";
+    let is_separator_run = |run: &str| run.contains(|c: char| SEPARATORS.contains(c));
+
+    // Splits the template into segments, each with the separator before it.
+    let mut segments = Vec::new();
+    let mut separator_start = 0;
+    let mut segment_start = 0;
+    let mut chars = template.char_indices().peekable();
+    while let Some((ix, c)) = chars.next() {
+        if !(c.is_whitespace() || SEPARATORS.contains(c)) {
+            continue;
+        }
+        let mut end = ix + c.len_utf8();
+        while let Some((next_ix, next_c)) = chars
+            .peek()
+            .copied()
+            .filter(|(_, c)| c.is_whitespace() || SEPARATORS.contains(*c))
+        {
+            end = next_ix + next_c.len_utf8();
+            chars.next();
+        }
+        if is_separator_run(&template[ix..end]) {
+            segments.push((
+                &template[separator_start..segment_start],
+                &template[segment_start..ix],
+            ));
+            separator_start = ix;
+            segment_start = end;
+        }
+    }
+    segments.push((
+        &template[separator_start..segment_start],
+        &template[segment_start..],
+    ));
+
+    let mut title = String::new();
+    for (separator, segment) in segments {
+        let segment = segment
+            .replace("{project}", project)
+            .replace("{branch}", branch.unwrap_or_default())
+            .replace("{file}", file.unwrap_or_default())
+            .replace("{filename}", filename.unwrap_or_default());
+        if segment.trim().is_empty() {
+            continue;
+        }
+        if !title.is_empty() {
+            title.push_str(separator);
+        }
+        title.push_str(&segment);
+    }
+    title.trim().to_string()
+}
+
 fn parse_pixel_position_env_var(value: &str) -> Option<Point<Pixels>> {
     let mut parts = value.split(',');
     let x: usize = parts.next()?.parse().ok()?;
@@ -5199,6 +5466,105 @@ mod tests {
     use serde_json::json;
     use settings::SettingsStore;
 
+    #[test]
+    fn test_format_window_title() {
+        let template = "{project} — {branch} — {filename}";
+        assert_eq!(
+            format_window_title(
+                template,
+                "zed",
+                Some("main"),
+                Some("src/lib.rs"),
+                Some("lib.rs")
+            ),
+            "zed — main — lib.rs"
+        );
+        assert_eq!(
+            format_window_title(template, "zed", Some("main"), None, None),
+            "zed — main"
+        );
+        assert_eq!(
+            format_window_title("{file} | {project}", "zed", None, Some("src/lib.rs"), None),
+            "src/lib.rs | zed"
+        );
+        assert_eq!(
+            format_window_title("{file} | {project}", "zed", None, None, None),
+            "zed"
+        );
+        assert_eq!(
+            format_window_title(template, "zed", None, None, Some("lib.rs")),
+            "zed — lib.rs"
+        );
+        assert_eq!(
+            format_window_title(
+                "{project}: {branch} - {filename}",
+                "my-app",
+                None,
+                None,
+                None
+            ),
+            "my-app"
+        );
+    }
+
+    #[gpui::test]
+    async fn test_serializing_reordered_worktrees(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/reordered", json!({ "a": {}, "b": {}, "c": {} }))
+            .await;
+        let project = Project::test(
+            fs,
+            [
+                "/reordered/a".as_ref(),
+                "/reordered/b".as_ref(),
+                "/reordered/c".as_ref(),
+            ],
+            cx,
+        )
+        .await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+        let database_id = persistence::DB.next_id().await.unwrap();
+        workspace.update(cx, |workspace, _| workspace.database_id = Some(database_id));
+
+        // Moves `c` before `a`, so that the roots aren't ordered by their paths.
+        let worktree_ids = project.read_with(cx, |project, cx| {
+            project
+                .visible_worktrees(cx)
+                .map(|worktree| worktree.read(cx).id())
+                .collect::<Vec<_>>()
+        });
+        project
+            .update(cx, |project, cx| {
+                project.move_worktree(worktree_ids[2], worktree_ids[0], cx)
+            })
+            .unwrap();
+        cx.executor().advance_clock(Duration::from_millis(100));
+        cx.run_until_parked();
+
+        let serialized = persistence::DB
+            .workspace_for_roots(&["/reordered/c", "/reordered/b", "/reordered/a"])
+            .expect("the workspace should have been serialized");
+        let SerializedWorkspaceLocation::Local(paths, order) = serialized.location else {
+            panic!("the workspace should be serialized as a local one");
+        };
+        let paths = paths.paths();
+        let roots = order
+            .order()
+            .iter()
+            .map(|&i| paths[i].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            roots,
+            [
+                PathBuf::from("/reordered/c"),
+                PathBuf::from("/reordered/a"),
+                PathBuf::from("/reordered/b"),
+            ]
+        );
+    }
+
     #[gpui::test]
     async fn test_tab_disambiguation(cx: &mut TestAppContext) {
         init_test(cx);
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use collections::HashMap;
 use gpui::AppContext;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -14,6 +15,10 @@ pub struct WorkspaceSettings {
     pub restore_on_startup: RestoreOnStartupBehaviour,
     pub drop_target_size: f32,
     pub when_closing_with_no_tabs: CloseWindowWhenNoItems,
+    pub unload_inactive_tabs: UnloadInactiveTabsSetting,
+    pub disable_gpu: bool,
+    pub window_title: Option<String>,
+    pub window_accent_color: Option<String>,
 }
 
 #[derive(Copy, Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -83,6 +88,27 @@ pub struct WorkspaceSettingsContent {
     ///
     /// Default: auto ("on" on macOS, "off" otherwise)
     pub when_closing_with_no_tabs: Option<CloseWindowWhenNoItems>,
+    /// When to drop the contents of tabs that weren't viewed for a while, to bound memory usage.
+    /// Unloaded tabs are reopened when activated again.
+    ///
+    /// Default: off
+    pub unload_inactive_tabs: Option<UnloadInactiveTabsSetting>,
+    /// Whether to render windows without the GPU, for machines whose graphics drivers
+    /// can't be used. This is much slower, and takes effect once Zed is restarted.
+    ///
+    /// Default: false
+    pub disable_gpu: Option<bool>,
+    /// A template for the window title, which can use the variables `{project}`,
+    /// `{branch}`, `{file}` and `{filename}`. When unset, the title shows the active
+    /// file's name followed by the project's name.
+    ///
+    /// Default: null
+    pub window_title: Option<Option<String>>,
+    /// A color, such as `"#ff8800"`, with which to tint the title bar and status
+    /// bar. Set this in a project's settings to tell its windows apart.
+    ///
+    /// Default: null
+    pub window_accent_color: Option<Option<String>>,
 }
 
 #[derive(Deserialize)]
@@ -103,6 +129,44 @@ pub struct TabBarSettingsContent {
     pub show_nav_history_buttons: Option<bool>,
 }
 
+#[derive(Deserialize)]
+pub struct StatusBarSettings {
+    pub items: HashMap<String, StatusItemSettings>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct StatusBarSettingsContent {
+    /// How to show the items of the status bar, by the item's identifier,
+    /// like `feedback`.
+    ///
+    /// Default: {}
+    pub items: Option<HashMap<String, StatusItemSettings>>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct StatusItemSettings {
+    /// Whether to show the item.
+    ///
+    /// Default: true
+    pub visible: Option<bool>,
+    /// The group of the status bar to show the item in.
+    ///
+    /// Default: the group that the item is added to
+    pub group: Option<StatusItemGroup>,
+    /// Where to show the item in its group. The items of a group are sorted
+    /// by this from left to right, with items that don't set it at 0.
+    ///
+    /// Default: 0
+    pub order: Option<i32>,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusItemGroup {
+    Left,
+    Right,
+}
+
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum AutosaveSetting {
@@ -116,6 +180,15 @@ pub enum AutosaveSetting {
     OnWindowChange,
 }
 
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UnloadInactiveTabsSetting {
+    /// Never unload tabs.
+    Off,
+    /// Unload clean tabs that weren't viewed for `minutes`.
+    AfterDelay { minutes: u64 },
+}
+
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct CenteredLayoutSettings {
@@ -150,3 +223,13 @@ impl Settings for TabBarSettings {
         sources.json_merge()
     }
 }
+
+impl Settings for StatusBarSettings {
+    const KEY: Option<&'static str> = Some("status_bar");
+
+    type FileContent = StatusBarSettingsContent;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut AppContext) -> Result<Self> {
+        sources.json_merge()
+    }
+}
@@ -289,6 +289,9 @@ pub struct LocalSnapshot {
     /// All of the gitignore files in the worktree, indexed by their relative path.
     /// The boolean indicates whether the gitignore needs to be updated.
     ignores_by_parent_abs_path: HashMap<Arc<Path>, (Arc<Gitignore>, bool)>,
+    /// All of the `.zedignore` files in and above the worktree, indexed by the
+    /// absolute path of their parent directory.
+    zedignores_by_parent_abs_path: HashMap<Arc<Path>, Arc<Gitignore>>,
     /// All of the git repositories in the worktree, indexed by the project entry
     /// id of their parent directory.
     git_repositories: TreeMap<ProjectEntryId, LocalRepositoryEntry>,
@@ -362,6 +365,11 @@ pub enum Event {
 
 static EMPTY_PATH: &str = "";
 
+/// The name of the files that hide paths from project search, the file finder
+/// and diagnostics, using the same syntax as `.gitignore` files. Unlike
+/// `.gitignore` files, they don't change what git ignores.
+pub const ZEDIGNORE: &str = ".zedignore";
+
 impl EventEmitter<Event> for Worktree {}
 
 impl Worktree {
@@ -406,6 +414,7 @@ impl Worktree {
 
             let mut snapshot = LocalSnapshot {
                 ignores_by_parent_abs_path: Default::default(),
+                zedignores_by_parent_abs_path: Default::default(),
                 git_repositories: Default::default(),
                 snapshot: Snapshot::new(
                     cx.entity_id().as_u64(),
@@ -2225,6 +2234,8 @@ impl Snapshot {
                     Some(GitFileStatus::Modified)
                 } else if statuses.added > 0 {
                     Some(GitFileStatus::Added)
+                } else if statuses.untracked > 0 {
+                    Some(GitFileStatus::Untracked)
                 } else {
                     None
                 };
@@ -2462,6 +2473,25 @@ impl LocalSnapshot {
         inodes
     }
 
+    /// Whether the path is hidden by the `.zedignore` files of its ancestors. The
+    /// patterns of the closest `.zedignore` take precedence.
+    fn is_abs_path_hidden(&self, abs_path: &Path, is_dir: bool) -> bool {
+        if self.zedignores_by_parent_abs_path.is_empty() {
+            return false;
+        }
+        for ancestor in abs_path.ancestors().skip(1) {
+            if let Some(zedignore) = self.zedignores_by_parent_abs_path.get(ancestor) {
+                let path = abs_path.strip_prefix(ancestor).unwrap();
+                match zedignore.matched_path_or_any_parents(path, is_dir) {
+                    ::ignore::Match::None => {}
+                    ::ignore::Match::Ignore(_) => return true,
+                    ::ignore::Match::Whitelist(_) => return false,
+                }
+            }
+        }
+        false
+    }
+
     fn ignore_stack_for_abs_path(&self, abs_path: &Path, is_dir: bool) -> Arc<IgnoreStack> {
         let mut new_ignores = Vec::new();
         for (index, ancestor) in abs_path.ancestors().enumerate() {
@@ -2524,7 +2554,7 @@ impl LocalSnapshot {
         for entry in self.entries_by_path.cursor::<()>() {
             if entry.is_file() {
                 assert_eq!(files.next().unwrap().inode, entry.inode);
-                if !entry.is_ignored && !entry.is_external {
+                if !entry.is_ignored && !entry.is_external && !entry.is_hidden {
                     assert_eq!(visible_files.next().unwrap().inode, entry.inode);
                 }
             }
@@ -2745,6 +2775,11 @@ impl BackgroundScannerState {
         }
         self.snapshot.entries_by_id.edit(entries_by_id_edits, &());
 
+        let abs_path = self.snapshot.abs_path.join(path);
+        self.snapshot
+            .zedignores_by_parent_abs_path
+            .retain(|parent_abs_path, _| !parent_abs_path.starts_with(&abs_path));
+
         if path.file_name() == Some(&GITIGNORE) {
             let abs_parent_path = self.snapshot.abs_path.join(path.parent().unwrap());
             if let Some((_, needs_update)) = self
@@ -2760,6 +2795,37 @@ impl BackgroundScannerState {
         self.snapshot.check_invariants(false);
     }
 
+    /// Updates which of the entries in the directory are hidden, after one of
+    /// the `.zedignore` files that apply to them changed.
+    fn update_hidden_entries(&mut self, dir_path: &Path) {
+        let mut entries_by_path_edits = Vec::new();
+        for entry in self
+            .snapshot
+            .traverse_from_path(true, true, true, dir_path)
+            .take_while(|entry| entry.path.starts_with(dir_path))
+        {
+            let abs_path = self.snapshot.abs_path.join(&entry.path);
+            let is_hidden = self.snapshot.is_abs_path_hidden(&abs_path, entry.is_dir());
+            if entry.is_hidden != is_hidden {
+                let mut entry = entry.clone();
+                entry.is_hidden = is_hidden;
+                entries_by_path_edits.push(Edit::Insert(entry));
+            }
+        }
+        if entries_by_path_edits.is_empty() {
+            return;
+        }
+        self.snapshot
+            .entries_by_path
+            .edit(entries_by_path_edits, &());
+        if let Err(ix) = self
+            .changed_paths
+            .binary_search_by(|path| path.as_ref().cmp(dir_path))
+        {
+            self.changed_paths.insert(ix, dir_path.into());
+        }
+    }
+
     fn build_git_repository(
         &mut self,
         dot_git_path: Arc<Path>,
@@ -3120,6 +3186,11 @@ pub struct Entry {
     pub git_status: Option<GitFileStatus>,
     /// Whether this entry is considered to be a `.env` file.
     pub is_private: bool,
+    /// Whether this entry is hidden by a `.zedignore` file.
+    ///
+    /// Hidden entries are scanned and tracked by git as usual, but are
+    /// excluded from searches like gitignored entries.
+    pub is_hidden: bool,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -3177,6 +3248,7 @@ impl Entry {
             is_ignored: false,
             is_external: false,
             is_private: false,
+            is_hidden: false,
             git_status: None,
         }
     }
@@ -3219,7 +3291,7 @@ impl sum_tree::Item for Entry {
     type Summary = EntrySummary;
 
     fn summary(&self) -> Self::Summary {
-        let non_ignored_count = if self.is_ignored || self.is_external {
+        let non_ignored_count = if self.is_ignored || self.is_external || self.is_hidden {
             0
         } else {
             1
@@ -3240,6 +3312,7 @@ impl sum_tree::Item for Entry {
                 GitFileStatus::Added => statuses.added = 1,
                 GitFileStatus::Modified => statuses.modified = 1,
                 GitFileStatus::Conflict => statuses.conflict = 1,
+                GitFileStatus::Untracked => statuses.untracked = 1,
             },
             None => {}
         }
@@ -3398,6 +3471,15 @@ impl BackgroundScanner {
                         .ignores_by_parent_abs_path
                         .insert(ancestor.into(), (ignore.into(), false));
                 }
+                if let Ok(zedignore) =
+                    build_gitignore(&ancestor.join(ZEDIGNORE), self.fs.as_ref()).await
+                {
+                    self.state
+                        .lock()
+                        .snapshot
+                        .zedignores_by_parent_abs_path
+                        .insert(ancestor.into(), zedignore.into());
+                }
             }
 
             let ancestor_dot_git = ancestor.join(&*DOT_GIT);
@@ -3798,6 +3880,7 @@ impl BackgroundScanner {
         let mut ignore_stack = job.ignore_stack.clone();
         let mut containing_repository = job.containing_repository.clone();
         let mut new_ignore = None;
+        let mut new_zedignore = None;
         let mut root_canonical_path = None;
         let mut new_entries: Vec<Entry> = Vec::new();
         let mut new_jobs: Vec<Option<ScanJob>> = Vec::new();
@@ -3869,6 +3952,15 @@ impl BackgroundScanner {
                         );
                     }
                 }
+            } else if child_name == ZEDIGNORE {
+                match build_gitignore(&child_abs_path, self.fs.as_ref()).await {
+                    Ok(zedignore) => new_zedignore = Some(Arc::new(zedignore)),
+                    Err(error) => {
+                        log::error!(
+                            "error loading {ZEDIGNORE} file {child_abs_path:?} - {error:?}"
+                        );
+                    }
+                }
             }
 
             if self.settings.is_path_excluded(&child_path) {
@@ -3977,11 +4069,28 @@ impl BackgroundScanner {
         }
 
         let mut state = self.state.lock();
+        match new_zedignore {
+            Some(zedignore) => {
+                state
+                    .snapshot
+                    .zedignores_by_parent_abs_path
+                    .insert(job.abs_path.clone(), zedignore);
+            }
+            None => {
+                state
+                    .snapshot
+                    .zedignores_by_parent_abs_path
+                    .remove(&job.abs_path);
+            }
+        }
 
         // Identify any subdirectories that should not be scanned.
         let mut job_ix = 0;
         for entry in &mut new_entries {
             state.reuse_entry_id(entry);
+            entry.is_hidden = state
+                .snapshot
+                .is_abs_path_hidden(&root_abs_path.join(&entry.path), entry.is_dir());
             if entry.is_dir() {
                 if state.should_scan_directory(entry) {
                     job_ix += 1;
@@ -4044,6 +4153,22 @@ impl BackgroundScanner {
         )
         .await;
 
+        // Reload the `.zedignore` files that changed, to update which entries they hide.
+        let zedignores = futures::future::join_all(
+            abs_paths
+                .iter()
+                .filter(|abs_path| abs_path.file_name() == Some(OsStr::new(ZEDIGNORE)))
+                .map(|abs_path| async move {
+                    let zedignore = if self.fs.is_file(abs_path).await {
+                        build_gitignore(abs_path, self.fs.as_ref()).await.log_err()
+                    } else {
+                        None
+                    };
+                    (abs_path.parent().unwrap(), zedignore)
+                }),
+        )
+        .await;
+
         let mut state = self.state.lock();
         let doing_recursive_update = scan_queue_tx.is_some();
 
@@ -4057,6 +4182,26 @@ impl BackgroundScanner {
             }
         }
 
+        for (parent_abs_path, zedignore) in zedignores {
+            match zedignore {
+                Some(zedignore) => {
+                    state
+                        .snapshot
+                        .zedignores_by_parent_abs_path
+                        .insert(parent_abs_path.into(), Arc::new(zedignore));
+                }
+                None => {
+                    state
+                        .snapshot
+                        .zedignores_by_parent_abs_path
+                        .remove(parent_abs_path);
+                }
+            }
+            if let Ok(parent_path) = parent_abs_path.strip_prefix(&root_abs_path) {
+                state.update_hidden_entries(parent_path);
+            }
+        }
+
         for (path, metadata) in relative_paths.iter().zip(metadata.iter()) {
             let abs_path: Arc<Path> = root_abs_path.join(&path).into();
             match metadata {
@@ -4081,6 +4226,7 @@ impl BackgroundScanner {
                     fs_entry.is_ignored = ignore_stack.is_abs_path_ignored(&abs_path, is_dir);
                     fs_entry.is_external = !canonical_path.starts_with(&root_canonical_path);
                     fs_entry.is_private = self.is_path_private(path);
+                    fs_entry.is_hidden = state.snapshot.is_abs_path_hidden(&abs_path, is_dir);
 
                     if !is_dir && !fs_entry.is_ignored && !fs_entry.is_external {
                         if let Some((repo_entry, repo)) = state.snapshot.repo_for_path(path) {
@@ -4832,6 +4978,7 @@ struct GitStatuses {
     added: usize,
     modified: usize,
     conflict: usize,
+    untracked: usize,
 }
 
 impl AddAssign for GitStatuses {
@@ -4839,6 +4986,7 @@ impl AddAssign for GitStatuses {
         self.added += rhs.added;
         self.modified += rhs.modified;
         self.conflict += rhs.conflict;
+        self.untracked += rhs.untracked;
     }
 }
 
@@ -4850,6 +4998,7 @@ impl Sub for GitStatuses {
             added: self.added - rhs.added,
             modified: self.modified - rhs.modified,
             conflict: self.conflict - rhs.conflict,
+            untracked: self.untracked - rhs.untracked,
         }
     }
 }
@@ -5064,6 +5213,7 @@ impl<'a> TryFrom<(&'a CharBag, proto::Entry)> for Entry {
             is_external: entry.is_external,
             git_status: git_status_from_proto(entry.git_status),
             is_private: false,
+            is_hidden: false,
             is_symlink: entry.is_symlink,
         })
     }
@@ -5075,6 +5225,7 @@ fn git_status_from_proto(git_status: Option<i32>) -> Option<GitFileStatus> {
             proto::GitStatus::Added => GitFileStatus::Added,
             proto::GitStatus::Modified => GitFileStatus::Modified,
             proto::GitStatus::Conflict => GitFileStatus::Conflict,
+            proto::GitStatus::Untracked => GitFileStatus::Untracked,
         })
     })
 }
@@ -5084,6 +5235,7 @@ fn git_status_to_proto(status: GitFileStatus) -> i32 {
         GitFileStatus::Added => proto::GitStatus::Added as i32,
         GitFileStatus::Modified => proto::GitStatus::Modified as i32,
         GitFileStatus::Conflict => proto::GitStatus::Conflict as i32,
+        GitFileStatus::Untracked => proto::GitStatus::Untracked as i32,
     }
 }
 
@@ -821,6 +821,88 @@ async fn test_update_gitignore(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_zedignore(cx: &mut TestAppContext) {
+    init_test(cx);
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree(
+        "/root",
+        json!({
+            ".gitignore": "*.log\n",
+            ".zedignore": "generated/\n!debug.log\n",
+            "a.rs": "",
+            "debug.log": "",
+            "other.log": "",
+            "generated": {
+                "b.rs": "",
+            },
+        }),
+    )
+    .await;
+
+    let tree = Worktree::local(
+        "/root".as_ref(),
+        true,
+        fs.clone(),
+        Default::default(),
+        &mut cx.to_async(),
+    )
+    .await
+    .unwrap();
+    cx.read(|cx| tree.read(cx).as_local().unwrap().scan_complete())
+        .await;
+
+    // The .zedignore hides paths without changing what's gitignored.
+    cx.read(|cx| {
+        let tree = tree.read(cx);
+        assert_entry_git_state(tree, "a.rs", None, false);
+        assert_entry_git_state(tree, "debug.log", None, true);
+        assert_entry_git_state(tree, "other.log", None, true);
+        assert_entry_git_state(tree, "generated", None, false);
+        assert_eq!(
+            hidden_paths(tree),
+            [Path::new("generated"), Path::new("generated/b.rs")]
+        );
+        assert_eq!(
+            tree.files(false, 0)
+                .map(|entry| entry.path.as_ref())
+                .collect::<Vec<_>>(),
+            [
+                Path::new(".gitignore"),
+                Path::new(".zedignore"),
+                Path::new("a.rs")
+            ]
+        );
+    });
+
+    fs.atomic_write("/root/.zedignore".into(), "*.rs\n".into())
+        .await
+        .unwrap();
+    cx.executor().run_until_parked();
+    cx.read(|cx| {
+        assert_eq!(
+            hidden_paths(tree.read(cx)),
+            [Path::new("a.rs"), Path::new("generated/b.rs")]
+        );
+    });
+
+    fs.remove_file("/root/.zedignore".as_ref(), Default::default())
+        .await
+        .unwrap();
+    cx.executor().run_until_parked();
+    cx.read(|cx| {
+        assert!(hidden_paths(tree.read(cx)).is_empty());
+        assert_entry_git_state(tree.read(cx), "other.log", None, true);
+    });
+}
+
+fn hidden_paths(tree: &Worktree) -> Vec<&Path> {
+    tree.entries(true, 0)
+        .filter(|entry| entry.is_hidden)
+        .map(|entry| entry.path.as_ref())
+        .collect()
+}
+
 #[gpui::test]
 async fn test_write_file(cx: &mut TestAppContext) {
     init_test(cx);
@@ -1881,7 +1963,7 @@ async fn test_rename_work_directory(cx: &mut TestAppContext) {
         );
         assert_eq!(
             tree.status_for_file(Path::new("projects/project1/b")),
-            Some(GitFileStatus::Added)
+            Some(GitFileStatus::Untracked)
         );
     });
 
@@ -1902,7 +1984,7 @@ async fn test_rename_work_directory(cx: &mut TestAppContext) {
         );
         assert_eq!(
             tree.status_for_file(Path::new("projects/project2/b")),
-            Some(GitFileStatus::Added)
+            Some(GitFileStatus::Untracked)
         );
     });
 }
@@ -2094,11 +2176,11 @@ async fn test_git_status(cx: &mut TestAppContext) {
 
         assert_eq!(
             snapshot.status_for_file(project_path.join(B_TXT)),
-            Some(GitFileStatus::Added)
+            Some(GitFileStatus::Untracked)
         );
         assert_eq!(
             snapshot.status_for_file(project_path.join(F_TXT)),
-            Some(GitFileStatus::Added)
+            Some(GitFileStatus::Untracked)
         );
     });
 
@@ -2128,7 +2210,7 @@ async fn test_git_status(cx: &mut TestAppContext) {
         let snapshot = tree.snapshot();
         assert_eq!(
             snapshot.status_for_file(project_path.join(F_TXT)),
-            Some(GitFileStatus::Added)
+            Some(GitFileStatus::Untracked)
         );
         assert_eq!(snapshot.status_for_file(project_path.join(B_TXT)), None);
         assert_eq!(snapshot.status_for_file(project_path.join(A_TXT)), None);
@@ -2150,7 +2232,7 @@ async fn test_git_status(cx: &mut TestAppContext) {
         assert_eq!(snapshot.status_for_file(project_path.join(A_TXT)), None);
         assert_eq!(
             snapshot.status_for_file(project_path.join(B_TXT)),
-            Some(GitFileStatus::Added)
+            Some(GitFileStatus::Untracked)
         );
         assert_eq!(
             snapshot.status_for_file(project_path.join(E_TXT)),
@@ -2189,7 +2271,7 @@ async fn test_git_status(cx: &mut TestAppContext) {
         let snapshot = tree.snapshot();
         assert_eq!(
             snapshot.status_for_file(&project_path.join(renamed_dir_name).join(RENAMED_FILE)),
-            Some(GitFileStatus::Added)
+            Some(GitFileStatus::Untracked)
         );
     });
 
@@ -2213,7 +2295,7 @@ async fn test_git_status(cx: &mut TestAppContext) {
                     .join(Path::new(renamed_dir_name))
                     .join(RENAMED_FILE)
             ),
-            Some(GitFileStatus::Added)
+            Some(GitFileStatus::Untracked)
         );
     });
 }
@@ -2285,7 +2367,7 @@ async fn test_repository_subfolder_git_status(cx: &mut TestAppContext) {
         assert_eq!(snapshot.status_for_file("c.txt"), None);
         assert_eq!(
             snapshot.status_for_file("d/e.txt"),
-            Some(GitFileStatus::Added)
+            Some(GitFileStatus::Untracked)
         );
     });
 
@@ -0,0 +1,205 @@
+//! Detects crashes of the previous session, from the panics that Zed recorded
+//! and the crash dumps that the OS wrote, and offers to report them.
+
+use std::{cell::Cell, path::PathBuf};
+
+use db::kvp::KEY_VALUE_STORE;
+use gpui::{AppContext, ViewContext, VisualContext};
+use paths::crashes_dir;
+use smol::stream::StreamExt;
+use telemetry_events::Panic;
+use util::ResultExt;
+use workspace::{
+    notifications::{simple_message_notification::MessageNotification, NotificationId},
+    Workspace,
+};
+
+/// The number of backtrace frames included in a crash report, to keep its URL short.
+const MAX_CRASH_REPORT_FRAMES: usize = 30;
+
+/// The number of lines of a crash dump included in a crash report.
+const MAX_CRASH_DUMP_LINES: usize = 60;
+
+static LAST_CRASH_DUMP_DETECTED: &str = "LAST_CRASH_DUMP_DETECTED";
+
+static LAST_PANIC_NOTIFIED: &str = "LAST_PANIC_NOTIFIED";
+
+/// A crash of the previous session.
+pub struct PreviousCrash {
+    /// A one-line description of the crash.
+    summary: String,
+    /// The details of the crash, like its backtrace, as Markdown.
+    details: String,
+    /// When the crash was a panic, the time it happened, for recording that it
+    /// was notified of.
+    panicked_on: Option<i64>,
+}
+
+impl PreviousCrash {
+    pub fn from_panic(panic: &Panic) -> Self {
+        let location = panic
+            .location_data
+            .as_ref()
+            .map(|location| format!(" at {}:{}", location.file, location.line))
+            .unwrap_or_default();
+        let mut details = format!(
+            "Zed {} ({}) panicked on thread {:?}{location}:\n\n```\n{}\n```\n\nBacktrace:\n\n```\n",
+            panic.app_version, panic.release_channel, panic.thread, panic.payload,
+        );
+        for frame in panic.backtrace.iter().take(MAX_CRASH_REPORT_FRAMES) {
+            details.push_str(frame);
+            details.push('\n');
+        }
+        if panic.backtrace.len() > MAX_CRASH_REPORT_FRAMES {
+            details.push_str("...\n");
+        }
+        details.push_str("```");
+
+        Self {
+            summary: format!("Zed crashed during the last session: {}", panic.payload),
+            details,
+            panicked_on: Some(panic.panicked_on),
+        }
+    }
+
+    fn from_crash_dump(file_name: &str, contents: &str) -> Self {
+        let mut details = format!("Zed crashed, as reported by `{file_name}`:\n\n```\n");
+        for line in contents.lines().take(MAX_CRASH_DUMP_LINES) {
+            details.push_str(line);
+            details.push('\n');
+        }
+        if contents.lines().nth(MAX_CRASH_DUMP_LINES).is_some() {
+            details.push_str("...\n");
+        }
+        details.push_str("```");
+
+        Self {
+            summary: "Zed crashed during the last session.".into(),
+            details,
+            panicked_on: None,
+        }
+    }
+}
+
+/// Returns whether the panic is newer than the last one that was notified of.
+/// Panics that fail to upload are kept to retry on the next launch, so this
+/// keeps them from being notified of again.
+pub fn is_panic_unnotified(panic: &Panic) -> bool {
+    KEY_VALUE_STORE
+        .read_kvp(LAST_PANIC_NOTIFIED)
+        .log_err()
+        .flatten()
+        .and_then(|last_notified| last_notified.parse::<i64>().log_err())
+        .map_or(true, |last_notified| panic.panicked_on > last_notified)
+}
+
+/// Returns the newest crash dump of Zed, if it was written since the last
+/// time that crash dumps were checked. Crash dumps are only written on macOS.
+pub async fn detect_crash_dump() -> Option<PreviousCrash> {
+    let dir = crashes_dir().as_ref()?;
+    let mut newest: Option<(String, PathBuf)> = None;
+    let mut children = smol::fs::read_dir(dir).await.log_err()?;
+    while let Some(child) = children.next().await {
+        let Ok(child) = child else {
+            continue;
+        };
+        let path = child.path();
+        let Some(file_name) = path.file_name().map(|f| f.to_string_lossy().to_lowercase()) else {
+            continue;
+        };
+        if !file_name.starts_with("zed-") || !file_name.ends_with(".ips") {
+            continue;
+        }
+        if newest
+            .as_ref()
+            .map_or(true, |(newest, _)| file_name > *newest)
+        {
+            newest = Some((file_name, path));
+        }
+    }
+
+    let (file_name, path) = newest?;
+    let last_detected = KEY_VALUE_STORE
+        .read_kvp(LAST_CRASH_DUMP_DETECTED)
+        .log_err()
+        .flatten();
+    KEY_VALUE_STORE
+        .write_kvp(LAST_CRASH_DUMP_DETECTED.to_string(), file_name.clone())
+        .await
+        .log_err();
+    // The first time crash dumps are checked, the newest one is only recorded,
+    // so that crashes from long ago aren't reported.
+    if last_detected.map_or(true, |last_detected| file_name <= last_detected) {
+        return None;
+    }
+
+    let contents = smol::fs::read_to_string(&path).await.log_err()?;
+    Some(PreviousCrash::from_crash_dump(&file_name, &contents))
+}
+
+/// Shows a notification about a crash during the previous session, which
+/// offers to report it with feedback or to file a crash report on GitHub.
+pub fn notify_of_previous_crash(crash: PreviousCrash, cx: &mut AppContext) {
+    let workspace = cx
+        .windows()
+        .into_iter()
+        .find_map(|window| window.downcast::<Workspace>());
+    if let Some(workspace) = workspace {
+        workspace
+            .update(cx, |workspace, cx| {
+                show_previous_crash_notification(workspace, crash, cx)
+            })
+            .log_err();
+    } else {
+        // Wait for the first workspace to be opened.
+        let notification = Cell::new(Some(crash));
+        cx.observe_new_views(move |workspace: &mut Workspace, cx| {
+            if let Some(crash) = notification.take() {
+                show_previous_crash_notification(workspace, crash, cx);
+            }
+        })
+        .detach();
+    }
+}
+
+fn show_previous_crash_notification(
+    workspace: &mut Workspace,
+    crash: PreviousCrash,
+    cx: &mut ViewContext<Workspace>,
+) {
+    struct PreviousCrashNotification;
+
+    if let Some(panicked_on) = crash.panicked_on {
+        cx.background_executor()
+            .spawn(
+                KEY_VALUE_STORE.write_kvp(LAST_PANIC_NOTIFIED.to_string(), panicked_on.to_string()),
+            )
+            .detach_and_log_err(cx);
+    }
+
+    let workspace_handle = workspace.weak_handle();
+    workspace.show_notification(
+        NotificationId::unique::<PreviousCrashNotification>(),
+        cx,
+        |cx| {
+            cx.new_view(|_| {
+                let PreviousCrash {
+                    summary, details, ..
+                } = crash;
+                let crash_report = details.clone();
+                MessageNotification::new(summary)
+                    .with_click_message("Report Crash")
+                    .on_click(move |cx| {
+                        let crash_report = crash_report.clone();
+                        workspace_handle
+                            .update(cx, |workspace, cx| {
+                                feedback::report_crash(workspace, crash_report, cx)
+                            })
+                            .log_err();
+                    })
+                    .with_secondary_click_message("File on GitHub")
+                    .on_secondary_click(move |cx| feedback::file_crash_report(details.clone(), cx))
+            })
+        },
+    );
+}
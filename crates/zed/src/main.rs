@@ -3,6 +3,8 @@
 // Disable command line from opening on release mode
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod crash_detection;
+mod performance_sampler;
 mod reliability;
 mod zed;
 
@@ -14,6 +16,7 @@ use collab_ui::channel_view::ChannelView;
 use db::kvp::KEY_VALUE_STORE;
 use editor::Editor;
 use env_logger::Builder;
+use feedback::SystemSpecs;
 use fs::RealFs;
 use futures::{future, StreamExt};
 use git::GitHostingProviderRegistry;
@@ -21,23 +24,24 @@ use gpui::{
     App, AppContext, AsyncAppContext, Context, Global, Task, UpdateGlobal as _, VisualContext,
 };
 use image_viewer;
-use language::LanguageRegistry;
+use language::{LanguageRegistry, TextMateGrammar};
 use log::LevelFilter;
 
 use assets::Assets;
 use node_runtime::RealNodeRuntime;
 use parking_lot::Mutex;
-use release_channel::{AppCommitSha, AppVersion};
+use release_channel::{AppCommitSha, AppVersion, RELEASE_CHANNEL};
 use settings::{handle_settings_file_changes, watch_config_file, Settings, SettingsStore};
 use simplelog::ConfigBuilder;
 use smol::process::Command;
 use std::{
     env,
     fs::OpenOptions,
-    io::{IsTerminal, Write},
+    io::{self, IsTerminal, Write},
     path::Path,
     process,
-    sync::Arc,
+    sync::{Arc, OnceLock},
+    time::Instant,
 };
 use theme::{ActiveTheme, SystemAppearance, ThemeRegistry, ThemeSettings};
 use util::{maybe, parse_env_output, with_clone, ResultExt, TryFutureExt};
@@ -45,12 +49,16 @@ use uuid::Uuid;
 use welcome::{show_welcome_view, BaseKeymap, FIRST_OPEN};
 use workspace::{AppState, WorkspaceSettings, WorkspaceStore};
 use zed::{
-    app_menus, build_window_options, handle_cli_connection, handle_keymap_file_changes,
-    initialize_workspace, open_paths_with_positions, OpenListener, OpenRequest,
+    build_window_options, confirm_link_paths, deferred_startup, handle_cli_connection,
+    handle_keymap_file_changes, initialize_workspace, open_paths_with_positions, set_app_menus,
+    OpenListener, OpenRequest,
 };
 
 use crate::zed::inline_completion_registry;
 
+/// When the process started, used to log how long it takes until the first window is drawn.
+static STARTUP_TIME: OnceLock<Instant> = OnceLock::new();
+
 #[cfg(feature = "mimalloc")]
 #[global_allocator]
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
@@ -167,6 +175,7 @@ fn init_ui(app_state: Arc<AppState>, cx: &mut AppContext) -> Result<()> {
 
     SystemAppearance::init(cx);
     load_embedded_fonts(cx);
+    i18n::init(cx);
 
     #[cfg(target_os = "linux")]
     crate::zed::linux_prompts::init(cx);
@@ -180,6 +189,10 @@ fn init_ui(app_state: Arc<AppState>, cx: &mut AppContext) -> Result<()> {
 
     audio::init(Assets, cx);
     workspace::init(app_state.clone(), cx);
+    // The renderer is chosen for the whole process, so this is only read at startup.
+    if WorkspaceSettings::get_global(cx).disable_gpu {
+        gpui::set_software_rendering(true);
+    }
     recent_projects::init(cx);
 
     go_to_line::init(cx);
@@ -189,6 +202,7 @@ fn init_ui(app_state: Arc<AppState>, cx: &mut AppContext) -> Result<()> {
     project_symbols::init(cx);
     project_panel::init(Assets, cx);
     outline_panel::init(Assets, cx);
+    todo_panel::init(cx);
     tasks_ui::init(cx);
     channel::init(&app_state.client.clone(), app_state.user_store.clone(), cx);
     search::init(cx);
@@ -202,10 +216,15 @@ fn init_ui(app_state: Arc<AppState>, cx: &mut AppContext) -> Result<()> {
     call::init(app_state.client.clone(), app_state.user_store.clone(), cx);
     notifications::init(app_state.client.clone(), app_state.user_store.clone(), cx);
     collab_ui::init(&app_state, cx);
-    feedback::init(cx);
+    feedback::init(app_state.client.clone(), cx);
+    performance_sampler::init(cx);
     markdown_preview::init(cx);
     welcome::init(cx);
     extensions_ui::init(cx);
+    external_tools::init(cx);
+    scripting::init(cx);
+    log_viewer::init(cx);
+    local_history::init(app_state.fs.clone(), cx);
 
     // Initialize each completion provider. Settings are used for toggling between them.
     let copilot_language_server_id = app_state.languages.next_language_server_id();
@@ -265,23 +284,36 @@ fn init_ui(app_state: Arc<AppState>, cx: &mut AppContext) -> Result<()> {
     dev_server_projects::init(app_state.client.clone(), cx);
 
     let fs = app_state.fs.clone();
+    snippet_provider::init(fs.clone(), cx);
     load_user_themes_in_background(fs.clone(), cx);
     watch_themes(fs.clone(), cx);
+    load_user_textmate_grammars_in_background(fs.clone(), app_state.languages.clone(), cx);
     watch_languages(fs.clone(), app_state.languages.clone(), cx);
     watch_file_types(fs.clone(), cx);
 
-    cx.set_menus(app_menus());
+    set_app_menus(cx);
+    cx.observe_global::<i18n::Catalog>(set_app_menus).detach();
+    // Connecting to collab, starting language servers and loading the project panel compete
+    // with the first window for the main thread, so they're only started once it's drawn.
+    deferred_startup::init(STARTUP_TIME.get().copied().unwrap_or_else(Instant::now), cx);
     initialize_workspace(app_state.clone(), cx);
 
     cx.activate(true);
 
-    cx.spawn(|cx| async move { authenticate(app_state.client.clone(), &cx).await })
-        .detach_and_log_err(cx);
+    let client = app_state.client.clone();
+    deferred_startup::after_first_window(cx, move |cx| {
+        cx.spawn(|cx| async move { authenticate(client, &cx).await })
+            .detach_and_log_err(cx);
+    });
 
+    if let Some(startup_time) = STARTUP_TIME.get() {
+        log::info!("UI initialized {:?} after startup", startup_time.elapsed());
+    }
     Ok(())
 }
 
 fn main() {
+    STARTUP_TIME.get_or_init(Instant::now);
     menu::init();
     zed_actions::init();
 
@@ -292,6 +324,15 @@ fn main() {
 
     init_logger();
 
+    let args = Args::parse();
+    if args.system_specs || args.feedback.is_some() {
+        if let Err(error) = run_command_without_ui(&args) {
+            eprintln!("{error:?}");
+            process::exit(1);
+        }
+        return;
+    }
+
     log::info!("========== starting zed ==========");
     let app = App::new().with_assets(Assets);
 
@@ -438,7 +479,9 @@ fn main() {
 
         reliability::init(client.http_client(), installation_id, cx);
 
-        let args = Args::parse();
+        if args.disable_gpu {
+            gpui::set_software_rendering(true);
+        }
         let urls: Vec<_> = args
             .paths_or_urls
             .iter()
@@ -515,21 +558,48 @@ fn handle_open_request(request: OpenRequest, app_state: Arc<AppState>, cx: &mut
     };
 
     let mut task = None;
-    if !request.open_paths.is_empty() {
+    if !request.open_paths.is_empty() || !request.open_projects.is_empty() {
         let app_state = app_state.clone();
+        let open_paths = request.open_paths;
+        let open_projects = request.open_projects;
+        let from_link = request.from_link;
         task = Some(cx.spawn(|mut cx| async move {
-            let (_window, results) = open_paths_with_positions(
-                &request.open_paths,
-                app_state,
-                workspace::OpenOptions::default(),
-                &mut cx,
-            )
-            .await?;
-            for result in results.into_iter().flatten() {
-                if let Err(err) = result {
-                    log::error!("Error opening path: {err}",);
+            if from_link {
+                let paths = open_paths
+                    .iter()
+                    .map(|path| path.path_like.clone())
+                    .chain(open_projects.iter().cloned())
+                    .collect::<Vec<_>>();
+                if !confirm_link_paths(&paths, app_state.clone(), &mut cx).await? {
+                    return anyhow::Ok(());
+                }
+            }
+
+            if !open_paths.is_empty() {
+                let (_window, results) = open_paths_with_positions(
+                    &open_paths,
+                    app_state.clone(),
+                    workspace::OpenOptions::default(),
+                    &mut cx,
+                )
+                .await?;
+                for result in results.into_iter().flatten() {
+                    if let Err(err) = result {
+                        log::error!("Error opening path: {err}",);
+                    }
                 }
             }
+            for project in open_projects {
+                cx.update(|cx| {
+                    workspace::open_paths(
+                        &[project],
+                        app_state.clone(),
+                        workspace::OpenOptions::default(),
+                        cx,
+                    )
+                })?
+                .await?;
+            }
             anyhow::Ok(())
         }));
     }
@@ -709,6 +779,9 @@ fn init_logger() {
 
                 config_builder.set_time_format_str("%Y-%m-%dT%T%:z");
                 config_builder.set_time_to_local(true);
+                // The log viewer filters entries by the module that logged them.
+                config_builder.set_target_level(LevelFilter::Error);
+                config_builder.set_thread_level(LevelFilter::Off);
 
                 #[cfg(target_os = "linux")]
                 {
@@ -878,6 +951,50 @@ struct Args {
     /// Instructs zed to run as a dev server on this machine. (not implemented)
     #[arg(long)]
     dev_server_token: Option<String>,
+
+    /// Render without the GPU, for machines whose graphics drivers can't be used.
+    #[arg(long)]
+    disable_gpu: bool,
+
+    /// Print the system specs that are attached to bug reports, and exit.
+    #[arg(long)]
+    system_specs: bool,
+
+    /// Submit feedback along with the system specs, and exit. Doesn't require the UI to start.
+    #[arg(long, value_name = "MESSAGE")]
+    feedback: Option<String>,
+}
+
+/// Runs the commands that don't need the UI, so that issues can still be reported from the
+/// terminal when Zed doesn't start.
+fn run_command_without_ui(args: &Args) -> Result<()> {
+    let app_version = AppVersion::init(env!("CARGO_PKG_VERSION"));
+    let commit_sha = option_env!("ZED_COMMIT_SHA").map(|sha| AppCommitSha(sha.into()));
+    let system_specs = SystemSpecs::new_stateless(app_version, commit_sha, *RELEASE_CHANNEL);
+    if args.system_specs {
+        println!("{system_specs}");
+    }
+
+    if let Some(feedback) = &args.feedback {
+        let installation_id = smol::block_on(installation_id())
+            .log_err()
+            .map(|(installation_id, _)| installation_id.into());
+        // The settings store isn't loaded without the UI, so the feedback
+        // crate reads the settings file itself.
+        let user_settings = match std::fs::read_to_string(paths::settings_file()) {
+            Ok(user_settings) => user_settings,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(error).context("failed to read the settings file"),
+        };
+        smol::block_on(feedback::submit_feedback_without_ui(
+            feedback,
+            installation_id,
+            system_specs,
+            &user_settings,
+        ))?;
+        println!("Thanks for your feedback!");
+    }
+    Ok(())
 }
 
 fn parse_url_arg(arg: &str, cx: &AppContext) -> Result<String> {
@@ -953,6 +1070,51 @@ fn load_user_themes_in_background(fs: Arc<dyn fs::Fs>, cx: &mut AppContext) {
     .detach_and_log_err(cx);
 }
 
+/// Loads the TextMate grammars from the grammars directory, which highlight languages
+/// that don't have a Tree-sitter grammar.
+fn load_user_textmate_grammars_in_background(
+    fs: Arc<dyn fs::Fs>,
+    languages: Arc<LanguageRegistry>,
+    cx: &mut AppContext,
+) {
+    cx.background_executor()
+        .spawn(async move {
+            let grammars_dir = paths::textmate_grammars_dir();
+            if !fs.is_dir(grammars_dir).await {
+                return anyhow::Ok(());
+            }
+            let mut entries = fs.read_dir(grammars_dir).await?;
+            while let Some(path) = entries.next().await {
+                let path = path?;
+                let file_name = path
+                    .file_name()
+                    .and_then(|file_name| file_name.to_str())
+                    .unwrap_or_default();
+                let is_json = file_name.ends_with(".tmLanguage.json");
+                if !is_json && !file_name.ends_with(".tmLanguage") {
+                    continue;
+                }
+
+                let grammar = maybe!(async {
+                    let contents = fs.load(&path).await?;
+                    if is_json {
+                        textmate::Grammar::from_json(&contents)
+                    } else {
+                        textmate::Grammar::from_plist(contents.as_bytes())
+                    }
+                })
+                .await
+                .with_context(|| format!("loading TextMate grammar {path:?}"))
+                .log_err();
+                if let Some(grammar) = grammar {
+                    languages.register_textmate_grammar(Arc::new(TextMateGrammar::new(grammar)));
+                }
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+}
+
 /// Spawns a background task to watch the themes directory for changes.
 fn watch_themes(fs: Arc<dyn fs::Fs>, cx: &mut AppContext) {
     use std::time::Duration;
@@ -0,0 +1,150 @@
+//! Samples how Zed performs for a few seconds, for the snapshot sent with
+//! feedback about its performance.
+
+use std::time::{Duration, Instant};
+
+use feedback::{DurationPercentiles, PerformanceSnapshot};
+use gpui::{AppContext, BackgroundExecutor, FrameTiming, FrameTimings, Task, WindowContext};
+use sysinfo::{Pid, ProcessRefreshKind, System};
+
+/// How long Zed is sampled for.
+const SAMPLE_DURATION: Duration = Duration::from_secs(3);
+
+/// How often the memory used by Zed's process is sampled.
+const MEMORY_SAMPLE_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How often the main thread is checked for whether it's stalled.
+const STALL_PROBE_INTERVAL: Duration = Duration::from_millis(10);
+
+/// How long the main thread must be busy, or a frame take, to count as a stall.
+const STALL_THRESHOLD: Duration = Duration::from_millis(100);
+
+/// The number of stalls kept in a snapshot, keeping the longest.
+const MAX_STALLS: usize = 20;
+
+pub fn init(cx: &mut AppContext) {
+    feedback::set_performance_sampler(capture_performance_snapshot, cx);
+}
+
+fn capture_performance_snapshot(cx: &mut WindowContext) -> Task<PerformanceSnapshot> {
+    let started_at = Instant::now();
+    // The frames that were slow before sampling started are the recent stalls
+    // that the feedback is likely about.
+    let mut stalls = long_frames(cx.frame_timings().iter(), started_at);
+    let memory_samples = cx
+        .background_executor()
+        .spawn(sample_memory(cx.background_executor().clone()));
+
+    cx.spawn(|mut cx| async move {
+        // The probe runs on the main thread, so the more it's woken up late,
+        // the longer the main thread was busy with something else.
+        let mut last_probe = Instant::now();
+        while last_probe.duration_since(started_at) < SAMPLE_DURATION {
+            cx.background_executor().timer(STALL_PROBE_INTERVAL).await;
+            let now = Instant::now();
+            let delay = now
+                .duration_since(last_probe)
+                .saturating_sub(STALL_PROBE_INTERVAL);
+            if delay >= STALL_THRESHOLD {
+                stalls.push(delay);
+            }
+            last_probe = now;
+        }
+        let memory_samples = memory_samples.await;
+
+        cx.update(|cx| snapshot(cx.frame_timings(), started_at, stalls, memory_samples))
+            .unwrap_or_default()
+    })
+}
+
+async fn sample_memory(executor: BackgroundExecutor) -> Vec<u64> {
+    let mut system = System::new();
+    let pid = Pid::from_u32(std::process::id());
+    let started_at = Instant::now();
+    let mut samples = Vec::new();
+    while started_at.elapsed() < SAMPLE_DURATION {
+        system.refresh_process_specifics(pid, ProcessRefreshKind::new().with_memory());
+        samples.extend(system.process(pid).map(|process| process.memory()));
+        executor.timer(MEMORY_SAMPLE_INTERVAL).await;
+    }
+    samples
+}
+
+fn snapshot(
+    timings: &FrameTimings,
+    started_at: Instant,
+    mut stalls: Vec<Duration>,
+    memory_samples: Vec<u64>,
+) -> PerformanceSnapshot {
+    let sample_duration = started_at.elapsed();
+    let sampled_frames = timings
+        .iter()
+        .filter(|timing| timing.start >= started_at)
+        .count();
+    let percentiles = |duration: fn(&FrameTiming) -> Duration| {
+        let [p50_ms, p95_ms, p99_ms] = [0.5, 0.95, 0.99].map(|percentile| {
+            timings
+                .percentile(percentile, duration)
+                .map_or(0., |duration| duration.as_secs_f64() * 1000.)
+        });
+        DurationPercentiles {
+            p50_ms,
+            p95_ms,
+            p99_ms,
+        }
+    };
+
+    stalls.sort_unstable_by(|a, b| b.cmp(a));
+    stalls.truncate(MAX_STALLS);
+
+    PerformanceSnapshot {
+        sample_duration_ms: sample_duration.as_millis() as u64,
+        frames_per_second: sampled_frames as f64 / sample_duration.as_secs_f64().max(0.001),
+        frame_time: percentiles(|timing| timing.total),
+        layout_time: percentiles(|timing| timing.layout),
+        paint_time: percentiles(|timing| timing.paint),
+        main_thread_stalls_ms: stalls
+            .into_iter()
+            .map(|stall| stall.as_secs_f64() * 1000.)
+            .collect(),
+        memory_samples,
+    }
+}
+
+/// Returns how long the frames that started before `until` took, for those
+/// that took long enough to count as stalls.
+fn long_frames<'a>(
+    timings: impl Iterator<Item = &'a FrameTiming>,
+    until: Instant,
+) -> Vec<Duration> {
+    timings
+        .filter(|timing| timing.start < until && timing.total >= STALL_THRESHOLD)
+        .map(|timing| timing.total)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_long_frames() {
+        let start = Instant::now();
+        let frame = |offset_ms, total_ms| FrameTiming {
+            start: start + Duration::from_millis(offset_ms),
+            layout: Duration::ZERO,
+            paint: Duration::ZERO,
+            total: Duration::from_millis(total_ms),
+        };
+        let timings = [
+            frame(0, 16),
+            frame(20, 250),
+            frame(300, 120),
+            frame(500, 400),
+        ];
+        assert_eq!(
+            long_frames(timings.iter(), start + Duration::from_millis(400)),
+            [Duration::from_millis(250), Duration::from_millis(120)]
+        );
+    }
+}
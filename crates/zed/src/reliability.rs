@@ -24,6 +24,9 @@ use telemetry_events::Panic;
 use telemetry_events::PanicRequest;
 use util::ResultExt;
 
+use crate::crash_detection::{
+    detect_crash_dump, is_panic_unnotified, notify_of_previous_crash, PreviousCrash,
+};
 use crate::stdout_is_a_pty;
 static PANIC_COUNT: AtomicU32 = AtomicU32::new(0);
 
@@ -349,24 +352,40 @@ fn upload_panics_and_crashes(
     cx: &mut AppContext,
 ) {
     let telemetry_settings = *client::TelemetrySettings::get_global(cx);
-    cx.background_executor()
-        .spawn(async move {
-            let most_recent_panic = upload_previous_panics(http.clone(), telemetry_settings)
-                .await
-                .log_err()
-                .flatten();
-            upload_previous_crashes(http, most_recent_panic, installation_id, telemetry_settings)
-                .await
-                .log_err()
-        })
-        .detach()
+    let upload = cx.background_executor().spawn(async move {
+        let most_recent_panic = upload_previous_panics(http.clone(), telemetry_settings)
+            .await
+            .log_err()
+            .flatten();
+        upload_previous_crashes(
+            http,
+            most_recent_panic
+                .as_ref()
+                .map(|panic| (panic.panicked_on, panic.payload.clone())),
+            installation_id,
+            telemetry_settings,
+        )
+        .await
+        .log_err();
+        match most_recent_panic {
+            Some(panic) => is_panic_unnotified(&panic).then(|| PreviousCrash::from_panic(&panic)),
+            None => detect_crash_dump().await,
+        }
+    });
+    cx.spawn(|mut cx| async move {
+        if let Some(crash) = upload.await {
+            cx.update(|cx| notify_of_previous_crash(crash, cx))
+                .log_err();
+        }
+    })
+    .detach()
 }
 
-/// Uploads panics via `zed.dev`.
+/// Uploads panics via `zed.dev`, when diagnostics are enabled, and returns the most recent one.
 async fn upload_previous_panics(
     http: Arc<HttpClientWithUrl>,
     telemetry_settings: client::TelemetrySettings,
-) -> Result<Option<(i64, String)>> {
+) -> Result<Option<Panic>> {
     let panic_report_url = http.build_zed_api_url("/telemetry/panics", &[])?;
     let mut children = smol::fs::read_dir(paths::logs_dir()).await?;
 
@@ -389,27 +408,34 @@ async fn upload_previous_panics(
             continue;
         }
 
-        if telemetry_settings.diagnostics {
-            let panic_file_content = smol::fs::read_to_string(&child_path)
-                .await
-                .context("error reading panic file")?;
-
-            let panic: Option<Panic> = serde_json::from_str(&panic_file_content)
-                .ok()
-                .or_else(|| {
-                    panic_file_content
-                        .lines()
-                        .next()
-                        .and_then(|line| serde_json::from_str(line).ok())
+        let panic_file_content = smol::fs::read_to_string(&child_path)
+            .await
+            .context("error reading panic file")?;
+
+        let panic: Option<Panic> = serde_json::from_str(&panic_file_content)
+            .ok()
+            .or_else(|| {
+                panic_file_content
+                    .lines()
+                    .next()
+                    .and_then(|line| serde_json::from_str(line).ok())
+            })
+            .unwrap_or_else(|| {
+                log::error!("failed to deserialize panic file {:?}", panic_file_content);
+                None
+            });
+
+        if let Some(panic) = panic {
+            if most_recent_panic
+                .as_ref()
+                .map_or(true, |most_recent: &Panic| {
+                    panic.panicked_on > most_recent.panicked_on
                 })
-                .unwrap_or_else(|| {
-                    log::error!("failed to deserialize panic file {:?}", panic_file_content);
-                    None
-                });
-
-            if let Some(panic) = panic {
-                most_recent_panic = Some((panic.panicked_on, panic.payload.clone()));
+            {
+                most_recent_panic = Some(panic.clone());
+            }
 
+            if telemetry_settings.diagnostics {
                 let json_bytes = serde_json::to_vec(&PanicRequest { panic }).unwrap();
 
                 let Some(checksum) = client::telemetry::calculate_json_checksum(&json_bytes) else {
@@ -425,9 +451,17 @@ async fn upload_previous_panics(
                     continue;
                 };
 
-                let response = http.send(request).await.context("error sending panic")?;
-                if !response.status().is_success() {
-                    log::error!("Error uploading panic to server: {}", response.status());
+                match http.send(request).await {
+                    Ok(response) => {
+                        if !response.status().is_success() {
+                            log::error!("Error uploading panic to server: {}", response.status());
+                        }
+                    }
+                    Err(error) => {
+                        // Keep the file, to try again on the next launch.
+                        log::error!("error sending panic: {error:?}");
+                        continue;
+                    }
                 }
             }
         }
@@ -1,4 +1,5 @@
 mod app_menus;
+pub mod deferred_startup;
 pub mod inline_completion_registry;
 #[cfg(target_os = "linux")]
 pub(crate) mod linux_prompts;
@@ -9,17 +10,20 @@ mod open_listener;
 pub use app_menus::*;
 use breadcrumbs::Breadcrumbs;
 use client::ZED_URL_SCHEME;
-use collections::VecDeque;
-use editor::{scroll::Autoscroll, Editor, MultiBuffer};
+use editor::{Editor, MultiBuffer};
 use gpui::{
-    actions, point, px, AppContext, AsyncAppContext, Context, FocusableView, MenuItem, PromptLevel,
-    ReadGlobal, TitlebarOptions, View, ViewContext, VisualContext, WindowKind, WindowOptions,
+    actions, point, px, AppContext, AsyncAppContext, Context, FocusableView, MenuItem,
+    PathPromptOptions, PromptLevel, ReadGlobal, TitlebarOptions, View, ViewContext, VisualContext,
+    WindowKind, WindowOptions,
 };
 pub use open_listener::*;
 
 use anyhow::Context as _;
 use assets::Assets;
-use futures::{channel::mpsc, select_biased, StreamExt};
+use futures::{
+    channel::{mpsc, oneshot},
+    select_biased, StreamExt,
+};
 use outline_panel::OutlinePanel;
 use project::TaskSourceKind;
 use project_panel::ProjectPanel;
@@ -37,14 +41,16 @@ use theme::ActiveTheme;
 use workspace::notifications::NotificationId;
 
 use paths::{local_settings_file_relative_path, local_tasks_file_relative_path};
+use snippet_provider::SnippetProvider;
 use terminal_view::terminal_panel::{self, TerminalPanel};
-use util::{asset_str, ResultExt};
+use todo_panel::TodoPanel;
+use util::{asset_str, paths::PathExt, ResultExt};
 use uuid::Uuid;
 use vim::VimModeSetting;
 use welcome::BaseKeymap;
 use workspace::{
     create_and_open_local_file, notifications::simple_message_notification::MessageNotification,
-    open_new, AppState, NewFile, NewWindow, OpenLog, Toast, Workspace, WorkspaceSettings,
+    open_new, AppState, NewFile, NewWindow, Toast, Workspace, WorkspaceSettings,
 };
 use workspace::{notifications::DetachAndPromptErr, Pane};
 use zed_actions::{OpenBrowser, OpenSettings, OpenZedUrl, Quit};
@@ -55,6 +61,7 @@ actions!(
         DebugElements,
         Hide,
         HideOthers,
+        ImportSnippets,
         Minimize,
         OpenDefaultKeymap,
         OpenDefaultSettings,
@@ -141,9 +148,13 @@ pub fn initialize_workspace(app_state: Arc<AppState>, cx: &mut AppContext) {
         let vim_mode_indicator = cx.new_view(|cx| vim::ModeIndicator::new(cx));
         let cursor_position =
             cx.new_view(|_| go_to_line::cursor_position::CursorPosition::new(workspace));
+        let pending_feedback_indicator = cx.new_view(feedback::PendingFeedbackIndicator::new);
+        let extension_status_bar = cx.new_view(extensions_ui::ExtensionStatusBar::new);
         workspace.status_bar().update(cx, |status_bar, cx| {
             status_bar.add_left_item(diagnostic_summary, cx);
             status_bar.add_left_item(activity_indicator, cx);
+            status_bar.add_left_item(pending_feedback_indicator, cx);
+            status_bar.add_right_item(extension_status_bar, cx);
             status_bar.add_right_item(inline_completion_button, cx);
             status_bar.add_right_item(active_buffer_language, cx);
             status_bar.add_right_item(vim_mode_indicator, cx);
@@ -184,11 +195,26 @@ pub fn initialize_workspace(app_state: Arc<AppState>, cx: &mut AppContext) {
             });
         }
 
+        // The project panel lists the entries of the worktrees as they're scanned, which competes
+        // with drawing the first window for the main thread at startup.
+        let (first_window_tx, first_window_rx) = oneshot::channel();
+        deferred_startup::after_first_window(cx, move |_| {
+            first_window_tx.send(()).ok();
+        });
+
         cx.spawn(|workspace_handle, mut cx| async move {
             let assistant_panel =
                 assistant::AssistantPanel::load(workspace_handle.clone(), cx.clone());
-            let project_panel = ProjectPanel::load(workspace_handle.clone(), cx.clone());
+            let project_panel = {
+                let workspace_handle = workspace_handle.clone();
+                let cx = cx.clone();
+                async move {
+                    first_window_rx.await.ok();
+                    ProjectPanel::load(workspace_handle, cx).await
+                }
+            };
             let outline_panel = OutlinePanel::load(workspace_handle.clone(), cx.clone());
+            let todo_panel = TodoPanel::load(workspace_handle.clone(), cx.clone());
             let terminal_panel = TerminalPanel::load(workspace_handle.clone(), cx.clone());
             let channels_panel =
                 collab_ui::collab_panel::CollabPanel::load(workspace_handle.clone(), cx.clone());
@@ -202,6 +228,7 @@ pub fn initialize_workspace(app_state: Arc<AppState>, cx: &mut AppContext) {
             let (
                 project_panel,
                 outline_panel,
+                todo_panel,
                 terminal_panel,
                 assistant_panel,
                 channels_panel,
@@ -210,6 +237,7 @@ pub fn initialize_workspace(app_state: Arc<AppState>, cx: &mut AppContext) {
             ) = futures::try_join!(
                 project_panel,
                 outline_panel,
+                todo_panel,
                 terminal_panel,
                 assistant_panel,
                 channels_panel,
@@ -221,6 +249,7 @@ pub fn initialize_workspace(app_state: Arc<AppState>, cx: &mut AppContext) {
                 workspace.add_panel(assistant_panel, cx);
                 workspace.add_panel(project_panel, cx);
                 workspace.add_panel(outline_panel, cx);
+                workspace.add_panel(todo_panel, cx);
                 workspace.add_panel(terminal_panel, cx);
                 workspace.add_panel(channels_panel, cx);
                 workspace.add_panel(chat_panel, cx);
@@ -263,6 +292,15 @@ pub fn initialize_workspace(app_state: Arc<AppState>, cx: &mut AppContext) {
             .register_action(move |_, _: &zed_actions::ResetUiFontSize, cx| {
                 theme::reset_ui_font_size(cx)
             })
+            .register_action(move |_, _: &zed_actions::IncreaseUiScale, cx| {
+                theme::adjust_ui_scale(cx, |scale| *scale += 0.1)
+            })
+            .register_action(move |_, _: &zed_actions::DecreaseUiScale, cx| {
+                theme::adjust_ui_scale(cx, |scale| *scale -= 0.1)
+            })
+            .register_action(move |_, _: &zed_actions::ResetUiScale, cx| {
+                theme::reset_ui_scale(cx)
+            })
             .register_action(move |_, _: &zed_actions::IncreaseBufferFontSize, cx| {
                 theme::adjust_buffer_font_size(cx, |size| *size += px(1.0))
             })
@@ -333,9 +371,6 @@ pub fn initialize_workspace(app_state: Arc<AppState>, cx: &mut AppContext) {
                     |_, _| None,
                 );
             })
-            .register_action(|workspace, _: &OpenLog, cx| {
-                open_log_file(workspace, cx);
-            })
             .register_action(|workspace, _: &zed_actions::OpenLicenses, cx| {
                 open_bundled_file(
                     workspace,
@@ -379,6 +414,7 @@ pub fn initialize_workspace(app_state: Arc<AppState>, cx: &mut AppContext) {
             )
             .register_action(open_local_settings_file)
             .register_action(open_local_tasks_file)
+            .register_action(import_snippets)
             .register_action(
                 move |workspace: &mut Workspace,
                       _: &OpenDefaultKeymap,
@@ -493,9 +529,13 @@ fn initialize_pane(workspace: &mut Workspace, pane: &View<Pane>, cx: &mut ViewCo
             toolbar.add_item(project_search_bar, cx);
             let lsp_log_item = cx.new_view(|_| language_tools::LspLogToolbarItemView::new());
             toolbar.add_item(lsp_log_item, cx);
+            let log_view_item = cx.new_view(|_| log_viewer::LogViewToolbarItemView::new());
+            toolbar.add_item(log_view_item, cx);
             let syntax_tree_item =
                 cx.new_view(|_| language_tools::SyntaxTreeToolbarItemView::new());
             toolbar.add_item(syntax_tree_item, cx);
+            let feedback_toolbar = cx.new_view(|_| feedback::FeedbackToolbar::new());
+            toolbar.add_item(feedback_toolbar, cx);
         })
     });
 }
@@ -574,93 +614,6 @@ fn quit(_: &Quit, cx: &mut AppContext) {
     .detach_and_log_err(cx);
 }
 
-fn open_log_file(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
-    const MAX_LINES: usize = 1000;
-    workspace
-        .with_local_workspace(cx, move |workspace, cx| {
-            let fs = workspace.app_state().fs.clone();
-            cx.spawn(|workspace, mut cx| async move {
-                let (old_log, new_log) =
-                    futures::join!(fs.load(paths::old_log_file()), fs.load(paths::log_file()));
-                let log = match (old_log, new_log) {
-                    (Err(_), Err(_)) => None,
-                    (old_log, new_log) => {
-                        let mut lines = VecDeque::with_capacity(MAX_LINES);
-                        for line in old_log
-                            .iter()
-                            .flat_map(|log| log.lines())
-                            .chain(new_log.iter().flat_map(|log| log.lines()))
-                        {
-                            if lines.len() == MAX_LINES {
-                                lines.pop_front();
-                            }
-                            lines.push_back(line);
-                        }
-                        Some(
-                            lines
-                                .into_iter()
-                                .flat_map(|line| [line, "\n"])
-                                .collect::<String>(),
-                        )
-                    }
-                };
-
-                workspace
-                    .update(&mut cx, |workspace, cx| {
-                        let Some(log) = log else {
-                            struct OpenLogError;
-
-                            workspace.show_notification(
-                                NotificationId::unique::<OpenLogError>(),
-                                cx,
-                                |cx| {
-                                    cx.new_view(|_| {
-                                        MessageNotification::new(format!(
-                                            "Unable to access/open log file at path {:?}",
-                                            paths::log_file().as_path()
-                                        ))
-                                    })
-                                },
-                            );
-                            return;
-                        };
-                        let project = workspace.project().clone();
-                        let buffer = project.update(cx, |project, cx| {
-                            project.create_local_buffer(&log, None, cx)
-                        });
-
-                        let buffer = cx.new_model(|cx| {
-                            MultiBuffer::singleton(buffer, cx).with_title("Log".into())
-                        });
-                        let editor = cx.new_view(|cx| {
-                            let mut editor =
-                                Editor::for_multibuffer(buffer, Some(project), true, cx);
-                            editor.set_breadcrumb_header(format!(
-                                "Last {} lines in {}",
-                                MAX_LINES,
-                                paths::log_file().display()
-                            ));
-                            editor
-                        });
-
-                        editor.update(cx, |editor, cx| {
-                            let last_multi_buffer_offset = editor.buffer().read(cx).len(cx);
-                            editor.change_selections(Some(Autoscroll::fit()), cx, |s| {
-                                s.select_ranges(Some(
-                                    last_multi_buffer_offset..last_multi_buffer_offset,
-                                ));
-                            })
-                        });
-
-                        workspace.add_item_to_active_pane(Box::new(editor), None, cx);
-                    })
-                    .log_err();
-            })
-            .detach();
-        })
-        .detach();
-}
-
 pub fn handle_keymap_file_changes(
     mut user_keymap_file_rx: mpsc::UnboundedReceiver<String>,
     cx: &mut AppContext,
@@ -710,7 +663,7 @@ fn reload_keymaps(cx: &mut AppContext, keymap_content: &KeymapFile) {
     cx.clear_key_bindings();
     load_default_keymap(cx);
     keymap_content.clone().add_to_cx(cx).log_err();
-    cx.set_menus(app_menus());
+    set_app_menus(cx);
     cx.set_dock_menu(vec![MenuItem::action("New Window", workspace::NewWindow)])
 }
 
@@ -756,6 +709,40 @@ fn open_local_tasks_file(
     )
 }
 
+fn import_snippets(_: &mut Workspace, _: &ImportSnippets, cx: &mut ViewContext<Workspace>) {
+    let snippet_paths = cx.prompt_for_paths(PathPromptOptions {
+        files: true,
+        directories: true,
+        multiple: true,
+    });
+    cx.spawn(|workspace, mut cx| async move {
+        let Some(snippet_paths) = snippet_paths.await? else {
+            return Ok(());
+        };
+        let imported_snippets = cx
+            .update(|cx| {
+                SnippetProvider::global(cx)
+                    .update(cx, |provider, cx| provider.import(snippet_paths, cx))
+            })?
+            .await?;
+        workspace.update(&mut cx, |workspace, cx| {
+            struct ImportedSnippets;
+
+            workspace.show_toast(
+                Toast::new(
+                    NotificationId::unique::<ImportedSnippets>(),
+                    format!(
+                        "Imported {imported_snippets} snippets to {}.",
+                        paths::snippets_dir().compact().to_string_lossy()
+                    ),
+                ),
+                cx,
+            )
+        })
+    })
+    .detach_and_prompt_err("Failed to import snippets", cx, |_, _| None);
+}
+
 fn open_local_file(
     workspace: &mut Workspace,
     settings_relative_path: &'static Path,
@@ -834,7 +821,7 @@ fn open_telemetry_log_file(workspace: &mut Workspace, cx: &mut ViewContext<Works
                 app_state.fs.load(&path).await.log_err()
             }
 
-            let log = fetch_log_string(&app_state).await.unwrap_or_else(|| "// No data has been collected yet".to_string());
+            let log = fetch_log_string(&app_state).await.filter(|log| !log.is_empty()).unwrap_or_else(|| "// No data has been sent yet\n".to_string());
 
             const MAX_TELEMETRY_LOG_LEN: usize = 5 * 1024 * 1024;
             let mut start_offset = log.len().saturating_sub(MAX_TELEMETRY_LOG_LEN);
@@ -845,24 +832,35 @@ fn open_telemetry_log_file(workspace: &mut Workspace, cx: &mut ViewContext<Works
             let json = app_state.languages.language_for_name("JSON").await.log_err();
 
             workspace.update(&mut cx, |workspace, cx| {
+                let telemetry_settings = *client::TelemetrySettings::get_global(cx);
+                let enabled = |enabled| if enabled { "enabled" } else { "disabled" };
+                let mut queued_events = app_state.client.telemetry().queued_events().join("\n");
+                if queued_events.is_empty() {
+                    queued_events = "// No data is waiting to be sent".to_string();
+                }
+                let header = format!(
+                    concat!(
+                        "// Zed collects anonymous usage data to help us understand how people are using the app.\n",
+                        "// Telemetry can be disabled via the `settings.json` file:\n",
+                        "//   - `telemetry.diagnostics` ({}) sends crash reports and panics.\n",
+                        "//   - `telemetry.metrics` ({}) sends the usage events below.\n",
+                        "\n",
+                        "// Here is the data that will be reported with the next batch:\n",
+                        "{}\n",
+                        "\n",
+                        "// Here is the data that has been reported for the current session:\n",
+                        "\n",
+                    ),
+                    enabled(telemetry_settings.diagnostics),
+                    enabled(telemetry_settings.metrics),
+                    queued_events,
+                );
                 let project = workspace.project().clone();
                 let buffer = project
                     .update(cx, |project, cx| project.create_local_buffer("", None, cx));
                 buffer.update(cx, |buffer, cx| {
                     buffer.set_language(json, cx);
-                    buffer.edit(
-                        [(
-                            0..0,
-                            concat!(
-                                "// Zed collects anonymous usage data to help us understand how people are using the app.\n",
-                                "// Telemetry can be disabled via the `settings.json` file.\n",
-                                "// Here is the data that has been reported for the current session:\n",
-                                "\n"
-                            ),
-                        )],
-                        None,
-                        cx,
-                    );
+                    buffer.edit([(0..0, header)], None, cx);
                     buffer.edit([(buffer.len()..buffer.len(), log_suffix)], None, cx);
                 });
 
@@ -958,9 +956,77 @@ mod tests {
     use workspace::{
         item::{Item, ItemHandle},
         open_new, open_paths, pane, NewFile, OpenVisible, SaveIntent, SplitDirection,
-        WorkspaceHandle,
+        StatusBarSettings, StatusItemSettings, WorkspaceHandle,
     };
 
+    #[gpui::test]
+    async fn test_hide_built_in_status_bar_item(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        app_state
+            .fs
+            .as_fake()
+            .insert_tree("/root", json!({"a": "hey"}))
+            .await;
+        cx.update(|cx| {
+            open_paths(
+                &[PathBuf::from("/root/a")],
+                app_state.clone(),
+                workspace::OpenOptions::default(),
+                cx,
+            )
+        })
+        .await
+        .unwrap();
+        let window = cx.update(|cx| cx.windows()[0].downcast::<Workspace>().unwrap());
+        let visible_item_ids = |cx: &mut TestAppContext| {
+            window
+                .update(cx, |workspace, cx| {
+                    workspace
+                        .status_bar()
+                        .read(cx)
+                        .visible_item_ids(cx)
+                        .concat()
+                })
+                .unwrap()
+        };
+
+        let built_in_item_ids = [
+            "diagnostics",
+            "activity",
+            "inline_completion",
+            "language",
+            "vim_mode",
+            "cursor_position",
+        ];
+        let item_ids = visible_item_ids(cx);
+        for id in built_in_item_ids {
+            assert!(item_ids.contains(&Some(id)), "{id} isn't shown");
+        }
+
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            store.update_user_settings::<StatusBarSettings>(cx, |settings| {
+                settings.items = Some(
+                    [(
+                        "cursor_position".to_string(),
+                        StatusItemSettings {
+                            visible: Some(false),
+                            ..Default::default()
+                        },
+                    )]
+                    .into_iter()
+                    .collect(),
+                );
+            });
+        });
+        let item_ids = visible_item_ids(cx);
+        assert!(!item_ids.contains(&Some("cursor_position")));
+        for id in built_in_item_ids {
+            if id != "cursor_position" {
+                assert!(item_ids.contains(&Some(id)), "{id} isn't shown");
+            }
+        }
+    }
+
     #[gpui::test]
     async fn test_open_non_existing_file(cx: &mut TestAppContext) {
         let app_state = init_test(cx);
@@ -3176,6 +3242,7 @@ mod tests {
             collab_ui::init(&app_state, cx);
             project_panel::init((), cx);
             outline_panel::init((), cx);
+            todo_panel::init(cx);
             terminal_view::init(cx);
             assistant::init(app_state.client.clone(), cx);
             tasks_ui::init(cx);
@@ -1,8 +1,16 @@
 use collab_ui::collab_panel;
-use gpui::{Menu, MenuItem, OsAction};
+use gpui::{AppContext, Menu, MenuItem, OsAction};
 use terminal_view::terminal_panel;
 
-pub fn app_menus() -> Vec<Menu<'static>> {
+/// Sets the application menus, with their localized labels in the current
+/// locale.
+pub fn set_app_menus(cx: &mut AppContext) {
+    let give_feedback = i18n::t("feedback.give_feedback_menu_item", cx);
+    let feedback_history = i18n::t("feedback.history.title", cx);
+    cx.set_menus(app_menus(&give_feedback, &feedback_history));
+}
+
+pub fn app_menus<'a>(give_feedback: &'a str, feedback_history: &'a str) -> Vec<Menu<'a>> {
     use zed_actions::Quit;
 
     vec![
@@ -166,7 +174,8 @@ pub fn app_menus() -> Vec<Menu<'static>> {
                 MenuItem::action("View Telemetry", zed_actions::OpenTelemetryLog),
                 MenuItem::action("View Dependency Licenses", zed_actions::OpenLicenses),
                 MenuItem::action("Show Welcome", workspace::Welcome),
-                MenuItem::action("Give Feedback...", feedback::GiveFeedback),
+                MenuItem::action(give_feedback, feedback::GiveFeedback::default()),
+                MenuItem::action(feedback_history, feedback::OpenFeedbackHistory),
                 MenuItem::separator(),
                 MenuItem::action(
                     "Documentation",
@@ -0,0 +1,71 @@
+//! Work that's deferred at startup until the first window has been drawn, so that it doesn't
+//! compete with drawing it for the main thread.
+
+use std::time::{Duration, Instant};
+
+use gpui::{AppContext, Global};
+use project::Project;
+use workspace::Workspace;
+
+/// How long to wait for a window to be drawn before starting the deferred work anyway, like when
+/// the first window is closed before it's drawn.
+const DEFERRED_STARTUP_TIMEOUT: Duration = Duration::from_secs(5);
+
+struct DeferredStartup {
+    startup_time: Instant,
+    callbacks: Vec<Box<dyn FnOnce(&mut AppContext)>>,
+}
+
+impl Global for DeferredStartup {}
+
+/// Defers the work passed to [`after_first_window`], and starting language servers, until a
+/// window has been drawn. The startup time is when Zed started, for logging how long that took.
+pub fn init(startup_time: Instant, cx: &mut AppContext) {
+    cx.set_global(DeferredStartup {
+        startup_time,
+        callbacks: Vec::new(),
+    });
+    Project::defer_language_servers(cx);
+    after_first_window(cx, Project::start_deferred_language_servers);
+
+    // Each new window is observed, as the first one may close before it's drawn.
+    cx.observe_new_views(|_: &mut Workspace, cx| {
+        cx.on_next_frame(|_, cx| finish(true, cx));
+    })
+    .detach();
+    cx.spawn(|cx| async move {
+        cx.background_executor()
+            .timer(DEFERRED_STARTUP_TIMEOUT)
+            .await;
+        cx.update(|cx| finish(false, cx)).ok();
+    })
+    .detach();
+}
+
+/// Calls the callback once the first window has been drawn, or right away if startup isn't
+/// deferred.
+pub fn after_first_window(cx: &mut AppContext, callback: impl FnOnce(&mut AppContext) + 'static) {
+    if cx.has_global::<DeferredStartup>() {
+        cx.global_mut::<DeferredStartup>()
+            .callbacks
+            .push(Box::new(callback));
+    } else {
+        callback(cx);
+    }
+}
+
+fn finish(window_drawn: bool, cx: &mut AppContext) {
+    if !cx.has_global::<DeferredStartup>() {
+        return;
+    }
+    let deferred = cx.remove_global::<DeferredStartup>();
+    let elapsed = deferred.startup_time.elapsed();
+    if window_drawn {
+        log::info!("first window drawn {elapsed:?} after startup");
+    } else {
+        log::info!("no window drawn {elapsed:?} after startup, starting deferred work anyway");
+    }
+    for callback in deferred.callbacks {
+        callback(cx);
+    }
+}
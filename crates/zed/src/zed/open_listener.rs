@@ -4,15 +4,19 @@ use cli::{ipc::IpcSender, CliRequest, CliResponse};
 use client::parse_zed_link;
 use collections::HashMap;
 use db::kvp::KEY_VALUE_STORE;
+use editor::actions::ExpandAllHunkDiffs;
 use editor::scroll::Autoscroll;
 use editor::Editor;
 use futures::channel::mpsc::{UnboundedReceiver, UnboundedSender};
 use futures::channel::{mpsc, oneshot};
 use futures::{FutureExt, SinkExt, StreamExt};
-use gpui::{AppContext, AsyncAppContext, Global, WindowHandle};
-use language::{Bias, Point};
-use std::path::Path;
+use gpui::{
+    AppContext, AsyncAppContext, Global, PromptLevel, View, ViewContext, VisualContext,
+    WindowHandle,
+};
+use language::{Bias, Language, Point};
 use std::path::PathBuf;
+use std::path::{Component, Path};
 use std::sync::Arc;
 use std::time::Duration;
 use std::{process, thread};
@@ -28,6 +32,10 @@ use crate::{init_headless, init_ui};
 pub struct OpenRequest {
     pub cli_connection: Option<(mpsc::Receiver<CliRequest>, IpcSender<CliResponse>)>,
     pub open_paths: Vec<PathLikeWithPosition<PathBuf>>,
+    pub open_projects: Vec<PathBuf>,
+    /// Whether the paths come from a `zed://` link, which any application can open,
+    /// and are thus confirmed by the user before being opened.
+    pub from_link: bool,
     pub open_channel_notes: Vec<(u64, Option<String>)>,
     pub join_channel: Option<u64>,
 }
@@ -41,7 +49,11 @@ impl OpenRequest {
             } else if let Some(file) = url.strip_prefix("file://") {
                 this.parse_file_path(file)
             } else if let Some(file) = url.strip_prefix("zed://file") {
+                this.from_link = true;
                 this.parse_file_path(file)
+            } else if let Some(project) = url.strip_prefix("zed://project") {
+                this.from_link = true;
+                this.parse_project_path(project)
             } else if let Some(request_path) = parse_zed_link(&url, cx) {
                 this.parse_request_path(request_path).log_err();
             } else {
@@ -62,6 +74,12 @@ impl OpenRequest {
         }
     }
 
+    fn parse_project_path(&mut self, project: &str) {
+        if let Some(decoded) = urlencoding::decode(project).log_err() {
+            self.open_projects.push(PathBuf::from(decoded.as_ref()));
+        }
+    }
+
     fn parse_request_path(&mut self, request_path: &str) -> Result<()> {
         let mut parts = request_path.split('/');
         if parts.next() == Some("channel") {
@@ -215,6 +233,55 @@ pub async fn open_paths_with_positions(
     Ok((workspace, items))
 }
 
+/// Asks the user whether to open the paths of a `zed://` link, unless they all
+/// belong to projects that are already open.
+pub async fn confirm_link_paths(
+    paths: &[PathBuf],
+    app_state: Arc<AppState>,
+    cx: &mut AsyncAppContext,
+) -> Result<bool> {
+    let trusted = cx.update(|cx| paths.iter().all(|path| is_in_open_project(path, cx)))?;
+    if trusted {
+        return Ok(true);
+    }
+
+    let window = workspace::get_any_active_workspace(app_state, cx.clone()).await?;
+    let detail = paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let answer = window.update(cx, |_, cx| {
+        cx.prompt(
+            PromptLevel::Warning,
+            "Another application wants to open these paths in Zed",
+            Some(&detail),
+            &["Open", "Cancel"],
+        )
+    })?;
+    Ok(answer.await? == 0)
+}
+
+fn is_in_open_project(path: &Path, cx: &AppContext) -> bool {
+    // `..` would let a link escape the project it appears to be in.
+    if path
+        .components()
+        .any(|component| component == Component::ParentDir)
+    {
+        return false;
+    }
+    cx.windows()
+        .into_iter()
+        .filter_map(|window| window.downcast::<Workspace>())
+        .any(|window| {
+            window.read(cx).map_or(false, |workspace| {
+                workspace
+                    .visible_worktrees(cx)
+                    .any(|worktree| path.starts_with(worktree.read(cx).abs_path()))
+            })
+        })
+}
+
 pub async fn handle_cli_connection(
     (mut requests, responses): (mpsc::Receiver<CliRequest>, IpcSender<CliResponse>),
     app_state: Arc<AppState>,
@@ -223,10 +290,13 @@ pub async fn handle_cli_connection(
     if let Some(request) = requests.next().await {
         match request {
             CliRequest::Open {
-                paths,
+                mut paths,
                 wait,
                 open_new_workspace,
                 dev_server_token,
+                stdin_contents,
+                language,
+                diff,
             } => {
                 if let Some(dev_server_token) = dev_server_token {
                     match cx
@@ -270,8 +340,22 @@ pub async fn handle_cli_connection(
                     return;
                 }
 
+                if let Some((_, new_path)) = &diff {
+                    paths.push(new_path.clone());
+                }
+
+                let stdin_language = match &language {
+                    Some(language) => app_state
+                        .languages
+                        .language_for_name_or_extension(language)
+                        .await
+                        .log_err(),
+                    None => None,
+                };
+                let stdin_contents = stdin_contents.map(|contents| (contents, stdin_language));
+
                 let paths = if paths.is_empty() {
-                    if open_new_workspace == Some(true) {
+                    if open_new_workspace == Some(true) || stdin_contents.is_some() {
                         vec![]
                     } else {
                         workspace::last_opened_workspace_paths()
@@ -311,7 +395,7 @@ pub async fn handle_cli_connection(
                 if !paths.is_empty() {
                     match open_paths_with_positions(
                         &paths,
-                        app_state,
+                        app_state.clone(),
                         workspace::OpenOptions {
                             open_new_workspace,
                             ..Default::default()
@@ -321,11 +405,61 @@ pub async fn handle_cli_connection(
                     .await
                     {
                         Ok((workspace, items)) => {
+                            if let Some((contents, language)) = stdin_contents {
+                                workspace
+                                    .update(&mut cx, |workspace, cx| {
+                                        open_text_in_new_buffer(workspace, &contents, language, cx)
+                                    })
+                                    .log_err();
+                            }
+
+                            if let Some((old_path, new_path)) = &diff {
+                                let new_path = Path::new(new_path);
+                                let editor = items
+                                    .iter()
+                                    .zip(&paths)
+                                    .find(|(_, path)| path.path_like == new_path)
+                                    .and_then(|(item, _)| item.as_ref()?.as_ref().ok())
+                                    .and_then(|item| {
+                                        cx.update(|cx| item.act_as::<Editor>(cx)).ok().flatten()
+                                    });
+                                let result = match editor {
+                                    Some(editor) => {
+                                        show_diff(
+                                            workspace,
+                                            editor,
+                                            Path::new(old_path),
+                                            &app_state,
+                                            &mut cx,
+                                        )
+                                        .await
+                                    }
+                                    None => {
+                                        Err(anyhow!("{new_path:?} was not opened in an editor"))
+                                    }
+                                };
+                                if let Err(error) = result {
+                                    responses
+                                        .send(CliResponse::Stderr {
+                                            message: format!("error showing diff: {error}"),
+                                        })
+                                        .log_err();
+                                    errored = true;
+                                }
+                            }
+
                             let mut item_release_futures = Vec::new();
 
                             for (item, path) in items.into_iter().zip(&paths) {
                                 match item {
                                     Some(Ok(item)) => {
+                                        if wait {
+                                            workspace
+                                                .update(&mut cx, |workspace, _| {
+                                                    workspace.keep_item_loaded(item.item_id())
+                                                })
+                                                .log_err();
+                                        }
                                         cx.update(|cx| {
                                             let released = oneshot::channel();
                                             item.on_release(
@@ -357,7 +491,8 @@ pub async fn handle_cli_connection(
                             if wait {
                                 let background = cx.background_executor().clone();
                                 let wait = async move {
-                                    if paths.is_empty() {
+                                    // Directories don't open items, so wait for their workspace to be closed.
+                                    if item_release_futures.is_empty() {
                                         let (done_tx, done_rx) = oneshot::channel();
                                         let _subscription = workspace.update(&mut cx, |_, cx| {
                                             cx.on_release(move |_, _, _| {
@@ -397,6 +532,33 @@ pub async fn handle_cli_connection(
                                 .log_err();
                         }
                     }
+                } else if let Some((contents, language)) = stdin_contents {
+                    let existing_workspace = if open_new_workspace == Some(true) {
+                        None
+                    } else {
+                        cx.update(|cx| {
+                            workspace::activate_workspace_for_project(cx, |project, _| {
+                                project.is_local()
+                            })
+                        })
+                        .ok()
+                        .flatten()
+                    };
+                    if let Some(workspace) = existing_workspace {
+                        workspace
+                            .update(&mut cx, |workspace, cx| {
+                                open_text_in_new_buffer(workspace, &contents, language, cx)
+                            })
+                            .log_err();
+                    } else {
+                        cx.update(|cx| {
+                            workspace::open_new(app_state, cx, move |workspace, cx| {
+                                open_text_in_new_buffer(workspace, &contents, language, cx)
+                            })
+                            .detach_and_log_err(cx);
+                        })
+                        .log_err();
+                    }
                 } else if matches!(KEY_VALUE_STORE.read_kvp(FIRST_OPEN), Ok(None)) {
                     cx.update(|cx| show_welcome_view(app_state, cx)).log_err();
                 } else {
@@ -418,3 +580,67 @@ pub async fn handle_cli_connection(
         }
     }
 }
+
+/// Opens the given text in a new, unsaved buffer, like text piped into `zed -`.
+fn open_text_in_new_buffer(
+    workspace: &mut Workspace,
+    text: &str,
+    language: Option<Arc<Language>>,
+    cx: &mut ViewContext<Workspace>,
+) {
+    let project = workspace.project().clone();
+    if project.read(cx).is_remote() {
+        log::error!("cannot open text in a new buffer in a remote project");
+        return;
+    }
+    let buffer = project.update(cx, |project, cx| {
+        project.create_local_buffer(text, language, cx)
+    });
+    let editor = cx.new_view(|cx| Editor::for_buffer(buffer, Some(project), cx));
+    workspace.add_item_to_active_pane(Box::new(editor), None, cx);
+}
+
+/// Shows the changes from the old file to the editor's contents, by using the old file as the
+/// editor's diff base and expanding all of its hunks.
+async fn show_diff(
+    workspace: WindowHandle<Workspace>,
+    editor: View<Editor>,
+    old_path: &Path,
+    app_state: &AppState,
+    cx: &mut AsyncAppContext,
+) -> Result<()> {
+    let old_text = app_state
+        .fs
+        .load(old_path)
+        .await
+        .with_context(|| format!("loading {old_path:?}"))?;
+
+    let project = workspace.update(cx, |workspace, _| workspace.project().clone())?;
+    let (diff_updated_tx, diff_updated_rx) = oneshot::channel();
+    let mut diff_updated_tx = Some(diff_updated_tx);
+    let _subscription = editor.update(cx, |editor, cx| {
+        let buffer = editor
+            .buffer()
+            .read(cx)
+            .as_singleton()
+            .context("editor doesn't show a single file")?;
+        let subscription = cx.subscribe(&buffer, move |_, _, event, _| {
+            if let language::Event::DiffBaseChanged = event {
+                if let Some(diff_updated_tx) = diff_updated_tx.take() {
+                    diff_updated_tx.send(()).ok();
+                }
+            }
+        });
+        // NEW may be in a git repository, whose diff base would replace OLD's contents otherwise.
+        project.update(cx, |project, cx| {
+            project.set_fixed_diff_base(&buffer, old_text, cx)
+        });
+        anyhow::Ok(subscription)
+    })??;
+
+    diff_updated_rx.await?;
+    editor.update(cx, |editor, cx| {
+        editor.expand_all_hunk_diffs(&ExpandAllHunkDiffs, cx)
+    })?;
+    Ok(())
+}
@@ -36,6 +36,9 @@ actions!(
         ResetBufferFontSize,
         DecreaseUiFontSize,
         IncreaseUiFontSize,
-        ResetUiFontSize
+        ResetUiFontSize,
+        DecreaseUiScale,
+        IncreaseUiScale,
+        ResetUiScale
     ]
 );